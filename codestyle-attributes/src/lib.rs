@@ -0,0 +1,24 @@
+//! The `#[codestyle::skip]` attribute. It does nothing to the item it's put on; `codestyle` reads it from the source to
+//! leave that item alone. Unlike the `//#[codestyle::skip]` comment form, it survives rustfmt and shows up in rustdoc.
+//!
+//! Depend on this crate under the name `codestyle`, so the attribute's path matches what the linter looks for:
+//! ```toml
+//! [dependencies]
+//! codestyle = { package = "codestyle-attributes", version = "0.1" }
+//! ```
+//! ```ignore
+//! #[codestyle::skip]
+//! fn all_rules_skipped() {}
+//!
+//! #[codestyle::skip(pub-first)]
+//! struct OnlyPubFirstSkipped;
+//! ```
+
+use proc_macro::TokenStream;
+
+/// Mark an item for codestyle to skip, for every rule or, as `#[codestyle::skip(rule-name)]`, for one. Expands to the
+/// item unchanged.
+#[proc_macro_attribute]
+pub fn skip(_rule: TokenStream, item: TokenStream) -> TokenStream {
+	item
+}
@@ -0,0 +1,45 @@
+//! Lint to require a conventional-commit `type(scope): ` subject prefix.
+//!
+//! See <https://www.conventionalcommits.org>. Only the standard types are accepted.
+
+use std::path::Path;
+
+use crate::rust_checks::{Severity, Violation};
+
+const RULE: &str = "conventional-prefix";
+const TYPES: &[&str] = &["build", "chore", "ci", "docs", "feat", "fix", "perf", "refactor", "revert", "style", "test"];
+pub fn check(path: &Path, (line, subject): (usize, &str)) -> Vec<Violation> {
+	let message = match parse(subject) {
+		Some((ty, _)) if TYPES.contains(&ty) => return vec![],
+		Some((ty, _)) => format!("unknown commit type `{ty}`; expected one of: {}", TYPES.join(", ")),
+		None => format!("subject has no conventional-commit prefix\nHINT: start it with `<type>: ` or `<type>(<scope>): `, where type is one of: {}", TYPES.join(", ")),
+	};
+
+	vec![Violation {
+		rule: RULE,
+		file: path.display().to_string(),
+		line,
+		column: 0,
+		message,
+		severity: Severity::Error,
+		..Default::default()
+	}]
+}
+
+/// Split `type(scope)!: description` into `type` and `description`.
+pub fn parse(subject: &str) -> Option<(&str, &str)> {
+	let (prefix, description) = subject.split_once(": ")?;
+	let prefix = prefix.strip_suffix('!').unwrap_or(prefix);
+	let ty = match prefix.split_once('(') {
+		Some((ty, scope)) => {
+			let scope = scope.strip_suffix(')')?;
+			if scope.is_empty() || scope.contains(['(', ')', ' ']) {
+				return None;
+			}
+			ty
+		}
+		None => prefix,
+	};
+	let is_word = !ty.is_empty() && ty.chars().all(|c| c.is_ascii_alphabetic());
+	is_word.then_some((ty, description.trim_start()))
+}
@@ -0,0 +1,57 @@
+//! Lint to require the subject to start with an imperative verb: "Add x", not "Added x" or "Adds x".
+//!
+//! Only inflections of common commit verbs are flagged, so unusual first words never false-positive.
+
+use std::path::Path;
+
+use super::subject_description;
+use crate::rust_checks::{Severity, Violation};
+
+const RULE: &str = "imperative-mood";
+const VERBS: &[&str] = &[
+	"add", "adjust", "allow", "avoid", "bump", "change", "clean", "convert", "create", "delete", "deprecate", "disable", "document", "drop", "enable", "ensure", "extract", "fix", "handle",
+	"implement", "improve", "introduce", "make", "merge", "move", "optimize", "prevent", "refactor", "remove", "rename", "replace", "restore", "return", "revert", "rewrite", "simplify",
+	"skip", "split", "support", "switch", "update", "upgrade", "use",
+];
+const IRREGULAR: &[(&str, &str)] = &[("make", "made"), ("rewrite", "rewrote"), ("rewrite", "rewritten")];
+pub fn check(path: &Path, (line, subject): (usize, &str)) -> Vec<Violation> {
+	let description = subject_description(subject);
+	let Some(word) = description.split_whitespace().next() else {
+		return vec![];
+	};
+	let word = word.trim_end_matches(|c: char| !c.is_alphanumeric());
+	let lower = word.to_lowercase();
+	let Some(verb) = VERBS.iter().find(|v| is_inflection_of(&lower, v)) else {
+		return vec![];
+	};
+
+	let mut suggestion = verb.to_string();
+	if word.starts_with(char::is_uppercase) {
+		suggestion[..1].make_ascii_uppercase();
+	}
+	vec![Violation {
+		rule: RULE,
+		file: path.display().to_string(),
+		line,
+		column: subject.len() - description.len(),
+		message: format!("subject should use the imperative mood: `{suggestion}`, not `{word}`"),
+		severity: Severity::Error,
+		..Default::default()
+	}]
+}
+
+fn is_inflection_of(word: &str, verb: &str) -> bool {
+	let stem = verb.strip_suffix('e').unwrap_or(verb);
+	// `drop` -> `dropped`, `skip` -> `skipping`
+	let doubled = format!("{verb}{}", &verb[verb.len() - 1..]);
+	let forms = [
+		format!("{verb}s"),
+		format!("{verb}es"),
+		format!("{verb}d"),
+		format!("{verb}ed"),
+		format!("{stem}ing"),
+		format!("{doubled}ed"),
+		format!("{doubled}ing"),
+	];
+	forms.iter().any(|f| f == word) || IRREGULAR.contains(&(verb, word))
+}
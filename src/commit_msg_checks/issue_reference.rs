@@ -0,0 +1,42 @@
+//! Lint to require an issue reference at the end of the commit message.
+//!
+//! Accepts `#123`, `owner/repo#123` and tracker keys like `PROJ-123`, on the subject or the last line.
+
+use std::path::Path;
+
+use super::CommitMsg;
+use crate::rust_checks::{Severity, Violation};
+
+const RULE: &str = "issue-reference";
+pub fn check(path: &Path, msg: &CommitMsg) -> Vec<Violation> {
+	let (Some((_, subject)), Some((last_line, last))) = (msg.subject(), msg.last_line()) else {
+		return vec![];
+	};
+	if subject.split_whitespace().last().is_some_and(is_issue_ref) || last.split_whitespace().any(is_issue_ref) {
+		return vec![];
+	}
+
+	vec![Violation {
+		rule: RULE,
+		file: path.display().to_string(),
+		line: last_line,
+		column: 0,
+		message: "commit message doesn't reference an issue\nHINT: end the subject with `(#123)` or add a `Closes #123` line".to_string(),
+		severity: Severity::Error,
+		..Default::default()
+	}]
+}
+
+fn is_issue_ref(word: &str) -> bool {
+	let word = word.trim_end_matches(['.', ',', ')']).trim_start_matches('(');
+	if let Some((repo, number)) = word.split_once('#') {
+		let repo_ok = repo.is_empty() || repo.split_once('/').is_some_and(|(owner, name)| !owner.is_empty() && !name.is_empty());
+		return repo_ok && is_number(number);
+	}
+	// `PROJ-123`
+	word.split_once('-').is_some_and(|(key, number)| !key.is_empty() && key.chars().all(|c| c.is_ascii_uppercase()) && is_number(number))
+}
+
+fn is_number(s: &str) -> bool {
+	!s.is_empty() && s.chars().all(|c| c.is_ascii_digit())
+}
@@ -0,0 +1,122 @@
+//! Commit message checks, meant to run from the `commit-msg` git hook.
+//!
+//! Git's comment lines and everything below the `--verbose` scissors line are dropped before checking,
+//! and merge/fixup/revert commits generated by git itself are let through untouched.
+
+pub mod conventional_prefix;
+pub mod imperative_mood;
+pub mod issue_reference;
+pub mod subject_length;
+
+use std::{fs, path::Path};
+
+use smart_default::SmartDefault;
+
+use crate::rust_checks::{FailPolicy, OutputFormat, Violation, report_assert};
+
+const SCISSORS: &str = "# ------------------------ >8 ------------------------";
+const GENERATED_PREFIXES: &[&str] = &["Merge ", "fixup! ", "squash! ", "amend! ", "Revert \""];
+
+#[derive(Clone, SmartDefault)]
+pub struct CommitMsgOptions {
+	/// Maximum subject line length, in characters (default: 72)
+	#[default = 72]
+	pub max_subject_len: usize,
+	/// Require the subject to start with an imperative verb (default: true)
+	#[default = true]
+	pub imperative_mood: bool,
+	/// Require a conventional-commit `type(scope): ` prefix (default: true)
+	#[default = true]
+	pub conventional_prefix: bool,
+	/// Require an issue reference (`#123`, `owner/repo#123`, `PROJ-123`) at the end of the message (default: false)
+	#[default = false]
+	pub issue_reference: bool,
+}
+impl CommitMsgOptions {
+	/// Every check enabled, for the `strict` profile.
+	pub fn strict() -> Self {
+		Self {
+			issue_reference: true,
+			..Self::default()
+		}
+	}
+}
+
+/// A commit message with git's comments stripped.
+pub struct CommitMsg<'a> {
+	/// `(1-indexed line, text)` of every remaining line
+	pub lines: Vec<(usize, &'a str)>,
+}
+
+impl<'a> CommitMsg<'a> {
+	pub fn parse(content: &'a str) -> Self {
+		let lines = content
+			.lines()
+			.take_while(|l| *l != SCISSORS)
+			.enumerate()
+			.filter(|(_, l)| !l.starts_with('#'))
+			.map(|(i, l)| (i + 1, l.trim_end()))
+			.collect();
+		Self { lines }
+	}
+
+	/// First non-empty line.
+	pub fn subject(&self) -> Option<(usize, &'a str)> {
+		self.lines.iter().copied().find(|(_, l)| !l.is_empty())
+	}
+
+	/// Last non-empty line.
+	pub fn last_line(&self) -> Option<(usize, &'a str)> {
+		self.lines.iter().copied().rfind(|(_, l)| !l.is_empty())
+	}
+
+	/// Messages git writes itself (merges, `--fixup`, reverts) aren't held to the convention.
+	pub fn is_generated(&self) -> bool {
+		self.subject().is_some_and(|(_, s)| GENERATED_PREFIXES.iter().any(|p| s.starts_with(p)))
+	}
+}
+
+pub fn run_assert(file: &Path, opts: &CommitMsgOptions) -> i32 {
+	let content = match fs::read_to_string(file) {
+		Ok(content) => content,
+		Err(e) => {
+			eprintln!("Failed to read commit message {file:?}: {e}");
+			return 1;
+		}
+	};
+
+	report_assert(&check_message(file, &content, opts), OutputFormat::Text, FailPolicy::default())
+}
+
+/// Run all enabled checks on a commit message.
+pub fn check_message(path: &Path, content: &str, opts: &CommitMsgOptions) -> Vec<Violation> {
+	let msg = CommitMsg::parse(content);
+	if msg.is_generated() {
+		return vec![];
+	}
+	let Some(subject) = msg.subject() else {
+		// Empty messages abort the commit anyway
+		return vec![];
+	};
+
+	let mut violations = Vec::new();
+	violations.extend(subject_length::check(path, subject, opts.max_subject_len));
+	if opts.conventional_prefix {
+		violations.extend(conventional_prefix::check(path, subject));
+	}
+	if opts.imperative_mood {
+		violations.extend(imperative_mood::check(path, subject));
+	}
+	if opts.issue_reference {
+		violations.extend(issue_reference::check(path, &msg));
+	}
+	violations
+}
+
+/// Subject with any conventional-commit `type(scope)!: ` prefix removed.
+pub fn subject_description(subject: &str) -> &str {
+	match conventional_prefix::parse(subject) {
+		Some((_, description)) => description,
+		None => subject,
+	}
+}
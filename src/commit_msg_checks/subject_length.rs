@@ -0,0 +1,25 @@
+//! Lint to cap the commit subject length.
+//!
+//! `git log --oneline`, GitHub and most UIs truncate long subjects.
+
+use std::path::Path;
+
+use crate::rust_checks::{Severity, Violation};
+
+const RULE: &str = "subject-length";
+pub fn check(path: &Path, (line, subject): (usize, &str), max_len: usize) -> Vec<Violation> {
+	let len = subject.chars().count();
+	if len <= max_len {
+		return vec![];
+	}
+
+	vec![Violation {
+		rule: RULE,
+		file: path.display().to_string(),
+		line,
+		column: max_len,
+		message: format!("subject is {len} characters long, limit is {max_len}\nHINT: move the details into the body, after a blank line"),
+		severity: Severity::Error,
+		..Default::default()
+	}]
+}
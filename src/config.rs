@@ -0,0 +1,337 @@
+//! Layered configuration for all subcommands.
+//!
+//! Each option resolves from, in increasing precedence:
+//! 1. the profile's defaults (`profile = "strict"` turns on every check)
+//! 2. `codestyle.toml` in the target directory (or the file given with `--config`), one `[section]` per subcommand,
+//!    then any `codestyle.toml` below it on the way to a workspace member, each overriding the ones above key by key
+//! 3. `CODESTYLE_<SECTION>_<OPTION>` environment variables, e.g. `CODESTYLE_RUST_NO_CHRONO=false`
+//! 4. CLI flags
+//!
+//! ```toml
+//! profile = "strict"
+//!
+//! [rust]
+//! no_chrono = false
+//! ```
+
+use std::{
+	collections::BTreeMap,
+	fs,
+	path::{Path, PathBuf},
+	str::FromStr,
+};
+
+use serde::de::DeserializeOwned;
+
+pub const CONFIG_FILE_NAME: &str = "codestyle.toml";
+const ENV_PREFIX: &str = "CODESTYLE_";
+/// Sections that aren't looked up in the environment, like `[override."<glob>"]`, have none
+static NO_ENV: BTreeMap<String, String> = BTreeMap::new();
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Profile {
+	/// Each option's own default
+	#[default]
+	Default,
+	/// Every check enabled
+	Strict,
+}
+impl FromStr for Profile {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"default" => Ok(Self::Default),
+			"strict" => Ok(Self::Strict),
+			_ => Err(format!("unknown profile `{s}`, expected `default` or `strict`")),
+		}
+	}
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Config {
+	pub profile: Profile,
+	/// Directory the top-level config file was looked up in
+	root: Option<PathBuf>,
+	file: toml::Table,
+	/// `CODESTYLE_*` variables, captured once at load
+	env: BTreeMap<String, String>,
+}
+impl Config {
+	/// Load `codestyle.toml` from `dir` (if present) and the process's `CODESTYLE_*` environment.
+	pub fn load(dir: &Path) -> Result<Self, String> {
+		Self::load_from(dir, &dir.join(CONFIG_FILE_NAME), false)
+	}
+
+	/// Like [`Config::load`], but with an explicit config file in place of `dir/codestyle.toml`, which must exist.
+	pub fn load_file(dir: &Path, path: &Path) -> Result<Self, String> {
+		Self::load_from(dir, path, true)
+	}
+
+	fn load_from(dir: &Path, path: &Path, required: bool) -> Result<Self, String> {
+		let file_content = read_config_file(path)?;
+		if required && file_content.is_none() {
+			return Err(format!("config file {path:?} does not exist"));
+		}
+		let env = std::env::vars().filter(|(k, _)| k.starts_with(ENV_PREFIX)).collect();
+		let config = Self::from_parts(file_content.as_deref(), env).map_err(|e| format!("{path:?}: {e}"))?;
+		Ok(Self {
+			root: Some(dir.to_path_buf()),
+			..config
+		})
+	}
+
+	pub fn from_parts(file_content: Option<&str>, env: BTreeMap<String, String>) -> Result<Self, String> {
+		let file: toml::Table = match file_content {
+			Some(content) => toml::from_str(content).map_err(|e| e.to_string())?,
+			None => toml::Table::new(),
+		};
+
+		Ok(Self {
+			profile: resolve_profile(&file, &env)?,
+			root: None,
+			file,
+			env,
+		})
+	}
+
+	/// Config for `dir`, with every `codestyle.toml` between the root and `dir` merged over this one.
+	/// Invalid nested files are reported and skipped, so one member crate can't break the whole run.
+	pub fn for_dir(&self, dir: &Path) -> Self {
+		let mut config = self.clone();
+		let Some(relative) = self.root.as_deref().and_then(|root| dir.strip_prefix(root).ok()) else {
+			return config;
+		};
+
+		let mut current = self.root.clone().expect("checked by strip_prefix above");
+		for component in relative.components() {
+			current.push(component);
+			let path = current.join(CONFIG_FILE_NAME);
+			let merged = read_config_file(&path).and_then(|content| match content {
+				Some(content) => config.merged_with(&content),
+				None => Ok(config.clone()),
+			});
+			match merged {
+				Ok(merged) => config = merged,
+				Err(e) => eprintln!("codestyle: ignoring {path:?}: {e}"),
+			}
+		}
+		config
+	}
+
+	fn merged_with(&self, content: &str) -> Result<Self, String> {
+		let overrides: toml::Table = toml::from_str(content).map_err(|e| e.to_string())?;
+		let mut file = self.file.clone();
+		for (key, value) in overrides {
+			match (file.get_mut(&key), value) {
+				(Some(toml::Value::Table(section)), toml::Value::Table(overrides)) => section.extend(overrides),
+				(_, value) => {
+					file.insert(key, value);
+				}
+			}
+		}
+		Ok(Self {
+			profile: resolve_profile(&file, &self.env)?,
+			root: self.root.clone(),
+			file,
+			env: self.env.clone(),
+		})
+	}
+
+	/// Directory the top-level config file was looked up in, which `[override."<glob>"]` globs are relative to.
+	pub fn root(&self) -> Option<&Path> {
+		self.root.as_deref()
+	}
+
+	/// The `[override."<glob>"]` sections, by glob. Their options come from the file only.
+	pub fn overrides(&self) -> Vec<(&str, Section<'_>)> {
+		let Some(overrides) = self.file.get("override").and_then(toml::Value::as_table) else {
+			return Vec::new();
+		};
+		overrides
+			.iter()
+			.filter_map(|(glob, table)| match table.as_table() {
+				Some(table) => Some((glob.as_str(), Section {
+					name: "override",
+					table: Some(table),
+					env: &NO_ENV,
+				})),
+				None => {
+					eprintln!("codestyle: ignoring `{glob}` in [override] of {CONFIG_FILE_NAME}: expected a table of options");
+					None
+				}
+			})
+			.collect()
+	}
+
+	/// Options for one subcommand, e.g. `"rust"` or `"commit_msg"`.
+	pub fn section(&self, name: &'static str) -> Section<'_> {
+		Section {
+			name,
+			table: self.file.get(name).and_then(toml::Value::as_table),
+			env: &self.env,
+		}
+	}
+}
+
+fn read_config_file(path: &Path) -> Result<Option<String>, String> {
+	match fs::read_to_string(path) {
+		Ok(content) => Ok(Some(content)),
+		Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+		Err(e) => Err(format!("failed to read {path:?}: {e}")),
+	}
+}
+
+/// `CODESTYLE_PROFILE` beats the file's `profile`.
+fn resolve_profile(file: &toml::Table, env: &BTreeMap<String, String>) -> Result<Profile, String> {
+	let env_profile = env.get(&format!("{ENV_PREFIX}PROFILE")).map(String::as_str);
+	match env_profile.or_else(|| file.get("profile").and_then(toml::Value::as_str)) {
+		Some(name) => name.parse(),
+		None => Ok(Profile::Default),
+	}
+}
+
+pub struct Section<'a> {
+	name: &'static str,
+	table: Option<&'a toml::Table>,
+	env: &'a BTreeMap<String, String>,
+}
+impl Section<'_> {
+	/// Look `key` up in the environment, then in the config file. Invalid values are reported and ignored.
+	pub fn get<T: FromStr>(&self, key: &str) -> Option<T> {
+		let env_key = format!("{ENV_PREFIX}{}_{}", self.name, key).to_uppercase();
+		if let Some(raw) = self.env.get(&env_key) {
+			match raw.parse() {
+				Ok(value) => return Some(value),
+				Err(_) => eprintln!("codestyle: ignoring invalid value `{raw}` for {env_key}"),
+			}
+		}
+
+		let value = self.table?.get(key)?;
+		let raw = match value {
+			toml::Value::String(s) => s.clone(),
+			other => other.to_string(),
+		};
+		raw.parse()
+			.inspect_err(|_| eprintln!("codestyle: ignoring invalid value `{raw}` for `{key}` in [{}] of {CONFIG_FILE_NAME}", self.name))
+			.ok()
+	}
+
+	/// Like [`Section::get`], for list options: a comma-separated environment variable, or an array (or single string) in the file.
+	pub fn get_list(&self, key: &str) -> Option<Vec<String>> {
+		let env_key = format!("{ENV_PREFIX}{}_{}", self.name, key).to_uppercase();
+		if let Some(raw) = self.env.get(&env_key) {
+			return Some(raw.split(',').map(str::trim).filter(|s| !s.is_empty()).map(String::from).collect());
+		}
+
+		match self.table?.get(key)? {
+			toml::Value::String(s) => Some(vec![s.clone()]),
+			toml::Value::Array(values) => {
+				let strings: Option<Vec<String>> = values.iter().map(|v| v.as_str().map(String::from)).collect();
+				strings.or_else(|| {
+					eprintln!("codestyle: ignoring `{key}` in [{}] of {CONFIG_FILE_NAME}: expected an array of strings", self.name);
+					None
+				})
+			}
+			_ => {
+				eprintln!("codestyle: ignoring `{key}` in [{}] of {CONFIG_FILE_NAME}: expected an array of strings", self.name);
+				None
+			}
+		}
+	}
+
+	/// A structured option, like an array of tables, deserialized from the config file only: these have no environment form.
+	pub fn get_deserialized<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+		let value = self.table?.get(key)?;
+		value
+			.clone()
+			.try_into()
+			.inspect_err(|e| eprintln!("codestyle: ignoring invalid `{key}` in [{}] of {CONFIG_FILE_NAME}: {e}", self.name))
+			.ok()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn env(vars: &[(&str, &str)]) -> BTreeMap<String, String> {
+		vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+	}
+
+	#[test]
+	fn env_overrides_file() {
+		let config = Config::from_parts(Some("[rust]\nno_chrono = true\nloops = false\n"), env(&[("CODESTYLE_RUST_NO_CHRONO", "false")])).unwrap();
+		let rust = config.section("rust");
+		assert_eq!(rust.get::<bool>("no_chrono"), Some(false));
+		assert_eq!(rust.get::<bool>("loops"), Some(false));
+		assert_eq!(rust.get::<bool>("pub_first"), None);
+	}
+
+	#[test]
+	fn override_sections_come_from_the_file_only() {
+		let config = Config::from_parts(Some("[override.\"tests/**\"]\nloops = \"allow\"\n"), env(&[("CODESTYLE_OVERRIDE_LOOPS", "error")])).unwrap();
+		let overrides = config.overrides();
+		assert_eq!(overrides.len(), 1);
+		assert_eq!(overrides[0].0, "tests/**");
+		assert_eq!(overrides[0].1.get::<String>("loops").as_deref(), Some("allow"));
+	}
+
+	#[test]
+	fn multi_word_section() {
+		let config = Config::from_parts(None, env(&[("CODESTYLE_COMMIT_MSG_MAX_SUBJECT_LEN", "50")])).unwrap();
+		assert_eq!(config.section("commit_msg").get::<usize>("max_subject_len"), Some(50));
+	}
+
+	#[test]
+	fn env_profile_overrides_file() {
+		let config = Config::from_parts(Some("profile = \"strict\"\n"), env(&[("CODESTYLE_PROFILE", "default")])).unwrap();
+		assert_eq!(config.profile, Profile::Default);
+	}
+
+	#[test]
+	fn member_config_merges_over_workspace() {
+		let root = tempfile::tempdir().unwrap();
+		fs::write(root.path().join(CONFIG_FILE_NAME), "[rust]\npub_first = true\nloops = false\n").unwrap();
+		fs::create_dir_all(root.path().join("xtask/src")).unwrap();
+		fs::write(root.path().join("xtask").join(CONFIG_FILE_NAME), "profile = \"strict\"\n\n[rust]\npub_first = false\n").unwrap();
+
+		let config = Config::from_parts(Some(&fs::read_to_string(root.path().join(CONFIG_FILE_NAME)).unwrap()), BTreeMap::new()).unwrap();
+		let config = Config {
+			root: Some(root.path().to_path_buf()),
+			..config
+		};
+		let member = config.for_dir(&root.path().join("xtask/src"));
+		assert_eq!(member.profile, Profile::Strict);
+		assert_eq!(member.section("rust").get::<bool>("pub_first"), Some(false));
+		assert_eq!(member.section("rust").get::<bool>("loops"), Some(false));
+		assert_eq!(config.section("rust").get::<bool>("pub_first"), Some(true));
+	}
+
+	#[test]
+	fn explicit_config_file_must_exist() {
+		let dir = tempfile::tempdir().unwrap();
+		assert!(Config::load_file(dir.path(), &dir.path().join("ci.toml")).is_err());
+
+		fs::write(dir.path().join("ci.toml"), "[rust]\nloops = false\n").unwrap();
+		let config = Config::load_file(dir.path(), &dir.path().join("ci.toml")).unwrap();
+		assert_eq!(config.section("rust").get::<bool>("loops"), Some(false));
+	}
+
+	#[test]
+	fn list_from_file_or_env() {
+		let config = Config::from_parts(Some("[rust]\nexclude = [\"**/generated/**\", \"build.rs\"]\n"), BTreeMap::new()).unwrap();
+		assert_eq!(config.section("rust").get_list("exclude"), Some(vec!["**/generated/**".to_string(), "build.rs".to_string()]));
+
+		let config = Config::from_parts(Some("[rust]\nexclude = [\"a\"]\n"), env(&[("CODESTYLE_RUST_EXCLUDE", "b, c")])).unwrap();
+		assert_eq!(config.section("rust").get_list("exclude"), Some(vec!["b".to_string(), "c".to_string()]));
+	}
+
+	#[test]
+	fn invalid_values_ignored() {
+		let config = Config::from_parts(Some("[rust]\nloops = \"sometimes\"\n"), env(&[("CODESTYLE_RUST_NO_CHRONO", "nope")])).unwrap();
+		let rust = config.section("rust");
+		assert_eq!(rust.get::<bool>("loops"), None);
+		assert_eq!(rust.get::<bool>("no_chrono"), None);
+	}
+}
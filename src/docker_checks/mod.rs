@@ -0,0 +1,205 @@
+//! Dockerfile checks.
+//!
+//! Dockerfiles are split into instructions (joining `\` continuations, skipping comments and heredoc bodies);
+//! none of the checks here need anything more than the keyword and its raw arguments.
+
+pub mod non_root_user;
+pub mod pinned_base_image;
+pub mod prefer_copy;
+
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+
+use smart_default::SmartDefault;
+use walkdir::WalkDir;
+
+use crate::rust_checks::{FailPolicy, OutputFormat, Violation, report_assert, report_format};
+
+#[derive(Clone, SmartDefault)]
+pub struct DockerCheckOptions {
+	/// Require `FROM` images to be pinned to a tag or digest (default: true)
+	#[default = true]
+	pub pinned_base_image: bool,
+	/// Require the final stage to switch to a non-root `USER` (default: true)
+	#[default = true]
+	pub non_root_user: bool,
+	/// Replace `ADD` with `COPY` when no URL or archive is involved (default: true)
+	#[default = true]
+	pub prefer_copy: bool,
+}
+
+/// A single Dockerfile instruction, with continuation lines joined.
+#[derive(Clone, Debug)]
+pub struct Instruction {
+	/// 1-indexed line of the keyword
+	pub line: usize,
+	/// Uppercased keyword, e.g. `FROM`
+	pub keyword: String,
+	/// Byte offset of the keyword in the document
+	pub keyword_start: usize,
+	/// Arguments with continuations joined by a space
+	pub args: String,
+}
+
+pub fn run_assert(target_dir: &Path, opts: &DockerCheckOptions, format: OutputFormat, fail: FailPolicy) -> i32 {
+	if !target_dir.exists() {
+		eprintln!("Target directory does not exist: {target_dir:?}");
+		return 1;
+	}
+
+	let mut all_violations = Vec::new();
+	for path in collect_dockerfiles(target_dir) {
+		if let Ok(content) = fs::read_to_string(&path) {
+			all_violations.extend(check_file(&path, &content, opts));
+		}
+	}
+
+	report_assert(&all_violations, format, fail)
+}
+
+pub fn run_format(target_dir: &Path, opts: &DockerCheckOptions) -> i32 {
+	if !target_dir.exists() {
+		eprintln!("Target directory does not exist: {target_dir:?}");
+		return 1;
+	}
+
+	let mut fixed_count = 0;
+	let mut unfixable_violations = Vec::new();
+
+	for path in collect_dockerfiles(target_dir) {
+		let Ok(content) = fs::read_to_string(&path) else {
+			continue;
+		};
+
+		// Every fix here rewrites a single instruction keyword, so they never overlap
+		let (mut fixes, unfixable): (Vec<_>, Vec<_>) = check_file(&path, &content, opts).into_iter().partition(|v| v.fix.is_some());
+		unfixable_violations.extend(unfixable);
+		if fixes.is_empty() {
+			continue;
+		}
+
+		fixes.sort_by_key(|v| std::cmp::Reverse(v.fix.as_ref().map(|f| f.start_byte)));
+		let mut new_content = content.clone();
+		for fix in fixes.iter().filter_map(|v| v.fix.as_ref()) {
+			new_content.replace_range(fix.start_byte..fix.end_byte, &fix.replacement);
+		}
+		if fs::write(&path, new_content).is_ok() {
+			fixed_count += fixes.len();
+		}
+	}
+
+	report_format(fixed_count, &unfixable_violations)
+}
+
+/// Run all enabled checks on a single Dockerfile.
+pub fn check_file(path: &Path, content: &str, opts: &DockerCheckOptions) -> Vec<Violation> {
+	let instructions = instructions(content);
+	let mut violations = Vec::new();
+	if opts.pinned_base_image {
+		violations.extend(pinned_base_image::check(path, &instructions));
+	}
+	if opts.non_root_user {
+		violations.extend(non_root_user::check(path, &instructions));
+	}
+	if opts.prefer_copy {
+		violations.extend(prefer_copy::check(path, &instructions));
+	}
+	violations
+}
+
+/// Collect `Dockerfile`, `Dockerfile.*` and `*.Dockerfile` files.
+pub fn collect_dockerfiles(target_dir: &Path) -> Vec<PathBuf> {
+	let walker = WalkDir::new(target_dir).into_iter().filter_entry(|e| {
+		let name = e.file_name().to_string_lossy();
+		!name.starts_with('.') && !matches!(name.as_ref(), "node_modules" | "target")
+	});
+
+	walker
+		.filter_map(Result::ok)
+		.filter(|e| e.file_type().is_file())
+		.filter(|e| {
+			let name = e.file_name().to_string_lossy();
+			name.starts_with("Dockerfile") || name.ends_with(".Dockerfile") || name.ends_with(".dockerfile")
+		})
+		.map(|e| e.path().to_path_buf())
+		.collect()
+}
+
+pub fn instructions(content: &str) -> Vec<Instruction> {
+	let mut instructions = Vec::new();
+	let mut current: Option<Instruction> = None;
+	let mut heredoc_terminators: Vec<String> = Vec::new();
+	let mut offset = 0;
+
+	for (i, raw_line) in content.split_inclusive('\n').enumerate() {
+		let line_offset = offset;
+		offset += raw_line.len();
+		let line = raw_line.trim_end_matches(['\n', '\r']);
+		let trimmed = line.trim();
+
+		if let Some(terminator) = heredoc_terminators.first() {
+			if trimmed == terminator {
+				heredoc_terminators.remove(0);
+			}
+			continue;
+		}
+		// Comment and empty lines are dropped even in the middle of a continuation
+		if trimmed.is_empty() || trimmed.starts_with('#') {
+			continue;
+		}
+
+		let (text, continues) = match trimmed.strip_suffix('\\') {
+			Some(rest) => (rest.trim_end(), true),
+			None => (trimmed, false),
+		};
+
+		match current.as_mut() {
+			Some(instruction) => {
+				if !text.is_empty() {
+					instruction.args.push(' ');
+					instruction.args.push_str(text);
+				}
+			}
+			None => {
+				let (keyword, args) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+				current = Some(Instruction {
+					line: i + 1,
+					keyword: keyword.to_ascii_uppercase(),
+					keyword_start: line_offset + (line.len() - line.trim_start().len()),
+					args: args.trim().to_string(),
+				});
+			}
+		}
+
+		if !continues && let Some(instruction) = current.take() {
+			heredoc_terminators = heredoc_terminators_of(&instruction.args);
+			instructions.push(instruction);
+		}
+	}
+	instructions.extend(current);
+
+	instructions
+}
+
+/// Split shell-form or JSON-form (`["a", "b"]`) arguments, dropping leading `--flag`s.
+pub fn positional_args(args: &str) -> Vec<String> {
+	if let Some(json) = args.strip_prefix('[').and_then(|a| a.strip_suffix(']')) {
+		return json.split(',').map(|a| a.trim().trim_matches('"').to_string()).filter(|a| !a.is_empty()).collect();
+	}
+	args.split_whitespace().skip_while(|a| a.starts_with("--")).map(str::to_string).collect()
+}
+
+/// Terminator words of `<<EOF` / `<<-"EOF"` heredocs opened by an instruction.
+fn heredoc_terminators_of(args: &str) -> Vec<String> {
+	args.match_indices("<<")
+		.filter_map(|(i, _)| {
+			let word = args[i + 2..].trim_start_matches('-');
+			let word = word.split(|c: char| c.is_whitespace() || c == ';' || c == '&' || c == '|' || c == '>').next()?;
+			let word = word.trim_matches(['"', '\'']);
+			let is_word = word.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') && word.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+			is_word.then(|| word.to_string())
+		})
+		.collect()
+}
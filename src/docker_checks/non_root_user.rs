@@ -0,0 +1,38 @@
+//! Lint to require the final build stage to run as a non-root user.
+//!
+//! Only the last stage ends up in the image, so earlier stages are free to build as root.
+
+use std::path::Path;
+
+use super::Instruction;
+use crate::rust_checks::{Severity, Violation};
+
+const RULE: &str = "non-root-user";
+const ROOT_USERS: &[&str] = &["root", "0"];
+pub fn check(path: &Path, instructions: &[Instruction]) -> Vec<Violation> {
+	let Some(final_from) = instructions.iter().rposition(|i| i.keyword == "FROM") else {
+		return vec![];
+	};
+	let final_stage = &instructions[final_from..];
+
+	let (line, message) = match final_stage.iter().rfind(|i| i.keyword == "USER") {
+		None => (final_stage[0].line, "final stage runs as root\nHINT: add a non-root `USER` before `CMD`/`ENTRYPOINT`"),
+		Some(user) => {
+			let name = user.args.split(':').next().unwrap_or_default().trim();
+			if !ROOT_USERS.contains(&name) {
+				return vec![];
+			}
+			(user.line, "final stage switches back to root as its last `USER`")
+		}
+	};
+
+	vec![Violation {
+		rule: RULE,
+		file: path.display().to_string(),
+		line,
+		column: 0,
+		message: message.to_string(),
+		severity: Severity::Error,
+		..Default::default()
+	}]
+}
@@ -0,0 +1,51 @@
+//! Lint to require `FROM` images to be pinned to a tag or digest.
+//!
+//! An untagged image means `latest`, which changes under you between builds.
+//! `scratch`, references to earlier build stages, and `$ARG`-templated images are exempt.
+
+use std::path::Path;
+
+use super::{Instruction, positional_args};
+use crate::rust_checks::{Severity, Violation};
+
+const RULE: &str = "pinned-base-image";
+pub fn check(path: &Path, instructions: &[Instruction]) -> Vec<Violation> {
+	let path_str = path.display().to_string();
+	let mut stages: Vec<String> = Vec::new();
+	let mut violations = Vec::new();
+
+	for instruction in instructions.iter().filter(|i| i.keyword == "FROM") {
+		let args = positional_args(&instruction.args);
+		let Some(image) = args.first() else {
+			continue;
+		};
+		if let [_, as_kw, name, ..] = args.as_slice()
+			&& as_kw.eq_ignore_ascii_case("as")
+		{
+			stages.push(name.to_ascii_lowercase());
+		}
+
+		if image == "scratch" || image.contains('$') || stages.contains(&image.to_ascii_lowercase()) || image.contains('@') {
+			continue;
+		}
+		// `registry:5000/image` has a port, not a tag
+		let name_part = image.rsplit('/').next().unwrap_or(image);
+		let message = match name_part.split_once(':') {
+			Some((_, "latest")) => format!("base image `{image}` uses the mutable `latest` tag; pin it to a version tag or digest"),
+			Some(_) => continue,
+			None => format!("base image `{image}` is not pinned; pin it to a version tag or digest"),
+		};
+
+		violations.push(Violation {
+			rule: RULE,
+			file: path_str.clone(),
+			line: instruction.line,
+			column: 0,
+			message,
+			severity: Severity::Error,
+			..Default::default()
+		});
+	}
+
+	violations
+}
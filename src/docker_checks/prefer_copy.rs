@@ -0,0 +1,47 @@
+//! Lint to replace `ADD` with `COPY` when none of `ADD`'s extra behavior is used.
+//!
+//! `ADD` also fetches URLs and unpacks tar archives; using it for plain files hides which one was meant.
+
+use std::path::Path;
+
+use super::{Instruction, positional_args};
+use crate::rust_checks::{Fix, Severity, Violation};
+
+const RULE: &str = "prefer-copy";
+const ARCHIVE_SUFFIXES: &[&str] = &[".tar", ".tar.gz", ".tgz", ".tar.bz2", ".tbz", ".tbz2", ".tar.xz", ".txz"];
+/// Flags only `ADD` accepts.
+const ADD_ONLY_FLAGS: &[&str] = &["--checksum", "--keep-git-dir", "--unpack"];
+pub fn check(path: &Path, instructions: &[Instruction]) -> Vec<Violation> {
+	let path_str = path.display().to_string();
+
+	instructions
+		.iter()
+		.filter(|i| i.keyword == "ADD")
+		.filter(|i| !i.args.split_whitespace().any(|a| ADD_ONLY_FLAGS.iter().any(|f| a.starts_with(f))))
+		.filter(|i| {
+			let args = positional_args(&i.args);
+			let sources = &args[..args.len().saturating_sub(1)];
+			!sources.is_empty() && sources.iter().all(|s| is_plain_source(s))
+		})
+		.map(|i| Violation {
+			rule: RULE,
+			file: path_str.clone(),
+			line: i.line,
+			column: 0,
+			message: "`ADD` used for plain files; use `COPY`".to_string(),
+			severity: Severity::Error,
+			fix: Some(Fix {
+				start_byte: i.keyword_start,
+				end_byte: i.keyword_start + "ADD".len(),
+				replacement: "COPY".to_string(),
+			}),
+			..Default::default()
+		})
+		.collect()
+}
+
+fn is_plain_source(source: &str) -> bool {
+	let lower = source.to_ascii_lowercase();
+	// `$VAR` sources could expand to anything
+	!source.contains('$') && !source.contains("://") && !source.starts_with("git@") && !ARCHIVE_SUFFIXES.iter().any(|s| lower.ends_with(s))
+}
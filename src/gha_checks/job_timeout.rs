@@ -0,0 +1,29 @@
+//! Lint to require `timeout-minutes` on every job.
+//!
+//! The default is 6 hours, so a single hung step silently burns through CI minutes.
+//! Jobs calling a reusable workflow (`uses:`) are exempt, since they can't set a timeout.
+
+use std::path::Path;
+
+use super::{jobs, yaml_entries};
+use crate::rust_checks::{Severity, Violation};
+
+const RULE: &str = "job-timeout";
+pub fn check(path: &Path, content: &str) -> Vec<Violation> {
+	let path_str = path.display().to_string();
+	let entries = yaml_entries(content);
+
+	jobs(&entries)
+		.into_iter()
+		.filter(|job| !job.props.iter().any(|p| p.key == "timeout-minutes" || p.key == "uses"))
+		.map(|job| Violation {
+			rule: RULE,
+			file: path_str.clone(),
+			line: job.entry.line,
+			column: job.entry.indent,
+			message: format!("job `{}` has no `timeout-minutes`", job.entry.key),
+			severity: Severity::Error,
+			..Default::default()
+		})
+		.collect()
+}
@@ -0,0 +1,262 @@
+//! GitHub Actions workflow checks.
+//!
+//! Workflows are scanned line by line rather than fully parsed: every check here only needs
+//! to know which `key: value` pairs exist at which nesting level, and exact line numbers.
+
+pub mod job_timeout;
+pub mod pinned_actions;
+pub mod workflow_permissions;
+
+use std::{
+	fs,
+	path::{Path, PathBuf},
+	process::Command,
+};
+
+use smart_default::SmartDefault;
+
+use crate::rust_checks::{FailPolicy, OutputFormat, Violation, report_assert, report_format};
+
+#[derive(Clone, SmartDefault)]
+pub struct GhaCheckOptions {
+	/// Require third-party actions to be pinned to a commit SHA (default: true)
+	#[default = true]
+	pub pinned_actions: bool,
+	/// Require a top-level `permissions:` block (default: true)
+	#[default = true]
+	pub workflow_permissions: bool,
+	/// Require `timeout-minutes` on every job (default: true)
+	#[default = true]
+	pub job_timeout: bool,
+	/// Resolve action refs to commit SHAs over the network when fixing (default: false)
+	#[default = false]
+	pub allow_network: bool,
+}
+
+/// A `key: value` line of a YAML document. Comments and block scalar contents are never yielded.
+#[derive(Clone, Debug)]
+pub struct YamlEntry<'a> {
+	/// 1-indexed
+	pub line: usize,
+	/// Column of the key (after any `- ` list marker)
+	pub indent: usize,
+	pub key: &'a str,
+	/// Raw value with the trailing comment stripped
+	pub value: &'a str,
+	/// Byte offset of `value` in the document
+	pub value_start: usize,
+	pub has_comment: bool,
+}
+
+/// A job under the top-level `jobs:` key, with its direct properties.
+pub struct Job<'a> {
+	pub entry: YamlEntry<'a>,
+	pub props: Vec<YamlEntry<'a>>,
+}
+
+pub fn run_assert(target_dir: &Path, opts: &GhaCheckOptions, format: OutputFormat, fail: FailPolicy) -> i32 {
+	if !target_dir.exists() {
+		eprintln!("Target directory does not exist: {target_dir:?}");
+		return 1;
+	}
+
+	let mut all_violations = Vec::new();
+	for path in collect_workflow_files(target_dir) {
+		if let Ok(content) = fs::read_to_string(&path) {
+			all_violations.extend(check_file(&path, &content, opts, false));
+		}
+	}
+
+	report_assert(&all_violations, format, fail)
+}
+
+pub fn run_format(target_dir: &Path, opts: &GhaCheckOptions) -> i32 {
+	if !target_dir.exists() {
+		eprintln!("Target directory does not exist: {target_dir:?}");
+		return 1;
+	}
+
+	let mut fixed_count = 0;
+	let mut unfixable_violations = Vec::new();
+
+	for path in collect_workflow_files(target_dir) {
+		let Ok(content) = fs::read_to_string(&path) else {
+			continue;
+		};
+
+		// Every fix here rewrites a single `uses:` value, so they never overlap
+		let (mut fixes, unfixable): (Vec<_>, Vec<_>) = check_file(&path, &content, opts, true).into_iter().partition(|v| v.fix.is_some());
+		unfixable_violations.extend(unfixable);
+		if fixes.is_empty() {
+			continue;
+		}
+
+		fixes.sort_by_key(|v| std::cmp::Reverse(v.fix.as_ref().map(|f| f.start_byte)));
+		let mut new_content = content.clone();
+		for fix in fixes.iter().filter_map(|v| v.fix.as_ref()) {
+			new_content.replace_range(fix.start_byte..fix.end_byte, &fix.replacement);
+		}
+		if fs::write(&path, new_content).is_ok() {
+			fixed_count += fixes.len();
+		}
+	}
+
+	report_format(fixed_count, &unfixable_violations)
+}
+
+/// Run all enabled checks on a single workflow file.
+/// Fixes are only produced when `is_format_mode` is set and network access is allowed.
+pub fn check_file(path: &Path, content: &str, opts: &GhaCheckOptions, is_format_mode: bool) -> Vec<Violation> {
+	let mut violations = Vec::new();
+	if opts.pinned_actions {
+		let resolver: Option<pinned_actions::ResolveSha> = if is_format_mode && opts.allow_network { Some(&resolve_ref) } else { None };
+		violations.extend(pinned_actions::check(path, content, resolver));
+	}
+	if opts.workflow_permissions {
+		violations.extend(workflow_permissions::check(path, content));
+	}
+	if opts.job_timeout {
+		violations.extend(job_timeout::check(path, content));
+	}
+	violations
+}
+
+/// Collect `.github/workflows/*.yml` and `*.yaml` files.
+pub fn collect_workflow_files(target_dir: &Path) -> Vec<PathBuf> {
+	let Ok(entries) = fs::read_dir(target_dir.join(".github").join("workflows")) else {
+		return vec![];
+	};
+	let mut files: Vec<PathBuf> = entries
+		.filter_map(Result::ok)
+		.map(|e| e.path())
+		.filter(|p| p.extension().is_some_and(|ext| ext == "yml" || ext == "yaml"))
+		.collect();
+	files.sort();
+	files
+}
+
+pub fn yaml_entries(content: &str) -> Vec<YamlEntry<'_>> {
+	let mut entries = Vec::new();
+	let mut block_scalar_indent: Option<usize> = None;
+	let mut offset = 0;
+
+	for (i, raw_line) in content.split_inclusive('\n').enumerate() {
+		let line_offset = offset;
+		offset += raw_line.len();
+		let line = raw_line.trim_end_matches(['\n', '\r']);
+		let trimmed = line.trim_start();
+		let line_indent = line.len() - trimmed.len();
+
+		if let Some(scalar_indent) = block_scalar_indent {
+			if trimmed.is_empty() || line_indent > scalar_indent {
+				continue;
+			}
+			block_scalar_indent = None;
+		}
+		if trimmed.is_empty() || trimmed.starts_with('#') {
+			continue;
+		}
+
+		let (indent, rest) = match trimmed.strip_prefix("- ") {
+			Some(after_dash) => {
+				let after_dash_trimmed = after_dash.trim_start();
+				(line.len() - after_dash_trimmed.len(), after_dash_trimmed)
+			}
+			None => (line_indent, trimmed),
+		};
+
+		let Some(colon) = find_key_colon(rest) else {
+			continue;
+		};
+		let key = rest[..colon].trim().trim_matches(['"', '\'']);
+		let after_colon = &rest[colon + 1..];
+		let value_with_comment = after_colon.trim_start();
+		let (value, has_comment) = match find_comment_start(value_with_comment) {
+			Some(hash) => (value_with_comment[..hash].trim_end(), true),
+			None => (value_with_comment.trim_end(), false),
+		};
+		let value_start = line_offset + (line.len() - rest.len()) + colon + 1 + (after_colon.len() - value_with_comment.len());
+
+		if value.starts_with('|') || value.starts_with('>') {
+			block_scalar_indent = Some(indent);
+		}
+
+		entries.push(YamlEntry {
+			line: i + 1,
+			indent,
+			key,
+			value,
+			value_start,
+			has_comment,
+		});
+	}
+
+	entries
+}
+
+/// Split the top-level `jobs:` mapping into jobs and their direct properties.
+pub fn jobs<'a>(entries: &[YamlEntry<'a>]) -> Vec<Job<'a>> {
+	let Some(jobs_idx) = entries.iter().position(|e| e.indent == 0 && e.key == "jobs") else {
+		return vec![];
+	};
+	let jobs_section: Vec<&YamlEntry> = entries[jobs_idx + 1..].iter().take_while(|e| e.indent > 0).collect();
+	let Some(job_indent) = jobs_section.first().map(|e| e.indent) else {
+		return vec![];
+	};
+
+	let mut jobs: Vec<Job> = Vec::new();
+	for entry in jobs_section {
+		if entry.indent == job_indent {
+			jobs.push(Job { entry: entry.clone(), props: Vec::new() });
+		} else if let Some(job) = jobs.last_mut() {
+			let prop_indent = job.props.first().map(|p| p.indent).unwrap_or(entry.indent);
+			if entry.indent == prop_indent {
+				job.props.push(entry.clone());
+			}
+		}
+	}
+	jobs
+}
+
+/// Resolve `owner/repo@ref` to a commit SHA with `git ls-remote`.
+fn resolve_ref(repo: &str, git_ref: &str) -> Option<String> {
+	let output = Command::new("git")
+		.args(["ls-remote", &format!("https://github.com/{repo}"), git_ref, &format!("{git_ref}^{{}}")])
+		.output()
+		.ok()?;
+	if !output.status.success() {
+		return None;
+	}
+	let stdout = String::from_utf8(output.stdout).ok()?;
+	let lines: Vec<(&str, &str)> = stdout.lines().filter_map(|l| l.split_once('\t')).collect();
+	// Annotated tags point at the tag object; the peeled `^{}` entry is the commit
+	let (sha, _) = lines.iter().find(|(_, name)| name.ends_with("^{}")).or(lines.first())?;
+	Some(sha.to_string())
+}
+
+/// Find the `:` that ends a mapping key (followed by whitespace or end of line).
+fn find_key_colon(s: &str) -> Option<usize> {
+	// Flow collections (`{ a: b }`, `[a, b]`) aren't keys
+	if s.starts_with(['{', '[']) {
+		return None;
+	}
+	let bytes = s.as_bytes();
+	(0..bytes.len()).find(|&i| bytes[i] == b':' && bytes.get(i + 1).is_none_or(|b| b.is_ascii_whitespace()))
+}
+
+/// Find the start of a trailing `# comment`, ignoring `#` inside quotes.
+fn find_comment_start(value: &str) -> Option<usize> {
+	let mut quote: Option<char> = None;
+	let mut prev_is_space = true;
+	for (i, c) in value.char_indices() {
+		match quote {
+			Some(q) if c == q => quote = None,
+			Some(_) => {}
+			None if c == '"' || c == '\'' => quote = Some(c),
+			None if c == '#' && prev_is_space => return Some(i),
+			None => {}
+		}
+		prev_is_space = c.is_whitespace();
+	}
+	None
+}
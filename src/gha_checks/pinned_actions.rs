@@ -0,0 +1,68 @@
+//! Lint to require third-party actions to be pinned to a full commit SHA.
+//!
+//! Tags and branches are mutable: whoever controls the action's repo controls what runs in CI.
+//! First-party `actions/*` and `github/*` actions, local actions and `docker://` images are exempt.
+
+use std::path::Path;
+
+use super::yaml_entries;
+use crate::rust_checks::{Fix, Severity, Violation};
+
+const RULE: &str = "pinned-actions";
+const FIRST_PARTY_OWNERS: &[&str] = &["actions", "github"];
+/// `resolver(owner/repo, ref)` returns the commit SHA the ref currently points to.
+pub type ResolveSha<'a> = &'a dyn Fn(&str, &str) -> Option<String>;
+
+pub fn check(path: &Path, content: &str, resolver: Option<ResolveSha>) -> Vec<Violation> {
+	let path_str = path.display().to_string();
+	let mut violations = Vec::new();
+
+	for entry in yaml_entries(content) {
+		if entry.key != "uses" {
+			continue;
+		}
+		let quote_len = usize::from(entry.value.starts_with(['"', '\'']));
+		let uses = entry.value.trim_matches(['"', '\'']);
+		if uses.starts_with("./") || uses.starts_with("docker://") {
+			continue;
+		}
+		let Some((action, git_ref)) = uses.split_once('@') else {
+			continue;
+		};
+		let owner = action.split('/').next().unwrap_or_default();
+		if FIRST_PARTY_OWNERS.contains(&owner) || is_commit_sha(git_ref) {
+			continue;
+		}
+
+		// `owner/repo/path/to/action` lives in `owner/repo`
+		let repo: String = action.splitn(3, '/').take(2).collect::<Vec<_>>().join("/");
+		let fix = resolver.and_then(|resolve| resolve(&repo, git_ref)).map(|sha| {
+			let ref_start = entry.value_start + quote_len + action.len() + 1;
+			let value_end = entry.value_start + entry.value.len();
+			let closing_quote = &content[ref_start + git_ref.len()..value_end];
+			let comment = if entry.has_comment { String::new() } else { format!(" # {git_ref}") };
+			Fix {
+				start_byte: ref_start,
+				end_byte: value_end,
+				replacement: format!("{sha}{closing_quote}{comment}"),
+			}
+		});
+
+		violations.push(Violation {
+			rule: RULE,
+			file: path_str.clone(),
+			line: entry.line,
+			column: entry.indent,
+			message: format!("third-party action `{action}` is pinned to mutable ref `{git_ref}`; pin it to a full commit SHA"),
+			severity: Severity::Error,
+			fix,
+			..Default::default()
+		});
+	}
+
+	violations
+}
+
+fn is_commit_sha(git_ref: &str) -> bool {
+	git_ref.len() == 40 && git_ref.chars().all(|c| c.is_ascii_hexdigit())
+}
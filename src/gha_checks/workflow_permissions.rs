@@ -0,0 +1,32 @@
+//! Lint to require an explicit `permissions:` block.
+//!
+//! Without it, `GITHUB_TOKEN` gets the repository's default permissions, which are often write-all.
+//! Satisfied by a top-level block, or by every job declaring its own.
+
+use std::path::Path;
+
+use super::{jobs, yaml_entries};
+use crate::rust_checks::{Severity, Violation};
+
+const RULE: &str = "workflow-permissions";
+pub fn check(path: &Path, content: &str) -> Vec<Violation> {
+	let entries = yaml_entries(content);
+	if entries.iter().any(|e| e.indent == 0 && e.key == "permissions") {
+		return vec![];
+	}
+
+	let jobs = jobs(&entries);
+	if !jobs.is_empty() && jobs.iter().all(|job| job.props.iter().any(|p| p.key == "permissions")) {
+		return vec![];
+	}
+
+	vec![Violation {
+		rule: RULE,
+		file: path.display().to_string(),
+		line: 1,
+		column: 0,
+		message: "workflow has no `permissions:` block\nHINT: add `permissions: { contents: read }` at the top level and widen it per job where needed".to_string(),
+		severity: Severity::Error,
+		..Default::default()
+	}]
+}
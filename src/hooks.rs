@@ -0,0 +1,110 @@
+//! `codestyle install-hooks`: git hooks that run codestyle, and their removal.
+//!
+//! Installed hooks carry a marker line, so existing hooks are never overwritten or removed unless codestyle wrote them.
+
+use std::{
+	fs,
+	path::{Path, PathBuf},
+	process::Command,
+};
+
+/// Identifies hooks written by `install-hooks`
+const MARKER: &str = "# Installed by `codestyle install-hooks`";
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
+pub enum Hook {
+	/// `codestyle rust assert` on the staged files
+	#[default]
+	PreCommit,
+	/// `codestyle rust assert` on the whole repository
+	PrePush,
+	/// `codestyle commit-msg` on the message being committed
+	CommitMsg,
+}
+impl Hook {
+	pub fn file_name(self) -> &'static str {
+		match self {
+			Self::PreCommit => "pre-commit",
+			Self::PrePush => "pre-push",
+			Self::CommitMsg => "commit-msg",
+		}
+	}
+
+	pub fn script(self) -> String {
+		let body = match self {
+			// Non-Rust files are skipped by `assert` itself; `sh -c` keeps an empty commit from running it with no paths
+			Self::PreCommit => "git diff --cached --name-only -z --diff-filter=ACMR | xargs -0 sh -c '[ $# -eq 0 ] || exec codestyle rust assert \"$@\"' codestyle",
+			Self::PrePush => "exec codestyle rust assert .",
+			Self::CommitMsg => "exec codestyle commit-msg \"$1\"",
+		};
+		format!("#!/bin/sh\n{MARKER}\n{body}\n")
+	}
+}
+
+/// Install `hook` into the git repository containing `dir`, or remove it with `uninstall`.
+/// A hook codestyle didn't write is only replaced with `force`, and never removed.
+pub fn run_install_hooks(dir: &Path, hook: Hook, uninstall: bool, force: bool) -> i32 {
+	let result = hooks_dir(dir).and_then(|hooks_dir| {
+		let path = hooks_dir.join(hook.file_name());
+		if uninstall { uninstall_hook(&path) } else { install_hook(&hooks_dir, &path, hook, force) }
+	});
+	match result {
+		Ok(message) => {
+			println!("{message}");
+			0
+		}
+		Err(e) => {
+			eprintln!("codestyle: {e}");
+			1
+		}
+	}
+}
+
+fn install_hook(hooks_dir: &Path, path: &Path, hook: Hook, force: bool) -> Result<String, String> {
+	if !force
+		&& let Some(existing) = read_hook(path)?
+		&& !existing.contains(MARKER)
+	{
+		return Err(format!("{path:?} already exists and wasn't installed by codestyle; rerun with --force to replace it"));
+	}
+	fs::create_dir_all(hooks_dir).map_err(|e| format!("failed to create {hooks_dir:?}: {e}"))?;
+	fs::write(path, hook.script()).map_err(|e| format!("failed to write {path:?}: {e}"))?;
+	#[cfg(unix)]
+	{
+		use std::os::unix::fs::PermissionsExt;
+		fs::set_permissions(path, fs::Permissions::from_mode(0o755)).map_err(|e| format!("failed to make {path:?} executable: {e}"))?;
+	}
+	Ok(format!("Installed {path:?}"))
+}
+
+fn uninstall_hook(path: &Path) -> Result<String, String> {
+	match read_hook(path)? {
+		None => Ok(format!("{path:?} is not installed")),
+		Some(existing) if !existing.contains(MARKER) => Err(format!("{path:?} wasn't installed by codestyle; leaving it in place")),
+		Some(_) => {
+			fs::remove_file(path).map_err(|e| format!("failed to remove {path:?}: {e}"))?;
+			Ok(format!("Removed {path:?}"))
+		}
+	}
+}
+
+fn read_hook(path: &Path) -> Result<Option<String>, String> {
+	match fs::read_to_string(path) {
+		Ok(content) => Ok(Some(content)),
+		Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+		Err(e) => Err(format!("failed to read {path:?}: {e}")),
+	}
+}
+
+/// The hooks directory git uses for `dir`, honoring worktrees and `core.hooksPath`.
+fn hooks_dir(dir: &Path) -> Result<PathBuf, String> {
+	let output = Command::new("git")
+		.args(["rev-parse", "--git-path", "hooks"])
+		.current_dir(dir)
+		.output()
+		.map_err(|e| format!("failed to run git: {e}"))?;
+	if !output.status.success() {
+		return Err(format!("{dir:?} is not in a git repository: {}", String::from_utf8_lossy(&output.stderr).trim()));
+	}
+	Ok(dir.join(String::from_utf8_lossy(&output.stdout).trim()))
+}
@@ -0,0 +1,161 @@
+//! `codestyle init`: a `codestyle.toml` listing every option, commented out with its default and a short description,
+//! so configuring codestyle starts from editing rather than from the docs.
+
+use std::{fs, path::Path};
+
+use crate::{
+	config::{CONFIG_FILE_NAME, Profile},
+	rust_checks::{RustCheckOptions, rules},
+};
+
+/// Rust options that aren't rules, as `(key, default, description)`
+const RUST_DISCOVERY_OPTIONS: &[(&str, &str, &str)] = &[
+	("exclude", "[]", "Globs of paths to skip, relative to the target directory"),
+	("generated", "[]", "Globs of generated files to skip, on top of `*_generated.rs` and files marked `@generated`"),
+	("include_nested", "false", "Also check crates nested inside source directories"),
+	("plugins", "[]", "Commands of external rule plugins"),
+	("test_exempt", "[]", "Options whose rules skip test code: `#[cfg(test)]` modules, `#[test]` functions and files under `tests/`"),
+	("unwrap_marker", "\"SAFE\"", "Comment marker `no_unwrap` takes as justification"),
+	("expect_message_min_len", "8", "Shortest `.expect()` message `expect_message` accepts"),
+	("no_panic_allow", "[]", "Macros `no_panic` lets through, e.g. `[\"todo\"]`"),
+	("no_assert_marker", "\"\"", "Comment marker `no_assert` takes as justifying an assertion outside tests, e.g. `\"INVARIANT\"`; none if empty"),
+	("todo_issue_pattern", "'#\\d+|https?://\\S+'", "Regex an issue reference in a `todo!` message or `TODO` comment matches, for `todo_issue` and `todo_comment`"),
+	("todo_comment_markers", "[\"TODO\", \"FIXME\", \"HACK\"]", "Comment markers `todo_comment` wants an issue reference after"),
+	("pub_use_modules", "[\"prelude\"]", "Modules `pub_use_location` allows `pub use` in besides `lib.rs` and `mod.rs`"),
+	("commented_code_confidence", "0.5", "Share of a comment block's lines that have to look like code, on top of it parsing, for `commented_code` to flag it"),
+	("nesting_depth_max", "4", "Deepest nesting of if/match/loops/closures `nesting_depth` accepts inside a function"),
+	("fn_params_max", "7", "Most parameters `fn_params` accepts on a function, `self` not counted"),
+	("bool_params_in_pub", "true", "Whether `bool_params` flags even a single `bool` parameter of a public fn"),
+	("mod_style_prefer", "\"file\"", "Module file layout `mod_style` keeps: `\"file\"` for `foo.rs` next to `foo/`, `\"mod_rs\"` for `foo/mod.rs`"),
+	("no_blocking_in_async_paths", "[]", "Paths `no_blocking_in_async` flags in async code besides std's blocking ones, e.g. `[\"reqwest::blocking\"]`"),
+	("no_other_runtimes_crates", "[]", "Crates `no_other_runtimes` flags besides async-std's and smol's, e.g. `[\"glommio\"]`"),
+];
+
+/// A `[section]` of the template for options described elsewhere, like the CLI flags of other subcommands.
+pub struct TemplateSection {
+	pub name: &'static str,
+	/// `(key, default, description)`, with the default as it's written in TOML
+	pub options: Vec<(String, String, String)>,
+}
+
+/// The commented config for `preset`: Rust rules and options first, then `sections`.
+pub fn template(preset: Profile, sections: &[TemplateSection]) -> String {
+	let mut out = String::from(
+		"# codestyle configuration. Every option is listed with its default, commented out: uncomment one to change it.\n\
+		 # `CODESTYLE_<SECTION>_<OPTION>` environment variables and CLI flags take precedence over this file.\n\n",
+	);
+	match preset {
+		Profile::Default => out.push_str("# Turn every check on before the options below apply\n# profile = \"strict\"\n"),
+		Profile::Strict => out.push_str("# Every check on before the options below apply\nprofile = \"strict\"\n"),
+	}
+
+	let rust_defaults = match preset {
+		Profile::Default => RustCheckOptions::default(),
+		Profile::Strict => RustCheckOptions::strict(),
+	};
+	out.push_str("\n[rust]\n# Rule severities: \"error\" fails `assert`, \"warn\" only reports, \"allow\" turns the rule off\n");
+	// Some options control several rules
+	let mut options: Vec<(&str, Vec<&str>)> = Vec::new();
+	for info in rules::RULES {
+		match options.iter_mut().find(|(option, _)| *option == info.option) {
+			Some((_, descriptions)) => descriptions.push(info.description),
+			None => options.push((info.option, vec![info.description])),
+		}
+	}
+	for (option, descriptions) in options {
+		push_option(&mut out, option, &format!("\"{}\"", rust_defaults.severity(option)), &descriptions.join("; "));
+	}
+	for (key, default, description) in RUST_DISCOVERY_OPTIONS {
+		push_option(&mut out, key, default, description);
+	}
+	out.push_str(
+		"\n# Project-specific bans, one table each; `pattern` is a path, a macro (`dbg!`) or a method (`.unwrap()`)\n\
+		 # [[rust.custom_rules]]\n\
+		 # id = \"no-env-var\"\n\
+		 # pattern = \"std::env::var\"\n\
+		 # message = \"use Config instead\"\n\
+		 \n# Bans that only need a message, as `pattern = message`, all reported as `banned`\n\
+		 # [rust.banned]\n\
+		 # \"std::env::set_var\" = \"not thread-safe, pass the value down instead\"\n",
+	);
+
+	for section in sections {
+		out.push_str(&format!("\n[{}]\n", section.name));
+		for (key, default, description) in &section.options {
+			push_option(&mut out, key, default, description);
+		}
+	}
+	out
+}
+
+/// Write the template for `preset` to `codestyle.toml` in `dir`. An existing file is only replaced with `force`.
+pub fn run_init(dir: &Path, preset: Profile, sections: &[TemplateSection], force: bool) -> i32 {
+	let path = dir.join(CONFIG_FILE_NAME);
+	if path.exists() && !force {
+		eprintln!("codestyle: {path:?} already exists; rerun with --force to replace it");
+		return 1;
+	}
+	match fs::write(&path, template(preset, sections)) {
+		Ok(()) => {
+			println!("Wrote {path:?}");
+			0
+		}
+		Err(e) => {
+			eprintln!("codestyle: failed to write {path:?}: {e}");
+			1
+		}
+	}
+}
+
+fn push_option(out: &mut String, key: &str, default: &str, description: &str) {
+	out.push_str(&format!("\n# {description}\n# {key} = {default}\n"));
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::BTreeMap;
+
+	use super::*;
+	use crate::config::Config;
+
+	/// `template` with every commented-out option (and table header) enabled, leaving descriptions as comments.
+	fn uncommented(template: &str) -> String {
+		let is_option = |l: &&str| l.starts_with("[[") || l.split_once(" = ").is_some_and(|(key, _)| key.chars().all(|c| c.is_ascii_lowercase() || c == '_'));
+		template
+			.lines()
+			.map(|line| line.strip_prefix("# ").filter(is_option).unwrap_or(line))
+			.collect::<Vec<_>>()
+			.join("\n")
+	}
+
+	#[test]
+	fn every_rule_is_listed_with_its_default() {
+		let template = template(Profile::Default, &[]);
+		for info in rules::RULES {
+			assert!(
+				template.contains(&format!("# {} = \"{}\"", info.option, RustCheckOptions::default().severity(info.option))),
+				"{}",
+				info.option
+			);
+		}
+		assert!(template.contains("# instrument = \"allow\""));
+		assert!(super::template(Profile::Strict, &[]).contains("# instrument = \"error\""));
+	}
+
+	#[test]
+	fn uncommenting_everything_is_a_valid_config() {
+		let sections = [TemplateSection {
+			name: "commit_msg",
+			options: vec![("max_subject_len".to_string(), "72".to_string(), "Maximum subject line length".to_string())],
+		}];
+		let config = Config::from_parts(Some(&uncommented(&template(Profile::Default, &sections))), BTreeMap::new()).unwrap();
+		assert_eq!(config.profile, Profile::Strict);
+		let rust = config.section("rust");
+		assert_eq!(rust.get::<String>("loops").as_deref(), Some("error"));
+		assert_eq!(rust.get_list("exclude"), Some(Vec::new()));
+		assert_eq!(config.section("commit_msg").get::<usize>("max_subject_len"), Some(72));
+
+		let strict = Config::from_parts(Some(&template(Profile::Strict, &[])), BTreeMap::new()).unwrap();
+		assert_eq!(strict.profile, Profile::Strict);
+	}
+}
@@ -1 +1,12 @@
+pub mod commit_msg_checks;
+pub mod config;
+pub mod docker_checks;
+pub mod gha_checks;
+pub mod hooks;
+pub mod init;
+pub mod linter;
 pub mod rust_checks;
+pub mod task_checks;
+pub mod ts_checks;
+
+pub use linter::{Fixed, Linter, Report};
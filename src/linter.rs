@@ -0,0 +1,120 @@
+//! Library entry point for embedding the Rust checks: bots, editors and build scripts can lint and fix code
+//! in-process, with the same results as `codestyle rust assert`/`format`.
+//!
+//! ```no_run
+//! use codestyle::{Linter, rust_checks::RustCheckOptions};
+//!
+//! let linter = Linter::new(RustCheckOptions::default());
+//! let report = linter.check_path("my-project").unwrap();
+//! for v in &report.violations {
+//! 	eprintln!("[{}] {}:{}:{}: {}", v.rule, v.file, v.line, v.column, v.message);
+//! }
+//!
+//! let fixed = linter.apply_fixes("src/lib.rs", "fn main() {}\n").unwrap();
+//! assert_eq!(fixed.source, "fn main() {}\n");
+//! ```
+
+use std::{
+	path::{Path, PathBuf},
+	slice,
+};
+
+use crate::rust_checks::{
+	CrateOptions, RustCheckOptions, Severity, Violation, check_file, collect_violations, exclude::DiscoveryOptions, format_source, parse_rust_source, rustfmt::RustfmtConfig,
+};
+
+/// Runs the Rust checks with fixed options, returning results instead of printing them. Nothing is written unless asked for.
+#[derive(Clone, Debug)]
+pub struct Linter<O = RustCheckOptions> {
+	opts: O,
+	discovery: DiscoveryOptions,
+	changed: Option<String>,
+	cache: bool,
+}
+impl<O: CrateOptions> Linter<O> {
+	/// `opts` can also be a closure from a directory to its options, for per-crate configuration.
+	pub fn new(opts: O) -> Self {
+		Self {
+			opts,
+			discovery: DiscoveryOptions::default(),
+			changed: None,
+			cache: false,
+		}
+	}
+
+	/// Which paths to skip while walking directories.
+	pub fn discovery(mut self, discovery: DiscoveryOptions) -> Self {
+		self.discovery = discovery;
+		self
+	}
+
+	/// Only check files that changed since `git_ref`, as `--changed` does.
+	pub fn changed_since(mut self, git_ref: impl Into<String>) -> Self {
+		self.changed = Some(git_ref.into());
+		self
+	}
+
+	/// Reuse and record results in `target/codestyle-cache`, as `assert` does by default. Off unless enabled.
+	pub fn cache(mut self, cache: bool) -> Self {
+		self.cache = cache;
+		self
+	}
+
+	/// Check a directory (walked for its crates' sources), a `.rs` file or a `Cargo.toml`.
+	pub fn check_path(&self, path: impl AsRef<Path>) -> Result<Report, String> {
+		self.check_paths(slice::from_ref(&path.as_ref().to_path_buf()))
+	}
+
+	/// Check several paths in one run, which is what `codestyle rust assert a b c` does.
+	pub fn check_paths(&self, paths: &[PathBuf]) -> Result<Report, String> {
+		let violations = collect_violations(paths, &self.opts, &self.discovery, self.changed.as_deref(), self.cache, false)?;
+		Ok(Report { violations })
+	}
+
+	/// Check `source` as the contents of `path`, which only needs to exist for options and `rustfmt.toml` lookup.
+	pub fn check_source(&self, path: impl AsRef<Path>, source: &str) -> Result<Report, String> {
+		let path = path.as_ref();
+		syn::parse_file(source).map_err(|e| format!("failed to parse {path:?}: {e}"))?;
+		let (opts, rustfmt) = self.dir_state(path);
+		let info = parse_rust_source(path.to_path_buf(), source.to_string()).expect("just parsed");
+		Ok(Report {
+			violations: check_file(&info, &opts, &rustfmt),
+		})
+	}
+
+	/// Apply every available fix to `source`, the contents of `path`, without writing anything.
+	pub fn apply_fixes(&self, path: impl AsRef<Path>, source: &str) -> Result<Fixed, String> {
+		let path = path.as_ref();
+		syn::parse_file(source).map_err(|e| format!("failed to parse {path:?}: {e}"))?;
+		let (opts, rustfmt) = self.dir_state(path);
+		let (source, fixed_count, remaining) = format_source(path, source, &opts, &rustfmt);
+		Ok(Fixed { source, fixed_count, remaining })
+	}
+
+	fn dir_state(&self, path: &Path) -> (RustCheckOptions, RustfmtConfig) {
+		let dir = path.parent().filter(|d| !d.as_os_str().is_empty()).unwrap_or(Path::new("."));
+		(self.opts.for_dir(dir), RustfmtConfig::load(dir))
+	}
+}
+
+/// What a check found.
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+	/// In the order they were found: by file, then by rule
+	pub violations: Vec<Violation>,
+}
+impl Report {
+	/// Whether any violation fails the run, as it would fail `assert`.
+	pub fn has_errors(&self) -> bool {
+		self.violations.iter().any(|v| v.severity == Severity::Error)
+	}
+}
+
+/// The result of [`Linter::apply_fixes`].
+#[derive(Clone, Debug)]
+pub struct Fixed {
+	pub source: String,
+	pub fixed_count: usize,
+	/// Violations left for fixing by hand
+	pub remaining: Vec<Violation>,
+}
@@ -0,0 +1,30 @@
+//! Diagnostics about the run itself, on stderr: which files were skipped and why, which fixes went in and in what
+//! order, how long each rule took. Off unless asked for with `-v`, so they never mix into regular output.
+
+use tracing::level_filters::LevelFilter;
+
+/// How log lines are printed.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
+pub enum LogFormat {
+	/// One human-readable line per event
+	#[default]
+	Text,
+	/// One JSON object per event, for piping into `jq`
+	Json,
+}
+
+/// Install the global subscriber: warnings only by default, `-v` for skipped files and applied fixes, `-vv` for
+/// per-rule timings as well.
+pub fn init(verbosity: u8, format: LogFormat) {
+	let level = match verbosity {
+		0 => LevelFilter::WARN,
+		1 => LevelFilter::DEBUG,
+		_ => LevelFilter::TRACE,
+	};
+	let builder = tracing_subscriber::fmt().with_max_level(level).with_writer(std::io::stderr).with_target(false);
+	//IGNORED_ERROR: only fails if a subscriber is already installed, which then gets the events instead
+	let _ = match format {
+		LogFormat::Text => builder.try_init(),
+		LogFormat::Json => builder.json().try_init(),
+	};
+}
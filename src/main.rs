@@ -1,12 +1,35 @@
-use std::path::PathBuf;
+use std::{
+	ffi::OsString,
+	path::{Path, PathBuf},
+	process::Command,
+};
 
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, CommandFactory, FromArgMatches, Parser, Subcommand};
+
+/// Path argument meaning "read from stdin"
+const STDIN: &str = "-";
+/// `cargo codestyle ...` runs `cargo-codestyle codestyle ...`
+const CARGO_SUBCOMMAND: &str = "codestyle";
+/// Arguments naming what to check, which default to the workspace root under cargo
+const TARGET_ARGS: &[&str] = &["paths", "target_dir"];
 
 #[derive(Parser)]
 #[command(author, version = concat!(env!("CARGO_PKG_VERSION"), " (", env!("GIT_HASH"), ")"), about, long_about = None)]
 struct Cli {
 	#[command(subcommand)]
 	command: Commands,
+
+	/// Config file to use instead of `codestyle.toml` in the target directory [env: CODESTYLE_CONFIG]
+	#[arg(long, global = true)]
+	config: Option<PathBuf>,
+
+	/// Log skipped files and applied fixes to stderr; `-vv` adds how long each rule took
+	#[arg(short, long, action = clap::ArgAction::Count, global = true)]
+	verbose: u8,
+
+	/// How to print `-v` logs
+	#[arg(long, value_enum, default_value_t, global = true)]
+	log_format: LogFormat,
 }
 #[derive(Subcommand)]
 enum Commands {
@@ -17,14 +40,414 @@ enum Commands {
 
 		#[command(flatten)]
 		options: RustCheckOptionsArgs,
+
+		/// Skip paths matching this glob, relative to the target directory; repeatable, and added to `exclude` from the config
+		#[arg(long, value_name = "GLOB", global = true)]
+		exclude: Vec<String>,
+
+		/// Also walk into nested packages and workspaces (directories with their own `Cargo.toml`) below a source directory
+		#[arg(long, global = true)]
+		include_nested: bool,
+	},
+	/// Run TypeScript/JavaScript code style checks
+	Ts {
+		#[command(subcommand)]
+		mode: CheckMode,
+
+		#[command(flatten)]
+		options: TsCheckOptionsArgs,
+	},
+	/// Run GitHub Actions workflow checks on .github/workflows/
+	Gha {
+		#[command(subcommand)]
+		mode: CheckMode,
+
+		#[command(flatten)]
+		options: GhaCheckOptionsArgs,
+	},
+	/// Run Dockerfile checks
+	Docker {
+		#[command(subcommand)]
+		mode: CheckMode,
+
+		#[command(flatten)]
+		options: DockerCheckOptionsArgs,
+	},
+	/// Run justfile/Makefile checks
+	Tasks {
+		#[command(subcommand)]
+		mode: CheckMode,
+
+		#[command(flatten)]
+		options: TaskCheckOptionsArgs,
+	},
+	/// Serve Rust check/format requests as newline-delimited JSON, keeping config and results warm for editor integrations
+	Daemon {
+		/// Listen on this unix socket instead of stdin/stdout
+		#[arg(long)]
+		socket: Option<PathBuf>,
+
+		#[command(flatten)]
+		options: RustCheckOptionsArgs,
+	},
+	/// Install a git hook running codestyle, or remove it with --uninstall
+	InstallHooks {
+		#[arg(long, value_enum, default_value_t)]
+		hook: Hook,
+
+		/// Remove the hook instead, if codestyle installed it
+		#[arg(long)]
+		uninstall: bool,
+
+		/// Replace an existing hook that codestyle didn't install
+		#[arg(long, conflicts_with = "uninstall")]
+		force: bool,
+	},
+	/// Write a `codestyle.toml` listing every option, commented out with its default and description
+	Init {
+		/// Directory to write it to
+		target_dir: PathBuf,
+
+		/// Profile to start from, `default` or `strict`
+		#[arg(long, default_value = "default")]
+		preset: Profile,
+
+		/// Replace an existing `codestyle.toml`
+		#[arg(long)]
+		force: bool,
+	},
+	/// Check a commit message file (for use in the commit-msg git hook)
+	CommitMsg {
+		/// Commit message file, as passed to the hook
+		file: PathBuf,
+
+		#[command(flatten)]
+		options: CommitMsgOptionsArgs,
 	},
 }
 #[derive(Subcommand)]
 enum RustMode {
+	/// Check for violations and exit 1 on failure
+	Assert {
+		/// Directories to check, or individual files (e.g. the staged ones, from a pre-commit hook)
+		#[arg(required = true)]
+		paths: Vec<PathBuf>,
+
+		#[arg(long, value_enum, default_value_t)]
+		format: OutputFormat,
+
+		/// Only fail on violations not recorded in this baseline file
+		#[arg(long)]
+		baseline: Option<PathBuf>,
+
+		/// Record the current violations in the --baseline file and exit 0
+		#[arg(long, requires = "baseline")]
+		write_baseline: bool,
+
+		/// Only check files changed since the working tree forked from this git ref (e.g. `origin/main`), plus untracked ones
+		#[arg(long, value_name = "REF")]
+		changed: Option<String>,
+
+		/// Check every file afresh instead of reusing results cached in `target/codestyle-cache`
+		#[arg(long)]
+		no_cache: bool,
+
+		#[command(flatten)]
+		fail: FailPolicy,
+	},
+	/// Attempt to fix violations automatically
+	Format {
+		/// Directories or files to fix, or `-` to fix a single file read from stdin and print it to stdout
+		#[arg(required = true)]
+		paths: Vec<PathBuf>,
+
+		/// Print the fixes as unified diffs instead of writing them; exits 1 if anything would change
+		#[arg(long)]
+		dry_run: bool,
+	},
+	/// Lay out every file's top-level items in one pass (mods, uses, then items per pub-first, impls after their types)
+	Organize {
+		/// Target directory to organize
+		target_dir: PathBuf,
+	},
+	/// Report codebase metrics: fn lengths, impl blocks per type, async/unsafe counts, violation density
+	Stats {
+		/// Target directory to analyze
+		target_dir: PathBuf,
+
+		#[arg(long, value_enum, default_value_t)]
+		format: OutputFormat,
+	},
+	/// List every rule with its id, default severity, autofix support and description
+	ListRules {
+		#[arg(long, value_enum, default_value_t)]
+		format: OutputFormat,
+	},
+	/// Print a rule's rationale, violating and passing examples, and fix behavior
+	Explain {
+		/// Rule id, as printed in violations (e.g. `no-tokio-spawn`)
+		rule: String,
+	},
+}
+impl RustMode {
+	fn target_dir(&self) -> &Path {
+		match self {
+			Self::Assert { paths, .. } | Self::Format { paths, .. } => rust_checks::run_root(paths),
+			Self::Organize { target_dir } | Self::Stats { target_dir, .. } => target_dir,
+			Self::ListRules { .. } | Self::Explain { .. } => Path::new("."),
+		}
+	}
+}
+#[derive(Args)]
+struct RustCheckOptionsArgs {
+	/// Order and group dependencies in Cargo.toml [default: error]
+	#[arg(long)]
+	cargo_dep_ordering: Option<Severity>,
+
+	/// Check for #[instrument] on async functions [default: allow]
+	#[arg(long)]
+	instrument: Option<Severity>,
+
+	/// Check for //LOOP comment on endless loops [default: error]
+	#[arg(long)]
+	loops: Option<Severity>,
+
+	/// Check for // SAFETY: comments on unsafe blocks and fns [default: allow]
+	#[arg(long)]
+	safety_comment: Option<Severity>,
+
+	/// Join split impl blocks for the same type [default: error]
+	#[arg(long)]
+	join_split_impls: Option<Severity>,
+
+	/// Wrap impl blocks with vim 1-fold markers [default: allow]
+	#[arg(long)]
+	impl_folds: Option<Severity>,
+
+	/// Check that impl blocks follow type definitions [default: error]
+	#[arg(long)]
+	impl_follows_type: Option<Severity>,
+
+	/// Place trait impls right after their type's inherent impl, std traits first [default: allow]
+	#[arg(long)]
+	trait_impls_follow: Option<Severity>,
+
+	/// Check for simple vars that should be embedded in format strings [default: error]
+	#[arg(long)]
+	embed_simple_vars: Option<Severity>,
+
+	/// Check that insta snapshots use inline @"" syntax [default: error]
+	#[arg(long)]
+	insta_inline_snapshot: Option<Severity>,
+
+	/// Disallow usage of chrono crate (use jiff instead) [default: error]
+	#[arg(long)]
+	no_chrono: Option<Severity>,
+
+	/// Disallow usage of tokio::spawn [default: error]
+	#[arg(long)]
+	no_tokio_spawn: Option<Severity>,
+
+	/// Disallow leftover dbg! [default: allow]
+	#[arg(long)]
+	no_dbg: Option<Severity>,
+
+	/// Replace `return Err(eyre!(...))` with `bail!(...)` [default: error]
+	#[arg(long)]
+	use_bail: Option<Severity>,
+
+	/// Check that test functions don't have redundant `test_` prefix [default: allow]
+	#[arg(long)]
+	test_fn_prefix: Option<Severity>,
+
+	/// Check that #[test] fns in src/ are inside a #[cfg(test)] module [default: allow]
+	#[arg(long)]
+	test_in_cfg_test: Option<Severity>,
+
+	/// Check that public items come before private items [default: error]
+	#[arg(long)]
+	pub_first: Option<Severity>,
+
+	/// Check that #[cfg(test)] mod tests is the last item in its file [default: allow]
+	#[arg(long)]
+	tests_last: Option<Severity>,
+
+	/// Check for //IGNORED_ERROR comments on unwrap_or/unwrap_or_default/unwrap_or_else and `let _ = ...` [default: error]
+	#[arg(long)]
+	ignored_error_comment: Option<Severity>,
+
+	/// Check for //SAFE comments (or `unwrap_marker`) on .unwrap() and .expect() outside tests [default: allow]
+	#[arg(long)]
+	no_unwrap: Option<Severity>,
+
+	/// Check that .expect() messages describe the invariant rather than being empty or restating the call [default: allow]
+	#[arg(long)]
+	expect_message: Option<Severity>,
+
+	/// Disallow panic!, todo! and unimplemented! in library code [default: allow]
+	#[arg(long)]
+	no_panic: Option<Severity>,
+
+	/// Require a reason on #[allow] and #[expect] attributes [default: allow]
+	#[arg(long)]
+	allow_reason: Option<Severity>,
+
+	/// Require doc comments on public fns, structs, enums and traits of libraries [default: allow]
+	#[arg(long)]
+	missing_docs: Option<Severity>,
+
+	/// Keep #[derive] lists in canonical order [default: allow]
+	#[arg(long)]
+	derive_order: Option<Severity>,
+
+	/// Limit nesting of if/match/loops/closures inside a function [default: allow]
+	#[arg(long)]
+	nesting_depth: Option<Severity>,
+
+	/// Limit the number of function parameters [default: allow]
+	#[arg(long)]
+	fn_params: Option<Severity>,
+
+	/// Disallow several bool parameters, or any in public fns [default: allow]
+	#[arg(long)]
+	bool_params: Option<Severity>,
+
+	/// Disallow lossy numeric `as` casts [default: allow]
+	#[arg(long)]
+	no_as_cast: Option<Severity>,
+
+	/// Prefer let-else over a match or if-let that only unwraps or diverges [default: allow]
+	#[arg(long)]
+	let_else: Option<Severity>,
+
+	/// Use matches!(...) instead of a match or if-let returning true/false [default: allow]
+	#[arg(long)]
+	use_matches: Option<Severity>,
+
+	/// Keep module files in one layout, foo.rs next to foo/ or foo/mod.rs [default: allow]
+	#[arg(long)]
+	mod_style: Option<Severity>,
+
+	/// Disallow std::sync lock guards held across .await [default: allow]
+	#[arg(long)]
+	no_lock_across_await: Option<Severity>,
+
+	/// Disallow blocking std::thread::sleep, std::fs and std::net calls in async code [default: allow]
+	#[arg(long)]
+	no_blocking_in_async: Option<Severity>,
+
+	/// Disallow block_on inside async fns and the closures they create [default: allow]
+	#[arg(long)]
+	no_block_on_in_async: Option<Severity>,
+
+	/// Disallow the async_trait crate, in favor of native async fn in traits [default: allow]
+	#[arg(long)]
+	no_async_trait: Option<Severity>,
+
+	/// Disallow the lazy_static crate, in favor of std::sync::LazyLock [default: allow]
+	#[arg(long)]
+	no_lazy_static: Option<Severity>,
+
+	/// Disallow once_cell's Lazy and OnceCell, in favor of std's LazyLock and OnceLock [default: allow]
+	#[arg(long)]
+	no_once_cell: Option<Severity>,
+
+	/// Disallow async runtimes other than tokio, like async-std and smol [default: allow]
+	#[arg(long)]
+	no_other_runtimes: Option<Severity>,
+
+	/// Require reqwest clients and requests to set a timeout [default: allow]
+	#[arg(long)]
+	reqwest_timeout: Option<Severity>,
+
+	/// Derive thiserror::Error on public error enums instead of implementing Display and Error by hand [default: allow]
+	#[arg(long)]
+	use_thiserror: Option<Severity>,
+
+	/// Require public structs and enums to implement Debug [default: allow]
+	#[arg(long)]
+	derive_debug: Option<Severity>,
+
+	/// Derive Default instead of implementing it by hand with each field's default [default: allow]
+	#[arg(long)]
+	derive_default: Option<Severity>,
+
+	/// Order struct fields: pub before private [default: allow]
+	#[arg(long)]
+	pub_fields_first: Option<Severity>,
+
+	/// Match mod declarations to module files, both ways [default: allow]
+	#[arg(long)]
+	mod_files: Option<Severity>,
+
+	/// Order inherent impls: associated items, constructors, pub methods, then private ones [default: allow]
+	#[arg(long)]
+	impl_method_order: Option<Severity>,
+
+	/// Disallow the get_ prefix on getters [default: allow]
+	#[arg(long)]
+	no_get_prefix: Option<Severity>,
+
+	/// Require an expected message on #[should_panic] [default: allow]
+	#[arg(long)]
+	should_panic_expected: Option<Severity>,
+
+	/// Disallow assert!, assert_eq! and assert_ne! outside tests [default: allow]
+	#[arg(long)]
+	no_assert: Option<Severity>,
+
+	/// Require an issue reference in todo! and unimplemented! messages [default: allow]
+	#[arg(long)]
+	todo_issue: Option<Severity>,
+
+	/// Require an issue reference in TODO, FIXME and HACK comments [default: allow]
+	#[arg(long)]
+	todo_comment: Option<Severity>,
+
+	/// Disallow commented-out code [default: allow]
+	#[arg(long)]
+	commented_code: Option<Severity>,
+
+	/// Disallow extern crate, unneeded since edition 2018 [default: allow]
+	#[arg(long)]
+	no_extern_crate: Option<Severity>,
+
+	/// Disallow #[macro_use] in favor of importing macros with use [default: allow]
+	#[arg(long)]
+	no_macro_use: Option<Severity>,
+
+	/// Restrict pub use re-exports to lib.rs, mod.rs and prelude modules [default: allow]
+	#[arg(long)]
+	pub_use_location: Option<Severity>,
+
+	/// Flag pub items nothing outside their module uses [default: allow]
+	#[arg(long)]
+	unused_pub: Option<Severity>,
+
+	/// Flag files under src/ that no mod declaration reaches [default: allow]
+	#[arg(long)]
+	dead_files: Option<Severity>,
+
+	/// Require a reason on codestyle::skip marker comments [default: allow]
+	#[arg(long)]
+	skip_reason: Option<Severity>,
+
+	/// Report codestyle::skip marker comments that suppress nothing [default: allow]
+	#[arg(long)]
+	unused_skip: Option<Severity>,
+}
+#[derive(Subcommand)]
+enum CheckMode {
 	/// Check for violations and exit 1 on failure
 	Assert {
 		/// Target directory to check
 		target_dir: PathBuf,
+
+		#[arg(long, value_enum, default_value_t)]
+		format: OutputFormat,
+
+		#[command(flatten)]
+		fail: FailPolicy,
 	},
 	/// Attempt to fix violations automatically
 	Format {
@@ -32,107 +455,466 @@ enum RustMode {
 		target_dir: PathBuf,
 	},
 }
+impl CheckMode {
+	fn target_dir(&self) -> &Path {
+		match self {
+			Self::Assert { target_dir, .. } | Self::Format { target_dir } => target_dir,
+		}
+	}
+}
 #[derive(Args)]
-struct RustCheckOptionsArgs {
-	/// Order and group dependencies in Cargo.toml [default: true]
+struct TsCheckOptionsArgs {
+	/// Disallow `as any` / `<any>` casts without //ANY_CAST comment [default: true]
 	#[arg(long)]
-	cargo_dep_ordering: Option<bool>,
+	no_any_cast: Option<bool>,
 
-	/// Check for #[instrument] on async functions [default: false]
+	/// Disallow `console.log` in src/ [default: true]
 	#[arg(long)]
-	instrument: Option<bool>,
+	no_console_log: Option<bool>,
 
-	/// Check for //LOOP comment on endless loops [default: true]
+	/// Require //FIRE_AND_FORGET comment on `void promise` patterns [default: true]
 	#[arg(long)]
-	loops: Option<bool>,
-
-	/// Join split impl blocks for the same type [default: true]
+	void_promise: Option<bool>,
+}
+#[derive(Args)]
+struct GhaCheckOptionsArgs {
+	/// Require third-party actions to be pinned to a commit SHA [default: true]
 	#[arg(long)]
-	join_split_impls: Option<bool>,
+	pinned_actions: Option<bool>,
 
-	/// Wrap impl blocks with vim 1-fold markers [default: false]
+	/// Require a top-level `permissions:` block [default: true]
 	#[arg(long)]
-	impl_folds: Option<bool>,
+	workflow_permissions: Option<bool>,
 
-	/// Check that impl blocks follow type definitions [default: true]
+	/// Require `timeout-minutes` on every job [default: true]
 	#[arg(long)]
-	impl_follows_type: Option<bool>,
+	job_timeout: Option<bool>,
 
-	/// Check for simple vars that should be embedded in format strings [default: true]
+	/// Resolve action refs to commit SHAs over the network when fixing [default: false]
+	#[arg(long)]
+	allow_network: Option<bool>,
+}
+#[derive(Args)]
+struct DockerCheckOptionsArgs {
+	/// Require `FROM` images to be pinned to a tag or digest [default: true]
 	#[arg(long)]
-	embed_simple_vars: Option<bool>,
+	pinned_base_image: Option<bool>,
 
-	/// Check that insta snapshots use inline @"" syntax [default: true]
+	/// Require the final stage to switch to a non-root `USER` [default: true]
 	#[arg(long)]
-	insta_inline_snapshot: Option<bool>,
+	non_root_user: Option<bool>,
 
-	/// Disallow usage of chrono crate (use jiff instead) [default: true]
+	/// Replace `ADD` with `COPY` when no URL or archive is involved [default: true]
+	#[arg(long)]
+	prefer_copy: Option<bool>,
+}
+#[derive(Args)]
+struct TaskCheckOptionsArgs {
+	/// Require a `default` or `help` recipe [default: true]
 	#[arg(long)]
-	no_chrono: Option<bool>,
+	default_recipe: Option<bool>,
 
-	/// Disallow usage of tokio::spawn [default: true]
+	/// Disallow recipes with identical bodies [default: true]
 	#[arg(long)]
-	no_tokio_spawn: Option<bool>,
+	duplicate_recipe_body: Option<bool>,
 
-	/// Replace `return Err(eyre!(...))` with `bail!(...)` [default: true]
+	/// Require non-file Makefile targets to be declared `.PHONY` [default: true]
 	#[arg(long)]
-	use_bail: Option<bool>,
+	phony_targets: Option<bool>,
+}
+#[derive(Args)]
+struct CommitMsgOptionsArgs {
+	/// Maximum subject line length, in characters [default: 72]
+	#[arg(long)]
+	max_subject_len: Option<usize>,
 
-	/// Check that test functions don't have redundant `test_` prefix [default: false]
+	/// Require the subject to start with an imperative verb [default: true]
 	#[arg(long)]
-	test_fn_prefix: Option<bool>,
+	imperative_mood: Option<bool>,
 
-	/// Check that public items come before private items [default: true]
+	/// Require a conventional-commit `type(scope): ` prefix [default: true]
 	#[arg(long)]
-	pub_first: Option<bool>,
+	conventional_prefix: Option<bool>,
 
-	/// Check for //IGNORED_ERROR comments on unwrap_or/unwrap_or_default/unwrap_or_else and `let _ = ...` [default: true]
+	/// Require an issue reference at the end of the message [default: false]
 	#[arg(long)]
-	ignored_error_comment: Option<bool>,
+	issue_reference: Option<bool>,
 }
 fn main() {
 	v_utils::clientside!();
-	let cli = Cli::parse();
+	let cli = parse_cli();
+	logging::init(cli.verbose, cli.log_format);
+
+	let config_dir = match &cli.command {
+		Commands::Rust { mode, .. } => mode.target_dir(),
+		Commands::Ts { mode, .. } | Commands::Gha { mode, .. } | Commands::Docker { mode, .. } | Commands::Tasks { mode, .. } => mode.target_dir(),
+		Commands::Daemon { .. } | Commands::InstallHooks { .. } | Commands::Init { .. } | Commands::CommitMsg { .. } => Path::new("."),
+	};
+	let config_file = cli.config.clone().or_else(|| std::env::var_os("CODESTYLE_CONFIG").map(PathBuf::from));
+	let loaded = match config_file {
+		Some(path) => Config::load_file(config_dir, &path),
+		None => Config::load(config_dir),
+	};
+	let config = match loaded {
+		Ok(config) => config,
+		Err(e) => {
+			eprintln!("codestyle: invalid config: {e}");
+			std::process::exit(1);
+		}
+	};
 
 	let exit_code = match cli.command {
-		Commands::Rust { mode, options } => {
-			let opts: RustCheckOptions = options.into();
+		Commands::Rust {
+			mode,
+			options,
+			exclude,
+			include_nested,
+		} => {
+			let opts = |dir: &Path| options.resolve(&config.for_dir(dir));
+			let rust_config = config.section("rust");
+			let discovery = DiscoveryOptions {
+				exclude: [rust_config.get_list("exclude").unwrap_or_default(), exclude].concat(),
+				generated: rust_config.get_list("generated").unwrap_or_default(),
+				include_nested: include_nested || rust_config.get::<bool>("include_nested").unwrap_or(false),
+			};
+			match mode {
+				RustMode::Assert {
+					paths,
+					format,
+					baseline,
+					write_baseline,
+					changed,
+					no_cache,
+					fail,
+				} => rust_checks::run_assert(&paths, &opts, &AssertOptions {
+					format,
+					baseline,
+					write_baseline,
+					changed,
+					discovery,
+					no_cache,
+					fail,
+				}),
+				RustMode::Format { paths, .. } if matches!(paths.as_slice(), [path] if path == Path::new(STDIN)) => rust_checks::run_format_stdin(&opts),
+				RustMode::Format { paths, dry_run } => rust_checks::run_format(&paths, &opts, &FormatOptions { dry_run, discovery }),
+				RustMode::Organize { target_dir } => rust_checks::organize::run_organize(&target_dir, &discovery),
+				RustMode::Stats { target_dir, format } => rust_checks::stats::run_stats(&target_dir, &discovery, format),
+				RustMode::ListRules { format } => rust_checks::rules::run_list_rules(matches!(format, OutputFormat::Json)),
+				RustMode::Explain { rule } => rust_checks::rules::run_explain(&rule),
+			}
+		}
+		Commands::Ts { mode, options } => {
+			let opts = options.resolve(&config);
+			match mode {
+				CheckMode::Assert { target_dir, format, fail } => ts_checks::run_assert(&target_dir, &opts, format, fail),
+				CheckMode::Format { target_dir } => ts_checks::run_format(&target_dir, &opts),
+			}
+		}
+		Commands::Gha { mode, options } => {
+			let opts = options.resolve(&config);
+			match mode {
+				CheckMode::Assert { target_dir, format, fail } => gha_checks::run_assert(&target_dir, &opts, format, fail),
+				CheckMode::Format { target_dir } => gha_checks::run_format(&target_dir, &opts),
+			}
+		}
+		Commands::Docker { mode, options } => {
+			let opts = options.resolve(&config);
+			match mode {
+				CheckMode::Assert { target_dir, format, fail } => docker_checks::run_assert(&target_dir, &opts, format, fail),
+				CheckMode::Format { target_dir } => docker_checks::run_format(&target_dir, &opts),
+			}
+		}
+		Commands::Tasks { mode, options } => {
+			let opts = options.resolve(&config);
 			match mode {
-				RustMode::Assert { target_dir } => rust_checks::run_assert(&target_dir, &opts),
-				RustMode::Format { target_dir } => rust_checks::run_format(&target_dir, &opts),
+				CheckMode::Assert { target_dir, format, fail } => task_checks::run_assert(&target_dir, &opts, format, fail),
+				CheckMode::Format { target_dir } => task_checks::run_format(&target_dir, &opts),
 			}
 		}
+		Commands::Daemon { socket, options } => rust_checks::daemon::run_daemon(|dir: &Path| options.resolve(&config.for_dir(dir)), socket.as_deref()),
+		Commands::InstallHooks { hook, uninstall, force } => hooks::run_install_hooks(Path::new("."), hook, uninstall, force),
+		Commands::Init { target_dir, preset, force } => init::run_init(&target_dir, preset, &init_sections(preset), force),
+		Commands::CommitMsg { file, options } => commit_msg_checks::run_assert(&file, &options.resolve(&config)),
 	};
 
 	std::process::exit(exit_code);
 }
+mod commit_msg_checks;
+mod config;
+mod docker_checks;
+mod gha_checks;
+mod hooks;
+mod init;
+mod logging;
 mod rust_checks;
+mod task_checks;
+mod ts_checks;
 
-use rust_checks::RustCheckOptions;
+use commit_msg_checks::CommitMsgOptions;
+use config::{Config, Profile};
+use docker_checks::DockerCheckOptions;
+use gha_checks::GhaCheckOptions;
+use hooks::Hook;
+use init::TemplateSection;
+use logging::LogFormat;
+use rust_checks::{AssertOptions, FailPolicy, FormatOptions, OutputFormat, RustCheckOptions, Severity, exclude::DiscoveryOptions, overrides::Overrides};
+use task_checks::TaskCheckOptions;
+use ts_checks::TsCheckOptions;
 
-impl From<RustCheckOptionsArgs> for RustCheckOptions {
-	fn from(args: RustCheckOptionsArgs) -> Self {
-		let d = RustCheckOptions::default();
-		macro_rules! or_default {
+impl RustCheckOptionsArgs {
+	fn resolve(&self, config: &Config) -> RustCheckOptions {
+		let d = match config.profile {
+			Profile::Default => RustCheckOptions::default(),
+			Profile::Strict => RustCheckOptions::strict(),
+		};
+		let section = config.section("rust");
+		macro_rules! resolve {
 			($($field:ident),+ $(,)?) => {
-				Self { $($field: args.$field.unwrap_or(d.$field)),+ }
+				RustCheckOptions {
+					$($field: self.$field.or_else(|| section.get(stringify!($field))).unwrap_or(d.$field),)+
+					plugins: section.get_list("plugins").unwrap_or_default(),
+					custom_rules: [
+						section.get_deserialized("custom_rules").unwrap_or_default(),
+						rust_checks::custom_rules::banned(section.get_deserialized("banned").unwrap_or_default()),
+					]
+					.concat(),
+					test_exempt: section.get_list("test_exempt").unwrap_or_default(),
+					unwrap_marker: section.get("unwrap_marker").unwrap_or(d.unwrap_marker),
+					expect_message_min_len: section.get("expect_message_min_len").unwrap_or(d.expect_message_min_len),
+					no_panic_allow: section.get_list("no_panic_allow").unwrap_or_default(),
+					no_assert_marker: section.get("no_assert_marker").unwrap_or_default(),
+					todo_issue_pattern: section.get("todo_issue_pattern").unwrap_or(d.todo_issue_pattern),
+					todo_comment_markers: section.get_list("todo_comment_markers").unwrap_or(d.todo_comment_markers),
+					pub_use_modules: section.get_list("pub_use_modules").unwrap_or(d.pub_use_modules),
+					commented_code_confidence: section.get("commented_code_confidence").unwrap_or(d.commented_code_confidence),
+					nesting_depth_max: section.get("nesting_depth_max").unwrap_or(d.nesting_depth_max),
+					fn_params_max: section.get("fn_params_max").unwrap_or(d.fn_params_max),
+					bool_params_in_pub: section.get("bool_params_in_pub").unwrap_or(d.bool_params_in_pub),
+					mod_style_prefer: section.get("mod_style_prefer").unwrap_or(d.mod_style_prefer),
+					no_blocking_in_async_paths: section.get_list("no_blocking_in_async_paths").unwrap_or_default(),
+					no_other_runtimes_crates: section.get_list("no_other_runtimes_crates").unwrap_or_default(),
+					overrides: rust_overrides(config, &[$((stringify!($field), self.$field.is_some())),+]),
+				}
 			};
 		}
-		or_default!(
+		resolve!(
 			cargo_dep_ordering,
 			instrument,
 			loops,
+			safety_comment,
 			join_split_impls,
 			impl_folds,
 			impl_follows_type,
+			trait_impls_follow,
 			embed_simple_vars,
 			insta_inline_snapshot,
 			no_chrono,
 			no_tokio_spawn,
+			no_dbg,
 			use_bail,
 			test_fn_prefix,
+			test_in_cfg_test,
 			pub_first,
+			tests_last,
 			ignored_error_comment,
+			no_unwrap,
+			expect_message,
+			no_panic,
+			allow_reason,
+			missing_docs,
+			derive_order,
+			nesting_depth,
+			fn_params,
+			bool_params,
+			no_as_cast,
+			let_else,
+			use_matches,
+			mod_style,
+			no_lock_across_await,
+			no_blocking_in_async,
+			no_block_on_in_async,
+			no_async_trait,
+			no_lazy_static,
+			no_once_cell,
+			no_other_runtimes,
+			reqwest_timeout,
+			use_thiserror,
+			derive_debug,
+			derive_default,
+			pub_fields_first,
+			mod_files,
+			impl_method_order,
+			no_get_prefix,
+			should_panic_expected,
+			no_assert,
+			todo_issue,
+			todo_comment,
+			commented_code,
+			no_extern_crate,
+			no_macro_use,
+			pub_use_location,
+			unused_pub,
+			dead_files,
+			skip_reason,
+			unused_skip,
 		)
 	}
 }
+
+/// `[override."<glob>"]` sections, for the rule options in `options` as `(option, whether a flag set it)`.
+/// Flags beat the config file, overrides included, so flagged options aren't overridden.
+fn rust_overrides(config: &Config, options: &[(&str, bool)]) -> Overrides {
+	let overrides = config.overrides().into_iter().map(|(glob, section)| {
+		let severities = options
+			.iter()
+			.filter(|&&(_, flagged)| !flagged)
+			.filter_map(|&(option, _)| Some((option.to_string(), section.get::<Severity>(option)?)))
+			.collect();
+		(glob.to_string(), severities)
+	});
+	Overrides::new(config.root().unwrap_or(Path::new(".")), overrides)
+}
+
+impl TsCheckOptionsArgs {
+	fn resolve(&self, config: &Config) -> TsCheckOptions {
+		let d = TsCheckOptions::default();
+		let section = config.section("ts");
+		macro_rules! resolve {
+			($($field:ident),+ $(,)?) => {
+				TsCheckOptions { $($field: self.$field.or_else(|| section.get(stringify!($field))).unwrap_or(d.$field)),+ }
+			};
+		}
+		resolve!(no_any_cast, no_console_log, void_promise)
+	}
+}
+
+impl GhaCheckOptionsArgs {
+	fn resolve(&self, config: &Config) -> GhaCheckOptions {
+		let d = GhaCheckOptions::default();
+		let section = config.section("gha");
+		macro_rules! resolve {
+			($($field:ident),+ $(,)?) => {
+				GhaCheckOptions { $($field: self.$field.or_else(|| section.get(stringify!($field))).unwrap_or(d.$field)),+ }
+			};
+		}
+		resolve!(pinned_actions, workflow_permissions, job_timeout, allow_network)
+	}
+}
+
+impl DockerCheckOptionsArgs {
+	fn resolve(&self, config: &Config) -> DockerCheckOptions {
+		let d = DockerCheckOptions::default();
+		let section = config.section("docker");
+		macro_rules! resolve {
+			($($field:ident),+ $(,)?) => {
+				DockerCheckOptions { $($field: self.$field.or_else(|| section.get(stringify!($field))).unwrap_or(d.$field)),+ }
+			};
+		}
+		resolve!(pinned_base_image, non_root_user, prefer_copy)
+	}
+}
+
+impl TaskCheckOptionsArgs {
+	fn resolve(&self, config: &Config) -> TaskCheckOptions {
+		let d = TaskCheckOptions::default();
+		let section = config.section("tasks");
+		macro_rules! resolve {
+			($($field:ident),+ $(,)?) => {
+				TaskCheckOptions { $($field: self.$field.or_else(|| section.get(stringify!($field))).unwrap_or(d.$field)),+ }
+			};
+		}
+		resolve!(default_recipe, duplicate_recipe_body, phony_targets)
+	}
+}
+
+impl CommitMsgOptionsArgs {
+	fn resolve(&self, config: &Config) -> CommitMsgOptions {
+		let d = match config.profile {
+			Profile::Default => CommitMsgOptions::default(),
+			Profile::Strict => CommitMsgOptions::strict(),
+		};
+		let section = config.section("commit_msg");
+		macro_rules! resolve {
+			($($field:ident),+ $(,)?) => {
+				CommitMsgOptions { $($field: self.$field.or_else(|| section.get(stringify!($field))).unwrap_or(d.$field)),+ }
+			};
+		}
+		resolve!(max_subject_len, imperative_mood, conventional_prefix, issue_reference)
+	}
+}
+
+/// `init` template sections for every subcommand but `rust`, from their flags' help, which documents each default.
+fn init_sections(preset: Profile) -> Vec<TemplateSection> {
+	let mut sections = vec![
+		template_section::<TsCheckOptionsArgs>("ts"),
+		template_section::<GhaCheckOptionsArgs>("gha"),
+		template_section::<DockerCheckOptionsArgs>("docker"),
+		template_section::<TaskCheckOptionsArgs>("tasks"),
+		template_section::<CommitMsgOptionsArgs>("commit_msg"),
+	];
+	// The only non-Rust option the strict profile changes
+	if preset == Profile::Strict {
+		let commit_msg = sections.last_mut().expect("just listed");
+		for (key, default, _) in &mut commit_msg.options {
+			if key == "issue_reference" {
+				*default = CommitMsgOptions::strict().issue_reference.to_string();
+			}
+		}
+	}
+	sections
+}
+
+fn template_section<A: Args>(name: &'static str) -> TemplateSection {
+	let command = A::augment_args(clap::Command::new(name));
+	let options = command
+		.get_arguments()
+		.filter_map(|arg| {
+			let help = arg.get_help()?.to_string();
+			let (description, default) = help.rsplit_once(" [default: ")?;
+			Some((arg.get_id().to_string(), default.trim_end_matches(']').to_string(), description.to_string()))
+		})
+		.collect();
+	TemplateSection { name, options }
+}
+
+/// Parse the command line, accounting for being run as `cargo codestyle`: cargo passes the subcommand name along,
+/// and paths left out default to the workspace root rather than being required.
+fn parse_cli() -> Cli {
+	let mut args: Vec<OsString> = std::env::args_os().collect();
+	let is_cargo_subcommand = args.first().and_then(|a| Path::new(a).file_stem()).is_some_and(|stem| stem == "cargo-codestyle") && args.get(1).is_some_and(|a| a == CARGO_SUBCOMMAND);
+	if !is_cargo_subcommand {
+		return Cli::parse();
+	}
+	args.remove(1);
+
+	let mut command = Cli::command().bin_name("cargo codestyle");
+	if let Some(root) = cargo_workspace_root() {
+		command = default_target_dir(command, &root.display().to_string());
+	}
+	let matches = command.get_matches_from(args);
+	Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit())
+}
+
+/// Make every target argument in `command` and its subcommands optional, defaulting to `dir`.
+fn default_target_dir(command: clap::Command, dir: &str) -> clap::Command {
+	let command = TARGET_ARGS.iter().fold(command, |command, &id| {
+		if command.get_arguments().any(|arg| arg.get_id() == id) {
+			command.mut_arg(id, |arg| arg.required(false).default_value(dir.to_owned()))
+		} else {
+			command
+		}
+	});
+	let subcommands: Vec<String> = command.get_subcommands().map(|c| c.get_name().to_owned()).collect();
+	subcommands.iter().fold(command, |command, name| command.mut_subcommand(name, |sub| default_target_dir(sub, dir)))
+}
+
+/// Directory of the workspace's root `Cargo.toml`, as cargo itself resolves it from the current directory.
+fn cargo_workspace_root() -> Option<PathBuf> {
+	let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+	let output = Command::new(cargo).args(["locate-project", "--workspace", "--message-format", "plain"]).output().ok()?;
+	if !output.status.success() {
+		return None;
+	}
+	let manifest = PathBuf::from(String::from_utf8(output.stdout).ok()?.trim());
+	manifest.parent().map(Path::to_path_buf)
+}
@@ -0,0 +1,116 @@
+//! Lint to require justifications on lint suppressions, the compiler-level counterpart of [`super::skip_reason`].
+//!
+//! An `#[allow(..)]` or `#[expect(..)]` outlives the reason it was added for unless the reason is written down, either
+//! as `reason = ".."` in the attribute itself, or as a `//` comment on its line or above it (other attributes of the
+//! same item in between are fine). Doc comments don't count: they describe the item, not the suppression.
+
+use std::path::Path;
+
+use syn::{Attribute, Meta, Token, punctuated::Punctuated, spanned::Spanned, visit::Visit};
+
+use super::{
+	Severity, Violation,
+	rules::{Context, Rule, RuleInfo},
+	skip::{self, SkipVisitor},
+};
+
+const RULE: &str = "allow-reason";
+/// Attributes suppressing lints
+const SUPPRESSING: &[&str] = &["allow", "expect"];
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "allow_reason",
+	autofix: false,
+	triggers: &["allow(", "expect("],
+	description: "Require a reason on #[allow] and #[expect] attributes",
+	rationale: "A lint suppression without a reason can't be told apart from a forgotten one, so nobody dares to remove it.",
+	bad: "#[allow(clippy::too_many_arguments)]\nfn render(a: u8, b: u8, c: u8, d: u8, e: u8, f: u8, g: u8, h: u8) {}",
+	good: "#[allow(clippy::too_many_arguments, reason = \"mirrors the shader's uniforms one to one\")]\nfn render(a: u8, b: u8, c: u8, d: u8, e: u8, f: u8, g: u8, h: u8) {}",
+	fix: "Add `reason = \"..\"` to the attribute, or a `//` comment above it saying why the lint doesn't apply.",
+};
+
+pub struct AllowReason;
+impl Rule for AllowReason {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+	let mut visitor = AllowVisitor {
+		path_str: path.display().to_string(),
+		lines: content.lines().collect(),
+		violations: Vec::new(),
+	};
+	// `SkipVisitor` only hands on the items, not the file's own `#![..]` attributes
+	for attr in &file.attrs {
+		if !skip::has_skip_marker_for_rule_at_line(content, attr.pound_token.span.start().line, RULE) {
+			visitor.visit_attribute(attr);
+		}
+	}
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+struct AllowVisitor<'a> {
+	path_str: String,
+	lines: Vec<&'a str>,
+	violations: Vec<Violation>,
+}
+
+impl AllowVisitor<'_> {
+	/// A `//` comment on `line`, or above it with only comments and attributes in between.
+	fn has_comment(&self, line: usize) -> bool {
+		let is_comment = |text: &str| {
+			let text = text.trim_start();
+			text.starts_with("//") && !text.starts_with("///") && !text.starts_with("//!")
+		};
+		let Some(text) = line.checked_sub(1).and_then(|i| self.lines.get(i)) else {
+			return false;
+		};
+		// A trailing comment, `#[allow(dead_code)] // kept for the FFI layout`
+		if text.split_once(']').is_some_and(|(_, rest)| rest.contains("//")) {
+			return true;
+		}
+		self.lines[..line - 1]
+			.iter()
+			.rev()
+			.take_while(|text| is_comment(text) || text.trim_start().starts_with("#[") || text.trim_start().starts_with("#!["))
+			.any(|text| is_comment(text))
+	}
+}
+
+impl<'a> Visit<'a> for AllowVisitor<'a> {
+	fn visit_attribute(&mut self, node: &'a Attribute) {
+		let Meta::List(list) = &node.meta else {
+			return;
+		};
+		let Some(kind) = list.path.get_ident().map(ToString::to_string).filter(|name| SUPPRESSING.contains(&name.as_str())) else {
+			return;
+		};
+		// Unparseable arguments are the compiler's to complain about
+		let Ok(args) = list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated) else {
+			return;
+		};
+		let has_reason = args.iter().any(|arg| matches!(arg, Meta::NameValue(nv) if nv.path.is_ident("reason")));
+		let start = node.pound_token.span.start();
+		if !has_reason && !self.has_comment(start.line) {
+			let end = node.span().end();
+			self.violations.push(Violation {
+				rule: RULE,
+				file: self.path_str.clone(),
+				line: start.line,
+				column: start.column,
+				end: Some((end.line, end.column)),
+				message: format!("`#[{kind}(..)]` without a reason\nHINT: add `reason = \"..\"`, or a `//` comment above it saying why the lint doesn't apply"),
+				severity: Severity::Error,
+				..Default::default()
+			});
+		}
+	}
+}
@@ -0,0 +1,86 @@
+//! Baselines: a recorded set of known violations that `assert` tolerates, so only new ones fail.
+//!
+//! Violations are matched by fingerprint (rule, file, and the trimmed text of the offending line) rather than by position,
+//! so code moving up or down doesn't invalidate the baseline. Each entry excuses one occurrence.
+
+use std::{
+	collections::{BTreeMap, HashMap},
+	fs,
+	path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Violation, cache::fnv1a};
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct Baseline {
+	violations: Vec<Entry>,
+}
+impl Baseline {
+	/// Record `violations`, with file paths relative to `root` so the baseline works from any checkout.
+	pub fn from_violations(violations: &[Violation], root: &Path) -> Self {
+		let mut sources = Sources::default();
+		let mut violations: Vec<Entry> = violations.iter().map(|v| sources.entry(v, root)).collect();
+		violations.sort();
+		Self { violations }
+	}
+
+	pub fn load(path: &Path) -> Result<Self, String> {
+		let content = fs::read_to_string(path).map_err(|e| format!("failed to read baseline {path:?}: {e}"))?;
+		serde_json::from_str(&content).map_err(|e| format!("invalid baseline {path:?}: {e}"))
+	}
+
+	pub fn save(&self, path: &Path) -> Result<(), String> {
+		let json = serde_json::to_string_pretty(self).expect("baseline is always serializable");
+		fs::write(path, json + "\n").map_err(|e| format!("failed to write baseline {path:?}: {e}"))
+	}
+
+	/// Drop the violations this baseline covers. Also returns how many entries went unused, i.e. violations since fixed.
+	pub fn filter(&self, violations: Vec<Violation>, root: &Path) -> (Vec<Violation>, usize) {
+		let mut remaining: BTreeMap<&Entry, usize> = BTreeMap::new();
+		for entry in &self.violations {
+			*remaining.entry(entry).or_default() += 1;
+		}
+
+		let mut sources = Sources::default();
+		let new = violations
+			.into_iter()
+			.filter(|v| match remaining.get_mut(&sources.entry(v, root)) {
+				Some(count) if *count > 0 => {
+					*count -= 1;
+					false
+				}
+				_ => true,
+			})
+			.collect();
+		(new, remaining.values().sum())
+	}
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+struct Entry {
+	rule: String,
+	file: String,
+	fingerprint: String,
+}
+
+/// File contents, read once per file, for fingerprinting.
+#[derive(Default)]
+struct Sources(HashMap<String, Option<String>>);
+impl Sources {
+	fn entry(&mut self, v: &Violation, root: &Path) -> Entry {
+		let source = self.0.entry(v.file.clone()).or_insert_with(|| fs::read_to_string(&v.file).ok());
+		let line = source.as_deref().and_then(|s| s.lines().nth(v.line.saturating_sub(1))).unwrap_or_default();
+		let file = Path::new(&v.file).strip_prefix(root).map_or_else(|_| v.file.clone(), |p| p.display().to_string());
+		Entry {
+			rule: v.rule.to_string(),
+			file: file.replace('\\', "/"),
+			fingerprint: fingerprint(v.rule, line.trim()),
+		}
+	}
+}
+
+fn fingerprint(rule: &str, line: &str) -> String {
+	format!("{:016x}", fnv1a(&[rule.as_bytes(), line.as_bytes()]))
+}
@@ -0,0 +1,137 @@
+//! Lint to discourage bare `bool` parameters.
+//!
+//! `connect(true, false)` says nothing at the call site; `connect(Tls::On, Retry::Never)` does. Functions taking more
+//! than one `bool` are flagged, and with the `bool_params_in_pub` option (on by default) public ones are flagged for
+//! any `bool` at all, since their callers are the furthest from the definition. Methods of trait impls are exempt,
+//! their signature is the trait's.
+
+use std::path::Path;
+
+use syn::{FnArg, ImplItem, ImplItemFn, ItemFn, ItemImpl, ItemTrait, Pat, Signature, TraitItem, Type, Visibility, visit::Visit};
+
+use super::{
+	Severity, Violation,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
+
+const RULE: &str = "bool-params";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "bool_params",
+	autofix: false,
+	triggers: &["bool"],
+	description: "Disallow several bool parameters, or any in public fns",
+	rationale: "A `true` at the call site doesn't say what it turns on; a two-variant enum does, and can't be passed in the wrong position.",
+	bad: "pub fn connect(host: &str, tls: bool, retry: bool) {}\n\nconnect(\"db\", true, false);",
+	good: "pub enum Tls {\n\tOn,\n\tOff,\n}\npub enum Retry {\n\tBackoff,\n\tNever,\n}\npub fn connect(host: &str, tls: Tls, retry: Retry) {}\n\nconnect(\"db\", Tls::On, Retry::Never);",
+	fix: "Replace the `bool` with a two-variant enum named after what it controls.",
+};
+
+pub struct BoolParams;
+impl Rule for BoolParams {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree, ctx.opts.bool_params_in_pub)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File, in_pub: bool) -> Vec<Violation> {
+	let visitor = BoolVisitor {
+		path_str: path.display().to_string(),
+		in_pub,
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+struct BoolVisitor {
+	path_str: String,
+	in_pub: bool,
+	violations: Vec<Violation>,
+}
+
+impl BoolVisitor {
+	fn check_sig(&mut self, sig: &Signature, is_pub: bool) {
+		let bools: Vec<String> = sig
+			.inputs
+			.iter()
+			.filter_map(|arg| match arg {
+				FnArg::Typed(typed) if is_bool(&typed.ty) => Some(match &*typed.pat {
+					Pat::Ident(ident) => format!("`{}`", ident.ident),
+					_ => "`_`".to_string(),
+				}),
+				_ => None,
+			})
+			.collect();
+		let message = match bools.len() {
+			0 => return,
+			1 if !(is_pub && self.in_pub) => return,
+			1 => format!("public `{}` takes a `bool` parameter, {}", sig.ident, bools[0]),
+			n => format!("`{}` takes {n} `bool` parameters, {}", sig.ident, bools.join(", ")),
+		};
+		let start = sig.ident.span().start();
+		let end = sig.ident.span().end();
+		self.violations.push(Violation {
+			rule: RULE,
+			file: self.path_str.clone(),
+			line: start.line,
+			column: start.column,
+			end: Some((end.line, end.column)),
+			message: format!("{message}\nHINT: use a two-variant enum, so call sites say what they mean"),
+			severity: Severity::Error,
+			..Default::default()
+		});
+	}
+}
+
+impl<'a> Visit<'a> for BoolVisitor {
+	fn visit_item_fn(&mut self, node: &'a ItemFn) {
+		self.check_sig(&node.sig, is_public(&node.vis));
+		syn::visit::visit_item_fn(self, node);
+	}
+
+	fn visit_item_impl(&mut self, node: &'a ItemImpl) {
+		if node.trait_.is_some() {
+			// The trait's signatures aren't this impl's to change, but fns nested in the bodies are
+			for item in &node.items {
+				match item {
+					ImplItem::Fn(method) => self.visit_block(&method.block),
+					item => self.visit_impl_item(item),
+				}
+			}
+			return;
+		}
+		syn::visit::visit_item_impl(self, node);
+	}
+
+	fn visit_impl_item_fn(&mut self, node: &'a ImplItemFn) {
+		self.check_sig(&node.sig, is_public(&node.vis));
+		syn::visit::visit_impl_item_fn(self, node);
+	}
+
+	fn visit_item_trait(&mut self, node: &'a ItemTrait) {
+		// Trait methods are as public as the trait
+		for item in &node.items {
+			if let TraitItem::Fn(method) = item {
+				self.check_sig(&method.sig, is_public(&node.vis));
+				if let Some(body) = &method.default {
+					self.visit_block(body);
+				}
+			}
+		}
+	}
+}
+
+fn is_public(vis: &Visibility) -> bool {
+	matches!(vis, Visibility::Public(_))
+}
+
+fn is_bool(ty: &Type) -> bool {
+	matches!(ty, Type::Path(path) if path.qself.is_none() && path.path.is_ident("bool"))
+}
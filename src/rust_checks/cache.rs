@@ -0,0 +1,125 @@
+//! Per-file result cache for `assert`, under `target/codestyle-cache`, so repeat runs on an unchanged tree skip parsing.
+//!
+//! Entries are keyed by a hash of everything a file's violations depend on: its path and contents, the options and
+//! `rustfmt.toml` it's checked with, the plugin files those options run, and the codestyle build; for a file with a skip
+//! marker's `until` in it, also the date, so a marker expiring is a miss too. Any change is just a miss, so entries never
+//! need invalidating; `cargo clean` or deleting the directory clears them. A file checked by an enabled rule that reads
+//! other files, like `mod_files`, isn't cached at all, as those could change with the file itself unchanged.
+
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Fix, Label, RustCheckOptions, Severity, Suggestion, Violation, plugins, rules, rustfmt::RustfmtConfig};
+
+/// Part of every key, so entries written by another codestyle build are never reused
+const BUILD: &str = concat!(env!("CARGO_PKG_VERSION"), "-", env!("GIT_HASH"));
+
+pub struct Cache {
+	dir: PathBuf,
+}
+impl Cache {
+	/// The cache for a run rooted at `root`.
+	pub fn new(root: &Path) -> Self {
+		Self {
+			dir: root.join("target").join("codestyle-cache"),
+		}
+	}
+
+	/// Key of `path`'s results, as checked on `today`, which is [`super::skip::today`] outside of tests.
+	pub fn key(path: &Path, contents: &str, opts: &RustCheckOptions, rustfmt: &RustfmtConfig, today: (i64, u32, u32)) -> String {
+		// Whether a marker with an `until` still skips depends on the date, so its file's results do too
+		let date = if contents.contains("until") { format!("{today:?}") } else { String::new() };
+		let settings = format!("{BUILD}\0{opts:?}\0{rustfmt:?}\0{}\0{date}", plugins::fingerprint(&opts.plugins));
+		let path = path.to_string_lossy();
+		format!("{:016x}", fnv1a(&[path.as_bytes(), contents.as_bytes(), settings.as_bytes()]))
+	}
+
+	/// Violations recorded under `key`, if any. Unreadable or stale-format entries are misses.
+	pub fn get(&self, key: &str) -> Option<Vec<Violation>> {
+		let content = fs::read_to_string(self.entry_path(key)).ok()?;
+		let entries: Vec<Entry> = serde_json::from_str(&content).ok()?;
+		entries.into_iter().map(Entry::into_violation).collect()
+	}
+
+	/// Record `violations` under `key`. Failing to write only costs the next run a miss, so errors are ignored.
+	pub fn put(&self, key: &str, violations: &[Violation]) {
+		let entries: Vec<Entry> = violations.iter().map(Entry::from).collect();
+		let json = serde_json::to_string(&entries).expect("violations are always serializable");
+		//IGNORED_ERROR: a read-only or missing target dir just means no caching
+		let _ = fs::create_dir_all(&self.dir).and_then(|()| fs::write(self.entry_path(key), json));
+	}
+
+	fn entry_path(&self, key: &str) -> PathBuf {
+		self.dir.join(format!("{key}.json"))
+	}
+}
+
+/// FNV-1a over `parts`, separated so that moving bytes between parts changes the hash.
+/// Unlike `DefaultHasher`, it's guaranteed to stay the same across Rust versions.
+pub fn fnv1a(parts: &[&[u8]]) -> u64 {
+	let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+	for (i, part) in parts.iter().enumerate() {
+		let separator: &[u8] = if i == 0 { &[] } else { &[0] };
+		for &byte in separator.iter().chain(part.iter()) {
+			hash ^= u64::from(byte);
+			hash = hash.wrapping_mul(0x0100_0000_01b3);
+		}
+	}
+	hash
+}
+
+/// A cached violation. Its fix isn't kept, since `assert` only reports whether there is one; suggestions are only ever shown, so they are.
+#[derive(Deserialize, Serialize)]
+struct Entry {
+	rule: String,
+	file: String,
+	line: usize,
+	column: usize,
+	end: Option<(usize, usize)>,
+	message: String,
+	severity: String,
+	fixable: bool,
+	labels: Vec<Label>,
+	notes: Vec<String>,
+	suggestions: Vec<Suggestion>,
+}
+impl Entry {
+	fn into_violation(self) -> Option<Violation> {
+		Some(Violation {
+			rule: rules::find(&self.rule)?.id,
+			file: self.file,
+			line: self.line,
+			column: self.column,
+			end: self.end,
+			message: self.message,
+			severity: self.severity.parse::<Severity>().ok()?,
+			// Only its presence matters to `assert`, which never applies fixes, or moves files
+			fix: self.fixable.then(Fix::default),
+			rename: None,
+			labels: self.labels,
+			notes: self.notes,
+			suggestions: self.suggestions,
+		})
+	}
+}
+impl From<&Violation> for Entry {
+	fn from(v: &Violation) -> Self {
+		Self {
+			rule: v.rule.to_string(),
+			file: v.file.clone(),
+			line: v.line,
+			column: v.column,
+			end: v.end,
+			message: v.message.clone(),
+			severity: v.severity.to_string(),
+			fixable: v.is_fixable(),
+			labels: v.labels.clone(),
+			notes: v.notes.clone(),
+			suggestions: v.suggestions.clone(),
+		}
+	}
+}
@@ -1,8 +1,19 @@
 use std::path::Path;
 
-use super::{Fix, Violation};
+use super::{Fix, Severity, Violation, rules::RuleInfo};
 
 const RULE: &str = "cargo-dep-ordering";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "cargo_dep_ordering",
+	autofix: true,
+	triggers: &[],
+	description: "Order and group dependencies in Cargo.toml",
+	rationale: "Grouping path, registry and workspace dependencies and sorting each group keeps Cargo.toml diffs small and makes it obvious where a new dependency goes.",
+	bad: "[dependencies]\nserde = \"1\"\nanyhow = \"1\"",
+	good: "[dependencies]\nanyhow = \"1\"\nserde = \"1\"",
+	fix: "Rewrites each dependency section in order: path dependencies, then registry ones, then workspace ones, each group sorted by name.",
+};
 
 /// Sections we care about (but NOT [patch.crates-io] etc.)
 const DEP_SECTIONS: &[&str] = &["[dependencies]", "[dev-dependencies]", "[build-dependencies]"];
@@ -80,11 +91,13 @@ fn check_section(content: &str, section_header: &str, path_str: &str) -> Option<
 		line,
 		column: 1,
 		message: format!("Dependencies in {section_header} are not properly grouped/ordered"),
+		severity: Severity::Error,
 		fix: Some(Fix {
 			start_byte: section_body_start,
 			end_byte: deps_end,
 			replacement,
 		}),
+		..Default::default()
 	})
 }
 
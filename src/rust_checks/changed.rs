@@ -0,0 +1,29 @@
+//! `--changed <ref>`: restrict a run to the files that differ from a git ref, for fast feedback on large repos.
+
+use std::{
+	collections::HashSet,
+	path::{Path, PathBuf},
+	process::Command,
+};
+
+#[derive(Debug, Default)]
+pub struct ChangedFiles(HashSet<PathBuf>);
+impl ChangedFiles {
+	/// Files under `dir` changed in the working tree since it forked from `git_ref`, plus untracked ones.
+	pub fn since(dir: &Path, git_ref: &str) -> Result<Self, String> {
+		let mut files = HashSet::new();
+		for args in [&["diff", "--name-only", "--relative", "--merge-base", git_ref, "--"][..], &["ls-files", "--others", "--exclude-standard"]] {
+			let output = Command::new("git").args(args).current_dir(dir).output().map_err(|e| format!("failed to run git: {e}"))?;
+			if !output.status.success() {
+				return Err(format!("`git {}` failed: {}", args.join(" "), String::from_utf8_lossy(&output.stderr).trim()));
+			}
+			// Deleted files don't canonicalize, and there's nothing left in them to check
+			files.extend(String::from_utf8_lossy(&output.stdout).lines().filter_map(|line| dir.join(line).canonicalize().ok()));
+		}
+		Ok(Self(files))
+	}
+
+	pub fn contains(&self, path: &Path) -> bool {
+		path.canonicalize().is_ok_and(|path| self.0.contains(&path))
+	}
+}
@@ -0,0 +1,126 @@
+//! Lint to flag commented-out code, which version control keeps better than a comment does.
+//!
+//! A block is a run of `//` comments, each alone on its line and on the line right under the previous one; doc
+//! comments aren't looked at. It's taken for code when its text, without the slashes, parses as the statements and
+//! items of a block, and enough of its lines look like code too: the share of them ending in `;`, `{`, `}`, `(`, `)`
+//! or `,`, or starting with a keyword like `let` or `fn`, has to reach the `commented_code_confidence` option. Parsing
+//! alone would let through prose that happens to be valid Rust, like a lone word, which the share weeds out.
+
+use std::path::Path;
+
+use super::{
+	Fix, Severity, Suggestion, Violation,
+	comments::{self, Comment},
+	line_index::LineIndex,
+	rules::{Context, Rule, RuleInfo},
+	skip::has_skip_marker_for_rule_at_line,
+};
+
+const RULE: &str = "commented-code";
+/// Share of a block's lines that have to look like code, unless `commented_code_confidence` says otherwise
+pub const DEFAULT_CONFIDENCE: f64 = 0.5;
+/// Keywords a line of code can start with
+const KEYWORDS: &[&str] = &[
+	"let ", "fn ", "pub ", "use ", "mod ", "impl ", "struct ", "enum ", "trait ", "type ", "const ", "static ", "if ", "for ", "while ", "loop ", "match ", "return", "async ", "unsafe ",
+];
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "commented_code",
+	autofix: false,
+	triggers: &["//"],
+	description: "Disallow commented-out code",
+	rationale: "Commented-out code rots unread: it isn't compiled, tested or refactored along, and version control already keeps what it was.",
+	bad: "fn total(items: &[Item]) -> u64 {\n\t// let taxed = items.iter().map(|i| i.price * 2);\n\t// log::debug!(\"{taxed:?}\");\n\titems.iter().map(|i| i.price).sum()\n}",
+	good: "fn total(items: &[Item]) -> u64 {\n\titems.iter().map(|i| i.price).sum()\n}",
+	fix: "Delete the code; if it documents an alternative, say so in prose.",
+};
+
+pub struct CommentedCode;
+impl Rule for CommentedCode {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.opts.commented_code_confidence)
+	}
+}
+
+pub fn check(path: &Path, content: &str, confidence: f64) -> Vec<Violation> {
+	let lines = LineIndex::new(content);
+	let mut violations = Vec::new();
+	for block in blocks(&lines, &comments::comments(content)) {
+		let texts: Vec<&str> = block.iter().map(|comment| strip_slashes(comment.text)).collect();
+		let code = texts.join("\n");
+		let non_empty: Vec<&str> = texts.iter().map(|text| text.trim()).filter(|text| !text.is_empty()).collect();
+		let code_like = non_empty.iter().filter(|text| looks_like_code(text)).count();
+		if non_empty.is_empty() || (code_like as f64) < confidence * non_empty.len() as f64 || syn::parse_str::<syn::Block>(&format!("{{{code}\n}}")).is_err() {
+			continue;
+		}
+		let (Some(first), Some(last)) = (block.first(), block.last()) else {
+			continue;
+		};
+		let (line, column) = lines.position(first.start);
+		if has_skip_marker_for_rule_at_line(content, line, RULE) {
+			continue;
+		}
+		let end = lines.position(last.start + last.text.len());
+		let start_byte = lines.line_start(first.start);
+		let end_byte = (lines.line_end(last.start) + 1).min(content.len());
+		violations.push(Violation {
+			rule: RULE,
+			file: path.display().to_string(),
+			line,
+			column,
+			end: Some(end),
+			message: "commented-out code\nHINT: delete it, version control keeps it; if it shows an alternative, say so in prose".to_string(),
+			severity: Severity::Error,
+			suggestions: vec![Suggestion {
+				message: "delete the commented-out code".to_string(),
+				fix: Fix {
+					start_byte,
+					end_byte,
+					replacement: String::new(),
+				},
+			}],
+			..Default::default()
+		});
+	}
+	violations
+}
+
+/// Runs of plain `//` comments, each alone on the line right under the previous one.
+fn blocks<'a, 'c>(lines: &LineIndex, comments: &'c [Comment<'a>]) -> Vec<&'c [Comment<'a>]> {
+	let mut blocks = Vec::new();
+	let mut start = 0;
+	let mut prev_line = None;
+	for (i, comment) in comments.iter().enumerate() {
+		let line = lines.position(comment.start).0;
+		let alone = lines.text()[lines.line_start(comment.start)..comment.start].trim().is_empty();
+		let is_plain = comment.text.starts_with("//") && !comment.text.starts_with("///") && !comment.text.starts_with("//!");
+		if !(is_plain && alone) {
+			blocks.push(&comments[start..i]);
+			start = i + 1;
+			prev_line = None;
+			continue;
+		}
+		if prev_line.is_some_and(|prev| prev + 1 != line) {
+			blocks.push(&comments[start..i]);
+			start = i;
+		}
+		prev_line = Some(line);
+	}
+	blocks.push(&comments[start..]);
+	blocks.retain(|block| !block.is_empty());
+	blocks
+}
+
+/// The comment's text without its `//` and the space after it.
+fn strip_slashes(text: &str) -> &str {
+	let text = text.trim_start_matches('/');
+	text.strip_prefix(' ').unwrap_or(text)
+}
+
+fn looks_like_code(line: &str) -> bool {
+	line.ends_with([';', '{', '}', '(', ')', ',']) || KEYWORDS.iter().any(|keyword| line.starts_with(keyword))
+}
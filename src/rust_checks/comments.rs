@@ -0,0 +1,126 @@
+//! The comments of a Rust source, which `syn` drops along with the rest of the whitespace: a lexer that knows just
+//! enough of Rust's literals to tell a `//` in a string from one that starts a comment.
+
+/// A `//` or `/* .. */` comment, doc comments included.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Comment<'a> {
+	/// Byte offset of its first `/`
+	pub start: usize,
+	/// The comment with its delimiters, without the newline ending a line comment
+	pub text: &'a str,
+}
+
+/// Every comment in `text`, in order. Block comments nest, as in Rust; an unterminated one runs to the end.
+pub fn comments(text: &str) -> Vec<Comment<'_>> {
+	let bytes = text.as_bytes();
+	let mut comments = Vec::new();
+	let mut i = 0;
+	while i < bytes.len() {
+		i = match bytes[i] {
+			b'/' if bytes.get(i + 1) == Some(&b'/') => {
+				let end = text[i..].find('\n').map_or(text.len(), |len| i + len);
+				comments.push(Comment { start: i, text: &text[i..end] });
+				end
+			}
+			b'/' if bytes.get(i + 1) == Some(&b'*') => {
+				let end = block_comment_end(bytes, i);
+				comments.push(Comment { start: i, text: &text[i..end] });
+				end
+			}
+			b'"' => string_end(bytes, i + 1),
+			b'r' if !continues_ident(bytes, i) || (i > 0 && matches!(bytes[i - 1], b'b' | b'c') && !continues_ident(bytes, i - 1)) => raw_string_end(bytes, i + 1).unwrap_or(i + 1),
+			b'\'' => char_end(text, i),
+			_ => i + 1,
+		};
+	}
+	comments
+}
+
+/// Whether the byte at `i` is part of an identifier started before it.
+fn continues_ident(bytes: &[u8], i: usize) -> bool {
+	i > 0 && (bytes[i - 1].is_ascii_alphanumeric() || bytes[i - 1] == b'_')
+}
+
+/// End of the block comment opening at `start`.
+fn block_comment_end(bytes: &[u8], start: usize) -> usize {
+	let mut depth = 0;
+	let mut i = start;
+	while i + 1 < bytes.len() {
+		match (bytes[i], bytes[i + 1]) {
+			(b'/', b'*') => {
+				depth += 1;
+				i += 2;
+			}
+			(b'*', b'/') => {
+				depth -= 1;
+				i += 2;
+				if depth == 0 {
+					return i;
+				}
+			}
+			_ => i += 1,
+		}
+	}
+	bytes.len()
+}
+
+/// End of the string literal whose contents start at `i`, past its closing quote.
+fn string_end(bytes: &[u8], mut i: usize) -> usize {
+	while i < bytes.len() {
+		match bytes[i] {
+			b'\\' => i += 2,
+			b'"' => return i + 1,
+			_ => i += 1,
+		}
+	}
+	bytes.len()
+}
+
+/// End of the raw string literal whose `#`s or opening quote are at `i`, if there's one there.
+fn raw_string_end(bytes: &[u8], i: usize) -> Option<usize> {
+	let hashes = bytes[i..].iter().take_while(|&&b| b == b'#').count();
+	if bytes.get(i + hashes) != Some(&b'"') {
+		return None;
+	}
+	let mut at = i + hashes + 1;
+	while at < bytes.len() {
+		if bytes[at] == b'"' && bytes[at + 1..].iter().take(hashes).filter(|&&b| b == b'#').count() == hashes {
+			return Some(at + 1 + hashes);
+		}
+		at += 1;
+	}
+	Some(bytes.len())
+}
+
+/// End of the char literal opening at `start`, or just past the quote if it starts a lifetime or label instead.
+fn char_end(text: &str, start: usize) -> usize {
+	let rest = &text[start + 1..];
+	match rest.chars().next() {
+		// The escaped char is ASCII, and no escape has a quote past it before the closing one
+		Some('\\') => rest.get(2..).and_then(|after| after.find('\'')).map_or(text.len(), |len| start + 3 + len + 1),
+		Some(c) if rest[c.len_utf8()..].starts_with('\'') => start + 1 + c.len_utf8() + 1,
+		_ => start + 1,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn texts(source: &str) -> Vec<&str> {
+		comments(source).into_iter().map(|comment| comment.text).collect()
+	}
+
+	#[test]
+	fn line_block_and_doc_comments() {
+		let source = "//! crate docs\n/// item docs\nfn a() {} // trailing\n/* block /* nested */ still */ fn b() {}\n";
+		assert_eq!(texts(source), ["//! crate docs", "/// item docs", "// trailing", "/* block /* nested */ still */"]);
+		assert_eq!(comments(source)[2].start, source.find("// trailing").unwrap());
+	}
+
+	#[test]
+	fn comment_delimiters_in_literals_are_not_comments() {
+		let source = "let url = \"https://example.com\"; let raw = r#\"a \" // b\"#; let c = '/'; let q = '\\''; let s = b\"//\";\nfn f<'a>(x: &'a str) {} // real\n";
+		assert_eq!(texts(source), ["// real"]);
+	}
+}
@@ -0,0 +1,291 @@
+//! Rules declared in config rather than code, for project-specific bans that don't warrant a plugin:
+//! ```toml
+//! [[rust.custom_rules]]
+//! id = "no-env-var"
+//! pattern = "std::env::var"
+//! message = "use Config instead"
+//! ```
+//! A pattern is a path (`std::env::var`, or a whole crate like `chrono`), a macro (`dbg!`) or a method (`.unwrap()`).
+//! Paths are matched as written after resolving the file's `use` imports, so `env::var` after `use std::env;` counts,
+//! and so does anything under the path, like `chrono::Utc`, along with glob imports of it. A pattern can leave out the
+//! `std`, `core` or `alloc` it's under: `mem::forget` bans `std::mem::forget` and `core::mem::forget` alike.
+//!
+//! Bans that need nothing but a message go under `[rust.banned]` instead, as `pattern = message`, see [`banned`].
+
+use std::{
+	collections::{BTreeMap, HashMap},
+	fmt,
+	path::Path,
+};
+
+use proc_macro2::Span;
+use serde::{Deserialize, Deserializer, de::Error as _};
+use syn::{ItemUse, UseTree, visit::Visit};
+
+use super::{Severity, Violation, rules::intern, skip::SkipVisitor};
+
+/// One `[[rust.custom_rules]]` entry.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomRule {
+	/// As printed in violations and used in skip markers
+	pub id: String,
+	#[serde(deserialize_with = "pattern")]
+	pub pattern: Pattern,
+	/// Why it's banned, or what to use instead
+	pub message: String,
+	#[serde(default = "default_severity", deserialize_with = "severity")]
+	pub severity: Severity,
+}
+
+#[derive(Clone, Debug)]
+pub enum Pattern {
+	/// `a::b`: any path starting with these segments
+	Path(Vec<String>),
+	/// `a::b!`: invocations of the macro; a single segment matches however the macro is qualified
+	Macro(Vec<String>),
+	/// `.name()`: calls of any method so named
+	Method(String),
+}
+impl Pattern {
+	fn parse(s: &str) -> Result<Self, String> {
+		let s = s.trim();
+		if let Some(method) = s.strip_prefix('.') {
+			let name = method.strip_suffix("()").unwrap_or(method);
+			return if is_ident(name) {
+				Ok(Pattern::Method(name.to_string()))
+			} else {
+				Err(format!("invalid method pattern `{s}`"))
+			};
+		}
+		let (path, is_macro) = match s.strip_suffix('!') {
+			Some(path) => (path, true),
+			None => (s, false),
+		};
+		let segments: Vec<String> = path.trim_start_matches("::").split("::").map(|seg| seg.trim().to_string()).collect();
+		if !segments.iter().all(|seg| is_ident(seg)) {
+			return Err(format!(
+				"invalid pattern `{s}`, expected a path like `std::env::var`, a macro like `dbg!` or a method like `.unwrap()`"
+			));
+		}
+		Ok(if is_macro { Pattern::Macro(segments) } else { Pattern::Path(segments) })
+	}
+
+	/// Whether a file without this substring can be skipped
+	fn trigger(&self) -> &str {
+		match self {
+			Pattern::Path(segments) | Pattern::Macro(segments) => segments.last().expect("patterns have at least one segment"),
+			Pattern::Method(name) => name,
+		}
+	}
+}
+impl fmt::Display for Pattern {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Pattern::Path(segments) => write!(f, "{}", segments.join("::")),
+			Pattern::Macro(segments) => write!(f, "{}!", segments.join("::")),
+			Pattern::Method(name) => write!(f, ".{name}()"),
+		}
+	}
+}
+
+/// Rule id of the `[rust.banned]` entries.
+pub const BANNED: &str = "banned";
+
+/// The `[rust.banned]` table of `pattern = message` entries, as rules reported under [`BANNED`]. Invalid patterns are
+/// left out, with a warning.
+pub fn banned(entries: BTreeMap<String, String>) -> Vec<CustomRule> {
+	entries
+		.into_iter()
+		.filter_map(|(pattern, message)| match Pattern::parse(&pattern) {
+			Ok(pattern) => Some(CustomRule {
+				id: BANNED.to_string(),
+				pattern,
+				message,
+				severity: Severity::Error,
+			}),
+			Err(e) => {
+				eprintln!("codestyle: ignoring [rust.banned] entry: {e}");
+				None
+			}
+		})
+		.collect()
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File, rules: &[CustomRule]) -> Vec<Violation> {
+	let rules: Vec<&CustomRule> = rules.iter().filter(|r| r.severity.is_enabled() && content.contains(r.pattern.trigger())).collect();
+	if rules.is_empty() {
+		return Vec::new();
+	}
+	let imports = Imports::of(file);
+
+	let mut violations = Vec::new();
+	for rule in rules {
+		let visitor = PatternVisitor {
+			rule,
+			id: intern(rule.id.clone()),
+			imports: &imports,
+			path_str: path.display().to_string(),
+			violations: Vec::new(),
+		};
+		let mut skip_visitor = SkipVisitor::for_rule(visitor, content, &rule.id);
+		skip_visitor.visit_file(file);
+		violations.extend(rule.severity.assign(skip_visitor.inner.violations));
+	}
+	violations
+}
+
+/// What each name brought in by a `use` stands for. Scopes aren't tracked: all of the file's imports apply everywhere.
+#[derive(Default)]
+pub(super) struct Imports {
+	aliases: HashMap<String, Vec<String>>,
+}
+impl Imports {
+	pub(super) fn of(file: &syn::File) -> Self {
+		let mut imports = Self::default();
+		imports.visit_file(file);
+		imports
+	}
+
+	/// `path`'s segments with a leading imported name replaced by what it was imported as.
+	pub(super) fn resolve(&self, path: &syn::Path) -> Vec<String> {
+		let mut segments = path.segments.iter().map(|s| s.ident.to_string());
+		let first = segments.next().unwrap_or_default();
+		let head = match self.aliases.get(&first) {
+			Some(full) if path.leading_colon.is_none() => full.clone(),
+			_ => vec![first],
+		};
+		head.into_iter().chain(segments).collect()
+	}
+
+	fn add(&mut self, tree: &UseTree, prefix: &[String]) {
+		let extend = |ident: &syn::Ident| prefix.iter().cloned().chain([ident.to_string()]).collect::<Vec<_>>();
+		match tree {
+			UseTree::Path(path) => self.add(&path.tree, &extend(&path.ident)),
+			UseTree::Name(name) if name.ident == "self" =>
+				if let Some(last) = prefix.last() {
+					self.aliases.insert(last.clone(), prefix.to_vec());
+				},
+			UseTree::Name(name) => {
+				self.aliases.insert(name.ident.to_string(), extend(&name.ident));
+			}
+			UseTree::Rename(rename) => {
+				let full = if rename.ident == "self" { prefix.to_vec() } else { extend(&rename.ident) };
+				self.aliases.insert(rename.rename.to_string(), full);
+			}
+			UseTree::Glob(_) => {}
+			UseTree::Group(group) =>
+				for item in &group.items {
+					self.add(item, prefix);
+				},
+		}
+	}
+}
+impl<'a> Visit<'a> for Imports {
+	fn visit_item_use(&mut self, node: &'a ItemUse) {
+		self.add(&node.tree, &[]);
+	}
+}
+
+struct PatternVisitor<'r> {
+	rule: &'r CustomRule,
+	id: &'static str,
+	imports: &'r Imports,
+	path_str: String,
+	violations: Vec<Violation>,
+}
+impl PatternVisitor<'_> {
+	fn report(&mut self, span: Span) {
+		self.violations.push(Violation {
+			rule: self.id,
+			file: self.path_str.clone(),
+			line: span.start().line,
+			column: span.start().column,
+			end: Some((span.end().line, span.end().column)),
+			message: format!("Usage of `{}` is disallowed: {}", self.rule.pattern, self.rule.message),
+			severity: Severity::Error,
+			..Default::default()
+		});
+	}
+
+	/// Glob imports are reported where they're made, since what they bring in can't be told apart afterwards.
+	fn check_use_tree(&mut self, tree: &UseTree, prefix: &mut Vec<String>) {
+		match tree {
+			UseTree::Path(path) => {
+				prefix.push(path.ident.to_string());
+				self.check_use_tree(&path.tree, prefix);
+				prefix.pop();
+			}
+			UseTree::Glob(glob) =>
+				if let Pattern::Path(pattern) = &self.rule.pattern
+					&& matches_path(prefix, pattern)
+				{
+					self.report(glob.star_token.spans[0]);
+				},
+			UseTree::Group(group) =>
+				for item in &group.items {
+					self.check_use_tree(item, prefix);
+				},
+			UseTree::Name(_) | UseTree::Rename(_) => {}
+		}
+	}
+}
+impl<'a> Visit<'a> for PatternVisitor<'_> {
+	fn visit_item_use(&mut self, node: &'a ItemUse) {
+		self.check_use_tree(&node.tree, &mut Vec::new());
+	}
+
+	fn visit_path(&mut self, node: &'a syn::Path) {
+		if let Pattern::Path(pattern) = &self.rule.pattern
+			&& matches_path(&self.imports.resolve(node), pattern)
+		{
+			self.report(node.segments.first().map_or_else(Span::call_site, |s| s.ident.span()));
+		}
+		syn::visit::visit_path(self, node);
+	}
+
+	fn visit_macro(&mut self, node: &'a syn::Macro) {
+		if let Pattern::Macro(pattern) = &self.rule.pattern {
+			let resolved = self.imports.resolve(&node.path);
+			let matches = match pattern.as_slice() {
+				[name] => resolved.last() == Some(name),
+				_ => resolved == *pattern,
+			};
+			if matches {
+				self.report(node.path.segments.first().map_or_else(Span::call_site, |s| s.ident.span()));
+			}
+		}
+		syn::visit::visit_macro(self, node);
+	}
+
+	fn visit_expr_method_call(&mut self, node: &'a syn::ExprMethodCall) {
+		if let Pattern::Method(name) = &self.rule.pattern
+			&& node.method == name
+		{
+			self.report(node.method.span());
+		}
+		syn::visit::visit_expr_method_call(self, node);
+	}
+}
+
+/// Whether `path` is `pattern` or under it, with or without the standard library crate `path` starts with.
+fn matches_path(path: &[String], pattern: &[String]) -> bool {
+	path.starts_with(pattern) || matches!(path.first().map(String::as_str), Some("std" | "core" | "alloc")) && path[1..].starts_with(pattern)
+}
+
+fn is_ident(s: &str) -> bool {
+	let mut chars = s.chars();
+	chars.next().is_some_and(|c| c.is_alphabetic() || c == '_') && chars.all(|c| c.is_alphanumeric() || c == '_') && s != "_"
+}
+
+fn pattern<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pattern, D::Error> {
+	Pattern::parse(&String::deserialize(deserializer)?).map_err(D::Error::custom)
+}
+
+fn severity<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Severity, D::Error> {
+	String::deserialize(deserializer)?.parse().map_err(D::Error::custom)
+}
+
+fn default_severity() -> Severity {
+	Severity::Error
+}
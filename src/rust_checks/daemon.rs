@@ -0,0 +1,175 @@
+//! `codestyle daemon`: a long-running process answering check and format requests, so editors don't pay startup,
+//! config and `rustfmt.toml` lookup on every keystroke.
+//!
+//! The protocol is newline-delimited JSON, over stdio or a unix socket. Each request is one line:
+//! ```json
+//! {"id": 1, "method": "check", "path": "src/lib.rs", "content": "fn main() {}"}
+//! ```
+//! `method` is `check`, `format` or `shutdown`; `content` defaults to the file on disk, and `id` is echoed back as-is.
+//! Each response is one line too: `{"id", "violations"}` for `check`, `{"id", "formatted", "violations"}` for `format`
+//! (with only the violations left to fix by hand), `{"id"}` for `shutdown`, or `{"id", "error"}` if the request failed.
+//! `format` never writes: applying the result is up to the client.
+
+use std::{
+	collections::HashMap,
+	fs,
+	io::{self, BufRead, Write},
+	path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+use syn::parse_file;
+
+use super::{CrateOptions, RustCheckOptions, Violation, ViolationJson, check_file, format_source, parse_rust_source, rustfmt::RustfmtConfig};
+
+#[derive(Debug, Deserialize)]
+struct Envelope {
+	#[serde(default)]
+	id: Value,
+	#[serde(flatten)]
+	request: Request,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+enum Request {
+	Check { path: PathBuf, content: Option<String> },
+	Format { path: PathBuf, content: Option<String> },
+	Shutdown,
+}
+
+/// State kept warm between requests.
+pub struct Daemon<O> {
+	opts: O,
+	/// Resolved options and `rustfmt.toml`, per directory
+	dirs: HashMap<PathBuf, (RustCheckOptions, RustfmtConfig)>,
+	/// Last `check` result per file, reused while its content is unchanged
+	checked: HashMap<PathBuf, (String, Vec<Violation>)>,
+	shutdown: bool,
+}
+impl<O: CrateOptions> Daemon<O> {
+	pub fn new(opts: O) -> Self {
+		Self {
+			opts,
+			dirs: HashMap::new(),
+			checked: HashMap::new(),
+			shutdown: false,
+		}
+	}
+
+	/// Whether a `shutdown` request has been answered.
+	pub fn is_shut_down(&self) -> bool {
+		self.shutdown
+	}
+
+	/// Answer one request line with one response line (without the trailing newline).
+	pub fn handle(&mut self, line: &str) -> String {
+		let Envelope { id, request } = match serde_json::from_str(line) {
+			Ok(envelope) => envelope,
+			Err(e) => return json!({ "id": Value::Null, "error": format!("invalid request: {e}") }).to_string(),
+		};
+		let response = match request {
+			Request::Check { path, content } => self.check(path, content).map(|violations| json!({ "id": id, "violations": violations })),
+			Request::Format { path, content } => self
+				.format(&path, content)
+				.map(|(formatted, violations)| json!({ "id": id, "formatted": formatted, "violations": violations })),
+			Request::Shutdown => {
+				self.shutdown = true;
+				Ok(json!({ "id": id }))
+			}
+		};
+		response.unwrap_or_else(|e| json!({ "id": id, "error": e })).to_string()
+	}
+
+	fn check(&mut self, path: PathBuf, content: Option<String>) -> Result<Vec<ViolationJson<'_>>, String> {
+		let content = read_content(&path, content)?;
+		let cached = self.checked.get(&path).is_some_and(|(c, _)| *c == content);
+		if !cached {
+			parse_file(&content).map_err(|e| format!("failed to parse {path:?}: {e}"))?;
+			let (opts, rustfmt) = self.dir_state(&path);
+			let info = parse_rust_source(path.clone(), content.clone()).expect("just parsed");
+			let violations = check_file(&info, opts, rustfmt);
+			self.checked.insert(path.clone(), (content, violations));
+		}
+		Ok(self.checked[&path].1.iter().map(ViolationJson::from).collect())
+	}
+
+	fn format(&mut self, path: &Path, content: Option<String>) -> Result<(String, Vec<Value>), String> {
+		let content = read_content(path, content)?;
+		parse_file(&content).map_err(|e| format!("failed to parse {path:?}: {e}"))?;
+		let (opts, rustfmt) = self.dir_state(path);
+		let (formatted, _, unfixable) = format_source(path, &content, opts, rustfmt);
+		let violations = unfixable.iter().map(|v| json!(ViolationJson::from(v))).collect();
+		Ok((formatted, violations))
+	}
+
+	fn dir_state(&mut self, path: &Path) -> &(RustCheckOptions, RustfmtConfig) {
+		let dir = path.parent().filter(|d| !d.as_os_str().is_empty()).unwrap_or(Path::new(".")).to_path_buf();
+		self.dirs.entry(dir).or_insert_with_key(|dir| (self.opts.for_dir(dir), RustfmtConfig::load(dir)))
+	}
+}
+
+/// Serve requests on stdio, or on a unix socket at `socket` (one connection at a time), until `shutdown`.
+pub fn run_daemon(opts: impl CrateOptions, socket: Option<&Path>) -> i32 {
+	let mut daemon = Daemon::new(opts);
+	let result = match socket {
+		None => serve(&mut daemon, io::stdin().lock(), io::stdout().lock()),
+		Some(socket) => serve_socket(&mut daemon, socket),
+	};
+	match result {
+		Ok(()) => 0,
+		Err(e) => {
+			eprintln!("codestyle daemon: {e}");
+			1
+		}
+	}
+}
+
+/// Answer every line of `reader` on `writer`; returns at end of input or after `shutdown`.
+fn serve<O: CrateOptions>(daemon: &mut Daemon<O>, reader: impl BufRead, mut writer: impl Write) -> io::Result<()> {
+	for line in reader.lines() {
+		let line = line?;
+		if line.trim().is_empty() {
+			continue;
+		}
+		writeln!(writer, "{}", daemon.handle(&line))?;
+		writer.flush()?;
+		if daemon.is_shut_down() {
+			break;
+		}
+	}
+	Ok(())
+}
+
+#[cfg(unix)]
+fn serve_socket<O: CrateOptions>(daemon: &mut Daemon<O>, socket: &Path) -> io::Result<()> {
+	use std::{io::BufReader, os::unix::net::UnixListener};
+
+	let listener = UnixListener::bind(socket).map_err(|e| io::Error::new(e.kind(), format!("failed to bind {socket:?}: {e}")))?;
+	for stream in listener.incoming() {
+		let stream = stream?;
+		// A client hanging up mid-request only ends its own connection
+		if let Err(e) = serve(daemon, BufReader::new(&stream), &stream) {
+			eprintln!("codestyle daemon: connection closed: {e}");
+		}
+		if daemon.is_shut_down() {
+			break;
+		}
+	}
+	//IGNORED_ERROR: the socket file is only left behind, and the next bind reports it
+	let _ = fs::remove_file(socket);
+	Ok(())
+}
+
+#[cfg(not(unix))]
+fn serve_socket<O: CrateOptions>(_daemon: &mut Daemon<O>, _socket: &Path) -> io::Result<()> {
+	Err(io::Error::new(io::ErrorKind::Unsupported, "--socket is only supported on unix; use stdio instead"))
+}
+
+fn read_content(path: &Path, content: Option<String>) -> Result<String, String> {
+	match content {
+		Some(content) => Ok(content),
+		None => fs::read_to_string(path).map_err(|e| format!("failed to read {path:?}: {e}")),
+	}
+}
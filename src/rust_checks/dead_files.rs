@@ -0,0 +1,91 @@
+//! Crate-wide lint for `.rs` files under `src/` that no `mod` declaration reaches.
+//!
+//! Like `unused_pub`, this one looks at a whole crate at once: its module tree is built from `lib.rs`, `main.rs`, the
+//! binaries under `src/bin/` and any target `path` its manifest sets, following plain `mod x;` and `#[path]` alike, and
+//! every file under `src/` outside it is flagged. Such a file isn't compiled, so it goes on reading as part of the crate
+//! while nothing checks it against the code around it. Modules declared from inside a macro, and files pulled in with
+//! `include!`, aren't seen, and want a skip. Only `assert` runs it, as whether to declare or delete the file is a choice.
+
+use std::{
+	collections::HashMap,
+	fs,
+	path::{Path, PathBuf},
+};
+
+use super::{
+	RustCheckOptions, Violation, path_modules,
+	rules::RuleInfo,
+	skip,
+	workspace::{crate_src, normalize, target_paths},
+};
+
+pub const RULE: &str = "dead-file";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "dead_files",
+	autofix: false,
+	triggers: &[],
+	description: "Flag files under src/ that no mod declaration reaches",
+	rationale: "A file no `mod` reaches isn't compiled, so it keeps reading as part of the crate while silently falling out of step with it.",
+	bad: "// src/lib.rs\nmod client;\n\n// src/retry.rs, declared nowhere\npub fn backoff(attempt: u32) -> Duration { .. }",
+	good: "// src/lib.rs\nmod client;\nmod retry;",
+	fix: "Declare the module with `mod` where it belongs, or delete the file.",
+};
+
+/// Violations for the files of `files` their crate's module tree doesn't reach, for the ones whose options enable the
+/// rule.
+pub fn check_crates(files: &[(PathBuf, RustCheckOptions)]) -> Vec<Violation> {
+	let mut crates: HashMap<PathBuf, Vec<&(PathBuf, RustCheckOptions)>> = HashMap::new();
+	for file in files {
+		if let Some(src) = crate_src(&file.0) {
+			crates.entry(src).or_default().push(file);
+		}
+	}
+	let mut violations = Vec::new();
+	for (src, files) in crates {
+		if !files.iter().any(|(path, opts)| opts.severity_for(path, INFO.option).is_enabled()) {
+			continue;
+		}
+		let reachable = path_modules::reachable(&roots(&src));
+		for (path, opts) in files {
+			let severity = opts.severity_for(path, INFO.option);
+			if !severity.is_enabled() || reachable.contains(&normalize(path)) {
+				continue;
+			}
+			if fs::read_to_string(path).is_ok_and(|content| skip::has_skip_marker_for_rule_at_line(&content, 1, RULE)) {
+				continue;
+			}
+			violations.push(Violation {
+				rule: RULE,
+				file: path.display().to_string(),
+				line: 1,
+				column: 0,
+				message: "no `mod` declaration or `#[path]` reaches this file, so it isn't compiled\nHINT: declare it with `mod` where it belongs, or delete it".to_string(),
+				severity,
+				..Default::default()
+			});
+		}
+	}
+	violations.sort_by(|a, b| a.file.cmp(&b.file));
+	violations
+}
+
+/// Entry files of the crate rooted at `src`: `lib.rs`, `main.rs`, the binaries under `bin/`, and the targets whose
+/// `path` the manifest sets.
+fn roots(src: &Path) -> Vec<PathBuf> {
+	let mut roots: Vec<PathBuf> = ["lib.rs", "main.rs"].iter().map(|name| src.join(name)).filter(|root| root.is_file()).collect();
+	if let Ok(entries) = fs::read_dir(src.join("bin")) {
+		for entry in entries.flatten() {
+			let path = entry.path();
+			if path.extension().is_some_and(|ext| ext == "rs") {
+				roots.push(path);
+			} else if path.join("main.rs").is_file() {
+				roots.push(path.join("main.rs"));
+			}
+		}
+	}
+	if let Some(package_dir) = src.parent() {
+		roots.extend(target_paths(package_dir).into_iter().filter(|root| root.is_file()));
+	}
+	roots
+}
@@ -0,0 +1,171 @@
+//! Lint to require `Debug` on public types.
+//!
+//! A public struct or enum without `Debug` can't be printed with `{:?}`, nor be part of any type deriving it, which
+//! every downstream user then finds out about. Types deriving it under any path, like `derive_more::Debug`, pass, as do
+//! types with an `impl Debug` in the same file and `#[doc(hidden)]` ones. The fix adds `Debug` to the type's first
+//! `#[derive(..)]`, where `derive_order` would put it, or adds a `#[derive(Debug)]` if there's none.
+
+use std::{cmp::Ordering, collections::HashSet, path::Path};
+
+use syn::{Attribute, Ident, ItemEnum, ItemImpl, ItemStruct, Meta, Token, Type, Visibility, punctuated::Punctuated, spanned::Spanned, visit::Visit};
+
+use super::{
+	Fix, Severity, Violation,
+	custom_rules::Imports,
+	derive_order,
+	line_index::LineIndex,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
+
+const RULE: &str = "derive-debug";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "derive_debug",
+	autofix: true,
+	triggers: &["pub"],
+	description: "Require public structs and enums to implement Debug",
+	rationale: "A public type without `Debug` can't be printed with `{:?}` or used in types deriving it, which users of it only find out downstream.",
+	bad: "#[derive(Clone)]\npub struct Config {\n\tpub retries: u32,\n}",
+	good: "#[derive(Clone, Debug)]\npub struct Config {\n\tpub retries: u32,\n}",
+	fix: "Adds `Debug` to the first `#[derive(..)]`, in derive-order position, or a `#[derive(Debug)]` above the type.",
+};
+
+pub struct DeriveDebug;
+impl Rule for DeriveDebug {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+	let mut manual = ManualImpls {
+		imports: Imports::of(file),
+		names: HashSet::new(),
+	};
+	manual.visit_file(file);
+	let visitor = DebugVisitor {
+		path_str: path.display().to_string(),
+		lines: LineIndex::new(content),
+		manual: manual.names,
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+/// Names of the types the file implements `Debug` for by hand.
+struct ManualImpls {
+	imports: Imports,
+	names: HashSet<String>,
+}
+
+impl<'a> Visit<'a> for ManualImpls {
+	fn visit_item_impl(&mut self, node: &'a ItemImpl) {
+		syn::visit::visit_item_impl(self, node);
+		if let Some((_, trait_path, _)) = &node.trait_
+			&& matches!(self.imports.resolve(trait_path).iter().map(String::as_str).collect::<Vec<_>>().as_slice(), ["std" | "core", "fmt", "Debug"])
+			&& let Type::Path(self_ty) = &*node.self_ty
+			&& let Some(last) = self_ty.path.segments.last()
+		{
+			self.names.insert(last.ident.to_string());
+		}
+	}
+}
+
+struct DebugVisitor<'a> {
+	path_str: String,
+	lines: LineIndex<'a>,
+	manual: HashSet<String>,
+	violations: Vec<Violation>,
+}
+
+impl DebugVisitor<'_> {
+	fn check_type(&mut self, attrs: &[Attribute], vis: &Visibility, ident: &Ident) {
+		if !matches!(vis, Visibility::Public(_)) || self.manual.contains(&ident.to_string()) || attrs.iter().any(is_doc_hidden) {
+			return;
+		}
+		let derives: Vec<(&Attribute, Vec<syn::Path>)> = attrs.iter().filter_map(|attr| Some((attr, derived(attr)?))).collect();
+		if derives.iter().flat_map(|(_, paths)| paths).any(|path| path.segments.last().is_some_and(|last| last.ident == "Debug")) {
+			return;
+		}
+		let fix = match derives.first() {
+			Some((_, paths)) => add_to_derives(&self.lines, paths, "Debug"),
+			None => add_derive(&self.lines, attrs, vis, "Debug"),
+		};
+		let start = ident.span().start();
+		let end = ident.span().end();
+		self.violations.push(Violation {
+			rule: RULE,
+			file: self.path_str.clone(),
+			line: start.line,
+			column: start.column,
+			end: Some((end.line, end.column)),
+			message: format!("public type `{ident}` doesn't implement `Debug`\nHINT: derive it"),
+			severity: Severity::Error,
+			fix,
+			..Default::default()
+		});
+	}
+}
+
+impl<'a> Visit<'a> for DebugVisitor<'_> {
+	fn visit_item_struct(&mut self, node: &'a ItemStruct) {
+		self.check_type(&node.attrs, &node.vis, &node.ident);
+		syn::visit::visit_item_struct(self, node);
+	}
+
+	fn visit_item_enum(&mut self, node: &'a ItemEnum) {
+		self.check_type(&node.attrs, &node.vis, &node.ident);
+		syn::visit::visit_item_enum(self, node);
+	}
+}
+
+/// Fix adding `name` to the derive list `paths`, before the first derive `derive_order` sorts after it.
+pub(super) fn add_to_derives(lines: &LineIndex, paths: &[syn::Path], name: &str) -> Option<Fix> {
+	let last = paths.last()?;
+	let (at, replacement) = match paths.iter().find(|path| path.segments.last().is_some_and(|seg| derive_order::compare(&seg.ident.to_string(), name) == Ordering::Greater)) {
+		Some(next) => (lines.offset_of(next.span().start())?, format!("{name}, ")),
+		None => (lines.offset_of(last.span().end())?, format!(", {name}")),
+	};
+	Some(Fix {
+		start_byte: at,
+		end_byte: at,
+		replacement,
+	})
+}
+
+/// Fix adding a `#[derive(name)]` line after the doc comments of the item with `attrs` and `vis`, above any other
+/// attribute.
+pub(super) fn add_derive(lines: &LineIndex, attrs: &[Attribute], vis: &Visibility, name: &str) -> Option<Fix> {
+	let first = attrs.iter().find(|attr| !attr.path().is_ident("doc")).map_or_else(|| vis.span(), Spanned::span);
+	let line_start = lines.line_start(lines.offset_of(first.start())?);
+	let text = lines.text();
+	let line = &text[line_start..lines.line_end(line_start)];
+	let indent = &line[..line.len() - line.trim_start().len()];
+	Some(Fix {
+		start_byte: line_start,
+		end_byte: line_start,
+		replacement: format!("{indent}#[derive({name})]\n"),
+	})
+}
+
+/// The paths `attr` derives, if it's a `#[derive(..)]`.
+pub(super) fn derived(attr: &Attribute) -> Option<Vec<syn::Path>> {
+	let Meta::List(list) = &attr.meta else {
+		return None;
+	};
+	if !list.path.is_ident("derive") {
+		return None;
+	}
+	list.parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated).ok().map(|paths| paths.into_iter().collect())
+}
+
+fn is_doc_hidden(attr: &Attribute) -> bool {
+	attr.path().is_ident("doc") && matches!(&attr.meta, Meta::List(list) if list.tokens.to_string() == "hidden")
+}
@@ -0,0 +1,262 @@
+//! Lint to derive `Default` rather than implement it by hand when the derive would do the same.
+//!
+//! A hand-written `impl Default` whose `default()` builds the type from each field's own default is the derive written
+//! out: it has to be kept in step with the fields by hand, and reads as if something about it were special. A field's
+//! value counts as its default if it's a `default()` call, `0`, `false`, `""`, `None`, `()`, `vec![]`, or the `new()`
+//! of a std collection or `String`. A unit variant of an enum counts too, which the derive picks with `#[default]`.
+//!
+//! Only types defined in the same file are looked at, and neither they nor the impl may have generics, as the derive
+//! bounds every parameter by `Default`. The fix drops the impl and adds `Default` to the type's derives, in
+//! `derive_order` position, or a `#[derive(Default)]` above it.
+
+use std::path::Path;
+
+use proc_macro2::Span;
+use syn::{Attribute, Expr, Fields, ImplItem, Item, ItemImpl, Lit, Stmt, spanned::Spanned};
+
+use super::{
+	Fix, Severity, Violation,
+	custom_rules::Imports,
+	derive_debug::{add_derive, add_to_derives, derived},
+	impl_folds,
+	line_index::LineIndex,
+	pub_first::find_item_text_start,
+	rules::{Context, Rule, RuleInfo},
+	skip::has_skip_marker_for_rule,
+};
+
+const RULE: &str = "derive-default";
+/// Types whose `new()` is their `default()`
+const DEFAULT_NEW: &[&str] = &["String", "Vec", "VecDeque", "HashMap", "HashSet", "BTreeMap", "BTreeSet", "BinaryHeap"];
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "derive_default",
+	autofix: true,
+	triggers: &["Default"],
+	description: "Derive Default instead of implementing it by hand with each field's default",
+	rationale: "A manual `impl Default` that only fills in defaults is the derive written out, to be kept in step with the fields by hand.",
+	bad: "struct Config {\n\tretries: u32,\n\tname: String,\n}\n\nimpl Default for Config {\n\tfn default() -> Self {\n\t\tSelf { retries: 0, name: String::new() }\n\t}\n}",
+	good: "#[derive(Default)]\nstruct Config {\n\tretries: u32,\n\tname: String,\n}",
+	fix: "Removes the impl and derives `Default` on the type, marking an enum's variant `#[default]`.",
+};
+
+pub struct DeriveDefault;
+impl Rule for DeriveDefault {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+	let lines = LineIndex::new(content);
+	let imports = Imports::of(file);
+	let mut violations = Vec::new();
+	for item in &file.items {
+		let Item::Impl(block) = item else {
+			continue;
+		};
+		if has_skip_marker_for_rule(content, block.span(), RULE) || !is_default_impl(block, &imports) {
+			continue;
+		}
+		let Some((name, body)) = default_body(block) else {
+			continue;
+		};
+		let Some(def) = file.items.iter().find(|item| type_ident(item).is_some_and(|ident| *ident == name)) else {
+			continue;
+		};
+		let Some(variant) = derive_equivalent(def, &name, body) else {
+			continue;
+		};
+		let hint = match &variant {
+			Some(variant) => format!("derive it, with `#[default]` on `{variant}`"),
+			None => "derive it".to_string(),
+		};
+		let start = block.impl_token.span.start();
+		let end = block.self_ty.span().end();
+		violations.push(Violation {
+			rule: RULE,
+			file: path.display().to_string(),
+			line: start.line,
+			column: start.column,
+			end: Some((end.line, end.column)),
+			message: format!("`impl Default for {name}` does what `#[derive(Default)]` does\nHINT: {hint}"),
+			severity: Severity::Error,
+			fix: derive_fix(&lines, def, variant.as_deref(), block),
+			..Default::default()
+		});
+	}
+	violations
+}
+
+/// Whether `block` is a plain `impl Default for Type` with nothing but its `fn default()` in it.
+fn is_default_impl(block: &ItemImpl, imports: &Imports) -> bool {
+	let Some((None, trait_path, _)) = &block.trait_ else {
+		return false;
+	};
+	let is_default = matches!(imports.resolve(trait_path).iter().map(String::as_str).collect::<Vec<_>>().as_slice(), ["Default"] | ["std" | "core", "default", "Default"]);
+	is_default && block.generics.params.is_empty() && block.items.len() == 1 && block.attrs.iter().all(is_doc) && block.unsafety.is_none()
+}
+
+/// Name of the type `block` is for and the one expression its `default()` returns.
+fn default_body(block: &ItemImpl) -> Option<(String, &Expr)> {
+	let syn::Type::Path(self_ty) = &*block.self_ty else {
+		return None;
+	};
+	let name = self_ty.path.get_ident().filter(|_| self_ty.qself.is_none())?;
+	let ImplItem::Fn(f) = block.items.first()? else {
+		return None;
+	};
+	let [Stmt::Expr(expr, None)] = f.block.stmts.as_slice() else {
+		return None;
+	};
+	Some((name.to_string(), expr))
+}
+
+fn type_ident(item: &Item) -> Option<&syn::Ident> {
+	match item {
+		Item::Struct(s) => Some(&s.ident),
+		Item::Enum(e) => Some(&e.ident),
+		_ => None,
+	}
+}
+
+/// Whether `body` builds the type `def` the way the derive does: `Some(None)` for a struct, `Some(Some(variant))` for an
+/// enum and the unit variant it picks.
+fn derive_equivalent(def: &Item, name: &str, body: &Expr) -> Option<Option<String>> {
+	let names_type = |path: &syn::Path| path.is_ident("Self") || path.is_ident(name);
+	match (def, body) {
+		(Item::Struct(s), _) if !s.generics.params.is_empty() => None,
+		(Item::Enum(e), _) if !e.generics.params.is_empty() => None,
+		(Item::Struct(s), Expr::Path(path)) if matches!(s.fields, Fields::Unit) && path.qself.is_none() && names_type(&path.path) => Some(None),
+		(Item::Struct(_), Expr::Struct(expr)) if expr.qself.is_none() && names_type(&expr.path) => {
+			let rest_is_default = expr.rest.as_deref().is_none_or(is_default_value);
+			(rest_is_default && expr.fields.iter().all(|field| is_default_value(&field.expr))).then_some(None)
+		}
+		(Item::Struct(_), Expr::Call(call)) => {
+			let Expr::Path(func) = &*call.func else {
+				return None;
+			};
+			(names_type(&func.path) && call.args.iter().all(is_default_value)).then_some(None)
+		}
+		(Item::Enum(e), Expr::Path(path)) => {
+			let segments = &path.path.segments;
+			if path.qself.is_some() || segments.len() != 2 || !(segments[0].ident == "Self" || segments[0].ident == name) {
+				return None;
+			}
+			let variant = &segments[1].ident;
+			let is_unit = e.variants.iter().any(|v| v.ident == *variant && matches!(v.fields, Fields::Unit));
+			is_unit.then(|| Some(variant.to_string()))
+		}
+		_ => None,
+	}
+}
+
+/// Whether `expr` is what `Default::default()` gives for its type, whatever that is.
+fn is_default_value(expr: &Expr) -> bool {
+	match expr {
+		Expr::Lit(lit) => match &lit.lit {
+			Lit::Int(int) => int.base10_digits() == "0",
+			Lit::Float(float) => float.base10_parse::<f64>().is_ok_and(|value| value == 0.0),
+			Lit::Bool(b) => !b.value,
+			Lit::Str(s) => s.value().is_empty(),
+			_ => false,
+		},
+		Expr::Path(path) => path.qself.is_none() && path.path.is_ident("None"),
+		Expr::Tuple(tuple) => tuple.elems.is_empty(),
+		Expr::Macro(mac) => mac.mac.path.is_ident("vec") && mac.mac.tokens.is_empty(),
+		Expr::Paren(paren) => is_default_value(&paren.expr),
+		Expr::Call(call) if call.args.is_empty() => {
+			let Expr::Path(func) = &*call.func else {
+				return false;
+			};
+			let segments: Vec<_> = func.path.segments.iter().map(|s| s.ident.to_string()).collect();
+			match segments.as_slice() {
+				[.., last] if last == "default" => true,
+				[.., ty, last] if last == "new" => DEFAULT_NEW.contains(&ty.as_str()),
+				_ => false,
+			}
+		}
+		_ => false,
+	}
+}
+
+/// Fix removing `block` and deriving `Default` on `def`, marking `variant` `#[default]` if it's an enum's.
+fn derive_fix(lines: &LineIndex, def: &Item, variant: Option<&str>, block: &ItemImpl) -> Option<Fix> {
+	let (attrs, vis) = match def {
+		Item::Struct(s) => (&s.attrs, &s.vis),
+		Item::Enum(e) => (&e.attrs, &e.vis),
+		_ => return None,
+	};
+	let derives: Vec<Vec<syn::Path>> = attrs.iter().filter_map(derived).collect();
+	if derives.iter().flatten().any(|path| path.segments.last().is_some_and(|last| last.ident == "Default")) {
+		return None;
+	}
+	let mut edits = vec![
+		match derives.first() {
+			Some(paths) => add_to_derives(lines, paths, "Default")?,
+			None => add_derive(lines, attrs, vis, "Default")?,
+		},
+		removal(lines, block)?,
+	];
+	if let (Item::Enum(e), Some(variant)) = (def, variant) {
+		let variant = e.variants.iter().find(|v| v.ident == variant)?;
+		edits.push(mark_default(lines, variant.span())?);
+	}
+	combine(lines.text(), edits)
+}
+
+/// Fix inserting a `#[default]` line above the variant at `span`.
+fn mark_default(lines: &LineIndex, span: Span) -> Option<Fix> {
+	let line_start = lines.line_start(lines.offset_of(span.start())?);
+	let line = &lines.text()[line_start..lines.line_end(line_start)];
+	let indent = &line[..line.len() - line.trim_start().len()];
+	Some(Fix {
+		start_byte: line_start,
+		end_byte: line_start,
+		replacement: format!("{indent}#[default]\n"),
+	})
+}
+
+/// Fix deleting `block` with its doc comments, the fold marker after it and the blank line before it.
+fn removal(lines: &LineIndex, block: &ItemImpl) -> Option<Fix> {
+	let text = lines.text();
+	let mut start = find_item_text_start(lines, lines.offset_of(block.span().start())?);
+	let mut end = (lines.line_end(lines.offset_of(block.span().end())?) + 1).min(text.len());
+	if end < text.len() && text[end..lines.line_end(end)].trim() == impl_folds::CLOSE_MARKER {
+		end = (lines.line_end(end) + 1).min(text.len());
+	}
+	if start > 0 && text[lines.line_start(start - 1)..start].trim().is_empty() {
+		start = lines.line_start(start - 1);
+	}
+	Some(Fix {
+		start_byte: start,
+		end_byte: end,
+		replacement: String::new(),
+	})
+}
+
+/// One fix doing all of `edits`, which mustn't overlap, by replacing everything from the first to the last.
+pub(super) fn combine(text: &str, mut edits: Vec<Fix>) -> Option<Fix> {
+	edits.sort_by_key(|edit| edit.start_byte);
+	let (start_byte, end_byte) = (edits.first()?.start_byte, edits.iter().map(|edit| edit.end_byte).max()?);
+	let mut replacement = String::new();
+	let mut at = start_byte;
+	for edit in edits {
+		if edit.start_byte < at {
+			return None;
+		}
+		replacement.push_str(&text[at..edit.start_byte]);
+		replacement.push_str(&edit.replacement);
+		at = edit.end_byte;
+	}
+	replacement.push_str(&text[at..end_byte]);
+	Some(Fix { start_byte, end_byte, replacement })
+}
+
+fn is_doc(attr: &Attribute) -> bool {
+	attr.path().is_ident("doc")
+}
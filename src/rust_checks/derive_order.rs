@@ -0,0 +1,127 @@
+//! Lint to keep `#[derive(..)]` lists in one canonical order, so the same set of derives always reads the same.
+//!
+//! The order is: `Clone, Copy, Debug, Default`, then serde's `Deserialize, Serialize`, then everything else
+//! alphabetically. Derives are ranked by their last path segment, so `serde::Serialize` sorts with `Serialize`.
+
+use std::{cmp::Ordering, path::Path};
+
+use syn::{Attribute, Meta, Token, punctuated::Punctuated, spanned::Spanned, visit::Visit};
+
+use super::{
+	Fix, Severity, Violation,
+	line_index::LineIndex,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
+
+const RULE: &str = "derive-order";
+/// Groups sorted first, in order; derives within a group keep the listed order
+const GROUPS: &[&[&str]] = &[&["Clone", "Copy", "Debug", "Default"], &["Deserialize", "Serialize"]];
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "derive_order",
+	autofix: true,
+	triggers: &["derive("],
+	description: "Keep #[derive] lists in canonical order",
+	rationale: "With one order, the same derives always read the same, and a missing one stands out.",
+	bad: "#[derive(PartialEq, Serialize, Debug, Clone, Eq)]",
+	good: "#[derive(Clone, Debug, Serialize, Eq, PartialEq)]",
+	fix: "Reorders the list: Clone, Copy, Debug, Default, then Deserialize, Serialize, then the rest alphabetically.",
+};
+
+pub struct DeriveOrder;
+impl Rule for DeriveOrder {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+	let visitor = DeriveVisitor {
+		path_str: path.display().to_string(),
+		content,
+		lines: LineIndex::new(content),
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+struct DeriveVisitor<'a> {
+	path_str: String,
+	content: &'a str,
+	lines: LineIndex<'a>,
+	violations: Vec<Violation>,
+}
+
+impl DeriveVisitor<'_> {
+	/// Rewrites the list spanned by `derives` in `order`; none if comments in between would be lost.
+	fn reorder(&self, derives: &[(String, syn::Path)], order: &[usize]) -> Option<Fix> {
+		let start_byte = self.lines.offset_of(derives.first()?.1.span().start())?;
+		let end_byte = self.lines.offset_of(derives.last()?.1.span().end())?;
+		let list = &self.content[start_byte..end_byte];
+		if list.contains("//") || list.contains("/*") {
+			return None;
+		}
+		let texts = derives
+			.iter()
+			.map(|(_, path)| Some(&self.content[self.lines.offset_of(path.span().start())?..self.lines.offset_of(path.span().end())?]))
+			.collect::<Option<Vec<&str>>>()?;
+		let replacement = order.iter().map(|&i| texts[i]).collect::<Vec<_>>().join(", ");
+		Some(Fix { start_byte, end_byte, replacement })
+	}
+}
+
+impl<'a> Visit<'a> for DeriveVisitor<'a> {
+	fn visit_attribute(&mut self, node: &'a Attribute) {
+		let Meta::List(list) = &node.meta else {
+			return;
+		};
+		if !list.path.is_ident("derive") {
+			return;
+		}
+		let Ok(paths) = list.parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated) else {
+			return;
+		};
+		let derives: Vec<(String, syn::Path)> = paths
+			.into_iter()
+			.filter_map(|path| Some((path.segments.last()?.ident.to_string(), path)))
+			.collect();
+		let mut order: Vec<usize> = (0..derives.len()).collect();
+		order.sort_by(|&a, &b| compare(&derives[a].0, &derives[b].0));
+		if order.iter().enumerate().all(|(i, &j)| i == j) {
+			return;
+		}
+		let expected = order.iter().map(|&i| derives[i].0.as_str()).collect::<Vec<_>>().join(", ");
+		let start = node.span().start();
+		let end = node.span().end();
+		self.violations.push(Violation {
+			rule: RULE,
+			file: self.path_str.clone(),
+			line: start.line,
+			column: start.column,
+			end: Some((end.line, end.column)),
+			message: format!("derives out of order\nHINT: expected `{expected}`"),
+			severity: Severity::Error,
+			fix: self.reorder(&derives, &order),
+			..Default::default()
+		});
+	}
+}
+
+/// Canonical order of two derive names: by group, then by position in the group, or alphabetically outside of them.
+pub(super) fn compare(a: &str, b: &str) -> Ordering {
+	let rank = |name: &str| {
+		GROUPS
+			.iter()
+			.enumerate()
+			.find_map(|(group, names)| Some((group, names.iter().position(|n| *n == name)?)))
+			.unwrap_or((GROUPS.len(), 0))
+	};
+	rank(a).cmp(&rank(b)).then_with(|| a.cmp(b))
+}
@@ -3,9 +3,37 @@ use std::{collections::HashSet, path::Path};
 use proc_macro2::{Span, TokenStream, TokenTree};
 use syn::{ExprMacro, Macro, spanned::Spanned, visit::Visit};
 
-use super::{Fix, Violation, skip::SkipVisitor};
+use super::{
+	Fix, Severity, Violation,
+	line_index::LineIndex,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
 
 const RULE: &str = "embed-simple-vars";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "embed_simple_vars",
+	autofix: true,
+	triggers: &[],
+	description: "Embed simple variables in format strings",
+	rationale: "`{x}` reads left to right and can't drift out of sync with the argument list the way positional `{}` can.",
+	bad: "println!(\"{}: {:?}\", name, value);",
+	good: "println!(\"{name}: {value:?}\");",
+	fix: "Moves each plain identifier argument into its placeholder, keeping the format spec, and drops it from the argument list.",
+};
+
+pub struct EmbedSimpleVars;
+impl Rule for EmbedSimpleVars {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
 const FORMAT_MACROS: &[&str] = &[
 	// std formatting
 	"format", "write", "writeln", "print", "println", "eprint", "eprintln", "format_args", // std panicking/unreachable
@@ -23,7 +51,7 @@ pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
 
 struct FormatMacroVisitor<'a> {
 	path_str: String,
-	content: &'a str,
+	lines: LineIndex<'a>,
 	violations: Vec<Violation>,
 	seen_spans: HashSet<(usize, usize)>,
 }
@@ -32,7 +60,7 @@ impl<'a> FormatMacroVisitor<'a> {
 	fn new(path: &Path, content: &'a str) -> Self {
 		Self {
 			path_str: path.display().to_string(),
-			content,
+			lines: LineIndex::new(content),
 			violations: Vec::new(),
 			seen_spans: HashSet::new(),
 		}
@@ -158,13 +186,13 @@ impl<'a> FormatMacroVisitor<'a> {
 		let fix = if remaining_args.is_empty() {
 			// All args were simple, just replace format string through last arg
 			let last_arg_span = args.last().map(|(_, span)| *span);
-			create_full_macro_fix(&new_fmt, fmt_span, last_arg_span, self.content)
+			create_full_macro_fix(&new_fmt, fmt_span, last_arg_span, &self.lines)
 		} else {
 			// Some args remain, need to build "new_fmt", remaining_args...
 			let remaining_args_str = remaining_args.join(", ");
 			let replacement = format!("{new_fmt}, {remaining_args_str}");
 			let last_arg_span = args.last().map(|(_, span)| *span);
-			create_full_macro_fix(&replacement, fmt_span, last_arg_span, self.content)
+			create_full_macro_fix(&replacement, fmt_span, last_arg_span, &self.lines)
 		};
 
 		for (placeholder, arg_str, arg_span) in &simple_args {
@@ -178,11 +206,14 @@ impl<'a> FormatMacroVisitor<'a> {
 				file: self.path_str.clone(),
 				line: arg_span.start().line,
 				column: arg_span.start().column,
+				end: Some((arg_span.end().line, arg_span.end().column)),
 				message: format!(
 					"variable `{arg_str}` should be embedded in format string: use `{{{arg_str}{}}}` instead of `{spec_display}, {arg_str}`",
 					placeholder.specifier
 				),
+				severity: Severity::Error,
 				fix: fix.clone(),
+				..Default::default()
 			});
 		}
 	}
@@ -345,43 +376,15 @@ fn collect_complex_argument(tokens: &[TokenTree], start: usize) -> Option<(Strin
 	if result.is_empty() { None } else { Some((result.trim().to_string(), last_span, i)) }
 }
 
-/// Convert a proc_macro2 line/column position to byte offset in content.
-/// Lines are 1-indexed, columns are 0-indexed character offsets within line.
-fn span_position_to_byte(content: &str, line: usize, column: usize) -> Option<usize> {
-	let mut current_line = 1;
-	let mut line_start = 0;
-
-	for (i, ch) in content.char_indices() {
-		if current_line == line {
-			// Found the line, convert character offset to byte offset
-			let line_content = &content[line_start..];
-			let byte_offset: usize = line_content.char_indices().take(column).map(|(_, c)| c.len_utf8()).sum();
-			return Some(line_start + byte_offset);
-		}
-		if ch == '\n' {
-			current_line += 1;
-			line_start = i + 1;
-		}
-	}
-
-	// Handle last line (no trailing newline)
-	if current_line == line {
-		let line_content = &content[line_start..];
-		let byte_offset: usize = line_content.char_indices().take(column).map(|(_, c)| c.len_utf8()).sum();
-		return Some(line_start + byte_offset);
-	}
-
-	None
-}
-
-fn create_full_macro_fix(new_fmt: &str, fmt_span: Span, last_arg_span: Option<Span>, content: &str) -> Option<Fix> {
+fn create_full_macro_fix(new_fmt: &str, fmt_span: Span, last_arg_span: Option<Span>, lines: &LineIndex) -> Option<Fix> {
 	let last_arg_span = last_arg_span?;
+	let content = lines.text();
 
 	// Get byte position of format string start
-	let fmt_start = span_position_to_byte(content, fmt_span.start().line, fmt_span.start().column)?;
+	let fmt_start = lines.offset_of(fmt_span.start())?;
 
 	// Get byte position after the last argument
-	let last_arg_end = span_position_to_byte(content, last_arg_span.end().line, last_arg_span.end().column)?;
+	let last_arg_end = lines.offset_of(last_arg_span.end())?;
 
 	// Verify the format string is where we expect
 	if !content[fmt_start..].starts_with('"') && !content[fmt_start..].starts_with("r#") && !content[fmt_start..].starts_with("r\"") {
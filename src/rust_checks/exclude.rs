@@ -0,0 +1,91 @@
+//! Which paths file discovery skips: hidden directories, `target/` and `libs/` by default, plus user `--exclude` globs,
+//! generated-file patterns, and nested packages (directories with their own `Cargo.toml`, like vendored crates) unless asked to include them.
+
+use std::path::{Component, Path, PathBuf};
+
+use glob::{MatchOptions, Pattern};
+use tracing::debug;
+
+/// Always excluded, before any user patterns.
+pub const DEFAULT_EXCLUDES: &[&str] = &["**/.*", "**/target", "**/libs"];
+/// Paths of generated files, skipped on top of the ones [`super::generated`] detects by their markers
+pub const DEFAULT_GENERATED: &[&str] = &["**/*_generated.rs", "**/*.generated.rs"];
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+	case_sensitive: true,
+	require_literal_separator: true,
+	require_literal_leading_dot: false,
+};
+
+/// How file discovery is configured, from `--exclude`/`--include-nested` and the `[rust]` config.
+#[derive(Clone, Debug, Default)]
+pub struct DiscoveryOptions {
+	/// Globs for paths to skip, on top of [`DEFAULT_EXCLUDES`]
+	pub exclude: Vec<String>,
+	/// Globs for generated files, on top of [`DEFAULT_GENERATED`]
+	pub generated: Vec<String>,
+	/// Also walk into nested packages and workspaces below a source directory
+	pub include_nested: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct Exclude {
+	/// Patterns match paths relative to this
+	root: PathBuf,
+	/// Fallback base for paths outside the root
+	cwd: PathBuf,
+	patterns: Vec<Pattern>,
+	include_nested: bool,
+}
+impl Exclude {
+	/// The defaults plus the configured exclude and generated-file patterns, matched relative to `root`.
+	pub fn new(root: &Path, options: &DiscoveryOptions) -> Result<Self, String> {
+		let patterns = DEFAULT_EXCLUDES
+			.iter()
+			.chain(DEFAULT_GENERATED)
+			.copied()
+			.chain(options.exclude.iter().chain(&options.generated).map(String::as_str))
+			.map(|p| Pattern::new(p).map_err(|e| format!("invalid exclude pattern `{p}`: {e}")))
+			.collect::<Result<_, _>>()?;
+		Ok(Self {
+			root: root.to_path_buf(),
+			cwd: std::env::current_dir().unwrap_or_default(),
+			patterns,
+			include_nested: options.include_nested,
+		})
+	}
+
+	/// Whether a walk should stay out of `dir`: it matches a pattern, or holds a package of its own.
+	pub fn skips_dir(&self, dir: &Path) -> bool {
+		if self.matches(dir) {
+			return true;
+		}
+		let nested = !self.include_nested && dir.join("Cargo.toml").is_file();
+		if nested {
+			debug!(path = %dir.display(), "skipped: nested package, see --include-nested");
+		}
+		nested
+	}
+
+	/// Whether `path`, or any directory it's in below the root, matches a pattern.
+	/// Paths outside both the root and the working directory only have their file name checked,
+	/// so e.g. a temp dir under a hidden parent isn't excluded wholesale.
+	pub fn matches(&self, path: &Path) -> bool {
+		let relative = match [self.root.as_path(), Path::new("."), &self.cwd].into_iter().find_map(|base| path.strip_prefix(base).ok()) {
+			Some(relative) => relative,
+			None => path.file_name().map_or(path, Path::new),
+		};
+		let mut prefix = PathBuf::new();
+		for component in relative.components() {
+			let Component::Normal(name) = component else {
+				continue;
+			};
+			prefix.push(name);
+			if let Some(pattern) = self.patterns.iter().find(|p| p.matches_path_with(&prefix, MATCH_OPTIONS)) {
+				debug!(path = %path.display(), pattern = pattern.as_str(), "skipped: excluded");
+				return true;
+			}
+		}
+		false
+	}
+}
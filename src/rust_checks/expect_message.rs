@@ -0,0 +1,138 @@
+//! Lint to require `.expect(..)` messages that say something.
+//!
+//! The message is what a panic prints, so it should name the invariant that was broken: `.expect("")`,
+//! `.expect("parse")` on a `.parse()` or `.expect("failed")` tell the reader nothing the backtrace doesn't.
+//! A message is flagged when it's empty, shorter than the `expect_message_min_len` option, or only restates the call,
+//! i.e. is nothing but the called method's name and filler like "failed to".
+
+use std::path::Path;
+
+use syn::{Expr, ExprLit, ExprMethodCall, Lit, visit::Visit};
+
+use super::{
+	Severity, Violation,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
+
+const RULE: &str = "expect-message";
+/// Shortest message that passes, unless `expect_message_min_len` says otherwise
+pub const DEFAULT_MIN_LEN: usize = 8;
+/// Words that don't add anything to a message on their own
+const FILLER: &[&str] = &[
+	"a", "an", "the", "to", "of", "should", "must", "not", "could", "couldn't", "can't", "cannot", "unable", "failed", "fail", "fails", "failure", "error", "err", "ok", "work", "works",
+	"expect", "expected", "unwrap", "value", "result", "option", "some", "none",
+];
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "expect_message",
+	autofix: false,
+	triggers: &["expect("],
+	description: "Require .expect() messages describing the invariant",
+	rationale: "An `.expect()` message is what the panic prints; an empty one or one restating the call wastes the chance to say which assumption broke.",
+	bad: "let n: u16 = s.parse().expect(\"parse failed\");",
+	good: "let n: u16 = s.parse().expect(\"validated as digits by the CLI parser\");",
+	fix: "Say which invariant was broken, e.g. what guaranteed the value is there.",
+};
+
+pub struct ExpectMessage;
+impl Rule for ExpectMessage {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree, ctx.opts.expect_message_min_len)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File, min_len: usize) -> Vec<Violation> {
+	let visitor = ExpectVisitor {
+		path_str: path.display().to_string(),
+		min_len,
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+struct ExpectVisitor {
+	path_str: String,
+	min_len: usize,
+	violations: Vec<Violation>,
+}
+
+impl ExpectVisitor {
+	/// What's wrong with `message` on a `.expect()` whose receiver is `receiver`, if anything.
+	fn problem(&self, message: &str, receiver: &Expr) -> Option<String> {
+		let message = message.trim();
+		if message.is_empty() {
+			return Some("`.expect()` with an empty message".to_string());
+		}
+		if message.chars().count() < self.min_len {
+			return Some(format!("`.expect()` message \"{message}\" is shorter than {} chars", self.min_len));
+		}
+		let called = called_name(receiver);
+		restates(message, called.as_deref()).then(|| format!("`.expect()` message \"{message}\" only restates the call"))
+	}
+}
+
+impl<'a> Visit<'a> for ExpectVisitor {
+	fn visit_expr_method_call(&mut self, node: &'a ExprMethodCall) {
+		// Receiver first, so chained calls are reported left to right
+		syn::visit::visit_expr_method_call(self, node);
+		if node.method != "expect" || node.args.len() != 1 {
+			return;
+		}
+		// Only literals: a `format!` or a constant has presumably been thought about
+		let Some(Expr::Lit(ExprLit { lit: Lit::Str(lit), .. })) = node.args.first() else {
+			return;
+		};
+		if let Some(problem) = self.problem(&lit.value(), &node.receiver) {
+			let start = lit.span().start();
+			let end = lit.span().end();
+			self.violations.push(Violation {
+				rule: RULE,
+				file: self.path_str.clone(),
+				line: start.line,
+				column: start.column,
+				end: Some((end.line, end.column)),
+				message: format!("{problem}\nHINT: say which invariant broke, e.g. `.expect(\"config is validated at startup\")`"),
+				severity: Severity::Error,
+				..Default::default()
+			});
+		}
+	}
+}
+
+/// Name of the method or function whose result is expected: `parse` for `s.parse()` and `File::open(p)?` alike.
+fn called_name(receiver: &Expr) -> Option<String> {
+	match receiver {
+		Expr::MethodCall(call) => Some(call.method.to_string()),
+		Expr::Call(call) => match &*call.func {
+			Expr::Path(path) => path.path.segments.last().map(|s| s.ident.to_string()),
+			_ => None,
+		},
+		Expr::Await(inner) => called_name(&inner.base),
+		Expr::Try(inner) => called_name(&inner.expr),
+		Expr::Paren(inner) => called_name(&inner.expr),
+		_ => None,
+	}
+}
+
+/// Whether `message` is only filler and the words of `called`, like "failed to parse" on a `.parse()`.
+fn restates(message: &str, called: Option<&str>) -> bool {
+	let called: Vec<String> = called.map(|name| name.split('_').map(str::to_lowercase).collect()).unwrap_or_default();
+	message
+		.split(|c: char| !(c.is_alphanumeric() || c == '\''))
+		.filter(|word| !word.is_empty())
+		.map(str::to_lowercase)
+		.all(|word| FILLER.contains(&word.as_str()) || called.iter().any(|called| same_word(called, &word)))
+}
+
+/// Whether `word` is `called`, or an inflection of it: "parsing" and "parsed" for `parse`, "reads" for `read`.
+fn same_word(called: &str, word: &str) -> bool {
+	let stem = ["ing", "ed", "s"].iter().find_map(|suffix| word.strip_suffix(suffix)).unwrap_or(word);
+	called == word || called.trim_end_matches('e') == stem.trim_end_matches('e')
+}
@@ -0,0 +1,113 @@
+//! Lint to limit how many parameters a function takes.
+//!
+//! Past a handful, call sites turn into rows of positional values that are easy to swap, and the parameters usually
+//! belong together in a struct anyway. Functions taking more than the `fn_params_max` option (7 by default) are
+//! flagged; `self` doesn't count. Methods of trait impls are exempt, their signature is the trait's.
+
+use std::path::Path;
+
+use syn::{FnArg, ImplItem, ImplItemFn, ItemFn, ItemImpl, Signature, TraitItemFn, visit::Visit};
+
+use super::{
+	Severity, Violation,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
+
+const RULE: &str = "fn-params";
+/// Most parameters that pass, unless `fn_params_max` says otherwise
+pub const DEFAULT_MAX: usize = 7;
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "fn_params",
+	autofix: false,
+	triggers: &["fn "],
+	description: "Limit the number of function parameters",
+	rationale: "Long positional argument lists are easy to get wrong at call sites, and usually hide a struct that wants to exist.",
+	bad: "fn render(x: u32, y: u32, width: u32, height: u32, color: Color, border: u32, radius: u32, shadow: bool) {}",
+	good: "struct Rect {\n\tx: u32,\n\ty: u32,\n\twidth: u32,\n\theight: u32,\n}\nstruct Style {\n\tcolor: Color,\n\tborder: u32,\n\tradius: u32,\n\tshadow: bool,\n}\nfn render(rect: Rect, style: &Style) {}",
+	fix: "Group related parameters into a params struct, or split the function.",
+};
+
+pub struct FnParams;
+impl Rule for FnParams {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree, ctx.opts.fn_params_max)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File, max: usize) -> Vec<Violation> {
+	let visitor = ParamsVisitor {
+		path_str: path.display().to_string(),
+		max,
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+struct ParamsVisitor {
+	path_str: String,
+	max: usize,
+	violations: Vec<Violation>,
+}
+
+impl ParamsVisitor {
+	fn check_sig(&mut self, sig: &Signature) {
+		let count = sig.inputs.iter().filter(|arg| matches!(arg, FnArg::Typed(_))).count();
+		if count <= self.max {
+			return;
+		}
+		let start = sig.ident.span().start();
+		let end = sig.ident.span().end();
+		self.violations.push(Violation {
+			rule: RULE,
+			file: self.path_str.clone(),
+			line: start.line,
+			column: start.column,
+			end: Some((end.line, end.column)),
+			message: format!(
+				"`{}` takes {count} parameters, more than {}\nHINT: group the ones that belong together into a params struct",
+				sig.ident, self.max
+			),
+			severity: Severity::Error,
+			..Default::default()
+		});
+	}
+}
+
+impl<'a> Visit<'a> for ParamsVisitor {
+	fn visit_item_fn(&mut self, node: &'a ItemFn) {
+		self.check_sig(&node.sig);
+		syn::visit::visit_item_fn(self, node);
+	}
+
+	fn visit_item_impl(&mut self, node: &'a ItemImpl) {
+		if node.trait_.is_some() {
+			// The trait's signatures aren't this impl's to change, but fns nested in the bodies are
+			for item in &node.items {
+				match item {
+					ImplItem::Fn(method) => self.visit_block(&method.block),
+					item => self.visit_impl_item(item),
+				}
+			}
+			return;
+		}
+		syn::visit::visit_item_impl(self, node);
+	}
+
+	fn visit_impl_item_fn(&mut self, node: &'a ImplItemFn) {
+		self.check_sig(&node.sig);
+		syn::visit::visit_impl_item_fn(self, node);
+	}
+
+	fn visit_trait_item_fn(&mut self, node: &'a TraitItemFn) {
+		self.check_sig(&node.sig);
+		syn::visit::visit_trait_item_fn(self, node);
+	}
+}
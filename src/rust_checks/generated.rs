@@ -0,0 +1,15 @@
+//! Generated code: files marked `@generated` or `DO NOT EDIT` near the top are never checked or rewritten.
+//! Generated files without a marker can be matched by path instead, see [`super::exclude::DEFAULT_GENERATED`].
+
+/// How far down a marker still counts, as rustfmt's `format_generated_files` does
+const HEADER_LINES: usize = 5;
+const MARKERS: &[&str] = &["@generated", "DO NOT EDIT"];
+
+/// Whether a comment in the first lines of `contents` marks the file as generated.
+pub fn is_generated(contents: &str) -> bool {
+	contents.lines().take(HEADER_LINES).any(|line| {
+		let line = line.trim_start();
+		let is_comment = line.starts_with("//") || line.starts_with("/*") || line.starts_with('*');
+		is_comment && MARKERS.iter().any(|marker| line.contains(marker))
+	})
+}
@@ -10,9 +10,37 @@ use std::{ops::Range, path::Path};
 
 use syn::{ExprMethodCall, Pat, PatWild, Stmt, spanned::Spanned, visit::Visit};
 
-use super::{Violation, skip::has_skip_marker_for_rule};
+use super::{
+	Fix, Severity, Suggestion, Violation,
+	line_index::LineIndex,
+	rules::{Context, Rule, RuleInfo},
+	skip::has_skip_marker_for_rule,
+};
 
 const RULE: &str = "ignored-error-comment";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "ignored_error_comment",
+	autofix: false,
+	triggers: &[],
+	description: "Require //IGNORED_ERROR on unwrap_or* and let _ = ...",
+	rationale: "Fallbacks like `unwrap_or_default()` and `let _ =` silently continue with possibly corrupted state; a comment forces an explicit decision that this is intended.",
+	bad: "let port = env::var(\"PORT\").ok().and_then(|p| p.parse().ok()).unwrap_or(8080);",
+	good: "//IGNORED_ERROR: unset or malformed PORT falls back to the documented default\nlet port = env::var(\"PORT\").ok().and_then(|p| p.parse().ok()).unwrap_or(8080);",
+	fix: "Either propagate the error or add a `//IGNORED_ERROR` comment explaining why ignoring it is correct.",
+};
+
+pub struct IgnoredErrorComment;
+impl Rule for IgnoredErrorComment {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
 pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
 	let mut visitor = IgnoredErrorVisitor::new(path, content);
 	visitor.visit_file(file);
@@ -22,6 +50,7 @@ pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
 struct IgnoredErrorVisitor<'a> {
 	path_str: String,
 	content: &'a str,
+	lines: LineIndex<'a>,
 	violations: Vec<Violation>,
 	/// Stack of line ranges that are skipped due to codestyle::skip markers
 	skipped_ranges: Vec<Range<usize>>,
@@ -32,6 +61,7 @@ impl<'a> IgnoredErrorVisitor<'a> {
 		Self {
 			path_str: path.display().to_string(),
 			content,
+			lines: LineIndex::new(content),
 			violations: Vec::new(),
 			skipped_ranges: Vec::new(),
 		}
@@ -63,6 +93,21 @@ impl<'a> IgnoredErrorVisitor<'a> {
 		false
 	}
 
+	/// A `//IGNORED_ERROR: ` comment above `line`, for the user to finish with the reason.
+	fn justify(&self, line: usize) -> Suggestion {
+		let line_start = self.lines.offset(line, 0).unwrap_or_default();
+		let text = &self.content[line_start..self.lines.line_end(line_start)];
+		let indent = &text[..text.len() - text.trim_start().len()];
+		Suggestion {
+			message: "explain why ignoring the error is correct".to_string(),
+			fix: Fix {
+				start_byte: line_start,
+				end_byte: line_start,
+				replacement: format!("{indent}//IGNORED_ERROR: \n"),
+			},
+		}
+	}
+
 	fn is_standalone_underscore<'b>(&self, pat: &'b Pat) -> Option<&'b PatWild> {
 		// Only match standalone `_`, not `_name` or destructuring like `(a, _)`
 		if let Pat::Wild(wild) = pat { Some(wild) } else { None }
@@ -118,7 +163,9 @@ impl<'a> Visit<'a> for IgnoredErrorVisitor<'a> {
 						"`{method_name}` without `//IGNORED_ERROR` comment\n\
 						HINT: Error out properly or explain why it's part of the intended logic and simply erroring out / panicking is not an option."
 					),
-					fix: None,
+					severity: Severity::Error,
+					suggestions: vec![self.justify(span_start.line)],
+					..Default::default()
 				});
 			}
 		}
@@ -141,7 +188,9 @@ impl<'a> Visit<'a> for IgnoredErrorVisitor<'a> {
 					message: "`let _ = ...` without `//IGNORED_ERROR` comment\n\
 						HINT: could the pattern be allowing to continue with corrupted state? Error out properly or explain why it's part of the intended logic."
 						.to_string(),
-					fix: None,
+					severity: Severity::Error,
+					suggestions: vec![self.justify(span_start.line)],
+					..Default::default()
 				});
 			}
 		}
@@ -2,13 +2,43 @@ use std::path::Path;
 
 use syn::{Item, spanned::Spanned};
 
-use super::{Fix, Violation, skip::has_skip_marker_for_rule};
+use super::{
+	Fix, Severity, Violation,
+	line_index::LineIndex,
+	rules::{Context, Rule, RuleInfo},
+	rustfmt::RustfmtConfig,
+	skip::has_skip_marker_for_rule,
+};
 
 const RULE: &str = "impl-folds";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "impl_folds",
+	autofix: true,
+	triggers: &["impl"],
+	description: "Wrap impl blocks in vim 1-fold markers",
+	rationale: "Fold markers let vim collapse every impl block to one line, so a file's types and their methods can be scanned at a glance.",
+	bad: "impl Foo {\n\tfn bar(&self) {}\n}",
+	good: "impl Foo /*{{{1*/ {\n\tfn bar(&self) {}\n}\n//,}}}1",
+	fix: "Adds the missing opening and/or closing marker, moving the opening one onto its own line when the header would exceed rustfmt's `max_width`.",
+};
+
+pub struct ImplFolds;
+impl Rule for ImplFolds {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree, ctx.rustfmt)
+	}
+}
+
 const OPEN_MARKER: &str = "/*{{{1*/";
-const CLOSE_MARKER: &str = "//,}}}1";
-pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+pub(super) const CLOSE_MARKER: &str = "//,}}}1";
+pub fn check(path: &Path, content: &str, file: &syn::File, rustfmt: &RustfmtConfig) -> Vec<Violation> {
 	let path_str = path.display().to_string();
+	let lines = LineIndex::new(content);
 	let mut violations = Vec::new();
 
 	for item in &file.items {
@@ -32,11 +62,11 @@ pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
 		let end_line = span.end().line;
 		let end_col = span.end().column;
 
-		let start_byte = match span_position_to_byte(content, start_line, start_col) {
+		let start_byte = match lines.offset(start_line, start_col) {
 			Some(b) => b,
 			None => continue,
 		};
-		let end_byte = match span_position_to_byte(content, end_line, end_col) {
+		let end_byte = match lines.offset(end_line, end_col) {
 			Some(b) => b,
 			None => continue,
 		};
@@ -68,7 +98,7 @@ pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
 		}
 
 		// Generate the fix
-		let fix = generate_fix(content, start_byte, end_byte, brace_open_offset, has_open_marker, has_close_marker);
+		let fix = generate_fix(content, rustfmt, start_byte, end_byte, brace_open_offset, has_open_marker, has_close_marker);
 
 		let message = if !has_open_marker && !has_close_marker {
 			"impl block missing vim fold markers".to_string()
@@ -84,7 +114,9 @@ pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
 			line: start_line,
 			column: start_col,
 			message,
+			severity: Severity::Error,
 			fix: Some(fix),
+			..Default::default()
 		});
 	}
 
@@ -106,7 +138,7 @@ fn check_close_marker_after_impl(content: &str, impl_end_byte: usize) -> bool {
 	false
 }
 
-fn generate_fix(content: &str, start_byte: usize, end_byte: usize, brace_open_offset: usize, has_open: bool, has_close: bool) -> Fix {
+fn generate_fix(content: &str, rustfmt: &RustfmtConfig, start_byte: usize, end_byte: usize, brace_open_offset: usize, has_open: bool, has_close: bool) -> Fix {
 	let impl_text = &content[start_byte..end_byte];
 
 	let mut new_impl = String::new();
@@ -124,7 +156,12 @@ fn generate_fix(content: &str, start_byte: usize, end_byte: usize, brace_open_of
 		let trimmed_before = before_brace.trim_end();
 		new_impl.push_str(trimmed_before);
 
-		if brace_on_new_line {
+		// The header line as it would read with the marker appended
+		let line_start = content[..start_byte].rfind('\n').map_or(0, |i| i + 1);
+		let header_line = format!("{}{trimmed_before} {OPEN_MARKER} {{", &content[line_start..start_byte]);
+		let header_line = header_line.rsplit('\n').next().unwrap_or_default();
+
+		if brace_on_new_line || !rustfmt.fits(header_line) {
 			// Put marker on its own line before the brace
 			new_impl.push('\n');
 			new_impl.push_str(OPEN_MARKER);
@@ -158,24 +195,3 @@ fn generate_fix(content: &str, start_byte: usize, end_byte: usize, brace_open_of
 		replacement: new_impl,
 	}
 }
-
-fn span_position_to_byte(content: &str, line: usize, column: usize) -> Option<usize> {
-	let mut current_line = 1;
-	let mut line_start = 0;
-
-	for (i, ch) in content.char_indices() {
-		if current_line == line {
-			return Some(line_start + column);
-		}
-		if ch == '\n' {
-			current_line += 1;
-			line_start = i + 1;
-		}
-	}
-
-	if current_line == line {
-		return Some(line_start + column);
-	}
-
-	None
-}
@@ -2,25 +2,58 @@ use std::{collections::HashMap, path::Path};
 
 use syn::{Item, ItemEnum, ItemImpl, ItemStruct, ItemUnion, spanned::Spanned};
 
-use super::{Fix, Violation, skip::has_skip_marker_for_rule};
+use super::{
+	Fix, Label, Severity, Violation,
+	line_index::LineIndex,
+	rules::{Context, Rule, RuleInfo},
+	skip::has_skip_marker_for_rule,
+};
 
 const RULE: &str = "impl-follows-type";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "impl_follows_type",
+	autofix: true,
+	triggers: &["impl"],
+	description: "Place impl blocks directly after their type definition",
+	rationale: "Keeping an inherent impl right under its type means the definition and its methods are always read together.",
+	bad: "struct Foo;\n\nfn helper() {}\n\nimpl Foo {}",
+	good: "struct Foo;\nimpl Foo {}\n\nfn helper() {}",
+	fix: "Moves the impl block to directly after the type definition, with no blank lines in between.",
+};
+
+pub struct ImplFollowsType;
+impl Rule for ImplFollowsType {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
 pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
 	let path_str = path.display().to_string();
+	let lines = LineIndex::new(content);
 	let mut type_defs: HashMap<String, TypeDef> = HashMap::new();
 	let mut violations = Vec::new();
 
 	// First pass: collect all type definitions
 	for item in &file.items {
-		let (name, end_line) = match item {
-			Item::Struct(ItemStruct { ident, .. }) => (ident.to_string(), item.span().end().line),
-			Item::Enum(ItemEnum { ident, .. }) => (ident.to_string(), item.span().end().line),
-			Item::Union(ItemUnion { ident, .. }) => (ident.to_string(), item.span().end().line),
+		let ident = match item {
+			Item::Struct(ItemStruct { ident, .. }) | Item::Enum(ItemEnum { ident, .. }) | Item::Union(ItemUnion { ident, .. }) => ident,
 			_ => continue,
 		};
 
-		let end_byte = span_position_to_byte(content, item.span().end().line, item.span().end().column).unwrap_or(0);
-		type_defs.insert(name, TypeDef { end_line, end_byte });
+		let end_line = item.span().end().line;
+		let end_byte = lines.offset(end_line, item.span().end().column).unwrap_or(0);
+		let ident_start = ident.span().start();
+		type_defs.insert(ident.to_string(), TypeDef {
+			end_line,
+			end_byte,
+			ident: (ident_start.line, ident_start.column),
+		});
 	}
 
 	// Second pass: collect impl blocks with their byte positions
@@ -53,8 +86,8 @@ pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
 			}
 
 			let start_line = impl_block.span().start().line;
-			let start_byte = span_position_to_byte(content, start_line, impl_block.span().start().column)?;
-			let end_byte = span_position_to_byte(content, impl_block.span().end().line, impl_block.span().end().column)?;
+			let start_byte = lines.offset(start_line, impl_block.span().start().column)?;
+			let end_byte = lines.offset_of(impl_block.span().end())?;
 
 			Some(ImplBlock {
 				item: impl_block,
@@ -87,15 +120,23 @@ pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
 			// Calculate fix: extract impl block text and create two fixes
 			// 1. Delete impl block from current location (including leading newlines)
 			// 2. Insert impl block after type definition
-			let fix = create_relocation_fix(content, type_def, impl_block);
+			let fix = create_relocation_fix(&lines, type_def, impl_block);
 
 			violations.push(Violation {
 				rule: RULE,
 				file: path_str.clone(),
 				line: impl_block.start_line,
 				column: impl_block.item.span().start().column,
+				end: Some((impl_block.item.self_ty.span().end().line, impl_block.item.self_ty.span().end().column)),
 				message: format!("`impl {type_name}` should follow type definition (line {}), but has {gap} blank line(s)", type_def.end_line),
+				severity: Severity::Error,
 				fix,
+				labels: vec![Label {
+					line: type_def.ident.0,
+					column: type_def.ident.1,
+					message: format!("`{type_name}` defined here"),
+				}],
+				..Default::default()
 			});
 		}
 
@@ -105,6 +146,7 @@ pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
 			TypeDef {
 				end_line: impl_block.item.span().end().line,
 				end_byte: impl_block.end_byte,
+				ident: type_def.ident,
 			},
 		);
 	}
@@ -115,6 +157,8 @@ pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
 struct TypeDef {
 	end_line: usize,
 	end_byte: usize,
+	/// Where the type's name is, as `(line, column)`
+	ident: (usize, usize),
 }
 
 struct ImplBlock<'a> {
@@ -128,16 +172,17 @@ struct ImplBlock<'a> {
 /// The fix replaces the region from type_def end to impl_block end with:
 /// - The impl block text (moved to right after type def)
 /// - Followed by any code that was between them
-fn create_relocation_fix(content: &str, type_def: &TypeDef, impl_block: &ImplBlock) -> Option<Fix> {
+fn create_relocation_fix(lines: &LineIndex, type_def: &TypeDef, impl_block: &ImplBlock) -> Option<Fix> {
+	let content = lines.text();
 	// Find the start of the impl block including any leading whitespace/newlines on that line
-	let impl_line_start = find_line_start(content, impl_block.start_byte);
+	let impl_line_start = lines.line_start(impl_block.start_byte);
 
 	// Extract the impl block text (from line start to end of impl block)
 	let impl_text = &content[impl_line_start..impl_block.end_byte];
 
 	// Find where to insert: right after type_def ends
 	// We want to find the newline after type_def.end_byte
-	let insert_pos = find_line_end(content, type_def.end_byte);
+	let insert_pos = lines.line_end(type_def.end_byte);
 
 	// Check what's between type def and impl block
 	let between_text = &content[insert_pos..impl_line_start];
@@ -162,36 +207,3 @@ fn create_relocation_fix(content: &str, type_def: &TypeDef, impl_block: &ImplBlo
 		})
 	}
 }
-
-/// Convert a line/column position to byte offset in content.
-/// Lines are 1-indexed, columns are 0-indexed (byte offset within line).
-fn span_position_to_byte(content: &str, line: usize, column: usize) -> Option<usize> {
-	let mut current_line = 1;
-	let mut line_start = 0;
-
-	for (i, ch) in content.char_indices() {
-		if current_line == line {
-			return Some(line_start + column);
-		}
-		if ch == '\n' {
-			current_line += 1;
-			line_start = i + 1;
-		}
-	}
-
-	if current_line == line {
-		return Some(line_start + column);
-	}
-
-	None
-}
-
-/// Find the byte position of the start of the line containing `pos`
-fn find_line_start(content: &str, pos: usize) -> usize {
-	content[..pos].rfind('\n').map(|i| i + 1).unwrap_or(0)
-}
-
-/// Find the byte position of the end of the line containing `pos` (the newline char position)
-fn find_line_end(content: &str, pos: usize) -> usize {
-	content[pos..].find('\n').map(|i| pos + i).unwrap_or(content.len())
-}
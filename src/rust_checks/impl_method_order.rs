@@ -0,0 +1,158 @@
+//! Lint to order the items of inherent impls like `pub_first` orders a file's.
+//!
+//! Associated consts and types come first, then constructors (`new`, `try_new` and `from_*` fns taking no `self`),
+//! then `pub` methods, then the rest, so an impl says how to get a value and what it offers before how it works.
+//! Trait impls are left alone, as their order is the trait's, and so are impls with macro invocations among their
+//! items, which can't be placed.
+//!
+//! The fix moves each item with the doc comments, attributes and comment lines above it, keeping the order within each
+//! group and the blank lines between items where they were. It needs every item on lines of its own.
+
+use std::path::Path;
+
+use syn::{ImplItem, ImplItemFn, ItemImpl, Visibility, spanned::Spanned, visit::Visit};
+
+use super::{
+	Fix, Severity, Violation,
+	line_index::LineIndex,
+	pub_fields_first::{comments_start, only_comments},
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
+
+const RULE: &str = "impl-method-order";
+/// What the items of each rank are, in order
+const GROUPS: &[&str] = &["associated consts and types", "constructors", "`pub` methods", "private methods"];
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "impl_method_order",
+	autofix: true,
+	triggers: &["impl"],
+	description: "Order inherent impls: associated items, constructors, pub methods, then private ones",
+	rationale: "An impl reads as its type's interface when how to get a value and what it offers come before the helpers it's built from, as `pub_first` has it for files.",
+	bad: "impl Client {\n\tfn sign(&self) {}\n\n\tpub fn send(&self) {}\n\n\tpub fn new() -> Self {\n\t\tSelf\n\t}\n}",
+	good: "impl Client {\n\tpub fn new() -> Self {\n\t\tSelf\n\t}\n\n\tpub fn send(&self) {}\n\n\tfn sign(&self) {}\n}",
+	fix: "Moves the items into order, with their doc comments and attributes, keeping the order within each group.",
+};
+
+pub struct ImplMethodOrder;
+impl Rule for ImplMethodOrder {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+	let visitor = OrderVisitor {
+		path_str: path.display().to_string(),
+		lines: LineIndex::new(content),
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+struct OrderVisitor<'a> {
+	path_str: String,
+	lines: LineIndex<'a>,
+	violations: Vec<Violation>,
+}
+
+impl OrderVisitor<'_> {
+	fn check_impl(&mut self, node: &ItemImpl) {
+		if node.trait_.is_some() {
+			return;
+		}
+		let Some(ranks) = node.items.iter().map(rank).collect::<Option<Vec<usize>>>() else {
+			return;
+		};
+		let mut highest = 0;
+		let Some((misplaced, after)) = ranks.iter().enumerate().find_map(|(i, &rank)| {
+			let after = highest;
+			highest = highest.max(rank);
+			(rank < after).then_some((i, after))
+		}) else {
+			return;
+		};
+		let item = &node.items[misplaced];
+		let (kind, ident) = match (item, ranks[misplaced]) {
+			(ImplItem::Const(c), _) => ("associated const", &c.ident),
+			(ImplItem::Type(t), _) => ("associated type", &t.ident),
+			(ImplItem::Fn(f), 1) => ("constructor", &f.sig.ident),
+			(ImplItem::Fn(f), 2) => ("`pub` method", &f.sig.ident),
+			(ImplItem::Fn(f), _) => ("method", &f.sig.ident),
+			_ => return,
+		};
+		let start = ident.span().start();
+		let end = ident.span().end();
+		self.violations.push(Violation {
+			rule: RULE,
+			file: self.path_str.clone(),
+			line: start.line,
+			column: start.column,
+			end: Some((end.line, end.column)),
+			message: format!(
+				"{kind} `{ident}` comes after {}\nHINT: order an impl's items: associated consts and types, constructors, `pub` methods, then the rest",
+				GROUPS[after]
+			),
+			severity: Severity::Error,
+			fix: self.reorder(node, &ranks),
+			..Default::default()
+		});
+	}
+
+	/// Fix stably sorting the impl's items by `ranks`, each with the lines it spans, leaving what's between them in place.
+	fn reorder(&self, node: &ItemImpl, ranks: &[usize]) -> Option<Fix> {
+		let text = self.lines.text();
+		let open = self.lines.offset_of(node.brace_token.span.open().end())?;
+		let close = self.lines.offset_of(node.brace_token.span.close().start())?;
+		let start_byte = self.lines.line_end(open) + 1;
+		let mut gaps = Vec::new();
+		let mut chunks = Vec::new();
+		let mut at = start_byte;
+		for item in &node.items {
+			let item_start = self.lines.offset_of(item.span().start())?;
+			let start = comments_start(&self.lines, self.lines.line_start(item_start));
+			let end = self.lines.line_end(self.lines.offset_of(item.span().end())?) + 1;
+			// Nothing but the item may be on its lines, and nothing but blank lines between it and the one before
+			if start < at || end > self.lines.line_start(close) || !text[at..start].trim().is_empty() || !only_comments(&text[start..item_start]) {
+				return None;
+			}
+			gaps.push(&text[at..start]);
+			chunks.push(&text[start..end]);
+			at = end;
+		}
+		let mut order: Vec<usize> = (0..chunks.len()).collect();
+		order.sort_by_key(|&i| ranks[i]);
+		let replacement = gaps.iter().zip(order).map(|(gap, i)| format!("{gap}{}", chunks[i])).collect();
+		Some(Fix { start_byte, end_byte: at, replacement })
+	}
+}
+
+impl<'a> Visit<'a> for OrderVisitor<'_> {
+	fn visit_item_impl(&mut self, node: &'a ItemImpl) {
+		self.check_impl(node);
+		syn::visit::visit_item_impl(self, node);
+	}
+}
+
+/// Index into [`GROUPS`] of the group `item` belongs in, or `None` for a macro invocation.
+fn rank(item: &ImplItem) -> Option<usize> {
+	match item {
+		ImplItem::Const(_) | ImplItem::Type(_) => Some(0),
+		ImplItem::Fn(f) if is_constructor(f) => Some(1),
+		ImplItem::Fn(f) if matches!(f.vis, Visibility::Public(_)) => Some(2),
+		ImplItem::Fn(_) => Some(3),
+		_ => None,
+	}
+}
+
+fn is_constructor(f: &ImplItemFn) -> bool {
+	let name = f.sig.ident.to_string();
+	f.sig.receiver().is_none() && (name == "new" || name == "try_new" || name.starts_with("from_"))
+}
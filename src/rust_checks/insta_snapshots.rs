@@ -3,10 +3,49 @@ use std::{collections::HashSet, path::Path};
 use proc_macro2::{Span, TokenTree};
 use syn::{ExprMacro, ItemFn, Macro, spanned::Spanned, visit::Visit};
 
-use super::{Fix, Violation, skip::SkipVisitor};
+use super::{
+	Fix, Severity, Violation,
+	line_index::LineIndex,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
 
 const RULE_INLINE: &str = "insta-inline-snapshot";
 const RULE_SEQUENTIAL: &str = "insta-sequential-snapshots";
+pub const INLINE_INFO: RuleInfo = RuleInfo {
+	id: RULE_INLINE,
+	option: "insta_inline_snapshot",
+	autofix: true,
+	triggers: &["snapshot"],
+	description: "Use inline @\"\" insta snapshots instead of .snap files",
+	rationale: "Inline snapshots keep the expected output next to the assertion, so tests can be reviewed without opening `.snap` files.",
+	bad: "insta::assert_snapshot!(output);",
+	good: "insta::assert_snapshot!(output, @\"\");",
+	fix: "Adds an empty `@\"\"` snapshot and deletes `.snap`/`.pending-snap` files; run `cargo insta accept` to fill the snapshots in.",
+};
+pub const SEQUENTIAL_INFO: RuleInfo = RuleInfo {
+	id: RULE_SEQUENTIAL,
+	option: "insta_inline_snapshot",
+	autofix: false,
+	triggers: &["snapshot"],
+	description: "Disallow several snapshot assertions in one test",
+	rationale: "With several snapshots in one test, a failure in the first hides whether the rest still pass, and `cargo insta` reviews them out of context.",
+	bad: "assert_snapshot!(a, @\"1\");\nassert_snapshot!(b, @\"2\");",
+	good: "assert_snapshot!(format!(\"{a}\\n{b}\"), @\"\n1\n2\n\");",
+	fix: "Join the tested strings into one snapshot, or split the test.",
+};
+
+/// Both insta rules, which share an option and a pass over the file
+pub struct InstaSnapshots;
+impl Rule for InstaSnapshots {
+	fn info(&self) -> &'static RuleInfo {
+		&INLINE_INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree, ctx.fixing)
+	}
+}
 
 const INSTA_SNAPSHOT_MACROS: &[&str] = &[
 	"assert_snapshot",
@@ -95,7 +134,9 @@ impl<'a> InstaSnapshotVisitor<'a> {
 				line: start_line(mac.span()),
 				column: start_column(mac.span()),
 				message: format!("`{macro_name}!` must use inline snapshot with `@r\"\"` or `@\"\"`"),
+				severity: Severity::Error,
 				fix,
+				..Default::default()
 			});
 		}
 		// If it has an inline snapshot (empty or not), it's correct - never touch it
@@ -139,43 +180,18 @@ fn find_inline_snapshot(tokens: &[TokenTree]) -> Option<()> {
 }
 
 fn create_add_inline_snapshot_fix(mac: &Macro, content: &str) -> Option<Fix> {
-	let span = mac.span();
-	let lines: Vec<&str> = content.lines().collect();
-	let end_line_idx = span.end().line - 1;
-
-	if end_line_idx >= lines.len() {
-		return None;
-	}
-
-	let line = lines[end_line_idx];
+	let lines = LineIndex::new(content);
+	let end = mac.span().end();
+	let line_start = lines.offset(end.line, 0)?;
 
 	// Find the closing ) of the macro on this line
 	// The macro span ends at the closing ), we need to insert before it
-	let end_col = span.end().column;
-
-	// Calculate byte position
-	let mut line_start_byte = 0;
-	for (i, l) in lines.iter().enumerate() {
-		if i == end_line_idx {
-			break;
-		}
-		line_start_byte += l.len() + 1;
-	}
-
-	// Find the closing parenthesis position
-	// We want to insert `, @""` before the closing )
-	let closing_paren_pos = if end_col > 0 && end_col <= line.len() {
+	let closing_paren_pos = match lines.offset_of(end).filter(|_| end.column > 0) {
 		// span.end() usually points just after the ), so we need the position of )
-		let pos = line_start_byte + end_col - 1;
-		// Verify it's actually a )
-		if content.as_bytes().get(pos) == Some(&b')') {
-			Some(pos)
-		} else {
-			// Search backwards for )
-			find_closing_paren_before(content, line_start_byte + end_col)
-		}
-	} else {
-		find_closing_paren_before(content, line_start_byte + line.len())
+		Some(after) if content.as_bytes()[after - 1] == b')' => Some(after - 1),
+		// Search backwards for )
+		Some(after) => find_closing_paren_before(content, after),
+		None => find_closing_paren_before(content, lines.line_end(line_start)),
 	};
 
 	let paren_pos = closing_paren_pos?;
@@ -193,14 +209,9 @@ fn create_add_inline_snapshot_fix(mac: &Macro, content: &str) -> Option<Fix> {
 	})
 }
 
+/// The last `)` within 50 bytes before `max_pos`.
 fn find_closing_paren_before(content: &str, max_pos: usize) -> Option<usize> {
-	let search_start = max_pos.saturating_sub(50);
-	for (i, c) in content[search_start..max_pos].char_indices().rev() {
-		if c == ')' {
-			return Some(search_start + i);
-		}
-	}
-	None
+	content[..max_pos].rfind(')').filter(|&i| i + 50 >= max_pos)
 }
 
 /// Visitor that detects sequential snapshot assertions within the same function
@@ -246,7 +257,8 @@ impl SequentialSnapshotVisitor {
 					join tested strings together or split into separate tests",
 					first.0,
 				),
-				fix: None,
+				severity: Severity::Error,
+				..Default::default()
 			});
 		}
 	}
@@ -1,8 +1,35 @@
 use syn::{ItemFn, spanned::Spanned};
 
-use super::{FileInfo, Violation, skip::has_skip_marker_for_rule};
+use super::{
+	FileInfo, Severity, Violation,
+	rules::{Context, Rule, RuleInfo},
+	skip::has_skip_marker_for_rule,
+};
 
 const RULE: &str = "instrument";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "instrument",
+	autofix: false,
+	triggers: &["async"],
+	description: "Require #[instrument] on async functions",
+	rationale: "Async code has no useful stack traces; `#[instrument]` spans are what ties log lines back to the call that produced them.",
+	bad: "async fn fetch(url: &str) -> Result<String> { ... }",
+	good: "#[instrument]\nasync fn fetch(url: &str) -> Result<String> { ... }",
+	fix: "Add `#[instrument]`, skipping arguments that shouldn't be logged.",
+};
+
+pub struct Instrument;
+impl Rule for Instrument {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check_instrument(ctx.file)
+	}
+}
+
 pub fn check_instrument(file_info: &FileInfo) -> Vec<Violation> {
 	let mut violations = Vec::new();
 	let filename = file_info.path.file_name().and_then(|f| f.to_str()).unwrap_or("");
@@ -30,7 +57,8 @@ pub fn check_instrument(file_info: &FileInfo) -> Vec<Violation> {
 			line: span_start.line,
 			column: span_start.column,
 			message: format!("No #[instrument] on async fn `{}`", func.sig.ident),
-			fix: None,
+			severity: Severity::Error,
+			..Default::default()
 		});
 	}
 	violations
@@ -2,11 +2,41 @@ use std::{collections::HashMap, path::Path};
 
 use syn::{Item, spanned::Spanned};
 
-use super::{Fix, Violation, skip::has_skip_marker_for_rule};
+use super::{
+	Fix, Label, Severity, Violation,
+	line_index::LineIndex,
+	rules::{Context, Rule, RuleInfo},
+	rustfmt::RustfmtConfig,
+	skip::has_skip_marker_for_rule,
+};
 
 const RULE: &str = "join-split-impls";
-pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "join_split_impls",
+	autofix: true,
+	triggers: &["impl"],
+	description: "Join split inherent impl blocks of the same type",
+	rationale: "Spreading one type's inherent methods across several impl blocks makes it hard to see everything the type offers.",
+	bad: "impl Foo {\n\tfn a(&self) {}\n}\nimpl Foo {\n\tfn b(&self) {}\n}",
+	good: "impl Foo {\n\tfn a(&self) {}\n\n\tfn b(&self) {}\n}",
+	fix: "Moves the bodies of later blocks into the first one and removes the empty blocks.",
+};
+
+pub struct JoinSplitImpls;
+impl Rule for JoinSplitImpls {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree, ctx.rustfmt)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File, rustfmt: &RustfmtConfig) -> Vec<Violation> {
 	let path_str = path.display().to_string();
+	let lines = LineIndex::new(content);
 	let mut violations = Vec::new();
 
 	// Group inherent impl blocks by type signature (including generics)
@@ -36,8 +66,8 @@ pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
 		let impl_signature = quote::quote!(#generics #self_ty).to_string();
 
 		let start_line = impl_block.span().start().line;
-		let start_byte = span_position_to_byte(content, start_line, impl_block.span().start().column);
-		let end_byte = span_position_to_byte(content, impl_block.span().end().line, impl_block.span().end().column);
+		let start_byte = lines.offset(start_line, impl_block.span().start().column);
+		let end_byte = lines.offset_of(impl_block.span().end());
 
 		let (Some(start_byte), Some(end_byte)) = (start_byte, end_byte) else {
 			continue;
@@ -115,7 +145,7 @@ pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
 		let mut replacement = String::from(impl_header);
 		replacement.push('\n');
 
-		// Add all items - they already have proper indentation
+		// Add all items - they keep their own indentation until `conform` applies the project's rustfmt.toml
 		for (i, items) in all_items_parts.iter().enumerate() {
 			if i > 0 {
 				replacement.push('\n');
@@ -134,7 +164,7 @@ pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
 		let fix = Some(Fix {
 			start_byte: first.start_byte,
 			end_byte: last.end_byte,
-			replacement,
+			replacement: rustfmt.conform(&replacement),
 		});
 
 		violations.push(Violation {
@@ -143,7 +173,14 @@ pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
 			line: impl_blocks[1].start_line,
 			column: 0,
 			message: format!("split `impl {impl_signature}` blocks should be joined into one"),
+			severity: Severity::Error,
 			fix,
+			labels: vec![Label {
+				line: first.start_line,
+				column: 0,
+				message: "first block, which the rest should be joined into".to_string(),
+			}],
+			..Default::default()
 		});
 	}
 
@@ -160,31 +197,9 @@ struct ImplBlockInfo {
 	items_text: String,
 }
 
-/// Convert a line/column position to byte offset in content.
-fn span_position_to_byte(content: &str, line: usize, column: usize) -> Option<usize> {
-	let mut current_line = 1;
-	let mut line_start = 0;
-
-	for (i, ch) in content.char_indices() {
-		if current_line == line {
-			return Some(line_start + column);
-		}
-		if ch == '\n' {
-			current_line += 1;
-			line_start = i + 1;
-		}
-	}
-
-	if current_line == line {
-		return Some(line_start + column);
-	}
-
-	None
-}
-
 /// Find the opening brace of an impl block, skipping braces inside comments.
 /// This handles fold markers like `/*{{{1*/` which contain braces in comments.
-fn find_impl_brace(text: &str) -> Option<usize> {
+pub(super) fn find_impl_brace(text: &str) -> Option<usize> {
 	let mut in_block_comment = false;
 	let mut in_line_comment = false;
 	let chars: Vec<char> = text.chars().collect();
@@ -227,7 +242,7 @@ fn find_impl_brace(text: &str) -> Option<usize> {
 }
 
 /// Strip leading and trailing blank lines from text, preserving internal structure.
-fn strip_blank_lines(text: &str) -> String {
+pub(super) fn strip_blank_lines(text: &str) -> String {
 	let lines: Vec<&str> = text.lines().collect();
 
 	// Find first non-empty line
@@ -0,0 +1,315 @@
+//! Lint to prefer `let ... else` over a `match` or `if let` whose other branch only diverges.
+//!
+//! `let x = match opt { Some(v) => v, None => return };` and `let x = if let Some(v) = opt { v } else { return };` are
+//! rewritten to `let Some(x) = opt else { return };`, as long as the diverging branch binds nothing, since `let ... else`
+//! has nothing to bind it to. A statement `if let P = e { .. } else { return; }` is flagged too, but left to fix by
+//! hand, as its body has to move out of the block.
+
+use std::path::Path;
+
+use syn::{Arm, BinOp, Block, Expr, ExprIf, ExprLet, ExprMatch, Local, LocalInit, Macro, Pat, PatIdent, Stmt, spanned::Spanned, visit::Visit};
+
+use super::{
+	Fix, Severity, Violation,
+	line_index::LineIndex,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
+
+const RULE: &str = "let-else";
+/// Macros that never return
+const DIVERGING_MACROS: &[&str] = &["panic", "unreachable", "todo", "unimplemented", "bail"];
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "let_else",
+	autofix: true,
+	triggers: &["match", "if let"],
+	description: "Prefer let-else over a match or if-let that only unwraps or diverges",
+	rationale: "`let ... else` keeps the happy path at the top level and says up front that the other case leaves, instead of spelling out an arm that hands the value back.",
+	bad: "let user = match users.get(id) {\n\tSome(user) => user,\n\tNone => return,\n};",
+	good: "let Some(user) = users.get(id) else { return };",
+	fix: "Rewrites `let x = match ..` and `let x = if let ..` with one diverging branch to `let <pattern> = .. else { .. };`. A statement `if let` is only reported.",
+};
+
+pub struct LetElse;
+impl Rule for LetElse {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+	let visitor = LetElseVisitor {
+		path_str: path.display().to_string(),
+		lines: LineIndex::new(content),
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+struct LetElseVisitor<'a> {
+	path_str: String,
+	lines: LineIndex<'a>,
+	violations: Vec<Violation>,
+}
+
+/// A `let x = ..` whose value is unwrapped out of a pattern, with a branch that diverges otherwise.
+struct Unwrap<'a> {
+	/// Pattern the value is taken out of, e.g. `Some(v)`
+	pat: &'a Pat,
+	/// The binding in `pat` that is the value
+	binding: &'a PatIdent,
+	scrutinee: &'a Expr,
+	/// Body of the diverging branch
+	diverge: &'a Expr,
+}
+
+impl LetElseVisitor<'_> {
+	fn check_local(&mut self, local: &Local) {
+		let Some(LocalInit { expr, diverge: None, .. }) = &local.init else {
+			return;
+		};
+		let (unwrap, construct) = match &**expr {
+			Expr::Match(expr_match) => (match_unwrap(expr_match), "match"),
+			Expr::If(expr_if) => (if_let_unwrap(expr_if), "if let"),
+			_ => return,
+		};
+		let Some(unwrap) = unwrap else {
+			return;
+		};
+		let pat = self.let_pattern(local, &unwrap);
+		let fix = pat.as_ref().and_then(|pat| self.fix(local, &unwrap, pat));
+		let pat = pat.as_deref().or_else(|| self.text(unwrap.pat)).unwrap_or_default();
+		let start = local.let_token.span.start();
+		let end = expr.span().end();
+		self.violations.push(Violation {
+			rule: RULE,
+			file: self.path_str.clone(),
+			line: start.line,
+			column: start.column,
+			end: Some((end.line, end.column)),
+			message: format!("`{construct}` that only unwraps `{pat}` or diverges\nHINT: use `let {pat} = .. else {{ .. }};`"),
+			severity: Severity::Error,
+			fix,
+			..Default::default()
+		});
+	}
+
+	fn check_if_stmt(&mut self, expr_if: &ExprIf) {
+		let Expr::Let(ExprLet { pat, .. }) = &*expr_if.cond else {
+			return;
+		};
+		let Some((_, else_branch)) = &expr_if.else_branch else {
+			return;
+		};
+		if !matches!(&**else_branch, Expr::Block(_)) || !diverges(else_branch) {
+			return;
+		}
+		let pat = self.text(pat).unwrap_or_default();
+		let start = expr_if.if_token.span.start();
+		let end = expr_if.cond.span().end();
+		self.violations.push(Violation {
+			rule: RULE,
+			file: self.path_str.clone(),
+			line: start.line,
+			column: start.column,
+			end: Some((end.line, end.column)),
+			message: format!("`if let {pat}` whose `else` only diverges\nHINT: use `let {pat} = .. else {{ .. }};` and dedent the body"),
+			severity: Severity::Error,
+			..Default::default()
+		});
+	}
+
+	/// The unwrapped pattern with its binding renamed to what the `let` binds, for a `let` of a plain name.
+	fn let_pattern(&self, local: &Local, unwrap: &Unwrap) -> Option<String> {
+		let Pat::Ident(name @ PatIdent { by_ref: None, subpat: None, .. }) = &local.pat else {
+			return None;
+		};
+		let pat_start = self.lines.offset_of(unwrap.pat.span().start())?;
+		let binding_start = self.lines.offset_of(unwrap.binding.span().start())?;
+		let binding_end = self.lines.offset_of(unwrap.binding.span().end())?;
+		let pat_end = self.lines.offset_of(unwrap.pat.span().end())?;
+		let content = self.lines.text();
+		Some(format!("{}{}{}", &content[pat_start..binding_start], self.text(name)?, &content[binding_end..pat_end]))
+	}
+
+	/// `let <pat> = <scrutinee> else <diverge>;` in place of the whole `let`.
+	fn fix(&self, local: &Local, unwrap: &Unwrap, pat: &str) -> Option<Fix> {
+		let scrutinee = self.text(unwrap.scrutinee)?;
+		// `let .. else` can't take an expression ending in `}`, nor a bare `&&`/`||`
+		let scrutinee = match unwrap.scrutinee {
+			Expr::Binary(binary) if matches!(binary.op, BinOp::And(_) | BinOp::Or(_)) => format!("({scrutinee})"),
+			_ if scrutinee.ends_with('}') => format!("({scrutinee})"),
+			_ => scrutinee.to_string(),
+		};
+		let diverge = match unwrap.diverge {
+			// The block moves out of an arm or `else`, so its lines move out to the `let`'s indentation
+			Expr::Block(block) if block.label.is_none() => {
+				let from = self.indent_at(block.span().start().line)?;
+				let to = self.indent_at(local.let_token.span.start().line)?;
+				self.text(block)?.replace(&format!("\n{from}"), &format!("\n{to}"))
+			}
+			diverge => format!("{{ {} }}", self.text(diverge)?),
+		};
+
+		let semi = local.semi_token.span.end();
+		Some(Fix {
+			start_byte: self.lines.offset_of(local.let_token.span.start())?,
+			end_byte: self.lines.offset_of(semi)?,
+			replacement: format!("let {pat} = {scrutinee} else {diverge};"),
+		})
+	}
+
+	/// Leading whitespace of the 1-based `line`.
+	fn indent_at(&self, line: usize) -> Option<&str> {
+		let start = self.lines.offset(line, 0)?;
+		let rest = &self.lines.text()[start..];
+		Some(&rest[..rest.len() - rest.trim_start_matches([' ', '\t']).len()])
+	}
+
+	fn text(&self, node: &impl Spanned) -> Option<&str> {
+		let span = node.span();
+		let start = self.lines.offset_of(span.start())?;
+		let end = self.lines.offset_of(span.end())?;
+		self.lines.text().get(start..end)
+	}
+}
+
+impl<'a> Visit<'a> for LetElseVisitor<'_> {
+	fn visit_local(&mut self, node: &'a Local) {
+		self.check_local(node);
+		syn::visit::visit_local(self, node);
+	}
+
+	fn visit_block(&mut self, node: &'a Block) {
+		for stmt in &node.stmts {
+			if let Stmt::Expr(Expr::If(expr_if), _) = stmt {
+				self.check_if_stmt(expr_if);
+			}
+		}
+		syn::visit::visit_block(self, node);
+	}
+}
+
+/// `match e { P(v) => v, _ => return }`, in either arm order, without guards.
+fn match_unwrap(expr_match: &ExprMatch) -> Option<Unwrap<'_>> {
+	let [first, second] = expr_match.arms.as_slice() else {
+		return None;
+	};
+	if first.guard.is_some() || second.guard.is_some() {
+		return None;
+	}
+	arm_unwrap(&expr_match.expr, first, second).or_else(|| arm_unwrap(&expr_match.expr, second, first))
+}
+
+/// `value`'s pattern bound by `let`, if `value` hands its one binding back and `other` diverges without binding anything.
+fn arm_unwrap<'a>(scrutinee: &'a Expr, value: &'a Arm, other: &'a Arm) -> Option<Unwrap<'a>> {
+	let binding = single_binding(&value.pat)?;
+	if !returns_binding(&value.body, binding) || !binds_nothing(&other.pat) || !diverges(&other.body) {
+		return None;
+	}
+	Some(Unwrap {
+		pat: &value.pat,
+		binding,
+		scrutinee,
+		diverge: &other.body,
+	})
+}
+
+/// `if let P(v) = e { v } else { return }`.
+fn if_let_unwrap(expr_if: &ExprIf) -> Option<Unwrap<'_>> {
+	let Expr::Let(ExprLet { pat, expr, .. }) = &*expr_if.cond else {
+		return None;
+	};
+	let (_, else_branch) = expr_if.else_branch.as_ref()?;
+	let binding = single_binding(pat)?;
+	let [Stmt::Expr(value, None)] = expr_if.then_branch.stmts.as_slice() else {
+		return None;
+	};
+	if !matches!(&**else_branch, Expr::Block(_)) || !returns_binding(value, binding) || !diverges(else_branch) {
+		return None;
+	}
+	Some(Unwrap {
+		pat,
+		binding,
+		scrutinee: expr,
+		diverge: else_branch,
+	})
+}
+
+/// The only name `pat` binds, if it binds exactly one, by value, inside a pattern that can fail to match.
+fn single_binding(pat: &Pat) -> Option<&PatIdent> {
+	if matches!(pat, Pat::Ident(_) | Pat::Wild(_)) {
+		return None;
+	}
+	let mut bindings = Vec::new();
+	collect_bindings(pat, &mut bindings);
+	match bindings.as_slice() {
+		[binding] if binding.by_ref.is_none() && binding.subpat.is_none() => Some(binding),
+		_ => None,
+	}
+}
+
+fn binds_nothing(pat: &Pat) -> bool {
+	let mut bindings = Vec::new();
+	collect_bindings(pat, &mut bindings);
+	bindings.is_empty()
+}
+
+fn collect_bindings<'a>(pat: &'a Pat, bindings: &mut Vec<&'a PatIdent>) {
+	match pat {
+		// `None` and other unit variants parse as idents too
+		Pat::Ident(ident) if ident.subpat.is_none() && ident.ident.to_string().starts_with(char::is_uppercase) => {}
+		Pat::Ident(ident) => {
+			bindings.push(ident);
+			if let Some((_, subpat)) = &ident.subpat {
+				collect_bindings(subpat, bindings);
+			}
+		}
+		Pat::TupleStruct(tuple) => tuple.elems.iter().for_each(|elem| collect_bindings(elem, bindings)),
+		Pat::Tuple(tuple) => tuple.elems.iter().for_each(|elem| collect_bindings(elem, bindings)),
+		Pat::Struct(strukt) => strukt.fields.iter().for_each(|field| collect_bindings(&field.pat, bindings)),
+		Pat::Slice(slice) => slice.elems.iter().for_each(|elem| collect_bindings(elem, bindings)),
+		Pat::Paren(paren) => collect_bindings(&paren.pat, bindings),
+		Pat::Reference(reference) => collect_bindings(&reference.pat, bindings),
+		Pat::Type(typed) => collect_bindings(&typed.pat, bindings),
+		Pat::Or(or) => or.cases.iter().for_each(|case| collect_bindings(case, bindings)),
+		_ => {}
+	}
+}
+
+/// Whether `body` is just `binding`, braced or not.
+fn returns_binding(body: &Expr, binding: &PatIdent) -> bool {
+	match body {
+		Expr::Path(path) => path.qself.is_none() && path.path.is_ident(&binding.ident),
+		Expr::Block(block) if block.label.is_none() => match block.block.stmts.as_slice() {
+			[Stmt::Expr(value, None)] => returns_binding(value, binding),
+			_ => false,
+		},
+		_ => false,
+	}
+}
+
+/// Whether evaluating `expr` never finishes normally.
+fn diverges(expr: &Expr) -> bool {
+	match expr {
+		Expr::Return(_) | Expr::Continue(_) | Expr::Break(_) => true,
+		Expr::Macro(mac) => is_diverging_macro(&mac.mac),
+		Expr::Block(block) if block.label.is_none() => match block.block.stmts.last() {
+			Some(Stmt::Expr(last, _)) => diverges(last),
+			Some(Stmt::Macro(mac)) => is_diverging_macro(&mac.mac),
+			_ => false,
+		},
+		_ => false,
+	}
+}
+
+fn is_diverging_macro(mac: &Macro) -> bool {
+	mac.path.segments.last().is_some_and(|last| DIVERGING_MACROS.iter().any(|name| last.ident == name))
+}
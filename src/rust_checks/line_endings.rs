@@ -0,0 +1,68 @@
+//! `\r\n` support. Rules only ever see `\n`-terminated source: files are normalized when they're parsed, so every
+//! fix's byte offsets are relative to the normalized text. Whatever writes a fixed file back puts its ending back,
+//! and output that points into the file on disk maps offsets over with [`original_offset`].
+
+use std::borrow::Cow;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum LineEnding {
+	#[default]
+	Lf,
+	Crlf,
+}
+impl LineEnding {
+	/// The ending most of `source`'s lines use, `Lf` on a tie. A file with mixed endings is written back with this one throughout.
+	pub fn detect(source: &str) -> Self {
+		let crlf = source.matches("\r\n").count();
+		if crlf * 2 > source.matches('\n').count() { Self::Crlf } else { Self::Lf }
+	}
+
+	/// `source`, which is `\n`-terminated, with this ending.
+	pub fn apply(self, source: &str) -> Cow<'_, str> {
+		match self {
+			Self::Lf => Cow::Borrowed(source),
+			Self::Crlf => Cow::Owned(source.replace('\n', "\r\n")),
+		}
+	}
+}
+
+/// `source` with every `\r\n` turned into `\n`.
+pub fn normalize(source: &str) -> Cow<'_, str> {
+	if source.contains("\r\n") { Cow::Owned(source.replace("\r\n", "\n")) } else { Cow::Borrowed(source) }
+}
+
+/// Byte offset in `original` of `offset` into [`normalize`]d `original`. An offset at a line end lands before its `\r`.
+pub fn original_offset(original: &str, offset: usize) -> usize {
+	let mut removed = 0;
+	for (i, _) in original.match_indices("\r\n") {
+		// Where this line end's `\n` is once the `\r`s before it are gone
+		if offset <= i - removed {
+			break;
+		}
+		removed += 1;
+	}
+	offset + removed
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn detects_the_majority_ending() {
+		assert_eq!(LineEnding::detect("a\r\nb\r\nc\n"), LineEnding::Crlf);
+		assert_eq!(LineEnding::detect("a\r\nb\nc\n"), LineEnding::Lf);
+		assert_eq!(LineEnding::detect("no newline"), LineEnding::Lf);
+	}
+
+	#[test]
+	fn normalized_offsets_map_back() {
+		let original = "ab\r\ncd\r\nef";
+		let normalized = normalize(original);
+		assert_eq!(normalized, "ab\ncd\nef");
+		for (offset, expected) in [(0, 0), (2, 2), (3, 4), (5, 6), (6, 8), (8, 10)] {
+			assert_eq!(original_offset(original, offset), expected, "offset {offset}");
+		}
+		assert_eq!(LineEnding::Crlf.apply(&normalized), original);
+	}
+}
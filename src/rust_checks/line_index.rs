@@ -0,0 +1,106 @@
+//! Conversion between the positions `proc_macro2` reports — 1-based lines, 0-based columns counted in chars — and byte
+//! offsets into the source, which is what fixes are made of. Columns are never byte offsets: on a line with non-ASCII
+//! text before the span, the two differ.
+
+use proc_macro2::LineColumn;
+
+/// Where each line of a source starts, for mapping positions to byte offsets and back.
+#[derive(Clone, Debug)]
+pub struct LineIndex<'a> {
+	text: &'a str,
+	/// Byte offset of each line's first char
+	starts: Vec<usize>,
+}
+impl<'a> LineIndex<'a> {
+	pub fn new(text: &'a str) -> Self {
+		let starts = std::iter::once(0).chain(text.match_indices('\n').map(|(i, _)| i + 1)).collect();
+		Self { text, starts }
+	}
+
+	pub fn text(&self) -> &'a str {
+		self.text
+	}
+
+	/// Byte offset of the 1-based `line` and 0-based char `column`. `None` if the text has no such line, or the line no
+	/// such column; the column just past the line's last char is its end.
+	pub fn offset(&self, line: usize, column: usize) -> Option<usize> {
+		let start = *self.starts.get(line.checked_sub(1)?)?;
+		let text = &self.text[start..self.line_end(start)];
+		match text.char_indices().nth(column) {
+			Some((i, _)) => Some(start + i),
+			None => (column == text.chars().count()).then_some(start + text.len()),
+		}
+	}
+
+	/// [`Self::offset`] of a span's start or end.
+	pub fn offset_of(&self, pos: LineColumn) -> Option<usize> {
+		self.offset(pos.line, pos.column)
+	}
+
+	/// 1-based line and 0-based char column of the byte `offset`, which has to be on a char boundary.
+	pub fn position(&self, offset: usize) -> (usize, usize) {
+		let line = self.starts.partition_point(|&start| start <= offset);
+		let start = self.starts[line - 1];
+		(line, self.text[start..offset].chars().count())
+	}
+
+	/// Byte offset where the line containing `offset` starts.
+	pub fn line_start(&self, offset: usize) -> usize {
+		self.starts[self.starts.partition_point(|&start| start <= offset) - 1]
+	}
+
+	/// Byte offset of the `\n` ending the line containing `offset`, or the end of the text on the last line.
+	pub fn line_end(&self, offset: usize) -> usize {
+		let next = self.starts.partition_point(|&start| start <= offset);
+		self.starts.get(next).map_or(self.text.len(), |&start| start - 1)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn columns_count_chars_not_bytes() {
+		let text = "let é = \"ü\";\nfn ß() {}\n";
+		let index = LineIndex::new(text);
+		assert_eq!(index.offset(1, 4), text.find('é'));
+		assert_eq!(index.offset(1, 6), text.find('='));
+		assert_eq!(index.offset(1, 9), text.find('ü'));
+		assert_eq!(index.offset(1, 10), Some(text.find('ü').unwrap() + 'ü'.len_utf8()));
+		assert_eq!(index.offset(2, 3), text.find('ß'));
+		assert_eq!(index.offset(2, 4), text.find("()"));
+	}
+
+	#[test]
+	fn line_ends_are_addressable_but_nothing_past_them() {
+		let text = "aé\nb";
+		let index = LineIndex::new(text);
+		assert_eq!(index.offset(1, 2), Some(3));
+		assert_eq!(index.offset(1, 3), None);
+		assert_eq!(index.offset(2, 1), Some(text.len()));
+		assert_eq!(index.offset(3, 0), None);
+		assert_eq!(index.offset(0, 0), None);
+	}
+
+	#[test]
+	fn positions_round_trip() {
+		let text = "ab\nçd\n\nend";
+		let index = LineIndex::new(text);
+		for (offset, _) in text.char_indices() {
+			let (line, column) = index.position(offset);
+			assert_eq!(index.offset(line, column), Some(offset), "offset {offset}");
+		}
+		assert_eq!(index.position(text.len()), (4, 3));
+	}
+
+	#[test]
+	fn line_bounds() {
+		let text = "one\ntwö\n";
+		let index = LineIndex::new(text);
+		let two = text.find('t').unwrap();
+		assert_eq!((index.line_start(two + 2), index.line_end(two + 2)), (two, text.len() - 1));
+		assert_eq!((index.line_start(text.len()), index.line_end(text.len())), (text.len(), text.len()));
+		assert_eq!(index.line_end(0), 3);
+	}
+}
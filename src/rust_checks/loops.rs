@@ -1,8 +1,35 @@
 use syn::{Expr, Stmt, spanned::Spanned};
 
-use super::{FileInfo, Violation, skip::has_skip_marker_for_rule};
+use super::{
+	FileInfo, Severity, Violation,
+	rules::{Context, Rule, RuleInfo},
+	skip::has_skip_marker_for_rule,
+};
 
 const RULE: &str = "loop-comment";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "loops",
+	autofix: false,
+	triggers: &["loop"],
+	description: "Require a //LOOP comment on endless loops",
+	rationale: "A `loop` with no bound is where hangs come from; most can be written as `while let`, and the rest deserve a note on why they terminate.",
+	bad: "loop {\n\tlet msg = rx.recv()?;\n\thandle(msg);\n}",
+	good: "//LOOP: runs until the channel is closed, which ends the process\nloop {\n\tlet msg = rx.recv()?;\n\thandle(msg);\n}",
+	fix: "Rewrite with `while let`/`for`, or add a `//LOOP` comment justifying the unbounded loop.",
+};
+
+pub struct Loops;
+impl Rule for Loops {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check_loops(ctx.file)
+	}
+}
+
 pub fn check_loops(file_info: &FileInfo) -> Vec<Violation> {
 	let mut violations = Vec::new();
 	let path_str = file_info.path.display().to_string();
@@ -43,7 +70,8 @@ fn check_expr_for_loops(expr: &Expr, file_contents: &str, file_path: &str, viola
 					line: span_start.line,
 					column: span_start.column,
 					message: "Endless loop without `//LOOP` comment\nHINT: try to rewrite the loop with `while let` or justify why a bound can't be enforced".to_string(),
-					fix: None,
+					severity: Severity::Error,
+					..Default::default()
 				});
 			}
 			collect_loop_issues_from_stmts(&loop_expr.body.stmts, file_contents, file_path, violations);
@@ -0,0 +1,127 @@
+//! Lint to require doc comments on the public API of library crates.
+//!
+//! Checks `pub` functions (methods of inherent impls included), structs, enums and traits in library files, as
+//! [`super::no_panic::is_library_file`] defines them. Restricted visibility like `pub(crate)` isn't API and is exempt,
+//! as are `#[doc(hidden)]` items, private inline modules and test code. Generated files are never checked; other paths
+//! can be exempted with an `[override."<glob>"]` section setting `missing_docs = "allow"`.
+
+use std::path::Path;
+
+use syn::{Attribute, Ident, ImplItemFn, ItemEnum, ItemFn, ItemImpl, ItemMod, ItemStruct, ItemTrait, Meta, Visibility, visit::Visit};
+
+use super::{
+	Severity, Violation, no_panic,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+	test_context::TestContext,
+};
+
+const RULE: &str = "missing-docs";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "missing_docs",
+	autofix: false,
+	triggers: &["pub "],
+	description: "Require doc comments on public fns, structs, enums and traits of libraries",
+	rationale: "The public API is what other crates read without the implementation at hand; an undocumented item makes them guess.",
+	bad: "pub fn parse(s: &str) -> Result<Config> {\n\ttoml::from_str(s)\n}",
+	good: "/// Parses a config file's contents, without applying defaults.\npub fn parse(s: &str) -> Result<Config> {\n\ttoml::from_str(s)\n}",
+	fix: "Add a `///` doc comment, or narrow the visibility to `pub(crate)` if it isn't meant to be API.",
+};
+
+pub struct MissingDocs;
+impl Rule for MissingDocs {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		if !no_panic::is_library_file(&ctx.file.path) {
+			return Vec::new();
+		}
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree, ctx.tests)
+	}
+}
+
+/// Violations in `file`, which is assumed to be library code, see [`no_panic::is_library_file`].
+pub fn check(path: &Path, content: &str, file: &syn::File, tests: &TestContext) -> Vec<Violation> {
+	let visitor = DocsVisitor {
+		path_str: path.display().to_string(),
+		tests,
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+struct DocsVisitor<'a> {
+	path_str: String,
+	tests: &'a TestContext,
+	violations: Vec<Violation>,
+}
+
+impl DocsVisitor<'_> {
+	fn check_item(&mut self, kind: &str, vis: &Visibility, attrs: &[Attribute], ident: &Ident) {
+		let start = ident.span().start();
+		if !matches!(vis, Visibility::Public(_)) || is_documented(attrs) || self.tests.contains(start.line) {
+			return;
+		}
+		let end = ident.span().end();
+		self.violations.push(Violation {
+			rule: RULE,
+			file: self.path_str.clone(),
+			line: start.line,
+			column: start.column,
+			end: Some((end.line, end.column)),
+			message: format!("public {kind} `{ident}` has no doc comment\nHINT: say what it's for, or narrow it to `pub(crate)` if it isn't API"),
+			severity: Severity::Error,
+			..Default::default()
+		});
+	}
+}
+
+impl<'a> Visit<'a> for DocsVisitor<'a> {
+	fn visit_item_fn(&mut self, node: &'a ItemFn) {
+		self.check_item("fn", &node.vis, &node.attrs, &node.sig.ident);
+	}
+
+	fn visit_item_struct(&mut self, node: &'a ItemStruct) {
+		self.check_item("struct", &node.vis, &node.attrs, &node.ident);
+	}
+
+	fn visit_item_enum(&mut self, node: &'a ItemEnum) {
+		self.check_item("enum", &node.vis, &node.attrs, &node.ident);
+	}
+
+	fn visit_item_trait(&mut self, node: &'a ItemTrait) {
+		self.check_item("trait", &node.vis, &node.attrs, &node.ident);
+	}
+
+	fn visit_item_impl(&mut self, node: &'a ItemImpl) {
+		// Trait impls are documented by the trait
+		if node.trait_.is_none() && !is_hidden(&node.attrs) {
+			syn::visit::visit_item_impl(self, node);
+		}
+	}
+
+	fn visit_impl_item_fn(&mut self, node: &'a ImplItemFn) {
+		self.check_item("fn", &node.vis, &node.attrs, &node.sig.ident);
+	}
+
+	fn visit_item_mod(&mut self, node: &'a ItemMod) {
+		if matches!(node.vis, Visibility::Public(_)) && !is_hidden(&node.attrs) {
+			syn::visit::visit_item_mod(self, node);
+		}
+	}
+}
+
+/// Whether `attrs` have a doc comment (`///` and `/** */` are `#[doc = ".."]` to syn), or hide the item from docs.
+fn is_documented(attrs: &[Attribute]) -> bool {
+	attrs.iter().any(|attr| matches!(&attr.meta, Meta::NameValue(nv) if nv.path.is_ident("doc"))) || is_hidden(attrs)
+}
+
+/// Whether `attrs` have `#[doc(hidden)]`.
+fn is_hidden(attrs: &[Attribute]) -> bool {
+	attrs.iter().any(|attr| matches!(&attr.meta, Meta::List(list) if list.path.is_ident("doc") && list.tokens.to_string() == "hidden"))
+}
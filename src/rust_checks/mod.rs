@@ -1,72 +1,650 @@
+pub mod allow_reason;
+pub mod baseline;
+pub mod bool_params;
+pub mod cache;
 pub mod cargo_dep_ordering;
+pub mod changed;
+pub mod commented_code;
+pub mod comments;
+pub mod custom_rules;
+pub mod daemon;
+pub mod dead_files;
+pub mod derive_debug;
+pub mod derive_default;
+pub mod derive_order;
 pub mod embed_simple_vars;
+pub mod exclude;
+pub mod expect_message;
+pub mod fn_params;
+pub mod generated;
 pub mod ignored_error_comment;
 pub mod impl_folds;
 pub mod impl_follows_type;
+pub mod impl_method_order;
 pub mod insta_snapshots;
 pub mod instrument;
 pub mod join_split_impls;
+pub mod let_else;
+pub mod line_endings;
+pub mod line_index;
 pub mod loops;
+pub mod missing_docs;
+pub mod mod_files;
+pub mod mod_style;
+pub mod nesting_depth;
+pub mod no_as_cast;
+pub mod no_assert;
+pub mod no_async_trait;
+pub mod no_block_on_in_async;
+pub mod no_blocking_in_async;
 pub mod no_chrono;
+pub mod no_dbg;
+pub mod no_extern_crate;
+pub mod no_get_prefix;
+pub mod no_lazy_static;
+pub mod no_lock_across_await;
+pub mod no_macro_use;
+pub mod no_once_cell;
+pub mod no_other_runtimes;
+pub mod no_panic;
 pub mod no_tokio_spawn;
+pub mod no_unwrap;
+pub mod organize;
+pub mod overrides;
+pub mod path_modules;
+pub mod plugins;
+pub mod pretty;
+pub mod progress;
+pub mod pub_fields_first;
 pub mod pub_first;
+pub mod pub_use_location;
+pub mod reqwest_timeout;
+pub mod rules;
+pub mod rustfix;
+pub mod rustfmt;
+pub mod safety_comment;
+pub mod sarif;
+pub mod should_panic_expected;
 pub mod skip;
+pub mod skip_reason;
+pub mod stats;
+pub mod test_context;
 pub mod test_fn_prefix;
+pub mod test_in_cfg_test;
+pub mod tests_last;
+pub mod todo_comment;
+pub mod todo_issue;
+pub mod trait_impls_follow;
+pub mod unused_pub;
+pub mod unused_skip;
 pub mod use_bail;
+pub mod use_matches;
+pub mod use_thiserror;
+pub mod workspace;
 
 use std::{
-	fs,
+	cmp::Reverse,
+	collections::HashSet,
+	fmt, fs,
+	io::{self, Read},
 	path::{Path, PathBuf},
+	str::FromStr,
+	time::Instant,
 };
 
+use baseline::Baseline;
+use cache::Cache;
+use changed::ChangedFiles;
+use custom_rules::CustomRule;
+use exclude::{DiscoveryOptions, Exclude};
+use line_endings::LineEnding;
+use line_index::LineIndex;
+use overrides::Overrides;
+use progress::Progress;
+use regex::Regex;
+use rules::{Context, Rule};
+use rustfmt::RustfmtConfig;
+use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
 use syn::{ItemFn, parse_file};
+use test_context::TestContext;
+use tracing::{Level, debug, trace};
 use walkdir::WalkDir;
 
-#[derive(Clone, SmartDefault)]
+/// Stands in for the file path in violations found in `format -` input
+const STDIN_PATH: &str = "<stdin>";
+/// Cap on re-parse-and-fix rounds per file in `format`
+const MAX_FIX_PASSES: usize = 100;
+/// Rule id of violations for skip region markers that don't pair up
+const SKIP_REGION_RULE: &str = "skip-region";
+/// Rule id of violations for skip markers past their `until` date
+const EXPIRED_SKIP_RULE: &str = "expired-skip";
+
+/// How results are printed.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
+pub enum OutputFormat {
+	/// Human-readable, on stderr
+	#[default]
+	Text,
+	/// A JSON array on stdout, for scripts and editor integrations
+	Json,
+	/// A SARIF 2.1.0 log on stdout, for GitHub code scanning
+	Sarif,
+	/// rustc-style diagnostics with the offending source line, on stderr
+	Pretty,
+	/// rustc's JSON diagnostics on stdout, one per line, with fixes as suggestions `rustfix` can apply
+	Rustfix,
+}
+
+/// Which violations make `assert` exit non-zero.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
+pub enum FailOn {
+	/// Only `error`-level ones
+	#[default]
+	Error,
+	/// Warnings too
+	Warn,
+	/// None, e.g. while rolling out new rules
+	Never,
+}
+impl FailOn {
+	pub fn fails(self, v: &Violation) -> bool {
+		match self {
+			FailOn::Error => v.severity == Severity::Error,
+			FailOn::Warn => v.severity.is_enabled(),
+			FailOn::Never => false,
+		}
+	}
+}
+
+/// When `assert` fails the run: on more than `max_violations` violations that `fail_on` counts.
+#[derive(Clone, Copy, Debug, Default, clap::Args)]
+pub struct FailPolicy {
+	/// Which violations fail the run
+	#[arg(long, value_enum, default_value_t)]
+	pub fail_on: FailOn,
+	/// How many failing violations to tolerate, e.g. while burning down a large backlog
+	#[arg(long, value_name = "N", default_value_t = 0)]
+	pub max_violations: usize,
+}
+impl FailPolicy {
+	fn failing(&self, violations: &[Violation]) -> usize {
+		violations.iter().filter(|v| self.fail_on.fails(v)).count()
+	}
+
+	fn exit_code(&self, violations: &[Violation]) -> i32 {
+		i32::from(self.failing(violations) > self.max_violations)
+	}
+}
+
+/// How a rule's violations are treated. Parses from `error`/`warn`/`allow`, and from `true`/`false` for backwards compatibility.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Severity {
+	/// Don't run the rule
+	Allow,
+	/// Report, but don't fail
+	Warn,
+	/// Report and fail
+	#[default]
+	Error,
+}
+impl Severity {
+	pub fn is_enabled(self) -> bool {
+		self != Severity::Allow
+	}
+
+	/// Stamp this severity on a rule's violations.
+	pub fn assign(self, violations: Vec<Violation>) -> Vec<Violation> {
+		violations.into_iter().map(|v| Violation { severity: self, ..v }).collect()
+	}
+}
+impl From<bool> for Severity {
+	fn from(enabled: bool) -> Self {
+		if enabled { Severity::Error } else { Severity::Allow }
+	}
+}
+impl FromStr for Severity {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"error" | "true" => Ok(Severity::Error),
+			"warn" => Ok(Severity::Warn),
+			"allow" | "false" => Ok(Severity::Allow),
+			_ => Err(format!("invalid severity `{s}`, expected `error`, `warn` or `allow`")),
+		}
+	}
+}
+impl fmt::Display for Severity {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.pad(match self {
+			Severity::Allow => "allow",
+			Severity::Warn => "warn",
+			Severity::Error => "error",
+		})
+	}
+}
+
+#[derive(Clone, Debug, SmartDefault)]
 pub struct RustCheckOptions {
-	/// Order and group dependencies in Cargo.toml (default: true)
-	#[default = true]
-	pub cargo_dep_ordering: bool,
-	/// Check for #[instrument] on async functions (default: false)
-	#[default = false]
-	pub instrument: bool,
-	/// Check for //LOOP comments on endless loops (default: true)
-	#[default = true]
-	pub loops: bool,
-	/// Join split impl blocks for the same type (default: true)
-	#[default = true]
-	pub join_split_impls: bool,
-	/// Wrap impl blocks with vim 1-fold markers (default: false)
-	#[default = false]
-	pub impl_folds: bool,
-	/// Check that impl blocks follow type definitions (default: true)
-	#[default = true]
-	pub impl_follows_type: bool,
-	/// Check for simple vars that should be embedded in format strings (default: true)
-	#[default = true]
-	pub embed_simple_vars: bool,
-	/// Check that insta snapshots use inline @"" syntax (default: true)
-	#[default = false]
-	pub insta_inline_snapshot: bool,
-	/// Disallow usage of chrono crate (use jiff instead) (default: true)
-	#[default = true]
-	pub no_chrono: bool,
-	/// Disallow usage of tokio::spawn (default: true)
-	#[default = true]
-	pub no_tokio_spawn: bool,
-	/// Replace `return Err(eyre!(...))` with `bail!(...)` (default: true)
-	#[default = true]
-	pub use_bail: bool,
-	/// Check that test functions don't have redundant `test_` prefix (default: false)
-	#[default = false]
-	pub test_fn_prefix: bool,
-	/// Check that public items come before private items (default: true)
-	#[default = true]
-	pub pub_first: bool,
-	/// Check for //IGNORED_ERROR comments on unwrap_or/unwrap_or_default/unwrap_or_else and `let _ = ...` (default: true)
-	#[default = false] // useful, but too many false positives. Sadly, the time commitment might not be worth it, unless I somehow make this smarter
-	pub ignored_error_comment: bool,
+	/// Order and group dependencies in Cargo.toml (default: error)
+	#[default(Severity::Error)]
+	pub cargo_dep_ordering: Severity,
+	/// Check for #[instrument] on async functions (default: allow)
+	#[default(Severity::Allow)]
+	pub instrument: Severity,
+	/// Check for //LOOP comments on endless loops (default: error)
+	#[default(Severity::Error)]
+	pub loops: Severity,
+	/// Check for // SAFETY: comments on unsafe blocks and fns (default: allow)
+	#[default(Severity::Allow)]
+	pub safety_comment: Severity,
+	/// Join split impl blocks for the same type (default: error)
+	#[default(Severity::Error)]
+	pub join_split_impls: Severity,
+	/// Wrap impl blocks with vim 1-fold markers (default: allow)
+	#[default(Severity::Allow)]
+	pub impl_folds: Severity,
+	/// Check that impl blocks follow type definitions (default: error)
+	#[default(Severity::Error)]
+	pub impl_follows_type: Severity,
+	/// Place trait impls right after their type's inherent impl, std traits first (default: allow)
+	#[default(Severity::Allow)]
+	pub trait_impls_follow: Severity,
+	/// Check for simple vars that should be embedded in format strings (default: error)
+	#[default(Severity::Error)]
+	pub embed_simple_vars: Severity,
+	/// Check that insta snapshots use inline @"" syntax (default: error)
+	#[default(Severity::Allow)]
+	pub insta_inline_snapshot: Severity,
+	/// Disallow usage of chrono crate (use jiff instead) (default: error)
+	#[default(Severity::Error)]
+	pub no_chrono: Severity,
+	/// Disallow usage of tokio::spawn (default: error)
+	#[default(Severity::Error)]
+	pub no_tokio_spawn: Severity,
+	/// Disallow leftover dbg! (default: allow)
+	#[default(Severity::Allow)]
+	pub no_dbg: Severity,
+	/// Replace `return Err(eyre!(...))` with `bail!(...)` (default: error)
+	#[default(Severity::Error)]
+	pub use_bail: Severity,
+	/// Check that test functions don't have redundant `test_` prefix (default: allow)
+	#[default(Severity::Allow)]
+	pub test_fn_prefix: Severity,
+	/// Check that #[test] fns in src/ are inside a #[cfg(test)] module (default: allow)
+	#[default(Severity::Allow)]
+	pub test_in_cfg_test: Severity,
+	/// Check that public items come before private items (default: error)
+	#[default(Severity::Error)]
+	pub pub_first: Severity,
+	/// Check that #[cfg(test)] mod tests is the last item in its file (default: allow)
+	#[default(Severity::Allow)]
+	pub tests_last: Severity,
+	/// Check for //IGNORED_ERROR comments on unwrap_or/unwrap_or_default/unwrap_or_else and `let _ = ...` (default: error)
+	#[default(Severity::Allow)] // useful, but too many false positives. Sadly, the time commitment might not be worth it, unless I somehow make this smarter
+	pub ignored_error_comment: Severity,
+	/// Check for //SAFE comments (or `unwrap_marker`) on .unwrap() and .expect() outside tests (default: allow)
+	#[default(Severity::Allow)]
+	pub no_unwrap: Severity,
+	/// Check that .expect() messages describe the invariant rather than being empty or restating the call (default: allow)
+	#[default(Severity::Allow)]
+	pub expect_message: Severity,
+	/// Disallow panic!, todo! and unimplemented! in library code (default: allow)
+	#[default(Severity::Allow)]
+	pub no_panic: Severity,
+	/// Require a reason on #[allow] and #[expect] attributes (default: allow)
+	#[default(Severity::Allow)]
+	pub allow_reason: Severity,
+	/// Require doc comments on public fns, structs, enums and traits of libraries (default: allow)
+	#[default(Severity::Allow)]
+	pub missing_docs: Severity,
+	/// Keep #[derive] lists in canonical order (default: allow)
+	#[default(Severity::Allow)]
+	pub derive_order: Severity,
+	/// Limit nesting of if/match/loops/closures inside a function (default: allow)
+	#[default(Severity::Allow)]
+	pub nesting_depth: Severity,
+	/// Limit the number of function parameters (default: allow)
+	#[default(Severity::Allow)]
+	pub fn_params: Severity,
+	/// Disallow several bool parameters, or any in public fns (default: allow)
+	#[default(Severity::Allow)]
+	pub bool_params: Severity,
+	/// Disallow lossy numeric `as` casts (default: allow)
+	#[default(Severity::Allow)]
+	pub no_as_cast: Severity,
+	/// Prefer let-else over a match or if-let that only unwraps or diverges (default: allow)
+	#[default(Severity::Allow)]
+	pub let_else: Severity,
+	/// Use matches!(...) instead of a match or if-let returning true/false (default: allow)
+	#[default(Severity::Allow)]
+	pub use_matches: Severity,
+	/// Keep module files in one layout, foo.rs next to foo/ or foo/mod.rs (default: allow)
+	#[default(Severity::Allow)]
+	pub mod_style: Severity,
+	/// Disallow std::sync lock guards held across .await (default: allow)
+	#[default(Severity::Allow)]
+	pub no_lock_across_await: Severity,
+	/// Disallow blocking std::thread::sleep, std::fs and std::net calls in async code (default: allow)
+	#[default(Severity::Allow)]
+	pub no_blocking_in_async: Severity,
+	/// Disallow block_on inside async fns and the closures they create (default: allow)
+	#[default(Severity::Allow)]
+	pub no_block_on_in_async: Severity,
+	/// Disallow the async_trait crate, in favor of native async fn in traits (default: allow)
+	#[default(Severity::Allow)]
+	pub no_async_trait: Severity,
+	/// Disallow the lazy_static crate, in favor of std::sync::LazyLock (default: allow)
+	#[default(Severity::Allow)]
+	pub no_lazy_static: Severity,
+	/// Disallow once_cell's Lazy and OnceCell, in favor of std's LazyLock and OnceLock (default: allow)
+	#[default(Severity::Allow)]
+	pub no_once_cell: Severity,
+	/// Disallow async runtimes other than tokio, like async-std and smol (default: allow)
+	#[default(Severity::Allow)]
+	pub no_other_runtimes: Severity,
+	/// Require reqwest clients and requests to set a timeout (default: allow)
+	#[default(Severity::Allow)]
+	pub reqwest_timeout: Severity,
+	/// Derive thiserror::Error on public error enums instead of implementing Display and Error by hand (default: allow)
+	#[default(Severity::Allow)]
+	pub use_thiserror: Severity,
+	/// Require public structs and enums to implement Debug (default: allow)
+	#[default(Severity::Allow)]
+	pub derive_debug: Severity,
+	/// Derive Default instead of implementing it by hand with each field's default (default: allow)
+	#[default(Severity::Allow)]
+	pub derive_default: Severity,
+	/// Order struct fields: pub before private (default: allow)
+	#[default(Severity::Allow)]
+	pub pub_fields_first: Severity,
+	/// Match mod declarations to module files, both ways (default: allow)
+	#[default(Severity::Allow)]
+	pub mod_files: Severity,
+	/// Order inherent impls: associated items, constructors, pub methods, then private ones (default: allow)
+	#[default(Severity::Allow)]
+	pub impl_method_order: Severity,
+	/// Disallow the get_ prefix on getters (default: allow)
+	#[default(Severity::Allow)]
+	pub no_get_prefix: Severity,
+	/// Require an expected message on #[should_panic] (default: allow)
+	#[default(Severity::Allow)]
+	pub should_panic_expected: Severity,
+	/// Disallow assert!, assert_eq! and assert_ne! outside tests (default: allow)
+	#[default(Severity::Allow)]
+	pub no_assert: Severity,
+	/// Require an issue reference in todo! and unimplemented! messages (default: allow)
+	#[default(Severity::Allow)]
+	pub todo_issue: Severity,
+	/// Require an issue reference in TODO, FIXME and HACK comments (default: allow)
+	#[default(Severity::Allow)]
+	pub todo_comment: Severity,
+	/// Disallow commented-out code (default: allow)
+	#[default(Severity::Allow)]
+	pub commented_code: Severity,
+	/// Disallow extern crate, unneeded since edition 2018 (default: allow)
+	#[default(Severity::Allow)]
+	pub no_extern_crate: Severity,
+	/// Disallow #[macro_use] in favor of importing macros with use (default: allow)
+	#[default(Severity::Allow)]
+	pub no_macro_use: Severity,
+	/// Restrict pub use re-exports to lib.rs, mod.rs and prelude modules (default: allow)
+	#[default(Severity::Allow)]
+	pub pub_use_location: Severity,
+	/// Flag pub items nothing outside their module uses (default: allow)
+	#[default(Severity::Allow)]
+	pub unused_pub: Severity,
+	/// Flag files under src/ that no mod declaration reaches (default: allow)
+	#[default(Severity::Allow)]
+	pub dead_files: Severity,
+	/// Require a reason on codestyle::skip marker comments (default: allow)
+	#[default(Severity::Allow)]
+	pub skip_reason: Severity,
+	/// Report codestyle::skip marker comments that suppress nothing (default: allow)
+	#[default(Severity::Allow)]
+	pub unused_skip: Severity,
+	/// Commands of external rule plugins, see [`plugins`]
+	pub plugins: Vec<String>,
+	/// Rules declared under `[[rust.custom_rules]]`, see [`custom_rules`]
+	pub custom_rules: Vec<CustomRule>,
+	/// Severities for the files matching `[override."<glob>"]` sections, see [`overrides`]
+	pub overrides: Overrides,
+	/// Options whose rules don't apply to test code, see [`test_context`]
+	pub test_exempt: Vec<String>,
+	/// Comment marker justifying an `.unwrap()`/`.expect()`, see [`no_unwrap`]
+	#[default(no_unwrap::DEFAULT_MARKER.to_string())]
+	pub unwrap_marker: String,
+	/// Shortest `.expect()` message that passes, see [`expect_message`]
+	#[default(expect_message::DEFAULT_MIN_LEN)]
+	pub expect_message_min_len: usize,
+	/// Macros `no_panic` lets through, like `todo`, see [`no_panic`]
+	pub no_panic_allow: Vec<String>,
+	/// Comment marker justifying an assertion outside tests, none if empty, see [`no_assert`]
+	pub no_assert_marker: String,
+	/// What an issue reference in a `todo!` message matches, see [`todo_issue`]
+	#[default(todo_issue::default_pattern())]
+	pub todo_issue_pattern: Regex,
+	/// Comment markers `todo_comment` wants an issue reference after, see [`todo_comment`]
+	#[default(todo_comment::DEFAULT_MARKERS.iter().map(ToString::to_string).collect())]
+	pub todo_comment_markers: Vec<String>,
+	/// Modules `pub use` is allowed in besides `lib.rs` and `mod.rs`, see [`pub_use_location`]
+	#[default(pub_use_location::DEFAULT_MODULES.iter().map(ToString::to_string).collect())]
+	pub pub_use_modules: Vec<String>,
+	/// Share of a commented-out block's lines that have to look like code, see [`commented_code`]
+	#[default(commented_code::DEFAULT_CONFIDENCE)]
+	pub commented_code_confidence: f64,
+	/// Deepest nesting inside a function that passes, see [`nesting_depth`]
+	#[default(nesting_depth::DEFAULT_MAX)]
+	pub nesting_depth_max: usize,
+	/// Most parameters a function may take, see [`fn_params`]
+	#[default(fn_params::DEFAULT_MAX)]
+	pub fn_params_max: usize,
+	/// Whether `bool_params` flags a single `bool` in public fns, see [`bool_params`]
+	#[default(true)]
+	pub bool_params_in_pub: bool,
+	/// Layout `mod_style` keeps module files in, see [`mod_style`]
+	pub mod_style_prefer: mod_style::Layout,
+	/// Paths `no_blocking_in_async` flags besides the built-in ones, see [`no_blocking_in_async`]
+	pub no_blocking_in_async_paths: Vec<String>,
+	/// Crates `no_other_runtimes` flags besides the built-in runtimes, see [`no_other_runtimes`]
+	pub no_other_runtimes_crates: Vec<String>,
+}
+impl RustCheckOptions {
+	/// Every rule enabled, for the `strict` profile.
+	pub fn strict() -> Self {
+		Self {
+			cargo_dep_ordering: Severity::Error,
+			instrument: Severity::Error,
+			loops: Severity::Error,
+			safety_comment: Severity::Error,
+			join_split_impls: Severity::Error,
+			impl_folds: Severity::Error,
+			impl_follows_type: Severity::Error,
+			trait_impls_follow: Severity::Error,
+			embed_simple_vars: Severity::Error,
+			insta_inline_snapshot: Severity::Error,
+			no_chrono: Severity::Error,
+			no_tokio_spawn: Severity::Error,
+			no_dbg: Severity::Error,
+			use_bail: Severity::Error,
+			test_fn_prefix: Severity::Error,
+			test_in_cfg_test: Severity::Error,
+			pub_first: Severity::Error,
+			tests_last: Severity::Error,
+			ignored_error_comment: Severity::Error,
+			no_unwrap: Severity::Error,
+			expect_message: Severity::Error,
+			no_panic: Severity::Error,
+			allow_reason: Severity::Error,
+			missing_docs: Severity::Error,
+			derive_order: Severity::Error,
+			nesting_depth: Severity::Error,
+			fn_params: Severity::Error,
+			bool_params: Severity::Error,
+			no_as_cast: Severity::Error,
+			let_else: Severity::Error,
+			use_matches: Severity::Error,
+			mod_style: Severity::Error,
+			no_lock_across_await: Severity::Error,
+			no_blocking_in_async: Severity::Error,
+			no_block_on_in_async: Severity::Error,
+			no_async_trait: Severity::Error,
+			no_lazy_static: Severity::Error,
+			no_once_cell: Severity::Error,
+			no_other_runtimes: Severity::Error,
+			reqwest_timeout: Severity::Error,
+			use_thiserror: Severity::Error,
+			derive_debug: Severity::Error,
+			derive_default: Severity::Error,
+			pub_fields_first: Severity::Error,
+			mod_files: Severity::Error,
+			impl_method_order: Severity::Error,
+			no_get_prefix: Severity::Error,
+			should_panic_expected: Severity::Error,
+			no_assert: Severity::Error,
+			todo_issue: Severity::Error,
+			todo_comment: Severity::Error,
+			commented_code: Severity::Error,
+			no_extern_crate: Severity::Error,
+			no_macro_use: Severity::Error,
+			pub_use_location: Severity::Error,
+			unused_pub: Severity::Error,
+			dead_files: Severity::Error,
+			skip_reason: Severity::Error,
+			unused_skip: Severity::Error,
+			plugins: Vec::new(),
+			custom_rules: Vec::new(),
+			overrides: Overrides::default(),
+			test_exempt: Vec::new(),
+			unwrap_marker: no_unwrap::DEFAULT_MARKER.to_string(),
+			expect_message_min_len: expect_message::DEFAULT_MIN_LEN,
+			no_panic_allow: Vec::new(),
+			no_assert_marker: String::new(),
+			todo_issue_pattern: todo_issue::default_pattern(),
+			todo_comment_markers: todo_comment::DEFAULT_MARKERS.iter().map(ToString::to_string).collect(),
+			pub_use_modules: pub_use_location::DEFAULT_MODULES.iter().map(ToString::to_string).collect(),
+			commented_code_confidence: commented_code::DEFAULT_CONFIDENCE,
+			nesting_depth_max: nesting_depth::DEFAULT_MAX,
+			fn_params_max: fn_params::DEFAULT_MAX,
+			bool_params_in_pub: true,
+			mod_style_prefer: mod_style::Layout::File,
+			no_blocking_in_async_paths: Vec::new(),
+			no_other_runtimes_crates: Vec::new(),
+		}
+	}
+
+	/// Severity of the option named `option` (a field name, as in [`rules::RuleInfo::option`]).
+	pub fn severity(&self, option: &str) -> Severity {
+		match option {
+			"cargo_dep_ordering" => self.cargo_dep_ordering,
+			"instrument" => self.instrument,
+			"loops" => self.loops,
+			"safety_comment" => self.safety_comment,
+			"join_split_impls" => self.join_split_impls,
+			"impl_folds" => self.impl_folds,
+			"impl_follows_type" => self.impl_follows_type,
+			"trait_impls_follow" => self.trait_impls_follow,
+			"embed_simple_vars" => self.embed_simple_vars,
+			"insta_inline_snapshot" => self.insta_inline_snapshot,
+			"no_chrono" => self.no_chrono,
+			"no_tokio_spawn" => self.no_tokio_spawn,
+			"no_dbg" => self.no_dbg,
+			"use_bail" => self.use_bail,
+			"test_fn_prefix" => self.test_fn_prefix,
+			"test_in_cfg_test" => self.test_in_cfg_test,
+			"pub_first" => self.pub_first,
+			"tests_last" => self.tests_last,
+			"ignored_error_comment" => self.ignored_error_comment,
+			"no_unwrap" => self.no_unwrap,
+			"expect_message" => self.expect_message,
+			"no_panic" => self.no_panic,
+			"allow_reason" => self.allow_reason,
+			"missing_docs" => self.missing_docs,
+			"derive_order" => self.derive_order,
+			"nesting_depth" => self.nesting_depth,
+			"fn_params" => self.fn_params,
+			"bool_params" => self.bool_params,
+			"no_as_cast" => self.no_as_cast,
+			"let_else" => self.let_else,
+			"use_matches" => self.use_matches,
+			"mod_style" => self.mod_style,
+			"no_lock_across_await" => self.no_lock_across_await,
+			"no_blocking_in_async" => self.no_blocking_in_async,
+			"no_block_on_in_async" => self.no_block_on_in_async,
+			"no_async_trait" => self.no_async_trait,
+			"no_lazy_static" => self.no_lazy_static,
+			"no_once_cell" => self.no_once_cell,
+			"no_other_runtimes" => self.no_other_runtimes,
+			"reqwest_timeout" => self.reqwest_timeout,
+			"use_thiserror" => self.use_thiserror,
+			"derive_debug" => self.derive_debug,
+			"derive_default" => self.derive_default,
+			"pub_fields_first" => self.pub_fields_first,
+			"mod_files" => self.mod_files,
+			"impl_method_order" => self.impl_method_order,
+			"no_get_prefix" => self.no_get_prefix,
+			"should_panic_expected" => self.should_panic_expected,
+			"no_assert" => self.no_assert,
+			"todo_issue" => self.todo_issue,
+			"todo_comment" => self.todo_comment,
+			"commented_code" => self.commented_code,
+			"no_extern_crate" => self.no_extern_crate,
+			"no_macro_use" => self.no_macro_use,
+			"pub_use_location" => self.pub_use_location,
+			"unused_pub" => self.unused_pub,
+			"dead_files" => self.dead_files,
+			"skip_reason" => self.skip_reason,
+			"unused_skip" => self.unused_skip,
+			_ => Severity::Allow,
+		}
+	}
+
+	/// Severity of `option` for the file at `path`: an `[override]` matching it, or [`Self::severity`].
+	pub fn severity_for(&self, path: &Path, option: &str) -> Severity {
+		self.overrides.severity(path, option).unwrap_or_else(|| self.severity(option))
+	}
+}
+
+/// Options for each crate of the target, so member crates can override the workspace's settings.
+pub trait CrateOptions {
+	fn for_dir(&self, dir: &Path) -> RustCheckOptions;
+}
+impl CrateOptions for RustCheckOptions {
+	fn for_dir(&self, _dir: &Path) -> RustCheckOptions {
+		self.clone()
+	}
+}
+impl<F: Fn(&Path) -> RustCheckOptions> CrateOptions for F {
+	fn for_dir(&self, dir: &Path) -> RustCheckOptions {
+		self(dir)
+	}
+}
+
+/// How `run_assert` reports and filters its results.
+#[derive(Clone, Debug, Default)]
+pub struct AssertOptions {
+	pub format: OutputFormat,
+	/// Violations recorded in this baseline file don't fail the run
+	pub baseline: Option<PathBuf>,
+	/// Record the current violations as the baseline instead of checking against it
+	pub write_baseline: bool,
+	/// Only check files changed relative to this git ref
+	pub changed: Option<String>,
+	/// Which paths to skip while looking for files
+	pub discovery: DiscoveryOptions,
+	/// Check every file afresh instead of reusing results from `target/codestyle-cache`
+	pub no_cache: bool,
+	pub fail: FailPolicy,
+}
+
+/// How `run_format` discovers files and applies its fixes.
+#[derive(Clone, Debug, Default)]
+pub struct FormatOptions {
+	/// Print the fixes as unified diffs instead of writing them
+	pub dry_run: bool,
+	/// Which paths to skip while looking for files
+	pub discovery: DiscoveryOptions,
 }
 
 #[derive(Clone, Default, derive_new::new)]
@@ -75,402 +653,726 @@ pub struct FileInfo {
 	pub syntax_tree: Option<syn::File>,
 	pub fn_items: Vec<ItemFn>,
 	pub path: PathBuf,
+	/// What `contents`, which always ends lines with `\n`, used on disk
+	pub line_ending: LineEnding,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct Violation {
 	pub rule: &'static str,
 	pub file: String,
 	pub line: usize,
 	pub column: usize,
+	/// `(line, column)` just past the offending code, for rules that know its extent
+	pub end: Option<(usize, usize)>,
 	pub message: String,
+	/// Set by the runner from the rule's configured level; rules themselves report `Error`
+	pub severity: Severity,
+	/// Applied by `format`
 	pub fix: Option<Fix>,
+	/// Where `format` moves the whole file, for rules about where a file lives rather than what's in it
+	pub rename: Option<PathBuf>,
+	/// Other places in the same file the violation involves, like the type an impl block should follow
+	pub labels: Vec<Label>,
+	/// Context that doesn't belong in the one-line message
+	pub notes: Vec<String>,
+	/// Fixes offered but never applied automatically, e.g. because the user has to pick one or fill something in
+	pub suggestions: Vec<Suggestion>,
+}
+impl Violation {
+	/// Whether `format` fixes it, by editing the file or by moving it.
+	pub fn is_fixable(&self) -> bool {
+		self.fix.is_some() || self.rename.is_some()
+	}
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct Fix {
 	pub start_byte: usize,
 	pub end_byte: usize,
 	pub replacement: String,
 }
 
-pub fn run_assert(target_dir: &Path, opts: &RustCheckOptions) -> i32 {
-	if !target_dir.exists() {
-		eprintln!("Target directory does not exist: {target_dir:?}");
-		return 1;
-	}
+/// A secondary position in a violation's file, with what's there.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Label {
+	pub line: usize,
+	pub column: usize,
+	pub message: String,
+}
 
-	let src_dirs = find_src_dirs(target_dir);
-	if src_dirs.is_empty() {
-		eprintln!("No source directories found");
-		return 1;
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Suggestion {
+	/// What applying it does, e.g. "justify the ignored error"
+	pub message: String,
+	pub fix: Fix,
+}
+
+/// Check `paths`: directories are walked for their crates' sources, files are checked as-is.
+pub fn run_assert(paths: &[PathBuf], opts: &impl CrateOptions, assert: &AssertOptions) -> i32 {
+	let root = run_root(paths);
+	// The cache only records whether there's a fix, and suggestions need the fix itself
+	let cache = !assert.no_cache && assert.format != OutputFormat::Rustfix;
+	let progress = matches!(assert.format, OutputFormat::Text | OutputFormat::Pretty);
+	let mut all_violations = match collect_violations(paths, opts, &assert.discovery, assert.changed.as_deref(), cache, progress) {
+		Ok(violations) => violations,
+		Err(e) => {
+			eprintln!("{e}");
+			return 1;
+		}
+	};
+
+	if let Some(path) = &assert.baseline {
+		if assert.write_baseline {
+			return match Baseline::from_violations(&all_violations, root).save(path) {
+				Ok(()) => {
+					println!("codestyle: recorded {} violation(s) in {path:?}", all_violations.len());
+					0
+				}
+				Err(e) => {
+					eprintln!("codestyle: {e}");
+					1
+				}
+			};
+		}
+
+		let baseline = match Baseline::load(path) {
+			Ok(baseline) => baseline,
+			Err(e) => {
+				eprintln!("codestyle: {e}");
+				return 1;
+			}
+		};
+		let (new, stale) = baseline.filter(all_violations, root);
+		if stale > 0 && assert.format == OutputFormat::Text {
+			eprintln!("codestyle: {stale} baselined violation(s) no longer occur; rerun with --write-baseline to drop them\n");
+		}
+		all_violations = new;
 	}
 
+	report_assert(&all_violations, assert.format, assert.fail)
+}
+
+/// Every violation in `paths`, found as in [`run_assert`]: only in files changed since `changed` if given,
+/// and reusing results from `target/codestyle-cache` with `cache`. With `progress`, long runs show a progress bar on stderr.
+pub fn collect_violations(paths: &[PathBuf], opts: &impl CrateOptions, discovery: &DiscoveryOptions, changed: Option<&str>, cache: bool, progress: bool) -> Result<Vec<Violation>, String> {
+	let root = run_root(paths);
+	let targets = Exclude::new(root, discovery).and_then(|exclude| Targets::collect(paths, opts, &exclude))?;
+	let changed = changed.map(|git_ref| ChangedFiles::since(root, git_ref).map_err(|e| format!("codestyle: {e}"))).transpose()?;
+	let selected = |path: &Path| {
+		let selected = changed.as_ref().is_none_or(|changed| changed.contains(path));
+		if !selected {
+			debug!(path = %path.display(), "skipped: unchanged since the --changed ref");
+		}
+		selected
+	};
+
+	let rustfmt = RustfmtConfig::load(root);
 	let mut all_violations = Vec::new();
 
 	// Cargo.toml checks
-	if opts.cargo_dep_ordering {
-		for toml_path in collect_cargo_tomls(target_dir) {
-			if let Ok(content) = fs::read_to_string(&toml_path) {
-				all_violations.extend(cargo_dep_ordering::check(&toml_path, &content));
+	for (toml_path, severity) in targets.cargo_tomls.iter().filter(|(p, _)| selected(p)) {
+		if severity.is_enabled()
+			&& let Ok(content) = fs::read_to_string(toml_path)
+		{
+			all_violations.extend(severity.assign(cargo_dep_ordering::check(toml_path, &content)));
+		}
+	}
+
+	// Crate-wide checks read every file of a crate, as an unchanged one can be what uses an item in a changed one
+	all_violations.extend(unused_pub::check_crates(&targets.rust_files).into_iter().filter(|v| selected(Path::new(&v.file))));
+	all_violations.extend(dead_files::check_crates(&targets.rust_files).into_iter().filter(|v| selected(Path::new(&v.file))));
+
+	let cache = cache.then(|| Cache::new(root));
+	let today = skip::today();
+	let rust_files: Vec<(PathBuf, RustCheckOptions)> = targets.rust_files.into_iter().filter(|(p, _)| selected(p)).collect();
+	let mut progress = Progress::new(rust_files.len(), progress);
+	for (path, file_opts) in rust_files {
+		let found_before = all_violations.len();
+		let Ok(contents) = fs::read_to_string(&path) else {
+			progress.tick(0);
+			continue;
+		};
+		// `check_file` would find nothing anyway; generated files can be huge, so don't even parse them
+		if generated::is_generated(&contents) {
+			debug!(path = %path.display(), "skipped: generated");
+			progress.tick(0);
+			continue;
+		}
+		let key = Cache::key(&path, &contents, &file_opts, &rustfmt, today);
+		// A rule looking at other files could find something else with this one unchanged
+		let cache = cache.as_ref().filter(|_| {
+			!rules::SOURCE_RULES
+				.iter()
+				.any(|rule| rule.reads_other_files() && file_opts.severity_for(&path, rule.info().option).is_enabled() && rule.info().may_apply(&contents))
+		});
+		if let Some(cached) = cache.and_then(|cache| cache.get(&key)) {
+			debug!(path = %path.display(), violations = cached.len(), "reused cached results");
+			all_violations.extend(cached);
+		} else if let Some(info) = parse_rust_source(path, contents) {
+			let violations = check_file(&info, &file_opts, &rustfmt);
+			if let Some(cache) = cache {
+				cache.put(&key, &violations);
 			}
+			all_violations.extend(violations);
 		}
+		progress.tick(all_violations.len() - found_before);
 	}
+	progress.finish();
+
+	Ok(all_violations)
+}
 
-	for src_dir in src_dirs {
-		let file_infos = collect_rust_files(&src_dir);
-		for info in &file_infos {
-			if opts.instrument {
-				all_violations.extend(instrument::check_instrument(info));
+/// Fix `paths`, found the same way as in [`run_assert`].
+/// With `dry_run`, nothing is written: the changes are printed as unified diffs on stdout instead.
+pub fn run_format(paths: &[PathBuf], opts: &impl CrateOptions, format: &FormatOptions) -> i32 {
+	let dry_run = format.dry_run;
+	let mut targets = match Exclude::new(run_root(paths), &format.discovery).and_then(|exclude| Targets::collect(paths, opts, &exclude)) {
+		Ok(targets) => targets,
+		Err(e) => {
+			eprintln!("{e}");
+			return 1;
+		}
+	};
+
+	// Delete any .snap and .pending-snap files in the target directories (only if insta check is enabled)
+	for dir in paths.iter().filter(|p| p.is_dir()) {
+		if opts.for_dir(dir).insta_inline_snapshot.is_enabled() {
+			delete_snap_files(dir, dry_run);
+		}
+	}
+
+	let rustfmt = RustfmtConfig::load(run_root(paths));
+	let mut fixed_count = 0;
+	let mut unfixable_violations = Vec::new();
+
+	// Cargo.toml checks
+	for (toml_path, severity) in targets.cargo_tomls {
+		if !severity.is_enabled() {
+			continue;
+		}
+		if let Ok(original) = fs::read_to_string(&toml_path) {
+			let content = line_endings::normalize(&original);
+			let (mut fixable, unfixable): (Vec<Violation>, Vec<Violation>) = severity.assign(cargo_dep_ordering::check(&toml_path, &content)).into_iter().partition(|v| v.fix.is_some());
+			unfixable_violations.extend(unfixable);
+
+			// Back to front, so each fix's byte range is still valid when it's applied
+			fixable.sort_by_key(|v| std::cmp::Reverse(v.fix.as_ref().map(|f| f.start_byte)));
+			let mut new_content = content.to_string();
+			let mut toml_fixed = 0;
+			for fix in fixable.into_iter().filter_map(|v| v.fix) {
+				if fix.start_byte <= fix.end_byte && fix.end_byte <= new_content.len() {
+					new_content.replace_range(fix.start_byte..fix.end_byte, &fix.replacement);
+					toml_fixed += 1;
+				}
 			}
-			if opts.loops {
-				all_violations.extend(loops::check_loops(info));
+			if toml_fixed > 0 {
+				new_content = LineEnding::detect(&original).apply(&new_content).into_owned();
 			}
-			if let Some(ref tree) = info.syntax_tree {
-				// Order matters: join_split_impls -> impl_follows_type -> impl_folds
-				if opts.join_split_impls {
-					all_violations.extend(join_split_impls::check(&info.path, &info.contents, tree));
-				}
-				if opts.impl_follows_type {
-					all_violations.extend(impl_follows_type::check(&info.path, &info.contents, tree));
-				}
-				if opts.impl_folds {
-					all_violations.extend(impl_folds::check(&info.path, &info.contents, tree));
-				}
-				if opts.embed_simple_vars {
-					all_violations.extend(embed_simple_vars::check(&info.path, &info.contents, tree));
-				}
-				if opts.insta_inline_snapshot {
-					all_violations.extend(insta_snapshots::check(&info.path, &info.contents, tree, false));
-				}
-				if opts.no_chrono {
-					all_violations.extend(no_chrono::check(&info.path, &info.contents, tree));
-				}
-				if opts.no_tokio_spawn {
-					all_violations.extend(no_tokio_spawn::check(&info.path, &info.contents, tree));
+			if write_or_diff(&toml_path, &original, &new_content, dry_run) {
+				fixed_count += toml_fixed;
+			}
+		}
+	}
+
+	// Module files move first, so the fixes below are made, and re-checked, where the files end up
+	for (file_path, file_opts) in &mut targets.rust_files {
+		if !file_opts.severity_for(file_path, mod_style::INFO.option).is_enabled() {
+			continue;
+		}
+		let Ok(contents) = fs::read_to_string(&*file_path) else {
+			continue;
+		};
+		let Some(new_path) = mod_style::check(file_path, &contents, file_opts.mod_style_prefer).into_iter().find_map(|v| v.rename) else {
+			continue;
+		};
+		if dry_run {
+			// As git's diff headers put it
+			println!("rename from {}\nrename to {}", file_path.display(), new_path.display());
+			fixed_count += 1;
+			continue;
+		}
+		match mod_style::rename(file_path, &new_path) {
+			Ok(()) => {
+				debug!(from = %file_path.display(), to = %new_path.display(), "moved module file");
+				*file_path = new_path;
+				fixed_count += 1;
+			}
+			Err(e) => eprintln!("Failed to move {file_path:?} to {new_path:?}: {e}"),
+		}
+	}
+
+	// Process files iteratively - when a fix is applied, re-check that file
+	for (file_path, file_opts) in targets.rust_files {
+		let (file_fixed, file_unfixable) = format_file_iteratively(&file_path, &file_opts, &rustfmt, dry_run);
+		fixed_count += file_fixed;
+		unfixable_violations.extend(file_unfixable);
+	}
+
+	if dry_run {
+		return report_dry_run(fixed_count, &unfixable_violations);
+	}
+	report_format(fixed_count, &unfixable_violations)
+}
+
+/// The files a run covers, each with the options of the crate it belongs to.
+struct Targets {
+	cargo_tomls: Vec<(PathBuf, Severity)>,
+	rust_files: Vec<(PathBuf, RustCheckOptions)>,
+}
+impl Targets {
+	/// Directories contribute their crates' manifests and source dirs; `Cargo.toml` and `.rs` files are taken as-is
+	/// and anything else is skipped, so hooks can pass every staged file.
+	fn collect(paths: &[PathBuf], opts: &impl CrateOptions, exclude: &Exclude) -> Result<Self, String> {
+		let mut targets = Self {
+			cargo_tomls: Vec::new(),
+			rust_files: Vec::new(),
+		};
+		// A `#[path]` module can be reached from more than one source dir
+		let mut seen = HashSet::new();
+		for path in paths {
+			if path.is_dir() {
+				let src_dirs = find_src_dirs(path);
+				if src_dirs.is_empty() {
+					return Err(format!("No source directories found in {path:?}"));
 				}
-				if opts.use_bail {
-					all_violations.extend(use_bail::check(&info.path, &info.contents, tree));
+				for toml_path in collect_cargo_tomls(path).into_iter().filter(|p| !exclude.matches(p)) {
+					let severity = opts.for_dir(toml_path.parent().unwrap_or(path)).cargo_dep_ordering;
+					targets.cargo_tomls.push((toml_path, severity));
 				}
-				if opts.test_fn_prefix {
-					all_violations.extend(test_fn_prefix::check(&info.path, &info.contents, tree));
+				for src_dir in src_dirs {
+					// Custom target paths are files; their options come from the directory they're in
+					let crate_opts = opts.for_dir(if src_dir.is_file() { src_dir.parent().unwrap_or(path) } else { &src_dir });
+					let files = rust_file_paths(&src_dir, exclude).into_iter().filter(|p| seen.insert(p.clone()));
+					targets.rust_files.extend(files.map(|p| (p, crate_opts.clone())));
 				}
-				if opts.pub_first {
-					all_violations.extend(pub_first::check(&info.path, &info.contents, tree));
+			} else if path.is_file() {
+				if exclude.matches(path) {
+					continue;
 				}
-				if opts.ignored_error_comment {
-					all_violations.extend(ignored_error_comment::check(&info.path, &info.contents, tree));
+				let dir = path.parent().filter(|d| !d.as_os_str().is_empty()).unwrap_or(Path::new("."));
+				if path.file_name().is_some_and(|name| name == "Cargo.toml") {
+					targets.cargo_tomls.push((path.clone(), opts.for_dir(dir).cargo_dep_ordering));
+				} else if path.extension().is_some_and(|ext| ext == "rs") {
+					targets.rust_files.push((path.clone(), opts.for_dir(dir)));
 				}
+			} else {
+				return Err(format!("Path does not exist: {path:?}"));
 			}
 		}
+		Ok(targets)
 	}
+}
 
-	if all_violations.is_empty() {
-		println!("codestyle: all checks passed");
-		0
-	} else {
-		eprintln!("codestyle: found {} violation(s):\n", all_violations.len());
-		for v in &all_violations {
-			eprintln!("  [{}] {}:{}:{}: {}", v.rule, v.file, v.line, v.column, v.message);
-		}
-		1
+/// Directory that paths in baselines, `--changed` and `rustfmt.toml` lookup are relative to:
+/// the target itself when it's a single directory, the current directory otherwise.
+pub fn run_root(paths: &[PathBuf]) -> &Path {
+	match paths {
+		[dir] if dir.is_dir() => dir,
+		_ => Path::new("."),
 	}
 }
 
-pub fn run_format(target_dir: &Path, opts: &RustCheckOptions) -> i32 {
-	if !target_dir.exists() {
-		eprintln!("Target directory does not exist: {target_dir:?}");
+/// Format Rust source read from stdin and write the result to stdout, for editors that pipe the buffer through on save.
+/// Options and `rustfmt.toml` are looked up from the current directory.
+/// Violations that need manual fixing go to stderr but don't change the exit code: the fixed source is the output either way.
+pub fn run_format_stdin(opts: &impl CrateOptions) -> i32 {
+	let mut original = String::new();
+	if let Err(e) = io::stdin().read_to_string(&mut original) {
+		eprintln!("Failed to read stdin: {e}");
 		return 1;
 	}
-
-	let src_dirs = find_src_dirs(target_dir);
-	if src_dirs.is_empty() {
-		eprintln!("No source directories found");
+	// Echo unparseable input back unchanged, so a filter never empties the buffer
+	if let Err(e) = parse_file(&original) {
+		print!("{original}");
+		eprintln!("Failed to parse stdin: {e}");
 		return 1;
 	}
 
-	// Delete any .snap and .pending-snap files in the target directory (only if insta check is enabled)
-	if opts.insta_inline_snapshot {
-		delete_snap_files(target_dir);
+	let dir = Path::new(".");
+	let (formatted, _, unfixable) = format_source(Path::new(STDIN_PATH), &original, &opts.for_dir(dir), &RustfmtConfig::load(dir));
+	print!("{formatted}");
+	for v in &unfixable {
+		print_violation(v);
 	}
+	0
+}
 
-	let mut fixed_count = 0;
-	let mut unfixable_violations = Vec::new();
+/// Run all enabled source checks on a single parsed file.
+/// Generated files have no violations.
+pub fn check_file(info: &FileInfo, opts: &RustCheckOptions, rustfmt: &RustfmtConfig) -> Vec<Violation> {
+	run_rules(info, opts, rustfmt, false, rules::SOURCE_RULES)
+}
 
-	// Cargo.toml checks
-	if opts.cargo_dep_ordering {
-		for toml_path in collect_cargo_tomls(target_dir) {
-			if let Ok(content) = fs::read_to_string(&toml_path) {
-				let violations = cargo_dep_ordering::check(&toml_path, &content);
-				for v in violations {
-					if let Some(fix) = v.fix {
-						if fix.start_byte <= content.len() && fix.end_byte <= content.len() {
-							let mut new_content = content.clone();
-							new_content.replace_range(fix.start_byte..fix.end_byte, &fix.replacement);
-							if fs::write(&toml_path, new_content).is_ok() {
-								fixed_count += 1;
-							}
-						}
-					} else {
-						unfixable_violations.push(v);
-					}
-				}
+/// Violations of every enabled rule out of `rules`, in order, with their configured severities.
+fn run_rules<'r>(info: &FileInfo, opts: &RustCheckOptions, rustfmt: &RustfmtConfig, fixing: bool, rules: impl IntoIterator<Item = &'r &'r dyn Rule>) -> Vec<Violation> {
+	let Some(tree) = &info.syntax_tree else {
+		return Vec::new();
+	};
+	if generated::is_generated(&info.contents) {
+		debug!(path = %info.path.display(), "skipped: generated");
+		return Vec::new();
+	}
+	let tests = TestContext::new(&info.path, tree);
+	let ctx = Context { file: info, opts, tree, rustfmt, fixing, tests: &tests };
+	let mut violations = Vec::new();
+	for rule in rules {
+		let severity = opts.severity_for(&info.path, rule.info().option);
+		if severity.is_enabled() && rule.info().may_apply(&info.contents) {
+			let start = Instant::now();
+			let mut found = rule.check(&ctx);
+			trace!(path = %info.path.display(), rule = rule.info().id, elapsed = ?start.elapsed(), violations = found.len(), "rule ran");
+			if opts.test_exempt.iter().any(|option| option == rule.info().option) {
+				found.retain(|v| !tests.contains(v.line));
 			}
+			violations.extend(severity.assign(found));
 		}
 	}
+	violations.extend(custom_rules::check(&info.path, &info.contents, tree, &opts.custom_rules));
+	// Plugins stamp their own severities
+	for plugin in plugins::load(&opts.plugins) {
+		violations.extend(plugin.check(&ctx));
+	}
+	// Checks the whole file again per marker, whichever `rules` this run is for
+	let unused_severity = opts.severity_for(&info.path, unused_skip::INFO.option);
+	if unused_severity.is_enabled() && unused_skip::INFO.may_apply(&info.contents) {
+		violations.extend(unused_severity.assign(unused_skip::check(info, opts, rustfmt, fixing)));
+	}
 
-	// Process files iteratively - when a fix is applied, re-check that file
-	for src_dir in src_dirs {
-		let file_paths: Vec<PathBuf> = collect_rust_files(&src_dir).into_iter().map(|f| f.path).collect();
+	let (regions, unmatched) = skip::skip_regions(&info.contents);
+	// Missing reasons can't be skipped, or a region would excuse its own marker
+	violations.retain(|v| v.rule == skip_reason::RULE || !regions.iter().any(|region| region.covers(v.line, v.rule)));
+	violations.extend(unmatched.into_iter().map(|marker| unmatched_skip_marker(&info.path, marker)));
+	violations.extend(skip::expired_skips(&info.contents).into_iter().map(|expired| expired_skip(&info.path, expired)));
+	violations
+}
 
-		for file_path in file_paths {
-			let (file_fixed, file_unfixable) = format_file_iteratively(&file_path, opts);
-			fixed_count += file_fixed;
-			unfixable_violations.extend(file_unfixable);
+/// A skip marker that stopped applying at its `until` date, so whatever it suppressed is reported again.
+fn expired_skip(path: &Path, expired: skip::ExpiredSkip) -> Violation {
+	let message = if expired.valid {
+		format!("expired suppression: skip marker was only valid until {}", expired.until)
+	} else {
+		format!("skip marker's `until` isn't a YYYY-MM-DD date: `{}`", expired.until)
+	};
+	Violation {
+		rule: EXPIRED_SKIP_RULE,
+		file: path.display().to_string(),
+		line: expired.line,
+		column: expired.column,
+		message,
+		..Default::default()
+	}
+}
+
+/// A skip region marker that doesn't pair up. These are always errors, since the code it was meant to skip is checked after all.
+fn unmatched_skip_marker(path: &Path, marker: skip::UnmatchedMarker) -> Violation {
+	let (line, column, message) = match marker {
+		skip::UnmatchedMarker::Start(line, column) => (line, column, "`codestyle::skip-start` without a matching `codestyle::skip-end`"),
+		skip::UnmatchedMarker::End(line, column) => (line, column, "`codestyle::skip-end` without a matching `codestyle::skip-start`"),
+	};
+	Violation {
+		rule: SKIP_REGION_RULE,
+		file: path.display().to_string(),
+		line,
+		column,
+		message: message.to_string(),
+		..Default::default()
+	}
+}
+
+/// Print assert-mode results and return the process exit code.
+/// Shared by all language subcommands, so their output stays uniform. Which violations fail the run is up to `fail`.
+pub fn report_assert(violations: &[Violation], format: OutputFormat, fail: FailPolicy) -> i32 {
+	let exit_code = fail.exit_code(violations);
+	match format {
+		OutputFormat::Text | OutputFormat::Pretty => {}
+		OutputFormat::Json => {
+			println!("{}", violations_json(violations));
+			return exit_code;
+		}
+		OutputFormat::Sarif => {
+			println!("{}", sarif::to_sarif(violations));
+			return exit_code;
+		}
+		OutputFormat::Rustfix => {
+			print!("{}", rustfix::to_diagnostics(violations));
+			return exit_code;
 		}
 	}
 
-	if fixed_count == 0 && unfixable_violations.is_empty() {
-		println!("codestyle: all checks passed, nothing to format");
-		0
-	} else {
-		if fixed_count > 0 {
-			println!("codestyle: fixed {fixed_count} violation(s)");
+	let failing = fail.failing(violations);
+	let (errors, warnings): (Vec<&Violation>, Vec<&Violation>) = violations.iter().partition(|v| v.severity == Severity::Error);
+	if format == OutputFormat::Pretty {
+		eprint!("{}", pretty::render_all(&warnings));
+		eprint!("{}", pretty::render_all(&errors));
+		match (errors.len(), warnings.len()) {
+			(0, 0) => println!("codestyle: all checks passed"),
+			(0, w) => eprintln!("codestyle: {w} warning(s)"),
+			(e, w) => eprintln!("codestyle: {e} error(s), {w} warning(s)"),
 		}
+		print_budget(failing, fail);
+		return exit_code;
+	}
 
-		if !unfixable_violations.is_empty() {
-			eprintln!("codestyle: {} violation(s) need manual fixing:\n", unfixable_violations.len());
-			for v in &unfixable_violations {
-				eprintln!("  [{}] {}:{}:{}: {}", v.rule, v.file, v.line, v.column, v.message);
-			}
-			1
-		} else {
-			0
+	print_warnings(&warnings);
+	if !errors.is_empty() {
+		eprintln!("codestyle: found {} violation(s):\n", errors.len());
+		for v in errors {
+			print_violation(v);
 		}
+	} else if failing == 0 {
+		println!("codestyle: all checks passed");
+	} else if fail.max_violations == 0 {
+		eprintln!("codestyle: failing on {failing} warning(s)");
 	}
+	print_budget(failing, fail);
+	exit_code
 }
 
-pub fn collect_rust_files(target_dir: &Path) -> Vec<FileInfo> {
-	let mut file_infos = Vec::new();
+/// How the failing violations compare to `--max-violations`, when there's a budget to compare against.
+fn print_budget(failing: usize, fail: FailPolicy) {
+	if fail.max_violations > 0 && failing > 0 {
+		let verdict = if failing > fail.max_violations { "over" } else { "within" };
+		eprintln!("codestyle: {failing} failing violation(s), {verdict} the --max-violations budget of {}", fail.max_violations);
+	}
+}
 
-	let walker = WalkDir::new(target_dir).into_iter().filter_entry(|e| {
-		let name = e.file_name().to_string_lossy();
-		!name.starts_with('.') && name != "target" && name != "libs"
-	});
+/// Print format-mode results and return the process exit code.
+pub fn report_format(fixed_count: usize, unfixable_violations: &[Violation]) -> i32 {
+	if fixed_count == 0 && unfixable_violations.is_empty() {
+		println!("codestyle: all checks passed, nothing to format");
+		return 0;
+	}
+	if fixed_count > 0 {
+		println!("codestyle: fixed {fixed_count} violation(s)");
+	}
 
-	for entry in walker.filter_map(Result::ok) {
-		let path = entry.path().to_path_buf();
-		if path.extension().is_some_and(|ext| ext == "rs")
-			&& let Some(info) = parse_rust_file(path)
-		{
-			file_infos.push(info);
-		}
+	let (errors, warnings): (Vec<&Violation>, Vec<&Violation>) = unfixable_violations.iter().partition(|v| v.severity == Severity::Error);
+	print_warnings(&warnings);
+	if errors.is_empty() {
+		return 0;
+	}
+	eprintln!("codestyle: {} violation(s) need manual fixing:\n", errors.len());
+	for v in errors {
+		print_violation(v);
 	}
-	file_infos
+	1
 }
-/// Format a single file iteratively - apply one fix at a time, re-parse, repeat.
-/// Unfixable violations are only collected on the final pass (when no more fixes are found),
-/// ensuring line numbers are stable and no duplicates are reported.
-fn format_file_iteratively(file_path: &Path, opts: &RustCheckOptions) -> (usize, Vec<Violation>) {
-	let mut fixed_count = 0;
-
-	loop {
-		let Some(info) = parse_rust_file(file_path.to_path_buf()) else {
-			break;
-		};
 
-		// Find the first fixable violation
-		let mut first_fix: Option<(Violation, Fix)> = None;
+/// Violations as a JSON array of `{rule, file, line, column, end_line, end_column, message, severity, fixable, labels, notes, suggestions}` objects.
+pub fn violations_json(violations: &[Violation]) -> String {
+	let entries: Vec<ViolationJson> = violations.iter().map(ViolationJson::from).collect();
+	serde_json::to_string_pretty(&entries).expect("violations are always serializable")
+}
 
-		if opts.instrument {
-			for v in instrument::check_instrument(&info) {
-				if let Some(fix) = v.fix.clone() {
-					first_fix = Some((v, fix));
-					break;
-				}
-			}
+/// A violation as it appears in JSON output, shared by `--format json` and the daemon.
+#[derive(Serialize)]
+struct ViolationJson<'a> {
+	rule: &'a str,
+	file: &'a str,
+	line: usize,
+	column: usize,
+	end_line: Option<usize>,
+	end_column: Option<usize>,
+	message: &'a str,
+	severity: String,
+	fixable: bool,
+	labels: &'a [Label],
+	notes: &'a [String],
+	suggestions: &'a [Suggestion],
+}
+impl<'a> From<&'a Violation> for ViolationJson<'a> {
+	fn from(v: &'a Violation) -> Self {
+		Self {
+			rule: v.rule,
+			file: &v.file,
+			line: v.line,
+			column: v.column,
+			end_line: v.end.map(|(line, _)| line),
+			end_column: v.end.map(|(_, column)| column),
+			message: &v.message,
+			severity: v.severity.to_string(),
+			fixable: v.is_fixable(),
+			labels: &v.labels,
+			notes: &v.notes,
+			suggestions: &v.suggestions,
 		}
+	}
+}
 
-		if first_fix.is_none() && opts.loops {
-			for v in loops::check_loops(&info) {
-				if let Some(fix) = v.fix.clone() {
-					first_fix = Some((v, fix));
-					break;
-				}
-			}
+/// Print `format --dry-run` results (the diffs themselves are already on stdout) and return the process exit code.
+/// Like `rustfmt --check`, pending fixes fail the run too.
+fn report_dry_run(fixable_count: usize, unfixable_violations: &[Violation]) -> i32 {
+	let (errors, warnings): (Vec<&Violation>, Vec<&Violation>) = unfixable_violations.iter().partition(|v| v.severity == Severity::Error);
+	print_warnings(&warnings);
+	if fixable_count > 0 {
+		eprintln!("codestyle: would fix {fixable_count} violation(s)");
+	}
+	if !errors.is_empty() {
+		eprintln!("codestyle: {} violation(s) need manual fixing:\n", errors.len());
+		for v in &errors {
+			print_violation(v);
 		}
+	}
+	i32::from(fixable_count > 0 || !errors.is_empty())
+}
 
-		if let Some(ref tree) = info.syntax_tree {
-			// Order matters: join_split_impls -> impl_follows_type -> impl_folds
-			if first_fix.is_none() && opts.join_split_impls {
-				for v in join_split_impls::check(&info.path, &info.contents, tree) {
-					if let Some(fix) = v.fix.clone() {
-						first_fix = Some((v, fix));
-						break;
-					}
-				}
-			}
-
-			if first_fix.is_none() && opts.impl_follows_type {
-				for v in impl_follows_type::check(&info.path, &info.contents, tree) {
-					if let Some(fix) = v.fix.clone() {
-						first_fix = Some((v, fix));
-						break;
-					}
-				}
-			}
-
-			if first_fix.is_none() && opts.impl_folds {
-				for v in impl_folds::check(&info.path, &info.contents, tree) {
-					if let Some(fix) = v.fix.clone() {
-						first_fix = Some((v, fix));
-						break;
-					}
-				}
-			}
-
-			if first_fix.is_none() && opts.embed_simple_vars {
-				for v in embed_simple_vars::check(&info.path, &info.contents, tree) {
-					if let Some(fix) = v.fix.clone() {
-						first_fix = Some((v, fix));
-						break;
-					}
-				}
-			}
-
-			if first_fix.is_none() && opts.insta_inline_snapshot {
-				for v in insta_snapshots::check(&info.path, &info.contents, tree, true) {
-					if let Some(fix) = v.fix.clone() {
-						first_fix = Some((v, fix));
-						break;
-					}
-				}
-			}
+/// One violation of the text output, with its labels, notes and suggestions on indented lines below it.
+fn print_violation(v: &Violation) {
+	eprintln!("  [{}] {}:{}:{}: {}", v.rule, v.file, v.line, v.column, v.message);
+	for label in &v.labels {
+		eprintln!("      {}:{}:{}: {}", v.file, label.line, label.column, label.message);
+	}
+	for note in &v.notes {
+		eprintln!("      note: {note}");
+	}
+	for suggestion in &v.suggestions {
+		eprintln!("      help: {}", suggestion.message);
+	}
+}
 
-			if first_fix.is_none() && opts.no_chrono {
-				for v in no_chrono::check(&info.path, &info.contents, tree) {
-					if let Some(fix) = v.fix.clone() {
-						first_fix = Some((v, fix));
-						break;
-					}
-				}
-			}
+fn print_warnings(warnings: &[&Violation]) {
+	if warnings.is_empty() {
+		return;
+	}
+	eprintln!("codestyle: {} warning(s):\n", warnings.len());
+	for v in warnings {
+		print_violation(v);
+	}
+	eprintln!();
+}
 
-			if first_fix.is_none() && opts.no_tokio_spawn {
-				for v in no_tokio_spawn::check(&info.path, &info.contents, tree) {
-					if let Some(fix) = v.fix.clone() {
-						first_fix = Some((v, fix));
-						break;
-					}
-				}
-			}
+pub fn collect_rust_files(target_dir: &Path, exclude: &Exclude) -> Vec<FileInfo> {
+	rust_file_paths(target_dir, exclude)
+		.into_iter()
+		.filter_map(parse_rust_file)
+		.filter(|info| !generated::is_generated(&info.contents))
+		.collect()
+}
 
-			if first_fix.is_none() && opts.use_bail {
-				for v in use_bail::check(&info.path, &info.contents, tree) {
-					if let Some(fix) = v.fix.clone() {
-						first_fix = Some((v, fix));
-						break;
-					}
-				}
-			}
+/// Every `.rs` file under `target_dir` that `exclude` doesn't skip, plus the `#[path]` modules they pull in from elsewhere.
+fn rust_file_paths(target_dir: &Path, exclude: &Exclude) -> Vec<PathBuf> {
+	let walker = WalkDir::new(target_dir).into_iter().filter_entry(|e| {
+		let skipped = if e.file_type().is_dir() { exclude.skips_dir(e.path()) } else { exclude.matches(e.path()) };
+		e.depth() == 0 || !skipped
+	});
 
-			if first_fix.is_none() && opts.test_fn_prefix {
-				for v in test_fn_prefix::check(&info.path, &info.contents, tree) {
-					if let Some(fix) = v.fix.clone() {
-						first_fix = Some((v, fix));
-						break;
-					}
-				}
-			}
+	let mut paths: Vec<PathBuf> = walker
+		.filter_map(Result::ok)
+		.map(|entry| entry.into_path())
+		.filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+		.collect();
+	let outside = path_modules::outside(&paths, target_dir);
+	paths.extend(outside.into_iter().filter(|p| !exclude.matches(p)));
+	paths
+}
+/// Format a single file; the result is written (or diffed, with `dry_run`) once at the end.
+fn format_file_iteratively(file_path: &Path, opts: &RustCheckOptions, rustfmt: &RustfmtConfig, dry_run: bool) -> (usize, Vec<Violation>) {
+	let Ok(original) = fs::read_to_string(file_path) else {
+		return (0, Vec::new());
+	};
+	let (formatted, fixed_count, unfixable) = format_source(file_path, &original, opts, rustfmt);
+	if !write_or_diff(file_path, &original, &formatted, dry_run) {
+		return (0, unfixable);
+	}
+	(fixed_count, unfixable)
+}
 
-			if first_fix.is_none() && opts.pub_first {
-				for v in pub_first::check(&info.path, &info.contents, tree) {
-					if let Some(fix) = v.fix.clone() {
-						first_fix = Some((v, fix));
-						break;
-					}
-				}
-			}
+/// Format source iteratively: apply every fix that doesn't conflict with another, re-parse, repeat.
+/// Conflicting fixes are left for a later pass, where they're recomputed against the updated source;
+/// rules earlier in the order win, which keeps join_split_impls -> impl_follows_type -> trait_impls_follow -> impl_folds sequenced.
+/// Unfixable violations are only collected on the final pass (when no more fixes are found),
+/// ensuring line numbers are stable and no duplicates are reported.
+/// Returns the fixed source, the number of fixes applied, and the unfixable violations; generated files come back as-is.
+/// Fixes are applied to the `\n`-normalized source. A fixed file is given the line ending `rustfmt.toml` asks for (its own by default),
+/// an unfixed one comes back untouched.
+pub(crate) fn format_source(path: &Path, original: &str, opts: &RustCheckOptions, rustfmt: &RustfmtConfig) -> (String, usize, Vec<Violation>) {
+	let mut contents = line_endings::normalize(original).into_owned();
+	let mut fixed_count = 0;
+	let mut unfixable = Vec::new();
+	if generated::is_generated(original) {
+		return (original.to_string(), fixed_count, unfixable);
+	}
 
-			if first_fix.is_none() && opts.ignored_error_comment {
-				for v in ignored_error_comment::check(&info.path, &info.contents, tree) {
-					if let Some(fix) = v.fix.clone() {
-						first_fix = Some((v, fix));
-						break;
-					}
-				}
-			}
-		}
+	for pass in 1.. {
+		let Some(info) = parse_rust_source(path.to_path_buf(), contents.clone()) else {
+			break;
+		};
 
-		// Apply the fix if found
-		let Some((_violation, fix)) = first_fix else {
+		let fixes = collect_fixes(&info, opts, rustfmt);
+		if fixes.is_empty() {
 			// No more fixes - collect unfixable violations now (final pass)
-			return (fixed_count, collect_unfixable(&info, opts));
-		};
+			unfixable = collect_unfixable(&info, opts, rustfmt);
+			break;
+		}
+		// Fixes that keep recreating their own violations, like a buggy plugin's, would otherwise loop forever
+		if pass == MAX_FIX_PASSES {
+			eprintln!("codestyle: fixes for {path:?} still haven't settled after {MAX_FIX_PASSES} passes; stopping");
+			unfixable = collect_unfixable(&info, opts, rustfmt);
+			break;
+		}
 
-		if fix.start_byte <= info.contents.len() && fix.end_byte <= info.contents.len() {
-			let mut new_content = info.contents.clone();
-			new_content.replace_range(fix.start_byte..fix.end_byte, &fix.replacement);
-			if fs::write(file_path, new_content).is_ok() {
-				fixed_count += 1;
-				// Loop again to find more violations in the modified file
-				continue;
+		let batch = non_conflicting(fixes, contents.len());
+		if batch.is_empty() {
+			break;
+		}
+		debug!(path = %path.display(), pass, fixes = batch.len(), "applying fixes");
+		// Back to front, so each fix's byte range is still valid when it's applied
+		for (rule, fix) in &batch {
+			if tracing::enabled!(Level::DEBUG) {
+				let (line, column) = LineIndex::new(&contents).position(fix.start_byte);
+				debug!(path = %path.display(), pass, rule, line, column = column + 1, "applied fix");
 			}
+			contents.replace_range(fix.start_byte..fix.end_byte, &line_endings::normalize(&fix.replacement));
 		}
-
-		break;
+		fixed_count += batch.len();
 	}
 
-	(fixed_count, Vec::new())
+	if fixed_count == 0 {
+		return (original.to_string(), fixed_count, unfixable);
+	}
+	(rustfmt.line_ending(original).apply(&contents).into_owned(), fixed_count, unfixable)
 }
 
-/// Collect all unfixable violations from a file (called only on final pass)
-fn collect_unfixable(info: &FileInfo, opts: &RustCheckOptions) -> Vec<Violation> {
-	let mut unfixable = Vec::new();
+/// Every fix the enabled rules offer for `info`, with the rule offering it, in rule order, which is also their
+/// priority when fixes conflict.
+fn collect_fixes(info: &FileInfo, opts: &RustCheckOptions, rustfmt: &RustfmtConfig) -> Vec<(&'static str, Fix)> {
+	let fixable = rules::SOURCE_RULES.iter().filter(|rule| rule.fixable());
+	run_rules(info, opts, rustfmt, true, fixable).into_iter().filter_map(|v| Some((v.rule, v.fix?))).collect()
+}
 
-	if opts.instrument {
-		unfixable.extend(instrument::check_instrument(info).into_iter().filter(|v| v.fix.is_none()));
+/// The fixes that can be applied in one pass, sorted back to front. A fix overlapping or touching one kept before it
+/// is dropped, as is one outside `len` bytes of source.
+fn non_conflicting(fixes: Vec<(&'static str, Fix)>, len: usize) -> Vec<(&'static str, Fix)> {
+	let mut batch: Vec<(&'static str, Fix)> = Vec::new();
+	for (rule, fix) in fixes {
+		let in_bounds = fix.start_byte <= fix.end_byte && fix.end_byte <= len;
+		if in_bounds && batch.iter().all(|(_, kept)| fix.end_byte < kept.start_byte || kept.end_byte < fix.start_byte) {
+			batch.push((rule, fix));
+		} else {
+			trace!(rule, start_byte = fix.start_byte, "fix left for a later pass: out of bounds, or overlaps one kept before it");
+		}
 	}
-	if opts.loops {
-		unfixable.extend(loops::check_loops(info).into_iter().filter(|v| v.fix.is_none()));
+	batch.sort_by_key(|(_, fix)| Reverse(fix.start_byte));
+	batch
+}
+
+/// Write `formatted` over `path`, or with `dry_run` print the change as a unified diff on stdout. Returns whether it succeeded.
+fn write_or_diff(path: &Path, original: &str, formatted: &str, dry_run: bool) -> bool {
+	if original == formatted {
+		return true;
 	}
-	if let Some(ref tree) = info.syntax_tree {
-		if opts.join_split_impls {
-			unfixable.extend(join_split_impls::check(&info.path, &info.contents, tree).into_iter().filter(|v| v.fix.is_none()));
-		}
-		if opts.impl_follows_type {
-			unfixable.extend(impl_follows_type::check(&info.path, &info.contents, tree).into_iter().filter(|v| v.fix.is_none()));
-		}
-		if opts.impl_folds {
-			unfixable.extend(impl_folds::check(&info.path, &info.contents, tree).into_iter().filter(|v| v.fix.is_none()));
-		}
-		if opts.embed_simple_vars {
-			unfixable.extend(embed_simple_vars::check(&info.path, &info.contents, tree).into_iter().filter(|v| v.fix.is_none()));
-		}
-		if opts.insta_inline_snapshot {
-			unfixable.extend(insta_snapshots::check(&info.path, &info.contents, tree, true).into_iter().filter(|v| v.fix.is_none()));
-		}
-		if opts.no_chrono {
-			unfixable.extend(no_chrono::check(&info.path, &info.contents, tree).into_iter().filter(|v| v.fix.is_none()));
-		}
-		if opts.no_tokio_spawn {
-			unfixable.extend(no_tokio_spawn::check(&info.path, &info.contents, tree).into_iter().filter(|v| v.fix.is_none()));
-		}
-		if opts.use_bail {
-			unfixable.extend(use_bail::check(&info.path, &info.contents, tree).into_iter().filter(|v| v.fix.is_none()));
-		}
-		if opts.test_fn_prefix {
-			unfixable.extend(test_fn_prefix::check(&info.path, &info.contents, tree).into_iter().filter(|v| v.fix.is_none()));
-		}
-		if opts.pub_first {
-			unfixable.extend(pub_first::check(&info.path, &info.contents, tree).into_iter().filter(|v| v.fix.is_none()));
-		}
-		if opts.ignored_error_comment {
-			unfixable.extend(ignored_error_comment::check(&info.path, &info.contents, tree).into_iter().filter(|v| v.fix.is_none()));
-		}
+	if dry_run {
+		let name = path.display().to_string();
+		print!("{}", similar::TextDiff::from_lines(original, formatted).unified_diff().header(&name, &name));
+		return true;
 	}
+	fs::write(path, formatted).inspect_err(|e| eprintln!("Failed to write {path:?}: {e}")).is_ok()
+}
 
-	unfixable
+/// Collect all unfixable violations from a file (called only on final pass)
+fn collect_unfixable(info: &FileInfo, opts: &RustCheckOptions, rustfmt: &RustfmtConfig) -> Vec<Violation> {
+	run_rules(info, opts, rustfmt, true, rules::SOURCE_RULES).into_iter().filter(|v| !v.is_fixable()).collect()
 }
 
 fn find_src_dirs(root: &Path) -> Vec<PathBuf> {
@@ -482,7 +1384,7 @@ fn find_src_dirs(root: &Path) -> Vec<PathBuf> {
 		return vec![];
 	}
 
-	let members = resolve_workspace_members(root);
+	let members = workspace::packages(root);
 	if members.is_empty() {
 		return collect_standard_dirs(root);
 	}
@@ -494,72 +1396,18 @@ fn find_src_dirs(root: &Path) -> Vec<PathBuf> {
 	dirs
 }
 
-/// Parse workspace members from Cargo.toml, expanding glob patterns.
-/// Returns resolved directory paths for each member.
-/// Returns empty vec if no [workspace] section or no members found.
-fn resolve_workspace_members(root: &Path) -> Vec<PathBuf> {
-	let cargo_toml = root.join("Cargo.toml");
-	let content = match fs::read_to_string(&cargo_toml) {
-		Ok(c) => c,
-		Err(_) => return vec![],
-	};
-
-	let mut in_workspace = false;
-	let mut patterns = Vec::new();
-
-	for line in content.lines() {
-		let trimmed = line.trim();
-		if trimmed == "[workspace]" {
-			in_workspace = true;
-		} else if trimmed.starts_with('[') && trimmed != "[workspace]" {
-			in_workspace = false;
-		} else if in_workspace
-			&& trimmed.starts_with("members")
-			&& let Some(start) = line.find('[')
-			&& let Some(end) = line.find(']')
-		{
-			let list = &line[start + 1..end];
-			for member in list.split(',') {
-				let member = member.trim().trim_matches('"').trim_matches('\'');
-				if !member.is_empty() {
-					patterns.push(member.to_string());
-				}
-			}
-		}
-	}
-
-	let mut members = Vec::new();
-	for pattern in patterns {
-		if pattern.contains('*') {
-			// Simple glob: only support trailing `*` after a prefix, e.g. `foo_*`
-			let prefix = pattern.trim_end_matches('*');
-			let (parent, name_prefix) = if let Some(slash) = prefix.rfind('/') {
-				(root.join(&prefix[..slash]), &prefix[slash + 1..])
-			} else {
-				(root.to_path_buf(), prefix)
-			};
-
-			if let Ok(entries) = fs::read_dir(&parent) {
-				for entry in entries.filter_map(Result::ok) {
-					let name = entry.file_name();
-					let name = name.to_string_lossy();
-					if name.starts_with(name_prefix) && entry.path().is_dir() {
-						members.push(entry.path());
-					}
-				}
-			}
-		} else {
-			members.push(root.join(&pattern));
-		}
-	}
-
-	members
-}
-
-/// Collect standard Rust directories: src/, tests/, examples/, benches/
+/// Collect standard Rust directories: src/, tests/, examples/, benches/,
+/// plus the entry files of targets whose manifest `path` points outside them.
 fn collect_standard_dirs(root: &Path) -> Vec<PathBuf> {
 	let standard_dirs = ["src", "tests", "examples", "benches"];
-	standard_dirs.iter().map(|d| root.join(d)).filter(|p| p.exists()).collect()
+	let mut dirs: Vec<PathBuf> = standard_dirs.iter().map(|d| root.join(d)).filter(|p| p.exists()).collect();
+	let custom: Vec<PathBuf> = workspace::target_paths(root)
+		.into_iter()
+		.map(|p| workspace::normalize(&p))
+		.filter(|p| p.is_file() && !dirs.iter().any(|d| p.starts_with(workspace::normalize(d))))
+		.collect();
+	dirs.extend(custom);
+	dirs
 }
 
 /// Collect all Cargo.toml files in the workspace that may have [dependencies].
@@ -570,7 +1418,7 @@ fn collect_cargo_tomls(root: &Path) -> Vec<PathBuf> {
 		return vec![];
 	}
 
-	let members = resolve_workspace_members(root);
+	let members = workspace::packages(root);
 	if members.is_empty() {
 		// Standalone crate
 		return vec![cargo_toml];
@@ -581,6 +1429,13 @@ fn collect_cargo_tomls(root: &Path) -> Vec<PathBuf> {
 
 fn parse_rust_file(path: PathBuf) -> Option<FileInfo> {
 	let contents = fs::read_to_string(&path).ok()?;
+	parse_rust_source(path, contents)
+}
+
+/// Parse `contents`, normalized to `\n` line endings first.
+pub(crate) fn parse_rust_source(path: PathBuf, contents: String) -> Option<FileInfo> {
+	let line_ending = LineEnding::detect(&contents);
+	let contents = if contents.contains('\r') { line_endings::normalize(&contents).into_owned() } else { contents };
 	let syntax_tree = match parse_file(&contents) {
 		Ok(tree) => tree,
 		Err(e) => {
@@ -600,10 +1455,11 @@ fn parse_rust_file(path: PathBuf) -> Option<FileInfo> {
 		syntax_tree: Some(syntax_tree),
 		fn_items,
 		path,
+		line_ending,
 	})
 }
 
-fn delete_snap_files(target_dir: &Path) {
+fn delete_snap_files(target_dir: &Path, dry_run: bool) {
 	let walker = WalkDir::new(target_dir).into_iter().filter_entry(|e| {
 		let name = e.file_name().to_string_lossy();
 		!name.starts_with('.') && name != "target"
@@ -629,6 +1485,10 @@ fn delete_snap_files(target_dir: &Path) {
 
 	// Delete snapshots/ directories (this also removes all files inside)
 	for dir in snapshot_dirs_to_delete {
+		if dry_run {
+			eprintln!("codestyle: would delete snapshots dir {dir:?}");
+			continue;
+		}
 		if let Err(e) = fs::remove_dir_all(&dir) {
 			eprintln!("Warning: Failed to delete snapshots dir {dir:?}: {e}");
 		} else {
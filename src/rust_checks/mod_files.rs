@@ -0,0 +1,265 @@
+//! Lint to keep `mod` declarations and module files in step.
+//!
+//! Both ways are checked from the declaring file, so a refactor that moves or renames one side gets reported where
+//! the other side is: a `mod foo;` whose `foo.rs` or `foo/mod.rs` isn't there (or whose `#[path]` points nowhere), and
+//! a `.rs` file, or a directory with a `mod.rs`, in the directory a module's submodules live in with no `mod` naming it.
+//! The latter overlaps with `dead_files`, which reports the file itself.
+//!
+//! Crate roots in `tests/`, `examples/`, `benches/` and `src/bin/` share their directory with other crates, so files
+//! next to them aren't looked at; `main.rs` leaves `src/` to `lib.rs` when there's one, counting its own `mod`s there.
+//! A file `#[path]` loaded has its submodules next to it, which can't be told from the file alone, so a plain module
+//! found there counts too.
+
+use std::{
+	collections::HashSet,
+	fs,
+	path::{Path, PathBuf},
+};
+
+use proc_macro2::Span;
+use syn::{Item, ItemMod, spanned::Spanned, visit::Visit};
+
+use super::{
+	Severity, Violation, mod_style,
+	path_modules::{self, path_attr},
+	rules::{Context, Rule, RuleInfo},
+	skip::{self, SkipVisitor},
+	workspace::normalize,
+};
+
+const RULE: &str = "mod-files";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "mod_files",
+	autofix: false,
+	triggers: &[],
+	description: "Match mod declarations to module files, both ways",
+	rationale: "After a file is moved or renamed, a `mod` left pointing nowhere or a file no `mod` names is the first sign; reported at the declaration, it shows up where the module tree is read.",
+	bad: "// src/lib.rs, next to src/client.rs and src/retry.rs\nmod client;\nmod cache;",
+	good: "// src/lib.rs, next to src/client.rs and src/retry.rs\nmod client;\nmod retry;",
+	fix: "Create or rename the module file, declare the stray file with `mod`, or delete whichever side is stale.",
+};
+
+pub struct ModFiles;
+impl Rule for ModFiles {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+
+	/// The module files, the directory listing and `main.rs`' `lib.rs`
+	fn reads_other_files(&self) -> bool {
+		true
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+	let (Some(dir), Some(stem)) = (path.parent(), path.file_stem()) else {
+		return Vec::new();
+	};
+	let shared_dir = mod_style::is_targets_dir(dir);
+	let owns_dir = shared_dir || path_modules::is_root(path);
+	let top = Scope {
+		dir: if owns_dir { dir.to_path_buf() } else { dir.join(stem) },
+		flat_dir: (!owns_dir).then(|| dir.to_path_buf()),
+		declared: HashSet::new(),
+		anchor: None,
+	};
+	let visitor = ModVisitor {
+		path: path.to_path_buf(),
+		content,
+		attr_dir: dir.to_path_buf(),
+		scopes: vec![top],
+		path_targets: HashSet::new(),
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	let mut visitor = skip_visitor.inner;
+
+	let Some(mut top) = visitor.scopes.pop() else {
+		return visitor.violations;
+	};
+	let in_src = dir.file_name().is_some_and(|name| name == "src");
+	let is_named = |name: &str| path.file_name().is_some_and(|file_name| file_name == name);
+	if shared_dir || in_src && is_named("main.rs") && dir.join("lib.rs").is_file() {
+		return visitor.violations;
+	}
+	if in_src && is_named("lib.rs") {
+		top.declared.extend(top_level_modules(&dir.join("main.rs")));
+	}
+	visitor.report_unlisted(&top, in_src);
+	visitor.violations
+}
+
+/// A directory submodules are looked up in: the file's own, or one an inline module adds.
+struct Scope {
+	dir: PathBuf,
+	/// Where submodules can be too, if the file may have been loaded through `#[path]`
+	flat_dir: Option<PathBuf>,
+	/// Names of the modules declared in it
+	declared: HashSet<String>,
+	/// Where unlisted files are reported: the last out-of-line `mod` in it, or the inline module's name. The top of
+	/// the file if neither is there.
+	anchor: Option<Span>,
+}
+
+struct ModVisitor<'a> {
+	path: PathBuf,
+	content: &'a str,
+	/// What a top-level `#[path]` is relative to
+	attr_dir: PathBuf,
+	scopes: Vec<Scope>,
+	/// Files `#[path]` attributes point to, normalized
+	path_targets: HashSet<PathBuf>,
+	violations: Vec<Violation>,
+}
+
+impl ModVisitor<'_> {
+	fn check_declaration(&mut self, node: &ItemMod) {
+		let nested = self.scopes.len() > 1;
+		let Some(scope) = self.scopes.last_mut() else {
+			return;
+		};
+		let name = node.ident.to_string();
+		scope.anchor = Some(node.span());
+		let message = match path_attr(&node.attrs) {
+			Some(target) => {
+				let file = if nested { scope.dir.join(&target) } else { self.attr_dir.join(&target) };
+				let exists = file.is_file();
+				self.path_targets.insert(normalize(&file));
+				if exists {
+					return;
+				}
+				format!("`#[path = \"{target}\"]` points to `{}`, which doesn't exist\nHINT: fix the path, or drop the declaration", self.shown(&file))
+			}
+			None => {
+				scope.declared.insert(name.clone());
+				let candidates: Vec<PathBuf> = [&scope.dir]
+					.into_iter()
+					.chain(&scope.flat_dir)
+					.flat_map(|dir| [dir.join(format!("{name}.rs")), dir.join(&name).join("mod.rs")])
+					.collect();
+				if candidates.iter().any(|file| file.is_file()) {
+					return;
+				}
+				let [flat, in_dir, ..] = candidates.as_slice() else {
+					return;
+				};
+				format!(
+					"`mod {name};` has no file: neither `{}` nor `{}` exists\nHINT: create the file, or drop the declaration",
+					self.shown(flat),
+					self.shown(in_dir)
+				)
+			}
+		};
+		self.push(node.ident.span(), message);
+	}
+
+	/// Report the files in `scope`'s directory no `mod` names. In `src/`, crate roots and `bin/` aren't modules.
+	fn report_unlisted(&mut self, scope: &Scope, in_src: bool) {
+		let Ok(entries) = fs::read_dir(&scope.dir) else {
+			return;
+		};
+		let mut unlisted = Vec::new();
+		for entry in entries.flatten() {
+			let path = entry.path();
+			let (module, file) = if path.is_dir() {
+				(path.file_name(), path.join("mod.rs"))
+			} else if path.extension().is_some_and(|ext| ext == "rs") {
+				(path.file_stem(), path.clone())
+			} else {
+				continue;
+			};
+			let Some(module) = module.and_then(|module| module.to_str()).map(str::to_string) else {
+				continue;
+			};
+			let is_root = in_src && ["lib", "main", "bin"].contains(&module.as_str());
+			if !file.is_file() || module == "mod" || is_root || file == self.path || scope.declared.contains(&module) || self.path_targets.contains(&normalize(&file)) {
+				continue;
+			}
+			unlisted.push((module, file));
+		}
+		unlisted.sort();
+		let line = scope.anchor.map_or(1, |span| span.start().line);
+		if skip::has_skip_marker_for_rule_at_line(self.content, line, RULE) {
+			return;
+		}
+		for (module, file) in unlisted {
+			let message = format!("`{}` has no `mod {module};` declaring it, so it isn't compiled\nHINT: declare it here, or delete the file", self.shown(&file));
+			match scope.anchor {
+				Some(span) => self.push(span, message),
+				None => self.violations.push(Violation {
+					rule: RULE,
+					file: self.path.display().to_string(),
+					line: 1,
+					column: 0,
+					message,
+					severity: Severity::Error,
+					..Default::default()
+				}),
+			}
+		}
+	}
+
+	fn push(&mut self, span: Span, message: String) {
+		let start = span.start();
+		let end = span.end();
+		self.violations.push(Violation {
+			rule: RULE,
+			file: self.path.display().to_string(),
+			line: start.line,
+			column: start.column,
+			end: Some((end.line, end.column)),
+			message,
+			severity: Severity::Error,
+			..Default::default()
+		});
+	}
+
+	/// `file` relative to the declaring file's directory, as it's written in `#[path]`.
+	fn shown(&self, file: &Path) -> String {
+		file.strip_prefix(&self.attr_dir).unwrap_or(file).display().to_string()
+	}
+}
+
+impl<'a> Visit<'a> for ModVisitor<'_> {
+	fn visit_item_mod(&mut self, node: &'a ItemMod) {
+		if node.content.is_none() {
+			self.check_declaration(node);
+			return;
+		}
+		let Some(parent) = self.scopes.last() else {
+			return;
+		};
+		let name = path_attr(&node.attrs).unwrap_or_else(|| node.ident.to_string());
+		let scope = Scope {
+			dir: parent.dir.join(&name),
+			flat_dir: parent.flat_dir.as_ref().map(|dir| dir.join(&name)),
+			declared: HashSet::new(),
+			anchor: Some(node.ident.span()),
+		};
+		self.scopes.push(scope);
+		syn::visit::visit_item_mod(self, node);
+		if let Some(scope) = self.scopes.pop() {
+			self.report_unlisted(&scope, false);
+		}
+	}
+}
+
+/// Names of the plain `mod x;` declarations at the top of `file`.
+fn top_level_modules(file: &Path) -> Vec<String> {
+	let Some(tree) = fs::read_to_string(file).ok().and_then(|content| syn::parse_file(&content).ok()) else {
+		return Vec::new();
+	};
+	tree.items
+		.iter()
+		.filter_map(|item| match item {
+			Item::Mod(module) if module.content.is_none() && path_attr(&module.attrs).is_none() => Some(module.ident.to_string()),
+			_ => None,
+		})
+		.collect()
+}
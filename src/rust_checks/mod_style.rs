@@ -0,0 +1,172 @@
+//! Lint to keep module files in one layout across a crate.
+//!
+//! A module with submodules keeps its own file either as `foo.rs` next to the `foo/` directory (the 2018 layout) or
+//! as `foo/mod.rs`; the `mod_style_prefer` option picks which, `file` (the former) by default. A module without
+//! submodules is a plain `foo.rs` either way. Crate roots aren't modules, and a `mod.rs` directly in `tests/`,
+//! `examples/`, `benches/` or `src/bin/` stays put, since a `foo.rs` there would be a target of its own.
+//!
+//! The fix is moving the file, which `format` does before any other rule sees it. A file that declares `#[path]`
+//! modules isn't moved, as those paths are relative to where it is.
+
+use std::{
+	fs, io,
+	path::{Path, PathBuf},
+	str::FromStr,
+};
+
+use super::{
+	Severity, Violation,
+	rules::{Context, Rule, RuleInfo},
+};
+
+const RULE: &str = "mod-style";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "mod_style",
+	autofix: true,
+	triggers: &[],
+	description: "Keep module files in one layout, foo.rs next to foo/ or foo/mod.rs",
+	rationale: "With both layouts in a crate, a module's file is in one of two places; with one, it's always where you look first.",
+	bad: "src/parser/mod.rs\nsrc/parser/lexer.rs\nsrc/render.rs\nsrc/render/html.rs",
+	good: "src/parser.rs\nsrc/parser/lexer.rs\nsrc/render.rs\nsrc/render/html.rs",
+	fix: "Moves the module's file into the configured layout; `mod` declarations find it either way.",
+};
+
+/// Where a module with submodules keeps its own file, set by `mod_style_prefer`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Layout {
+	/// `foo.rs` next to `foo/`
+	#[default]
+	File,
+	/// `foo/mod.rs`
+	ModRs,
+}
+impl FromStr for Layout {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"file" => Ok(Layout::File),
+			"mod_rs" | "mod.rs" => Ok(Layout::ModRs),
+			_ => Err(format!("invalid module layout `{s}`, expected `file` or `mod_rs`")),
+		}
+	}
+}
+
+pub struct ModStyle;
+impl Rule for ModStyle {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.opts.mod_style_prefer)
+	}
+
+	/// Whether the module's directory, and the file it would be moved to, exist
+	fn reads_other_files(&self) -> bool {
+		true
+	}
+}
+
+pub fn check(path: &Path, content: &str, layout: Layout) -> Vec<Violation> {
+	let Some(misplaced) = misplaced(path, layout) else {
+		return Vec::new();
+	};
+	let (hint, rename) = if misplaced.target.exists() {
+		(format!("`{}` already exists, so the two have to be merged by hand", misplaced.to), None)
+	} else if content.contains("#[path") {
+		("move it by hand, updating its `#[path]` attributes, which are relative to where it is".to_string(), None)
+	} else {
+		let hint = match layout {
+			Layout::File => "this crate keeps a module's file next to its directory",
+			Layout::ModRs => "this crate keeps a module's file inside its directory, as `mod.rs`",
+		};
+		(hint.to_string(), Some(misplaced.target))
+	};
+	vec![Violation {
+		rule: RULE,
+		file: path.display().to_string(),
+		line: 1,
+		column: 0,
+		message: format!("`{}` should be `{}`\nHINT: {hint}", misplaced.from, misplaced.to),
+		severity: Severity::Error,
+		rename,
+		..Default::default()
+	}]
+}
+
+/// Move a module file from `from` to `to`, dropping the directory it leaves empty, if it does.
+pub fn rename(from: &Path, to: &Path) -> io::Result<()> {
+	if let Some(dir) = to.parent() {
+		fs::create_dir_all(dir)?;
+	}
+	fs::rename(from, to)?;
+	if let Some(dir) = from.parent() {
+		//IGNORED_ERROR: a directory with anything left in it stays, which is the usual case
+		let _ = fs::remove_dir(dir);
+	}
+	Ok(())
+}
+
+/// A module file in the other layout, with the names to show for it.
+struct Misplaced {
+	/// Where it is, as `foo/mod.rs` or `foo.rs`
+	from: String,
+	/// Where it belongs, likewise
+	to: String,
+	target: PathBuf,
+}
+
+fn misplaced(path: &Path, layout: Layout) -> Option<Misplaced> {
+	let name = path.file_name()?.to_str()?;
+	let dir = path.parent()?;
+	match layout {
+		Layout::File => {
+			let module = dir.file_name()?.to_str()?;
+			if name != "mod.rs" || is_source_root(dir) || is_targets_dir(dir) || dir.parent().is_none_or(is_targets_dir) {
+				return None;
+			}
+			Some(Misplaced {
+				from: format!("{module}/mod.rs"),
+				to: format!("{module}.rs"),
+				target: dir.with_file_name(format!("{module}.rs")),
+			})
+		}
+		Layout::ModRs => {
+			let module = name.strip_suffix(".rs")?;
+			if name == "mod.rs" || is_crate_root(path) || !dir.join(module).is_dir() {
+				return None;
+			}
+			Some(Misplaced {
+				from: name.to_string(),
+				to: format!("{module}/mod.rs"),
+				target: dir.join(module).join("mod.rs"),
+			})
+		}
+	}
+}
+
+/// `src/`, whose `lib.rs` and `main.rs` are crate roots.
+fn is_source_root(dir: &Path) -> bool {
+	dir.file_name().is_some_and(|name| name == "src")
+}
+
+/// A directory whose every file is a crate root of its own: `src/bin/`, `tests/`, `examples/` or `benches/`.
+pub(super) fn is_targets_dir(dir: &Path) -> bool {
+	match dir.file_name().and_then(|name| name.to_str()) {
+		Some("tests" | "examples" | "benches") => true,
+		Some("bin") => dir.parent().is_some_and(is_source_root),
+		_ => false,
+	}
+}
+
+fn is_crate_root(path: &Path) -> bool {
+	let Some(dir) = path.parent() else {
+		return false;
+	};
+	if is_source_root(dir) {
+		return path.file_name().is_some_and(|name| name == "lib.rs" || name == "main.rs");
+	}
+	is_targets_dir(dir)
+}
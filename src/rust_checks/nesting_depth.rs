@@ -0,0 +1,151 @@
+//! Lint to limit how deep control flow nests inside a function.
+//!
+//! Every `if`, `match`, loop and closure opens a level; an `else if` stays on the level of its `if`. A function nesting
+//! deeper than the `nesting_depth_max` option gets one violation, pointing at its innermost block: that's where an early
+//! return, a `let .. else` or an extracted helper would flatten things.
+
+use std::path::Path;
+
+use proc_macro2::Span;
+use syn::{Block, Expr, ExprClosure, ExprForLoop, ExprIf, ExprLoop, ExprMatch, ExprWhile, ImplItemFn, ItemFn, TraitItemFn, spanned::Spanned, visit::Visit};
+
+use super::{
+	Severity, Violation,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
+
+const RULE: &str = "nesting-depth";
+/// Deepest nesting that passes, unless `nesting_depth_max` says otherwise
+pub const DEFAULT_MAX: usize = 4;
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "nesting_depth",
+	autofix: false,
+	triggers: &[],
+	description: "Limit nesting of if/match/loops/closures inside a function",
+	rationale: "Each level of nesting is one more condition to keep in mind while reading the innermost line.",
+	bad: "fn sync(items: &[Item]) {\n\tfor item in items {\n\t\tif item.dirty {\n\t\t\tmatch item.kind {\n\t\t\t\tKind::File => {\n\t\t\t\t\tif let Some(path) = &item.path {\n\t\t\t\t\t\tfor chunk in item.chunks() {\n\t\t\t\t\t\t\twrite(path, chunk);\n\t\t\t\t\t\t}\n\t\t\t\t\t}\n\t\t\t\t}\n\t\t\t\t_ => {}\n\t\t\t}\n\t\t}\n\t}\n}",
+	good: "fn sync(items: &[Item]) {\n\tfor item in items.iter().filter(|item| item.dirty) {\n\t\tlet (Kind::File, Some(path)) = (&item.kind, &item.path) else {\n\t\t\tcontinue;\n\t\t};\n\t\tfor chunk in item.chunks() {\n\t\t\twrite(path, chunk);\n\t\t}\n\t}\n}",
+	fix: "Return early, use `let .. else` or `continue`, or extract the inner blocks into helpers.",
+};
+
+pub struct NestingDepth;
+impl Rule for NestingDepth {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree, ctx.opts.nesting_depth_max)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File, max: usize) -> Vec<Violation> {
+	let visitor = NestingVisitor {
+		path_str: path.display().to_string(),
+		max,
+		depth: 0,
+		deepest: None,
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+struct NestingVisitor {
+	path_str: String,
+	max: usize,
+	/// Levels open at the current point of the current fn
+	depth: usize,
+	/// The current fn's deepest level so far, and the keyword opening it
+	deepest: Option<(usize, Span)>,
+	violations: Vec<Violation>,
+}
+
+impl NestingVisitor {
+	/// Visits a fn `body` on its own: nested fns are measured separately from the fn around them.
+	fn check_fn(&mut self, name: &str, body: &Block) {
+		let outer = (self.depth, self.deepest.take());
+		self.depth = 0;
+		self.visit_block(body);
+		if let Some((depth, keyword)) = self.deepest
+			&& depth > self.max
+		{
+			let (start, end) = (keyword.start(), keyword.end());
+			self.violations.push(Violation {
+				rule: RULE,
+				file: self.path_str.clone(),
+				line: start.line,
+				column: start.column,
+				end: Some((end.line, end.column)),
+				message: format!(
+					"`{name}` nests {depth} levels deep, more than {}\nHINT: return early, use `let .. else` or `continue`, or extract a helper",
+					self.max
+				),
+				severity: Severity::Error,
+				..Default::default()
+			});
+		}
+		(self.depth, self.deepest) = outer;
+	}
+
+	/// Runs `visit` one level deeper, opened by `keyword`.
+	fn nested(&mut self, keyword: Span, visit: impl FnOnce(&mut Self)) {
+		self.depth += 1;
+		if self.deepest.is_none_or(|(deepest, _)| self.depth > deepest) {
+			self.deepest = Some((self.depth, keyword));
+		}
+		visit(self);
+		self.depth -= 1;
+	}
+}
+
+impl<'a> Visit<'a> for NestingVisitor {
+	fn visit_item_fn(&mut self, node: &'a ItemFn) {
+		self.check_fn(&node.sig.ident.to_string(), &node.block);
+	}
+
+	fn visit_impl_item_fn(&mut self, node: &'a ImplItemFn) {
+		self.check_fn(&node.sig.ident.to_string(), &node.block);
+	}
+
+	fn visit_trait_item_fn(&mut self, node: &'a TraitItemFn) {
+		if let Some(body) = &node.default {
+			self.check_fn(&node.sig.ident.to_string(), body);
+		}
+	}
+
+	fn visit_expr_if(&mut self, node: &'a ExprIf) {
+		self.visit_expr(&node.cond);
+		self.nested(node.if_token.span, |this| this.visit_block(&node.then_branch));
+		match node.else_branch.as_ref().map(|(token, branch)| (token, &**branch)) {
+			// `else if` continues the chain on the same level
+			Some((_, branch @ Expr::If(_))) => self.visit_expr(branch),
+			Some((token, branch)) => self.nested(token.span, |this| this.visit_expr(branch)),
+			None => {}
+		}
+	}
+
+	fn visit_expr_match(&mut self, node: &'a ExprMatch) {
+		self.visit_expr(&node.expr);
+		self.nested(node.match_token.span, |this| node.arms.iter().for_each(|arm| this.visit_arm(arm)));
+	}
+
+	fn visit_expr_loop(&mut self, node: &'a ExprLoop) {
+		self.nested(node.loop_token.span, |this| syn::visit::visit_expr_loop(this, node));
+	}
+
+	fn visit_expr_while(&mut self, node: &'a ExprWhile) {
+		self.nested(node.while_token.span, |this| syn::visit::visit_expr_while(this, node));
+	}
+
+	fn visit_expr_for_loop(&mut self, node: &'a ExprForLoop) {
+		self.nested(node.for_token.span, |this| syn::visit::visit_expr_for_loop(this, node));
+	}
+
+	fn visit_expr_closure(&mut self, node: &'a ExprClosure) {
+		self.nested(node.or1_token.span(), |this| syn::visit::visit_expr_closure(this, node));
+	}
+}
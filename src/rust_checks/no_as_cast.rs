@@ -0,0 +1,207 @@
+//! Lint to disallow numeric `as` casts, which silently truncate, wrap or round.
+//!
+//! Without type information, any `as` to a numeric type is taken to be lossy, except for casts that provably aren't:
+//! integer literals that fit the target, and enum variants like `Kind::File as u8`, which have no `From` to use instead.
+//! Const contexts are exempt, as `From`/`TryFrom` can't be called there: `const`/`static` items, `const fn`s, array
+//! lengths, const generic arguments, enum discriminants and `const { .. }` blocks.
+
+use std::path::Path;
+
+use syn::{
+	Expr, ExprCast, ExprConst, ExprLit, ExprUnary, GenericArgument, ImplItemConst, ImplItemFn, ItemConst, ItemFn, ItemStatic, Lit, Local, TraitItemConst, Type, TypeArray, UnOp, Variant,
+	spanned::Spanned, visit::Visit,
+};
+
+use super::{
+	Severity, Violation,
+	rules::{Context, Rule, RuleInfo},
+	skip::{SkipVisitor, has_skip_marker_for_rule},
+};
+
+const RULE: &str = "no-as-cast";
+/// Numeric types, with the range of integer values they hold. `usize`/`isize` get the 32-bit range, the narrowest
+/// they're likely to be; floats hold any integer literal closely enough
+const NUMERIC: &[(&str, i128, i128)] = &[
+	("u8", 0, u8::MAX as i128),
+	("u16", 0, u16::MAX as i128),
+	("u32", 0, u32::MAX as i128),
+	("u64", 0, u64::MAX as i128),
+	("u128", 0, i128::MAX),
+	("usize", 0, u32::MAX as i128),
+	("i8", i8::MIN as i128, i8::MAX as i128),
+	("i16", i16::MIN as i128, i16::MAX as i128),
+	("i32", i32::MIN as i128, i32::MAX as i128),
+	("i64", i64::MIN as i128, i64::MAX as i128),
+	("i128", i128::MIN, i128::MAX),
+	("isize", i32::MIN as i128, i32::MAX as i128),
+	("f32", i128::MIN, i128::MAX),
+	("f64", i128::MIN, i128::MAX),
+];
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "no_as_cast",
+	autofix: false,
+	triggers: &[" as "],
+	description: "Disallow lossy numeric `as` casts",
+	rationale: "`as` between numeric types truncates, wraps or rounds without a word; `From` only compiles when nothing is lost, and `TryFrom` makes the caller handle it.",
+	bad: "let len = bytes.len() as u16;\nlet ratio = done as f32 / total as f32;",
+	good: "let len = u16::try_from(bytes.len())?;\nlet ratio = f64::from(done) / f64::from(total);",
+	fix: "Use `T::from(x)` where the conversion is lossless, `T::try_from(x)` where it isn't, or a skip marker where the loss is intended.",
+};
+
+pub struct NoAsCast;
+impl Rule for NoAsCast {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+	let visitor = CastVisitor {
+		path_str: path.display().to_string(),
+		content,
+		in_const: 0,
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+struct CastVisitor<'a> {
+	path_str: String,
+	content: &'a str,
+	/// How many const contexts the current node is in
+	in_const: usize,
+	violations: Vec<Violation>,
+}
+
+impl CastVisitor<'_> {
+	fn in_const(&mut self, visit: impl FnOnce(&mut Self)) {
+		self.in_const += 1;
+		visit(self);
+		self.in_const -= 1;
+	}
+}
+
+impl<'a> Visit<'a> for CastVisitor<'_> {
+	fn visit_expr_cast(&mut self, node: &'a ExprCast) {
+		syn::visit::visit_expr_cast(self, node);
+		let Some(&(target, min, max)) = numeric_target(&node.ty) else {
+			return;
+		};
+		if self.in_const > 0 || is_enum_variant(&node.expr) || int_literal(&node.expr).is_some_and(|value| (min..=max).contains(&value)) {
+			return;
+		}
+		let start = node.as_token.span.start();
+		let end = node.ty.span().end();
+		self.violations.push(Violation {
+			rule: RULE,
+			file: self.path_str.clone(),
+			line: start.line,
+			column: start.column,
+			end: Some((end.line, end.column)),
+			message: format!("`as {target}` cast may truncate, wrap or round\nHINT: use `{target}::from(..)` if it's lossless, `{target}::try_from(..)` if it isn't"),
+			severity: Severity::Error,
+			..Default::default()
+		});
+	}
+
+	// A function's statements are visited from here rather than through `SkipVisitor`, which misses their markers
+	fn visit_local(&mut self, node: &'a Local) {
+		if !has_skip_marker_for_rule(self.content, node.span(), RULE) {
+			syn::visit::visit_local(self, node);
+		}
+	}
+
+	fn visit_item_const(&mut self, node: &'a ItemConst) {
+		self.in_const(|this| syn::visit::visit_item_const(this, node));
+	}
+
+	fn visit_item_static(&mut self, node: &'a ItemStatic) {
+		self.in_const(|this| syn::visit::visit_item_static(this, node));
+	}
+
+	fn visit_impl_item_const(&mut self, node: &'a ImplItemConst) {
+		self.in_const(|this| syn::visit::visit_impl_item_const(this, node));
+	}
+
+	fn visit_trait_item_const(&mut self, node: &'a TraitItemConst) {
+		self.in_const(|this| syn::visit::visit_trait_item_const(this, node));
+	}
+
+	fn visit_item_fn(&mut self, node: &'a ItemFn) {
+		if node.sig.constness.is_some() {
+			self.in_const(|this| syn::visit::visit_item_fn(this, node));
+		} else {
+			// A regular fn nested in a const context is a runtime context again
+			let outer = std::mem::take(&mut self.in_const);
+			syn::visit::visit_item_fn(self, node);
+			self.in_const = outer;
+		}
+	}
+
+	fn visit_impl_item_fn(&mut self, node: &'a ImplItemFn) {
+		if node.sig.constness.is_some() {
+			self.in_const(|this| syn::visit::visit_impl_item_fn(this, node));
+		} else {
+			syn::visit::visit_impl_item_fn(self, node);
+		}
+	}
+
+	fn visit_type_array(&mut self, node: &'a TypeArray) {
+		self.visit_type(&node.elem);
+		self.in_const(|this| this.visit_expr(&node.len));
+	}
+
+	fn visit_generic_argument(&mut self, node: &'a GenericArgument) {
+		match node {
+			GenericArgument::Const(expr) => self.in_const(|this| this.visit_expr(expr)),
+			_ => syn::visit::visit_generic_argument(self, node),
+		}
+	}
+
+	fn visit_variant(&mut self, node: &'a Variant) {
+		self.in_const(|this| syn::visit::visit_variant(this, node));
+	}
+
+	fn visit_expr_const(&mut self, node: &'a ExprConst) {
+		self.in_const(|this| syn::visit::visit_expr_const(this, node));
+	}
+}
+
+/// The numeric type `ty` names, with its range.
+fn numeric_target(ty: &Type) -> Option<&'static (&'static str, i128, i128)> {
+	let Type::Path(path) = ty else {
+		return None;
+	};
+	let ident = path.path.get_ident()?;
+	NUMERIC.iter().find(|(name, ..)| ident == *name)
+}
+
+/// Value of an integer literal, negated ones included.
+fn int_literal(expr: &Expr) -> Option<i128> {
+	match expr {
+		Expr::Lit(ExprLit { lit: Lit::Int(int), .. }) => int.base10_parse().ok(),
+		Expr::Unary(ExprUnary { op: UnOp::Neg(_), expr, .. }) => int_literal(expr).map(|value| -value),
+		Expr::Paren(inner) => int_literal(&inner.expr),
+		_ => None,
+	}
+}
+
+/// `Kind::File` and the like: a path of several segments ending in a CamelCase name, unlike a constant's `u64::MAX`.
+fn is_enum_variant(expr: &Expr) -> bool {
+	let Expr::Path(path) = expr else {
+		return false;
+	};
+	let segments = &path.path.segments;
+	segments.len() > 1
+		&& segments.last().is_some_and(|last| {
+			let name = last.ident.to_string();
+			name.starts_with(char::is_uppercase) && name.contains(char::is_lowercase)
+		})
+}
@@ -0,0 +1,159 @@
+//! Lint to disallow `assert!`, `assert_eq!` and `assert_ne!` outside tests.
+//!
+//! A failed assertion in non-test code is a panic the caller can't handle: a condition the input can break should be
+//! an error, and one only a bug can break can be a `debug_assert!`, which costs nothing in release builds. Assertions
+//! in `const` items and `const` blocks are exempt, as they fail the build rather than the program.
+//!
+//! Some invariants are worth checking in release builds anyway. The `no_assert_marker` option names a comment marker
+//! that lets an assertion through when it's on its line or the one above, e.g. `//INVARIANT: <why>` with
+//! `no_assert_marker = "INVARIANT"`. It's empty by default, which lets none through.
+
+use std::path::Path;
+
+use syn::{ExprConst, ItemConst, Macro, visit::Visit};
+
+use super::{
+	Fix, Severity, Suggestion, Violation,
+	line_index::LineIndex,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+	test_context::TestContext,
+};
+
+const RULE: &str = "no-assert";
+/// Macros the rule flags
+const ASSERTING: &[&str] = &["assert", "assert_eq", "assert_ne"];
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "no_assert",
+	autofix: false,
+	triggers: &["assert!", "assert_eq!", "assert_ne!"],
+	description: "Disallow assert!, assert_eq! and assert_ne! outside tests",
+	rationale: "An assertion outside tests is a panic the caller can't handle; input that breaks it deserves an error, and a bug that breaks it a `debug_assert!`.",
+	bad: "pub fn split(total: u64, parts: u64) -> u64 {\n\tassert!(parts > 0);\n\ttotal / parts\n}",
+	good: "pub fn split(total: u64, parts: u64) -> Result<u64> {\n\tensure!(parts > 0, \"can't split into zero parts\");\n\tOk(total / parts)\n}",
+	fix: "Return an error, or use `debug_assert!`. With `no_assert_marker` set, a comment with the marker keeps an invariant check.",
+};
+
+pub struct NoAssert;
+impl Rule for NoAssert {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree, ctx.tests, &ctx.opts.no_assert_marker)
+	}
+}
+
+/// Violations in `file`; an empty `marker` means no comment justifies an assertion.
+pub fn check(path: &Path, content: &str, file: &syn::File, tests: &TestContext, marker: &str) -> Vec<Violation> {
+	let visitor = AssertVisitor {
+		path_str: path.display().to_string(),
+		content,
+		lines: LineIndex::new(content),
+		tests,
+		marker,
+		const_depth: 0,
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+struct AssertVisitor<'a> {
+	path_str: String,
+	content: &'a str,
+	lines: LineIndex<'a>,
+	tests: &'a TestContext,
+	marker: &'a str,
+	/// How many `const` items and blocks the visitor is in
+	const_depth: usize,
+	violations: Vec<Violation>,
+}
+
+impl AssertVisitor<'_> {
+	/// `//<marker>` or `// <marker>` on `line` or the line above it.
+	fn is_justified(&self, line: usize) -> bool {
+		if self.marker.is_empty() {
+			return false;
+		}
+		let tight = format!("//{}", self.marker);
+		let spaced = format!("// {}", self.marker);
+		(line.saturating_sub(1)..=line)
+			.filter_map(|l| self.content.lines().nth(l.checked_sub(1)?))
+			.any(|text| text.contains(&tight) || text.contains(&spaced))
+	}
+
+	/// Ways out: `debug_assert` in place of the macro, and a marker comment above `line` if there's a marker.
+	fn suggestions(&self, node: &Macro, name: &str, line: usize) -> Vec<Suggestion> {
+		let mut suggestions = Vec::new();
+		let range = node.path.get_ident().and_then(|ident| Some((self.lines.offset_of(ident.span().start())?, self.lines.offset_of(ident.span().end())?)));
+		if let Some((start, end)) = range {
+			suggestions.push(Suggestion {
+				message: "check it in debug builds only".to_string(),
+				fix: Fix {
+					start_byte: start,
+					end_byte: end,
+					replacement: format!("debug_{name}"),
+				},
+			});
+		}
+		if !self.marker.is_empty() {
+			let line_start = self.lines.offset(line, 0).unwrap_or_default();
+			let text = &self.content[line_start..self.lines.line_end(line_start)];
+			let indent = &text[..text.len() - text.trim_start().len()];
+			suggestions.push(Suggestion {
+				message: "explain why the invariant is worth checking in release builds".to_string(),
+				fix: Fix {
+					start_byte: line_start,
+					end_byte: line_start,
+					replacement: format!("{indent}//{}: \n", self.marker),
+				},
+			});
+		}
+		suggestions
+	}
+}
+
+impl<'a> Visit<'a> for AssertVisitor<'a> {
+	fn visit_item_const(&mut self, node: &'a ItemConst) {
+		self.const_depth += 1;
+		syn::visit::visit_item_const(self, node);
+		self.const_depth -= 1;
+	}
+
+	fn visit_expr_const(&mut self, node: &'a ExprConst) {
+		self.const_depth += 1;
+		syn::visit::visit_expr_const(self, node);
+		self.const_depth -= 1;
+	}
+
+	fn visit_macro(&mut self, node: &'a Macro) {
+		let Some(name) = node.path.segments.last().map(|s| s.ident.to_string()) else {
+			return;
+		};
+		let start = node.path.segments[0].ident.span().start();
+		if ASSERTING.contains(&name.as_str()) && self.const_depth == 0 && !self.tests.contains(start.line) && !self.is_justified(start.line) {
+			let end = node.bang_token.spans[0].end();
+			let hint = if self.marker.is_empty() {
+				format!("return an error if input can break it, or use `debug_{name}!` if only a bug can")
+			} else {
+				format!("return an error if input can break it, use `debug_{name}!` if only a bug can, or add a `//{}` comment saying why it's checked in release builds", self.marker)
+			};
+			self.violations.push(Violation {
+				rule: RULE,
+				file: self.path_str.clone(),
+				line: start.line,
+				column: start.column,
+				end: Some((end.line, end.column)),
+				message: format!("`{name}!` outside tests\nHINT: {hint}"),
+				severity: Severity::Error,
+				suggestions: self.suggestions(node, &name, start.line),
+				..Default::default()
+			});
+		}
+		syn::visit::visit_macro(self, node);
+	}
+}
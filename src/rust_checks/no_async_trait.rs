@@ -0,0 +1,175 @@
+//! Lint to disallow the `async_trait` crate now that traits can have `async fn`s natively.
+//!
+//! `#[async_trait]` boxes every future its methods return, and the macro stands between the signatures as written and
+//! the ones compiled. Both its attributes and imports are flagged. Removing an attribute is the fix, unless the file
+//! mentions the trait as `dyn Trait`: native `async fn`s aren't dyn-compatible, so such a trait has to box its futures
+//! by hand. The import goes too, once every attribute in the file can. Note that the native futures aren't required to
+//! be `Send`, as `#[async_trait]`'s are; a trait whose callers spawn them spells `-> impl Future<Output = T> + Send`.
+
+use std::path::Path;
+
+use syn::{Attribute, ItemImpl, ItemTrait, ItemUse, UseTree, Visibility, spanned::Spanned, visit::Visit};
+
+use super::{
+	Fix, Severity, Violation,
+	line_index::LineIndex,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
+
+const RULE: &str = "no-async-trait";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "no_async_trait",
+	autofix: true,
+	triggers: &["async_trait"],
+	description: "Disallow the async_trait crate, in favor of native async fn in traits",
+	rationale: "Traits support `async fn` natively; `#[async_trait]` boxes every returned future and rewrites the signatures behind the macro.",
+	bad: "#[async_trait]\ntrait Store {\n\tasync fn get(&self, key: &str) -> Option<Bytes>;\n}",
+	good: "trait Store {\n\tasync fn get(&self, key: &str) -> Option<Bytes>;\n}",
+	fix: "Removes the attributes and the import, unless the trait is used as `dyn Trait`, whose methods have to return boxed futures by hand.",
+};
+
+pub struct NoAsyncTrait;
+impl Rule for NoAsyncTrait {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+	// The import can only go once no attribute is left behind, skipped ones included, so all are counted first
+	let mut all = AsyncTraitVisitor::new(path, content, false);
+	all.visit_file(file);
+	let mut unskipped = SkipVisitor::for_rule(AsyncTraitVisitor::new(path, content, false), content, RULE);
+	unskipped.visit_file(file);
+	let remove_import = unskipped.inner.fixable == all.attributes;
+
+	let visitor = AsyncTraitVisitor::new(path, content, remove_import);
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+struct AsyncTraitVisitor<'a> {
+	path_str: String,
+	lines: LineIndex<'a>,
+	/// Whether `use async_trait::async_trait;` is removed as part of the fix
+	remove_import: bool,
+	attributes: usize,
+	/// Attributes that get a fix
+	fixable: usize,
+	violations: Vec<Violation>,
+}
+
+impl<'a> AsyncTraitVisitor<'a> {
+	fn new(path: &Path, content: &'a str, remove_import: bool) -> Self {
+		Self {
+			path_str: path.display().to_string(),
+			lines: LineIndex::new(content),
+			remove_import,
+			attributes: 0,
+			fixable: 0,
+			violations: Vec::new(),
+		}
+	}
+
+	fn check_attrs(&mut self, attrs: &[Attribute], trait_name: Option<&syn::Ident>) {
+		for attr in attrs.iter().filter(|attr| attr.path().segments.last().is_some_and(|last| last.ident == "async_trait")) {
+			let dyn_name = trait_name.filter(|name| mentions_dyn(self.lines.text(), &name.to_string()));
+			let (hint, fix) = match dyn_name {
+				Some(name) => (
+					format!("`dyn {name}` needs boxed futures, so its methods return `Pin<Box<dyn Future<Output = ..> + Send + '_>>` by hand"),
+					None,
+				),
+				None => ("remove it; write `-> impl Future<Output = ..> + Send` where callers need `Send` futures".to_string(), self.removal(attr)),
+			};
+			self.attributes += 1;
+			self.fixable += usize::from(fix.is_some());
+			let on = trait_name.map(|name| format!(" on `{name}`")).unwrap_or_default();
+			self.report(attr, format!("`#[async_trait]`{on}, which native `async fn` in traits replaces\nHINT: {hint}"), fix);
+		}
+	}
+
+	fn report(&mut self, node: &impl Spanned, message: String, fix: Option<Fix>) {
+		let start = node.span().start();
+		let end = node.span().end();
+		self.violations.push(Violation {
+			rule: RULE,
+			file: self.path_str.clone(),
+			line: start.line,
+			column: start.column,
+			end: Some((end.line, end.column)),
+			message,
+			severity: Severity::Error,
+			fix,
+			..Default::default()
+		});
+	}
+
+	/// Fix deleting `node`, along with its line if nothing else is on it.
+	fn removal(&self, node: &impl Spanned) -> Option<Fix> {
+		let start = self.lines.offset_of(node.span().start())?;
+		let end = self.lines.offset_of(node.span().end())?;
+		let (line_start, line_end) = (self.lines.line_start(start), self.lines.line_end(end));
+		let text = self.lines.text();
+		let (start_byte, end_byte) = if text[line_start..start].trim().is_empty() && text[end..line_end].trim().is_empty() {
+			(line_start, (line_end + 1).min(text.len()))
+		} else {
+			(start, end)
+		};
+		Some(Fix {
+			start_byte,
+			end_byte,
+			replacement: String::new(),
+		})
+	}
+}
+
+impl<'a> Visit<'a> for AsyncTraitVisitor<'_> {
+	fn visit_item_trait(&mut self, node: &'a ItemTrait) {
+		self.check_attrs(&node.attrs, Some(&node.ident));
+		syn::visit::visit_item_trait(self, node);
+	}
+
+	fn visit_item_impl(&mut self, node: &'a ItemImpl) {
+		let trait_name = node.trait_.as_ref().and_then(|(_, path, _)| path.segments.last()).map(|last| &last.ident);
+		self.check_attrs(&node.attrs, trait_name);
+		syn::visit::visit_item_impl(self, node);
+	}
+
+	fn visit_item_use(&mut self, node: &'a ItemUse) {
+		if !imports_async_trait(&node.tree) {
+			return;
+		}
+		// Only a lone `use async_trait::async_trait;` is removed: anything else around it would have to be rewritten
+		let is_lone = matches!(&node.tree, UseTree::Path(path) if path.ident == "async_trait" && matches!(&*path.tree, UseTree::Name(name) if name.ident == "async_trait"));
+		let fix = (self.remove_import && is_lone && node.attrs.is_empty() && matches!(node.vis, Visibility::Inherited)).then(|| self.removal(node)).flatten();
+		self.report(
+			node,
+			"`async_trait` import, for an attribute native `async fn` in traits replaces\nHINT: remove it along with the `#[async_trait]` attributes".to_string(),
+			fix,
+		);
+	}
+}
+
+/// Whether `tree` brings in anything from the `async_trait` crate.
+fn imports_async_trait(tree: &UseTree) -> bool {
+	match tree {
+		UseTree::Path(path) => path.ident == "async_trait" || imports_async_trait(&path.tree),
+		UseTree::Name(name) => name.ident == "async_trait",
+		UseTree::Rename(rename) => rename.ident == "async_trait",
+		UseTree::Glob(_) => false,
+		UseTree::Group(group) => group.items.iter().any(imports_async_trait),
+	}
+}
+
+/// Whether `content` has `dyn name`, as a whole word.
+fn mentions_dyn(content: &str, name: &str) -> bool {
+	let needle = format!("dyn {name}");
+	content.match_indices(&needle).any(|(i, _)| !content[i + needle.len()..].starts_with(|c: char| c.is_alphanumeric() || c == '_'))
+}
@@ -0,0 +1,147 @@
+//! Lint to disallow `block_on` inside async code.
+//!
+//! Blocking on a future from a task parks the executor thread until that future completes, and if it needs the same
+//! thread to make progress, it never does: a current-thread runtime deadlocks, and tokio's `Runtime::block_on` and
+//! `Handle::block_on` panic outright. Calls of `futures::executor::block_on`, `Runtime::block_on`, `Handle::block_on`
+//! and any `.block_on()` method are flagged inside async fns and blocks, and inside closures created there, since
+//! those mostly run on the task too. Closures handed to `spawn_blocking`, `block_in_place` or `std::thread::spawn`
+//! run off the executor, where `block_on` is the way back into async code, so they're exempt.
+
+use std::path::Path;
+
+use syn::{Expr, ExprAsync, ExprCall, ExprClosure, ExprMethodCall, ImplItemFn, ItemFn, TraitItemFn, spanned::Spanned, visit::Visit};
+
+use super::{
+	Severity, Violation,
+	custom_rules::Imports,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
+
+const RULE: &str = "no-block-on-in-async";
+/// Calls whose closure arguments run on a thread of their own
+const OFFLOADING: &[&str] = &["spawn_blocking", "block_in_place"];
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "no_block_on_in_async",
+	autofix: false,
+	triggers: &["block_on"],
+	description: "Disallow block_on inside async fns and the closures they create",
+	rationale: "Blocking on a future from a task parks the executor thread, deadlocking a runtime that needs it to make progress; tokio panics instead.",
+	bad: "async fn refresh(&self) -> Result<()> {\n\tlet token = Handle::current().block_on(self.auth.token())?;\n\tself.client.set_token(token);\n\tOk(())\n}",
+	good: "async fn refresh(&self) -> Result<()> {\n\tlet token = self.auth.token().await?;\n\tself.client.set_token(token);\n\tOk(())\n}",
+	fix: "`.await` the future instead, or move the blocking code into `spawn_blocking`.",
+};
+
+pub struct NoBlockOnInAsync;
+impl Rule for NoBlockOnInAsync {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+	let visitor = BlockOnVisitor {
+		path_str: path.display().to_string(),
+		imports: Imports::of(file),
+		in_async: false,
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+struct BlockOnVisitor {
+	path_str: String,
+	imports: Imports,
+	/// Whether the current code runs on an executor thread
+	in_async: bool,
+	violations: Vec<Violation>,
+}
+
+impl BlockOnVisitor {
+	fn with_async(&mut self, in_async: bool, visit: impl FnOnce(&mut Self)) {
+		let outer = std::mem::replace(&mut self.in_async, in_async);
+		visit(self);
+		self.in_async = outer;
+	}
+
+	fn report(&mut self, span: &impl Spanned, call: &str) {
+		let start = span.span().start();
+		let end = span.span().end();
+		self.violations.push(Violation {
+			rule: RULE,
+			file: self.path_str.clone(),
+			line: start.line,
+			column: start.column,
+			end: Some((end.line, end.column)),
+			message: format!("`{call}` inside async code blocks the executor thread it runs on\nHINT: `.await` the future instead"),
+			severity: Severity::Error,
+			..Default::default()
+		});
+	}
+
+	/// Visit the arguments of a call, leaving async code if `name` runs them on a thread of its own.
+	fn visit_args<'a>(&mut self, name: &str, resolved: &[String], args: impl IntoIterator<Item = &'a Expr>) {
+		let offloads = OFFLOADING.contains(&name) || resolved == ["std", "thread", "spawn"];
+		self.with_async(self.in_async && !offloads, |this| {
+			for arg in args {
+				this.visit_expr(arg);
+			}
+		});
+	}
+}
+
+impl<'a> Visit<'a> for BlockOnVisitor {
+	fn visit_item_fn(&mut self, node: &'a ItemFn) {
+		self.with_async(node.sig.asyncness.is_some(), |this| syn::visit::visit_item_fn(this, node));
+	}
+
+	fn visit_impl_item_fn(&mut self, node: &'a ImplItemFn) {
+		self.with_async(node.sig.asyncness.is_some(), |this| syn::visit::visit_impl_item_fn(this, node));
+	}
+
+	fn visit_trait_item_fn(&mut self, node: &'a TraitItemFn) {
+		self.with_async(node.sig.asyncness.is_some(), |this| syn::visit::visit_trait_item_fn(this, node));
+	}
+
+	fn visit_expr_async(&mut self, node: &'a ExprAsync) {
+		self.with_async(true, |this| syn::visit::visit_expr_async(this, node));
+	}
+
+	fn visit_expr_closure(&mut self, node: &'a ExprClosure) {
+		self.with_async(self.in_async || node.asyncness.is_some(), |this| syn::visit::visit_expr_closure(this, node));
+	}
+
+	fn visit_expr_call(&mut self, node: &'a ExprCall) {
+		let Expr::Path(func) = &*node.func else {
+			syn::visit::visit_expr_call(self, node);
+			return;
+		};
+		let resolved = self.imports.resolve(&func.path);
+		if self.in_async {
+			let segments: Vec<&str> = resolved.iter().map(String::as_str).collect();
+			if matches!(
+				segments.as_slice(),
+				["futures", "executor", "block_on"] | ["futures_executor", "block_on"] | [.., "Runtime" | "Handle", "block_on"]
+			) {
+				self.report(&node.func, &resolved.join("::"));
+			}
+		}
+		self.visit_expr(&node.func);
+		self.visit_args(resolved.last().map_or("", String::as_str), &resolved, &node.args);
+	}
+
+	fn visit_expr_method_call(&mut self, node: &'a ExprMethodCall) {
+		if self.in_async && node.method == "block_on" {
+			self.report(&node.method, ".block_on()");
+		}
+		self.visit_expr(&node.receiver);
+		self.visit_args(&node.method.to_string(), &[], &node.args);
+	}
+}
@@ -0,0 +1,152 @@
+//! Lint to disallow blocking std calls inside async code.
+//!
+//! `std::thread::sleep`, `std::fs` and `std::net`'s sockets block the thread they run on, which in async code is an
+//! executor thread shared with every other task. Calls of them are flagged inside async fns, blocks and closures, with
+//! the tokio equivalent as the hint; a sync closure, like the one given to `spawn_blocking`, is fine. Paths listed in
+//! the `no_blocking_in_async_paths` option are flagged too, along with anything under them.
+//!
+//! Only calls by path are seen, after resolving the file's `use` imports: a method called on a `std::fs::File` isn't,
+//! but opening it is.
+
+use std::path::Path;
+
+use syn::{Expr, ExprAsync, ExprCall, ExprClosure, ImplItemFn, ItemFn, TraitItemFn, spanned::Spanned, visit::Visit};
+
+use super::{
+	Severity, Violation,
+	custom_rules::Imports,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
+
+const RULE: &str = "no-blocking-in-async";
+/// Blocking paths with their tokio counterparts; a call of anything under one is flagged.
+/// Of `std::net`, only the sockets block: addresses are plain data.
+const BLOCKING: &[(&str, &str)] = &[
+	("std::thread::sleep", "tokio::time::sleep"),
+	("std::fs", "tokio::fs"),
+	("std::net::TcpListener", "tokio::net::TcpListener"),
+	("std::net::TcpStream", "tokio::net::TcpStream"),
+	("std::net::UdpSocket", "tokio::net::UdpSocket"),
+];
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "no_blocking_in_async",
+	autofix: false,
+	triggers: &["async"],
+	description: "Disallow blocking std::thread::sleep, std::fs and std::net calls in async code",
+	rationale: "A blocking call in async code stalls the executor thread, and with it every other task scheduled there.",
+	bad: "async fn load(path: &Path) -> Result<Config> {\n\tlet text = std::fs::read_to_string(path)?;\n\tOk(toml::from_str(&text)?)\n}",
+	good: "async fn load(path: &Path) -> Result<Config> {\n\tlet text = tokio::fs::read_to_string(path).await?;\n\tOk(toml::from_str(&text)?)\n}",
+	fix: "Use the tokio equivalent, or run the blocking code in `tokio::task::spawn_blocking`. Paths listed in `no_blocking_in_async_paths` are flagged too.",
+};
+
+pub struct NoBlockingInAsync;
+impl Rule for NoBlockingInAsync {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree, &ctx.opts.no_blocking_in_async_paths)
+	}
+}
+
+/// Violations in `file`, where `extra` lists blocking paths besides the built-in ones.
+pub fn check(path: &Path, content: &str, file: &syn::File, extra: &[String]) -> Vec<Violation> {
+	let split = |path: &str| path.trim().trim_start_matches("::").split("::").map(|seg| seg.trim().to_string()).collect::<Vec<_>>();
+	let blocking = BLOCKING
+		.iter()
+		.map(|(path, equivalent)| (split(path), Some(*equivalent)))
+		.chain(extra.iter().map(|path| (split(path), None)))
+		.collect();
+	let visitor = BlockingVisitor {
+		path_str: path.display().to_string(),
+		imports: Imports::of(file),
+		blocking,
+		in_async: false,
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+struct BlockingVisitor {
+	path_str: String,
+	imports: Imports,
+	/// Segments of each blocking path, with the prefix replacing them, if there's an equivalent
+	blocking: Vec<(Vec<String>, Option<&'static str>)>,
+	/// Whether the current code runs as part of a future
+	in_async: bool,
+	violations: Vec<Violation>,
+}
+
+impl BlockingVisitor {
+	fn with_async(&mut self, in_async: bool, visit: impl FnOnce(&mut Self)) {
+		let outer = std::mem::replace(&mut self.in_async, in_async);
+		visit(self);
+		self.in_async = outer;
+	}
+
+	fn check_call(&mut self, node: &ExprCall) {
+		let Expr::Path(func) = &*node.func else {
+			return;
+		};
+		let resolved = self.imports.resolve(&func.path);
+		let Some((prefix, equivalent)) = self.blocking.iter().find(|(prefix, _)| resolved.starts_with(prefix)) else {
+			return;
+		};
+		let hint = match equivalent {
+			Some(equivalent) => {
+				let rest: String = resolved[prefix.len()..].iter().map(|seg| format!("::{seg}")).collect();
+				format!("use `{equivalent}{rest}` instead")
+			}
+			None => "use an async equivalent".to_string(),
+		};
+		let start = node.func.span().start();
+		let end = node.func.span().end();
+		self.violations.push(Violation {
+			rule: RULE,
+			file: self.path_str.clone(),
+			line: start.line,
+			column: start.column,
+			end: Some((end.line, end.column)),
+			message: format!(
+				"`{}` blocks the executor thread in async code\nHINT: {hint}, or run it in `tokio::task::spawn_blocking`",
+				resolved.join("::")
+			),
+			severity: Severity::Error,
+			..Default::default()
+		});
+	}
+}
+
+impl<'a> Visit<'a> for BlockingVisitor {
+	fn visit_item_fn(&mut self, node: &'a ItemFn) {
+		self.with_async(node.sig.asyncness.is_some(), |this| syn::visit::visit_item_fn(this, node));
+	}
+
+	fn visit_impl_item_fn(&mut self, node: &'a ImplItemFn) {
+		self.with_async(node.sig.asyncness.is_some(), |this| syn::visit::visit_impl_item_fn(this, node));
+	}
+
+	fn visit_trait_item_fn(&mut self, node: &'a TraitItemFn) {
+		self.with_async(node.sig.asyncness.is_some(), |this| syn::visit::visit_trait_item_fn(this, node));
+	}
+
+	fn visit_expr_async(&mut self, node: &'a ExprAsync) {
+		self.with_async(true, |this| syn::visit::visit_expr_async(this, node));
+	}
+
+	fn visit_expr_closure(&mut self, node: &'a ExprClosure) {
+		self.with_async(node.asyncness.is_some(), |this| syn::visit::visit_expr_closure(this, node));
+	}
+
+	fn visit_expr_call(&mut self, node: &'a ExprCall) {
+		if self.in_async {
+			self.check_call(node);
+		}
+		syn::visit::visit_expr_call(self, node);
+	}
+}
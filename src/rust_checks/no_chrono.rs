@@ -8,9 +8,36 @@ use std::{collections::HashSet, path::Path};
 use proc_macro2::Span;
 use syn::{ItemUse, UseTree, visit::Visit};
 
-use super::{Violation, skip::SkipVisitor};
+use super::{
+	Severity, Violation,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
 
 const RULE: &str = "no-chrono";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "no_chrono",
+	autofix: false,
+	triggers: &["chrono"],
+	description: "Disallow the chrono crate (use jiff instead)",
+	rationale: "`chrono` has known soundness and API issues; `jiff` covers the same ground with correct time zone handling.",
+	bad: "use chrono::Utc;\nlet now = Utc::now();",
+	good: "let now = jiff::Timestamp::now();",
+	fix: "Port the code to `jiff`.",
+};
+
+pub struct NoChrono;
+impl Rule for NoChrono {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
 pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
 	let visitor = ChronoVisitor::new(path);
 	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
@@ -45,8 +72,11 @@ impl ChronoVisitor {
 			file: self.path_str.clone(),
 			line: span.start().line,
 			column: span.start().column,
+			end: Some((span.end().line, span.end().column)),
 			message: format!("Usage of `chrono` crate is disallowed{context}. Use `jiff` crate instead."),
+			severity: Severity::Error,
 			fix: None, // No auto-fix - requires manual migration
+			..Default::default()
 		});
 	}
 
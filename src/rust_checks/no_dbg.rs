@@ -0,0 +1,129 @@
+//! Lint to disallow leftover `dbg!` invocations.
+//!
+//! `dbg!` is for a debugging session, not for committed code: it prints to stderr on every call, in release builds too.
+//! The fix unwraps it back to its argument, `dbg!(expr)` to `expr`, since `dbg!` returns what it's given.
+
+use std::path::Path;
+
+use syn::{Expr, Macro, Token, punctuated::Punctuated, spanned::Spanned, visit::Visit};
+
+use super::{
+	Fix, Severity, Violation,
+	line_index::LineIndex,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
+
+const RULE: &str = "no-dbg";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "no_dbg",
+	autofix: true,
+	triggers: &["dbg!"],
+	description: "Disallow leftover dbg!",
+	rationale: "`dbg!` is a debugging aid: left in, it prints to stderr on every call, in release builds too.",
+	bad: "let total = dbg!(price * quantity);",
+	good: "let total = price * quantity;",
+	fix: "Replaces `dbg!(expr)` with `expr`, parenthesized if it isn't a single term; `dbg!(a, b)` becomes the tuple `(a, b)`.",
+};
+
+pub struct NoDbg;
+impl Rule for NoDbg {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+	let visitor = DbgVisitor {
+		path_str: path.display().to_string(),
+		content,
+		lines: LineIndex::new(content),
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+struct DbgVisitor<'a> {
+	path_str: String,
+	content: &'a str,
+	lines: LineIndex<'a>,
+	violations: Vec<Violation>,
+}
+
+impl DbgVisitor<'_> {
+	/// What `dbg!` with these arguments evaluates to, as source: the argument itself, or a tuple of several.
+	fn unwrapped(&self, args: &Punctuated<Expr, Token![,]>) -> Option<String> {
+		let text = |expr: &Expr| Some(&self.content[self.lines.offset_of(expr.span().start())?..self.lines.offset_of(expr.span().end())?]);
+		match args.len() {
+			0 => Some("()".to_string()),
+			1 => {
+				let arg = args.first()?;
+				Some(if is_term(arg) { text(arg)?.to_string() } else { format!("({})", text(arg)?) })
+			}
+			_ => {
+				let start = self.lines.offset_of(args.first()?.span().start())?;
+				let end = self.lines.offset_of(args.last()?.span().end())?;
+				Some(format!("({})", &self.content[start..end]))
+			}
+		}
+	}
+}
+
+// Any lifetime, so the arguments parsed out of a macro body can be visited too
+impl<'ast> Visit<'ast> for DbgVisitor<'_> {
+	fn visit_macro(&mut self, node: &'ast Macro) {
+		if node.path.segments.last().is_some_and(|s| s.ident == "dbg") {
+			let span = node.span();
+			let (start, end) = (span.start(), span.end());
+			// Unparseable arguments still get reported, just without a fix
+			let args = node.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated).ok();
+			let fix = args
+				.as_ref()
+				.and_then(|args| self.unwrapped(args))
+				.zip(self.lines.offset_of(start).zip(self.lines.offset_of(end)))
+				.map(|(replacement, (start_byte, end_byte))| Fix { start_byte, end_byte, replacement });
+			self.violations.push(Violation {
+				rule: RULE,
+				file: self.path_str.clone(),
+				line: start.line,
+				column: start.column,
+				end: Some((end.line, end.column)),
+				message: "leftover `dbg!`\nHINT: remove it, or log through `tracing` if the output is worth keeping".to_string(),
+				severity: Severity::Error,
+				fix,
+				..Default::default()
+			});
+			// Macro bodies aren't visited on their own, and there may be another `dbg!` in there
+			for arg in args.iter().flatten() {
+				self.visit_expr(arg);
+			}
+		}
+		syn::visit::visit_macro(self, node);
+	}
+}
+
+/// Whether `expr` binds tighter than any operator around it, so it can replace `dbg!(..)` without parentheses.
+fn is_term(expr: &Expr) -> bool {
+	matches!(
+		expr,
+		Expr::Array(_)
+			| Expr::Await(_)
+			| Expr::Call(_)
+			| Expr::Field(_)
+			| Expr::Index(_)
+			| Expr::Lit(_)
+			| Expr::Macro(_)
+			| Expr::MethodCall(_)
+			| Expr::Paren(_)
+			| Expr::Path(_)
+			| Expr::Try(_)
+			| Expr::Tuple(_)
+	)
+}
@@ -0,0 +1,117 @@
+//! Lint to drop `extern crate` items, which edition 2018 made unnecessary: dependencies are in scope by their name.
+//!
+//! `alloc` and `proc_macro` aren't in the extern prelude, so their `extern crate` stays, as does the one of `std`,
+//! `core` or `test`, and `extern crate self as ..`. The fix deletes the item with its attributes and doc comments,
+//! unless deleting it would change what's in scope: a renamed crate (`as ..`), a `pub` re-export or a `#[macro_use]`
+//! import are reported without one.
+
+use std::path::Path;
+
+use syn::{ItemExternCrate, Visibility, spanned::Spanned, visit::Visit};
+
+use super::{
+	Fix, Severity, Violation,
+	line_index::LineIndex,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
+
+const RULE: &str = "no-extern-crate";
+/// Crates whose `extern crate` is still needed
+const NEEDED: &[&str] = &["alloc", "proc_macro", "std", "core", "test"];
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "no_extern_crate",
+	autofix: true,
+	triggers: &["extern crate"],
+	description: "Disallow extern crate, unneeded since edition 2018",
+	rationale: "Since edition 2018 dependencies are in scope by their name; an `extern crate` only repeats `Cargo.toml`.",
+	bad: "extern crate serde;\n\nuse serde::Deserialize;",
+	good: "use serde::Deserialize;",
+	fix: "Deletes the item. Renamed, re-exported and `#[macro_use]` crates are left to be replaced with `use` by hand.",
+};
+
+pub struct NoExternCrate;
+impl Rule for NoExternCrate {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+	let visitor = ExternCrateVisitor {
+		path_str: path.display().to_string(),
+		lines: LineIndex::new(content),
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+struct ExternCrateVisitor<'a> {
+	path_str: String,
+	lines: LineIndex<'a>,
+	violations: Vec<Violation>,
+}
+
+impl ExternCrateVisitor<'_> {
+	/// Fix deleting `node`'s lines, if nothing else is on them.
+	fn deletion(&self, node: &ItemExternCrate) -> Option<Fix> {
+		let text = self.lines.text();
+		let start = self.lines.offset_of(node.span().start())?;
+		let end = self.lines.offset_of(node.span().end())?;
+		let (line_start, line_end) = (self.lines.line_start(start), self.lines.line_end(end));
+		if !text[line_start..start].trim().is_empty() || !text[end..line_end].trim().is_empty() {
+			return None;
+		}
+		let mut end_byte = (line_end + 1).min(text.len());
+		// With blank lines on both sides, or at the top of the file, one goes too
+		let blank_after = end_byte < text.len() && text[end_byte..self.lines.line_end(end_byte)].trim().is_empty();
+		if blank_after && (line_start == 0 || text[..line_start].ends_with("\n\n")) {
+			end_byte = (self.lines.line_end(end_byte) + 1).min(text.len());
+		}
+		Some(Fix {
+			start_byte: line_start,
+			end_byte,
+			replacement: String::new(),
+		})
+	}
+}
+
+impl<'a> Visit<'a> for ExternCrateVisitor<'_> {
+	fn visit_item_extern_crate(&mut self, node: &'a ItemExternCrate) {
+		let name = node.ident.to_string();
+		if name == "self" || NEEDED.contains(&name.as_str()) {
+			return;
+		}
+		let macro_use = node.attrs.iter().any(|attr| attr.path().is_ident("macro_use"));
+		let hint = if let Some((_, rename)) = &node.rename {
+			format!("replace it with `use {name} as {rename};`, or rename the dependency in `Cargo.toml`")
+		} else if !matches!(node.vis, Visibility::Inherited) {
+			format!("replace it with `pub use {name};`")
+		} else if macro_use {
+			format!("import the macros with `use {name}::..;` where they're used")
+		} else {
+			"delete it".to_string()
+		};
+		let fix = (node.rename.is_none() && matches!(node.vis, Visibility::Inherited) && !macro_use).then(|| self.deletion(node)).flatten();
+		let start = node.extern_token.span.start();
+		let end = node.semi_token.span.end();
+		self.violations.push(Violation {
+			rule: RULE,
+			file: self.path_str.clone(),
+			line: start.line,
+			column: start.column,
+			end: Some((end.line, end.column)),
+			message: format!("`extern crate {name}` is unneeded since edition 2018\nHINT: {hint}"),
+			severity: Severity::Error,
+			fix,
+			..Default::default()
+		});
+	}
+}
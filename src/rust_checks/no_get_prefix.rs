@@ -0,0 +1,414 @@
+//! Lint to name getters after what they get, without a `get_` prefix.
+//!
+//! A method of an inherent impl is a getter here if it takes nothing but `self` and returns one of its fields, as is,
+//! borrowed, or through `clone()`, `as_ref()`, `as_deref()`, `as_str()` or `as_slice()`. The Rust API guidelines name
+//! those `foo`, not `get_foo`. Names the prefix can't come off of, like `get_mut` or `get_ref`, are left alone.
+//!
+//! The fix renames the method and what in the file calls it: `Self::get_foo` or `Type::get_foo` paths, and
+//! `.get_foo()` method calls on a receiver known to be of the type: `self` in its impls, a field declared with the type,
+//! or a parameter or local declared with it or built as a `Type { .. }` literal, and not bound to anything else in the
+//! same fn. Other calls could be to a same-named method of another type, and it can't see into macros, so uses there
+//! are marked rather than renamed, as are paths through some other type; it does nothing when another fn named
+//! `get_foo` in the file makes the calls ambiguous. Callers in other files of a getter visible outside the file are
+//! theirs to rename.
+
+use std::{
+	collections::{HashMap, HashSet},
+	path::Path,
+};
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use syn::{
+	Expr, ExprMethodCall, ExprPath, Fields, ImplItem, ImplItemFn, ItemFn, ItemImpl, ItemStruct, Local, Pat, PatIdent, PatType, ReturnType, Stmt, TraitItemFn, Type, Visibility,
+	visit::Visit,
+};
+
+use super::{
+	Fix, Label, Severity, Violation,
+	line_index::LineIndex,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
+
+const RULE: &str = "no-get-prefix";
+/// Methods a getter may return its field through
+const PASSTHROUGH: &[&str] = &["clone", "as_ref", "as_deref", "as_str", "as_slice"];
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "no_get_prefix",
+	autofix: true,
+	triggers: &["get_"],
+	description: "Disallow the get_ prefix on getters",
+	rationale: "The Rust API guidelines name a getter after the field it returns; `get_` adds nothing a `&self` method returning a field doesn't already say.",
+	bad: "impl Client {\n\tpub fn get_timeout(&self) -> Duration {\n\t\tself.timeout\n\t}\n}",
+	good: "impl Client {\n\tpub fn timeout(&self) -> Duration {\n\t\tself.timeout\n\t}\n}",
+	fix: "Renames the getter and its calls in the file, marking the uses it couldn't rename.",
+};
+
+pub struct NoGetPrefix;
+impl Rule for NoGetPrefix {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+	let visitor = GetterVisitor {
+		path_str: path.display().to_string(),
+		lines: LineIndex::new(content),
+		file,
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+struct GetterVisitor<'a> {
+	path_str: String,
+	lines: LineIndex<'a>,
+	file: &'a syn::File,
+	violations: Vec<Violation>,
+}
+
+impl GetterVisitor<'_> {
+	fn check_impl(&mut self, node: &ItemImpl) {
+		let syn::Type::Path(self_ty) = &*node.self_ty else {
+			return;
+		};
+		if node.trait_.is_some() {
+			return;
+		}
+		let Some(type_name) = self_ty.path.segments.last().map(|last| last.ident.to_string()) else {
+			return;
+		};
+		for item in &node.items {
+			let ImplItem::Fn(f) = item else {
+				continue;
+			};
+			let name = f.sig.ident.to_string();
+			let Some(new_name) = name.strip_prefix("get_").filter(|new_name| syn::parse_str::<syn::Ident>(new_name).is_ok()) else {
+				continue;
+			};
+			if !is_getter(f) {
+				continue;
+			}
+			let taken = node.items.iter().any(|other| matches!(other, ImplItem::Fn(other) if other.sig.ident == new_name));
+			self.report(f, new_name, &type_name, taken);
+		}
+	}
+
+	fn report(&mut self, f: &ImplItemFn, new_name: &str, type_name: &str, taken: bool) {
+		let name = f.sig.ident.to_string();
+		let mut uses = Uses {
+			name: &name,
+			type_name,
+			typed_fields: typed_fields(self.file, type_name),
+			impl_type: None,
+			bindings: HashMap::new(),
+			defs: 0,
+			renamed: Vec::new(),
+			missed: Vec::new(),
+		};
+		uses.visit_file(self.file);
+		let mut hint = format!("name it `{new_name}`");
+		if taken {
+			hint.push_str(&format!(", after renaming the `{new_name}` method already there"));
+		} else if uses.defs > 1 {
+			hint.push_str(&format!(", renaming its calls by hand: other fns are named `{name}` too"));
+		} else if !uses.missed.is_empty() {
+			hint.push_str(", renaming the marked uses the fix can't by hand");
+		}
+		if !matches!(f.vis, Visibility::Inherited) {
+			hint.push_str(", and its callers in other files");
+		}
+		let fix = (!taken && uses.defs == 1).then(|| self.rename_fix(&uses.renamed, new_name)).flatten();
+		let labels = uses
+			.missed
+			.iter()
+			.map(|span| Label {
+				line: span.start().line,
+				column: span.start().column,
+				message: format!("`{name}` used here, which the fix doesn't rename"),
+			})
+			.collect();
+		let span = f.sig.ident.span();
+		self.violations.push(Violation {
+			rule: RULE,
+			file: self.path_str.clone(),
+			line: span.start().line,
+			column: span.start().column,
+			end: Some((span.end().line, span.end().column)),
+			message: format!("getter `{name}` has a `get_` prefix\nHINT: {hint}"),
+			severity: Severity::Error,
+			fix,
+			labels,
+			..Default::default()
+		});
+	}
+
+	/// One fix renaming every identifier at `spans` to `new_name`.
+	fn rename_fix(&self, spans: &[Span], new_name: &str) -> Option<Fix> {
+		let mut ranges = spans
+			.iter()
+			.map(|span| Some((self.lines.offset_of(span.start())?, self.lines.offset_of(span.end())?)))
+			.collect::<Option<Vec<_>>>()?;
+		ranges.sort();
+		let (start_byte, end_byte) = (ranges.first()?.0, ranges.last()?.1);
+		let text = self.lines.text();
+		let mut replacement = String::new();
+		let mut at = start_byte;
+		for (start, end) in ranges {
+			replacement.push_str(&text[at..start]);
+			replacement.push_str(new_name);
+			at = end;
+		}
+		Some(Fix { start_byte, end_byte, replacement })
+	}
+}
+
+impl<'a> Visit<'a> for GetterVisitor<'_> {
+	fn visit_item_impl(&mut self, node: &'a ItemImpl) {
+		self.check_impl(node);
+		syn::visit::visit_item_impl(self, node);
+	}
+}
+
+/// Where a method is defined and used in a file.
+struct Uses<'a> {
+	name: &'a str,
+	type_name: &'a str,
+	/// Fields declared with the type, by the struct they're in
+	typed_fields: HashMap<String, HashSet<String>>,
+	/// Type of the impl being visited
+	impl_type: Option<String>,
+	/// Names bound in the fn being visited, with whether every binding of the name is of the type
+	bindings: HashMap<String, bool>,
+	/// How many fns of any kind have the name
+	defs: usize,
+	/// The definition and the calls the fix renames
+	renamed: Vec<Span>,
+	/// Uses the fix can't tell are the method's, or can't reach
+	missed: Vec<Span>,
+}
+
+impl<'a> Visit<'a> for Uses<'_> {
+	fn visit_item_impl(&mut self, node: &'a ItemImpl) {
+		let impl_type = match &*node.self_ty {
+			Type::Path(ty) => ty.path.segments.last().map(|last| last.ident.to_string()),
+			_ => None,
+		};
+		let outer = std::mem::replace(&mut self.impl_type, impl_type);
+		syn::visit::visit_item_impl(self, node);
+		self.impl_type = outer;
+	}
+
+	fn visit_impl_item_fn(&mut self, node: &'a ImplItemFn) {
+		if node.sig.ident == self.name {
+			self.defs += 1;
+			self.renamed.push(node.sig.ident.span());
+		}
+		let bindings = self.bindings_of(|bindings| bindings.visit_impl_item_fn(node));
+		let outer = std::mem::replace(&mut self.bindings, bindings);
+		syn::visit::visit_impl_item_fn(self, node);
+		self.bindings = outer;
+	}
+
+	fn visit_item_fn(&mut self, node: &'a ItemFn) {
+		if node.sig.ident == self.name {
+			self.defs += 1;
+		}
+		let bindings = self.bindings_of(|bindings| bindings.visit_item_fn(node));
+		let outer = std::mem::replace(&mut self.bindings, bindings);
+		syn::visit::visit_item_fn(self, node);
+		self.bindings = outer;
+	}
+
+	fn visit_trait_item_fn(&mut self, node: &'a TraitItemFn) {
+		if node.sig.ident == self.name {
+			self.defs += 1;
+		}
+		syn::visit::visit_trait_item_fn(self, node);
+	}
+
+	fn visit_expr_method_call(&mut self, node: &'a ExprMethodCall) {
+		if node.method == self.name {
+			if self.is_own(&node.receiver) {
+				self.renamed.push(node.method.span());
+			} else {
+				self.missed.push(node.method.span());
+			}
+		}
+		syn::visit::visit_expr_method_call(self, node);
+	}
+
+	fn visit_expr_path(&mut self, node: &'a ExprPath) {
+		let segments = &node.path.segments;
+		if let Some(last) = segments.last().filter(|last| last.ident == self.name) {
+			let qualifier = segments.len().checked_sub(2).map(|i| &segments[i].ident);
+			let through_type = node.qself.is_none() && qualifier.is_some_and(|ty| ty == "Self" || ty == self.type_name);
+			if through_type {
+				self.renamed.push(last.ident.span());
+			} else {
+				self.missed.push(last.ident.span());
+			}
+		}
+		syn::visit::visit_expr_path(self, node);
+	}
+
+	fn visit_macro(&mut self, node: &'a syn::Macro) {
+		self.scan_tokens(node.tokens.clone());
+	}
+}
+
+impl Uses<'_> {
+	/// Whether the impl being visited is one of the type's, where `self` and `Self` are it.
+	fn in_own_impl(&self) -> bool {
+		self.impl_type.as_deref() == Some(self.type_name)
+	}
+
+	/// Whether `receiver` is known to be of the type, or a reference to it.
+	fn is_own(&self, receiver: &Expr) -> bool {
+		match receiver {
+			Expr::Reference(reference) => self.is_own(&reference.expr),
+			Expr::Paren(paren) => self.is_own(&paren.expr),
+			Expr::Path(path) if path.qself.is_none() => match path.path.get_ident() {
+				Some(ident) if ident == "self" => self.in_own_impl(),
+				Some(ident) => self.bindings.get(&ident.to_string()) == Some(&true),
+				None => false,
+			},
+			Expr::Field(field) => {
+				let syn::Member::Named(member) = &field.member else {
+					return false;
+				};
+				let on_self = matches!(&*field.base, Expr::Path(base) if base.path.is_ident("self"));
+				on_self && self.impl_type.as_ref().and_then(|ty| self.typed_fields.get(ty)).is_some_and(|fields| fields.contains(&member.to_string()))
+			}
+			_ => false,
+		}
+	}
+
+	/// Bindings `visit` finds.
+	fn bindings_of(&self, visit: impl FnOnce(&mut Bindings)) -> HashMap<String, bool> {
+		let mut bindings = Bindings {
+			type_name: self.type_name,
+			self_is_type: self.in_own_impl(),
+			found: HashMap::new(),
+		};
+		visit(&mut bindings);
+		bindings.found
+	}
+
+	fn scan_tokens(&mut self, tokens: TokenStream) {
+		for token in tokens {
+			match token {
+				TokenTree::Ident(ident) if ident == self.name => self.missed.push(ident.span()),
+				TokenTree::Group(group) => self.scan_tokens(group.stream()),
+				_ => {}
+			}
+		}
+	}
+}
+
+/// Names bound in a fn, with whether every binding of the name is known to be of the type.
+struct Bindings<'a> {
+	type_name: &'a str,
+	/// Whether `Self` is the type, in one of its impls
+	self_is_type: bool,
+	found: HashMap<String, bool>,
+}
+
+impl Bindings<'_> {
+	fn bind(&mut self, name: &PatIdent, own: bool) {
+		*self.found.entry(name.ident.to_string()).or_insert(own) &= own;
+	}
+}
+
+impl<'a> Visit<'a> for Bindings<'_> {
+	fn visit_pat_type(&mut self, node: &'a PatType) {
+		match &*node.pat {
+			Pat::Ident(name) if name.subpat.is_none() => self.bind(name, is_type(&node.ty, self.type_name, self.self_is_type)),
+			_ => syn::visit::visit_pat_type(self, node),
+		}
+	}
+
+	fn visit_local(&mut self, node: &'a Local) {
+		let literal = node.init.as_ref().is_some_and(|init| {
+			matches!(&*init.expr, Expr::Struct(init) if init.path.segments.last().is_some_and(|last| last.ident == self.type_name || (self.self_is_type && last.ident == "Self")))
+		});
+		match &node.pat {
+			Pat::Ident(name) if literal && name.subpat.is_none() => {
+				self.bind(name, true);
+				if let Some(init) = &node.init {
+					self.visit_local_init(init);
+				}
+			}
+			_ => syn::visit::visit_local(self, node),
+		}
+	}
+
+	fn visit_pat_ident(&mut self, node: &'a PatIdent) {
+		self.bind(node, false);
+		syn::visit::visit_pat_ident(self, node);
+	}
+}
+
+/// Fields of the struct `struct_name`, for each struct in `file`, that are declared with the type `type_name`.
+fn typed_fields(file: &syn::File, type_name: &str) -> HashMap<String, HashSet<String>> {
+	struct Structs<'t> {
+		type_name: &'t str,
+		fields: HashMap<String, HashSet<String>>,
+	}
+	impl<'a> Visit<'a> for Structs<'_> {
+		fn visit_item_struct(&mut self, node: &'a ItemStruct) {
+			if let Fields::Named(fields) = &node.fields {
+				let typed = fields
+					.named
+					.iter()
+					.filter(|field| is_type(&field.ty, self.type_name, node.ident == self.type_name))
+					.filter_map(|field| field.ident.as_ref().map(ToString::to_string))
+					.collect();
+				self.fields.insert(node.ident.to_string(), typed);
+			}
+			syn::visit::visit_item_struct(self, node);
+		}
+	}
+	let mut structs = Structs { type_name, fields: HashMap::new() };
+	structs.visit_file(file);
+	structs.fields
+}
+
+/// Whether `ty` is the type `type_name`, or a reference to it; `Self` counts if `self_is_type`.
+fn is_type(ty: &Type, type_name: &str, self_is_type: bool) -> bool {
+	match ty {
+		Type::Reference(reference) => is_type(&reference.elem, type_name, self_is_type),
+		Type::Paren(paren) => is_type(&paren.elem, type_name, self_is_type),
+		Type::Path(path) if path.qself.is_none() => path.path.segments.last().is_some_and(|last| last.arguments.is_empty() && (last.ident == type_name || (self_is_type && last.ident == "Self"))),
+		_ => false,
+	}
+}
+
+/// Whether `f` takes only `self` and returns one of its fields.
+fn is_getter(f: &ImplItemFn) -> bool {
+	if f.sig.receiver().is_none() || f.sig.inputs.len() != 1 || !f.sig.generics.params.is_empty() || matches!(f.sig.output, ReturnType::Default) {
+		return false;
+	}
+	let [Stmt::Expr(expr, None)] = f.block.stmts.as_slice() else {
+		return false;
+	};
+	is_self_field(expr)
+}
+
+fn is_self_field(expr: &Expr) -> bool {
+	match expr {
+		Expr::Field(field) => matches!(&*field.base, Expr::Path(base) if base.path.is_ident("self")),
+		Expr::Reference(reference) => is_self_field(&reference.expr),
+		Expr::Paren(paren) => is_self_field(&paren.expr),
+		Expr::MethodCall(call) => call.args.is_empty() && PASSTHROUGH.iter().any(|method| call.method == method) && is_self_field(&call.receiver),
+		_ => false,
+	}
+}
@@ -0,0 +1,262 @@
+//! Lint to disallow the `lazy_static` crate, which `std::sync::LazyLock` replaces.
+//!
+//! Both `lazy_static!` invocations and imports of the crate are flagged. An invocation defining a single static without
+//! attributes is rewritten to `static NAME: LazyLock<T> = LazyLock::new(|| init);`. Once every invocation in the file
+//! can be, a lone `use lazy_static::lazy_static;` becomes `use std::sync::LazyLock;` and `#[macro_use] extern crate
+//! lazy_static;` goes; otherwise the statics name `std::sync::LazyLock` in full.
+
+use std::path::Path;
+
+use syn::{
+	Attribute, Expr, Ident, ItemExternCrate, ItemUse, Macro, MacroDelimiter, Token, Type, UseTree, Visibility,
+	parse::{ParseStream, Parser},
+	spanned::Spanned,
+	visit::Visit,
+};
+
+use super::{
+	Fix, Severity, Violation,
+	custom_rules::Imports,
+	line_index::LineIndex,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
+
+const RULE: &str = "no-lazy-static";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "no_lazy_static",
+	autofix: true,
+	triggers: &["lazy_static"],
+	description: "Disallow the lazy_static crate, in favor of std::sync::LazyLock",
+	rationale: "`std::sync::LazyLock` does what `lazy_static!` does as a plain `static` with a real type, without a dependency or a macro.",
+	bad: "lazy_static! {\n\tstatic ref WORD: Regex = Regex::new(r\"\\w+\").unwrap();\n}",
+	good: "static WORD: LazyLock<Regex> = LazyLock::new(|| Regex::new(r\"\\w+\").unwrap());",
+	fix: "Rewrites an invocation defining a single static without attributes to a `LazyLock` static, and the import along with the last of them.",
+};
+
+pub struct NoLazyStatic;
+impl Rule for NoLazyStatic {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+	// The imports can only change once no invocation is left behind, skipped ones included, so all are counted first
+	let mut all = LazyStaticVisitor::new(path, content, Plan::default());
+	all.visit_file(file);
+	let mut unskipped = SkipVisitor::for_rule(LazyStaticVisitor::new(path, content, Plan::default()), content, RULE);
+	unskipped.visit_file(file);
+	let lock_imported = Imports::of(file).resolve(&syn::Path::from(Ident::new("LazyLock", proc_macro2::Span::call_site()))) == ["std", "sync", "LazyLock"];
+	let rewrite_imports = unskipped.inner.fixable == all.invocations;
+	let plan = Plan {
+		rewrite_imports,
+		import_lock: rewrite_imports && all.invocations > 0 && !lock_imported,
+		lock_path: if lock_imported || (rewrite_imports && all.lone_import) { "LazyLock" } else { "std::sync::LazyLock" },
+	};
+
+	let visitor = LazyStaticVisitor::new(path, content, plan);
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+/// How the file's fixes fit together. The default only serves to count invocations.
+#[derive(Clone, Copy, Default)]
+struct Plan {
+	/// Whether imports of the crate are fixed, which is once no invocation is left behind
+	rewrite_imports: bool,
+	/// Whether a lone `use lazy_static::lazy_static;` becomes `use std::sync::LazyLock;`, rather than going
+	import_lock: bool,
+	/// How the rewritten statics name `LazyLock`
+	lock_path: &'static str,
+}
+
+/// One `static ref` of a `lazy_static!`.
+struct LazyStatic {
+	attrs: Vec<Attribute>,
+	vis: Visibility,
+	name: Ident,
+	ty: Type,
+	init: Expr,
+}
+
+struct LazyStaticVisitor<'a> {
+	path_str: String,
+	lines: LineIndex<'a>,
+	plan: Plan,
+	invocations: usize,
+	/// Invocations that get a fix
+	fixable: usize,
+	/// Whether there's a `use lazy_static::lazy_static;` on its own
+	lone_import: bool,
+	violations: Vec<Violation>,
+}
+
+impl<'a> LazyStaticVisitor<'a> {
+	fn new(path: &Path, content: &'a str, plan: Plan) -> Self {
+		Self {
+			path_str: path.display().to_string(),
+			lines: LineIndex::new(content),
+			plan,
+			invocations: 0,
+			fixable: 0,
+			lone_import: false,
+			violations: Vec::new(),
+		}
+	}
+
+	fn check_macro(&mut self, node: &Macro) {
+		self.invocations += 1;
+		let statics = parse_statics.parse2(node.tokens.clone()).unwrap_or_default();
+		let names: Vec<String> = statics.iter().map(|s| format!("`{}`", s.name)).collect();
+		let (message, fix) = match statics.as_slice() {
+			[single] => {
+				let fix = self.rewrite(node, single);
+				let hint = format!("use `static {}: LazyLock<..> = LazyLock::new(|| ..);`", single.name);
+				(format!("`lazy_static!` defining {}\nHINT: {hint}", names.join(", ")), fix)
+			}
+			[] => ("`lazy_static!`\nHINT: use `std::sync::LazyLock` statics".to_string(), None),
+			_ => (format!("`lazy_static!` defining {}\nHINT: use a `std::sync::LazyLock` static for each", names.join(", ")), None),
+		};
+		self.fixable += usize::from(fix.is_some());
+		let start = node.path.span().start();
+		let end = node.delimiter.span().close().end();
+		self.push(start, end, message, fix);
+	}
+
+	/// Fix turning the invocation `node` of `single` into a `LazyLock` static.
+	fn rewrite(&self, node: &Macro, single: &LazyStatic) -> Option<Fix> {
+		if !single.attrs.is_empty() || !matches!(node.delimiter, MacroDelimiter::Brace(_)) {
+			return None;
+		}
+		let start_byte = self.lines.offset_of(node.path.span().start())?;
+		let end_byte = self.lines.offset_of(node.delimiter.span().close().end())?;
+		let vis = match &single.vis {
+			Visibility::Inherited => String::new(),
+			vis => format!("{} ", self.text(vis)?),
+		};
+		let ty = self.text(&single.ty)?;
+		// The initializer moves out of the macro's braces, so it loses their level of indentation
+		let text = self.lines.text();
+		let outer = indent_of(text, self.lines.line_start(start_byte));
+		let inner = indent_of(text, self.lines.line_start(self.lines.offset_of(single.name.span().start())?));
+		let init = self.text(&single.init)?.replace(&format!("\n{inner}"), &format!("\n{outer}"));
+		let lock = self.plan.lock_path;
+		Some(Fix {
+			start_byte,
+			end_byte,
+			replacement: format!("{vis}static {}: {lock}<{ty}> = {lock}::new(|| {init});", single.name),
+		})
+	}
+
+	fn push(&mut self, start: proc_macro2::LineColumn, end: proc_macro2::LineColumn, message: String, fix: Option<Fix>) {
+		self.violations.push(Violation {
+			rule: RULE,
+			file: self.path_str.clone(),
+			line: start.line,
+			column: start.column,
+			end: Some((end.line, end.column)),
+			message,
+			severity: Severity::Error,
+			fix,
+			..Default::default()
+		});
+	}
+
+	/// Fix replacing `node` with `replacement`, or deleting it along with its lines if there's none.
+	fn import_fix(&self, node: &impl Spanned, replacement: Option<&str>) -> Option<Fix> {
+		let start = self.lines.offset_of(node.span().start())?;
+		let end = self.lines.offset_of(node.span().end())?;
+		Some(match replacement {
+			Some(replacement) => Fix {
+				start_byte: start,
+				end_byte: end,
+				replacement: replacement.to_string(),
+			},
+			None => Fix {
+				start_byte: self.lines.line_start(start),
+				end_byte: (self.lines.line_end(end) + 1).min(self.lines.text().len()),
+				replacement: String::new(),
+			},
+		})
+	}
+
+	fn text(&self, node: &impl Spanned) -> Option<&'a str> {
+		let span = node.span();
+		self.lines.text().get(self.lines.offset_of(span.start())?..self.lines.offset_of(span.end())?)
+	}
+}
+
+impl<'a> Visit<'a> for LazyStaticVisitor<'_> {
+	fn visit_macro(&mut self, node: &'a Macro) {
+		if node.path.segments.last().is_some_and(|last| last.ident == "lazy_static") {
+			self.check_macro(node);
+		}
+		syn::visit::visit_macro(self, node);
+	}
+
+	fn visit_item_use(&mut self, node: &'a ItemUse) {
+		if !imports_lazy_static(&node.tree) {
+			return;
+		}
+		let is_lone = matches!(&node.tree, UseTree::Path(path) if path.ident == "lazy_static" && matches!(&*path.tree, UseTree::Name(name) if name.ident == "lazy_static"))
+			&& node.attrs.is_empty()
+			&& matches!(node.vis, Visibility::Inherited);
+		self.lone_import |= is_lone;
+		let replacement = self.plan.import_lock.then_some("use std::sync::LazyLock;");
+		let fix = (self.plan.rewrite_imports && is_lone).then(|| self.import_fix(node, replacement)).flatten();
+		let message = "`lazy_static` import\nHINT: `std::sync::LazyLock` replaces the crate".to_string();
+		self.push(node.span().start(), node.span().end(), message, fix);
+	}
+
+	fn visit_item_extern_crate(&mut self, node: &'a ItemExternCrate) {
+		if node.ident != "lazy_static" {
+			return;
+		}
+		let fix = (self.plan.rewrite_imports && node.rename.is_none()).then(|| self.import_fix(node, None)).flatten();
+		let message = "`lazy_static` crate\nHINT: `std::sync::LazyLock` replaces it".to_string();
+		self.push(node.span().start(), node.span().end(), message, fix);
+	}
+}
+
+/// The `$vis static ref $name: $ty = $init;` items a `lazy_static!` body consists of.
+fn parse_statics(input: ParseStream) -> syn::Result<Vec<LazyStatic>> {
+	let mut statics = Vec::new();
+	while !input.is_empty() {
+		let attrs = input.call(Attribute::parse_outer)?;
+		let vis = input.parse()?;
+		input.parse::<Token![static]>()?;
+		input.parse::<Token![ref]>()?;
+		let name = input.parse()?;
+		input.parse::<Token![:]>()?;
+		let ty = input.parse()?;
+		input.parse::<Token![=]>()?;
+		let init = input.parse()?;
+		input.parse::<Token![;]>()?;
+		statics.push(LazyStatic { attrs, vis, name, ty, init });
+	}
+	Ok(statics)
+}
+
+/// Whether `tree` brings in anything from the `lazy_static` crate.
+fn imports_lazy_static(tree: &UseTree) -> bool {
+	match tree {
+		UseTree::Path(path) => path.ident == "lazy_static" || imports_lazy_static(&path.tree),
+		UseTree::Name(name) => name.ident == "lazy_static",
+		UseTree::Rename(rename) => rename.ident == "lazy_static",
+		UseTree::Glob(_) => false,
+		UseTree::Group(group) => group.items.iter().any(imports_lazy_static),
+	}
+}
+
+/// Leading whitespace of the line starting at `line_start`.
+fn indent_of(text: &str, line_start: usize) -> &str {
+	let line = &text[line_start..];
+	&line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
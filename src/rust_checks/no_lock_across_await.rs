@@ -0,0 +1,193 @@
+//! Lint to disallow holding a `std::sync::Mutex`/`RwLock` guard across an `.await`.
+//!
+//! While a blocking guard is held, every other task wanting the lock blocks its executor thread, and one that was
+//! waiting on the same thread can deadlock it; the guard also makes the future `!Send`. Like `no_tokio_spawn`, this
+//! keeps async code to what the runtime can schedule around.
+//!
+//! Without type information, a guard is a `let` of a `.lock()`, `.read()` or `.write()` call that isn't awaited
+//! itself (async locks' are), possibly unwrapped, inside an async fn, block or closure. It's flagged when a later
+//! statement of the same block awaits before a `drop(guard)`.
+
+use std::path::Path;
+
+use proc_macro2::Span;
+use syn::{
+	Block, Expr, ExprAsync, ExprAwait, ExprCall, ExprClosure, ExprMethodCall, ImplItemFn, Item, ItemFn, Local, LocalInit, Pat, PatIdent, Stmt, TraitItemFn, spanned::Spanned,
+	visit::Visit,
+};
+
+use super::{
+	Label, Severity, Violation,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
+
+const RULE: &str = "no-lock-across-await";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "no_lock_across_await",
+	autofix: false,
+	triggers: &[".lock()", ".read()", ".write()"],
+	description: "Disallow std::sync lock guards held across .await",
+	rationale: "A blocking guard held over an `.await` blocks the executor thread for every other task wanting the lock, can deadlock it, and makes the future `!Send`.",
+	bad: "let mut cache = self.cache.lock().unwrap();\nlet value = fetch(key).await;\ncache.insert(key, value);",
+	good: "let value = fetch(key).await;\nself.cache.lock().unwrap().insert(key, value);",
+	fix: "Drop the guard before the `.await`, e.g. by scoping it to a block, or switch to `tokio::sync::Mutex`/`RwLock`, whose guards can be held across it.",
+};
+
+pub struct NoLockAcrossAwait;
+impl Rule for NoLockAcrossAwait {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+	let visitor = LockVisitor {
+		path_str: path.display().to_string(),
+		in_async: false,
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+struct LockVisitor {
+	path_str: String,
+	/// Whether the current code runs as part of a future
+	in_async: bool,
+	violations: Vec<Violation>,
+}
+
+impl LockVisitor {
+	fn with_async(&mut self, in_async: bool, visit: impl FnOnce(&mut Self)) {
+		let outer = std::mem::replace(&mut self.in_async, in_async);
+		visit(self);
+		self.in_async = outer;
+	}
+
+	fn check_block(&mut self, block: &Block) {
+		for (i, stmt) in block.stmts.iter().enumerate() {
+			let Stmt::Local(local) = stmt else {
+				continue;
+			};
+			let Some((guard, method)) = lock_guard(local) else {
+				continue;
+			};
+			let Some(await_span) = block.stmts[i + 1..].iter().take_while(|stmt| !drops(stmt, &guard.ident)).find_map(first_await) else {
+				continue;
+			};
+			let lock = if method.method == "lock" { "Mutex" } else { "RwLock" };
+			let start = guard.span().start();
+			let end = local.span().end();
+			self.violations.push(Violation {
+				rule: RULE,
+				file: self.path_str.clone(),
+				line: start.line,
+				column: start.column,
+				end: Some((end.line, end.column)),
+				message: format!(
+					"`{}` holds a `{}()` guard across an `.await`\nHINT: drop it before the `.await`, or use `tokio::sync::{lock}`, whose guard can be held across one",
+					guard.ident, method.method
+				),
+				severity: Severity::Error,
+				labels: vec![Label {
+					line: await_span.start().line,
+					column: await_span.start().column,
+					message: format!("awaited here, with `{}` still locked", guard.ident),
+				}],
+				..Default::default()
+			});
+		}
+	}
+}
+
+impl<'a> Visit<'a> for LockVisitor {
+	fn visit_item_fn(&mut self, node: &'a ItemFn) {
+		self.with_async(node.sig.asyncness.is_some(), |this| syn::visit::visit_item_fn(this, node));
+	}
+
+	fn visit_impl_item_fn(&mut self, node: &'a ImplItemFn) {
+		self.with_async(node.sig.asyncness.is_some(), |this| syn::visit::visit_impl_item_fn(this, node));
+	}
+
+	fn visit_trait_item_fn(&mut self, node: &'a TraitItemFn) {
+		self.with_async(node.sig.asyncness.is_some(), |this| syn::visit::visit_trait_item_fn(this, node));
+	}
+
+	fn visit_expr_async(&mut self, node: &'a ExprAsync) {
+		self.with_async(true, |this| syn::visit::visit_expr_async(this, node));
+	}
+
+	fn visit_expr_closure(&mut self, node: &'a ExprClosure) {
+		self.with_async(node.asyncness.is_some(), |this| syn::visit::visit_expr_closure(this, node));
+	}
+
+	fn visit_block(&mut self, node: &'a Block) {
+		if self.in_async {
+			self.check_block(node);
+		}
+		syn::visit::visit_block(self, node);
+	}
+}
+
+/// The name a `let` binds a blocking lock guard to, with the locking call.
+fn lock_guard(local: &Local) -> Option<(&PatIdent, &ExprMethodCall)> {
+	let Pat::Ident(guard @ PatIdent { subpat: None, .. }) = &local.pat else {
+		return None;
+	};
+	let Some(LocalInit { expr, diverge: None, .. }) = &local.init else {
+		return None;
+	};
+	Some((guard, lock_call(expr)?))
+}
+
+/// The `.lock()`/`.read()`/`.write()` call `expr` ends in, past any unwrapping of its poisoning error.
+fn lock_call(expr: &Expr) -> Option<&ExprMethodCall> {
+	match expr {
+		Expr::Try(try_expr) => lock_call(&try_expr.expr),
+		Expr::MethodCall(call) if ["unwrap", "expect", "unwrap_or_else"].iter().any(|m| call.method == m) => lock_call(&call.receiver),
+		Expr::MethodCall(call) if call.args.is_empty() && ["lock", "read", "write"].iter().any(|m| call.method == m) => Some(call),
+		_ => None,
+	}
+}
+
+/// Whether `stmt` is `drop(guard);`.
+fn drops(stmt: &Stmt, guard: &syn::Ident) -> bool {
+	let Stmt::Expr(Expr::Call(ExprCall { func, args, .. }), _) = stmt else {
+		return false;
+	};
+	let Expr::Path(func) = &**func else {
+		return false;
+	};
+	func.path.segments.last().is_some_and(|last| last.ident == "drop") && matches!(args.first(), Some(Expr::Path(arg)) if arg.path.is_ident(guard))
+}
+
+/// Span of the first `.await` that `stmt` runs itself, not counting ones in futures and closures it only creates.
+fn first_await(stmt: &Stmt) -> Option<Span> {
+	let mut finder = AwaitFinder { found: None };
+	finder.visit_stmt(stmt);
+	finder.found
+}
+
+struct AwaitFinder {
+	found: Option<Span>,
+}
+
+impl<'a> Visit<'a> for AwaitFinder {
+	fn visit_expr_await(&mut self, node: &'a ExprAwait) {
+		syn::visit::visit_expr_await(self, node);
+		self.found.get_or_insert(node.await_token.span);
+	}
+
+	fn visit_expr_async(&mut self, _: &'a ExprAsync) {}
+
+	fn visit_expr_closure(&mut self, _: &'a ExprClosure) {}
+
+	fn visit_item(&mut self, _: &'a Item) {}
+}
@@ -0,0 +1,249 @@
+//! Lint to import macros with `use` rather than `#[macro_use]`.
+//!
+//! `#[macro_use]` makes macros available by where they're declared, not by name: on a `mod`, everything declared after
+//! it sees the module's macros, so which macro a name refers to depends on declaration order. Since edition 2018 a
+//! `#[macro_export]` macro is imported like any item, with `use crate::name;`, and an external crate's with
+//! `use krate::name;`.
+//!
+//! The fix is for `#[macro_use] mod` whose `macro_rules!` are all `#[macro_export]`ed: it drops the attribute and
+//! imports the macros the rest of the file invokes, above the module's doc comments and attributes, under the same
+//! `#[cfg]`s as the module. It only looks at the declaring file, so other files relying on the attribute need their own
+//! imports. An out-of-line module is read from its file, which `#[macro_use]` on a module nested in an inline one
+//! doesn't get a fix for.
+
+use std::{
+	fs,
+	ops::Range,
+	path::{Path, PathBuf},
+};
+
+use syn::{Attribute, Item, ItemExternCrate, ItemMod, Macro, spanned::Spanned, visit::Visit};
+
+use super::{
+	Fix, Severity, Violation,
+	derive_default::combine,
+	line_index::LineIndex,
+	path_modules::{self, path_attr},
+	pub_first::find_item_text_start,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
+
+const RULE: &str = "no-macro-use";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "no_macro_use",
+	autofix: true,
+	triggers: &["macro_use"],
+	description: "Disallow #[macro_use] in favor of importing macros with use",
+	rationale: "`#[macro_use]` scopes macros by declaration order; a `use` names where each macro comes from, like any other import.",
+	bad: "#[macro_use]\nmod macros;\n\nfn main() {\n\tlog_call!();\n}",
+	good: "use crate::log_call;\n\nmod macros;\n\nfn main() {\n\tlog_call!();\n}",
+	fix: "Drops `#[macro_use]` from a module whose macros are all `#[macro_export]`ed, importing the ones the file uses.",
+};
+
+pub struct NoMacroUse;
+impl Rule for NoMacroUse {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+
+	/// The files of out-of-line modules, to see whether their macros are exported
+	fn reads_other_files(&self) -> bool {
+		true
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+	let lines = LineIndex::new(content);
+	let mut invocations = Invocations { lines: &lines, found: Vec::new() };
+	invocations.visit_file(file);
+	let visitor = MacroUseVisitor {
+		path,
+		lines: &lines,
+		invocations: invocations.found,
+		depth: 0,
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+struct MacroUseVisitor<'a> {
+	path: &'a Path,
+	lines: &'a LineIndex<'a>,
+	/// Names and byte offsets of the macros invoked by a plain name in the file
+	invocations: Vec<(String, usize)>,
+	/// How many inline modules the visitor is in
+	depth: usize,
+	violations: Vec<Violation>,
+}
+
+impl MacroUseVisitor<'_> {
+	fn push(&mut self, attr: &Attribute, message: String, fix: Option<Fix>) {
+		let start = attr.pound_token.span.start();
+		let end = attr.span().end();
+		self.violations.push(Violation {
+			rule: RULE,
+			file: self.path.display().to_string(),
+			line: start.line,
+			column: start.column,
+			end: Some((end.line, end.column)),
+			message,
+			severity: Severity::Error,
+			fix,
+			..Default::default()
+		});
+	}
+
+	/// Items of `node`, from its file if it's out of line and can be found.
+	fn module_items(&self, node: &ItemMod) -> Option<Vec<Item>> {
+		if let Some((_, items)) = &node.content {
+			return Some(items.clone());
+		}
+		if self.depth > 0 {
+			return None;
+		}
+		let file = self.module_file(node)?;
+		Some(syn::parse_file(&fs::read_to_string(file).ok()?).ok()?.items)
+	}
+
+	/// File of the top-level out-of-line module `node`.
+	fn module_file(&self, node: &ItemMod) -> Option<PathBuf> {
+		let dir = self.path.parent()?;
+		if let Some(target) = path_attr(&node.attrs) {
+			return Some(dir.join(target));
+		}
+		let child_dir = if path_modules::is_root(self.path) { dir.to_path_buf() } else { dir.join(self.path.file_stem()?) };
+		let name = node.ident.to_string();
+		[child_dir.join(format!("{name}.rs")), child_dir.join(&name).join("mod.rs")].into_iter().find(|file| file.is_file())
+	}
+
+	/// Fix dropping `attr` from `node` and importing `imports` above the module, under its `#[cfg]`s.
+	fn fix(&self, node: &ItemMod, attr: &Attribute, imports: &[String]) -> Option<Fix> {
+		let text = self.lines.text();
+		let start = self.lines.offset_of(attr.span().start())?;
+		let end = self.lines.offset_of(attr.span().end())?;
+		let (line_start, line_end) = (self.lines.line_start(start), self.lines.line_end(end));
+		let rest = &text[end..line_end];
+		// Alone on its line, the attribute's line goes; otherwise just it and the space after it
+		let removal = if text[line_start..start].trim().is_empty() && rest.trim().is_empty() {
+			Fix {
+				start_byte: line_start,
+				end_byte: (line_end + 1).min(text.len()),
+				replacement: String::new(),
+			}
+		} else {
+			Fix {
+				start_byte: start,
+				end_byte: end + rest.len() - rest.trim_start().len(),
+				replacement: String::new(),
+			}
+		};
+		let Some(import) = (match imports {
+			[] => None,
+			[name] => Some(name.clone()),
+			names => Some(format!("{{{}}}", names.join(", "))),
+		}) else {
+			return Some(removal);
+		};
+		// Above the doc comments and attributes, which would otherwise end up on the `use`
+		let item_start = find_item_text_start(self.lines, self.lines.offset_of(node.span().start())?);
+		let indent: String = text[item_start..].chars().take_while(|c| matches!(c, ' ' | '\t')).collect();
+		let mut replacement = String::new();
+		for cfg in node.attrs.iter().filter(|attr| attr.path().is_ident("cfg")) {
+			let cfg = &text[self.lines.offset_of(cfg.span().start())?..self.lines.offset_of(cfg.span().end())?];
+			replacement.push_str(&format!("{indent}{cfg}\n"));
+		}
+		replacement.push_str(&format!("{indent}use crate::{import};\n"));
+		let insertion = Fix {
+			start_byte: item_start,
+			end_byte: item_start,
+			replacement,
+		};
+		// The insertion first, as the removal can start where it does
+		combine(text, vec![insertion, removal])
+	}
+}
+
+impl<'a> Visit<'a> for MacroUseVisitor<'_> {
+	fn visit_item_mod(&mut self, node: &'a ItemMod) {
+		if let Some(attr) = node.attrs.iter().find(|attr| is_macro_use(attr)) {
+			let name = &node.ident;
+			let macros = self.module_items(node).map(|items| exported_macros(&items));
+			let span = self.lines.offset_of(node.span().start()).zip(self.lines.offset_of(node.span().end())).map(|(start, end)| start..end);
+			let fix = match (macros, span) {
+				(Some(Some(macros)), Some(span)) => self.fix(node, attr, &used_outside(&self.invocations, &macros, &span)),
+				_ => None,
+			};
+			let hint = if fix.is_some() {
+				"import its macros with `use crate::..;` where they're used, in other files too".to_string()
+			} else {
+				format!("export `{name}`'s macros with `#[macro_export]` and import them with `use crate::..;` where they're used")
+			};
+			self.push(attr, format!("`#[macro_use]` on `mod {name}`\nHINT: {hint}"), fix);
+		}
+		if node.content.is_some() {
+			self.depth += 1;
+			syn::visit::visit_item_mod(self, node);
+			self.depth -= 1;
+		}
+	}
+
+	fn visit_item_extern_crate(&mut self, node: &'a ItemExternCrate) {
+		if let Some(attr) = node.attrs.iter().find(|attr| is_macro_use(attr)) {
+			let name = &node.ident;
+			self.push(
+				attr,
+				format!("`#[macro_use]` on `extern crate {name}`\nHINT: import its macros with `use {name}::..;` where they're used"),
+				None,
+			);
+		}
+	}
+}
+
+/// Macro invocations by a plain name, which a `#[macro_use]` or an import brings into scope.
+struct Invocations<'a> {
+	lines: &'a LineIndex<'a>,
+	found: Vec<(String, usize)>,
+}
+
+impl<'a> Visit<'a> for Invocations<'_> {
+	fn visit_macro(&mut self, node: &'a Macro) {
+		if let (Some(ident), Some(at)) = (node.path.get_ident(), self.lines.offset_of(node.path.span().start())) {
+			self.found.push((ident.to_string(), at));
+		}
+		syn::visit::visit_macro(self, node);
+	}
+}
+
+fn is_macro_use(attr: &Attribute) -> bool {
+	attr.path().is_ident("macro_use")
+}
+
+/// Names of the `macro_rules!` among `items`, `None` if any of them isn't `#[macro_export]`ed.
+fn exported_macros(items: &[Item]) -> Option<Vec<String>> {
+	items
+		.iter()
+		.filter_map(|item| match item {
+			Item::Macro(item) if item.mac.path.is_ident("macro_rules") => Some(item),
+			_ => None,
+		})
+		.map(|item| {
+			let exported = item.attrs.iter().any(|attr| attr.path().is_ident("macro_export"));
+			exported.then(|| item.ident.as_ref().map(ToString::to_string)).flatten()
+		})
+		.collect()
+}
+
+/// Which of `macros` are invoked outside the module at `span`, sorted.
+fn used_outside(invocations: &[(String, usize)], macros: &[String], span: &Range<usize>) -> Vec<String> {
+	let mut used: Vec<String> = macros.iter().filter(|name| invocations.iter().any(|(invoked, at)| invoked == *name && !span.contains(at))).cloned().collect();
+	used.sort();
+	used
+}
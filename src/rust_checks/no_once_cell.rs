@@ -0,0 +1,232 @@
+//! Lint to disallow `once_cell`'s `Lazy` and `OnceCell` now that std has them.
+//!
+//! `once_cell::sync::{Lazy, OnceCell}` are `std::sync::{LazyLock, OnceLock}`, and `once_cell::unsync::{Lazy, OnceCell}`
+//! are `std::cell::{LazyCell, OnceCell}`. Imports of them and paths resolving to them are flagged, and the fix renames
+//! the segments as written: `use once_cell::sync::Lazy;` becomes `use std::sync::LazyLock;`, `Lazy<T>` becomes
+//! `LazyLock<T>` and `Lazy::new(..)` becomes `LazyLock::new(..)`; names imported under an alias stay.
+//!
+//! Renaming is all or nothing per file, as an import rewritten without its uses, or the other way around, doesn't
+//! compile: nothing is fixed if any import can't be (a glob, or something std doesn't have, like `OnceBox`), if anything
+//! is skipped, or if the file calls `get_or_try_init`, which std's `OnceLock` only has on nightly.
+
+use std::path::Path;
+
+use proc_macro2::Ident;
+use syn::{ItemUse, UseTree, spanned::Spanned, visit::Visit};
+
+use super::{
+	Fix, Severity, Violation,
+	custom_rules::Imports,
+	line_index::LineIndex,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
+
+const RULE: &str = "no-once-cell";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "no_once_cell",
+	autofix: true,
+	triggers: &["once_cell"],
+	description: "Disallow once_cell's Lazy and OnceCell, in favor of std's LazyLock and OnceLock",
+	rationale: "std has had `LazyLock` and `OnceLock` since 1.80; keeping `once_cell` for them is a dependency that does nothing std doesn't.",
+	bad: "use once_cell::sync::Lazy;\n\nstatic CONFIG: Lazy<Config> = Lazy::new(Config::load);",
+	good: "use std::sync::LazyLock;\n\nstatic CONFIG: LazyLock<Config> = LazyLock::new(Config::load);",
+	fix: "Renames the imports and paths to std's types, unless some can't be, as with globs or `get_or_try_init`, in which case nothing in the file is.",
+};
+
+pub struct NoOnceCell;
+impl Rule for NoOnceCell {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+	let imports = Imports::of(file);
+	let mut all = OnceCellVisitor::new(path, content, &imports);
+	all.visit_file(file);
+	let mut skip_visitor = SkipVisitor::for_rule(OnceCellVisitor::new(path, content, &imports), content, RULE);
+	skip_visitor.visit_file(file);
+	let mut violations = skip_visitor.inner.violations;
+
+	let renames_all = violations.len() == all.violations.len() && all.violations.iter().all(|v| v.fix.is_some()) && !content.contains("get_or_try_init");
+	if !renames_all {
+		for violation in &mut violations {
+			violation.fix = None;
+		}
+	}
+	violations
+}
+
+/// A rename of one identifier, at byte offsets into the source. Edits are made in source order.
+struct Edit {
+	start: usize,
+	end: usize,
+	to: &'static str,
+}
+
+struct OnceCellVisitor<'a> {
+	path_str: String,
+	lines: LineIndex<'a>,
+	imports: &'a Imports,
+	violations: Vec<Violation>,
+}
+
+impl<'a> OnceCellVisitor<'a> {
+	fn new(path: &Path, content: &'a str, imports: &'a Imports) -> Self {
+		Self {
+			path_str: path.display().to_string(),
+			lines: LineIndex::new(content),
+			imports,
+			violations: Vec::new(),
+		}
+	}
+
+	fn check_path(&mut self, node: &syn::Path) {
+		let resolved = self.imports.resolve(node);
+		if resolved.len() < 3 || resolved[0] != "once_cell" {
+			return;
+		}
+		let Some(std_name) = std_segment(&resolved, 2) else {
+			return;
+		};
+		// The written segments line up with the end of the resolved ones, the first standing for all it was imported as
+		let head_len = resolved.len() + 1 - node.segments.len();
+		let mut edits = Some(Vec::new());
+		for (i, segment) in node.segments.iter().enumerate() {
+			let index = head_len - 1 + i;
+			let aliased = i == 0 && head_len > 1 && segment.ident != resolved[index];
+			if index <= 2 && !aliased {
+				self.rename(&mut edits, &segment.ident, std_segment(&resolved, index));
+			}
+		}
+		// Like `OnceCell` imported from `once_cell::unsync`, which renaming the import alone moves to std
+		if edits.as_ref().is_some_and(Vec::is_empty) {
+			return;
+		}
+		let module = if resolved[1] == "sync" { "sync" } else { "cell" };
+		let message = format!("`once_cell::{}::{}` is in std\nHINT: use `std::{module}::{std_name}`", resolved[1], resolved[2]);
+		let fix = edits.and_then(|edits| self.fix(&edits));
+		self.report(node, message, fix);
+	}
+
+	fn check_use(&mut self, node: &ItemUse) {
+		let mut edits = Some(Vec::new());
+		if !self.use_edits(&node.tree, &mut Vec::new(), &mut edits) {
+			return;
+		}
+		let fix = edits.and_then(|edits| self.fix(&edits));
+		let message = "`once_cell` import of what std has\nHINT: import `LazyLock`/`OnceLock` from `std::sync`, or `LazyCell`/`OnceCell` from `std::cell`";
+		self.report(node, message.to_string(), fix);
+	}
+
+	/// Whether `tree` imports from `once_cell`, adding to `edits` the renames making it import from std instead.
+	fn use_edits(&self, tree: &UseTree, prefix: &mut Vec<String>, edits: &mut Option<Vec<Edit>>) -> bool {
+		let (ident, rest) = match tree {
+			UseTree::Path(path) => (&path.ident, Some(&*path.tree)),
+			UseTree::Name(name) => (&name.ident, None),
+			UseTree::Rename(rename) => (&rename.ident, None),
+			UseTree::Glob(_) => {
+				if !prefix.is_empty() {
+					*edits = None;
+				}
+				return !prefix.is_empty();
+			}
+			UseTree::Group(group) => return group.items.iter().fold(false, |imports, item| self.use_edits(item, prefix, edits) | imports),
+		};
+		if ident == "self" || (prefix.is_empty() && ident != "once_cell") {
+			return !prefix.is_empty();
+		}
+		prefix.push(ident.to_string());
+		// `use once_cell;` itself has no std counterpart
+		let to = if prefix.len() == 1 && rest.is_none() { None } else { std_segment(prefix, prefix.len() - 1) };
+		self.rename(edits, ident, to);
+		if let Some(rest) = rest {
+			self.use_edits(rest, prefix, edits);
+		}
+		prefix.pop();
+		true
+	}
+
+	/// Add to `edits` the rename of `ident` to `to`, or drop them all if std has nothing for it.
+	fn rename(&self, edits: &mut Option<Vec<Edit>>, ident: &Ident, to: Option<&'static str>) {
+		let Some(to) = to else {
+			*edits = None;
+			return;
+		};
+		if let Some(edits) = edits.as_mut()
+			&& ident != to
+			&& let (Some(start), Some(end)) = (self.lines.offset_of(ident.span().start()), self.lines.offset_of(ident.span().end()))
+		{
+			edits.push(Edit { start, end, to });
+		}
+	}
+
+	/// A single fix making `edits`, spanning from the first to the last.
+	fn fix(&self, edits: &[Edit]) -> Option<Fix> {
+		let start_byte = edits.first()?.start;
+		let end_byte = edits.last()?.end;
+		let text = self.lines.text();
+		let mut replacement = String::new();
+		let mut at = start_byte;
+		for edit in edits {
+			replacement.push_str(&text[at..edit.start]);
+			replacement.push_str(edit.to);
+			at = edit.end;
+		}
+		Some(Fix { start_byte, end_byte, replacement })
+	}
+
+	fn report(&mut self, node: &impl Spanned, message: String, fix: Option<Fix>) {
+		let start = node.span().start();
+		let end = node.span().end();
+		self.violations.push(Violation {
+			rule: RULE,
+			file: self.path_str.clone(),
+			line: start.line,
+			column: start.column,
+			end: Some((end.line, end.column)),
+			message,
+			severity: Severity::Error,
+			fix,
+			..Default::default()
+		});
+	}
+}
+
+impl<'a> Visit<'a> for OnceCellVisitor<'_> {
+	fn visit_item_use(&mut self, node: &'a ItemUse) {
+		self.check_use(node);
+	}
+
+	fn visit_path(&mut self, node: &'a syn::Path) {
+		self.check_path(node);
+		syn::visit::visit_path(self, node);
+	}
+}
+
+/// What the `index`th segment of the `once_cell` path `path` is called in std, if std has it.
+fn std_segment(path: &[String], index: usize) -> Option<&'static str> {
+	let module = path.get(1).map(String::as_str);
+	match index {
+		0 => Some("std"),
+		1 => match module? {
+			"sync" => Some("sync"),
+			"unsync" => Some("cell"),
+			_ => None,
+		},
+		2 => match (module?, path.get(2)?.as_str()) {
+			("sync", "Lazy") => Some("LazyLock"),
+			("sync", "OnceCell") => Some("OnceLock"),
+			("unsync", "Lazy") => Some("LazyCell"),
+			("unsync", "OnceCell") => Some("OnceCell"),
+			_ => None,
+		},
+		_ => None,
+	}
+}
@@ -0,0 +1,129 @@
+//! Lint to disallow async runtimes other than tokio.
+//!
+//! A future written against one runtime's reactor, timers or task-locals hangs or panics when polled on another's, and
+//! two runtimes in one binary each bring their own threads. Imports of `async_std`, `smol`, `async_executor` and
+//! `async_global_executor` are flagged, along with paths written from them, like `#[async_std::main]`. Crates listed in
+//! the `no_other_runtimes_crates` option are flagged too.
+//!
+//! Paths reached through an import aren't reported again: the import already is.
+
+use std::path::Path;
+
+use proc_macro2::Ident;
+use syn::{ItemExternCrate, ItemUse, UseTree, spanned::Spanned, visit::Visit};
+
+use super::{
+	Severity, Violation,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
+
+const RULE: &str = "no-other-runtimes";
+/// Runtimes flagged without any configuration, with the runtime they're part of.
+const RUNTIMES: &[(&str, &str)] = &[
+	("async_std", "async-std"),
+	("smol", "smol"),
+	("async_executor", "smol"),
+	("async_global_executor", "async-std"),
+];
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "no_other_runtimes",
+	autofix: false,
+	// Crates from `no_other_runtimes_crates` could be named anywhere
+	triggers: &[],
+	description: "Disallow async runtimes other than tokio, like async-std and smol",
+	rationale: "Futures tied to one runtime's reactor or timers hang or panic on another's; mixing runtimes also runs two sets of executor threads.",
+	bad: "#[async_std::main]\nasync fn main() {\n\tasync_std::task::sleep(Duration::from_secs(1)).await;\n}",
+	good: "#[tokio::main]\nasync fn main() {\n\ttokio::time::sleep(Duration::from_secs(1)).await;\n}",
+	fix: "Use tokio's counterpart. Further crates to flag go in `no_other_runtimes_crates`.",
+};
+
+pub struct NoOtherRuntimes;
+impl Rule for NoOtherRuntimes {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree, &ctx.opts.no_other_runtimes_crates)
+	}
+}
+
+/// Violations in `file`, where `extra` lists crates to flag besides the built-in runtimes.
+pub fn check(path: &Path, content: &str, file: &syn::File, extra: &[String]) -> Vec<Violation> {
+	let crates = RUNTIMES
+		.iter()
+		.map(|(name, runtime)| (name.to_string(), Some(*runtime)))
+		.chain(extra.iter().map(|name| (name.trim().replace('-', "_"), None)))
+		.collect();
+	let visitor = RuntimeVisitor {
+		path_str: path.display().to_string(),
+		crates,
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+struct RuntimeVisitor {
+	path_str: String,
+	/// Crate names as written in paths, with the runtime each belongs to, if it's a built-in one
+	crates: Vec<(String, Option<&'static str>)>,
+	violations: Vec<Violation>,
+}
+
+impl RuntimeVisitor {
+	/// Report `node` if `ident`, the first segment of a path, names a flagged crate.
+	fn check_root(&mut self, ident: &Ident, node: &impl Spanned) {
+		let Some((name, runtime)) = self.crates.iter().find(|(name, _)| ident == name) else {
+			return;
+		};
+		let of = runtime.filter(|runtime| runtime.replace('-', "_") != *name).map(|runtime| format!(", part of {runtime},")).unwrap_or_default();
+		let message = format!("`{name}`{of} is an async runtime other than tokio\nHINT: use tokio's counterpart; futures tied to one runtime hang or panic on another");
+		let start = node.span().start();
+		let end = node.span().end();
+		self.violations.push(Violation {
+			rule: RULE,
+			file: self.path_str.clone(),
+			line: start.line,
+			column: start.column,
+			end: Some((end.line, end.column)),
+			message,
+			severity: Severity::Error,
+			..Default::default()
+		});
+	}
+}
+
+impl<'a> Visit<'a> for RuntimeVisitor {
+	fn visit_item_use(&mut self, node: &'a ItemUse) {
+		for root in roots(&node.tree) {
+			self.check_root(root, node);
+		}
+	}
+
+	fn visit_item_extern_crate(&mut self, node: &'a ItemExternCrate) {
+		self.check_root(&node.ident, node);
+	}
+
+	fn visit_path(&mut self, node: &'a syn::Path) {
+		// A lone `smol` is a local binding; the crate itself can't be used as a value
+		if node.segments.len() > 1 {
+			self.check_root(&node.segments[0].ident, node);
+		}
+		syn::visit::visit_path(self, node);
+	}
+}
+
+/// The first segment of each path `tree` imports, like `a` and `b` for `use {a::x, b};`.
+fn roots(tree: &UseTree) -> Vec<&Ident> {
+	match tree {
+		UseTree::Path(path) => vec![&path.ident],
+		UseTree::Name(name) => vec![&name.ident],
+		UseTree::Rename(rename) => vec![&rename.ident],
+		UseTree::Glob(_) => Vec::new(),
+		UseTree::Group(group) => group.items.iter().flat_map(roots).collect(),
+	}
+}
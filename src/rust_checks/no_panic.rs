@@ -0,0 +1,105 @@
+//! Lint to disallow `panic!`, `todo!` and `unimplemented!` in library code.
+//!
+//! A library that panics takes the decision whether a failure is fatal away from its callers; it should return an error
+//! and let them decide. Only files of a library target are checked: the `src/` tree of a crate with a `src/lib.rs`,
+//! minus `src/main.rs` and `src/bin/`. Test code is exempt. Macros listed in the `no_panic_allow` option are allowed,
+//! e.g. `["todo"]` while a crate is being scaffolded.
+
+use std::path::{Component, Path};
+
+use syn::{Macro, visit::Visit};
+
+use super::{
+	Severity, Violation,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+	test_context::TestContext,
+};
+
+const RULE: &str = "no-panic";
+/// Macros the rule flags, unless allowed
+const PANICKING: &[&str] = &["panic", "todo", "unimplemented"];
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "no_panic",
+	autofix: false,
+	triggers: &["panic!", "todo!", "unimplemented!"],
+	description: "Disallow panic!, todo! and unimplemented! in library code",
+	rationale: "A library that panics decides for its callers that the failure is fatal; returning an error leaves that decision to them.",
+	bad: "pub fn parse(s: &str) -> Config {\n\tif s.is_empty() {\n\t\tpanic!(\"empty config\");\n\t}\n\ttodo!()\n}",
+	good: "pub fn parse(s: &str) -> Result<Config> {\n\tif s.is_empty() {\n\t\tbail!(\"empty config\");\n\t}\n\t...\n}",
+	fix: "Return an error instead. Macros listed in `no_panic_allow` are let through.",
+};
+
+pub struct NoPanic;
+impl Rule for NoPanic {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		if !is_library_file(&ctx.file.path) {
+			return Vec::new();
+		}
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree, ctx.tests, &ctx.opts.no_panic_allow)
+	}
+}
+
+/// Violations in `file`, which is assumed to be library code, see [`is_library_file`].
+pub fn check(path: &Path, content: &str, file: &syn::File, tests: &TestContext, allow: &[String]) -> Vec<Violation> {
+	let visitor = PanicVisitor {
+		path_str: path.display().to_string(),
+		tests,
+		allow,
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+/// Whether `path` belongs to a library target: it's in a `src/` directory next to a `lib.rs`, and isn't `src/main.rs`
+/// or under `src/bin/`.
+pub fn is_library_file(path: &Path) -> bool {
+	let Some(src) = path.ancestors().skip(1).find(|dir| dir.file_name().is_some_and(|name| name == "src")) else {
+		return false;
+	};
+	let Ok(relative) = path.strip_prefix(src) else {
+		return false;
+	};
+	let first = relative.components().next();
+	if first == Some(Component::Normal("main.rs".as_ref())) || (first == Some(Component::Normal("bin".as_ref())) && relative.components().count() > 1) {
+		return false;
+	}
+	relative == Path::new("lib.rs") || src.join("lib.rs").is_file()
+}
+
+struct PanicVisitor<'a> {
+	path_str: String,
+	tests: &'a TestContext,
+	allow: &'a [String],
+	violations: Vec<Violation>,
+}
+
+impl<'a> Visit<'a> for PanicVisitor<'a> {
+	fn visit_macro(&mut self, node: &'a Macro) {
+		let Some(name) = node.path.segments.last().map(|s| s.ident.to_string()) else {
+			return;
+		};
+		let start = node.path.segments[0].ident.span().start();
+		if PANICKING.contains(&name.as_str()) && !self.allow.contains(&name) && !self.tests.contains(start.line) {
+			let end = node.bang_token.spans[0].end();
+			self.violations.push(Violation {
+				rule: RULE,
+				file: self.path_str.clone(),
+				line: start.line,
+				column: start.column,
+				end: Some((end.line, end.column)),
+				message: format!("`{name}!` in library code\nHINT: return an error instead, so the caller decides whether it's fatal"),
+				severity: Severity::Error,
+				..Default::default()
+			});
+		}
+		syn::visit::visit_macro(self, node);
+	}
+}
@@ -8,9 +8,36 @@ use std::path::Path;
 use proc_macro2::Span;
 use syn::{Expr, ExprCall, ExprPath, spanned::Spanned, visit::Visit};
 
-use super::{Violation, skip::SkipVisitor};
+use super::{
+	Severity, Violation,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
 
 const RULE: &str = "no-tokio-spawn";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "no_tokio_spawn",
+	autofix: false,
+	triggers: &["spawn"],
+	description: "Disallow tokio::spawn",
+	rationale: "Detached tasks outlive their caller, swallow panics and make shutdown and cancellation hard to reason about (\"Go statement considered harmful\").",
+	bad: "tokio::spawn(async move { worker(rx).await });",
+	good: "let mut tasks = JoinSet::new();\ntasks.spawn(worker(rx));\nwhile let Some(res) = tasks.join_next().await { res?; }",
+	fix: "Restructure around `JoinSet`, `join!`/`select!` or another owner for the task. `spawn_blocking` is allowed.",
+};
+
+pub struct NoTokioSpawn;
+impl Rule for NoTokioSpawn {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
 const GO_STATEMENT_HARMFUL_URL: &str = "https://vorpus.org/blog/notes-on-structured-concurrency-or-go-statement-considered-harmful/";
 pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
 	let visitor = TokioSpawnVisitor::new(path);
@@ -38,11 +65,14 @@ impl TokioSpawnVisitor {
 			file: self.path_str.clone(),
 			line: span.start().line,
 			column: span.start().column,
+			end: Some((span.end().line, span.end().column)),
 			message: format!(
 				"Usage of `{variant}` is disallowed. Unstructured concurrency makes code harder to reason about. \
 				 See: {GO_STATEMENT_HARMFUL_URL}"
 			),
+			severity: Severity::Error,
 			fix: None, // No auto-fix - requires architectural changes
+			..Default::default()
 		});
 	}
 
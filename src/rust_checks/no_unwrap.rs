@@ -0,0 +1,121 @@
+//! Lint to require justification comments on `.unwrap()` and `.expect(..)`, the panicking counterpart of
+//! [`super::ignored_error_comment`].
+//!
+//! A panic outside tests takes the whole program down, so each one needs a comment saying why it can't happen, or why
+//! crashing is the right call. The comment marker defaults to `//SAFE` and is set by the `unwrap_marker` option.
+//! Test code is never checked: a failing unwrap is how a test fails.
+
+use std::path::Path;
+
+use syn::{ExprMethodCall, visit::Visit};
+
+use super::{
+	Fix, Severity, Suggestion, Violation,
+	line_index::LineIndex,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+	test_context::TestContext,
+};
+
+const RULE: &str = "no-unwrap";
+/// Comment marker justifying a panic, unless `unwrap_marker` says otherwise
+pub const DEFAULT_MARKER: &str = "SAFE";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "no_unwrap",
+	autofix: false,
+	triggers: &["unwrap(", "expect("],
+	description: "Require //SAFE on .unwrap() and .expect() outside tests",
+	rationale: "A panic takes the whole program down; a comment forces an explicit argument for why it can't happen, or why crashing is the right response.",
+	bad: "let port: u16 = env::var(\"PORT\").unwrap().parse().unwrap();",
+	good: "let port: u16 = env::var(\"PORT\")?.parse()?;\n//SAFE: the regex is a literal, checked by the tests\nlet re = Regex::new(r\"^\\d+$\").unwrap();",
+	fix: "Propagate the error with `?`, or add a `//SAFE` comment (or the configured `unwrap_marker`) saying why panicking is acceptable.",
+};
+
+pub struct NoUnwrap;
+impl Rule for NoUnwrap {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree, ctx.tests, &ctx.opts.unwrap_marker)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File, tests: &TestContext, marker: &str) -> Vec<Violation> {
+	let visitor = UnwrapVisitor {
+		path_str: path.display().to_string(),
+		content,
+		lines: LineIndex::new(content),
+		tests,
+		marker,
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+struct UnwrapVisitor<'a> {
+	path_str: String,
+	content: &'a str,
+	lines: LineIndex<'a>,
+	tests: &'a TestContext,
+	marker: &'a str,
+	violations: Vec<Violation>,
+}
+
+impl UnwrapVisitor<'_> {
+	/// `//<marker>` or `// <marker>` on `line` or the line above it.
+	fn is_justified(&self, line: usize) -> bool {
+		let tight = format!("//{}", self.marker);
+		let spaced = format!("// {}", self.marker);
+		(line.saturating_sub(1)..=line)
+			.filter_map(|l| self.content.lines().nth(l.checked_sub(1)?))
+			.any(|text| text.contains(&tight) || text.contains(&spaced))
+	}
+
+	/// A `//<marker>: ` comment above `line`, for the user to finish with the reason.
+	fn justify(&self, line: usize) -> Suggestion {
+		let line_start = self.lines.offset(line, 0).unwrap_or_default();
+		let text = &self.content[line_start..self.lines.line_end(line_start)];
+		let indent = &text[..text.len() - text.trim_start().len()];
+		Suggestion {
+			message: "explain why panicking is acceptable".to_string(),
+			fix: Fix {
+				start_byte: line_start,
+				end_byte: line_start,
+				replacement: format!("{indent}//{}: \n", self.marker),
+			},
+		}
+	}
+}
+
+impl<'a> Visit<'a> for UnwrapVisitor<'a> {
+	fn visit_expr_method_call(&mut self, node: &'a ExprMethodCall) {
+		// Receiver first, so chained calls are reported left to right
+		syn::visit::visit_expr_method_call(self, node);
+		let method = node.method.to_string();
+		let start = node.method.span().start();
+		let panics = (method == "unwrap" && node.args.is_empty()) || (method == "expect" && node.args.len() == 1);
+		if panics && !self.tests.contains(start.line) && !self.is_justified(start.line) {
+			let end = node.paren_token.span.close().end();
+			self.violations.push(Violation {
+				rule: RULE,
+				file: self.path_str.clone(),
+				line: start.line,
+				column: start.column,
+				end: Some((end.line, end.column)),
+				message: format!(
+					"`.{method}()` without `//{}` comment\n\
+					HINT: propagate the error, or explain why it can't happen or why panicking is the right response",
+					self.marker
+				),
+				severity: Severity::Error,
+				suggestions: vec![self.justify(start.line)],
+				..Default::default()
+			});
+		}
+	}
+}
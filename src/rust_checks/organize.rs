@@ -0,0 +1,346 @@
+//! Whole-file reorganizer behind `codestyle rust organize`.
+//!
+//! Format mode converges one fix at a time, and each fix only knows about its own rule. This instead lays out
+//! all top-level items in a single deterministic pass:
+//! 1. `mod` declarations
+//! 2. `extern crate`s and `use`s, grouped std / external / crate
+//! 3. macros and `extern` blocks, in their original order (`macro_rules!` must precede its uses)
+//! 4. items in `pub_first` order, each type directly followed by its joined inherent impl, then its trait impls
+//! 5. impls of types defined elsewhere, then inline modules
+//!
+//! Inner attributes and `//!` docs stay at the top. Comments between items travel with the item below them,
+//! unless they directly trail the item above (no blank line in between), like fold-closing markers.
+//! Files that can't be laid out safely (stray code between items, skip markers) are left untouched.
+
+use std::{collections::HashMap, fs, path::Path};
+
+use syn::{Item, UseTree, spanned::Spanned};
+
+use super::{
+	collect_rust_files,
+	exclude::{DiscoveryOptions, Exclude},
+	find_src_dirs,
+	join_split_impls::{find_impl_brace, strip_blank_lines},
+	line_index::LineIndex,
+	pub_first::{self, find_item_text_start},
+	skip::{self, has_skip_marker_for_rule},
+};
+
+/// Rules whose skip markers make a file off-limits, since moving items around is exactly what they opt out of.
+const RESPECTED_SKIPS: &[&str] = &["pub-first", "impl-follows-type", "join-split-impls"];
+/// Prefix of vim fold-closing markers (see `impl_folds`), which always trail the item above.
+const FOLD_CLOSE_PREFIX: &str = "//,}}}";
+
+/// Where an item goes in the laid out file. Consecutive items of a `tight` section aren't separated by blank lines.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Section {
+	Mod,
+	ExternCrate,
+	Use(u8),
+	Macro,
+	Const,
+	Item,
+}
+impl Section {
+	fn is_tight(self) -> bool {
+		matches!(self, Section::Mod | Section::ExternCrate | Section::Use(_) | Section::Const)
+	}
+}
+
+/// A top-level item's text, with the comments attached to it.
+struct Chunk<'a> {
+	item: &'a Item,
+	/// Start of the item's text, including doc comments and attributes
+	start: usize,
+	/// End of the item's last line
+	end: usize,
+	leading: String,
+	trailing: String,
+}
+impl Chunk<'_> {
+	fn body<'c>(&self, content: &'c str) -> &'c str {
+		&content[self.start..self.end]
+	}
+
+	fn render(&self, content: &str) -> String {
+		self.wrap(self.body(content))
+	}
+
+	/// Surround `body` with this chunk's comments.
+	fn wrap(&self, body: &str) -> String {
+		[self.leading.as_str(), body, self.trailing.as_str()].into_iter().filter(|s| !s.is_empty()).collect::<Vec<_>>().join("\n")
+	}
+}
+
+/// `discovery` configures which paths are skipped while looking for files.
+pub fn run_organize(target_dir: &Path, discovery: &DiscoveryOptions) -> i32 {
+	if !target_dir.exists() {
+		eprintln!("Target directory does not exist: {target_dir:?}");
+		return 1;
+	}
+	let exclude = match Exclude::new(target_dir, discovery) {
+		Ok(exclude) => exclude,
+		Err(e) => {
+			eprintln!("{e}");
+			return 1;
+		}
+	};
+
+	let src_dirs = find_src_dirs(target_dir);
+	if src_dirs.is_empty() {
+		eprintln!("No source directories found");
+		return 1;
+	}
+
+	let mut organized_count = 0;
+	for src_dir in src_dirs {
+		for info in collect_rust_files(&src_dir, &exclude) {
+			let Some(tree) = &info.syntax_tree else {
+				continue;
+			};
+			let Some(new_content) = organize(&info.contents, tree) else {
+				continue;
+			};
+			if new_content != info.contents && fs::write(&info.path, &*info.line_ending.apply(&new_content)).is_ok() {
+				organized_count += 1;
+			}
+		}
+	}
+
+	if organized_count == 0 {
+		println!("codestyle: all files already organized");
+	} else {
+		println!("codestyle: organized {organized_count} file(s)");
+	}
+	0
+}
+
+/// Lay out a file's top-level items. Returns `None` if the file can't be reorganized safely.
+pub fn organize(content: &str, file: &syn::File) -> Option<String> {
+	if file.items.is_empty() {
+		return Some(content.to_string());
+	}
+
+	let (regions, _) = skip::skip_regions(content);
+	if regions.iter().any(|region| RESPECTED_SKIPS.iter().any(|rule| region.skips(rule))) {
+		return None;
+	}
+
+	let lines = LineIndex::new(content);
+	let mut chunks = Vec::with_capacity(file.items.len());
+	for item in &file.items {
+		if RESPECTED_SKIPS.iter().any(|rule| has_skip_marker_for_rule(content, item.span(), rule)) {
+			return None;
+		}
+		let span_start = lines.offset_of(item.span().start())?;
+		let span_end = lines.offset_of(item.span().end())?;
+		chunks.push(Chunk {
+			item,
+			start: find_item_text_start(&lines, span_start),
+			end: lines.line_end(span_end),
+			leading: String::new(),
+			trailing: String::new(),
+		});
+	}
+	// Several items on one line can't be moved apart
+	if chunks.windows(2).any(|w| w[0].end >= w[1].start) {
+		return None;
+	}
+
+	let (preamble, first_leading) = split_preamble(&content[..chunks[0].start]);
+	chunks[0].leading = first_leading;
+	for i in 1..chunks.len() {
+		let (trailing, leading) = split_gap(&content[chunks[i - 1].end..chunks[i].start])?;
+		chunks[i - 1].trailing = trailing;
+		chunks[i].leading = leading;
+	}
+	let last = chunks.last_mut().expect("checked non-empty above");
+	let (trailing, epilogue) = split_gap(&content[last.end..])?;
+	last.trailing = trailing;
+
+	let laid_out = lay_out(content, &chunks)?;
+
+	let mut out = preamble.trim_end().to_string();
+	for (i, (section, text)) in laid_out.iter().enumerate() {
+		if i > 0 || !out.is_empty() {
+			let tight = i > 0 && section.is_tight() && laid_out[i - 1].0 == *section;
+			out.push_str(if tight { "\n" } else { "\n\n" });
+		}
+		out.push_str(text);
+	}
+	if !epilogue.is_empty() {
+		out.push_str("\n\n");
+		out.push_str(&epilogue);
+	}
+	out.push('\n');
+	Some(out)
+}
+
+/// Order chunks into sections, attaching impls to their types.
+fn lay_out(content: &str, chunks: &[Chunk]) -> Option<Vec<(Section, String)>> {
+	let types: HashMap<String, usize> = chunks
+		.iter()
+		.enumerate()
+		.filter_map(|(i, c)| match c.item {
+			Item::Struct(s) => Some((s.ident.to_string(), i)),
+			Item::Enum(e) => Some((e.ident.to_string(), i)),
+			Item::Union(u) => Some((u.ident.to_string(), i)),
+			_ => None,
+		})
+		.collect();
+
+	let mut mods = Vec::new();
+	let mut uses = Vec::new();
+	let mut macros = Vec::new();
+	let mut ranked = Vec::new();
+	let mut inherent_impls: HashMap<usize, Vec<&Chunk>> = HashMap::new();
+	let mut trait_impls: HashMap<usize, Vec<&Chunk>> = HashMap::new();
+	let mut foreign_impls = Vec::new();
+	let mut inline_mods = Vec::new();
+
+	for (idx, chunk) in chunks.iter().enumerate() {
+		match chunk.item {
+			Item::Mod(m) if m.content.is_none() => mods.push((Section::Mod, chunk.render(content))),
+			Item::Mod(_) => inline_mods.push((Section::Item, chunk.render(content))),
+			Item::ExternCrate(_) => uses.push((Section::ExternCrate, chunk.render(content))),
+			Item::Use(u) => uses.push((Section::Use(use_group(&u.tree, u.leading_colon.is_some())), chunk.render(content))),
+			Item::Macro(_) | Item::ForeignMod(_) => macros.push((Section::Macro, chunk.render(content))),
+			Item::Impl(imp) => {
+				let type_name = match &*imp.self_ty {
+					syn::Type::Path(type_path) => type_path.path.segments.last().map(|s| s.ident.to_string()),
+					_ => None,
+				};
+				match type_name.and_then(|name| types.get(&name)) {
+					Some(&type_idx) if imp.trait_.is_none() => inherent_impls.entry(type_idx).or_default().push(chunk),
+					Some(&type_idx) => trait_impls.entry(type_idx).or_default().push(chunk),
+					None => foreign_impls.push((Section::Item, chunk.render(content))),
+				}
+			}
+			item => {
+				let rank = pub_first::rank(item, content)?;
+				ranked.push((rank, idx));
+			}
+		}
+	}
+	// Stable, so items of equal rank keep their relative order
+	ranked.sort_by_key(|(rank, _)| *rank);
+	// `use` groups are ordered std < external < crate; `extern crate` goes before all of them
+	uses.sort_by_key(|(section, _)| match section {
+		Section::Use(group) => group + 1,
+		_ => 0,
+	});
+
+	let mut laid_out = mods;
+	laid_out.extend(uses);
+	laid_out.extend(macros);
+	for (rank, idx) in ranked {
+		let section = if rank == 0 { Section::Const } else { Section::Item };
+		laid_out.push((section, chunks[idx].render(content)));
+		if let Some(impls) = inherent_impls.get(&idx) {
+			laid_out.extend(join_impls(content, impls).into_iter().map(|text| (Section::Item, text)));
+		}
+		if let Some(impls) = trait_impls.get(&idx) {
+			laid_out.extend(impls.iter().map(|c| (Section::Item, c.render(content))));
+		}
+	}
+	laid_out.extend(foreign_impls);
+	laid_out.extend(inline_mods);
+
+	Some(laid_out)
+}
+
+/// Join inherent impls with the same signature into the first of them, the way `join_split_impls` does.
+/// Blocks carrying their own docs, attributes or comments are kept separate, so nothing attached to them is lost.
+fn join_impls(content: &str, impls: &[&Chunk]) -> Vec<String> {
+	let signature = |chunk: &Chunk| {
+		let Item::Impl(imp) = chunk.item else { unreachable!("only impls are passed in") };
+		let (generics, self_ty) = (&imp.generics, &imp.self_ty);
+		quote::quote!(#generics #self_ty).to_string()
+	};
+	let is_bare = |chunk: &Chunk| matches!(chunk.item, Item::Impl(imp) if imp.attrs.is_empty()) && chunk.leading.is_empty() && chunk.trailing.is_empty();
+
+	let mut joined: Vec<(String, &Chunk, Vec<String>)> = Vec::new();
+	let mut separate = Vec::new();
+	for &chunk in impls {
+		let body = chunk.body(content);
+		let (Some(open), Some(close)) = (find_impl_brace(body), body.rfind('}')) else {
+			separate.push(chunk.render(content));
+			continue;
+		};
+		let items = strip_blank_lines(&body[open + 1..close]);
+		let sig = signature(chunk);
+		match joined.iter_mut().find(|(s, ..)| *s == sig) {
+			Some((.., parts)) if is_bare(chunk) => parts.push(items),
+			Some(_) => separate.push(chunk.render(content)),
+			None => joined.push((sig, chunk, vec![items])),
+		}
+	}
+
+	let mut out: Vec<String> = joined
+		.into_iter()
+		.map(|(_, first, parts)| {
+			let body = first.body(content);
+			if parts.len() == 1 {
+				return first.render(content);
+			}
+			let header = &body[..find_impl_brace(body).expect("found above") + 1];
+			let items: Vec<String> = parts.into_iter().filter(|p| !p.is_empty()).collect();
+			first.wrap(&format!("{header}\n{}\n}}", items.join("\n")))
+		})
+		.collect();
+	out.extend(separate);
+	out
+}
+
+/// `std`/`core`/`alloc` (0), external crates (1), `crate`/`self`/`super` (2), matching rustfmt's `StdExternalCrate`.
+fn use_group(tree: &UseTree, has_leading_colon: bool) -> u8 {
+	let root = match tree {
+		UseTree::Path(p) => &p.ident,
+		UseTree::Name(n) => &n.ident,
+		UseTree::Rename(r) => &r.ident,
+		UseTree::Glob(_) | UseTree::Group(_) => return 1,
+	};
+	if has_leading_colon {
+		return 1;
+	}
+	match root.to_string().as_str() {
+		"std" | "core" | "alloc" => 0,
+		"crate" | "self" | "super" => 2,
+		_ => 1,
+	}
+}
+
+/// Split the text before the first item into the file header and comments leading the first item.
+fn split_preamble(preamble: &str) -> (String, String) {
+	let lines: Vec<&str> = preamble.lines().collect();
+	let tail_start = lines.iter().rposition(|l| l.trim().is_empty()).map_or(0, |i| i + 1);
+	let tail = &lines[tail_start..];
+	let is_item_comment = |l: &&str| {
+		let l = l.trim_start();
+		l.starts_with("//") && !l.starts_with("//!")
+	};
+	if tail.is_empty() || !tail.iter().all(is_item_comment) {
+		return (preamble.to_string(), String::new());
+	}
+	(lines[..tail_start].join("\n"), tail.join("\n"))
+}
+
+/// Split the text between two items into comments trailing the upper one and comments leading the lower one.
+/// Returns `None` if there's anything but comments and blank lines.
+fn split_gap(gap: &str) -> Option<(String, String)> {
+	let lines: Vec<&str> = gap.strip_prefix('\n').unwrap_or(gap).lines().collect();
+	if lines.iter().any(|l| {
+		let l = l.trim();
+		!l.is_empty() && !l.starts_with("//")
+	}) {
+		return None;
+	}
+
+	let split = match lines.iter().position(|l| l.trim().is_empty()) {
+		Some(blank) => blank,
+		None => lines.iter().take_while(|l| l.trim_start().starts_with(FOLD_CLOSE_PREFIX)).count(),
+	};
+	// Blank lines holding only indentation, like a file's last one, aren't comments to keep
+	let leading = strip_blank_lines(&lines[split..].join("\n"));
+	Some((lines[..split].join("\n"), if leading.trim().is_empty() { String::new() } else { leading }))
+}
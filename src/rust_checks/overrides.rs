@@ -0,0 +1,90 @@
+//! Per-path rule severities, from `[override."<glob>"]` config sections:
+//!
+//! ```toml
+//! [override."tests/**"]
+//! ignored_error_comment = "allow"
+//! ```
+//!
+//! They're applied when rules are dispatched for a file, over the options resolved for its crate.
+
+use std::{
+	collections::BTreeMap,
+	path::{Path, PathBuf},
+};
+
+use glob::{MatchOptions, Pattern};
+
+use super::Severity;
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+	case_sensitive: true,
+	require_literal_separator: true,
+	require_literal_leading_dot: false,
+};
+
+#[derive(Clone, Debug, Default)]
+pub struct Overrides {
+	/// Patterns match paths relative to this
+	root: PathBuf,
+	/// Most specific (longest) pattern last, so it's the one that applies when several match
+	rules: Vec<(Pattern, BTreeMap<String, Severity>)>,
+}
+impl Overrides {
+	/// Overrides from `(glob, option severities)` pairs, matched relative to `root`. Invalid globs are reported and skipped.
+	pub fn new(root: &Path, overrides: impl IntoIterator<Item = (String, BTreeMap<String, Severity>)>) -> Self {
+		let mut rules: Vec<_> = overrides
+			.into_iter()
+			.filter_map(|(glob, severities)| {
+				Pattern::new(&glob)
+					.inspect_err(|e| eprintln!("codestyle: ignoring [override.\"{glob}\"]: invalid pattern: {e}"))
+					.ok()
+					.map(|pattern| (pattern, severities))
+			})
+			.collect();
+		rules.sort_by_key(|(pattern, _)| pattern.as_str().len());
+		Self { root: root.to_path_buf(), rules }
+	}
+
+	/// These overrides, minus any for `option`.
+	pub fn without(&self, option: &str) -> Self {
+		let mut overrides = self.clone();
+		for (_, severities) in &mut overrides.rules {
+			severities.remove(option);
+		}
+		overrides
+	}
+
+	/// The severity `path` overrides `option` with, if any.
+	pub fn severity(&self, path: &Path, option: &str) -> Option<Severity> {
+		if self.rules.is_empty() {
+			return None;
+		}
+		let relative = [self.root.as_path(), Path::new(".")].into_iter().find_map(|base| path.strip_prefix(base).ok()).unwrap_or(path);
+		self.rules
+			.iter()
+			.rev()
+			.filter(|(pattern, _)| pattern.matches_path_with(relative, MATCH_OPTIONS))
+			.find_map(|(_, severities)| severities.get(option).copied())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn most_specific_match_wins() {
+		let overrides = Overrides::new(
+			Path::new("/repo"),
+			[
+				("tests/**".to_string(), BTreeMap::from([("loops".to_string(), Severity::Allow), ("pub_first".to_string(), Severity::Warn)])),
+				("tests/strict/**".to_string(), BTreeMap::from([("loops".to_string(), Severity::Error)])),
+			],
+		);
+		assert_eq!(overrides.severity(Path::new("/repo/tests/a.rs"), "loops"), Some(Severity::Allow));
+		assert_eq!(overrides.severity(Path::new("/repo/tests/strict/a.rs"), "loops"), Some(Severity::Error));
+		assert_eq!(overrides.severity(Path::new("/repo/tests/strict/a.rs"), "pub_first"), Some(Severity::Warn));
+		assert_eq!(overrides.severity(Path::new("/repo/src/a.rs"), "loops"), None);
+		assert_eq!(overrides.severity(Path::new("tests/a.rs"), "loops"), Some(Severity::Allow));
+	}
+}
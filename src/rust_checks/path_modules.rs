@@ -0,0 +1,115 @@
+//! Module files a directory walk misses: ones pulled in with `#[path = "..."]`, which can live anywhere,
+//! along with the modules those files declare in turn. And the other way around, the files a crate's module tree
+//! reaches at all, see [`reachable`].
+//!
+//! Resolution follows rustc's: a top-level `#[path]` is relative to the declaring file's directory, while plain
+//! `mod x;` (and anything inside inline modules) is relative to the directory that file owns, which is its own for
+//! `mod.rs`, `lib.rs`, `main.rs` and `#[path]`-loaded files, and `<dir>/<stem>/` otherwise.
+
+use std::{
+	collections::HashSet,
+	fs,
+	path::{Path, PathBuf},
+};
+
+use syn::{Expr, ExprLit, Item, Lit, Meta};
+
+use super::workspace::normalize;
+
+/// Module files reachable from `files` through `#[path]` attributes that don't live under `walked`.
+pub fn outside(files: &[PathBuf], walked: &Path) -> Vec<PathBuf> {
+	let walked = normalize(walked);
+	let mut found: Vec<PathBuf> = Vec::new();
+	// (file, whether it was loaded through `#[path]` from a walked file or one of ours)
+	let mut queue: Vec<(PathBuf, bool)> = files.iter().map(|f| (f.clone(), false)).collect();
+	while let Some((file, outside_walk)) = queue.pop() {
+		for (module, _) in declared_modules(&file, outside_walk || is_root(&file), outside_walk) {
+			let module = normalize(&module);
+			if module.starts_with(&walked) || found.contains(&module) || !module.is_file() {
+				continue;
+			}
+			found.push(module.clone());
+			queue.push((module, true));
+		}
+	}
+	found
+}
+
+/// Module files reachable from the crate roots `roots` through `mod` declarations, normalized, the roots included.
+pub fn reachable(roots: &[PathBuf]) -> HashSet<PathBuf> {
+	let mut found: HashSet<PathBuf> = roots.iter().map(|root| normalize(root)).collect();
+	// (file, whether it owns its directory, as crate roots and `#[path]` modules do)
+	let mut queue: Vec<(PathBuf, bool)> = roots.iter().map(|root| (normalize(root), true)).collect();
+	while let Some((file, owns_dir)) = queue.pop() {
+		for (module, via_path) in declared_modules(&file, owns_dir || is_root(&file), true) {
+			let module = normalize(&module);
+			if module.is_file() && found.insert(module.clone()) {
+				queue.push((module, via_path));
+			}
+		}
+	}
+	found
+}
+
+/// Files of the out-of-line modules `file` declares, each with whether a `#[path]` points at it: only those unless
+/// `follow_plain`, as for walked files, whose plain modules the walk finds anyway. A file that `owns_dir` has its plain
+/// modules next to it rather than in a directory named after it.
+fn declared_modules(file: &Path, owns_dir: bool, follow_plain: bool) -> Vec<(PathBuf, bool)> {
+	let Ok(contents) = fs::read_to_string(file) else {
+		return Vec::new();
+	};
+	if !follow_plain && !contents.contains("path") {
+		return Vec::new();
+	}
+	let Ok(tree) = syn::parse_file(&contents) else {
+		return Vec::new();
+	};
+
+	let dir = file.parent().unwrap_or(Path::new(""));
+	let child_dir = match file.file_stem() {
+		Some(stem) if !owns_dir => dir.join(stem),
+		_ => dir.to_path_buf(),
+	};
+	let mut modules = Vec::new();
+	collect(&tree.items, dir, &child_dir, follow_plain, &mut modules);
+	modules
+}
+
+/// Whether `file` has its plain modules next to it whatever loaded it: `mod.rs`, `lib.rs` and `main.rs`.
+pub(super) fn is_root(file: &Path) -> bool {
+	file.file_name().is_some_and(|name| name == "mod.rs" || name == "lib.rs" || name == "main.rs")
+}
+
+/// `attr_dir` is what a `#[path]` here is relative to, `child_dir` what a plain `mod x;` is.
+fn collect(items: &[Item], attr_dir: &Path, child_dir: &Path, follow_plain: bool, modules: &mut Vec<(PathBuf, bool)>) {
+	for item in items {
+		let Item::Mod(module) = item else {
+			continue;
+		};
+		let path = path_attr(&module.attrs);
+		match (&module.content, path) {
+			(None, Some(path)) => modules.push((attr_dir.join(path), true)),
+			(None, None) if follow_plain => {
+				let name = module.ident.to_string();
+				let flat = child_dir.join(format!("{name}.rs"));
+				modules.push((if flat.is_file() { flat } else { child_dir.join(name).join("mod.rs") }, false));
+			}
+			(None, None) => {}
+			(Some((_, items)), path) => {
+				let dir = child_dir.join(path.unwrap_or_else(|| module.ident.to_string()));
+				collect(items, &dir, &dir, follow_plain, modules);
+			}
+		}
+	}
+}
+
+/// The value of a `#[path = "..."]` attribute, if there is one.
+pub(super) fn path_attr(attrs: &[syn::Attribute]) -> Option<String> {
+	attrs.iter().find_map(|attr| match &attr.meta {
+		Meta::NameValue(nv) if nv.path.is_ident("path") => match &nv.value {
+			Expr::Lit(ExprLit { lit: Lit::Str(s), .. }) => Some(s.value()),
+			_ => None,
+		},
+		_ => None,
+	})
+}
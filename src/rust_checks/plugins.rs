@@ -0,0 +1,295 @@
+//! External rules, loaded at runtime from `[rust] plugins` so teams can add their own without forking codestyle.
+//!
+//! A plugin is a dynamic library (a path ending in the platform's extension: `.so`, `.dylib` or `.dll`) or any other
+//! command, a WASM module included through its runtime's CLI, e.g. `wasmtime run rules.wasm`. Either way it gets one
+//! JSON request per file:
+//! ```json
+//! {"path": "src/lib.rs", "content": "..."}
+//! ```
+//! answered by one line, with `severity` (`error` or `warn`, default `error`) and `fix` (byte offsets into `content`) optional:
+//! ```json
+//! {"violations": [{"rule": "acme-no-println", "line": 3, "column": 5, "message": "...", "severity": "warn", "fix": {"start_byte": 10, "end_byte": 18, "replacement": "log::info!"}}]}
+//! ```
+//! A library implements the [`Rule`] ABI with three C functions: `codestyle_plugin_abi_version() -> u32`, which has to
+//! return [`ABI_VERSION`]; `codestyle_plugin_check(request: *const c_char) -> *mut c_char`, taking the request and
+//! returning the response, both NUL-terminated; and `codestyle_plugin_free(response: *mut c_char)`, which codestyle
+//! hands each response back to once it's read. It's loaded once per run and `check` is never called concurrently.
+//!
+//! A command is started once per run and kept alive, and speaks the same JSON newline-delimited on stdin/stdout. It
+//! should exit once its stdin closes.
+//!
+//! A plugin that fails to load or answers garbage is reported and then skipped for the rest of the run, rather than
+//! failing it. Plugins are keyed by their command and [`fingerprint`], so one that changes on disk is loaded afresh.
+
+use std::{
+	collections::HashMap,
+	env,
+	ffi::{CStr, CString, c_char},
+	fs,
+	io::{BufRead, BufReader, Write},
+	path::{Path, PathBuf},
+	process::{ChildStdin, ChildStdout, Command, Stdio},
+	slice,
+	sync::{Arc, LazyLock, Mutex},
+	time::UNIX_EPOCH,
+};
+
+use serde::Deserialize;
+use serde_json::json;
+
+use super::{
+	Fix, Severity, Violation,
+	rules::{Context, Rule, RuleInfo, intern},
+};
+
+/// Version of the library ABI, which a library's `codestyle_plugin_abi_version` has to return
+pub const ABI_VERSION: u32 = 1;
+
+/// Plugins started so far, by command and fingerprint, so each runs once however many files and directories a run covers
+static LOADED: LazyLock<Mutex<HashMap<String, Arc<Plugin>>>> = LazyLock::new(Default::default);
+
+/// The plugins for `commands`, started on first use.
+pub fn load(commands: &[String]) -> Vec<Arc<Plugin>> {
+	let mut loaded = LOADED.lock().expect("plugin registry is never poisoned");
+	commands
+		.iter()
+		.map(|command| {
+			let key = format!("{command}\0{}", fingerprint(slice::from_ref(command)));
+			Arc::clone(loaded.entry(key).or_insert_with(|| Arc::new(Plugin::new(command))))
+		})
+		.collect()
+}
+
+/// Paths, sizes and modification times of the files `commands` run, the program found on `PATH` if need be, so that
+/// cached results and started plugins can tell a plugin changed under the same command.
+pub fn fingerprint(commands: &[String]) -> String {
+	commands
+		.iter()
+		.flat_map(|command| command.split_whitespace().enumerate())
+		.filter_map(|(i, part)| {
+			let path = if i == 0 { resolve(part)? } else { PathBuf::from(part) };
+			let meta = fs::metadata(&path).ok().filter(fs::Metadata::is_file)?;
+			let modified = meta.modified().ok()?.duration_since(UNIX_EPOCH).ok()?.as_nanos();
+			Some(format!("{}:{}:{modified}", path.display(), meta.len()))
+		})
+		.collect::<Vec<_>>()
+		.join("\0")
+}
+
+/// Where `program` is: as written if it's a path, otherwise the first match on `PATH`.
+fn resolve(program: &str) -> Option<PathBuf> {
+	if Path::new(program).components().count() > 1 {
+		return Some(PathBuf::from(program));
+	}
+	env::split_paths(&env::var_os("PATH")?).map(|dir| dir.join(program)).find(|path| path.is_file())
+}
+
+/// Whether `command` is a dynamic library to load rather than a program to run.
+fn is_library(command: &str) -> bool {
+	Path::new(command).extension().is_some_and(|ext| ext == env::consts::DLL_EXTENSION)
+}
+
+pub struct Plugin {
+	info: &'static RuleInfo,
+	command: String,
+	/// `None` once the plugin has failed, so it's reported only once
+	backend: Mutex<Option<Backend>>,
+}
+impl Plugin {
+	fn new(command: &str) -> Self {
+		let name = command
+			.split_whitespace()
+			.next()
+			.and_then(|program| Path::new(program).file_stem())
+			.map_or_else(|| command.to_string(), |s| s.to_string_lossy().into_owned());
+		let info = Box::leak(Box::new(RuleInfo {
+			id: intern(name),
+			option: "plugins",
+			autofix: true,
+			triggers: &[],
+			description: "Rules from an external plugin",
+			rationale: "",
+			bad: "",
+			good: "",
+			fix: "",
+		}));
+		let backend = if is_library(command) {
+			Library::load(command).map(Backend::Library)
+		} else {
+			Process::spawn(command).map(Backend::Process)
+		};
+		let backend = backend.inspect_err(|e| eprintln!("codestyle: plugin `{command}` failed to start: {e}")).ok();
+		Self {
+			info,
+			command: command.to_string(),
+			backend: Mutex::new(backend),
+		}
+	}
+}
+impl Rule for Plugin {
+	fn info(&self) -> &'static RuleInfo {
+		self.info
+	}
+
+	/// Violations come with the severity the plugin gave them, so callers shouldn't stamp one on.
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		let mut backend = self.backend.lock().expect("plugin backend lock is never poisoned");
+		let Some(running) = backend.as_mut() else {
+			return Vec::new();
+		};
+		let request = json!({ "path": ctx.file.path, "content": ctx.file.contents }).to_string();
+		match running.answer(&request).and_then(|response| parse_response(&response, &ctx.file.path)) {
+			Ok(violations) => violations,
+			Err(e) => {
+				eprintln!("codestyle: plugin `{}` failed on {:?}, skipping it from now on: {e}", self.command, ctx.file.path);
+				*backend = None;
+				Vec::new()
+			}
+		}
+	}
+}
+
+enum Backend {
+	Library(Library),
+	Process(Process),
+}
+impl Backend {
+	/// The plugin's response to the JSON `request`.
+	fn answer(&mut self, request: &str) -> Result<String, String> {
+		match self {
+			Self::Library(library) => library.answer(request),
+			Self::Process(process) => process.answer(request),
+		}
+	}
+}
+
+struct Library {
+	check: unsafe extern "C" fn(*const c_char) -> *mut c_char,
+	free: unsafe extern "C" fn(*mut c_char),
+	/// Keeps `check` and `free` loaded; declared last, so it's dropped after them
+	_library: libloading::Library,
+}
+impl Library {
+	fn load(path: &str) -> Result<Self, String> {
+		// SAFETY: loading runs the library's initializers, which is trusting it as much as running a plugin command does
+		let library = unsafe { libloading::Library::new(path) }.map_err(|e| e.to_string())?;
+		// SAFETY: the symbols are declared with the ABI's signatures, and the version check makes sure the library has it
+		let (check, free) = unsafe {
+			let abi_version = library.get::<unsafe extern "C" fn() -> u32>(b"codestyle_plugin_abi_version\0").map_err(|e| e.to_string())?;
+			let version = abi_version();
+			if version != ABI_VERSION {
+				return Err(format!("implements plugin ABI version {version}, expected {ABI_VERSION}"));
+			}
+			let check = *library.get::<unsafe extern "C" fn(*const c_char) -> *mut c_char>(b"codestyle_plugin_check\0").map_err(|e| e.to_string())?;
+			let free = *library.get::<unsafe extern "C" fn(*mut c_char)>(b"codestyle_plugin_free\0").map_err(|e| e.to_string())?;
+			(check, free)
+		};
+		Ok(Self { check, free, _library: library })
+	}
+
+	fn answer(&mut self, request: &str) -> Result<String, String> {
+		let request = CString::new(request).expect("JSON escapes NUL bytes");
+		// SAFETY: `request` is NUL-terminated and outlives the call, as the ABI asks
+		let response = unsafe { (self.check)(request.as_ptr()) };
+		if response.is_null() {
+			return Err("returned no response".to_string());
+		}
+		// SAFETY: a response is NUL-terminated and the plugin's until it's handed to `free`, which happens right after
+		let text = unsafe { CStr::from_ptr(response) }.to_string_lossy().into_owned();
+		// SAFETY: `response` came from this library's `check` and isn't used again
+		unsafe { (self.free)(response) };
+		Ok(text)
+	}
+}
+
+struct Process {
+	stdin: ChildStdin,
+	stdout: BufReader<ChildStdout>,
+}
+impl Process {
+	fn spawn(command: &str) -> Result<Self, String> {
+		let mut parts = command.split_whitespace();
+		let program = parts.next().ok_or("empty command")?;
+		let mut child = Command::new(program)
+			.args(parts)
+			.stdin(Stdio::piped())
+			.stdout(Stdio::piped())
+			.spawn()
+			.map_err(|e| e.to_string())?;
+		let stdin = child.stdin.take().expect("stdin is piped");
+		let stdout = BufReader::new(child.stdout.take().expect("stdout is piped"));
+		Ok(Self { stdin, stdout })
+	}
+
+	fn answer(&mut self, request: &str) -> Result<String, String> {
+		writeln!(self.stdin, "{request}")
+			.and_then(|()| self.stdin.flush())
+			.map_err(|e| format!("failed to send request: {e}"))?;
+		let mut line = String::new();
+		match self.stdout.read_line(&mut line) {
+			Ok(0) => return Err("exited without answering".to_string()),
+			Ok(_) => {}
+			Err(e) => return Err(format!("failed to read response: {e}")),
+		}
+		Ok(line)
+	}
+}
+
+/// The violations in a plugin's `response` for the file at `path`.
+fn parse_response(response: &str, path: &Path) -> Result<Vec<Violation>, String> {
+	let response: Response = serde_json::from_str(response).map_err(|e| format!("invalid response: {e}"))?;
+	if let Some(error) = response.error {
+		return Err(error);
+	}
+	response.violations.into_iter().map(|v| v.into_violation(path)).collect()
+}
+
+#[derive(Deserialize)]
+struct Response {
+	#[serde(default)]
+	violations: Vec<PluginViolation>,
+	error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PluginViolation {
+	rule: String,
+	line: usize,
+	column: usize,
+	message: String,
+	severity: Option<String>,
+	fix: Option<PluginFix>,
+}
+impl PluginViolation {
+	fn into_violation(self, path: &Path) -> Result<Violation, String> {
+		let severity = match self.severity.as_deref() {
+			None => Severity::Error,
+			Some(s) => s
+				.parse::<Severity>()
+				.ok()
+				.filter(|s| s.is_enabled())
+				.ok_or_else(|| format!("invalid severity `{s}`, expected `error` or `warn`"))?,
+		};
+		Ok(Violation {
+			rule: intern(self.rule),
+			file: path.display().to_string(),
+			line: self.line,
+			column: self.column,
+			message: self.message,
+			severity,
+			fix: self.fix.map(|f| Fix {
+				start_byte: f.start_byte,
+				end_byte: f.end_byte,
+				replacement: f.replacement,
+			}),
+			..Default::default()
+		})
+	}
+}
+
+#[derive(Deserialize)]
+struct PluginFix {
+	start_byte: usize,
+	end_byte: usize,
+	replacement: String,
+}
@@ -0,0 +1,98 @@
+//! rustc-style rendering of violations: a header, the offending source line with a caret underline (and any labelled
+//! lines with a dashed one), then notes and help.
+//!
+//! ```text
+//! error[loop-comment]: Endless loop without `//LOOP` comment
+//!  --> src/main.rs:2:5
+//!   |
+//! 2 |     loop {
+//!   |     ^^^^
+//!   |
+//!   = help: try to rewrite the loop with `while let` or justify why a bound can't be enforced
+//! ```
+
+use std::{collections::HashMap, fs};
+
+use super::{Severity, Violation};
+
+const TAB: &str = "    ";
+
+/// Render `violations`, reading each file once for its source lines. Unreadable files just lose the excerpt.
+pub fn render_all(violations: &[&Violation]) -> String {
+	let mut sources: HashMap<&str, Option<String>> = HashMap::new();
+	let mut out = String::new();
+	for v in violations {
+		let source = sources.entry(&v.file).or_insert_with(|| fs::read_to_string(&v.file).ok());
+		out.push_str(&render(v, source.as_deref()));
+		out.push('\n');
+	}
+	out
+}
+
+/// One diagnostic for `v`, with `source` being the contents of `v.file` if available.
+pub fn render(v: &Violation, source: Option<&str>) -> String {
+	let level = match v.severity {
+		Severity::Warn => "warning",
+		Severity::Error | Severity::Allow => "error",
+	};
+	let mut message_lines = v.message.lines();
+	let title = message_lines.next().unwrap_or_default();
+
+	// The primary position first, then labels, each `(line, column, end column, marker, label)`
+	let end_column = v.end.filter(|(line, _)| *line == v.line).map(|(_, column)| column);
+	let mut marks = vec![(v.line, v.column, end_column, '^', "")];
+	marks.extend(v.labels.iter().map(|l| (l.line, l.column, None, '-', l.message.as_str())));
+	marks.sort_by_key(|&(line, column, ..)| (line, column));
+	let gutter = " ".repeat(marks.iter().map(|m| m.0.to_string().len()).max().unwrap_or(1));
+
+	let mut out = format!("{level}[{}]: {title}\n{gutter}--> {}:{}:{}\n", v.rule, v.file, v.line, v.column + 1);
+	if let Some(source) = source {
+		let mut excerpt = String::new();
+		for (line, column, end_column, marker, label) in marks {
+			let Some(text) = source.lines().nth(line.saturating_sub(1)) else {
+				continue;
+			};
+			let (prefix, underline) = underline(text, column, end_column, marker);
+			let label = if label.is_empty() { String::new() } else { format!(" {label}") };
+			excerpt.push_str(&format!("{line:>width$} | {}\n{gutter} | {prefix}{underline}{label}\n", text.replace('\t', TAB), width = gutter.len()));
+		}
+		if !excerpt.is_empty() {
+			out.push_str(&format!("{gutter} |\n{excerpt}"));
+		}
+	}
+
+	let mut notes: Vec<String> = Vec::new();
+	for note in message_lines.map(str::trim).filter(|l| !l.is_empty()) {
+		match note.strip_prefix("HINT:") {
+			Some(help) => notes.push(format!("help: {}", help.trim_start())),
+			None => notes.push(format!("note: {note}")),
+		}
+	}
+	notes.extend(v.notes.iter().map(|note| format!("note: {note}")));
+	for suggestion in &v.suggestions {
+		let replacement = &suggestion.fix.replacement;
+		if replacement.is_empty() || replacement.contains('\n') {
+			notes.push(format!("help: {}", suggestion.message));
+		} else {
+			notes.push(format!("help: {}: `{replacement}`", suggestion.message));
+		}
+	}
+	if !notes.is_empty() {
+		out.push_str(&format!("{gutter} |\n"));
+	}
+	for note in notes {
+		out.push_str(&format!("{gutter} = {note}\n"));
+	}
+	out
+}
+
+/// Padding up to `column` (a char offset) and `marker`s up to `end_column`, or under the word starting at `column`
+/// without one, with tabs expanded like the excerpt.
+fn underline(line: &str, column: usize, end_column: Option<usize>, marker: char) -> (String, String) {
+	let prefix: String = line.chars().take(column).map(|c| if c == '\t' { TAB.to_string() } else { " ".to_string() }).collect();
+	let len = match end_column {
+		Some(end) => end.saturating_sub(column),
+		None => line.chars().skip(column).take_while(|c| c.is_alphanumeric() || *c == '_').count(),
+	};
+	(prefix, marker.to_string().repeat(len.max(1)))
+}
@@ -0,0 +1,69 @@
+//! A progress bar on stderr for long `assert` runs: files checked so far and violations found, redrawn in place.
+//!
+//! Runs that finish quickly never draw it, so it only shows up on large trees; it's cleared once the run is done,
+//! leaving the report as it would be without it.
+
+use std::{
+	io::{IsTerminal, Write, stderr},
+	time::{Duration, Instant},
+};
+
+/// How long a run goes before the bar first appears, and how often it's redrawn after that
+const REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+const BAR_WIDTH: usize = 30;
+
+pub struct Progress {
+	enabled: bool,
+	total: usize,
+	done: usize,
+	violations: usize,
+	last_draw: Instant,
+	drawn: bool,
+}
+impl Progress {
+	/// A bar for `total` files, shown only if `enabled` and stderr is a terminal.
+	pub fn new(total: usize, enabled: bool) -> Self {
+		Self {
+			enabled: enabled && stderr().is_terminal(),
+			total,
+			done: 0,
+			violations: 0,
+			last_draw: Instant::now(),
+			drawn: false,
+		}
+	}
+
+	/// Record one more file checked, which had `violations` violations.
+	pub fn tick(&mut self, violations: usize) {
+		self.done += 1;
+		self.violations += violations;
+		if self.enabled && self.last_draw.elapsed() >= REDRAW_INTERVAL {
+			self.draw();
+		}
+	}
+
+	/// Clear the bar, if it was ever drawn.
+	pub fn finish(self) {
+		if self.drawn {
+			eprint!("\r\x1b[2K");
+		}
+	}
+
+	fn draw(&mut self) {
+		let filled = (self.done * BAR_WIDTH).checked_div(self.total).unwrap_or(BAR_WIDTH).min(BAR_WIDTH);
+		let mut err = stderr().lock();
+		//IGNORED_ERROR: a broken stderr only loses the progress bar
+		let _ = write!(
+			err,
+			"\r\x1b[2K[{}{}] {}/{} files, {} violation(s)",
+			"=".repeat(filled),
+			" ".repeat(BAR_WIDTH - filled),
+			self.done,
+			self.total,
+			self.violations
+		)
+		.and_then(|()| err.flush());
+		self.last_draw = Instant::now();
+		self.drawn = true;
+	}
+}
@@ -0,0 +1,173 @@
+//! Lint to order struct fields like `pub_first` orders items: `pub` fields first, then the rest.
+//!
+//! A struct's definition reads as its interface when the fields a user can touch come first. Only named fields are
+//! ordered, as reordering a tuple struct's renumbers them, and structs whose field order means something are left
+//! alone: `#[repr(..)]` ones, whose layout follows it, and ones deriving `PartialOrd` or `Ord`, which compare in it.
+//!
+//! The fix moves each field with the doc comments, attributes and comment lines above it and anything after it on its
+//! line, keeping the order within each group. It needs every field on lines of its own, and isn't offered for structs
+//! deriving serde's traits, since formats without field names serialize in declaration order.
+
+use std::path::Path;
+
+use syn::{Attribute, Fields, ItemStruct, Meta, Token, Visibility, punctuated::Punctuated, spanned::Spanned, visit::Visit};
+
+use super::{
+	Fix, Severity, Violation,
+	line_index::LineIndex,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
+
+const RULE: &str = "pub-fields-first";
+/// Derives that make the field order part of the struct's behavior, barring a reordering fix
+const ORDERED_DERIVES: &[&str] = &["PartialOrd", "Ord"];
+/// Derives whose output can depend on the field order, barring the fix but not the report
+const SERIALIZED_DERIVES: &[&str] = &["Serialize", "Deserialize"];
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "pub_fields_first",
+	autofix: true,
+	triggers: &["pub"],
+	description: "Order struct fields: pub before private",
+	rationale: "A struct reads as its interface when the fields users can touch come before its internals, as `pub_first` does for items.",
+	bad: "pub struct Client {\n\tpool: Pool,\n\tpub timeout: Duration,\n}",
+	good: "pub struct Client {\n\tpub timeout: Duration,\n\tpool: Pool,\n}",
+	fix: "Moves the `pub` fields above the private ones, with their doc comments and attributes, unless the struct derives serde's traits.",
+};
+
+pub struct PubFieldsFirst;
+impl Rule for PubFieldsFirst {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+	let visitor = FieldsVisitor {
+		path_str: path.display().to_string(),
+		lines: LineIndex::new(content),
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+struct FieldsVisitor<'a> {
+	path_str: String,
+	lines: LineIndex<'a>,
+	violations: Vec<Violation>,
+}
+
+impl FieldsVisitor<'_> {
+	fn check_struct(&mut self, node: &ItemStruct) {
+		let Fields::Named(fields) = &node.fields else {
+			return;
+		};
+		let derives = derived(&node.attrs);
+		if node.attrs.iter().any(|attr| attr.path().is_ident("repr")) || derives.iter().any(|d| ORDERED_DERIVES.contains(&d.as_str())) {
+			return;
+		}
+		let is_pub: Vec<bool> = fields.named.iter().map(|field| matches!(field.vis, Visibility::Public(_))).collect();
+		let Some(first_private) = is_pub.iter().position(|p| !p) else {
+			return;
+		};
+		let Some(misplaced) = fields.named.iter().skip(first_private).find(|field| matches!(field.vis, Visibility::Public(_))) else {
+			return;
+		};
+		let fix = if derives.iter().any(|d| SERIALIZED_DERIVES.contains(&d.as_str())) {
+			None
+		} else {
+			self.reorder(fields, &is_pub)
+		};
+		let name = misplaced.ident.as_ref().map(ToString::to_string).unwrap_or_default();
+		let start = misplaced.span().start();
+		let end = misplaced.span().end();
+		self.violations.push(Violation {
+			rule: RULE,
+			file: self.path_str.clone(),
+			line: start.line,
+			column: start.column,
+			end: Some((end.line, end.column)),
+			message: format!("`pub` field `{name}` of `{}` comes after private ones\nHINT: move the `pub` fields above the private ones", node.ident),
+			severity: Severity::Error,
+			fix,
+			..Default::default()
+		});
+	}
+
+	/// Fix moving the fields whose `is_pub` is set above the others, each with the lines it spans.
+	fn reorder(&self, fields: &syn::FieldsNamed, is_pub: &[bool]) -> Option<Fix> {
+		let text = self.lines.text();
+		let open = self.lines.offset_of(fields.brace_token.span.open().end())?;
+		let close = self.lines.offset_of(fields.brace_token.span.close().start())?;
+		let start_byte = comments_start(&self.lines, self.lines.line_start(self.lines.offset_of(fields.named.first()?.span().start())?));
+		// Each field runs from the end of the one before, or the comment lines above the first, to the end of its line
+		let mut chunks = Vec::new();
+		let mut at = start_byte;
+		for pair in fields.named.pairs() {
+			let field_start = self.lines.offset_of(pair.value().span().start())?;
+			let field_end = self.lines.offset_of(pair.value().span().end())?;
+			let last = match pair.punct() {
+				Some(comma) => self.lines.offset_of(comma.span.end())?,
+				None => field_end,
+			};
+			let end = self.lines.line_end(last) + 1;
+			// Nothing but the field may be on its lines
+			if at <= self.lines.line_end(open) || at > self.lines.line_start(field_start) || end > self.lines.line_start(close) || !only_comments(&text[at..field_start]) {
+				return None;
+			}
+			chunks.push(match pair.punct() {
+				Some(_) => text[at..end].to_string(),
+				None => format!("{},{}", &text[at..field_end], &text[field_end..end]),
+			});
+			at = end;
+		}
+		let (public, private): (Vec<_>, Vec<_>) = chunks.into_iter().zip(is_pub).partition(|(_, is_pub)| **is_pub);
+		let replacement = public.into_iter().chain(private).map(|(chunk, _)| chunk).collect();
+		Some(Fix { start_byte, end_byte: at, replacement })
+	}
+}
+
+impl<'a> Visit<'a> for FieldsVisitor<'_> {
+	fn visit_item_struct(&mut self, node: &'a ItemStruct) {
+		self.check_struct(node);
+		syn::visit::visit_item_struct(self, node);
+	}
+}
+
+/// Start of the `//` comment lines right above the line starting at `line_start`, or of that line if there are none.
+pub(super) fn comments_start(lines: &LineIndex, line_start: usize) -> usize {
+	let mut start = line_start;
+	while start > 0 {
+		let prev = lines.line_start(start - 1);
+		if !lines.text()[prev..start].trim_start().starts_with("//") {
+			break;
+		}
+		start = prev;
+	}
+	start
+}
+
+/// Whether `text` is nothing but whitespace and `//` comment lines.
+pub(super) fn only_comments(text: &str) -> bool {
+	text.lines().all(|line| line.trim().is_empty() || line.trim_start().starts_with("//"))
+}
+
+/// Last segments of the paths `attrs` derive.
+fn derived(attrs: &[Attribute]) -> Vec<String> {
+	attrs
+		.iter()
+		.filter_map(|attr| match &attr.meta {
+			Meta::List(list) if list.path.is_ident("derive") => list.parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated).ok(),
+			_ => None,
+		})
+		.flatten()
+		.filter_map(|path| Some(path.segments.last()?.ident.to_string()))
+		.collect()
+}
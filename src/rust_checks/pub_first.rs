@@ -8,11 +8,49 @@ use std::path::Path;
 
 use syn::{Item, Visibility, spanned::Spanned};
 
-use super::{Fix, Violation, skip::has_skip_marker_for_rule};
+use super::{
+	Fix, Severity, Violation,
+	line_index::LineIndex,
+	rules::{Context, Rule, RuleInfo},
+	rustfmt::RustfmtConfig,
+	skip::has_skip_marker_for_rule,
+};
 
 const RULE: &str = "pub-first";
-pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "pub_first",
+	autofix: true,
+	triggers: &[],
+	description: "Order items: consts, types, then public before private",
+	rationale: "A file reads top-down as its interface: constants and types first, then the public API, then the private helpers it's built from.",
+	bad: "fn helper() {}\npub fn api() { helper() }",
+	good: "pub fn api() { helper() }\nfn helper() {}",
+	fix: "Moves the first misordered item above the items it should precede; `format` repeats this until the file is ordered.",
+};
+
+pub struct PubFirst;
+impl Rule for PubFirst {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree, ctx.rustfmt)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File, rustfmt: &RustfmtConfig) -> Vec<Violation> {
+	let mut violations = find_misordered(path, &LineIndex::new(content), file);
+	for fix in violations.iter_mut().filter_map(|v| v.fix.as_mut()) {
+		fix.replacement = rustfmt.conform(&fix.replacement);
+	}
+	violations
+}
+
+fn find_misordered(path: &Path, lines: &LineIndex, file: &syn::File) -> Vec<Violation> {
 	let path_str = path.display().to_string();
+	let content = lines.text();
 
 	// Collect byte ranges of mod/use/extern-crate items so the fix can avoid displacing
 	// them when reordering. These conventionally live at the top of the file.
@@ -21,10 +59,10 @@ pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
 		.iter()
 		.filter(|item| matches!(item, Item::Mod(_) | Item::Use(_) | Item::ExternCrate(_)))
 		.filter_map(|item| {
-			let start_byte = span_position_to_byte(content, item.span().start().line, item.span().start().column)?;
-			let end_byte = span_position_to_byte(content, item.span().end().line, item.span().end().column)?;
-			let text_start = find_item_text_start(content, start_byte);
-			let text_end = find_line_end(content, end_byte);
+			let start_byte = lines.offset_of(item.span().start())?;
+			let end_byte = lines.offset_of(item.span().end())?;
+			let text_start = find_item_text_start(lines, start_byte);
+			let text_end = lines.line_end(end_byte);
 			Some((text_start, text_end))
 		})
 		.collect();
@@ -43,12 +81,12 @@ pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
 			let span_end_line = item.span().end().line;
 			let span_end_col = item.span().end().column;
 
-			let span_start_byte = span_position_to_byte(content, span_start_line, span_start_col)?;
-			let span_end_byte = span_position_to_byte(content, span_end_line, span_end_col)?;
+			let span_start_byte = lines.offset(span_start_line, span_start_col)?;
+			let span_end_byte = lines.offset(span_end_line, span_end_col)?;
 
 			// Find the actual start including doc comments by looking backwards
-			let text_start = find_item_text_start(content, span_start_byte);
-			let text_end = find_line_end(content, span_end_byte);
+			let text_start = find_item_text_start(lines, span_start_byte);
+			let text_end = lines.line_end(span_end_byte);
 
 			Some(ItemInfo {
 				is_pub,
@@ -79,14 +117,16 @@ pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
 		if item.is_const
 			&& let Some(target_idx) = first_non_const_idx
 		{
-			let fix = create_move_fix(content, &items, &anchor_ranges, i, target_idx);
+			let fix = create_move_fix(lines, &items, &anchor_ranges, i, target_idx);
 			return vec![Violation {
 				rule: RULE,
 				file: path_str,
 				line: item.start_line,
 				column: 0,
 				message: "`const` should come before all other items".to_string(),
+				severity: Severity::Error,
 				fix,
+				..Default::default()
 			}];
 		}
 	}
@@ -100,14 +140,16 @@ pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
 		if item.is_type
 			&& let Some(target_idx) = first_non_const_non_type_idx
 		{
-			let fix = create_move_fix(content, &items, &anchor_ranges, i, target_idx);
+			let fix = create_move_fix(lines, &items, &anchor_ranges, i, target_idx);
 			return vec![Violation {
 				rule: RULE,
 				file: path_str,
 				line: item.start_line,
 				column: 0,
 				message: "`type` should come before all other items (after const)".to_string(),
+				severity: Severity::Error,
 				fix,
+				..Default::default()
 			}];
 		}
 	}
@@ -126,14 +168,16 @@ pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
 		if item.is_pub
 			&& let Some(target_idx) = first_private_idx
 		{
-			let fix = create_move_fix(content, &items, &anchor_ranges, i, target_idx);
+			let fix = create_move_fix(lines, &items, &anchor_ranges, i, target_idx);
 			return vec![Violation {
 				rule: RULE,
 				file: path_str,
 				line: item.start_line,
 				column: 0,
 				message: "public item should come before private items".to_string(),
+				severity: Severity::Error,
 				fix,
+				..Default::default()
 			}];
 		}
 	}
@@ -172,7 +216,7 @@ pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
 				"`trait` should be at the top of its visibility category (after main)",
 			),
 		] {
-			if let Some(v) = check_kind_ordering(&items, &anchor_ranges, content, &path_str, is_pub, is_target, is_higher_priority, message) {
+			if let Some(v) = check_kind_ordering(&items, &anchor_ranges, lines, &path_str, is_pub, is_target, is_higher_priority, message) {
 				return vec![v];
 			}
 		}
@@ -187,7 +231,7 @@ pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
 fn check_kind_ordering(
 	items: &[ItemInfo],
 	anchor_ranges: &[(usize, usize)],
-	content: &str,
+	lines: &LineIndex,
 	path_str: &str,
 	is_pub: bool,
 	is_target: fn(&ItemInfo) -> bool,
@@ -203,14 +247,16 @@ fn check_kind_ordering(
 			if is_target(item)
 				&& let Some(target_idx) = first_lower_idx
 			{
-				let fix = create_move_fix(content, items, anchor_ranges, i, target_idx);
+				let fix = create_move_fix(lines, items, anchor_ranges, i, target_idx);
 				return Some(Violation {
 					rule: RULE,
 					file: path_str.to_string(),
 					line: item.start_line,
 					column: 0,
 					message: message.to_string(),
+					severity: Severity::Error,
 					fix,
+					..Default::default()
 				});
 			}
 		}
@@ -279,6 +325,20 @@ fn get_item_visibility_and_main(item: &Item, content: &str) -> Option<(bool, boo
 	Some((is_pub, is_main_fn, is_const, is_type, is_trait, is_parser, is_subcommand, is_args))
 }
 
+/// Position of `item` in the full ordering this rule enforces, or `None` if the rule doesn't place it.
+pub(super) fn rank(item: &Item, content: &str) -> Option<usize> {
+	let (is_pub, is_main_fn, is_const, is_type, is_trait, is_parser, is_subcommand, is_args) = get_item_visibility_and_main(item, content)?;
+	if is_const {
+		return Some(0);
+	}
+	if is_type {
+		return Some(1);
+	}
+	// Parser > Subcommand > Args > main > trait > other, pub before private
+	let kind = [is_parser, is_subcommand, is_args, is_main_fn, is_trait].iter().position(|&k| k).unwrap_or(5);
+	Some(2 + if is_pub { 0 } else { 6 } + kind)
+}
+
 fn has_clap_derive(attrs: &[syn::Attribute], trait_name: &str) -> bool {
 	attrs.iter().any(|attr| {
 		if !attr.path().is_ident("derive") {
@@ -298,10 +358,11 @@ fn has_clap_derive(attrs: &[syn::Attribute], trait_name: &str) -> bool {
 ///
 /// Anchor items (mod/use/extern crate) in the gap between from and to are kept before the
 /// reordered code items — the moved item is placed after all anchors.
-fn create_move_fix(content: &str, items: &[ItemInfo], anchor_ranges: &[(usize, usize)], from_idx: usize, to_idx: usize) -> Option<Fix> {
+fn create_move_fix(lines: &LineIndex, items: &[ItemInfo], anchor_ranges: &[(usize, usize)], from_idx: usize, to_idx: usize) -> Option<Fix> {
 	if from_idx <= to_idx {
 		return None;
 	}
+	let content = lines.text();
 
 	let from_item = &items[from_idx];
 	let to_item = &items[to_idx];
@@ -355,7 +416,7 @@ fn create_move_fix(content: &str, items: &[ItemInfo], anchor_ranges: &[(usize, u
 			code_text.push_str(&content[pos..*anchor_start]);
 		}
 		// The anchor itself (including its line)
-		let anchor_line_end = find_line_end(content, *anchor_end);
+		let anchor_line_end = lines.line_end(*anchor_end);
 		let anchor_chunk_end = if anchor_line_end < content.len() && content.as_bytes()[anchor_line_end] == b'\n' {
 			anchor_line_end + 1
 		} else {
@@ -385,8 +446,9 @@ fn create_move_fix(content: &str, items: &[ItemInfo], anchor_ranges: &[(usize, u
 
 /// Find the start of an item's text, including preceding doc comments and attributes.
 /// We look backwards from the span start to find consecutive comment/attribute lines.
-fn find_item_text_start(content: &str, span_start: usize) -> usize {
-	let line_start = find_line_start(content, span_start);
+pub(super) fn find_item_text_start(lines: &LineIndex, span_start: usize) -> usize {
+	let content = lines.text();
+	let line_start = lines.line_start(span_start);
 
 	// Look backwards line by line to find doc comments or blank lines that should be included
 	let mut current_start = line_start;
@@ -398,7 +460,7 @@ fn find_item_text_start(content: &str, span_start: usize) -> usize {
 
 		// Find the previous line
 		let prev_line_end = current_start - 1; // Position of the \n
-		let prev_line_start = find_line_start(content, prev_line_end.saturating_sub(1));
+		let prev_line_start = lines.line_start(prev_line_end.saturating_sub(1));
 		let prev_line = content[prev_line_start..prev_line_end].trim_start();
 
 		// Check if previous line is a doc comment (///) or attribute (#[)
@@ -413,36 +475,3 @@ fn find_item_text_start(content: &str, span_start: usize) -> usize {
 
 	current_start
 }
-
-/// Convert a line/column position to byte offset in content.
-/// Lines are 1-indexed, columns are 0-indexed (byte offset within line).
-fn span_position_to_byte(content: &str, line: usize, column: usize) -> Option<usize> {
-	let mut current_line = 1;
-	let mut line_start = 0;
-
-	for (i, ch) in content.char_indices() {
-		if current_line == line {
-			return Some(line_start + column);
-		}
-		if ch == '\n' {
-			current_line += 1;
-			line_start = i + 1;
-		}
-	}
-
-	if current_line == line {
-		return Some(line_start + column);
-	}
-
-	None
-}
-
-/// Find the byte position of the start of the line containing `pos`
-fn find_line_start(content: &str, pos: usize) -> usize {
-	content[..pos].rfind('\n').map(|i| i + 1).unwrap_or(0)
-}
-
-/// Find the byte position of the end of the line containing `pos` (the newline char position)
-fn find_line_end(content: &str, pos: usize) -> usize {
-	content[pos..].find('\n').map(|i| pos + i).unwrap_or(content.len())
-}
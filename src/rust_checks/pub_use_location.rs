@@ -0,0 +1,106 @@
+//! Lint to keep `pub use` re-exports in the places readers look for a crate's public surface.
+//!
+//! A `pub use` is allowed at the top level of a `lib.rs` or `mod.rs`, and anywhere in a module named in the
+//! `pub_use_modules` option, `prelude` unless configured otherwise: an inline `mod prelude { .. }`, or a `prelude.rs`
+//! file. Everywhere else a re-export moves an item's public path away from where it's declared without the roots
+//! saying so. `pub(crate)` and other restricted re-exports aren't part of the public surface and are left alone.
+
+use std::path::Path;
+
+use syn::{ItemMod, ItemUse, Visibility, spanned::Spanned, visit::Visit};
+
+use super::{
+	Severity, Violation,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
+
+const RULE: &str = "pub-use-location";
+/// Modules `pub use` is allowed in, unless `pub_use_modules` says otherwise
+pub const DEFAULT_MODULES: &[&str] = &["prelude"];
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "pub_use_location",
+	autofix: false,
+	triggers: &["pub use"],
+	description: "Restrict pub use re-exports to lib.rs, mod.rs and prelude modules",
+	rationale: "Re-exports scattered across modules hide an item's public path; kept in the roots and a prelude, the public surface reads in one place.",
+	bad: "// src/client/retry.rs\npub use crate::client::backoff::Backoff;",
+	good: "// src/client/mod.rs\npub use backoff::Backoff;",
+	fix: "Move the re-export to `lib.rs`, the module's `mod.rs` or a prelude module, or narrow it to `pub(crate) use`.",
+};
+
+pub struct PubUseLocation;
+impl Rule for PubUseLocation {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree, &ctx.opts.pub_use_modules)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File, modules: &[String]) -> Vec<Violation> {
+	let is_named = |name: &str| modules.iter().any(|module| module == name);
+	let file_name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+	let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default();
+	let visitor = PubUseVisitor {
+		path_str: path.display().to_string(),
+		modules,
+		is_root: file_name == "lib.rs" || file_name == "mod.rs",
+		in_allowed: is_named(stem),
+		depth: 0,
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+struct PubUseVisitor<'a> {
+	path_str: String,
+	modules: &'a [String],
+	/// Whether the file is a `lib.rs` or `mod.rs`
+	is_root: bool,
+	/// Whether the visitor is in one of `modules`
+	in_allowed: bool,
+	/// How many inline modules and functions the visitor is in
+	depth: usize,
+	violations: Vec<Violation>,
+}
+
+impl<'a> Visit<'a> for PubUseVisitor<'_> {
+	fn visit_item_mod(&mut self, node: &'a ItemMod) {
+		let was_allowed = self.in_allowed;
+		self.in_allowed |= self.modules.iter().any(|module| node.ident == module);
+		self.depth += 1;
+		syn::visit::visit_item_mod(self, node);
+		self.depth -= 1;
+		self.in_allowed = was_allowed;
+	}
+
+	fn visit_item_fn(&mut self, node: &'a syn::ItemFn) {
+		self.depth += 1;
+		syn::visit::visit_item_fn(self, node);
+		self.depth -= 1;
+	}
+
+	fn visit_item_use(&mut self, node: &'a ItemUse) {
+		if !matches!(node.vis, Visibility::Public(_)) || self.in_allowed || (self.is_root && self.depth == 0) {
+			return;
+		}
+		let start = node.vis.span().start();
+		let end = node.semi_token.span.end();
+		self.violations.push(Violation {
+			rule: RULE,
+			file: self.path_str.clone(),
+			line: start.line,
+			column: start.column,
+			end: Some((end.line, end.column)),
+			message: "`pub use` outside `lib.rs`, `mod.rs` or a prelude module\nHINT: move it to the module's root or a prelude, or narrow it to `pub(crate) use`".to_string(),
+			severity: Severity::Error,
+			..Default::default()
+		});
+	}
+}
@@ -0,0 +1,130 @@
+//! Lint to require a timeout on reqwest's async clients.
+//!
+//! reqwest's async `Client` has no timeout unless one is set, so a request to a server that stops answering never
+//! returns, and whatever awaits it hangs along with it. `Client::new()`, `Client::default()`, `Client::builder()` and
+//! `ClientBuilder::new()` are flagged unless the method chain they start calls `.timeout(..)` or `.connect_timeout(..)`,
+//! be it on the builder or on a request sent from the client. `reqwest::get` is always flagged, as its client can't be
+//! given one. The blocking client times out after 30 seconds by default, so it's left alone.
+//!
+//! Only the chain is looked at: a builder bound to a variable and given its timeout later is flagged, and wants a skip.
+
+use std::path::Path;
+
+use proc_macro2::LineColumn;
+use syn::{Expr, ExprCall, ExprMethodCall, spanned::Spanned, visit::Visit};
+
+use super::{
+	Severity, Violation,
+	custom_rules::Imports,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
+
+const RULE: &str = "reqwest-timeout";
+/// Methods setting a timeout, on either `ClientBuilder` or `RequestBuilder`
+const TIMEOUTS: &[&str] = &["timeout", "connect_timeout"];
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "reqwest_timeout",
+	autofix: false,
+	triggers: &["reqwest"],
+	description: "Require reqwest clients and requests to set a timeout",
+	rationale: "reqwest's async client waits forever by default, so one server that stops answering hangs every task awaiting it.",
+	bad: "let client = reqwest::Client::new();",
+	good: "let client = reqwest::Client::builder().timeout(Duration::from_secs(30)).build()?;",
+	fix: "Build the client with `.timeout(..)` or `.connect_timeout(..)`, or set `.timeout(..)` on the request.",
+};
+
+pub struct ReqwestTimeout;
+impl Rule for ReqwestTimeout {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+	let visitor = TimeoutVisitor {
+		path_str: path.display().to_string(),
+		imports: Imports::of(file),
+		timed: Vec::new(),
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+struct TimeoutVisitor {
+	path_str: String,
+	imports: Imports,
+	/// Starts of the calls heading a method chain that sets a timeout. Chains are visited before the calls they start with.
+	timed: Vec<LineColumn>,
+	violations: Vec<Violation>,
+}
+
+impl TimeoutVisitor {
+	fn check_call(&mut self, node: &ExprCall) {
+		let Expr::Path(func) = &*node.func else {
+			return;
+		};
+		let resolved = self.imports.resolve(&func.path);
+		let hint = match resolved.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+			["reqwest", "get"] => "send the request from a `Client` built with `.timeout(..)`",
+			["reqwest", "Client", "new" | "default"] => "build it with `Client::builder().timeout(..)`, or call `.timeout(..)` on each request",
+			["reqwest", "Client", "builder"] | ["reqwest", "ClientBuilder", "new" | "default"] => "call `.timeout(..)` or `.connect_timeout(..)` on the builder",
+			_ => return,
+		};
+		if resolved[1] != "get" && self.timed.contains(&node.span().start()) {
+			return;
+		}
+		let start = node.func.span().start();
+		let end = node.func.span().end();
+		self.violations.push(Violation {
+			rule: RULE,
+			file: self.path_str.clone(),
+			line: start.line,
+			column: start.column,
+			end: Some((end.line, end.column)),
+			message: format!("`{}` sets no timeout, so a request to a server that stops answering never returns\nHINT: {hint}", resolved.join("::")),
+			severity: Severity::Error,
+			..Default::default()
+		});
+	}
+
+	/// Note the call heading the chain `node` ends, if the chain sets a timeout.
+	fn check_chain(&mut self, node: &ExprMethodCall) {
+		let mut sets_timeout = TIMEOUTS.iter().any(|t| node.method == t);
+		let mut head = &*node.receiver;
+		loop {
+			head = match head {
+				Expr::MethodCall(call) => {
+					sets_timeout |= TIMEOUTS.iter().any(|t| call.method == t);
+					&call.receiver
+				}
+				Expr::Await(expr) => &expr.base,
+				Expr::Try(expr) => &expr.expr,
+				Expr::Paren(expr) => &expr.expr,
+				_ => break,
+			};
+		}
+		if sets_timeout && let Expr::Call(call) = head {
+			self.timed.push(call.span().start());
+		}
+	}
+}
+
+impl<'a> Visit<'a> for TimeoutVisitor {
+	fn visit_expr_method_call(&mut self, node: &'a ExprMethodCall) {
+		self.check_chain(node);
+		syn::visit::visit_expr_method_call(self, node);
+	}
+
+	fn visit_expr_call(&mut self, node: &'a ExprCall) {
+		self.check_call(node);
+		syn::visit::visit_expr_call(self, node);
+	}
+}
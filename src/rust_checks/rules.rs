@@ -0,0 +1,277 @@
+//! Registry of Rust rules: the [`Rule`]s every file is run through, and the metadata behind
+//! `codestyle rust list-rules` and `codestyle rust explain`.
+
+use std::{
+	collections::HashSet,
+	sync::{LazyLock, Mutex},
+};
+
+use serde::Serialize;
+
+use super::{
+	FileInfo, RustCheckOptions, Violation, allow_reason, bool_params, cargo_dep_ordering, commented_code, dead_files, derive_debug, derive_default, derive_order, embed_simple_vars,
+	expect_message, fn_params, ignored_error_comment, impl_folds, impl_follows_type, impl_method_order, insta_snapshots, instrument, join_split_impls, let_else, loops, missing_docs,
+	mod_files, mod_style, nesting_depth, no_as_cast, no_assert, no_async_trait, no_block_on_in_async, no_blocking_in_async, no_chrono, no_dbg, no_extern_crate, no_get_prefix, no_lazy_static,
+	no_lock_across_await, no_macro_use, no_once_cell, no_other_runtimes, no_panic, no_tokio_spawn, no_unwrap, pub_fields_first, pub_first, pub_use_location, reqwest_timeout,
+	rustfmt::RustfmtConfig, safety_comment, should_panic_expected, skip_reason, test_context::TestContext, test_fn_prefix, test_in_cfg_test, tests_last, todo_comment, todo_issue,
+	trait_impls_follow, unused_pub, unused_skip, use_bail, use_matches, use_thiserror,
+};
+
+/// Static description of a rule, declared next to its implementation.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct RuleInfo {
+	/// As printed in violations and used in skip markers
+	pub id: &'static str,
+	/// The `RustCheckOptions` field controlling the rule; its CLI flag is the kebab-case of this
+	pub option: &'static str,
+	pub autofix: bool,
+	/// Substrings every violating file contains, so files without any can skip the rule without parsing; empty to always run
+	#[serde(skip)]
+	pub triggers: &'static [&'static str],
+	pub description: &'static str,
+	/// Why the rule exists
+	pub rationale: &'static str,
+	/// Code the rule flags
+	pub bad: &'static str,
+	/// The same code once it passes
+	pub good: &'static str,
+	/// What `format` does about a violation, or what to do by hand if there's no autofix
+	pub fix: &'static str,
+}
+
+impl RuleInfo {
+	/// Whether `contents` could violate the rule at all, judging only by its `triggers`.
+	pub fn may_apply(&self, contents: &str) -> bool {
+		self.triggers.is_empty() || self.triggers.iter().any(|t| contents.contains(t))
+	}
+}
+
+/// What a rule's check gets to look at.
+pub struct Context<'a> {
+	pub file: &'a FileInfo,
+	/// Options of the file's crate, for rules with settings beyond their severity
+	pub opts: &'a RustCheckOptions,
+	pub tree: &'a syn::File,
+	pub rustfmt: &'a RustfmtConfig,
+	/// Whether the fixes are going to be applied, so rules can skip building ones that are only worth it for `format`
+	pub fixing: bool,
+	/// Which lines are test code
+	pub tests: &'a TestContext,
+}
+
+/// A check over Rust source. Each rule implements it next to its [`RuleInfo`] and is listed in [`SOURCE_RULES`],
+/// which the check, fix and report passes all run through.
+pub trait Rule: Sync {
+	/// Id, controlling option (and so default severity) and docs
+	fn info(&self) -> &'static RuleInfo;
+
+	/// Every violation in the file, regardless of severity: callers only run enabled rules, and stamp the severity on.
+	fn check(&self, ctx: &Context) -> Vec<Violation>;
+
+	/// Whether any violation can come with a fix; the fix passes skip rules that can't.
+	fn fixable(&self) -> bool {
+		self.info().autofix
+	}
+
+	/// Whether the check reads files besides the one it's given, so its results can't be cached by that file's contents.
+	fn reads_other_files(&self) -> bool {
+		false
+	}
+}
+
+/// Every source rule, in the order they run, which is also their priority when fixes conflict.
+/// Order matters: join_split_impls -> impl_follows_type -> trait_impls_follow -> impl_folds
+pub const SOURCE_RULES: &[&dyn Rule] = &[
+	&instrument::Instrument,
+	&loops::Loops,
+	&safety_comment::SafetyComment,
+	&join_split_impls::JoinSplitImpls,
+	&impl_follows_type::ImplFollowsType,
+	&trait_impls_follow::TraitImplsFollow,
+	&impl_folds::ImplFolds,
+	&embed_simple_vars::EmbedSimpleVars,
+	&insta_snapshots::InstaSnapshots,
+	&no_chrono::NoChrono,
+	&no_tokio_spawn::NoTokioSpawn,
+	&no_dbg::NoDbg,
+	&use_bail::UseBail,
+	&test_fn_prefix::TestFnPrefix,
+	&test_in_cfg_test::TestInCfgTest,
+	&pub_first::PubFirst,
+	&tests_last::TestsLast,
+	&ignored_error_comment::IgnoredErrorComment,
+	&no_unwrap::NoUnwrap,
+	&expect_message::ExpectMessage,
+	&no_panic::NoPanic,
+	&allow_reason::AllowReason,
+	&missing_docs::MissingDocs,
+	&derive_order::DeriveOrder,
+	&nesting_depth::NestingDepth,
+	&fn_params::FnParams,
+	&bool_params::BoolParams,
+	&no_as_cast::NoAsCast,
+	&let_else::LetElse,
+	&use_matches::UseMatches,
+	&mod_style::ModStyle,
+	&no_lock_across_await::NoLockAcrossAwait,
+	&no_blocking_in_async::NoBlockingInAsync,
+	&no_block_on_in_async::NoBlockOnInAsync,
+	&no_async_trait::NoAsyncTrait,
+	&no_lazy_static::NoLazyStatic,
+	&no_once_cell::NoOnceCell,
+	&no_other_runtimes::NoOtherRuntimes,
+	&reqwest_timeout::ReqwestTimeout,
+	&use_thiserror::UseThiserror,
+	&derive_debug::DeriveDebug,
+	&derive_default::DeriveDefault,
+	&pub_fields_first::PubFieldsFirst,
+	&mod_files::ModFiles,
+	&impl_method_order::ImplMethodOrder,
+	&no_get_prefix::NoGetPrefix,
+	&should_panic_expected::ShouldPanicExpected,
+	&no_assert::NoAssert,
+	&todo_issue::TodoIssue,
+	&todo_comment::TodoComment,
+	&commented_code::CommentedCode,
+	&no_extern_crate::NoExternCrate,
+	&no_macro_use::NoMacroUse,
+	&pub_use_location::PubUseLocation,
+	&skip_reason::SkipReason,
+];
+
+/// Every rule's metadata, `Cargo.toml` ones included, in the order they run.
+pub const RULES: &[RuleInfo] = &[
+	cargo_dep_ordering::INFO,
+	instrument::INFO,
+	loops::INFO,
+	safety_comment::INFO,
+	join_split_impls::INFO,
+	impl_follows_type::INFO,
+	trait_impls_follow::INFO,
+	impl_folds::INFO,
+	embed_simple_vars::INFO,
+	insta_snapshots::INLINE_INFO,
+	insta_snapshots::SEQUENTIAL_INFO,
+	no_chrono::INFO,
+	no_tokio_spawn::INFO,
+	no_dbg::INFO,
+	use_bail::INFO,
+	test_fn_prefix::INFO,
+	test_in_cfg_test::INFO,
+	pub_first::INFO,
+	tests_last::INFO,
+	ignored_error_comment::INFO,
+	no_unwrap::INFO,
+	expect_message::INFO,
+	no_panic::INFO,
+	allow_reason::INFO,
+	missing_docs::INFO,
+	derive_order::INFO,
+	nesting_depth::INFO,
+	fn_params::INFO,
+	bool_params::INFO,
+	no_as_cast::INFO,
+	let_else::INFO,
+	use_matches::INFO,
+	mod_style::INFO,
+	no_lock_across_await::INFO,
+	no_blocking_in_async::INFO,
+	no_block_on_in_async::INFO,
+	no_async_trait::INFO,
+	no_lazy_static::INFO,
+	no_once_cell::INFO,
+	no_other_runtimes::INFO,
+	reqwest_timeout::INFO,
+	use_thiserror::INFO,
+	derive_debug::INFO,
+	derive_default::INFO,
+	pub_fields_first::INFO,
+	mod_files::INFO,
+	impl_method_order::INFO,
+	no_get_prefix::INFO,
+	should_panic_expected::INFO,
+	no_assert::INFO,
+	todo_issue::INFO,
+	todo_comment::INFO,
+	commented_code::INFO,
+	no_extern_crate::INFO,
+	no_macro_use::INFO,
+	pub_use_location::INFO,
+	unused_pub::INFO,
+	dead_files::INFO,
+	skip_reason::INFO,
+	unused_skip::INFO,
+];
+
+/// Ids of rules only known at runtime, from plugins and custom rules. Violations carry `&'static str` ids, so each distinct one is leaked once.
+static RUNTIME_IDS: LazyLock<Mutex<HashSet<&'static str>>> = LazyLock::new(Default::default);
+
+pub fn find(id: &str) -> Option<&'static RuleInfo> {
+	RULES.iter().find(|r| r.id == id)
+}
+
+/// A `'static` id for a rule only known at runtime.
+pub fn intern(id: String) -> &'static str {
+	let mut ids = RUNTIME_IDS.lock().expect("rule id set is never poisoned");
+	match ids.get(id.as_str()) {
+		Some(&interned) => interned,
+		None => {
+			let interned: &'static str = Box::leak(id.into_boxed_str());
+			ids.insert(interned);
+			interned
+		}
+	}
+}
+
+pub fn run_list_rules(json: bool) -> i32 {
+	let defaults = RustCheckOptions::default();
+
+	if json {
+		#[derive(Serialize)]
+		struct Entry {
+			#[serde(flatten)]
+			info: RuleInfo,
+			default: String,
+		}
+		let entries: Vec<Entry> = RULES
+			.iter()
+			.map(|&info| Entry {
+				info,
+				default: defaults.severity(info.option).to_string(),
+			})
+			.collect();
+		println!("{}", serde_json::to_string_pretty(&entries).expect("rule metadata is always serializable"));
+		return 0;
+	}
+
+	let id_width = RULES.iter().map(|r| r.id.len()).max().unwrap_or_default();
+	for info in RULES {
+		let default = defaults.severity(info.option);
+		let autofix = if info.autofix { "fix" } else { "   " };
+		println!("{:<id_width$}  {default:<5}  {autofix}  {} (--{})", info.id, info.description, info.option.replace('_', "-"));
+	}
+	0
+}
+
+pub fn run_explain(id: &str) -> i32 {
+	let Some(info) = find(id) else {
+		eprintln!("codestyle: unknown rule `{id}`; see `codestyle rust list-rules`");
+		return 1;
+	};
+	let indent = |code: &str| code.lines().map(|l| format!("    {}", l.replace('\t', "    "))).collect::<Vec<_>>().join("\n");
+
+	println!("{}: {}", info.id, info.description);
+	println!();
+	println!("Enabled with --{}=error|warn (default: {})", info.option.replace('_', "-"), RustCheckOptions::default().severity(info.option));
+	println!();
+	println!("{}", info.rationale);
+	println!();
+	println!("Violating:");
+	println!("{}", indent(info.bad));
+	println!();
+	println!("Passing:");
+	println!("{}", indent(info.good));
+	println!();
+	println!("{}: {}", if info.autofix { "Autofix" } else { "Manual fix" }, info.fix);
+	0
+}
@@ -0,0 +1,148 @@
+//! rustc's JSON diagnostics (as from `--error-format=json`), one per line, so tooling built on compiler suggestions,
+//! like the `rustfix` crate behind `cargo fix`, can apply codestyle's fixes too.
+//!
+//! Each violation is a diagnostic whose `code` is the rule id, with its labels as secondary spans and its notes as
+//! `note` children; one with a fix gets a `help` child carrying it as a machine-applicable suggestion, and each of its
+//! suggestions one carrying a maybe-incorrect one, which `cargo fix` leaves alone. Suggestions are computed against the
+//! files as they are, so apply them before editing.
+
+use std::{collections::HashMap, fs};
+
+use serde_json::{Value, json};
+
+use super::{
+	Fix, Severity, Violation,
+	line_endings::{self, LineEnding},
+	line_index::LineIndex,
+	pretty,
+};
+
+/// Newline-delimited diagnostics for `violations`, reading each file once to turn positions into byte offsets.
+pub fn to_diagnostics(violations: &[Violation]) -> String {
+	let mut sources: HashMap<&str, Option<String>> = HashMap::new();
+	let mut out = String::new();
+	for v in violations {
+		let source = sources.entry(&v.file).or_insert_with(|| fs::read_to_string(&v.file).ok());
+		out.push_str(&diagnostic(v, source.as_deref()).to_string());
+		out.push('\n');
+	}
+	out
+}
+
+/// The diagnostic for `v`, with `source` being the contents of `v.file` if available. Without it, the violation
+/// is still reported, just without a suggestion.
+fn diagnostic(v: &Violation, source: Option<&str>) -> Value {
+	let level = match v.severity {
+		Severity::Warn => "warning",
+		Severity::Error | Severity::Allow => "error",
+	};
+	let lines = source.map(LineIndex::new);
+	let mut spans = vec![span(v, lines.as_ref(), (v.line, v.column), v.end.unwrap_or((v.line, v.column)), None)];
+	for label in &v.labels {
+		spans.push(span(v, lines.as_ref(), (label.line, label.column), (label.line, label.column), Some(&label.message)));
+	}
+
+	let mut children: Vec<Value> = v.notes.iter().map(|note| child(note, "note", Vec::new())).collect();
+	let fix = v.fix.as_ref().map(|fix| ("apply the codestyle fix", fix, "MachineApplicable"));
+	let suggestions = v.suggestions.iter().map(|s| (s.message.as_str(), &s.fix, "MaybeIncorrect"));
+	for (message, fix, applicability) in fix.into_iter().chain(suggestions) {
+		if let Some(span) = lines.as_ref().and_then(|lines| suggestion(&v.file, fix, lines, applicability)) {
+			children.push(child(message, "help", vec![span]));
+		}
+	}
+	json!({
+		"$message_type": "diagnostic",
+		"message": v.message.lines().next().unwrap_or_default(),
+		"code": { "code": v.rule, "explanation": null },
+		"level": level,
+		"spans": spans,
+		"children": children,
+		"rendered": pretty::render(v, source),
+	})
+}
+
+/// A span of `v.file` between two `(line, column)` positions, the primary one unless it has a `label`.
+fn span(v: &Violation, lines: Option<&LineIndex>, from: (usize, usize), to: (usize, usize), label: Option<&str>) -> Value {
+	let start = lines.and_then(|l| l.offset(from.0, from.1)).unwrap_or(0);
+	let end = lines.and_then(|l| l.offset(to.0, to.1)).filter(|&end| end >= start).unwrap_or(start);
+	json!({
+		"file_name": v.file,
+		"byte_start": start,
+		"byte_end": end,
+		"line_start": from.0,
+		"line_end": to.0,
+		// rustc's columns are 1-based, ours 0-based
+		"column_start": from.1 + 1,
+		"column_end": to.1 + 1,
+		"is_primary": label.is_none(),
+		"text": lines.map(|l| span_lines(l.text(), start, end)).unwrap_or_default(),
+		"label": label,
+		"suggested_replacement": null,
+		"suggestion_applicability": null,
+		"expansion": null,
+	})
+}
+
+fn child(message: &str, level: &str, spans: Vec<Value>) -> Value {
+	json!({
+		"message": message,
+		"code": null,
+		"level": level,
+		"spans": spans,
+		"children": [],
+		"rendered": null,
+	})
+}
+
+/// The span `fix` replaces, carrying its replacement. `None` if its offsets don't fit the source.
+/// Fixes are computed against `\n`-normalized source, so on a `\r\n` file both are mapped back to it.
+fn suggestion(file: &str, fix: &Fix, lines: &LineIndex, applicability: &str) -> Option<Value> {
+	let source = lines.text();
+	let (start, end) = (line_endings::original_offset(source, fix.start_byte), line_endings::original_offset(source, fix.end_byte));
+	if start > end || !source.is_char_boundary(start) || !source.is_char_boundary(end) {
+		return None;
+	}
+	let (line_start, column_start) = lines.position(start);
+	let (line_end, column_end) = lines.position(end);
+	Some(json!({
+		"file_name": file,
+		"byte_start": start,
+		"byte_end": end,
+		"line_start": line_start,
+		"line_end": line_end,
+		// rustc's columns are 1-based, ours 0-based
+		"column_start": column_start + 1,
+		"column_end": column_end + 1,
+		"is_primary": true,
+		"text": span_lines(source, start, end),
+		"label": null,
+		"suggested_replacement": LineEnding::detect(source).apply(&fix.replacement),
+		"suggestion_applicability": applicability,
+		"expansion": null,
+	}))
+}
+
+/// The source lines `start..end` touches, each with the part inside the span highlighted.
+fn span_lines(source: &str, start: usize, end: usize) -> Vec<Value> {
+	let mut lines = Vec::new();
+	let mut line_start = 0;
+	for line in source.split_inclusive('\n') {
+		let line_end = line_start + line.len();
+		if line_start <= end && line_end > start {
+			let text = line.strip_suffix('\n').unwrap_or(line);
+			let text = text.strip_suffix('\r').unwrap_or(text);
+			let from = start.saturating_sub(line_start).min(text.len());
+			let to = end.saturating_sub(line_start).min(text.len());
+			lines.push(json!({
+				"text": text,
+				"highlight_start": text[..from].chars().count() + 1,
+				"highlight_end": text[..to].chars().count() + 1,
+			}));
+		}
+		if line_end > end {
+			break;
+		}
+		line_start = line_end;
+	}
+	lines
+}
@@ -0,0 +1,114 @@
+//! The target project's `rustfmt.toml`, so that fix builders emit text rustfmt wouldn't touch.
+//!
+//! Only the options fixes can get wrong are read. Whatever isn't set follows the file being fixed.
+
+use std::{fs, path::Path};
+
+use super::line_endings::LineEnding;
+
+const FILE_NAMES: &[&str] = &["rustfmt.toml", ".rustfmt.toml"];
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum NewlineStyle {
+	/// Whatever the file already uses
+	#[default]
+	Auto,
+	Native,
+	Unix,
+	Windows,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct RustfmtConfig {
+	pub hard_tabs: Option<bool>,
+	/// Width of one indentation level; rustfmt defaults to 4
+	pub tab_spaces: Option<usize>,
+	pub max_width: Option<usize>,
+	pub newline_style: NewlineStyle,
+}
+impl RustfmtConfig {
+	/// Read the nearest `rustfmt.toml` or `.rustfmt.toml` at or above `dir`, the same lookup rustfmt does.
+	pub fn load(dir: &Path) -> Self {
+		let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+		for ancestor in dir.ancestors() {
+			for name in FILE_NAMES {
+				let path = ancestor.join(name);
+				if let Ok(content) = fs::read_to_string(&path) {
+					return Self::parse(&content).unwrap_or_else(|e| {
+						eprintln!("codestyle: ignoring invalid {path:?}: {e}");
+						Self::default()
+					});
+				}
+			}
+		}
+		Self::default()
+	}
+
+	pub fn parse(content: &str) -> Result<Self, String> {
+		let table: toml::Table = toml::from_str(content).map_err(|e| e.to_string())?;
+		let usize_opt = |key: &str| table.get(key).and_then(toml::Value::as_integer).and_then(|n| usize::try_from(n).ok());
+		let newline_style = match table.get("newline_style").and_then(toml::Value::as_str) {
+			None | Some("Auto") => NewlineStyle::Auto,
+			Some("Native") => NewlineStyle::Native,
+			Some("Unix") => NewlineStyle::Unix,
+			Some("Windows") => NewlineStyle::Windows,
+			Some(other) => return Err(format!("unknown newline_style `{other}`")),
+		};
+
+		Ok(Self {
+			hard_tabs: table.get("hard_tabs").and_then(toml::Value::as_bool),
+			tab_spaces: usize_opt("tab_spaces"),
+			max_width: usize_opt("max_width"),
+			newline_style,
+		})
+	}
+
+	/// Line ending a fixed file is written with, `original` being its contents before any fix.
+	pub fn line_ending(&self, original: &str) -> LineEnding {
+		match self.newline_style {
+			NewlineStyle::Auto => LineEnding::detect(original),
+			NewlineStyle::Native if cfg!(windows) => LineEnding::Crlf,
+			NewlineStyle::Native | NewlineStyle::Unix => LineEnding::Lf,
+			NewlineStyle::Windows => LineEnding::Crlf,
+		}
+	}
+
+	/// Whether `line` stays within `max_width`, counting tabs as `tab_spaces` columns.
+	pub fn fits(&self, line: &str) -> bool {
+		let Some(max_width) = self.max_width else {
+			return true;
+		};
+		let tab_width = self.tab_spaces.unwrap_or(4);
+		line.chars().map(|c| if c == '\t' { tab_width } else { 1 }).sum::<usize>() <= max_width
+	}
+
+	/// Convert `text` (a fix's replacement for part of some source) to the configured indentation, with `\n` line endings.
+	pub fn conform(&self, text: &str) -> String {
+		let mut out = String::with_capacity(text.len());
+		for (i, line) in text.split('\n').enumerate() {
+			if i > 0 {
+				out.push('\n');
+			}
+			let line = line.strip_suffix('\r').unwrap_or(line);
+			let code = line.trim_start_matches([' ', '\t']);
+			match self.hard_tabs {
+				Some(hard_tabs) if !code.is_empty() => {
+					out.push_str(&self.indent(&line[..line.len() - code.len()], hard_tabs));
+					out.push_str(code);
+				}
+				_ => out.push_str(line),
+			}
+		}
+		out
+	}
+
+	fn indent(&self, whitespace: &str, hard_tabs: bool) -> String {
+		let tab_width = self.tab_spaces.unwrap_or(4);
+		let width: usize = whitespace.chars().map(|c| if c == '\t' { tab_width } else { 1 }).sum();
+		if hard_tabs {
+			format!("{}{}", "\t".repeat(width / tab_width), " ".repeat(width % tab_width))
+		} else {
+			" ".repeat(width)
+		}
+	}
+}
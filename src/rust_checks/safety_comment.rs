@@ -0,0 +1,157 @@
+//! Lint to require `// SAFETY:` comments on `unsafe` blocks and functions, like `//LOOP` on endless loops.
+//!
+//! Every `unsafe` block relies on invariants the compiler can't check, so it needs a comment on or right above it
+//! saying why they hold; a comment block counts as long as one of its lines has the `SAFETY:`. An `unsafe fn` can
+//! instead have a `# Safety` section in its docs, telling callers what they have to uphold.
+
+use std::path::Path;
+
+use proc_macro2::Span;
+use syn::{ExprUnsafe, ImplItemFn, ItemFn, Signature, TraitItemFn, spanned::Spanned, visit::Visit};
+
+use super::{
+	Fix, Severity, Suggestion, Violation,
+	line_index::LineIndex,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
+
+const RULE: &str = "safety-comment";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "safety_comment",
+	autofix: false,
+	triggers: &["unsafe"],
+	description: "Require a // SAFETY: comment on unsafe blocks and fns",
+	rationale: "`unsafe` code relies on invariants the compiler can't check; writing down why they hold is the only review they get.",
+	bad: "let bytes = unsafe { std::slice::from_raw_parts(ptr, len) };",
+	good: "// SAFETY: `ptr` and `len` come from a live Vec that outlives `bytes`\nlet bytes = unsafe { std::slice::from_raw_parts(ptr, len) };",
+	fix: "Add a `// SAFETY:` comment explaining why the invariants hold, or for an `unsafe fn` a `# Safety` doc section.",
+};
+
+pub struct SafetyComment;
+impl Rule for SafetyComment {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+	let visitor = SafetyVisitor {
+		path_str: path.display().to_string(),
+		content,
+		lines: LineIndex::new(content),
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+struct SafetyVisitor<'a> {
+	path_str: String,
+	content: &'a str,
+	lines: LineIndex<'a>,
+	violations: Vec<Violation>,
+}
+
+impl SafetyVisitor<'_> {
+	/// Whether `line`, or the comment lines right above it, have a `SAFETY:`.
+	fn has_safety_comment(&self, line: usize) -> bool {
+		let lines: Vec<&str> = self.content.lines().collect();
+		let is_safety = |text: &str| text.contains("// SAFETY:") || text.contains("//SAFETY:");
+		if line == 0 || line > lines.len() {
+			return false;
+		}
+		if is_safety(lines[line - 1]) {
+			return true;
+		}
+		lines[..line - 1].iter().rev().take_while(|text| text.trim_start().starts_with("//")).any(|text| is_safety(text))
+	}
+
+	/// For an `unsafe fn`: a `SAFETY:` comment anywhere from above its attributes down to its signature,
+	/// or a `# Safety` doc section.
+	fn fn_is_documented(&self, item: Span, sig: &Signature) -> bool {
+		let (first, fn_line) = (item.start().line, sig.fn_token.span.start().line);
+		let lines: Vec<&str> = self.content.lines().collect();
+		let header = lines.get(first.saturating_sub(1)..fn_line.min(lines.len())).unwrap_or_default();
+		self.has_safety_comment(first) || header.iter().any(|text| text.contains("SAFETY:") || text.trim_start().starts_with("/// # Safety"))
+	}
+
+	/// A `// SAFETY: ` comment above `line`, for the user to finish with the reason.
+	fn justify(&self, line: usize) -> Suggestion {
+		let line_start = self.lines.offset(line, 0).unwrap_or_default();
+		let text = &self.content[line_start..self.lines.line_end(line_start)];
+		let indent = &text[..text.len() - text.trim_start().len()];
+		Suggestion {
+			message: "explain why the invariants hold".to_string(),
+			fix: Fix {
+				start_byte: line_start,
+				end_byte: line_start,
+				replacement: format!("{indent}// SAFETY: \n"),
+			},
+		}
+	}
+
+	fn check_fn(&mut self, item: Span, sig: &Signature) {
+		let Some(unsafety) = sig.unsafety else {
+			return;
+		};
+		if self.fn_is_documented(item, sig) {
+			return;
+		}
+		let start = unsafety.span.start();
+		self.violations.push(Violation {
+			rule: RULE,
+			file: self.path_str.clone(),
+			line: start.line,
+			column: start.column,
+			end: Some((sig.ident.span().end().line, sig.ident.span().end().column)),
+			message: format!(
+				"`unsafe fn {}` without a `// SAFETY:` comment or `# Safety` doc section\nHINT: document what callers have to uphold",
+				sig.ident
+			),
+			severity: Severity::Error,
+			..Default::default()
+		});
+	}
+}
+
+impl<'a> Visit<'a> for SafetyVisitor<'a> {
+	fn visit_expr_unsafe(&mut self, node: &'a ExprUnsafe) {
+		let start = node.unsafe_token.span.start();
+		if !self.has_safety_comment(start.line) {
+			self.violations.push(Violation {
+				rule: RULE,
+				file: self.path_str.clone(),
+				line: start.line,
+				column: start.column,
+				end: Some((node.unsafe_token.span.end().line, node.unsafe_token.span.end().column)),
+				message: "`unsafe` block without a `// SAFETY:` comment\nHINT: explain why the invariants it relies on hold".to_string(),
+				severity: Severity::Error,
+				suggestions: vec![self.justify(start.line)],
+				..Default::default()
+			});
+		}
+		syn::visit::visit_expr_unsafe(self, node);
+	}
+
+	fn visit_item_fn(&mut self, node: &'a ItemFn) {
+		self.check_fn(node.span(), &node.sig);
+		syn::visit::visit_item_fn(self, node);
+	}
+
+	fn visit_impl_item_fn(&mut self, node: &'a ImplItemFn) {
+		self.check_fn(node.span(), &node.sig);
+		syn::visit::visit_impl_item_fn(self, node);
+	}
+
+	fn visit_trait_item_fn(&mut self, node: &'a TraitItemFn) {
+		self.check_fn(node.span(), &node.sig);
+		syn::visit::visit_trait_item_fn(self, node);
+	}
+}
@@ -0,0 +1,115 @@
+//! [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html) output, the format GitHub code scanning ingests.
+
+use serde_json::{Value, json};
+
+use super::{Severity, Violation, rules};
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// A SARIF log with one run: every rule that fired in `tool.driver.rules`, one result per violation.
+pub fn to_sarif(violations: &[Violation]) -> String {
+	let mut rule_ids: Vec<&str> = violations.iter().map(|v| v.rule).collect();
+	rule_ids.sort_unstable();
+	rule_ids.dedup();
+
+	let rules: Vec<Value> = rule_ids
+		.iter()
+		.map(|&id| {
+			// Only Rust rules are in the registry; other languages' rules go by id alone
+			let description = rules::find(id).map_or(id, |info| info.description);
+			json!({
+				"id": id,
+				"shortDescription": { "text": description },
+			})
+		})
+		.collect();
+
+	let results: Vec<Value> = violations
+		.iter()
+		.map(|v| {
+			// Our columns are 0-based, SARIF's are 1-based
+			let mut region = json!({ "startLine": v.line, "startColumn": v.column + 1 });
+			if let Some((end_line, end_column)) = v.end {
+				region["endLine"] = json!(end_line);
+				region["endColumn"] = json!(end_column + 1);
+			}
+			let related: Vec<Value> = v
+				.labels
+				.iter()
+				.enumerate()
+				.map(|(i, label)| {
+					json!({
+						"id": i,
+						"message": { "text": label.message },
+						"physicalLocation": {
+							"artifactLocation": { "uri": uri(&v.file) },
+							"region": { "startLine": label.line, "startColumn": label.column + 1 },
+						},
+					})
+				})
+				.collect();
+			let fixes: Vec<Value> = v
+				.suggestions
+				.iter()
+				.map(|s| {
+					json!({
+						"description": { "text": s.message },
+						"artifactChanges": [{
+							"artifactLocation": { "uri": uri(&v.file) },
+							"replacements": [{
+								"deletedRegion": { "byteOffset": s.fix.start_byte, "byteLength": s.fix.end_byte.saturating_sub(s.fix.start_byte) },
+								"insertedContent": { "text": s.fix.replacement },
+							}],
+						}],
+					})
+				})
+				.collect();
+			let message = std::iter::once(v.message.as_str()).chain(v.notes.iter().map(String::as_str)).collect::<Vec<_>>().join("\n");
+			json!({
+				"ruleId": v.rule,
+				"ruleIndex": rule_ids.binary_search(&v.rule).expect("collected from the same violations"),
+				"level": level(v.severity),
+				"message": { "text": message },
+				"locations": [{
+					"physicalLocation": {
+						"artifactLocation": { "uri": uri(&v.file) },
+						"region": region,
+					},
+				}],
+				"relatedLocations": related,
+				"fixes": fixes,
+			})
+		})
+		.collect();
+
+	let log = json!({
+		"$schema": SCHEMA,
+		"version": "2.1.0",
+		"runs": [{
+			"tool": {
+				"driver": {
+					"name": "codestyle",
+					"version": env!("CARGO_PKG_VERSION"),
+					"informationUri": env!("CARGO_PKG_REPOSITORY"),
+					"rules": rules,
+				},
+			},
+			"results": results,
+		}],
+	});
+	serde_json::to_string_pretty(&log).expect("SARIF log is always serializable")
+}
+
+fn level(severity: Severity) -> &'static str {
+	match severity {
+		Severity::Error => "error",
+		Severity::Warn => "warning",
+		Severity::Allow => "none",
+	}
+}
+
+/// Relative paths as URIs with forward slashes, which is what code scanning resolves against the checkout.
+fn uri(file: &str) -> String {
+	let file = file.replace('\\', "/");
+	file.strip_prefix("./").unwrap_or(&file).to_string()
+}
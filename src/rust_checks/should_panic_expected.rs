@@ -0,0 +1,106 @@
+//! Lint to require the message a `#[should_panic]` test expects.
+//!
+//! Without `expected = ".."`, any panic passes the test, so one from a typo in its setup, or an `unwrap` that happens to
+//! fail first, reads as the panic it was written for. `#[should_panic = ".."]` counts as giving one; an empty message
+//! doesn't, as it matches every panic.
+
+use std::path::Path;
+
+use syn::{Attribute, Expr, ExprLit, Lit, Meta, spanned::Spanned, visit::Visit};
+
+use super::{
+	Fix, Severity, Violation,
+	line_index::LineIndex,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
+
+const RULE: &str = "should-panic-expected";
+/// Message for an `expected` that's there but empty, which the fix leaves for filling in
+const EMPTY_MESSAGE: &str = "`#[should_panic]` expects an empty message, which every panic has\nHINT: fill in the panic message the test is for";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "should_panic_expected",
+	autofix: true,
+	triggers: &["should_panic"],
+	description: "Require an expected message on #[should_panic]",
+	rationale: "A `#[should_panic]` without `expected` passes on any panic, including one from a broken setup rather than the behavior under test.",
+	bad: "#[test]\n#[should_panic]\nfn rejects_zero() {\n\tRatio::new(1, 0);\n}",
+	good: "#[test]\n#[should_panic(expected = \"denominator is zero\")]\nfn rejects_zero() {\n\tRatio::new(1, 0);\n}",
+	fix: "Adds an empty `expected = \"\"`, which stays flagged until the message is filled in.",
+};
+
+pub struct ShouldPanicExpected;
+impl Rule for ShouldPanicExpected {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+	let visitor = ShouldPanicVisitor {
+		path_str: path.display().to_string(),
+		lines: LineIndex::new(content),
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+struct ShouldPanicVisitor<'a> {
+	path_str: String,
+	lines: LineIndex<'a>,
+	violations: Vec<Violation>,
+}
+
+impl<'a> Visit<'a> for ShouldPanicVisitor<'_> {
+	fn visit_attribute(&mut self, node: &'a Attribute) {
+		if !node.path().is_ident("should_panic") {
+			return;
+		}
+		let (message, fix) = match &node.meta {
+			Meta::Path(path) => {
+				let fix = self.lines.offset_of(path.span().end()).map(|end| Fix {
+					start_byte: end,
+					end_byte: end,
+					replacement: "(expected = \"\")".to_string(),
+				});
+				("`#[should_panic]` without an expected message\nHINT: add `expected = \"..\"` with the panic message the test is for", fix)
+			}
+			Meta::NameValue(nv) if is_empty_str(&nv.value) => (EMPTY_MESSAGE, None),
+			Meta::List(list) => {
+				// Unparseable arguments are the compiler's to complain about
+				let Ok(Meta::NameValue(nv)) = list.parse_args::<Meta>() else {
+					return;
+				};
+				if !nv.path.is_ident("expected") || !is_empty_str(&nv.value) {
+					return;
+				}
+				(EMPTY_MESSAGE, None)
+			}
+			Meta::NameValue(_) => return,
+		};
+		let start = node.pound_token.span.start();
+		let end = node.span().end();
+		self.violations.push(Violation {
+			rule: RULE,
+			file: self.path_str.clone(),
+			line: start.line,
+			column: start.column,
+			end: Some((end.line, end.column)),
+			message: message.to_string(),
+			severity: Severity::Error,
+			fix,
+			..Default::default()
+		});
+	}
+}
+
+fn is_empty_str(expr: &Expr) -> bool {
+	matches!(expr, Expr::Lit(ExprLit { lit: Lit::Str(s), .. }) if s.value().trim().is_empty())
+}
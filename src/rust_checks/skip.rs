@@ -2,7 +2,11 @@
 //!
 //! When an item is marked with this marker, codestyle checks should skip it.
 //!
-//! Supported formats (as comments to avoid compiler errors):
+//! Supported formats:
+//! - `#[codestyle::skip]` - skip all rules, the real attribute from the `codestyle-attributes` crate
+//! - `#[codestyle::skip(rule-name)]` - skip specific rule, same
+//!
+//! And as comments, for crates that don't depend on `codestyle-attributes`:
 //! - `//#[codestyle::skip]` - skip all rules
 //! - `// #[codestyle::skip]` - skip all rules
 //! - `//@codestyle::skip` - skip all rules
@@ -11,6 +15,15 @@
 //! - `// #[codestyle::skip(rule-name)]` - skip specific rule
 //! - `//@codestyle::skip(rule-name)` - skip specific rule
 //! - `// @codestyle::skip(rule-name)` - skip specific rule
+//!
+//! A block of lines, rather than an item, is skipped by a `// codestyle::skip-start` (or
+//! `// codestyle::skip-start(rule-name)`) line and a `// codestyle::skip-end` line, see [`skip_regions`].
+//!
+//! Comment markers can give their reason after a colon, `//@codestyle::skip(rule-name): <reason>`, which the
+//! `skip-reason` rule requires. Any marker can expire: `//@codestyle::skip(rule-name, until = "2025-09-01")` stops
+//! applying after that day, see [`expired_skips`].
+
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use proc_macro2::Span;
 use syn::visit::Visit;
@@ -24,11 +37,15 @@ pub enum SkipMarker {
 	Rule(String),
 }
 
-/// Check if the line before the given span contains a codestyle::skip marker for a specific rule.
+/// Check if the header of the item at the given span (the line above it and its attributes) contains a
+/// codestyle::skip marker for a specific rule.
 /// Returns `true` if there's a skip-all marker OR a skip marker for the specified rule.
 pub fn has_skip_marker_for_rule(content: &str, span: Span, rule: &str) -> bool {
-	let line = span.start().line;
-	has_skip_marker_for_rule_at_line(content, line, rule)
+	match get_skip_marker_in_header(content, span.start().line) {
+		Some(SkipMarker::All) => true,
+		Some(SkipMarker::Rule(r)) => r == rule,
+		None => false,
+	}
 }
 
 /// Check if the given line or the line above contains a codestyle::skip marker for a specific rule.
@@ -40,6 +57,117 @@ pub fn has_skip_marker_for_rule_at_line(content: &str, line: usize, rule: &str)
 	}
 }
 
+/// Lines between a `// codestyle::skip-start` and its `// codestyle::skip-end`, both included.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SkipRegion {
+	pub marker: SkipMarker,
+	pub start_line: usize,
+	pub end_line: usize,
+}
+impl SkipRegion {
+	/// Whether violations of `rule` on `line` are skipped by this region.
+	pub fn covers(&self, line: usize, rule: &str) -> bool {
+		(self.start_line..=self.end_line).contains(&line) && self.skips(rule)
+	}
+
+	/// Whether this region skips `rule` at all.
+	pub fn skips(&self, rule: &str) -> bool {
+		match &self.marker {
+			SkipMarker::All => true,
+			SkipMarker::Rule(r) => r == rule,
+		}
+	}
+}
+
+/// A region marker without its counterpart.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UnmatchedMarker {
+	/// A `skip-start` never closed, at this line and column
+	Start(usize, usize),
+	/// A `skip-end` with no open `skip-start`, at this line and column
+	End(usize, usize),
+}
+
+/// The skip regions of `content`, and the markers that don't pair up. Regions nest: a `skip-end` closes the
+/// innermost open `skip-start`. An unclosed `skip-start` skips nothing.
+pub fn skip_regions(content: &str) -> (Vec<SkipRegion>, Vec<UnmatchedMarker>) {
+	let mut regions = Vec::new();
+	let mut unmatched = Vec::new();
+	if !content.contains("codestyle::skip-") {
+		return (regions, unmatched);
+	}
+
+	let mut open: Vec<(SkipMarker, usize, usize)> = Vec::new();
+	for (i, line) in content.lines().enumerate() {
+		let line_num = i + 1;
+		let column = line.chars().take_while(|c| c.is_whitespace()).count();
+		let Some(marker) = line.trim().strip_prefix("//").map(str::trim_start).and_then(|c| c.strip_prefix("codestyle::skip-")) else {
+			continue;
+		};
+		if let Some(rest) = marker.strip_prefix("start") {
+			if let Some(skip) = parse_skip_suffix(rest) {
+				open.push((skip, line_num, column));
+			}
+		} else if marker.strip_prefix("end").is_some_and(|rest| rest.trim().is_empty()) {
+			match open.pop() {
+				Some((marker, start_line, _)) => regions.push(SkipRegion { marker, start_line, end_line: line_num }),
+				None => unmatched.push(UnmatchedMarker::End(line_num, column)),
+			}
+		}
+	}
+	unmatched.extend(open.into_iter().map(|(_, line, column)| UnmatchedMarker::Start(line, column)));
+	unmatched.sort_by_key(|m| match *m {
+		UnmatchedMarker::Start(line, _) | UnmatchedMarker::End(line, _) => line,
+	});
+	(regions, unmatched)
+}
+
+/// A skip marker written as a `//` comment, for an item or a region start, with the reason given after it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarkerComment {
+	pub marker: SkipMarker,
+	/// Whether it's a `skip-start` rather than an item marker
+	pub region: bool,
+	pub line: usize,
+	pub column: usize,
+	pub reason: Option<String>,
+}
+
+/// Every skip marker comment in `content`. The reason is what follows the marker after a `:`, e.g.
+/// `//@codestyle::skip(pub-first): re-exports read best next to their imports`.
+pub fn marker_comments(content: &str) -> Vec<MarkerComment> {
+	content
+		.lines()
+		.enumerate()
+		.filter_map(|(i, line)| {
+			let after_slashes = line.trim_start().strip_prefix("//")?.trim_start();
+			let (rest, bracketed, region) = if let Some(rest) = after_slashes.strip_prefix("#[codestyle::skip") {
+				(rest, true, false)
+			} else if let Some(rest) = after_slashes.strip_prefix("@codestyle::skip") {
+				(rest, false, false)
+			} else {
+				(after_slashes.strip_prefix("codestyle::skip-start")?, false, true)
+			};
+			let marker = parse_skip_suffix(rest)?;
+
+			let rest = rest.trim_start();
+			let rest = match rest.strip_prefix('(') {
+				Some(args) => &args[args.find(')')? + 1..],
+				None => rest,
+			};
+			let rest = if bracketed { rest.trim_start().strip_prefix(']')? } else { rest };
+			let reason = rest.trim_start().strip_prefix(':').map(str::trim).filter(|reason| !reason.is_empty());
+			Some(MarkerComment {
+				marker,
+				region,
+				line: i + 1,
+				column: line.chars().take_while(|c| c.is_whitespace()).count(),
+				reason: reason.map(str::to_string),
+			})
+		})
+		.collect()
+}
+
 /// A visitor wrapper that automatically skips items marked with codestyle::skip.
 ///
 /// Wrap your visitor with this to get automatic skip handling without duplicating
@@ -76,7 +204,7 @@ fn get_skip_marker_at_line(content: &str, line: usize) -> Option<SkipMarker> {
 	// Check current line (inline comment)
 	if line > 0 && line <= lines.len() {
 		let current_line = lines[line - 1];
-		if let Some(marker) = parse_skip_comment(current_line) {
+		if let Some(marker) = parse_skip_marker(current_line) {
 			return Some(marker);
 		}
 	}
@@ -84,7 +212,7 @@ fn get_skip_marker_at_line(content: &str, line: usize) -> Option<SkipMarker> {
 	// Check line above
 	if line > 1 {
 		let prev_line = lines[line - 2];
-		if let Some(marker) = parse_skip_comment(prev_line) {
+		if let Some(marker) = parse_skip_marker(prev_line) {
 			return Some(marker);
 		}
 	}
@@ -105,15 +233,19 @@ fn get_skip_marker_in_header(content: &str, start_line: usize) -> Option<SkipMar
 		return Some(marker);
 	}
 
-	// Scan forward from the span start through attribute/comment lines
+	// Scan forward from the span start through attribute/comment lines, if it starts at one at all
 	let lines: Vec<&str> = content.lines().collect();
+	let is_header = |line: &str| line.starts_with("#[") || line.starts_with("//");
+	if !lines.get(start_line.wrapping_sub(1)).is_some_and(|line| is_header(line.trim_start())) {
+		return None;
+	}
 	for line in lines.iter().skip(start_line) {
 		let trimmed = line.trim();
-		if let Some(marker) = parse_skip_comment(trimmed) {
+		if let Some(marker) = parse_skip_marker(trimmed) {
 			return Some(marker);
 		}
 		// Stop scanning when we hit a line that is neither an attribute nor a comment
-		if !trimmed.starts_with("#[") && !trimmed.starts_with("//") {
+		if !is_header(trimmed) {
 			break;
 		}
 	}
@@ -121,10 +253,15 @@ fn get_skip_marker_in_header(content: &str, start_line: usize) -> Option<SkipMar
 	None
 }
 
-/// Parse a skip comment and return the skip marker if present.
-fn parse_skip_comment(line: &str) -> Option<SkipMarker> {
+/// Parse a skip attribute or comment and return the skip marker if present.
+fn parse_skip_marker(line: &str) -> Option<SkipMarker> {
 	let trimmed = line.trim();
 
+	// #[codestyle::skip...] as a real attribute
+	if let Some(rest) = trimmed.strip_prefix("#[codestyle::skip") {
+		return parse_skip_suffix(rest);
+	}
+
 	// //#[codestyle::skip...] or // #[codestyle::skip...]
 	let after_slashes = trimmed.strip_prefix("//")?;
 	let after_slashes = after_slashes.trim_start();
@@ -143,25 +280,111 @@ fn parse_skip_comment(line: &str) -> Option<SkipMarker> {
 }
 
 /// Parse the suffix after "codestyle::skip" to determine if it's skip-all or skip-specific.
+/// A marker whose `until` date has passed, or isn't a date, doesn't skip anything.
 fn parse_skip_suffix(rest: &str) -> Option<SkipMarker> {
+	let (marker, until) = parse_skip_args(rest)?;
+	match until {
+		Some(until) if !parse_date(until).is_some_and(|date| date >= today()) => None,
+		_ => Some(marker),
+	}
+}
+
+/// The marker a "codestyle::skip" suffix stands for, and its `until` date as written, if it has one.
+fn parse_skip_args(rest: &str) -> Option<(SkipMarker, Option<&str>)> {
 	let rest = rest.trim_start();
 
-	// skip] or just end of line for @-style -> skip all
-	if rest.is_empty() || rest.starts_with(']') {
-		return Some(SkipMarker::All);
+	// skip] or just end of line for @-style, possibly followed by `: <reason>` -> skip all
+	if rest.is_empty() || rest.starts_with(']') || rest.starts_with(':') {
+		return Some((SkipMarker::All, None));
 	}
 
-	// (rule-name)] -> skip specific rule
-	if let Some(after_paren) = rest.strip_prefix('(') {
-		// Find the closing paren
-		let end = after_paren.find(')')?;
-		let rule_name = after_paren[..end].trim();
-		if !rule_name.is_empty() {
-			return Some(SkipMarker::Rule(rule_name.to_string()));
+	// (rule-name)] or (rule-name, until = "2025-09-01")] -> skip specific rule; (until = "...")] -> skip all
+	let after_paren = rest.strip_prefix('(')?;
+	// Find the closing paren
+	let end = after_paren.find(')')?;
+	let mut rule_name = String::new();
+	let mut until = None;
+	for arg in after_paren[..end].split(',') {
+		match arg.trim().strip_prefix("until").map(str::trim_start).and_then(|value| value.strip_prefix('=')) {
+			Some(value) => until = Some(value.trim().trim_matches('"')),
+			// Whitespace inside is dropped: the attribute's arguments are tokens, which may get spaced out as `pub - first`
+			None => rule_name.extend(arg.split_whitespace()),
 		}
 	}
+	match (rule_name.is_empty(), until) {
+		(false, _) => Some((SkipMarker::Rule(rule_name), until)),
+		(true, Some(_)) => Some((SkipMarker::All, until)),
+		(true, None) => None,
+	}
+}
 
-	None
+/// A skip marker past its `until` date, or with one that isn't a date.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpiredSkip {
+	pub line: usize,
+	pub column: usize,
+	/// As written
+	pub until: String,
+	/// Whether `until` is a `YYYY-MM-DD` date at all
+	pub valid: bool,
+}
+
+/// Every marker in `content`, in any form, that has stopped skipping because of its `until` date.
+pub fn expired_skips(content: &str) -> Vec<ExpiredSkip> {
+	if !content.contains("until") {
+		return Vec::new();
+	}
+	let today = today();
+	content
+		.lines()
+		.enumerate()
+		.filter_map(|(i, line)| {
+			let trimmed = line.trim();
+			let rest = match trimmed.strip_prefix("#[codestyle::skip") {
+				Some(rest) => rest,
+				None => {
+					let after_slashes = trimmed.strip_prefix("//")?.trim_start();
+					["#[codestyle::skip", "@codestyle::skip", "codestyle::skip-start"]
+						.iter()
+						.find_map(|prefix| after_slashes.strip_prefix(prefix))?
+				}
+			};
+			let until = parse_skip_args(rest)?.1?;
+			let date = parse_date(until);
+			date.is_none_or(|date| date < today).then(|| ExpiredSkip {
+				line: i + 1,
+				column: line.chars().take_while(|c| c.is_whitespace()).count(),
+				until: until.to_string(),
+				valid: date.is_some(),
+			})
+		})
+		.collect()
+}
+
+/// `(year, month, day)` of a `YYYY-MM-DD` date.
+fn parse_date(s: &str) -> Option<(i64, u32, u32)> {
+	let mut parts = s.split('-');
+	let (year, month, day) = (parts.next()?.parse().ok()?, parts.next()?.parse().ok()?, parts.next()?.parse().ok()?);
+	(parts.next().is_none() && (1..=12).contains(&month) && (1..=31).contains(&day)).then_some((year, month, day))
+}
+
+/// Today's date in UTC, as `(year, month, day)`: the date `until`s are compared to.
+pub fn today() -> (i64, u32, u32) {
+	let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs());
+	civil_from_days((secs / 86_400) as i64)
+}
+
+/// The date `days` after 1970-01-01, by Howard Hinnant's `civil_from_days`.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+	let z = days + 719_468;
+	let era = z.div_euclid(146_097);
+	let doe = z.rem_euclid(146_097);
+	let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+	let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+	let mp = (5 * doy + 2) / 153;
+	let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+	let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+	(yoe + era * 400 + i64::from(month <= 2), month, day)
 }
 
 /// Macro for container items that can have skip markers.
@@ -200,6 +423,10 @@ impl<'ast, V: Visit<'ast>> Visit<'ast> for SkipVisitor<'_, V> {
 
 	impl_skip_visit_container!(visit_item_use, syn::ItemUse);
 
+	impl_skip_visit_container!(visit_item_extern_crate, syn::ItemExternCrate);
+
+	impl_skip_visit_container!(visit_item_macro, syn::ItemMacro);
+
 	impl_skip_visit_container!(visit_expr_block, syn::ExprBlock);
 
 	impl_skip_visit_container!(visit_local, syn::Local);
@@ -211,40 +438,106 @@ mod tests {
 
 	#[test]
 	fn parse_skip_all_bracket() {
-		assert_eq!(parse_skip_comment("//#[codestyle::skip]"), Some(SkipMarker::All));
-		assert_eq!(parse_skip_comment("// #[codestyle::skip]"), Some(SkipMarker::All));
-		assert_eq!(parse_skip_comment("  //#[codestyle::skip]"), Some(SkipMarker::All));
-		assert_eq!(parse_skip_comment("  // #[codestyle::skip]  "), Some(SkipMarker::All));
+		assert_eq!(parse_skip_marker("//#[codestyle::skip]"), Some(SkipMarker::All));
+		assert_eq!(parse_skip_marker("// #[codestyle::skip]"), Some(SkipMarker::All));
+		assert_eq!(parse_skip_marker("  //#[codestyle::skip]"), Some(SkipMarker::All));
+		assert_eq!(parse_skip_marker("  // #[codestyle::skip]  "), Some(SkipMarker::All));
 	}
 
 	#[test]
 	fn parse_skip_all_at() {
-		assert_eq!(parse_skip_comment("//@codestyle::skip"), Some(SkipMarker::All));
-		assert_eq!(parse_skip_comment("// @codestyle::skip"), Some(SkipMarker::All));
-		assert_eq!(parse_skip_comment("  //@codestyle::skip"), Some(SkipMarker::All));
+		assert_eq!(parse_skip_marker("//@codestyle::skip"), Some(SkipMarker::All));
+		assert_eq!(parse_skip_marker("// @codestyle::skip"), Some(SkipMarker::All));
+		assert_eq!(parse_skip_marker("  //@codestyle::skip"), Some(SkipMarker::All));
 	}
 
 	#[test]
 	fn parse_skip_specific_rule_bracket() {
-		assert_eq!(parse_skip_comment("//#[codestyle::skip(pub-first)]"), Some(SkipMarker::Rule("pub-first".to_string())));
+		assert_eq!(parse_skip_marker("//#[codestyle::skip(pub-first)]"), Some(SkipMarker::Rule("pub-first".to_string())));
 		assert_eq!(
-			parse_skip_comment("// #[codestyle::skip(ignored-error-comment)]"),
+			parse_skip_marker("// #[codestyle::skip(ignored-error-comment)]"),
 			Some(SkipMarker::Rule("ignored-error-comment".to_string()))
 		);
-		assert_eq!(parse_skip_comment("//#[codestyle::skip( loop-comment )]"), Some(SkipMarker::Rule("loop-comment".to_string())));
+		assert_eq!(parse_skip_marker("//#[codestyle::skip( loop-comment )]"), Some(SkipMarker::Rule("loop-comment".to_string())));
 	}
 
 	#[test]
 	fn parse_skip_specific_rule_at() {
-		assert_eq!(parse_skip_comment("//@codestyle::skip(pub-first)"), Some(SkipMarker::Rule("pub-first".to_string())));
-		assert_eq!(parse_skip_comment("// @codestyle::skip(no-chrono)"), Some(SkipMarker::Rule("no-chrono".to_string())));
+		assert_eq!(parse_skip_marker("//@codestyle::skip(pub-first)"), Some(SkipMarker::Rule("pub-first".to_string())));
+		assert_eq!(parse_skip_marker("// @codestyle::skip(no-chrono)"), Some(SkipMarker::Rule("no-chrono".to_string())));
+	}
+
+	#[test]
+	fn parse_skip_attribute() {
+		assert_eq!(parse_skip_marker("#[codestyle::skip]"), Some(SkipMarker::All));
+		assert_eq!(parse_skip_marker("\t#[codestyle::skip] fn foo() {}"), Some(SkipMarker::All));
+		assert_eq!(parse_skip_marker("#[codestyle::skip(pub-first)]"), Some(SkipMarker::Rule("pub-first".to_string())));
+		assert_eq!(parse_skip_marker("#[codestyle::skip(pub - first)]"), Some(SkipMarker::Rule("pub-first".to_string())));
+		assert_eq!(parse_skip_marker("#[codestyle::skipper]"), None);
 	}
 
 	#[test]
 	fn parse_skip_not_a_skip() {
-		assert_eq!(parse_skip_comment("// some other comment"), None);
-		assert_eq!(parse_skip_comment("let x = 1;"), None);
-		assert_eq!(parse_skip_comment("// codestyle::skip"), None); // missing # or @
+		assert_eq!(parse_skip_marker("// some other comment"), None);
+		assert_eq!(parse_skip_marker("let x = 1;"), None);
+		assert_eq!(parse_skip_marker("// codestyle::skip"), None); // missing # or @
+	}
+
+	#[test]
+	fn regions_pair_up_innermost_first() {
+		let content = "// codestyle::skip-start\nfn a() {}\n\t// codestyle::skip-start(pub-first)\nfn b() {}\n\t// codestyle::skip-end\n// codestyle::skip-end\n";
+		let (regions, unmatched) = skip_regions(content);
+		assert_eq!(
+			regions,
+			vec![
+				SkipRegion { marker: SkipMarker::Rule("pub-first".to_string()), start_line: 3, end_line: 5 },
+				SkipRegion { marker: SkipMarker::All, start_line: 1, end_line: 6 },
+			]
+		);
+		assert!(unmatched.is_empty());
+		assert!(regions[0].covers(4, "pub-first") && !regions[0].covers(4, "loop-comment") && !regions[0].covers(6, "pub-first"));
+		assert!(regions[1].covers(6, "loop-comment"));
+	}
+
+	#[test]
+	fn unmatched_region_markers() {
+		let content = "// codestyle::skip-end\nfn a() {}\n  // codestyle::skip-start(loop-comment)\n";
+		let (regions, unmatched) = skip_regions(content);
+		assert!(regions.is_empty());
+		assert_eq!(unmatched, vec![UnmatchedMarker::End(1, 0), UnmatchedMarker::Start(3, 2)]);
+	}
+
+	#[test]
+	fn marker_reasons() {
+		let content = "//@codestyle::skip(pub-first): keeps re-exports together\nfn a() {}\n\t// #[codestyle::skip]:\n// codestyle::skip-start: generated by hand\n// plain comment\n";
+		let reasons: Vec<_> = marker_comments(content).into_iter().map(|m| (m.line, m.column, m.reason)).collect();
+		assert_eq!(
+			reasons,
+			vec![(1, 0, Some("keeps re-exports together".to_string())), (3, 1, None), (4, 0, Some("generated by hand".to_string()))]
+		);
+		assert_eq!(parse_skip_marker("//@codestyle::skip: reason"), Some(SkipMarker::All));
+	}
+
+	#[test]
+	fn until_dates() {
+		assert_eq!(civil_from_days(0), (1970, 1, 1));
+		assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+		assert_eq!(civil_from_days(20_332), (2025, 9, 1));
+		assert_eq!(parse_date("2025-09-01"), Some((2025, 9, 1)));
+		assert_eq!(parse_date("2025-13-01"), None);
+		assert_eq!(parse_date("next week"), None);
+	}
+
+	#[test]
+	fn parse_skip_until() {
+		assert_eq!(parse_skip_marker(r#"//@codestyle::skip(pub-first, until = "2999-01-01")"#), Some(SkipMarker::Rule("pub-first".to_string())));
+		assert_eq!(parse_skip_marker(r#"#[codestyle::skip(until = "2999-01-01")]"#), Some(SkipMarker::All));
+		assert_eq!(parse_skip_marker(r#"//@codestyle::skip(pub-first, until = "2000-01-01")"#), None);
+		assert_eq!(parse_skip_marker(r#"//@codestyle::skip(pub-first, until = "soon")"#), None);
+
+		let content = "//@codestyle::skip(pub-first, until = \"2000-01-01\")\nfn a() {}\n  // codestyle::skip-start(until = \"soon\")\n// @codestyle::skip(until = \"2999-01-01\")\n";
+		let expired: Vec<_> = expired_skips(content).into_iter().map(|e| (e.line, e.column, e.until, e.valid)).collect();
+		assert_eq!(expired, vec![(1, 0, "2000-01-01".to_string(), true), (3, 2, "soon".to_string(), false)]);
 	}
 
 	#[test]
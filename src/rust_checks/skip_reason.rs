@@ -0,0 +1,49 @@
+//! Lint requiring skip marker comments to say why they skip, e.g. `//@codestyle::skip(pub-first): <reason>`.
+
+use std::path::Path;
+
+use super::{
+	Severity, Violation,
+	rules::{Context, Rule, RuleInfo},
+	skip::marker_comments,
+};
+
+pub(super) const RULE: &str = "skip-reason";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "skip_reason",
+	autofix: false,
+	triggers: &["codestyle::skip"],
+	description: "Require a reason on codestyle::skip marker comments",
+	rationale: "A suppression nobody can explain later is never revisited; writing down why it's there keeps them auditable.",
+	bad: "//@codestyle::skip(pub-first)\nmod reexports;",
+	good: "//@codestyle::skip(pub-first): re-exports read best next to their imports\nmod reexports;",
+	fix: "Add `: <reason>` after the marker.",
+};
+
+pub struct SkipReason;
+impl Rule for SkipReason {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents)
+	}
+}
+
+pub fn check(path: &Path, content: &str) -> Vec<Violation> {
+	marker_comments(content)
+		.into_iter()
+		.filter(|marker| marker.reason.is_none())
+		.map(|marker| Violation {
+			rule: RULE,
+			file: path.display().to_string(),
+			line: marker.line,
+			column: marker.column,
+			message: "skip marker without a reason\nHINT: add `: <reason>` after it, saying why the code can't follow the rule".to_string(),
+			severity: Severity::Error,
+			..Default::default()
+		})
+		.collect()
+}
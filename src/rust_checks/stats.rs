@@ -0,0 +1,288 @@
+//! Read-only codebase metrics behind `codestyle rust stats`.
+//!
+//! Violation density is measured with every rule enabled, regardless of the flags passed,
+//! so it can be used to decide which rules to turn on first. Counts are also broken down per crate and per directory,
+//! to see where cleanup would pay off most.
+
+use std::{
+	collections::{BTreeMap, HashMap},
+	fmt, fs,
+	path::{Path, PathBuf},
+};
+
+use serde_json::{Value, json};
+use syn::{ExprUnsafe, ImplItemFn, ItemFn, ItemImpl, Signature, TraitItemFn, spanned::Spanned, visit::Visit};
+
+use super::{
+	FileInfo, OutputFormat, RustCheckOptions, cargo_dep_ordering, check_file, collect_cargo_tomls, collect_rust_files,
+	exclude::{DiscoveryOptions, Exclude},
+	find_src_dirs,
+	rustfmt::RustfmtConfig,
+};
+
+/// Upper bounds (inclusive) of the function length buckets; the last bucket is open-ended.
+const FN_LENGTH_BUCKETS: &[usize] = &[10, 25, 50, 100];
+/// How many types to list in the impl-block breakdown.
+const TOP_IMPL_TYPES: usize = 10;
+/// How many directories to list in the text violation breakdown; JSON has them all.
+const TOP_DIRS: usize = 10;
+/// Crate label for files outside any package below the root
+const NO_CRATE: &str = "(no crate)";
+
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+	/// What crate and directory names are relative to
+	pub root: PathBuf,
+	pub files: usize,
+	/// Lines with anything but whitespace on them, so that blank ones don't dilute violation densities
+	pub lines: usize,
+	/// Line counts of every fn with a body: free fns, methods, and default trait methods
+	pub fn_lengths: Vec<usize>,
+	pub async_fns: usize,
+	pub unsafe_blocks: usize,
+	/// Inherent and trait impl blocks, keyed by the implementing type's name
+	pub impls_per_type: BTreeMap<String, usize>,
+	pub violations_per_rule: BTreeMap<&'static str, usize>,
+	/// Keyed by the package directory relative to `root`, `.` for the root package
+	pub violations_per_crate: BTreeMap<String, usize>,
+	/// Keyed by the file's directory relative to `root`
+	pub violations_per_dir: BTreeMap<String, usize>,
+	/// Whether each directory looked at so far holds a package manifest
+	is_package: HashMap<PathBuf, bool>,
+}
+impl Stats {
+	pub fn new(root: &Path) -> Self {
+		Self {
+			root: root.to_path_buf(),
+			..Self::default()
+		}
+	}
+
+	pub fn add_file(&mut self, info: &FileInfo) {
+		self.files += 1;
+		self.lines += info.contents.lines().filter(|line| !line.trim().is_empty()).count();
+
+		if let Some(ref tree) = info.syntax_tree {
+			let mut visitor = StatsVisitor { stats: self };
+			visitor.visit_file(tree);
+		}
+
+		for v in check_file(info, &RustCheckOptions::strict(), &RustfmtConfig::default()) {
+			self.add_violation(&info.path, v.rule);
+		}
+	}
+
+	/// Count a violation of `rule` in `file`, under its rule, crate and directory.
+	pub fn add_violation(&mut self, file: &Path, rule: &'static str) {
+		let dir = file.parent().unwrap_or(Path::new(""));
+		let root = self.root.clone();
+		let package = dir.ancestors().take_while(|d| d.starts_with(&root)).find(|d| self.is_package(d));
+		let package = package.map_or_else(|| NO_CRATE.to_string(), |p| self.relative(p));
+		*self.violations_per_rule.entry(rule).or_default() += 1;
+		*self.violations_per_crate.entry(package).or_default() += 1;
+		*self.violations_per_dir.entry(self.relative(dir)).or_default() += 1;
+	}
+
+	/// Machine-readable stats, for `--format json`.
+	pub fn to_json(&self) -> Value {
+		let lengths = self.sorted_fn_lengths();
+		let length_summary = (!lengths.is_empty()).then(|| {
+			json!({
+				"min": lengths[0],
+				"median": percentile(&lengths, 50),
+				"p90": percentile(&lengths, 90),
+				"max": lengths[lengths.len() - 1],
+			})
+		});
+		json!({
+			"files": self.files,
+			"lines": self.lines,
+			"functions": { "count": lengths.len(), "async": self.async_fns, "length": length_summary },
+			"unsafe_blocks": self.unsafe_blocks,
+			"impls_per_type": self.impls_per_type,
+			"violations": {
+				"per_rule": self.violations_per_rule,
+				"per_crate": self.violations_per_crate,
+				"per_dir": self.violations_per_dir,
+			},
+		})
+	}
+
+	fn sorted_fn_lengths(&self) -> Vec<usize> {
+		let mut lengths = self.fn_lengths.clone();
+		lengths.sort_unstable();
+		lengths
+	}
+
+	/// Whether `dir` has a `Cargo.toml` with a `[package]`, as opposed to none or a virtual workspace manifest.
+	fn is_package(&mut self, dir: &Path) -> bool {
+		*self
+			.is_package
+			.entry(dir.to_path_buf())
+			.or_insert_with(|| fs::read_to_string(dir.join("Cargo.toml")).is_ok_and(|content| content.parse::<toml::Table>().is_ok_and(|manifest| manifest.contains_key("package"))))
+	}
+
+	fn relative(&self, dir: &Path) -> String {
+		match dir.strip_prefix(&self.root) {
+			Ok(rel) if rel.as_os_str().is_empty() => ".".to_string(),
+			Ok(rel) => rel.display().to_string(),
+			Err(_) => dir.display().to_string(),
+		}
+	}
+}
+impl fmt::Display for Stats {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		writeln!(f, "Files: {}, lines: {}", self.files, self.lines)?;
+
+		let lengths = self.sorted_fn_lengths();
+		writeln!(f, "\nFunctions: {} ({} async)", lengths.len(), self.async_fns)?;
+		if !lengths.is_empty() {
+			writeln!(
+				f,
+				"  length: min {}, median {}, p90 {}, max {}",
+				lengths[0],
+				percentile(&lengths, 50),
+				percentile(&lengths, 90),
+				lengths[lengths.len() - 1]
+			)?;
+			let mut lower = 1;
+			for &upper in FN_LENGTH_BUCKETS {
+				let count = lengths.iter().filter(|&&l| (lower..=upper).contains(&l)).count();
+				writeln!(f, "  {lower}-{upper} lines: {count}")?;
+				lower = upper + 1;
+			}
+			writeln!(f, "  {lower}+ lines: {}", lengths.iter().filter(|&&l| l >= lower).count())?;
+		}
+
+		writeln!(f, "\nUnsafe blocks: {}", self.unsafe_blocks)?;
+
+		let impls = by_count(&self.impls_per_type);
+		writeln!(f, "\nImpl blocks per type ({} types):", impls.len())?;
+		for (name, count) in impls.iter().take(TOP_IMPL_TYPES) {
+			writeln!(f, "  {name}: {count}")?;
+		}
+
+		let mut violations: Vec<(&&str, &usize)> = self.violations_per_rule.iter().collect();
+		violations.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+		write!(f, "\nViolations per rule (per 1k lines, all rules enabled):")?;
+		if violations.is_empty() {
+			write!(f, "\n  none")?;
+		}
+		for (rule, count) in violations {
+			let density = *count as f64 * 1000.0 / self.lines.max(1) as f64;
+			write!(f, "\n  {rule}: {count} ({density:.1})")?;
+		}
+		if self.violations_per_rule.is_empty() {
+			return Ok(());
+		}
+
+		write!(f, "\n\nViolations per crate:")?;
+		for (name, count) in by_count(&self.violations_per_crate) {
+			write!(f, "\n  {name}: {count}")?;
+		}
+		let dirs = by_count(&self.violations_per_dir);
+		write!(f, "\n\nViolations per directory ({} directories):", dirs.len())?;
+		for (name, count) in dirs.iter().take(TOP_DIRS) {
+			write!(f, "\n  {name}: {count}")?;
+		}
+		Ok(())
+	}
+}
+
+/// `discovery` configures which paths are skipped while looking for files. Only `--format json` changes the output;
+/// every other format prints the text report.
+pub fn run_stats(target_dir: &Path, discovery: &DiscoveryOptions, format: OutputFormat) -> i32 {
+	if !target_dir.exists() {
+		eprintln!("Target directory does not exist: {target_dir:?}");
+		return 1;
+	}
+	let exclude = match Exclude::new(target_dir, discovery) {
+		Ok(exclude) => exclude,
+		Err(e) => {
+			eprintln!("{e}");
+			return 1;
+		}
+	};
+
+	let src_dirs = find_src_dirs(target_dir);
+	if src_dirs.is_empty() {
+		eprintln!("No source directories found");
+		return 1;
+	}
+
+	let mut stats = Stats::new(target_dir);
+	for src_dir in src_dirs {
+		for info in &collect_rust_files(&src_dir, &exclude) {
+			stats.add_file(info);
+		}
+	}
+	for toml_path in collect_cargo_tomls(target_dir) {
+		if let Ok(content) = fs::read_to_string(&toml_path) {
+			for v in cargo_dep_ordering::check(&toml_path, &content) {
+				stats.add_violation(&toml_path, v.rule);
+			}
+		}
+	}
+
+	match format {
+		OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&stats.to_json()).expect("stats are always serializable")),
+		_ => println!("{stats}"),
+	}
+	0
+}
+
+/// The `p`th percentile of the sorted, non-empty `values`.
+fn percentile(values: &[usize], p: usize) -> usize {
+	values[(values.len() * p).div_ceil(100).saturating_sub(1)]
+}
+
+/// Entries from most to fewest, ties by name.
+fn by_count(counts: &BTreeMap<String, usize>) -> Vec<(&String, &usize)> {
+	let mut entries: Vec<(&String, &usize)> = counts.iter().collect();
+	entries.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+	entries
+}
+
+struct StatsVisitor<'a> {
+	stats: &'a mut Stats,
+}
+impl StatsVisitor<'_> {
+	fn record_fn(&mut self, sig: &Signature, body: &syn::Block) {
+		self.stats.fn_lengths.push(body.span().end().line - sig.span().start().line + 1);
+		if sig.asyncness.is_some() {
+			self.stats.async_fns += 1;
+		}
+	}
+}
+impl<'ast> Visit<'ast> for StatsVisitor<'_> {
+	fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+		self.record_fn(&node.sig, &node.block);
+		syn::visit::visit_item_fn(self, node);
+	}
+
+	fn visit_impl_item_fn(&mut self, node: &'ast ImplItemFn) {
+		self.record_fn(&node.sig, &node.block);
+		syn::visit::visit_impl_item_fn(self, node);
+	}
+
+	fn visit_trait_item_fn(&mut self, node: &'ast TraitItemFn) {
+		if let Some(ref body) = node.default {
+			self.record_fn(&node.sig, body);
+		}
+		syn::visit::visit_trait_item_fn(self, node);
+	}
+
+	fn visit_expr_unsafe(&mut self, node: &'ast ExprUnsafe) {
+		self.stats.unsafe_blocks += 1;
+		syn::visit::visit_expr_unsafe(self, node);
+	}
+
+	fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
+		if let syn::Type::Path(type_path) = &*node.self_ty
+			&& let Some(segment) = type_path.path.segments.last()
+		{
+			*self.stats.impls_per_type.entry(segment.ident.to_string()).or_default() += 1;
+		}
+		syn::visit::visit_item_impl(self, node);
+	}
+}
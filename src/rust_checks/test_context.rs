@@ -0,0 +1,108 @@
+//! Which code is test code: files under a `tests/` directory, `#[cfg(test)]` modules and `#[test]` functions.
+//!
+//! Rules get it through [`super::rules::Context::tests`]. Violations of the options listed in `test_exempt` are dropped
+//! from test code for every rule at once, so a rule only needs to look itself when it treats tests differently rather
+//! than not at all.
+
+use std::{
+	ops::RangeInclusive,
+	path::{Component, Path},
+};
+
+use syn::{Attribute, ItemFn, ItemMod, spanned::Spanned, visit::Visit};
+
+/// Test code of one file, by line.
+#[derive(Clone, Debug, Default)]
+pub struct TestContext {
+	/// The whole file is a test, e.g. an integration test under `tests/`
+	whole_file: bool,
+	/// 1-based lines of each test module or function, attributes included
+	ranges: Vec<RangeInclusive<usize>>,
+}
+impl TestContext {
+	pub fn new(path: &Path, tree: &syn::File) -> Self {
+		let whole_file = is_test_file(path);
+		let mut visitor = TestItemVisitor::default();
+		if !whole_file {
+			visitor.visit_file(tree);
+		}
+		Self { whole_file, ranges: visitor.ranges }
+	}
+
+	/// Whether the 1-based `line` is test code.
+	pub fn contains(&self, line: usize) -> bool {
+		self.whole_file || self.ranges.iter().any(|range| range.contains(&line))
+	}
+}
+
+/// Whether the file at `path` is a test file as a whole: anything under a `tests` directory.
+pub fn is_test_file(path: &Path) -> bool {
+	path.parent().is_some_and(|dir| dir.components().any(|c| c == Component::Normal("tests".as_ref())))
+}
+
+/// `#[test]`, `#[rstest]`, or anything else whose path ends in `test`, like `#[tokio::test]`.
+pub fn is_test_attr(attr: &Attribute) -> bool {
+	let path = attr.path();
+	path.is_ident("rstest") || path.segments.last().is_some_and(|last| last.ident == "test")
+}
+
+/// `#[cfg(test)]`, also inside `all(..)`/`any(..)`. A `not(test)` anywhere means it isn't.
+pub fn is_cfg_test_attr(attr: &Attribute) -> bool {
+	if !attr.path().is_ident("cfg") {
+		return false;
+	}
+	let Ok(list) = attr.meta.require_list() else {
+		return false;
+	};
+	let tokens = list.tokens.to_string();
+	let mut words = tokens.split(|c: char| !(c.is_alphanumeric() || c == '_'));
+	words.clone().any(|word| word == "test") && !words.any(|word| word == "not")
+}
+
+#[derive(Default)]
+struct TestItemVisitor {
+	ranges: Vec<RangeInclusive<usize>>,
+}
+impl TestItemVisitor {
+	fn push(&mut self, span: proc_macro2::Span) {
+		self.ranges.push(span.start().line..=span.end().line);
+	}
+}
+
+impl<'a> Visit<'a> for TestItemVisitor {
+	fn visit_item_mod(&mut self, node: &'a ItemMod) {
+		if node.attrs.iter().any(is_cfg_test_attr) {
+			self.push(node.span());
+			return;
+		}
+		syn::visit::visit_item_mod(self, node);
+	}
+
+	fn visit_item_fn(&mut self, node: &'a ItemFn) {
+		if node.attrs.iter().any(|attr| is_test_attr(attr) || is_cfg_test_attr(attr)) {
+			self.push(node.span());
+			return;
+		}
+		syn::visit::visit_item_fn(self, node);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn cfg_test_modules_and_test_fns() {
+		let source = "fn run() {}\n\n#[cfg(test)]\nmod tests {\n\tfn helper() {}\n}\n\n#[tokio::test]\nasync fn works() {}\n\n#[cfg(not(test))]\nfn real() {}\n";
+		let tests = TestContext::new(Path::new("src/lib.rs"), &syn::parse_file(source).unwrap());
+		let lines: Vec<usize> = (1..=source.lines().count()).filter(|&line| tests.contains(line)).collect();
+		assert_eq!(lines, [3, 4, 5, 6, 8, 9]);
+	}
+
+	#[test]
+	fn files_under_tests_are_tests() {
+		let tree = syn::parse_file("fn helper() {}\n").unwrap();
+		assert!(TestContext::new(Path::new("tests/integration/main.rs"), &tree).contains(1));
+		assert!(!TestContext::new(Path::new("src/tests.rs"), &tree).contains(1));
+	}
+}
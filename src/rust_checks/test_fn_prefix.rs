@@ -5,11 +5,40 @@
 
 use std::path::Path;
 
-use syn::{Attribute, ItemFn, visit::Visit};
+use syn::{ItemFn, visit::Visit};
 
-use super::{Fix, Violation, skip::SkipVisitor};
+use super::{
+	Fix, Severity, Violation,
+	line_index::LineIndex,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+	test_context::is_test_attr,
+};
 
 const RULE: &str = "test-fn-prefix";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "test_fn_prefix",
+	autofix: true,
+	triggers: &["test_"],
+	description: "Disallow the redundant test_ prefix on test functions",
+	rationale: "`#[test]` already says it's a test, and the test runner prints the module path; `test_` only adds noise to every name.",
+	bad: "#[test]\nfn test_parses_empty() {}",
+	good: "#[test]\nfn parses_empty() {}",
+	fix: "Renames the function without the `test_` prefix.",
+};
+
+pub struct TestFnPrefix;
+impl Rule for TestFnPrefix {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
 pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
 	let visitor = TestFnPrefixVisitor::new(path, content);
 	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
@@ -19,7 +48,7 @@ pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
 
 struct TestFnPrefixVisitor<'a> {
 	path_str: String,
-	content: &'a str,
+	lines: LineIndex<'a>,
 	violations: Vec<Violation>,
 }
 
@@ -27,7 +56,7 @@ impl<'a> TestFnPrefixVisitor<'a> {
 	fn new(path: &Path, content: &'a str) -> Self {
 		Self {
 			path_str: path.display().to_string(),
-			content,
+			lines: LineIndex::new(content),
 			violations: Vec::new(),
 		}
 	}
@@ -45,8 +74,8 @@ impl<'a> TestFnPrefixVisitor<'a> {
 		let new_name = fn_name.strip_prefix("test_").unwrap();
 		let span = func.sig.ident.span();
 
-		let fix = span_to_byte(self.content, span.start()).and_then(|start| {
-			span_to_byte(self.content, span.end()).map(|end| Fix {
+		let fix = self.lines.offset_of(span.start()).and_then(|start| {
+			self.lines.offset_of(span.end()).map(|end| Fix {
 				start_byte: start,
 				end_byte: end,
 				replacement: new_name.to_string(),
@@ -58,8 +87,11 @@ impl<'a> TestFnPrefixVisitor<'a> {
 			file: self.path_str.clone(),
 			line: span.start().line,
 			column: span.start().column,
+			end: Some((span.end().line, span.end().column)),
 			message: format!("test function `{fn_name}` has redundant `test_` prefix"),
+			severity: Severity::Error,
 			fix,
+			..Default::default()
 		});
 	}
 }
@@ -74,47 +106,3 @@ impl<'a> Visit<'a> for TestFnPrefixVisitor<'a> {
 fn has_test_attr(func: &ItemFn) -> bool {
 	func.attrs.iter().any(is_test_attr)
 }
-
-fn is_test_attr(attr: &Attribute) -> bool {
-	let path = attr.path();
-
-	// Check for #[test]
-	if path.is_ident("test") {
-		return true;
-	}
-
-	// Check for #[rstest]
-	if path.is_ident("rstest") {
-		return true;
-	}
-
-	// Check for #[tokio::test] or similar paths ending in "test"
-	if let Some(last) = path.segments.last()
-		&& last.ident == "test"
-	{
-		return true;
-	}
-
-	false
-}
-
-fn span_to_byte(content: &str, pos: proc_macro2::LineColumn) -> Option<usize> {
-	let mut current_line = 1;
-	let mut line_start = 0;
-
-	for (i, ch) in content.char_indices() {
-		if current_line == pos.line {
-			return Some(line_start + pos.column);
-		}
-		if ch == '\n' {
-			current_line += 1;
-			line_start = i + 1;
-		}
-	}
-
-	if current_line == pos.line {
-		return Some(line_start + pos.column);
-	}
-
-	None
-}
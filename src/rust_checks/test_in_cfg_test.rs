@@ -0,0 +1,93 @@
+//! Lint to keep `#[test]` functions in `src/` inside `#[cfg(test)]` modules.
+//!
+//! A test fn outside one compiles into every build along with whatever it imports: dead code, and unused-import
+//! warnings in non-test builds. Integration tests under a `tests/` directory are exempt, and so are files that are
+//! test modules as a whole: `tests.rs`/`test.rs` (declared behind a `#[cfg(test)] mod tests;`) and files starting with
+//! `#![cfg(test)]`.
+
+use std::path::{Component, Path};
+
+use syn::{ItemFn, ItemMod, visit::Visit};
+
+use super::{
+	Severity, Violation,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+	test_context::{is_cfg_test_attr, is_test_attr, is_test_file},
+};
+
+const RULE: &str = "test-in-cfg-test";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "test_in_cfg_test",
+	autofix: false,
+	triggers: &["test"],
+	description: "Require #[test] fns in src/ to be inside a #[cfg(test)] module",
+	rationale: "A test outside `#[cfg(test)]` drags its helpers and imports into every build, where they're dead code.",
+	bad: "fn parse(s: &str) -> u8 { s.parse().unwrap_or(0) }\n\n#[test]\nfn parses() {\n\tassert_eq!(parse(\"1\"), 1);\n}",
+	good: "fn parse(s: &str) -> u8 { s.parse().unwrap_or(0) }\n\n#[cfg(test)]\nmod tests {\n\tuse super::*;\n\n\t#[test]\n\tfn parses() {\n\t\tassert_eq!(parse(\"1\"), 1);\n\t}\n}",
+	fix: "Move the test into the file's `#[cfg(test)] mod tests`, creating one if needed.",
+};
+
+pub struct TestInCfgTest;
+impl Rule for TestInCfgTest {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+	if !is_source_file(path) || file.attrs.iter().any(is_cfg_test_attr) {
+		return Vec::new();
+	}
+	let visitor = TestFnVisitor {
+		path_str: path.display().to_string(),
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+/// Whether `path` is under a `src/` directory, and isn't test code as a whole.
+fn is_source_file(path: &Path) -> bool {
+	let in_src = path.components().any(|c| c == Component::Normal("src".as_ref()));
+	let is_test_module = path.file_stem().is_some_and(|stem| stem == "tests" || stem == "test");
+	in_src && !is_test_module && !is_test_file(path)
+}
+
+struct TestFnVisitor {
+	path_str: String,
+	violations: Vec<Violation>,
+}
+
+impl<'a> Visit<'a> for TestFnVisitor {
+	fn visit_item_mod(&mut self, node: &'a ItemMod) {
+		// Anything goes inside a test module
+		if !node.attrs.iter().any(is_cfg_test_attr) {
+			syn::visit::visit_item_mod(self, node);
+		}
+	}
+
+	fn visit_item_fn(&mut self, node: &'a ItemFn) {
+		if node.attrs.iter().any(is_test_attr) && !node.attrs.iter().any(is_cfg_test_attr) {
+			let start = node.sig.fn_token.span.start();
+			let end = node.sig.ident.span().end();
+			self.violations.push(Violation {
+				rule: RULE,
+				file: self.path_str.clone(),
+				line: start.line,
+				column: start.column,
+				end: Some((end.line, end.column)),
+				message: format!("test `{}` outside a `#[cfg(test)]` module\nHINT: move it into the file's `mod tests`", node.sig.ident),
+				severity: Severity::Error,
+				..Default::default()
+			});
+		}
+		syn::visit::visit_item_fn(self, node);
+	}
+}
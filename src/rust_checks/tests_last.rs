@@ -0,0 +1,96 @@
+//! Lint to keep inline `#[cfg(test)] mod tests { .. }` blocks at the bottom of their file.
+//!
+//! Like [`super::pub_first`], this is about a predictable layout: the code a file is about reads first, and its tests
+//! always sit where the reader expects them. Several test modules are fine as long as nothing but test modules follows
+//! them; a `#[cfg(test)] mod tests;` declaration lives with the other `mod` declarations and isn't checked.
+
+use std::path::Path;
+
+use syn::{Item, ItemMod, spanned::Spanned};
+
+use super::{
+	Fix, Severity, Violation,
+	line_index::LineIndex,
+	pub_first::find_item_text_start,
+	rules::{Context, Rule, RuleInfo},
+	skip::has_skip_marker_for_rule,
+	test_context::is_cfg_test_attr,
+};
+
+const RULE: &str = "tests-last";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "tests_last",
+	autofix: true,
+	triggers: &["cfg(test)"],
+	description: "Keep #[cfg(test)] mod tests at the bottom of the file",
+	rationale: "Tests are read after the code they test; with the module always last, neither has to be searched for.",
+	bad: "#[cfg(test)]\nmod tests {\n\t#[test]\n\tfn parses() {}\n}\n\nfn parse() {}",
+	good: "fn parse() {}\n\n#[cfg(test)]\nmod tests {\n\t#[test]\n\tfn parses() {}\n}",
+	fix: "Moves the test module, with its attributes and doc comments, to the end of the file.",
+};
+
+pub struct TestsLast;
+impl Rule for TestsLast {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+	let Some(last_code) = file.items.iter().rposition(|item| test_module(item).is_none() && !is_test_declaration(item)) else {
+		return Vec::new();
+	};
+	let lines = LineIndex::new(content);
+	// One at a time, like `pub_first`: the fix moves the module, which shifts everything after it
+	let Some(module) = file.items[..last_code]
+		.iter()
+		.filter_map(test_module)
+		.find(|module| !has_skip_marker_for_rule(content, module.span(), RULE))
+	else {
+		return Vec::new();
+	};
+	let start = module.mod_token.span.start();
+	vec![Violation {
+		rule: RULE,
+		file: path.display().to_string(),
+		line: start.line,
+		column: start.column,
+		end: Some((module.ident.span().end().line, module.ident.span().end().column)),
+		message: format!("`#[cfg(test)] mod {}` isn't the last item in the file\nHINT: move it to the bottom", module.ident),
+		severity: Severity::Error,
+		fix: move_to_end(&lines, module),
+		..Default::default()
+	}]
+}
+
+/// `item` if it's an inline `#[cfg(test)]` module.
+fn test_module(item: &Item) -> Option<&ItemMod> {
+	match item {
+		Item::Mod(module) if module.content.is_some() && module.attrs.iter().any(is_cfg_test_attr) => Some(module),
+		_ => None,
+	}
+}
+
+/// Whether `item` is an out-of-line `#[cfg(test)] mod ..;`, which may sit anywhere.
+fn is_test_declaration(item: &Item) -> bool {
+	matches!(item, Item::Mod(module) if module.content.is_none() && module.attrs.iter().any(is_cfg_test_attr))
+}
+
+/// Cuts `module` out and appends it to the end of the file, a blank line apart from what's now above it.
+fn move_to_end(lines: &LineIndex, module: &ItemMod) -> Option<Fix> {
+	let content = lines.text();
+	let start_byte = find_item_text_start(lines, lines.offset_of(module.span().start())?);
+	let module_end = lines.line_end(lines.offset_of(module.span().end())?);
+	let module_text = &content[start_byte..module_end];
+	let rest = content[module_end..].trim_start_matches(['\n', '\r']).trim_end();
+	Some(Fix {
+		start_byte,
+		end_byte: content.len(),
+		replacement: format!("{rest}\n\n{module_text}\n"),
+	})
+}
@@ -0,0 +1,111 @@
+//! Lint to require an issue reference in `TODO`, `FIXME` and `HACK` comments, the comment counterpart of
+//! [`super::todo_issue`].
+//!
+//! `syn` drops comments, so they're found by [`super::comments`]' lexer rather than in the tree, doc comments
+//! included. A marker is one of the `todo_comment_markers` as a word of its own, case and all, so `TODOs` or `todo`
+//! don't count. What follows it has to match `todo_issue_pattern`, in the comment itself or in the line comments
+//! right under it that continue it, e.g. `// TODO(#42): ..` or `// FIXME: see https://..`.
+
+use std::path::Path;
+
+use regex::Regex;
+
+use super::{
+	Severity, Violation,
+	comments::{self, Comment},
+	line_index::LineIndex,
+	rules::{Context, Rule, RuleInfo},
+	skip::has_skip_marker_for_rule_at_line,
+};
+
+const RULE: &str = "todo-comment";
+/// Markers the rule looks for, unless `todo_comment_markers` says otherwise
+pub const DEFAULT_MARKERS: &[&str] = &["TODO", "FIXME", "HACK"];
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "todo_comment",
+	autofix: false,
+	triggers: &[],
+	description: "Require an issue reference in TODO, FIXME and HACK comments",
+	rationale: "A `TODO` nobody tracks is read past until it's forgotten; linking the issue keeps the remaining work where it gets planned.",
+	bad: "// TODO: retry on timeouts\nlet response = client.send(request).await?;",
+	good: "// TODO(#88): retry on timeouts\nlet response = client.send(request).await?;",
+	fix: "Open an issue for the remaining work and reference it in the comment, as `todo_issue_pattern` expects.",
+};
+
+pub struct TodoComment;
+impl Rule for TodoComment {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, &ctx.opts.todo_comment_markers, &ctx.opts.todo_issue_pattern)
+	}
+}
+
+pub fn check(path: &Path, content: &str, markers: &[String], pattern: &Regex) -> Vec<Violation> {
+	let lines = LineIndex::new(content);
+	let comments = comments::comments(content);
+	let mut violations = Vec::new();
+	for (i, comment) in comments.iter().enumerate() {
+		let Some((marker, at)) = find_marker(comment.text, markers) else {
+			continue;
+		};
+		let following = continuation(&lines, &comments[i + 1..], comment, markers);
+		if std::iter::once(&comment.text[at..]).chain(following).any(|text| pattern.is_match(text)) {
+			continue;
+		}
+		let (line, column) = lines.position(comment.start + at);
+		if has_skip_marker_for_rule_at_line(content, line, RULE) {
+			continue;
+		}
+		violations.push(Violation {
+			rule: RULE,
+			file: path.display().to_string(),
+			line,
+			column,
+			end: Some((line, column + marker.chars().count())),
+			message: format!("`{marker}` comment without an issue reference\nHINT: reference the issue tracking it, so it matches `{pattern}`"),
+			severity: Severity::Error,
+			..Default::default()
+		});
+	}
+	violations
+}
+
+/// The first of `markers` in `text` as a word of its own, with its byte offset.
+fn find_marker<'a>(text: &str, markers: &'a [String]) -> Option<(&'a str, usize)> {
+	let is_word = |c: char| c.is_alphanumeric() || c == '_';
+	markers
+		.iter()
+		.filter(|marker| !marker.is_empty())
+		.flat_map(|marker| text.match_indices(marker.as_str()).map(move |(at, _)| (marker.as_str(), at)))
+		.filter(|(marker, at)| {
+			let before = text[..*at].chars().next_back();
+			let after = text[at + marker.len()..].chars().next();
+			!before.is_some_and(is_word) && !after.is_some_and(is_word)
+		})
+		.min_by_key(|(_, at)| *at)
+}
+
+/// Texts of the line comments after `comment` that continue it: each alone on the line right under the previous one,
+/// up to one with a marker of its own.
+fn continuation<'a>(lines: &LineIndex, rest: &[Comment<'a>], comment: &Comment, markers: &[String]) -> Vec<&'a str> {
+	if !comment.text.starts_with("//") {
+		return Vec::new();
+	}
+	let mut line = lines.position(comment.start).0;
+	let mut texts = Vec::new();
+	for next in rest {
+		let (next_line, _) = lines.position(next.start);
+		let line_start = lines.line_start(next.start);
+		let alone = lines.text()[line_start..next.start].trim().is_empty();
+		if next_line != line + 1 || !alone || !next.text.starts_with("//") || find_marker(next.text, markers).is_some() {
+			break;
+		}
+		texts.push(next.text);
+		line = next_line;
+	}
+	texts
+}
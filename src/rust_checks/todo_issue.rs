@@ -0,0 +1,109 @@
+//! Lint to require an issue reference in `todo!` and `unimplemented!` messages.
+//!
+//! A placeholder nobody tracks stays one: the message should point to where the remaining work is written down. It
+//! counts as referencing an issue if the `todo_issue_pattern` regex matches it, which by default takes a `#123` or a
+//! URL. A bare `todo!()` has nothing to match and is always flagged. Format arguments aren't looked into, only the
+//! format string, as that's where a reference is written.
+
+use std::path::Path;
+
+use regex::Regex;
+use syn::{Expr, ExprLit, Lit, Macro, Token, punctuated::Punctuated, visit::Visit};
+
+use super::{
+	Severity, Violation,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
+
+const RULE: &str = "todo-issue";
+/// Pattern an issue reference matches, unless `todo_issue_pattern` says otherwise
+pub const DEFAULT_PATTERN: &str = r"#\d+|https?://\S+";
+/// Placeholder macros the rule checks
+const PLACEHOLDERS: &[&str] = &["todo", "unimplemented"];
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "todo_issue",
+	autofix: false,
+	triggers: &["todo!", "unimplemented!"],
+	description: "Require an issue reference in todo! and unimplemented! messages",
+	rationale: "A placeholder without an issue is tracked by nothing but the code it's in; linking one keeps the remaining work where it gets planned.",
+	bad: "pub fn export(&self) -> Result<()> {\n\ttodo!()\n}",
+	good: "pub fn export(&self) -> Result<()> {\n\ttodo!(\"#142: export to parquet\")\n}",
+	fix: "Open an issue for the remaining work and reference it in the message, as `todo_issue_pattern` expects.",
+};
+
+pub struct TodoIssue;
+impl Rule for TodoIssue {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree, &ctx.opts.todo_issue_pattern)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File, pattern: &Regex) -> Vec<Violation> {
+	let visitor = TodoVisitor {
+		path_str: path.display().to_string(),
+		pattern,
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+/// [`DEFAULT_PATTERN`], compiled.
+pub fn default_pattern() -> Regex {
+	Regex::new(DEFAULT_PATTERN).expect("DEFAULT_PATTERN is a valid regex")
+}
+
+struct TodoVisitor<'a> {
+	path_str: String,
+	pattern: &'a Regex,
+	violations: Vec<Violation>,
+}
+
+impl<'a> Visit<'a> for TodoVisitor<'a> {
+	fn visit_macro(&mut self, node: &'a Macro) {
+		let Some(name) = node.path.segments.last().map(|s| s.ident.to_string()) else {
+			return;
+		};
+		if PLACEHOLDERS.contains(&name.as_str()) {
+			let message = match format_string(node) {
+				None if node.tokens.is_empty() => Some(format!("`{name}!()` without a message\nHINT: say what's left, with a reference to the issue tracking it")),
+				Some(text) if !self.pattern.is_match(&text) => Some(format!(
+					"`{name}!` message doesn't reference an issue: nothing in it matches `{}`\nHINT: reference the issue tracking what's left",
+					self.pattern
+				)),
+				_ => None,
+			};
+			if let Some(message) = message {
+				let start = node.path.segments[0].ident.span().start();
+				let end = node.delimiter.span().close().end();
+				self.violations.push(Violation {
+					rule: RULE,
+					file: self.path_str.clone(),
+					line: start.line,
+					column: start.column,
+					end: Some((end.line, end.column)),
+					message,
+					severity: Severity::Error,
+					..Default::default()
+				});
+			}
+		}
+		syn::visit::visit_macro(self, node);
+	}
+}
+
+/// The string literal `node` is called with first, if any.
+fn format_string(node: &Macro) -> Option<String> {
+	let args = node.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated).ok()?;
+	match args.first()? {
+		Expr::Lit(ExprLit { lit: Lit::Str(s), .. }) => Some(s.value()),
+		_ => None,
+	}
+}
@@ -0,0 +1,243 @@
+//! Lint to keep a type's trait impls together, right after its inherent impl.
+//!
+//! Where `impl_follows_type` puts a type's inherent impl under it, this puts the type's trait impls under that: every
+//! top-level `impl Trait for Type` of a type defined in the file follows the type and its inherent impls, with nothing
+//! else in between. They're ordered std traits first (std, core and alloc ones, and the prelude's), then the rest,
+//! each in derive order: `Clone, Copy, Debug, Default`, then alphabetically, impls of one trait keeping theirs.
+//!
+//! The fix moves one impl at a time, with its doc comments, attributes and fold marker, which `format` repeats until
+//! the impls are in place. A skip marker on the type, or on any of its trait impls, leaves them all where they are.
+
+use std::{cmp::Ordering, path::Path};
+
+use syn::{Item, ItemImpl, spanned::Spanned};
+
+use super::{
+	Fix, Label, Severity, Violation,
+	custom_rules::Imports,
+	derive_order, impl_folds,
+	line_index::LineIndex,
+	pub_first::find_item_text_start,
+	rules::{Context, Rule, RuleInfo},
+	skip::has_skip_marker_for_rule,
+};
+
+const RULE: &str = "trait-impls-follow";
+/// Traits in std's prelude, which resolve to std without an import
+const PRELUDE_TRAITS: &[&str] = &[
+	"AsMut",
+	"AsRef",
+	"Clone",
+	"Copy",
+	"Default",
+	"DoubleEndedIterator",
+	"Drop",
+	"Eq",
+	"ExactSizeIterator",
+	"Extend",
+	"Fn",
+	"FnMut",
+	"FnOnce",
+	"From",
+	"FromIterator",
+	"Into",
+	"IntoIterator",
+	"Iterator",
+	"Ord",
+	"PartialEq",
+	"PartialOrd",
+	"Send",
+	"Sync",
+	"ToOwned",
+	"ToString",
+	"TryFrom",
+	"TryInto",
+	"Unpin",
+];
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "trait_impls_follow",
+	autofix: true,
+	triggers: &["impl"],
+	description: "Place trait impls right after their type's inherent impl, std traits first",
+	rationale: "With a type's trait impls under its inherent impl in one order, everything the type implements is read in one place and found where expected.",
+	bad: "struct Foo;\nimpl Foo {}\n\nfn helper() {}\n\nimpl fmt::Display for Foo {}\n\nimpl Clone for Foo {}",
+	good: "struct Foo;\nimpl Foo {}\n\nimpl Clone for Foo {}\n\nimpl fmt::Display for Foo {}\n\nfn helper() {}",
+	fix: "Moves the misplaced impl, with its doc comments and attributes, after its type's inherent impl or the trait impl it should follow.",
+};
+
+pub struct TraitImplsFollow;
+impl Rule for TraitImplsFollow {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+	let checker = Checker {
+		path_str: path.display().to_string(),
+		lines: LineIndex::new(content),
+		imports: Imports::of(file),
+		items: &file.items,
+	};
+	file.items
+		.iter()
+		.enumerate()
+		.filter_map(|(def, item)| {
+			let ident = match item {
+				Item::Struct(s) => &s.ident,
+				Item::Enum(e) => &e.ident,
+				Item::Union(u) => &u.ident,
+				_ => return None,
+			};
+			if has_skip_marker_for_rule(content, item.span(), RULE) {
+				return None;
+			}
+			checker.check_type(def, ident)
+		})
+		.collect()
+}
+
+struct Checker<'a> {
+	path_str: String,
+	lines: LineIndex<'a>,
+	imports: Imports,
+	items: &'a [Item],
+}
+
+impl Checker<'_> {
+	/// The first of the trait impls of the type defined by `items[def]` that's out of place, if any is.
+	fn check_type(&self, def: usize, ident: &syn::Ident) -> Option<Violation> {
+		let name = ident.to_string();
+		// The type and the inherent impls right under it, which the trait impls go after
+		let mut head = def;
+		while self.items.get(head + 1).and_then(|item| impl_of(item, &name)).is_some_and(|block| block.trait_.is_none()) {
+			head += 1;
+		}
+		let mut trait_impls: Vec<(usize, &ItemImpl)> = self
+			.items
+			.iter()
+			.enumerate()
+			.filter_map(|(i, item)| Some((i, impl_of(item, &name)?)))
+			.filter(|(_, block)| block.trait_.is_some())
+			.collect();
+		if trait_impls.iter().any(|(_, block)| has_skip_marker_for_rule(self.lines.text(), block.span(), RULE)) {
+			return None;
+		}
+		trait_impls.sort_by(|(a, a_block), (b, b_block)| self.compare(a_block, b_block).then(a.cmp(b)));
+
+		let mut prev = head;
+		for (i, block) in trait_impls {
+			if i == prev + 1 {
+				prev = i;
+				continue;
+			}
+			let after = match &self.items[prev] {
+				Item::Impl(previous) if prev > head => format!("`impl {} for {name}`", self.trait_text(previous)),
+				_ if head > def => format!("`{name}`'s inherent impl"),
+				_ => format!("`{name}`"),
+			};
+			let start = block.impl_token.span.start();
+			let end = block.self_ty.span().end();
+			let ident_start = ident.span().start();
+			return Some(Violation {
+				rule: RULE,
+				file: self.path_str.clone(),
+				line: start.line,
+				column: start.column,
+				end: Some((end.line, end.column)),
+				message: format!(
+					"`impl {} for {name}` should follow {after}\nHINT: a type's trait impls go right after its inherent impl, std traits first, then the rest, each in derive order",
+					self.trait_text(block)
+				),
+				severity: Severity::Error,
+				fix: self.move_fix(i, prev),
+				labels: vec![Label {
+					line: ident_start.line,
+					column: ident_start.column,
+					message: format!("`{name}` defined here"),
+				}],
+				..Default::default()
+			});
+		}
+		None
+	}
+
+	/// Order of two trait impls: std traits first, then by derive order of the traits' names.
+	fn compare(&self, a: &ItemImpl, b: &ItemImpl) -> Ordering {
+		let key = |block: &ItemImpl| {
+			let resolved = block.trait_.as_ref().map(|(_, path, _)| self.imports.resolve(path)).unwrap_or_default();
+			let is_std = match resolved.as_slice() {
+				[name] => PRELUDE_TRAITS.contains(&name.as_str()),
+				[root, ..] => ["std", "core", "alloc"].contains(&root.as_str()),
+				[] => false,
+			};
+			(!is_std, resolved.last().cloned().unwrap_or_default())
+		};
+		let ((a_foreign, a_name), (b_foreign, b_name)) = (key(a), key(b));
+		a_foreign.cmp(&b_foreign).then_with(|| derive_order::compare(&a_name, &b_name))
+	}
+
+	/// The trait of `block` as written, generics included.
+	fn trait_text(&self, block: &ItemImpl) -> String {
+		let Some((_, path, _)) = &block.trait_ else {
+			return String::new();
+		};
+		match (self.lines.offset_of(path.span().start()), self.lines.offset_of(path.span().end())) {
+			(Some(start), Some(end)) => self.lines.text()[start..end].to_string(),
+			_ => path.segments.iter().map(|s| s.ident.to_string()).collect::<Vec<_>>().join("::"),
+		}
+	}
+
+	/// Fix moving `items[from]` to right after `items[after]`, keeping a blank line around it.
+	fn move_fix(&self, from: usize, after: usize) -> Option<Fix> {
+		let text = self.lines.text();
+		let (start, end) = self.chunk(from)?;
+		let (_, insert) = self.chunk(after)?;
+		let moved = text[start..end].trim_end_matches('\n');
+		if start > insert {
+			// Moving up: what was between follows the moved impl, after the blank lines that led it
+			let between = &text[insert..start];
+			let lead = &between[..between.len() - between.trim_start().len()];
+			Some(Fix {
+				start_byte: insert,
+				end_byte: end,
+				replacement: format!("{lead}{moved}\n\n{}\n", between.trim()),
+			})
+		} else {
+			// Moving down: what was between takes its place
+			Some(Fix {
+				start_byte: start,
+				end_byte: insert,
+				replacement: format!("{}\n\n{moved}\n", text[end..insert].trim()),
+			})
+		}
+	}
+
+	/// Byte range of `items[i]`: its doc comments and attributes, and its lines, with a fold marker on the line after.
+	fn chunk(&self, i: usize) -> Option<(usize, usize)> {
+		let span = self.items[i].span();
+		let start = find_item_text_start(&self.lines, self.lines.offset_of(span.start())?);
+		let len = self.lines.text().len();
+		let mut end = (self.lines.line_end(self.lines.offset_of(span.end())?) + 1).min(len);
+		if end < len && self.lines.text()[end..self.lines.line_end(end)].trim() == impl_folds::CLOSE_MARKER {
+			end = (self.lines.line_end(end) + 1).min(len);
+		}
+		Some((start, end))
+	}
+}
+
+/// `item`, if it's an impl for the type named `name`, whatever its path or generics.
+fn impl_of<'a>(item: &'a Item, name: &str) -> Option<&'a ItemImpl> {
+	let Item::Impl(block) = item else {
+		return None;
+	};
+	match &*block.self_ty {
+		syn::Type::Path(type_path) if type_path.path.segments.last().is_some_and(|last| last.ident == name) => Some(block),
+		_ => None,
+	}
+}
@@ -0,0 +1,262 @@
+//! Crate-wide lint for `pub` items nothing outside their module uses.
+//!
+//! Unlike the other rules, this one needs every file of a crate at once: each crate under a `src/` with a `lib.rs` or
+//! `main.rs` is read whole, its files placed in the module tree by their paths, and every identifier in them recorded
+//! with the module it appears in. A `pub` fn, struct, enum, union, trait, type, const or static whose name no module
+//! outside its own, or the ones nested in it, mentions is flagged: private would do, after which rustc's dead-code lint
+//! tells whether it's used at all.
+//!
+//! Matching is by name, so a mention of the same name for something else counts as a use: the rule only ever misses
+//! items, it doesn't flag used ones. A library's API is left alone, which is every `pub` item whose modules are all
+//! `pub mod`s, or glob re-exported, from `lib.rs` down; so is `main`, anything `#[no_mangle]` or `#[export_name]`, and
+//! anything in `#[cfg(test)]` modules. Only `assert` runs it, as it has no fix.
+
+use std::{
+	collections::{HashMap, HashSet},
+	fs,
+	path::{Path, PathBuf},
+};
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::{Attribute, Ident, Item, ItemMod, Macro, UseTree, Visibility, spanned::Spanned, visit::Visit};
+
+use super::{RustCheckOptions, Violation, generated, rules::RuleInfo, skip, test_context::is_cfg_test_attr, workspace::crate_src};
+
+pub const RULE: &str = "unused-pub";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "unused_pub",
+	autofix: false,
+	triggers: &["pub"],
+	description: "Flag pub items nothing outside their module uses",
+	rationale: "`pub` says other modules depend on an item; when none do, it only hides the item from rustc's dead-code lint and widens what a change has to consider.",
+	bad: "// src/cache.rs, with nothing else naming `evict`\npub fn evict(&mut self) {}",
+	good: "// src/cache.rs\nfn evict(&mut self) {}",
+	fix: "Drop the `pub`, or the item, if rustc then finds it unused. Items a library exports from `lib.rs` aren't flagged.",
+};
+
+/// Violations in the crates `files` belong to, for the files whose options enable the rule. Every file of such a crate
+/// is read, as any of them could be the one using an item.
+pub fn check_crates(files: &[(PathBuf, RustCheckOptions)]) -> Vec<Violation> {
+	let mut crates: HashMap<PathBuf, Vec<&(PathBuf, RustCheckOptions)>> = HashMap::new();
+	for file in files {
+		if let Some(src) = crate_src(&file.0) {
+			crates.entry(src).or_default().push(file);
+		}
+	}
+	let mut violations = Vec::new();
+	for (src, files) in crates {
+		if files.iter().any(|(path, opts)| opts.severity_for(path, INFO.option).is_enabled()) {
+			violations.extend(check_crate(&src, &files));
+		}
+	}
+	violations.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+	violations
+}
+
+/// A `pub` item and the module it's in.
+struct PubItem {
+	file: usize,
+	module: Vec<String>,
+	kind: &'static str,
+	name: String,
+	/// The whole item, attributes included, which is where skip markers go
+	span: Span,
+	ident: Span,
+}
+
+/// What the crate's files say about its modules and names.
+#[derive(Default)]
+struct CrateIndex {
+	items: Vec<PubItem>,
+	/// Modules declared `pub mod`
+	public_modules: HashSet<Vec<String>>,
+	/// Names of modules glob re-exported by a `pub use`, wherever they are
+	reexported_globs: HashSet<String>,
+	/// Modules each name is mentioned in
+	mentions: HashMap<String, HashSet<Vec<String>>>,
+}
+
+fn check_crate(src: &Path, files: &[&(PathBuf, RustCheckOptions)]) -> Vec<Violation> {
+	let mut index = CrateIndex::default();
+	let mut contents = Vec::new();
+	for (i, (path, _)) in files.iter().enumerate() {
+		let Ok(content) = fs::read_to_string(path) else {
+			contents.push(String::new());
+			continue;
+		};
+		if !generated::is_generated(&content)
+			&& let Ok(tree) = syn::parse_file(&content)
+		{
+			let mut visitor = IndexVisitor {
+				index: &mut index,
+				file: i,
+				module: module_path(src, path),
+				in_test: false,
+			};
+			visitor.visit_file(&tree);
+		}
+		contents.push(content);
+	}
+
+	let is_lib = src.join("lib.rs").is_file();
+	let mut violations = Vec::new();
+	for item in &index.items {
+		let (path, opts) = files[item.file];
+		let severity = opts.severity_for(path, INFO.option);
+		let content = &contents[item.file];
+		let (start, end) = (item.ident.start(), item.ident.end());
+		if !severity.is_enabled()
+			|| is_lib && index.is_exported(&item.module)
+			|| index.mentions.get(&item.name).is_some_and(|modules| modules.iter().any(|m| !m.starts_with(&item.module)))
+			|| skip::has_skip_marker_for_rule(content, item.span, RULE)
+			|| skip::skip_regions(content).0.iter().any(|region| region.covers(start.line, RULE))
+		{
+			continue;
+		}
+		let outside = match item.module.as_slice() {
+			[] => "the crate root".to_string(),
+			module => format!("`crate::{}`", module.join("::")),
+		};
+		violations.push(Violation {
+			rule: RULE,
+			file: path.display().to_string(),
+			line: start.line,
+			column: start.column,
+			end: Some((end.line, end.column)),
+			message: format!(
+				"`pub` {} `{}` isn't used outside {outside}\nHINT: drop the `pub`; rustc's dead-code lint then says whether it's used at all",
+				item.kind, item.name
+			),
+			severity,
+			..Default::default()
+		});
+	}
+	violations
+}
+
+impl CrateIndex {
+	/// Whether a `pub` item in `module` is part of the library's API: it's reachable through `pub mod`s from the root,
+	/// or from a module that's glob re-exported.
+	fn is_exported(&self, module: &[String]) -> bool {
+		match module.split_last() {
+			None => true,
+			Some((last, parent)) => self.reexported_globs.contains(last) || self.public_modules.contains(module) && self.is_exported(parent),
+		}
+	}
+}
+
+struct IndexVisitor<'i> {
+	index: &'i mut CrateIndex,
+	file: usize,
+	module: Vec<String>,
+	in_test: bool,
+}
+
+impl IndexVisitor<'_> {
+	fn mention(&mut self, ident: &Ident) {
+		self.index.mentions.entry(ident.to_string()).or_default().insert(self.module.clone());
+	}
+
+	fn mention_tokens(&mut self, tokens: TokenStream) {
+		for token in tokens {
+			match token {
+				TokenTree::Ident(ident) => self.mention(&ident),
+				TokenTree::Group(group) => self.mention_tokens(group.stream()),
+				TokenTree::Punct(_) | TokenTree::Literal(_) => {}
+			}
+		}
+	}
+
+	fn record(&mut self, item: &Item) {
+		let (kind, vis, ident, attrs) = match item {
+			Item::Fn(f) => ("fn", &f.vis, &f.sig.ident, &f.attrs),
+			Item::Struct(s) => ("struct", &s.vis, &s.ident, &s.attrs),
+			Item::Enum(e) => ("enum", &e.vis, &e.ident, &e.attrs),
+			Item::Union(u) => ("union", &u.vis, &u.ident, &u.attrs),
+			Item::Trait(t) => ("trait", &t.vis, &t.ident, &t.attrs),
+			Item::Type(t) => ("type", &t.vis, &t.ident, &t.attrs),
+			Item::Const(c) => ("const", &c.vis, &c.ident, &c.attrs),
+			Item::Static(s) => ("static", &s.vis, &s.ident, &s.attrs),
+			_ => return,
+		};
+		let is_main = kind == "fn" && ident == "main" && self.module.is_empty();
+		if !matches!(vis, Visibility::Public(_)) || self.in_test || is_main || attrs.iter().any(is_exported_symbol) {
+			return;
+		}
+		self.index.items.push(PubItem {
+			file: self.file,
+			module: self.module.clone(),
+			kind,
+			name: ident.to_string(),
+			span: item.span(),
+			ident: ident.span(),
+		});
+	}
+
+	/// Note the modules `tree`, a `pub use`, glob re-exports.
+	fn record_globs(&mut self, tree: &UseTree, parent: Option<&Ident>) {
+		match tree {
+			UseTree::Path(path) => self.record_globs(&path.tree, Some(&path.ident)),
+			UseTree::Group(group) =>
+				for item in &group.items {
+					self.record_globs(item, parent);
+				},
+			UseTree::Glob(_) =>
+				if let Some(parent) = parent {
+					self.index.reexported_globs.insert(parent.to_string());
+				},
+			UseTree::Name(_) | UseTree::Rename(_) => {}
+		}
+	}
+}
+
+impl<'a> Visit<'a> for IndexVisitor<'_> {
+	fn visit_item(&mut self, node: &'a Item) {
+		self.record(node);
+		if let Item::Use(item) = node
+			&& matches!(item.vis, Visibility::Public(_))
+		{
+			self.record_globs(&item.tree, None);
+		}
+		syn::visit::visit_item(self, node);
+	}
+
+	fn visit_item_mod(&mut self, node: &'a ItemMod) {
+		self.module.push(node.ident.to_string());
+		if matches!(node.vis, Visibility::Public(_)) {
+			self.index.public_modules.insert(self.module.clone());
+		}
+		let in_test = self.in_test;
+		self.in_test |= node.attrs.iter().any(is_cfg_test_attr);
+		syn::visit::visit_item_mod(self, node);
+		self.in_test = in_test;
+		self.module.pop();
+	}
+
+	fn visit_ident(&mut self, node: &'a Ident) {
+		self.mention(node);
+	}
+
+	fn visit_macro(&mut self, node: &'a Macro) {
+		syn::visit::visit_macro(self, node);
+		self.mention_tokens(node.tokens.clone());
+	}
+}
+
+/// Module of the file at `path` in the crate rooted at `src`: `src/a/b.rs` and `src/a/b/mod.rs` are `a::b`.
+fn module_path(src: &Path, path: &Path) -> Vec<String> {
+	let relative = path.strip_prefix(src).unwrap_or(path).with_extension("");
+	let mut module: Vec<String> = relative.iter().map(|part| part.to_string_lossy().into_owned()).collect();
+	if matches!(module.as_slice(), [root] if root == "lib" || root == "main") || module.last().is_some_and(|last| last == "mod") {
+		module.pop();
+	}
+	module
+}
+
+/// Whether `attr` exports the item's symbol from the binary, under its name or another, as `#[unsafe(no_mangle)]` too.
+fn is_exported_symbol(attr: &Attribute) -> bool {
+	let tokens = attr.meta.to_token_stream().to_string();
+	tokens.contains("no_mangle") || tokens.contains("export_name")
+}
@@ -0,0 +1,91 @@
+//! Lint for skip marker comments that no longer suppress anything, so stale suppressions get cleaned up.
+//!
+//! Whether a marker is used is found out by taking it away: the file is checked again with the marker's line blanked,
+//! and if that turns up more violations of the rules it names, it was doing something. That works for every rule
+//! without knowing how each applies its skips, at the cost of a full check per marker.
+
+use super::{
+	EXPIRED_SKIP_RULE, FileInfo, Fix, RustCheckOptions, SKIP_REGION_RULE, Severity, Violation, line_index::LineIndex, parse_rust_source,
+	rules::{RuleInfo, SOURCE_RULES},
+	run_rules,
+	rustfmt::RustfmtConfig,
+	skip::{SkipMarker, marker_comments},
+	skip_reason,
+};
+
+pub(super) const RULE: &str = "unused-skip";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "unused_skip",
+	autofix: true,
+	triggers: &["codestyle::skip"],
+	description: "Report codestyle::skip marker comments that suppress nothing",
+	rationale: "Code changes under a suppression; once it would pass anyway, the marker only hides whatever goes wrong there next.",
+	bad: "//@codestyle::skip(pub-first)\npub fn run() {}",
+	good: "pub fn run() {}",
+	fix: "Removes the marker comment.",
+};
+
+/// Item marker comments in `info` that suppress no violation of the rules enabled in `opts`. Region markers are left
+/// to the `skip-region` checks, since dropping one end would unbalance them.
+pub(super) fn check(info: &FileInfo, opts: &RustCheckOptions, rustfmt: &RustfmtConfig, fixing: bool) -> Vec<Violation> {
+	let markers: Vec<_> = marker_comments(&info.contents).into_iter().filter(|m| !m.region).collect();
+	if markers.is_empty() {
+		return Vec::new();
+	}
+	// Without this rule, or checking the ablated files would look for unused markers in them too
+	let opts = RustCheckOptions {
+		unused_skip: Severity::Allow,
+		overrides: opts.overrides.without(INFO.option),
+		..opts.clone()
+	};
+	let suppressed = |violations: &[Violation], marker: &SkipMarker| {
+		violations
+			.iter()
+			.filter(|v| match marker {
+				// Removing a marker also removes its own `skip-reason` violation, which isn't a suppression
+				SkipMarker::All => ![skip_reason::RULE, SKIP_REGION_RULE, EXPIRED_SKIP_RULE].contains(&v.rule),
+				SkipMarker::Rule(rule) => v.rule == rule,
+			})
+			.count()
+	};
+	let baseline = run_rules(info, &opts, rustfmt, fixing, SOURCE_RULES);
+
+	let lines = LineIndex::new(&info.contents);
+	let mut violations = Vec::new();
+	for marker in markers {
+		let Some(start) = lines.offset(marker.line, 0) else {
+			continue;
+		};
+		let end = lines.line_end(start);
+		let mut ablated = info.contents.clone();
+		ablated.replace_range(start..end, "");
+		let Some(ablated) = parse_rust_source(info.path.clone(), ablated) else {
+			continue;
+		};
+		if suppressed(&run_rules(&ablated, &opts, rustfmt, fixing, SOURCE_RULES), &marker.marker) > suppressed(&baseline, &marker.marker) {
+			continue;
+		}
+
+		let what = match &marker.marker {
+			SkipMarker::All => "any rule".to_string(),
+			SkipMarker::Rule(rule) => format!("`{rule}`"),
+		};
+		violations.push(Violation {
+			rule: RULE,
+			file: info.path.display().to_string(),
+			line: marker.line,
+			column: marker.column,
+			message: format!("skip marker suppresses no violation of {what}"),
+			severity: Severity::Error,
+			fix: Some(Fix {
+				start_byte: start,
+				// The line's `\n` goes with it
+				end_byte: (end + 1).min(info.contents.len()),
+				replacement: String::new(),
+			}),
+			..Default::default()
+		});
+	}
+	violations
+}
@@ -8,9 +8,37 @@ use std::{collections::HashSet, path::Path};
 use proc_macro2::Span;
 use syn::{Expr, ExprCall, ExprMacro, ExprReturn, ItemUse, Macro, UseTree, spanned::Spanned, visit::Visit};
 
-use super::{Fix, Violation, skip::SkipVisitor};
+use super::{
+	Fix, Severity, Violation,
+	line_index::LineIndex,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
 
 const RULE: &str = "use-bail";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "use_bail",
+	autofix: true,
+	triggers: &["eyre"],
+	description: "Use bail!(...) instead of return Err(eyre!(...))",
+	rationale: "`bail!` says the same thing as `return Err(eyre!(...))` in one macro, and is what readers of eyre/anyhow code expect.",
+	bad: "return Err(eyre!(\"invalid value: {}\", x));",
+	good: "bail!(\"invalid value: {}\", x);",
+	fix: "Replaces the return with `bail!` using the same arguments, and imports `bail` from the same crate if it isn't already.",
+};
+
+pub struct UseBail;
+impl Rule for UseBail {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
 pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
 	let visitor = UseBailVisitor::new(path, content, file);
 	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
@@ -27,6 +55,7 @@ enum ErrorCrate {
 struct UseBailVisitor<'a> {
 	path_str: String,
 	content: &'a str,
+	lines: LineIndex<'a>,
 	violations: Vec<Violation>,
 	seen_spans: HashSet<(usize, usize)>,
 	/// Which error crate is being used (eyre, color_eyre, anyhow)
@@ -44,6 +73,7 @@ impl<'a> UseBailVisitor<'a> {
 		let mut visitor = Self {
 			path_str: path.display().to_string(),
 			content,
+			lines: LineIndex::new(content),
 			violations: Vec::new(),
 			seen_spans: HashSet::new(),
 			error_crate: None,
@@ -177,18 +207,24 @@ impl<'a> UseBailVisitor<'a> {
 			file: self.path_str.clone(),
 			line: return_expr.span().start().line,
 			column: return_expr.span().start().column,
+			end: Some((return_expr.span().end().line, return_expr.span().end().column)),
 			message: format!("use `bail!(...)` instead of `return Err({macro_name}!(...))`"),
+			severity: Severity::Error,
 			fix,
+			..Default::default()
 		});
 	}
 
 	fn create_fix(&self, return_expr: &ExprReturn, macro_expr: &ExprMacro) -> Option<Fix> {
-		// Get the macro content (everything inside eyre!(...))
-		let macro_content = macro_expr.mac.tokens.to_string();
+		// Get the macro content (everything inside eyre!(...)) as written, so the author's formatting is kept
+		let delimiters = macro_expr.mac.delimiter.span().join();
+		let args_start = self.lines.offset_of(delimiters.start())? + 1;
+		let args_end = self.lines.offset_of(delimiters.end())? - 1;
+		let macro_content = self.content.get(args_start..args_end)?;
 
 		// Calculate byte positions for the return statement
-		let return_start = span_to_byte(self.content, return_expr.span().start())?;
-		let return_end = span_to_byte(self.content, return_expr.span().end())?;
+		let return_start = self.lines.offset_of(return_expr.span().start())?;
+		let return_end = self.lines.offset_of(return_expr.span().end())?;
 
 		// Build the replacement
 		let bail_call = format!("bail!({macro_content})");
@@ -243,24 +279,3 @@ fn is_err_call(call: &ExprCall) -> bool {
 fn get_macro_name(mac: &Macro) -> String {
 	mac.path.segments.last().map(|s| s.ident.to_string()).unwrap_or_default()
 }
-
-fn span_to_byte(content: &str, pos: proc_macro2::LineColumn) -> Option<usize> {
-	let mut current_line = 1;
-	let mut line_start = 0;
-
-	for (i, ch) in content.char_indices() {
-		if current_line == pos.line {
-			return Some(line_start + pos.column);
-		}
-		if ch == '\n' {
-			current_line += 1;
-			line_start = i + 1;
-		}
-	}
-
-	if current_line == pos.line {
-		return Some(line_start + pos.column);
-	}
-
-	None
-}
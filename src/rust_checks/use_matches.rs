@@ -0,0 +1,178 @@
+//! Lint to replace a `match` or `if let` that only maps patterns to `true`/`false` with `matches!`.
+//!
+//! `match x { A | B => true, _ => false }` becomes `matches!(x, A | B)`, and `if let P = x { true } else { false }`
+//! becomes `matches!(x, P)`; the same with `false` and `true` swapped becomes `!matches!(..)`. Several arms returning
+//! the same bool are joined into one pattern, unless one has a guard, which only `matches!`'s single pattern can carry.
+
+use std::path::Path;
+
+use syn::{Expr, ExprIf, ExprLet, ExprLit, ExprMatch, Lit, Pat, Stmt, spanned::Spanned, visit::Visit};
+
+use super::{
+	Fix, Severity, Violation,
+	line_index::LineIndex,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
+
+const RULE: &str = "use-matches";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "use_matches",
+	autofix: true,
+	triggers: &["true"],
+	description: "Use matches!(...) instead of a match or if-let returning true/false",
+	rationale: "`matches!` says in one line that the expression only tests a pattern, where a `match` spells out both answers.",
+	bad: "let is_digit = match c {\n\t'0'..='9' => true,\n\t_ => false,\n};",
+	good: "let is_digit = matches!(c, '0'..='9');",
+	fix: "Replaces the `match` or `if let` with `matches!(scrutinee, pattern)`, negated if the pattern maps to `false`.",
+};
+
+pub struct UseMatches;
+impl Rule for UseMatches {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+	let visitor = UseMatchesVisitor {
+		path_str: path.display().to_string(),
+		lines: LineIndex::new(content),
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+struct UseMatchesVisitor<'a> {
+	path_str: String,
+	lines: LineIndex<'a>,
+	violations: Vec<Violation>,
+}
+
+impl UseMatchesVisitor<'_> {
+	fn check_match(&mut self, node: &ExprMatch) {
+		let Some((last, arms)) = node.arms.split_last() else {
+			return;
+		};
+		if !node.attrs.is_empty() || arms.is_empty() || last.guard.is_some() || !matches!(last.pat, Pat::Wild(_)) {
+			return;
+		}
+		let Some(otherwise) = bool_value(&last.body) else {
+			return;
+		};
+		if arms.iter().any(|arm| bool_value(&arm.body) != Some(!otherwise)) || (arms.len() > 1 && arms.iter().any(|arm| arm.guard.is_some())) {
+			return;
+		}
+		let pats: Option<Vec<&str>> = arms
+			.iter()
+			.map(|arm| {
+				let end = arm.guard.as_ref().map_or(arm.pat.span().end(), |(_, guard)| guard.span().end());
+				let start = self.lines.offset_of(arm.pat.span().start())?;
+				self.lines.text().get(start..self.lines.offset_of(end)?)
+			})
+			.collect();
+		let Some(pats) = pats else {
+			return;
+		};
+		self.report(node, "match", &node.expr, &pats.join(" | "), otherwise);
+	}
+
+	fn check_if(&mut self, node: &ExprIf) {
+		let Expr::Let(ExprLet { pat, expr, .. }) = &*node.cond else {
+			return;
+		};
+		let Some((_, else_branch)) = &node.else_branch else {
+			return;
+		};
+		let [Stmt::Expr(then_value, None)] = node.then_branch.stmts.as_slice() else {
+			return;
+		};
+		if !node.attrs.is_empty() || !matches!(&**else_branch, Expr::Block(_)) {
+			return;
+		}
+		let (Some(then_value), Some(otherwise)) = (bool_value(then_value), bool_value(else_branch)) else {
+			return;
+		};
+		let Some(pat) = self.text(pat).map(str::to_string) else {
+			return;
+		};
+		if then_value != otherwise {
+			self.report(node, "if let", expr, &pat, otherwise);
+		}
+	}
+
+	/// Report `node`, which tests `scrutinee` against `pat` and is `otherwise` when it doesn't match.
+	fn report(&mut self, node: &impl Spanned, construct: &str, scrutinee: &Expr, pat: &str, otherwise: bool) {
+		let negation = if otherwise { "!" } else { "" };
+		let fix = self.text(scrutinee).zip(self.byte_range(node)).map(|(scrutinee, (start_byte, end_byte))| Fix {
+			start_byte,
+			end_byte,
+			replacement: format!("{negation}matches!({scrutinee}, {pat})"),
+		});
+		let start = node.span().start();
+		let end = node.span().end();
+		self.violations.push(Violation {
+			rule: RULE,
+			file: self.path_str.clone(),
+			line: start.line,
+			column: start.column,
+			end: Some((end.line, end.column)),
+			message: format!("`{construct}` that only tests for `{pat}`\nHINT: use `{negation}matches!(.., {pat})`"),
+			severity: Severity::Error,
+			fix,
+			..Default::default()
+		});
+	}
+
+	/// Visit the parts of `node`, without checking an `else if` itself: `else matches!(..)` doesn't parse.
+	fn visit_if_parts(&mut self, node: &ExprIf) {
+		self.visit_expr(&node.cond);
+		self.visit_block(&node.then_branch);
+		match node.else_branch.as_ref().map(|(_, else_branch)| &**else_branch) {
+			Some(Expr::If(else_if)) => self.visit_if_parts(else_if),
+			Some(else_branch) => self.visit_expr(else_branch),
+			None => {}
+		}
+	}
+
+	fn byte_range(&self, node: &impl Spanned) -> Option<(usize, usize)> {
+		let span = node.span();
+		Some((self.lines.offset_of(span.start())?, self.lines.offset_of(span.end())?))
+	}
+
+	fn text(&self, node: &impl Spanned) -> Option<&str> {
+		let (start, end) = self.byte_range(node)?;
+		self.lines.text().get(start..end)
+	}
+}
+
+impl<'a> Visit<'a> for UseMatchesVisitor<'_> {
+	fn visit_expr_match(&mut self, node: &'a ExprMatch) {
+		self.check_match(node);
+		syn::visit::visit_expr_match(self, node);
+	}
+
+	fn visit_expr_if(&mut self, node: &'a ExprIf) {
+		self.check_if(node);
+		self.visit_if_parts(node);
+	}
+}
+
+/// The `true` or `false` that `expr` is, braced or not.
+fn bool_value(expr: &Expr) -> Option<bool> {
+	match expr {
+		Expr::Lit(ExprLit { lit: Lit::Bool(value), .. }) => Some(value.value),
+		Expr::Block(block) if block.label.is_none() => match block.block.stmts.as_slice() {
+			[Stmt::Expr(value, None)] => bool_value(value),
+			_ => None,
+		},
+		_ => None,
+	}
+}
@@ -0,0 +1,112 @@
+//! Lint to derive `thiserror::Error` on public error enums rather than implementing `Display` and `Error` by hand.
+//!
+//! An error enum is a `pub enum` whose name ends in `Error`. A hand-written `impl Display` or `impl Error` for one in the
+//! same file is flagged: the derive keeps each variant's message next to the variant, and `#[from]`/`#[source]` wire up
+//! `source()` and the `From` conversions the hand-written version tends to grow. Traits are matched after resolving the
+//! file's imports, so `fmt::Display` and `Error` from `std::error` count while, say, `serde::de::Error` doesn't.
+
+use std::{collections::HashSet, path::Path};
+
+use syn::{ItemEnum, ItemImpl, Type, Visibility, spanned::Spanned, visit::Visit};
+
+use super::{
+	Severity, Violation,
+	custom_rules::Imports,
+	rules::{Context, Rule, RuleInfo},
+	skip::SkipVisitor,
+};
+
+const RULE: &str = "use-thiserror";
+pub const INFO: RuleInfo = RuleInfo {
+	id: RULE,
+	option: "use_thiserror",
+	autofix: false,
+	triggers: &["Error"],
+	description: "Derive thiserror::Error on public error enums instead of implementing Display and Error by hand",
+	rationale: "With the derive, each variant's message sits on the variant, and `source()` and `From` come from `#[source]` and `#[from]` instead of hand-written matches.",
+	bad: "pub enum ParseError {\n\tEmpty,\n}\n\nimpl fmt::Display for ParseError {\n\tfn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {\n\t\tf.write_str(\"empty input\")\n\t}\n}\n\nimpl std::error::Error for ParseError {}",
+	good: "#[derive(Debug, thiserror::Error)]\npub enum ParseError {\n\t#[error(\"empty input\")]\n\tEmpty,\n}",
+	fix: "Derive `thiserror::Error`, moving each variant's message into an `#[error(\"..\")]` on it.",
+};
+
+pub struct UseThiserror;
+impl Rule for UseThiserror {
+	fn info(&self) -> &'static RuleInfo {
+		&INFO
+	}
+
+	fn check(&self, ctx: &Context) -> Vec<Violation> {
+		check(&ctx.file.path, &ctx.file.contents, ctx.tree)
+	}
+}
+
+pub fn check(path: &Path, content: &str, file: &syn::File) -> Vec<Violation> {
+	let mut enums = ErrorEnums::default();
+	enums.visit_file(file);
+	if enums.names.is_empty() {
+		return Vec::new();
+	}
+	let visitor = ImplVisitor {
+		path_str: path.display().to_string(),
+		imports: Imports::of(file),
+		enums: enums.names,
+		violations: Vec::new(),
+	};
+	let mut skip_visitor = SkipVisitor::for_rule(visitor, content, RULE);
+	skip_visitor.visit_file(file);
+	skip_visitor.inner.violations
+}
+
+/// Names of the file's public error enums.
+#[derive(Default)]
+struct ErrorEnums {
+	names: HashSet<String>,
+}
+
+impl<'a> Visit<'a> for ErrorEnums {
+	fn visit_item_enum(&mut self, node: &'a ItemEnum) {
+		let name = node.ident.to_string();
+		if matches!(node.vis, Visibility::Public(_)) && name.ends_with("Error") {
+			self.names.insert(name);
+		}
+	}
+}
+
+struct ImplVisitor {
+	path_str: String,
+	imports: Imports,
+	enums: HashSet<String>,
+	violations: Vec<Violation>,
+}
+
+impl<'a> Visit<'a> for ImplVisitor {
+	fn visit_item_impl(&mut self, node: &'a ItemImpl) {
+		syn::visit::visit_item_impl(self, node);
+		let Some((_, trait_path, _)) = &node.trait_ else {
+			return;
+		};
+		let Type::Path(self_ty) = &*node.self_ty else {
+			return;
+		};
+		let Some(name) = self_ty.path.segments.last().map(|last| last.ident.to_string()).filter(|name| self.enums.contains(name)) else {
+			return;
+		};
+		let trait_name = match self.imports.resolve(trait_path).iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+			["std" | "core", "fmt", "Display"] => "Display",
+			["std" | "core", "error", "Error"] => "Error",
+			_ => return,
+		};
+		let start = node.span().start();
+		let end = node.self_ty.span().end();
+		self.violations.push(Violation {
+			rule: RULE,
+			file: self.path_str.clone(),
+			line: start.line,
+			column: start.column,
+			end: Some((end.line, end.column)),
+			message: format!("hand-written `{trait_name}` for the public error enum `{name}`\nHINT: `#[derive(thiserror::Error)]` on it, with an `#[error(\"..\")]` on each variant"),
+			severity: Severity::Error,
+			..Default::default()
+		});
+	}
+}
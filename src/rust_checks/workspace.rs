@@ -0,0 +1,148 @@
+//! Workspace discovery: which packages a `Cargo.toml` at the target root stands for.
+//!
+//! Follows cargo's own rules: the root package (if the manifest has one), every `[workspace] members` entry,
+//! and path dependencies that live inside the workspace directory, which cargo makes members implicitly.
+//! Anything under a `[workspace] exclude` entry is left out, even if listed or depended on.
+
+use std::{
+	fs,
+	path::{Component, Path, PathBuf},
+};
+
+/// Dependency tables whose `path` entries can pull in members
+const DEPENDENCY_TABLES: &[&str] = &["dependencies", "dev-dependencies", "build-dependencies"];
+/// Target tables that can set a custom `path`
+const TARGET_TABLES: &[&str] = &["bin", "example", "test", "bench"];
+
+/// Directories of the packages in the workspace rooted at `root`.
+/// Empty if `root/Cargo.toml` doesn't declare a `[workspace]`, i.e. it's a standalone package (or not there at all).
+pub fn packages(root: &Path) -> Vec<PathBuf> {
+	let Some(manifest) = read_manifest(root) else {
+		return Vec::new();
+	};
+	let Some(workspace) = manifest.get("workspace").and_then(toml::Value::as_table) else {
+		return Vec::new();
+	};
+
+	let excluded: Vec<PathBuf> = workspace
+		.get("exclude")
+		.and_then(toml::Value::as_array)
+		.into_iter()
+		.flatten()
+		.filter_map(toml::Value::as_str)
+		.map(|p| normalize(Path::new(p)))
+		.collect();
+	let is_excluded = |dir: &Path| relative_to(dir, root).is_some_and(|rel| excluded.iter().any(|e| rel.starts_with(e)));
+
+	let mut queue = Vec::new();
+	if manifest.contains_key("package") {
+		queue.push(root.to_path_buf());
+	}
+	let members = workspace.get("members").and_then(toml::Value::as_array).into_iter().flatten().filter_map(toml::Value::as_str);
+	for pattern in members {
+		queue.extend(expand_member(root, pattern));
+	}
+
+	// Path dependencies of members may be members themselves, so keep following them
+	let mut packages: Vec<(PathBuf, PathBuf)> = Vec::new();
+	while let Some(dir) = queue.pop() {
+		let key = normalize(&dir);
+		if packages.iter().any(|(seen, _)| *seen == key) || is_excluded(&dir) {
+			continue;
+		}
+		if let Some(manifest) = read_manifest(&dir) {
+			queue.extend(path_dependencies(&manifest).filter_map(|p| relative_to(&dir.join(p), root)).map(|p| root.join(p)));
+		}
+		packages.push((key, dir));
+	}
+	packages.sort();
+	packages.into_iter().map(|(_, dir)| dir).collect()
+}
+
+/// Entry files of the package's targets that set an explicit `path` in its manifest, e.g. `[lib] path = "lib.rs"`.
+pub fn target_paths(package_dir: &Path) -> Vec<PathBuf> {
+	let Some(manifest) = read_manifest(package_dir) else {
+		return Vec::new();
+	};
+	let lib = manifest.get("lib").and_then(toml::Value::as_table);
+	let targets = TARGET_TABLES
+		.iter()
+		.filter_map(|name| manifest.get(*name).and_then(toml::Value::as_array))
+		.flatten()
+		.filter_map(toml::Value::as_table);
+	lib.into_iter()
+		.chain(targets)
+		.filter_map(|target| target.get("path").and_then(toml::Value::as_str))
+		.map(|path| package_dir.join(path))
+		.collect()
+}
+
+/// The `src/` directory of the crate `path` is in, if it's a library or binary's.
+pub fn crate_src(path: &Path) -> Option<PathBuf> {
+	path.ancestors()
+		.skip(1)
+		.find(|dir| dir.file_name().is_some_and(|name| name == "src") && (dir.join("lib.rs").is_file() || dir.join("main.rs").is_file()))
+		.map(Path::to_path_buf)
+}
+
+fn read_manifest(dir: &Path) -> Option<toml::Table> {
+	let content = fs::read_to_string(dir.join("Cargo.toml")).ok()?;
+	match toml::from_str(&content) {
+		Ok(manifest) => Some(manifest),
+		Err(e) => {
+			eprintln!("codestyle: ignoring unparseable {:?}: {e}", dir.join("Cargo.toml"));
+			None
+		}
+	}
+}
+
+/// `path` values of the manifest's dependencies, including target-specific ones.
+fn path_dependencies(manifest: &toml::Table) -> impl Iterator<Item = &str> {
+	let targets = manifest
+		.get("target")
+		.and_then(toml::Value::as_table)
+		.into_iter()
+		.flat_map(|t| t.values())
+		.filter_map(toml::Value::as_table);
+	std::iter::once(manifest)
+		.chain(targets)
+		.flat_map(|table| DEPENDENCY_TABLES.iter().filter_map(|name| table.get(*name).and_then(toml::Value::as_table)))
+		.flat_map(|deps| deps.values())
+		.filter_map(|dep| dep.get("path").and_then(toml::Value::as_str))
+}
+
+/// Member directories matching `pattern`, which may be a glob like `crates/*`. As with cargo, only directories
+/// holding a `Cargo.toml` count as matches.
+fn expand_member(root: &Path, pattern: &str) -> Vec<PathBuf> {
+	if !pattern.contains(['*', '?', '[']) {
+		return vec![root.join(pattern)];
+	}
+	// The root is taken literally, in case its path happens to contain glob metacharacters
+	let full = Path::new(&glob::Pattern::escape(&root.to_string_lossy())).join(pattern);
+	let Ok(paths) = glob::glob(&full.to_string_lossy()) else {
+		eprintln!("codestyle: ignoring invalid workspace member pattern `{pattern}`");
+		return Vec::new();
+	};
+	paths.filter_map(Result::ok).filter(|dir| dir.join("Cargo.toml").is_file()).collect()
+}
+
+/// `path` relative to `root`, or `None` if it's outside.
+fn relative_to(path: &Path, root: &Path) -> Option<PathBuf> {
+	let relative = normalize(path).strip_prefix(normalize(root)).ok()?.to_path_buf();
+	(!relative.starts_with("..")).then_some(relative)
+}
+
+/// Resolve `.` and `..` lexically, so the same package reached two ways compares equal.
+pub fn normalize(path: &Path) -> PathBuf {
+	let mut normalized = PathBuf::new();
+	for component in path.components() {
+		match component {
+			Component::CurDir => {}
+			Component::ParentDir if normalized.file_name().is_some() => {
+				normalized.pop();
+			}
+			component => normalized.push(component),
+		}
+	}
+	normalized
+}
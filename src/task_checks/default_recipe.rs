@@ -0,0 +1,49 @@
+//! Lint to require a `default` or `help` recipe.
+//!
+//! Running `just`/`make` bare should be safe and tell you what's available, not kick off whatever recipe happens to be first.
+
+use std::path::Path;
+
+use super::{DEFAULT_RECIPE_NAMES, Recipe, TaskFileKind};
+use crate::rust_checks::{Fix, Severity, Violation};
+
+const RULE: &str = "default-recipe";
+pub fn check(path: &Path, content: &str, kind: TaskFileKind, recipes: &[Recipe]) -> Vec<Violation> {
+	if recipes.is_empty() || recipes.iter().any(|r| DEFAULT_RECIPE_NAMES.contains(&r.name)) {
+		return vec![];
+	}
+
+	// `just` runs the first recipe, so a listing one goes right before it
+	let fix = (kind == TaskFileKind::Just).then(|| {
+		let insert_at = recipe_preamble_start(content, recipes[0].start_byte);
+		Fix {
+			start_byte: insert_at,
+			end_byte: insert_at,
+			replacement: "default:\n\t@just --list\n\n".to_string(),
+		}
+	});
+
+	vec![Violation {
+		rule: RULE,
+		file: path.display().to_string(),
+		line: recipes[0].line,
+		column: 0,
+		message: format!("no `default` or `help` recipe; running `{}` bare executes `{}`", if kind == TaskFileKind::Just { "just" } else { "make" }, recipes[0].name),
+		severity: Severity::Error,
+		fix,
+		..Default::default()
+	}]
+}
+
+/// Walk back over the doc comments and `[attribute]`s directly above a recipe header.
+fn recipe_preamble_start(content: &str, line_start: usize) -> usize {
+	let mut start = line_start;
+	while start > 0 {
+		let prev_start = content[..start - 1].rfind('\n').map(|i| i + 1).unwrap_or(0);
+		if !content[prev_start..start].trim_start().starts_with(['#', '[']) {
+			break;
+		}
+		start = prev_start;
+	}
+	start
+}
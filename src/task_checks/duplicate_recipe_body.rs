@@ -0,0 +1,40 @@
+//! Lint to disallow recipes with identical bodies.
+//!
+//! A copy-pasted recipe drifts from the original; have one depend on the other, or alias it.
+
+use std::path::Path;
+
+use super::Recipe;
+use crate::rust_checks::{Label, Severity, Violation};
+
+const RULE: &str = "duplicate-recipe-body";
+pub fn check(path: &Path, recipes: &[Recipe]) -> Vec<Violation> {
+	let path_str = path.display().to_string();
+	let mut violations = Vec::new();
+
+	for (i, recipe) in recipes.iter().enumerate() {
+		if recipe.body.is_empty() {
+			continue;
+		}
+		// Makefile rules with several targets share one body, and so one header line
+		let Some(original) = recipes[..i].iter().find(|r| r.body == recipe.body && r.line != recipe.line) else {
+			continue;
+		};
+		violations.push(Violation {
+			rule: RULE,
+			file: path_str.clone(),
+			line: recipe.line,
+			column: 0,
+			message: format!("recipe `{}` has the same body as `{}` (line {})", recipe.name, original.name, original.line),
+			severity: Severity::Error,
+			labels: vec![Label {
+				line: original.line,
+				column: 0,
+				message: format!("`{}` defined here", original.name),
+			}],
+			..Default::default()
+		});
+	}
+
+	violations
+}
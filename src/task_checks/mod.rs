@@ -0,0 +1,212 @@
+//! Task-runner file checks (`justfile`, `Makefile`).
+//!
+//! Both formats boil down to named recipes with indented bodies, which is all the checks here look at.
+
+pub mod default_recipe;
+pub mod duplicate_recipe_body;
+pub mod phony_targets;
+
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+
+use smart_default::SmartDefault;
+use walkdir::WalkDir;
+
+use crate::rust_checks::{FailPolicy, OutputFormat, Violation, report_assert, report_format};
+
+const JUSTFILE_NAMES: &[&str] = &["justfile", "Justfile", ".justfile"];
+const MAKEFILE_NAMES: &[&str] = &["Makefile", "makefile", "GNUmakefile"];
+/// Names that conventionally mean "what happens when you run it bare".
+pub const DEFAULT_RECIPE_NAMES: &[&str] = &["default", "help"];
+
+#[derive(Clone, SmartDefault)]
+pub struct TaskCheckOptions {
+	/// Require a `default` or `help` recipe (default: true)
+	#[default = true]
+	pub default_recipe: bool,
+	/// Disallow recipes with identical bodies (default: true)
+	#[default = true]
+	pub duplicate_recipe_body: bool,
+	/// Require non-file Makefile targets to be declared `.PHONY` (default: true)
+	#[default = true]
+	pub phony_targets: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TaskFileKind {
+	Just,
+	Make,
+}
+
+#[derive(Clone, Debug)]
+pub struct Recipe<'a> {
+	pub name: &'a str,
+	/// 1-indexed line of the recipe header
+	pub line: usize,
+	/// Byte offset of the header line in the document
+	pub start_byte: usize,
+	/// Body lines, trimmed, without blank lines and comments
+	pub body: Vec<&'a str>,
+}
+
+pub fn run_assert(target_dir: &Path, opts: &TaskCheckOptions, format: OutputFormat, fail: FailPolicy) -> i32 {
+	if !target_dir.exists() {
+		eprintln!("Target directory does not exist: {target_dir:?}");
+		return 1;
+	}
+
+	let mut all_violations = Vec::new();
+	for (path, kind) in collect_task_files(target_dir) {
+		if let Ok(content) = fs::read_to_string(&path) {
+			all_violations.extend(check_file(&path, &content, kind, opts));
+		}
+	}
+
+	report_assert(&all_violations, format, fail)
+}
+
+pub fn run_format(target_dir: &Path, opts: &TaskCheckOptions) -> i32 {
+	if !target_dir.exists() {
+		eprintln!("Target directory does not exist: {target_dir:?}");
+		return 1;
+	}
+
+	let mut fixed_count = 0;
+	let mut unfixable_violations = Vec::new();
+
+	for (path, kind) in collect_task_files(target_dir) {
+		let Ok(content) = fs::read_to_string(&path) else {
+			continue;
+		};
+
+		// Every fix here is an insertion before a recipe header, so they never overlap
+		let (mut fixes, unfixable): (Vec<_>, Vec<_>) = check_file(&path, &content, kind, opts).into_iter().partition(|v| v.fix.is_some());
+		unfixable_violations.extend(unfixable);
+		if fixes.is_empty() {
+			continue;
+		}
+
+		fixes.sort_by_key(|v| std::cmp::Reverse(v.fix.as_ref().map(|f| f.start_byte)));
+		let mut new_content = content.clone();
+		for fix in fixes.iter().filter_map(|v| v.fix.as_ref()) {
+			new_content.replace_range(fix.start_byte..fix.end_byte, &fix.replacement);
+		}
+		if fs::write(&path, new_content).is_ok() {
+			fixed_count += fixes.len();
+		}
+	}
+
+	report_format(fixed_count, &unfixable_violations)
+}
+
+/// Run all enabled checks on a single task-runner file.
+pub fn check_file(path: &Path, content: &str, kind: TaskFileKind, opts: &TaskCheckOptions) -> Vec<Violation> {
+	let recipes = recipes(content, kind);
+	let mut violations = Vec::new();
+	if opts.default_recipe {
+		violations.extend(default_recipe::check(path, content, kind, &recipes));
+	}
+	if opts.duplicate_recipe_body {
+		violations.extend(duplicate_recipe_body::check(path, &recipes));
+	}
+	if opts.phony_targets && kind == TaskFileKind::Make {
+		violations.extend(phony_targets::check(path, content, &recipes));
+	}
+	violations
+}
+
+pub fn collect_task_files(target_dir: &Path) -> Vec<(PathBuf, TaskFileKind)> {
+	let walker = WalkDir::new(target_dir).into_iter().filter_entry(|e| {
+		let name = e.file_name().to_string_lossy();
+		!e.file_type().is_dir() || !(name.starts_with('.') || matches!(name.as_ref(), "node_modules" | "target"))
+	});
+
+	walker
+		.filter_map(Result::ok)
+		.filter(|e| e.file_type().is_file())
+		.filter_map(|e| {
+			let name = e.file_name().to_string_lossy();
+			let kind = if JUSTFILE_NAMES.contains(&name.as_ref()) {
+				TaskFileKind::Just
+			} else if MAKEFILE_NAMES.contains(&name.as_ref()) {
+				TaskFileKind::Make
+			} else {
+				return None;
+			};
+			Some((e.path().to_path_buf(), kind))
+		})
+		.collect()
+}
+
+/// Split a task-runner file into recipes. A Makefile rule with several targets yields one recipe per target.
+pub fn recipes(content: &str, kind: TaskFileKind) -> Vec<Recipe<'_>> {
+	let mut recipes: Vec<Recipe> = Vec::new();
+	// Recipes defined by the most recent header line
+	let mut open = 0..0;
+	let mut offset = 0;
+
+	for (i, raw_line) in content.split_inclusive('\n').enumerate() {
+		let line_offset = offset;
+		offset += raw_line.len();
+		let line = raw_line.trim_end_matches(['\n', '\r']);
+		let trimmed = line.trim();
+
+		if trimmed.is_empty() {
+			continue;
+		}
+		let is_body_line = match kind {
+			TaskFileKind::Just => line.starts_with([' ', '\t']),
+			TaskFileKind::Make => line.starts_with('\t'),
+		};
+		if is_body_line {
+			if !trimmed.starts_with('#') {
+				for recipe in &mut recipes[open.clone()] {
+					recipe.body.push(trimmed);
+				}
+			}
+			continue;
+		}
+
+		let start = recipes.len();
+		let names = match kind {
+			TaskFileKind::Just => just_recipe_name(line).into_iter().collect(),
+			TaskFileKind::Make => make_rule_targets(line),
+		};
+		recipes.extend(names.into_iter().map(|name| Recipe {
+			name,
+			line: i + 1,
+			start_byte: line_offset,
+			body: Vec::new(),
+		}));
+		open = start..recipes.len();
+	}
+
+	recipes
+}
+
+/// `[@]name [params]: [deps]`; assignments, settings and attributes aren't recipes.
+fn just_recipe_name(line: &str) -> Option<&str> {
+	let colon = line.find(':')?;
+	if line[colon + 1..].starts_with('=') {
+		return None;
+	}
+	let header = line[..colon].strip_prefix('@').unwrap_or(&line[..colon]);
+	let name = header.split_whitespace().next()?;
+	let is_ident = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+	is_ident.then_some(name)
+}
+
+/// `targets: prerequisites`; special (`.PHONY`), pattern and variable targets are skipped.
+fn make_rule_targets(line: &str) -> Vec<&str> {
+	let Some(colon) = line.find(':') else {
+		return vec![];
+	};
+	let (targets, rest) = (&line[..colon], line[colon..].trim_start_matches(':'));
+	// `X := y`, `X = a:b` and target-specific `foo: X = y` are assignments
+	if line.starts_with('#') || targets.contains('=') || rest.starts_with('=') || rest.contains('=') {
+		return vec![];
+	}
+	targets.split_whitespace().filter(|t| !t.starts_with('.') && !t.contains(['%', '$'])).collect()
+}
@@ -0,0 +1,61 @@
+//! Lint to require non-file Makefile targets to be declared `.PHONY`.
+//!
+//! Otherwise a stray file named like the target (e.g. a `test` directory) silently turns the target into a no-op.
+//! Targets that look like files (contain `.` or `/`, or write `$@`) are left alone.
+
+use std::path::Path;
+
+use super::Recipe;
+use crate::rust_checks::{Fix, Severity, Violation};
+
+const RULE: &str = "phony-targets";
+pub fn check(path: &Path, content: &str, recipes: &[Recipe]) -> Vec<Violation> {
+	let path_str = path.display().to_string();
+	let phony = phony_declarations(content);
+
+	let missing: Vec<&Recipe> = recipes
+		.iter()
+		.filter(|r| !phony.contains(&r.name))
+		.filter(|r| !r.name.contains(['.', '/']))
+		.filter(|r| !r.body.iter().any(|l| l.contains("$@") || l.contains(&format!("-o {}", r.name))))
+		.collect();
+
+	// Targets of one rule share a header line, and get one declaration
+	missing
+		.chunk_by(|a, b| a.line == b.line)
+		.map(|rule| {
+			let names: Vec<&str> = rule.iter().map(|r| r.name).collect();
+			let listed = names.iter().map(|n| format!("`{n}`")).collect::<Vec<_>>().join(", ");
+			Violation {
+				rule: RULE,
+				file: path_str.clone(),
+				line: rule[0].line,
+				column: 0,
+				message: format!("{} {listed} not declared `.PHONY`", if names.len() == 1 { "target" } else { "targets" }),
+				severity: Severity::Error,
+				fix: Some(Fix {
+					start_byte: rule[0].start_byte,
+					end_byte: rule[0].start_byte,
+					replacement: format!(".PHONY: {}\n", names.join(" ")),
+				}),
+				..Default::default()
+			}
+		})
+		.collect()
+}
+
+/// Names listed on `.PHONY:` lines, following `\` continuations.
+fn phony_declarations(content: &str) -> Vec<&str> {
+	let mut names = Vec::new();
+	let mut continues = false;
+	for line in content.lines() {
+		let list = match line.trim_start().strip_prefix(".PHONY:") {
+			Some(list) => list,
+			None if continues => line,
+			None => continue,
+		};
+		continues = list.trim_end().ends_with('\\');
+		names.extend(list.split_whitespace().map(|n| n.trim_end_matches('\\')).filter(|n| !n.is_empty()));
+	}
+	names
+}
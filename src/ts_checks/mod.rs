@@ -0,0 +1,187 @@
+//! TypeScript/JavaScript checks.
+//!
+//! Files are parsed with tree-sitter, so only checks that don't need a type checker live here.
+//! Violations and fixes go through the same pipeline as the Rust checks.
+
+pub mod no_any_cast;
+pub mod no_console_log;
+pub mod void_promise;
+
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+
+use smart_default::SmartDefault;
+use tree_sitter::{Node, Parser, Tree};
+use walkdir::WalkDir;
+
+use crate::rust_checks::{FailPolicy, Fix, OutputFormat, Violation, report_assert, report_format};
+
+const TS_EXTENSIONS: &[&str] = &["ts", "mts", "cts", "js", "mjs", "cjs"];
+const TSX_EXTENSIONS: &[&str] = &["tsx", "jsx"];
+
+#[derive(Clone, SmartDefault)]
+pub struct TsCheckOptions {
+	/// Disallow `as any` / `<any>` casts without `//ANY_CAST` comment (default: true)
+	#[default = true]
+	pub no_any_cast: bool,
+	/// Disallow `console.log` in src/ (default: true)
+	#[default = true]
+	pub no_console_log: bool,
+	/// Require `//FIRE_AND_FORGET` comment on `void promise` patterns (default: true)
+	#[default = true]
+	pub void_promise: bool,
+}
+
+pub struct TsFileInfo {
+	pub contents: String,
+	pub tree: Tree,
+	pub path: PathBuf,
+}
+
+pub fn run_assert(target_dir: &Path, opts: &TsCheckOptions, format: OutputFormat, fail: FailPolicy) -> i32 {
+	if !target_dir.exists() {
+		eprintln!("Target directory does not exist: {target_dir:?}");
+		return 1;
+	}
+
+	let mut all_violations = Vec::new();
+	for info in collect_ts_files(target_dir) {
+		all_violations.extend(check_file(&info, opts));
+	}
+
+	report_assert(&all_violations, format, fail)
+}
+
+pub fn run_format(target_dir: &Path, opts: &TsCheckOptions) -> i32 {
+	if !target_dir.exists() {
+		eprintln!("Target directory does not exist: {target_dir:?}");
+		return 1;
+	}
+
+	let mut fixed_count = 0;
+	let mut unfixable_violations = Vec::new();
+
+	for info in collect_ts_files(target_dir) {
+		let (file_fixed, file_unfixable) = format_file_iteratively(info, opts);
+		fixed_count += file_fixed;
+		unfixable_violations.extend(file_unfixable);
+	}
+
+	report_format(fixed_count, &unfixable_violations)
+}
+
+/// Run all enabled checks on a single parsed file.
+pub fn check_file(info: &TsFileInfo, opts: &TsCheckOptions) -> Vec<Violation> {
+	let mut violations = Vec::new();
+	if opts.no_any_cast {
+		violations.extend(no_any_cast::check(info));
+	}
+	if opts.no_console_log {
+		violations.extend(no_console_log::check(info));
+	}
+	if opts.void_promise {
+		violations.extend(void_promise::check(info));
+	}
+	violations
+}
+
+pub fn collect_ts_files(target_dir: &Path) -> Vec<TsFileInfo> {
+	let walker = WalkDir::new(target_dir).into_iter().filter_entry(|e| {
+		let name = e.file_name().to_string_lossy();
+		!name.starts_with('.') && !matches!(name.as_ref(), "node_modules" | "target" | "dist" | "build")
+	});
+
+	walker.filter_map(Result::ok).filter_map(|entry| parse_ts_file(entry.path().to_path_buf())).collect()
+}
+
+/// Call `f` on `node` and every node below it, in source order.
+pub fn walk_nodes<'tree>(node: Node<'tree>, f: &mut impl FnMut(Node<'tree>)) {
+	f(node);
+	let mut cursor = node.walk();
+	for child in node.children(&mut cursor) {
+		walk_nodes(child, f);
+	}
+}
+
+/// Check if the given 1-indexed line or the line above contains `marker`.
+pub fn has_marker_comment(content: &str, line: usize, marker: &str) -> bool {
+	let lines: Vec<&str> = content.lines().collect();
+	let with_space = marker.replacen("//", "// ", 1);
+	let matches = |l: &str| l.contains(marker) || l.contains(&with_space);
+
+	if line > 0 && line <= lines.len() && matches(lines[line - 1]) {
+		return true;
+	}
+	line > 1 && matches(lines[line - 2])
+}
+
+/// Apply one fix at a time, re-parsing after each, mirroring the Rust format mode.
+/// Fixes are applied in memory; the file is written once at the end, and only if it changed.
+fn format_file_iteratively(mut info: TsFileInfo, opts: &TsCheckOptions) -> (usize, Vec<Violation>) {
+	let original = info.contents.clone();
+	let mut fixed_count = 0;
+
+	let unfixable = loop {
+		let violations = check_file(&info, opts);
+		let Some(fix) = violations.iter().find_map(|v| v.fix.clone()) else {
+			break violations;
+		};
+		let Some(contents) = apply_fix(&info.contents, &fix) else {
+			break Vec::new();
+		};
+		let Some(reparsed) = parse_ts_source(info.path.clone(), contents) else {
+			break Vec::new();
+		};
+		info = reparsed;
+		fixed_count += 1;
+	};
+
+	if info.contents != original
+		&& let Err(e) = fs::write(&info.path, &info.contents)
+	{
+		eprintln!("Failed to write {:?}: {e}", info.path);
+		return (0, unfixable);
+	}
+	(fixed_count, unfixable)
+}
+
+fn apply_fix(contents: &str, fix: &Fix) -> Option<String> {
+	if fix.start_byte > contents.len() || fix.end_byte > contents.len() {
+		return None;
+	}
+	let mut new_content = contents.to_string();
+	new_content.replace_range(fix.start_byte..fix.end_byte, &fix.replacement);
+	Some(new_content)
+}
+
+fn parse_ts_file(path: PathBuf) -> Option<TsFileInfo> {
+	let ext = path.extension()?.to_str()?;
+	if !TS_EXTENSIONS.contains(&ext) && !TSX_EXTENSIONS.contains(&ext) {
+		return None;
+	}
+	let contents = fs::read_to_string(&path).ok()?;
+	parse_ts_source(path, contents)
+}
+
+/// Parse `contents` with the grammar `path`'s extension selects.
+fn parse_ts_source(path: PathBuf, contents: String) -> Option<TsFileInfo> {
+	let ext = path.extension()?.to_str()?;
+	let language = if TSX_EXTENSIONS.contains(&ext) {
+		tree_sitter_typescript::LANGUAGE_TSX
+	} else {
+		tree_sitter_typescript::LANGUAGE_TYPESCRIPT
+	};
+	let mut parser = Parser::new();
+	if let Err(e) = parser.set_language(&language.into()) {
+		eprintln!("Failed to load tree-sitter grammar for {path:?}: {e}");
+		return None;
+	}
+	let Some(tree) = parser.parse(&contents, None) else {
+		eprintln!("Failed to parse file {path:?}");
+		return None;
+	};
+
+	Some(TsFileInfo { contents, tree, path })
+}
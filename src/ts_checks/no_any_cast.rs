@@ -0,0 +1,53 @@
+//! Lint to disallow casting to `any` without a justification comment.
+//!
+//! `x as any` and `<any>x` silently opt out of type checking for everything downstream.
+//! A `//ANY_CAST` comment forces explicit acknowledgment of why it's unavoidable.
+
+use tree_sitter::Node;
+
+use super::{TsFileInfo, has_marker_comment, walk_nodes};
+use crate::rust_checks::{Severity, Violation};
+
+const RULE: &str = "no-any-cast";
+const MARKER: &str = "//ANY_CAST";
+pub fn check(info: &TsFileInfo) -> Vec<Violation> {
+	let mut violations = Vec::new();
+	let source = info.contents.as_bytes();
+
+	walk_nodes(info.tree.root_node(), &mut |node| {
+		if !is_any_cast(node, source) {
+			return;
+		}
+		let pos = node.start_position();
+		let line = pos.row + 1;
+		if has_marker_comment(&info.contents, line, MARKER) {
+			return;
+		}
+		violations.push(Violation {
+			rule: RULE,
+			file: info.path.display().to_string(),
+			line,
+			column: pos.column,
+			message: format!("cast to `any` without `{MARKER}` comment\nHINT: use `unknown` and narrow it, or explain why the type system can't express this"),
+			severity: Severity::Error,
+			..Default::default()
+		});
+	});
+
+	violations
+}
+
+fn is_any_cast(node: Node, source: &[u8]) -> bool {
+	let is_any = |n: Node| n.kind() == "predefined_type" && n.utf8_text(source) == Ok("any");
+	match node.kind() {
+		// `x as any` - the target type is the last named child
+		"as_expression" => node.named_child(node.named_child_count().saturating_sub(1)).is_some_and(is_any),
+		// `<any>x`
+		"type_assertion" => node
+			.named_child(0)
+			.filter(|args| args.kind() == "type_arguments")
+			.and_then(|args| args.named_child(0))
+			.is_some_and(is_any),
+		_ => false,
+	}
+}
@@ -0,0 +1,69 @@
+//! Lint to disallow `console.log` in `src/`.
+//!
+//! Debug prints tend to get committed by accident. Scripts and tests outside `src/` are exempt.
+//! When the call is a standalone statement on its own line(s), the fix removes it.
+
+use tree_sitter::Node;
+
+use super::{TsFileInfo, walk_nodes};
+use crate::rust_checks::{Fix, Severity, Violation};
+
+const RULE: &str = "no-console-log";
+pub fn check(info: &TsFileInfo) -> Vec<Violation> {
+	if !info.path.components().any(|c| c.as_os_str() == "src") {
+		return vec![];
+	}
+
+	let mut violations = Vec::new();
+	let source = info.contents.as_bytes();
+
+	walk_nodes(info.tree.root_node(), &mut |node| {
+		if node.kind() != "call_expression" || !is_console_log(node, source) {
+			return;
+		}
+		let pos = node.start_position();
+		violations.push(Violation {
+			rule: RULE,
+			file: info.path.display().to_string(),
+			line: pos.row + 1,
+			column: pos.column,
+			message: "`console.log` in src/\nHINT: use a proper logger, or remove the debug print".to_string(),
+			severity: Severity::Error,
+			fix: create_remove_statement_fix(&info.contents, node),
+			..Default::default()
+		});
+	});
+
+	violations
+}
+
+fn is_console_log(call: Node, source: &[u8]) -> bool {
+	let Some(function) = call.child_by_field_name("function") else {
+		return false;
+	};
+	if function.kind() != "member_expression" {
+		return false;
+	}
+	let text = |field: &str| function.child_by_field_name(field).and_then(|n| n.utf8_text(source).ok());
+	text("object") == Some("console") && text("property") == Some("log")
+}
+
+/// Remove the whole statement if it is the only thing on its lines.
+fn create_remove_statement_fix(content: &str, call: Node) -> Option<Fix> {
+	let statement = call.parent().filter(|p| p.kind() == "expression_statement")?;
+
+	let line_start = content[..statement.start_byte()].rfind('\n').map(|i| i + 1).unwrap_or(0);
+	let line_end = content[statement.end_byte()..].find('\n').map(|i| statement.end_byte() + i + 1).unwrap_or(content.len());
+
+	let before = &content[line_start..statement.start_byte()];
+	let after = &content[statement.end_byte()..line_end];
+	if !before.trim().is_empty() || !after.trim().is_empty() {
+		return None;
+	}
+
+	Some(Fix {
+		start_byte: line_start,
+		end_byte: line_end,
+		replacement: String::new(),
+	})
+}
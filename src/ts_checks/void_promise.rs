@@ -0,0 +1,52 @@
+//! Lint to require justification for fire-and-forget `void promise` patterns.
+//!
+//! `void doWork()` detaches the promise: errors are swallowed and the work may outlive its caller.
+//! This is the JS equivalent of an unstructured `tokio::spawn`, so it needs a `//FIRE_AND_FORGET` comment.
+//! See: "Go statement considered harmful" - <https://vorpus.org/blog/notes-on-structured-concurrency-or-go-statement-considered-harmful>
+
+use super::{TsFileInfo, has_marker_comment, walk_nodes};
+use crate::rust_checks::{Severity, Violation};
+
+const RULE: &str = "void-promise";
+const MARKER: &str = "//FIRE_AND_FORGET";
+const GO_STATEMENT_HARMFUL_URL: &str = "https://vorpus.org/blog/notes-on-structured-concurrency-or-go-statement-considered-harmful/";
+pub fn check(info: &TsFileInfo) -> Vec<Violation> {
+	let mut violations = Vec::new();
+	let source = info.contents.as_bytes();
+
+	walk_nodes(info.tree.root_node(), &mut |node| {
+		if node.kind() != "unary_expression" {
+			return;
+		}
+		if node.child_by_field_name("operator").and_then(|op| op.utf8_text(source).ok()) != Some("void") {
+			return;
+		}
+		// `void 0` is the `undefined` idiom, not a detached promise
+		let Some(argument) = node.child_by_field_name("argument") else {
+			return;
+		};
+		if !matches!(argument.kind(), "call_expression" | "identifier" | "member_expression" | "new_expression") {
+			return;
+		}
+
+		let pos = node.start_position();
+		let line = pos.row + 1;
+		if has_marker_comment(&info.contents, line, MARKER) {
+			return;
+		}
+		violations.push(Violation {
+			rule: RULE,
+			file: info.path.display().to_string(),
+			line,
+			column: pos.column,
+			message: format!(
+				"fire-and-forget `void` promise without `{MARKER}` comment\n\
+				HINT: `await` it, or justify why the work may outlive its caller. See: {GO_STATEMENT_HARMFUL_URL}"
+			),
+			severity: Severity::Error,
+			..Default::default()
+		});
+	});
+
+	violations
+}
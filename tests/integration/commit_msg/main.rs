@@ -0,0 +1,107 @@
+//! Commit message check integration tests.
+
+use std::path::Path;
+
+use codestyle::commit_msg_checks::{CommitMsgOptions, check_message};
+
+fn check(content: &str, opts: &CommitMsgOptions) -> String {
+	check_message(Path::new("COMMIT_EDITMSG"), content, opts)
+		.iter()
+		.map(|v| format!("[{}] {}:{}: {}", v.rule, v.file, v.line, v.message))
+		.collect::<Vec<_>>()
+		.join("\n")
+}
+
+fn all_opts() -> CommitMsgOptions {
+	CommitMsgOptions {
+		issue_reference: true,
+		..Default::default()
+	}
+}
+
+// === Passing cases ===
+
+#[test]
+fn conforming_message_passes() {
+	let content = r#"feat(gha)!: add workflow checks (#42)
+
+Longer explanation of the change, which can be as long as it likes to be without tripping the subject limit.
+# Please enter the commit message for your changes. Lines starting
+# with '#' will be ignored.
+"#;
+	assert_eq!(check(content, &all_opts()), "");
+}
+
+#[test]
+fn generated_messages_pass() {
+	for content in ["Merge branch 'master' into feature\n", "fixup! feat: add thing\n", "Revert \"feat: add thing\"\n"] {
+		assert_eq!(check(content, &all_opts()), "");
+	}
+}
+
+#[test]
+fn scissors_content_ignored() {
+	let content = r#"fix: handle empty input
+
+Closes valeratrades/codestyle#7
+# ------------------------ >8 ------------------------
+# Do not modify or remove the line above.
+diff --git a/src/main.rs b/src/main.rs
+Added a very long line that would otherwise be reported as a subject if parsing went wrong at all here
+"#;
+	assert_eq!(check(content, &all_opts()), "");
+}
+
+#[test]
+fn tracker_key_reference_passes() {
+	assert_eq!(check("chore: bump deps\n\nRefs: INFRA-118\n", &all_opts()), "");
+}
+
+// === Violation cases ===
+
+#[test]
+fn subject_too_long() {
+	let opts = CommitMsgOptions {
+		max_subject_len: 40,
+		..Default::default()
+	};
+	insta::assert_snapshot!(check("refactor: split the rule runner into per-language modules\n", &opts), @"
+	[subject-length] COMMIT_EDITMSG:1: subject is 57 characters long, limit is 40
+	HINT: move the details into the body, after a blank line
+	");
+}
+
+#[test]
+fn missing_and_unknown_prefix() {
+	let opts = CommitMsgOptions::default();
+	insta::assert_snapshot!(check("update readme\n", &opts) + "\n" + &check("feature: add gha checks\n", &opts), @"
+	[conventional-prefix] COMMIT_EDITMSG:1: subject has no conventional-commit prefix
+	HINT: start it with `<type>: ` or `<type>(<scope>): `, where type is one of: build, chore, ci, docs, feat, fix, perf, refactor, revert, style, test
+	[conventional-prefix] COMMIT_EDITMSG:1: unknown commit type `feature`; expected one of: build, chore, ci, docs, feat, fix, perf, refactor, revert, style, test
+	");
+}
+
+#[test]
+fn non_imperative_subjects() {
+	let opts = CommitMsgOptions {
+		conventional_prefix: false,
+		..Default::default()
+	};
+	let subjects = ["Added docker checks", "fix: fixes CRLF handling", "perf(rust): skipping unchanged files", "Made it faster", "Support stdin"];
+	let rendered: Vec<String> = subjects.iter().map(|s| check(s, &opts)).filter(|r| !r.is_empty()).collect();
+	insta::assert_snapshot!(rendered.join("\n"), @"
+	[imperative-mood] COMMIT_EDITMSG:1: subject should use the imperative mood: `Add`, not `Added`
+	[imperative-mood] COMMIT_EDITMSG:1: subject should use the imperative mood: `fix`, not `fixes`
+	[imperative-mood] COMMIT_EDITMSG:1: subject should use the imperative mood: `skip`, not `skipping`
+	[imperative-mood] COMMIT_EDITMSG:1: subject should use the imperative mood: `Make`, not `Made`
+	");
+}
+
+#[test]
+fn missing_issue_reference() {
+	let content = "fix: handle empty input\n\nThe parser used to panic on `\"\"`.\n";
+	insta::assert_snapshot!(check(content, &all_opts()), @"
+	[issue-reference] COMMIT_EDITMSG:3: commit message doesn't reference an issue
+	HINT: end the subject with `(#123)` or add a `Closes #123` line
+	");
+}
@@ -0,0 +1,8 @@
+//! Dockerfile check integration tests.
+//!
+//! Dockerfile checks operate on a single file's instructions, so tests call them directly.
+
+mod non_root_user;
+mod pinned_base_image;
+mod prefer_copy;
+mod utils;
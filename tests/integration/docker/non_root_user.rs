@@ -0,0 +1,61 @@
+use crate::utils::{check, opts_for, render};
+
+fn opts() -> codestyle::docker_checks::DockerCheckOptions {
+	opts_for("non_root_user")
+}
+
+// === Passing cases ===
+
+#[test]
+fn final_stage_user_passes() {
+	let content = r#"FROM rust:1.85 AS builder
+USER root
+RUN cargo build --release
+
+FROM debian:bookworm-slim
+COPY --from=builder /app/target/release/app /usr/local/bin/app
+USER 10001:10001
+CMD ["app"]
+"#;
+	assert!(check(content, &opts()).is_empty());
+}
+
+#[test]
+fn heredoc_body_not_parsed() {
+	let content = r#"FROM debian:bookworm-slim
+RUN <<EOF
+useradd app
+FROM nothing
+EOF
+USER app
+"#;
+	assert!(check(content, &opts()).is_empty());
+}
+
+// === Violation cases ===
+
+#[test]
+fn missing_user_in_final_stage() {
+	let content = r#"FROM rust:1.85 AS builder
+USER builder
+RUN cargo build --release
+
+FROM debian:bookworm-slim
+CMD ["app"]
+"#;
+	insta::assert_snapshot!(render(&check(content, &opts())), @"
+	[non-root-user] Dockerfile:5: final stage runs as root
+	HINT: add a non-root `USER` before `CMD`/`ENTRYPOINT`
+	");
+}
+
+#[test]
+fn switching_back_to_root() {
+	let content = r#"FROM debian:bookworm-slim
+USER app
+RUN echo hi \
+  && echo bye
+USER root:root
+"#;
+	insta::assert_snapshot!(render(&check(content, &opts())), @"[non-root-user] Dockerfile:5: final stage switches back to root as its last `USER`");
+}
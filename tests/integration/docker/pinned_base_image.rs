@@ -0,0 +1,35 @@
+use crate::utils::{check, opts_for, render};
+
+fn opts() -> codestyle::docker_checks::DockerCheckOptions {
+	opts_for("pinned_base_image")
+}
+
+// === Passing cases ===
+
+#[test]
+fn tags_digests_and_stages_pass() {
+	let content = r#"ARG RUST_VERSION=1.85
+FROM rust:${RUST_VERSION} AS builder
+FROM --platform=$BUILDPLATFORM debian:bookworm-slim AS runtime
+FROM alpine@sha256:4bcff63911fcb4448bd4fdacec207030997caf25e9bea4045fa6c8c44de311d1
+FROM localhost:5000/tools:1.2
+FROM builder
+FROM scratch
+"#;
+	assert!(check(content, &opts()).is_empty());
+}
+
+// === Violation cases ===
+
+#[test]
+fn unpinned_images() {
+	let content = r#"FROM rust AS builder
+from ubuntu:latest
+FROM localhost:5000/tools
+"#;
+	insta::assert_snapshot!(render(&check(content, &opts())), @"
+	[pinned-base-image] Dockerfile:1: base image `rust` is not pinned; pin it to a version tag or digest
+	[pinned-base-image] Dockerfile:2: base image `ubuntu:latest` uses the mutable `latest` tag; pin it to a version tag or digest
+	[pinned-base-image] Dockerfile:3: base image `localhost:5000/tools` is not pinned; pin it to a version tag or digest
+	");
+}
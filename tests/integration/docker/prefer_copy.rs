@@ -0,0 +1,54 @@
+use crate::utils::{apply_fixes, check, opts_for, render};
+
+fn opts() -> codestyle::docker_checks::DockerCheckOptions {
+	opts_for("prefer_copy")
+}
+
+// === Passing cases ===
+
+#[test]
+fn urls_archives_and_add_only_flags_pass() {
+	let content = r#"FROM debian:bookworm-slim
+ADD https://example.com/tool.sh /usr/local/bin/tool.sh
+ADD rootfs.tar.gz /
+ADD --checksum=sha256:24454f830cdb571e2c4ad15481119c43b3cafd48dd869a9b2945d1036d1dc68d app.bin /app/
+ADD git@github.com:valeratrades/codestyle.git /src
+ADD $ARTIFACT /app/
+COPY . /app
+"#;
+	assert!(check(content, &opts()).is_empty());
+}
+
+// === Violation cases ===
+
+#[test]
+fn add_for_plain_files() {
+	let content = r#"FROM debian:bookworm-slim
+ADD . /app
+  ADD --chown=app:app config.toml \
+    /etc/app/
+ADD ["entrypoint.sh", "/usr/local/bin/"]
+"#;
+	insta::assert_snapshot!(render(&check(content, &opts())), @"
+	[prefer-copy] Dockerfile:2: `ADD` used for plain files; use `COPY`
+	[prefer-copy] Dockerfile:3: `ADD` used for plain files; use `COPY`
+	[prefer-copy] Dockerfile:5: `ADD` used for plain files; use `COPY`
+	");
+}
+
+#[test]
+fn fix_replaces_add_with_copy() {
+	let content = r#"FROM debian:bookworm-slim
+ADD . /app
+  ADD --chown=app:app config.toml \
+    /etc/app/
+ADD https://example.com/tool.sh /usr/local/bin/tool.sh
+"#;
+	insta::assert_snapshot!(apply_fixes(content, check(content, &opts())), @r"
+	FROM debian:bookworm-slim
+	COPY . /app
+	  COPY --chown=app:app config.toml \
+	    /etc/app/
+	ADD https://example.com/tool.sh /usr/local/bin/tool.sh
+	");
+}
@@ -0,0 +1,36 @@
+use std::path::Path;
+
+use codestyle::{
+	docker_checks::{DockerCheckOptions, check_file},
+	rust_checks::Violation,
+};
+
+pub(crate) const DOCKERFILE_PATH: &str = "Dockerfile";
+
+/// Options with only the named check enabled.
+pub(crate) fn opts_for(check: &str) -> DockerCheckOptions {
+	DockerCheckOptions {
+		pinned_base_image: check == "pinned_base_image",
+		non_root_user: check == "non_root_user",
+		prefer_copy: check == "prefer_copy",
+	}
+}
+
+pub(crate) fn check(content: &str, opts: &DockerCheckOptions) -> Vec<Violation> {
+	check_file(Path::new(DOCKERFILE_PATH), content, opts)
+}
+
+pub(crate) fn render(violations: &[Violation]) -> String {
+	violations.iter().map(|v| format!("[{}] {}:{}: {}", v.rule, v.file, v.line, v.message)).collect::<Vec<_>>().join("\n")
+}
+
+/// Apply all fixes, highest offset first.
+pub(crate) fn apply_fixes(content: &str, violations: Vec<Violation>) -> String {
+	let mut fixes: Vec<_> = violations.into_iter().filter_map(|v| v.fix).collect();
+	fixes.sort_by_key(|f| std::cmp::Reverse(f.start_byte));
+	let mut result = content.to_string();
+	for fix in fixes {
+		result.replace_range(fix.start_byte..fix.end_byte, &fix.replacement);
+	}
+	result
+}
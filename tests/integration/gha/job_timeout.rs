@@ -0,0 +1,45 @@
+use std::path::Path;
+
+use codestyle::{gha_checks::job_timeout, rust_checks::Violation};
+
+use crate::utils::{WORKFLOW_PATH, render};
+
+fn check(content: &str) -> Vec<Violation> {
+	job_timeout::check(Path::new(WORKFLOW_PATH), content)
+}
+
+// === Passing cases ===
+
+#[test]
+fn timeouts_and_reusable_workflows_pass() {
+	let content = r#"jobs:
+  pre_ci:
+    uses: valeratrades/.github/.github/workflows/pre_ci.yml@master
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: cargo build
+    timeout-minutes: 45
+"#;
+	assert!(check(content).is_empty());
+}
+
+// === Violation cases ===
+
+#[test]
+fn missing_timeouts() {
+	let content = r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: cargo build
+        timeout-minutes: 10
+  "test suite":
+    runs-on: ubuntu-latest
+"#;
+	insta::assert_snapshot!(render(&check(content)), @"
+	[job-timeout] .github/workflows/ci.yml:3: job `build` has no `timeout-minutes`
+	[job-timeout] .github/workflows/ci.yml:8: job `test suite` has no `timeout-minutes`
+	");
+}
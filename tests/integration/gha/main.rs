@@ -0,0 +1,8 @@
+//! GitHub Actions workflow check integration tests.
+//!
+//! Workflow checks operate on a single file's content, so tests call them directly.
+
+mod job_timeout;
+mod pinned_actions;
+mod utils;
+mod workflow_permissions;
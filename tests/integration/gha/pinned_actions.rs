@@ -0,0 +1,88 @@
+use std::path::Path;
+
+use codestyle::{gha_checks::pinned_actions, rust_checks::Violation};
+
+use crate::utils::{WORKFLOW_PATH, apply_fixes, render};
+
+const SHA: &str = "0123456789abcdef0123456789abcdef01234567";
+
+fn check(content: &str) -> Vec<Violation> {
+	pinned_actions::check(Path::new(WORKFLOW_PATH), content, None)
+}
+
+fn fake_resolver(_repo: &str, _git_ref: &str) -> Option<String> {
+	Some(SHA.to_string())
+}
+
+// === Passing cases ===
+
+#[test]
+fn first_party_local_and_pinned_pass() {
+	let content = r#"jobs:
+  build:
+    steps:
+      - uses: actions/checkout@v4
+      - uses: github/codeql-action/init@v3
+      - uses: ./.github/actions/setup
+      - uses: docker://alpine:3.20
+      - uses: dtolnay/rust-toolchain@0123456789abcdef0123456789abcdef01234567 # master
+"#;
+	assert!(check(content).is_empty());
+}
+
+#[test]
+fn uses_inside_run_script_ignored() {
+	let content = r#"jobs:
+  build:
+    steps:
+      - run: |
+          echo "uses: foo/bar@main"
+"#;
+	assert!(check(content).is_empty());
+}
+
+// === Violation cases ===
+
+#[test]
+fn mutable_refs() {
+	let content = r#"jobs:
+  build:
+    steps:
+      - uses: rui314/setup-mold@v1
+      - name: Toolchain
+        uses: "dtolnay/rust-toolchain@master"
+  call:
+    uses: valeratrades/.github/.github/workflows/pre_ci.yml@master
+"#;
+	insta::assert_snapshot!(render(&check(content)), @"
+	[pinned-actions] .github/workflows/ci.yml:4: third-party action `rui314/setup-mold` is pinned to mutable ref `v1`; pin it to a full commit SHA
+	[pinned-actions] .github/workflows/ci.yml:6: third-party action `dtolnay/rust-toolchain` is pinned to mutable ref `master`; pin it to a full commit SHA
+	[pinned-actions] .github/workflows/ci.yml:8: third-party action `valeratrades/.github/.github/workflows/pre_ci.yml` is pinned to mutable ref `master`; pin it to a full commit SHA
+	");
+}
+
+#[test]
+fn no_fix_without_network() {
+	let content = "jobs:\n  build:\n    steps:\n      - uses: rui314/setup-mold@v1\n";
+	assert!(check(content).iter().all(|v| v.fix.is_none()));
+}
+
+#[test]
+fn fix_pins_to_resolved_sha() {
+	let content = r#"jobs:
+  build:
+    steps:
+      - uses: rui314/setup-mold@v1
+      - uses: "dtolnay/rust-toolchain@master"
+      - uses: Swatinem/rust-cache@v2 # caches target/
+"#;
+	let violations = pinned_actions::check(Path::new(WORKFLOW_PATH), content, Some(&fake_resolver));
+	insta::assert_snapshot!(apply_fixes(content, violations), @r#"
+	jobs:
+	  build:
+	    steps:
+	      - uses: rui314/setup-mold@0123456789abcdef0123456789abcdef01234567 # v1
+	      - uses: "dtolnay/rust-toolchain@0123456789abcdef0123456789abcdef01234567" # master
+	      - uses: Swatinem/rust-cache@0123456789abcdef0123456789abcdef01234567 # caches target/
+	"#);
+}
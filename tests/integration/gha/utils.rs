@@ -0,0 +1,18 @@
+use codestyle::rust_checks::Violation;
+
+pub(crate) const WORKFLOW_PATH: &str = ".github/workflows/ci.yml";
+
+pub(crate) fn render(violations: &[Violation]) -> String {
+	violations.iter().map(|v| format!("[{}] {}:{}: {}", v.rule, v.file, v.line, v.message)).collect::<Vec<_>>().join("\n")
+}
+
+/// Apply all fixes, highest offset first.
+pub(crate) fn apply_fixes(content: &str, violations: Vec<Violation>) -> String {
+	let mut fixes: Vec<_> = violations.into_iter().filter_map(|v| v.fix).collect();
+	fixes.sort_by_key(|f| std::cmp::Reverse(f.start_byte));
+	let mut result = content.to_string();
+	for fix in fixes {
+		result.replace_range(fix.start_byte..fix.end_byte, &fix.replacement);
+	}
+	result
+}
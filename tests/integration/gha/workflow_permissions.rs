@@ -0,0 +1,55 @@
+use std::path::Path;
+
+use codestyle::{gha_checks::workflow_permissions, rust_checks::Violation};
+
+use crate::utils::{WORKFLOW_PATH, render};
+
+fn check(content: &str) -> Vec<Violation> {
+	workflow_permissions::check(Path::new(WORKFLOW_PATH), content)
+}
+
+// === Passing cases ===
+
+#[test]
+fn top_level_permissions_pass() {
+	let content = r#"name: CI
+permissions:
+  contents: read
+jobs:
+  build:
+    runs-on: ubuntu-latest
+"#;
+	assert!(check(content).is_empty());
+}
+
+#[test]
+fn every_job_declaring_permissions_passes() {
+	let content = r#"jobs:
+  build:
+    permissions:
+      contents: read
+  release:
+    permissions: write-all
+"#;
+	assert!(check(content).is_empty());
+}
+
+// === Violation cases ===
+
+#[test]
+fn missing_permissions() {
+	let content = r#"name: CI
+jobs:
+  build:
+    permissions:
+      contents: read
+  release:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "permissions: none"
+"#;
+	insta::assert_snapshot!(render(&check(content)), @"
+	[workflow-permissions] .github/workflows/ci.yml:1: workflow has no `permissions:` block
+	HINT: add `permissions: { contents: read }` at the top level and widen it per job where needed
+	");
+}
@@ -0,0 +1,76 @@
+//! `install-hooks` integration tests.
+
+use std::{
+	fs,
+	path::{Path, PathBuf},
+	process::Command,
+};
+
+use codestyle::hooks::{Hook, run_install_hooks};
+
+fn git(dir: &Path, args: &[&str]) -> bool {
+	let bin_dir = Path::new(env!("CARGO_BIN_EXE_codestyle")).parent().unwrap();
+	let path = std::env::join_paths(std::iter::once(bin_dir.to_path_buf()).chain(std::env::split_paths(&std::env::var_os("PATH").unwrap_or_default()))).unwrap();
+	Command::new("git")
+		.args(["-c", "user.name=test", "-c", "user.email=test@example.com", "-c", "commit.gpgsign=false"])
+		.args(args)
+		.current_dir(dir)
+		.env("PATH", path)
+		.output()
+		.unwrap()
+		.status
+		.success()
+}
+
+fn repo() -> (tempfile::TempDir, PathBuf) {
+	let dir = tempfile::tempdir().unwrap();
+	assert!(git(dir.path(), &["init", "-q"]));
+	let hook = dir.path().join(".git/hooks/pre-commit");
+	(dir, hook)
+}
+
+#[test]
+fn installs_and_uninstalls() {
+	let (dir, hook) = repo();
+	assert_eq!(run_install_hooks(dir.path(), Hook::PreCommit, false, false), 0);
+	assert_eq!(fs::read_to_string(&hook).unwrap(), Hook::PreCommit.script());
+	// Reinstalling over our own hook is fine
+	assert_eq!(run_install_hooks(dir.path(), Hook::PreCommit, false, false), 0);
+
+	assert_eq!(run_install_hooks(dir.path(), Hook::PreCommit, true, false), 0);
+	assert!(!hook.exists());
+	assert_eq!(run_install_hooks(dir.path(), Hook::PreCommit, true, false), 0);
+}
+
+#[test]
+fn foreign_hooks_are_left_alone() {
+	let (dir, hook) = repo();
+	fs::write(&hook, "#!/bin/sh\nmake lint\n").unwrap();
+	assert_eq!(run_install_hooks(dir.path(), Hook::PreCommit, false, false), 1);
+	assert_eq!(run_install_hooks(dir.path(), Hook::PreCommit, true, false), 1);
+	assert_eq!(fs::read_to_string(&hook).unwrap(), "#!/bin/sh\nmake lint\n");
+
+	assert_eq!(run_install_hooks(dir.path(), Hook::PreCommit, false, true), 0);
+	assert_eq!(fs::read_to_string(&hook).unwrap(), Hook::PreCommit.script());
+}
+
+#[test]
+fn outside_a_repository_fails() {
+	let dir = tempfile::tempdir().unwrap();
+	assert_eq!(run_install_hooks(dir.path(), Hook::PreCommit, false, false), 1);
+}
+
+#[cfg(unix)]
+#[test]
+fn pre_commit_checks_staged_files() {
+	let (dir, _) = repo();
+	assert_eq!(run_install_hooks(dir.path(), Hook::PreCommit, false, false), 0);
+
+	fs::write(dir.path().join("notes.txt"), "not rust\n").unwrap();
+	assert!(git(dir.path(), &["add", "notes.txt"]));
+	assert!(git(dir.path(), &["commit", "-qm", "Add notes"]));
+
+	fs::write(dir.path().join("time.rs"), "use chrono::Utc;\n").unwrap();
+	assert!(git(dir.path(), &["add", "time.rs"]));
+	assert!(!git(dir.path(), &["commit", "-qm", "Add time"]));
+}
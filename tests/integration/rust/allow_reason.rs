@@ -0,0 +1,70 @@
+use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
+
+fn opts() -> codestyle::rust_checks::RustCheckOptions {
+	opts_for("allow_reason")
+}
+
+// === Passing cases ===
+
+#[test]
+fn justified_suppressions_pass() {
+	assert_check_passing(
+		r#"
+		#![expect(clippy::module_inception, reason = "re-exported under the crate root")]
+
+		#[allow(clippy::too_many_arguments, reason = "mirrors the shader's uniforms")]
+		fn render(a: u8, b: u8, c: u8, d: u8, e: u8, f: u8, g: u8, h: u8) {}
+
+		// Read through the FFI layout only
+		#[derive(Debug)]
+		#[allow(dead_code)]
+		struct Raw {
+			#[allow(unused)] // padding, see the C header
+			pad: [u8; 3],
+		}
+		"#,
+		&opts(),
+	);
+}
+
+#[test]
+fn other_attributes_pass() {
+	assert_check_passing(
+		r#"
+		#[derive(Debug)]
+		#[cfg_attr(test, derive(Clone))]
+		struct S;
+
+		fn main() {
+			let s = "1".parse::<u8>().expect("a digit literal");
+		}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn unjustified_suppressions() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		#![allow(unused_imports)]
+
+		/// Raw bytes, as read from the socket.
+		#[allow(dead_code)]
+		struct Raw {
+			#[expect(unused)]
+			pad: [u8; 3],
+		}
+		"#,
+		&opts(),
+	), @r#"
+	[allow-reason] /main.rs:1: `#[allow(..)]` without a reason
+	HINT: add `reason = ".."`, or a `//` comment above it saying why the lint doesn't apply
+	[allow-reason] /main.rs:4: `#[allow(..)]` without a reason
+	HINT: add `reason = ".."`, or a `//` comment above it saying why the lint doesn't apply
+	[allow-reason] /main.rs:6: `#[expect(..)]` without a reason
+	HINT: add `reason = ".."`, or a `//` comment above it saying why the lint doesn't apply
+	"#);
+}
@@ -0,0 +1,50 @@
+use std::{fs, slice};
+
+use codestyle::rust_checks::{self, AssertOptions};
+use v_fixtures::Fixture;
+
+use crate::utils::opts_for;
+
+#[test]
+fn only_new_violations_fail() {
+	let temp = Fixture::parse(
+		r#"
+		//- /main.rs
+		use chrono::Utc;
+		"#,
+	)
+	.write_to_tempdir();
+	let opts = opts_for("no_chrono");
+	let path = temp.root.join("main.rs");
+	let with_baseline = |write_baseline| AssertOptions {
+		baseline: Some(temp.root.join("baseline.json")),
+		write_baseline,
+		..Default::default()
+	};
+
+	assert_eq!(rust_checks::run_assert(slice::from_ref(&temp.root), &opts, &with_baseline(true)), 0);
+	assert_eq!(rust_checks::run_assert(slice::from_ref(&temp.root), &opts, &with_baseline(false)), 0);
+
+	// Shifting the recorded violation down doesn't invalidate it
+	fs::write(&path, format!("//! docs\n\n{}", fs::read_to_string(&path).unwrap())).unwrap();
+	assert_eq!(rust_checks::run_assert(slice::from_ref(&temp.root), &opts, &with_baseline(false)), 0);
+
+	fs::write(&path, format!("{}use chrono::Local;\n", fs::read_to_string(&path).unwrap())).unwrap();
+	assert_eq!(rust_checks::run_assert(slice::from_ref(&temp.root), &opts, &with_baseline(false)), 1);
+}
+
+#[test]
+fn missing_baseline_is_an_error() {
+	let temp = Fixture::parse(
+		r#"
+		//- /main.rs
+		fn main() {}
+		"#,
+	)
+	.write_to_tempdir();
+	let assert = AssertOptions {
+		baseline: Some(temp.root.join("baseline.json")),
+		..Default::default()
+	};
+	assert_eq!(rust_checks::run_assert(slice::from_ref(&temp.root), &opts_for(""), &assert), 1);
+}
@@ -0,0 +1,75 @@
+use codestyle::rust_checks::RustCheckOptions;
+
+use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
+
+fn opts() -> RustCheckOptions {
+	opts_for("bool_params")
+}
+
+// === Passing cases ===
+
+#[test]
+fn single_private_bool_and_enums_pass() {
+	assert_check_passing(
+		r#"
+		fn log(message: &str, verbose: bool) {}
+
+		pub fn connect(host: &str, tls: Tls, retry: Retry) {}
+
+		pub fn flags(flags: &[bool], maybe: Option<bool>) {}
+
+		impl Visitor for Printer {
+			fn visit(&mut self, node: &Node, first: bool, last: bool) {}
+		}
+		"#,
+		&opts(),
+	);
+}
+
+#[test]
+fn pub_bools_pass_when_not_configured() {
+	let opts = RustCheckOptions {
+		bool_params_in_pub: false,
+		..opts()
+	};
+	assert_check_passing(
+		r#"
+		pub fn log(message: &str, verbose: bool) {}
+		"#,
+		&opts,
+	);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn bare_bools() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		fn copy(from: &Path, to: &Path, overwrite: bool, follow_links: bool) {}
+
+		pub fn log(message: &str, verbose: bool) {}
+
+		impl Client {
+			pub fn connect(&self, tls: bool) {}
+			fn retry(&self, _: bool, backoff: bool) {}
+		}
+
+		pub trait Render {
+			fn render(&self, dark: bool);
+		}
+		"#,
+		&opts(),
+	), @"
+	[bool-params] /main.rs:1: `copy` takes 2 `bool` parameters, `overwrite`, `follow_links`
+	HINT: use a two-variant enum, so call sites say what they mean
+	[bool-params] /main.rs:3: public `log` takes a `bool` parameter, `verbose`
+	HINT: use a two-variant enum, so call sites say what they mean
+	[bool-params] /main.rs:6: public `connect` takes a `bool` parameter, `tls`
+	HINT: use a two-variant enum, so call sites say what they mean
+	[bool-params] /main.rs:7: `retry` takes 2 `bool` parameters, `_`, `backoff`
+	HINT: use a two-variant enum, so call sites say what they mean
+	[bool-params] /main.rs:11: public `render` takes a `bool` parameter, `dark`
+	HINT: use a two-variant enum, so call sites say what they mean
+	");
+}
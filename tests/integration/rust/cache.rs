@@ -0,0 +1,94 @@
+use std::{fs, slice};
+
+use codestyle::rust_checks::{self, AssertOptions, cache::Cache, rustfmt::RustfmtConfig, skip};
+use v_fixtures::Fixture;
+
+use crate::utils::opts_for;
+
+#[test]
+fn unchanged_files_reuse_cached_results() {
+	let temp = Fixture::parse(
+		r#"
+		//- /main.rs
+		use chrono::Utc;
+		"#,
+	)
+	.write_to_tempdir();
+	let opts = opts_for("no_chrono");
+	let cache_dir = temp.root.join("target/codestyle-cache");
+	let assert = |no_cache| rust_checks::run_assert(slice::from_ref(&temp.root), &opts, &AssertOptions { no_cache, ..Default::default() });
+
+	assert_eq!(assert(false), 1);
+	let entries: Vec<_> = fs::read_dir(&cache_dir).unwrap().map(|e| e.unwrap().path()).collect();
+	assert_eq!(entries.len(), 1);
+
+	// Doctor the entry: a run that picks it up must be reading the cache rather than the source
+	fs::write(&entries[0], "[]").unwrap();
+	assert_eq!(assert(false), 0);
+	assert_eq!(assert(true), 1);
+
+	// Any edit to the file is a different key
+	fs::write(temp.root.join("main.rs"), "use chrono::Utc;\nfn main() {}\n").unwrap();
+	assert_eq!(assert(false), 1);
+	assert_eq!(fs::read_dir(&cache_dir).unwrap().count(), 2);
+}
+
+#[test]
+fn options_are_part_of_the_key() {
+	let temp = Fixture::parse(
+		r#"
+		//- /main.rs
+		use chrono::Utc;
+		"#,
+	)
+	.write_to_tempdir();
+	assert_eq!(rust_checks::run_assert(slice::from_ref(&temp.root), &opts_for(""), &AssertOptions::default()), 0);
+	assert_eq!(rust_checks::run_assert(slice::from_ref(&temp.root), &opts_for("no_chrono"), &AssertOptions::default()), 1);
+}
+
+#[test]
+fn results_cached_before_a_skip_expired_are_not_reused() {
+	let temp = Fixture::parse(
+		r#"
+		//- /main.rs
+		//@codestyle::skip(loop-comment, until = "2025-09-01")
+		fn run() {
+			loop {}
+		}
+		"#,
+	)
+	.write_to_tempdir();
+	let opts = opts_for("loops");
+	let path = temp.root.join("main.rs");
+	let contents = fs::read_to_string(&path).unwrap();
+	let rustfmt = RustfmtConfig::load(&temp.root);
+	let cache_dir = temp.root.join("target/codestyle-cache");
+	let assert = || rust_checks::run_assert(slice::from_ref(&temp.root), &opts, &AssertOptions::default());
+
+	assert_eq!(assert(), 1);
+	let entries: Vec<_> = fs::read_dir(&cache_dir).unwrap().map(|e| e.unwrap().path()).collect();
+	assert_eq!(entries, [cache_dir.join(format!("{}.json", Cache::key(&path, &contents, &opts, &rustfmt, skip::today())))]);
+
+	// What a run on the marker's last day would have cached: nothing, as the loop was still skipped
+	Cache::new(&temp.root).put(&Cache::key(&path, &contents, &opts, &rustfmt, (2025, 9, 1)), &[]);
+	fs::remove_file(&entries[0]).unwrap();
+	assert_eq!(assert(), 1);
+}
+
+#[test]
+fn files_checked_against_other_files_are_not_cached() {
+	let temp = Fixture::parse(
+		r#"
+		//- /src/lib.rs
+		mod client;
+		"#,
+	)
+	.write_to_tempdir();
+	let opts = opts_for("mod_files");
+	let assert = || rust_checks::run_assert(slice::from_ref(&temp.root), &opts, &AssertOptions::default());
+
+	assert_eq!(assert(), 1);
+	// `lib.rs` is unchanged, but the module it declares now exists
+	fs::write(temp.root.join("src/client.rs"), "").unwrap();
+	assert_eq!(assert(), 0);
+}
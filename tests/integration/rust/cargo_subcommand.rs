@@ -0,0 +1,32 @@
+#![cfg(unix)]
+
+use std::{fs, os::unix::fs::symlink, process::Command};
+
+use v_fixtures::Fixture;
+
+#[test]
+fn runs_as_cargo_subcommand_from_workspace_root() {
+	let temp = Fixture::parse(
+		r#"
+		//- /Cargo.toml
+		[package]
+		name = "app"
+		version = "0.1.0"
+		edition = "2024"
+		//- /src/main.rs
+		use chrono::Utc;
+		fn main() {}
+		"#,
+	)
+	.write_to_tempdir();
+	let bin_dir = tempfile::tempdir().unwrap();
+	let cargo_codestyle = bin_dir.path().join("cargo-codestyle");
+	symlink(env!("CARGO_BIN_EXE_codestyle"), &cargo_codestyle).unwrap();
+	let nested = temp.root.join("src/nested");
+	fs::create_dir(&nested).unwrap();
+
+	// What `cargo codestyle rust ...` runs, from somewhere inside the package
+	let succeeds = |args: &[&str]| Command::new(&cargo_codestyle).arg("codestyle").args(args).current_dir(&nested).output().unwrap().status.success();
+	assert!(!succeeds(&["rust", "assert"]), "the workspace root should have been checked, finding the chrono import");
+	assert!(succeeds(&["rust", "--no-chrono=allow", "assert"]));
+}
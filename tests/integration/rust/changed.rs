@@ -0,0 +1,64 @@
+use std::{fs, path::Path, process::Command, slice};
+
+use codestyle::rust_checks::{self, AssertOptions};
+use v_fixtures::Fixture;
+
+use crate::utils::opts_for;
+
+fn git(dir: &Path, args: &[&str]) {
+	let status = Command::new("git")
+		.args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+		.args(args)
+		.current_dir(dir)
+		.status()
+		.unwrap();
+	assert!(status.success(), "git {args:?} failed");
+}
+
+#[test]
+fn only_files_changed_since_ref_are_checked() {
+	let temp = Fixture::parse(
+		r#"
+		//- /legacy.rs
+		use chrono::Utc;
+		//- /new.rs
+		fn main() {}
+		"#,
+	)
+	.write_to_tempdir();
+	git(&temp.root, &["init", "-q"]);
+	git(&temp.root, &["add", "."]);
+	git(&temp.root, &["commit", "-qm", "init"]);
+
+	let opts = opts_for("no_chrono");
+	let changed = AssertOptions {
+		changed: Some("HEAD".to_string()),
+		..Default::default()
+	};
+	assert_eq!(rust_checks::run_assert(slice::from_ref(&temp.root), &opts, &AssertOptions::default()), 1);
+	assert_eq!(rust_checks::run_assert(slice::from_ref(&temp.root), &opts, &changed), 0);
+
+	fs::write(temp.root.join("new.rs"), "use chrono::Local;\n").unwrap();
+	assert_eq!(rust_checks::run_assert(slice::from_ref(&temp.root), &opts, &changed), 1);
+
+	git(&temp.root, &["checkout", "-q", "new.rs"]);
+	fs::write(temp.root.join("untracked.rs"), "use chrono::Local;\n").unwrap();
+	assert_eq!(rust_checks::run_assert(slice::from_ref(&temp.root), &opts, &changed), 1);
+}
+
+#[test]
+fn unknown_ref_is_an_error() {
+	let temp = Fixture::parse(
+		r#"
+		//- /main.rs
+		fn main() {}
+		"#,
+	)
+	.write_to_tempdir();
+	git(&temp.root, &["init", "-q"]);
+	let changed = AssertOptions {
+		changed: Some("no-such-ref".to_string()),
+		..Default::default()
+	};
+	assert_eq!(rust_checks::run_assert(slice::from_ref(&temp.root), &opts_for(""), &changed), 1);
+}
@@ -0,0 +1,54 @@
+use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
+
+fn opts() -> codestyle::rust_checks::RustCheckOptions {
+	opts_for("commented_code")
+}
+
+// === Passing cases ===
+
+#[test]
+fn prose_and_doc_comments_pass() {
+	assert_check_passing(
+		r#"
+		// Retries are capped, as the upstream rate limit
+		// resets every minute.
+		fn send() {}
+
+		// Safety
+		fn parse() {}
+
+		/// ```
+		/// let total = sum(&[1, 2]);
+		/// ```
+		fn sum(items: &[u64]) -> u64 {
+			items.iter().sum()
+		}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn commented_out_statements_and_items() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		fn total(items: &[u64]) -> u64 {
+			// let taxed = items.iter().map(|i| i * 2);
+			// println!("{taxed:?}");
+			items.iter().sum()
+		}
+
+		// fn legacy() {
+		//     todo!()
+		// }
+		"#,
+		&opts(),
+	), @"
+	[commented-code] /main.rs:2: commented-out code
+	HINT: delete it, version control keeps it; if it shows an alternative, say so in prose
+	[commented-code] /main.rs:7: commented-out code
+	HINT: delete it, version control keeps it; if it shows an alternative, say so in prose
+	");
+}
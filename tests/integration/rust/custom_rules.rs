@@ -0,0 +1,112 @@
+use std::collections::BTreeMap;
+
+use codestyle::{
+	Linter,
+	config::Config,
+	rust_checks::{
+		RustCheckOptions, Severity,
+		custom_rules::{self, CustomRule},
+	},
+};
+
+const CONFIG: &str = r#"
+[[rust.custom_rules]]
+id = "no-env-var"
+pattern = "std::env::var"
+message = "use Config instead"
+
+[[rust.custom_rules]]
+id = "no-dbg"
+pattern = "dbg!"
+message = "remove before committing"
+severity = "warn"
+
+[[rust.custom_rules]]
+id = "no-unwrap"
+pattern = ".unwrap()"
+message = "handle the error"
+"#;
+
+fn custom_rules(config: &str) -> Option<Vec<CustomRule>> {
+	let config = Config::from_parts(Some(config), BTreeMap::new()).unwrap();
+	config.section("rust").get_deserialized("custom_rules")
+}
+
+fn linter() -> Linter {
+	Linter::new(RustCheckOptions {
+		custom_rules: custom_rules(CONFIG).unwrap(),
+		..RustCheckOptions::default()
+	})
+}
+
+fn found(source: &str) -> Vec<(&'static str, usize, Severity)> {
+	let report = linter().check_source("lib.rs", source).unwrap();
+	report.violations.iter().map(|v| (v.rule, v.line, v.severity)).collect()
+}
+
+#[test]
+fn banned_paths_are_found_through_imports() {
+	let source = r#"use std::env;
+use std::env::var as read;
+
+fn config() {
+	let _home = std::env::var("HOME");
+	let _user = env::var("USER");
+	let _shell = read("SHELL");
+	let _path = env::var_os("PATH");
+}
+"#;
+	assert_eq!(
+		found(source),
+		vec![("no-env-var", 5, Severity::Error), ("no-env-var", 6, Severity::Error), ("no-env-var", 7, Severity::Error)]
+	);
+}
+
+#[test]
+fn glob_imports_of_banned_paths_are_flagged() {
+	assert_eq!(found("use std::env::var::*;\nuse std::env::*;\n"), vec![("no-env-var", 1, Severity::Error)]);
+}
+
+#[test]
+fn banned_macros_and_methods() {
+	let source = "fn f(x: Option<u8>) -> u8 {\n\tstd::dbg!(x);\n\tdbg!(x).unwrap()\n}\n";
+	assert_eq!(
+		found(source),
+		vec![("no-dbg", 2, Severity::Warn), ("no-dbg", 3, Severity::Warn), ("no-unwrap", 3, Severity::Error)]
+	);
+	let report = linter().check_source("lib.rs", source).unwrap();
+	assert_eq!(report.violations[2].message, "Usage of `.unwrap()` is disallowed: handle the error");
+}
+
+#[test]
+fn custom_rules_respect_skip_markers() {
+	let source = "//#[codestyle::skip(no-unwrap)]\nfn f(x: Option<u8>) -> u8 {\n\tx.unwrap()\n}\n";
+	assert!(found(source).is_empty());
+}
+
+#[test]
+fn banned_table_reports_under_one_id() {
+	let config = Config::from_parts(
+		Some("[rust.banned]\n\"std::env::set_var\" = \"not thread-safe\"\n\"mem::forget\" = \"leaks\"\n\"not a path\" = \"\"\n"),
+		BTreeMap::new(),
+	)
+	.unwrap();
+	let banned = custom_rules::banned(config.section("rust").get_deserialized("banned").unwrap());
+	assert_eq!(banned.len(), 2);
+	let linter = Linter::new(RustCheckOptions {
+		custom_rules: banned,
+		..RustCheckOptions::default()
+	});
+	let source = "use std::mem;\n\nfn f(x: Vec<u8>) {\n\tstd::env::set_var(\"A\", \"1\");\n\tmem::forget(x);\n\tcore::mem::forget(1);\n\tstd::env::var(\"A\");\n}\n";
+	let report = linter.check_source("lib.rs", source).unwrap();
+	let banned: Vec<_> = report.violations.iter().filter(|v| v.rule == custom_rules::BANNED).collect();
+	assert_eq!(banned.iter().map(|v| v.line).collect::<Vec<_>>(), vec![5, 6, 4]);
+	assert_eq!(banned[2].message, "Usage of `std::env::set_var` is disallowed: not thread-safe");
+}
+
+#[test]
+fn invalid_custom_rules_are_ignored() {
+	assert!(custom_rules("[[rust.custom_rules]]\nid = \"x\"\npattern = \"not a path\"\nmessage = \"\"\n").is_none());
+	assert!(custom_rules("[[rust.custom_rules]]\nid = \"x\"\npattern = \"dbg!\"\nmessage = \"\"\nseverity = \"fatal\"\n").is_none());
+	assert!(custom_rules("[[rust.custom_rules]]\nid = \"x\"\npattern = \"dbg!\"\n").is_none());
+}
@@ -0,0 +1,69 @@
+use std::{
+	io::Write,
+	process::{Command, Stdio},
+};
+
+use codestyle::rust_checks::{RustCheckOptions, daemon::Daemon};
+use serde_json::Value;
+
+use crate::utils::opts_for;
+
+fn respond(daemon: &mut Daemon<RustCheckOptions>, request: &str) -> Value {
+	serde_json::from_str(&daemon.handle(request)).unwrap()
+}
+
+#[test]
+fn check_reports_violations_for_given_content() {
+	let mut daemon = Daemon::new(opts_for("no_chrono"));
+	let response = respond(&mut daemon, r#"{"id": 7, "method": "check", "path": "lib.rs", "content": "use chrono::Utc;\n"}"#);
+	assert_eq!(response["id"], 7);
+	assert_eq!(response["violations"][0]["rule"], "no-chrono");
+	assert_eq!(response["violations"][0]["line"], 1);
+
+	let response = respond(&mut daemon, r#"{"id": 8, "method": "check", "path": "lib.rs", "content": "use jiff::Timestamp;\n"}"#);
+	assert_eq!(response["violations"], Value::Array(Vec::new()));
+}
+
+#[test]
+fn format_returns_fixed_source_without_writing() {
+	let dir = tempfile::tempdir().unwrap();
+	let path = dir.path().join("lib.rs");
+	std::fs::write(&path, "#[test]\nfn test_parses() {}\n").unwrap();
+
+	let mut daemon = Daemon::new(opts_for("test_fn_prefix"));
+	let request = serde_json::json!({ "id": "a", "method": "format", "path": path });
+	let response = respond(&mut daemon, &request.to_string());
+	assert_eq!(response["formatted"], "#[test]\nfn parses() {}\n");
+	assert_eq!(std::fs::read_to_string(&path).unwrap(), "#[test]\nfn test_parses() {}\n");
+}
+
+#[test]
+fn bad_requests_get_errors() {
+	let mut daemon = Daemon::new(opts_for(""));
+	assert!(respond(&mut daemon, "not json")["error"].is_string());
+	assert!(respond(&mut daemon, r#"{"id": 1, "method": "check", "path": "lib.rs", "content": "fn broken( {"}"#)["error"].is_string());
+	assert!(respond(&mut daemon, r#"{"id": 2, "method": "check", "path": "does/not/exist.rs"}"#)["error"].is_string());
+}
+
+#[test]
+fn serves_stdio_until_shutdown() {
+	let dir = tempfile::tempdir().unwrap();
+	let mut child = Command::new(env!("CARGO_BIN_EXE_codestyle"))
+		.args(["daemon", "--no-chrono=error"])
+		.current_dir(dir.path())
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::null())
+		.spawn()
+		.unwrap();
+	let mut stdin = child.stdin.take().unwrap();
+	writeln!(stdin, r#"{{"id": 1, "method": "check", "path": "lib.rs", "content": "use chrono::Utc;"}}"#).unwrap();
+	writeln!(stdin, r#"{{"id": 2, "method": "shutdown"}}"#).unwrap();
+	let output = child.wait_with_output().unwrap();
+	assert!(output.status.success());
+
+	let responses: Vec<Value> = String::from_utf8(output.stdout).unwrap().lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+	assert_eq!(responses.len(), 2);
+	assert_eq!(responses[0]["violations"][0]["rule"], "no-chrono");
+	assert_eq!(responses[1]["id"], 2);
+}
@@ -0,0 +1,48 @@
+use std::{path::Path, slice};
+
+use codestyle::rust_checks::{self, exclude::DiscoveryOptions};
+use v_fixtures::Fixture;
+
+use crate::utils::opts_for;
+
+#[test]
+fn files_no_mod_reaches() {
+	let temp = Fixture::parse(
+		r#"
+		//- /Cargo.toml
+		[package]
+		name = "app"
+		//- /src/main.rs
+		mod client;
+		#[path = "vendored/codec.rs"]
+		mod codec;
+
+		fn main() {}
+		//- /src/client.rs
+		mod retry;
+		//- /src/client/retry.rs
+		pub fn backoff() {}
+		//- /src/client/stale.rs
+		pub fn old_backoff() {}
+		//- /src/vendored/codec.rs
+		mod frame;
+		//- /src/vendored/frame.rs
+		pub struct Frame;
+		//- /src/bin/tool/main.rs
+		mod args;
+
+		fn main() {}
+		//- /src/bin/tool/args.rs
+		pub struct Args;
+		//- /src/legacy.rs
+		pub fn migrate() {}
+		//- /src/scratch.rs
+		//#[codestyle::skip(dead-file)]
+		pub fn try_things() {}
+		"#,
+	)
+	.write_to_tempdir();
+	let violations = rust_checks::collect_violations(slice::from_ref(&temp.root), &opts_for("dead_files"), &DiscoveryOptions::default(), None, false, false).unwrap();
+	let found: Vec<_> = violations.iter().map(|v| (Path::new(&v.file).strip_prefix(&temp.root).unwrap().display().to_string(), v.line)).collect();
+	assert_eq!(found, vec![("src/client/stale.rs".to_string(), 1), ("src/legacy.rs".to_string(), 1)]);
+}
@@ -0,0 +1,88 @@
+use crate::utils::{assert_check_passing, opts_for, test_case};
+
+fn opts() -> codestyle::rust_checks::RustCheckOptions {
+	opts_for("derive_debug")
+}
+
+// === Passing cases ===
+
+#[test]
+fn debug_types_and_exempt_ones_pass() {
+	assert_check_passing(
+		r#"
+		use std::fmt;
+
+		#[derive(Clone, Debug)]
+		pub struct Config;
+
+		#[derive(derive_more::Debug)]
+		pub enum Mode {}
+
+		pub struct Secret(String);
+
+		impl fmt::Debug for Secret {
+			fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+				f.write_str("Secret(..)")
+			}
+		}
+
+		#[doc(hidden)]
+		pub struct Internal;
+
+		pub(crate) struct Cache;
+
+		struct Private;
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases ===
+
+#[test]
+fn debug_is_derived() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		/// Retry settings.
+		#[derive(Clone, PartialEq)]
+		pub struct Config {
+			pub retries: u32,
+		}
+
+		/// What went wrong.
+		#[non_exhaustive]
+		pub enum Failure {
+			Timeout,
+		}
+
+		#[derive(Clone, Copy)]
+		pub struct Id(u64);
+		"#,
+		&opts(),
+	), @"
+	# Assert mode
+	[derive-debug] /main.rs:3: public type `Config` doesn't implement `Debug`
+	HINT: derive it
+	[derive-debug] /main.rs:9: public type `Failure` doesn't implement `Debug`
+	HINT: derive it
+	[derive-debug] /main.rs:14: public type `Id` doesn't implement `Debug`
+	HINT: derive it
+
+	# Format mode
+	/// Retry settings.
+	#[derive(Clone, Debug, PartialEq)]
+	pub struct Config {
+		pub retries: u32,
+	}
+
+	/// What went wrong.
+	#[derive(Debug)]
+	#[non_exhaustive]
+	pub enum Failure {
+		Timeout,
+	}
+
+	#[derive(Clone, Copy, Debug)]
+	pub struct Id(u64);
+	");
+}
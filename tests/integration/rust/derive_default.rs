@@ -0,0 +1,107 @@
+use crate::utils::{assert_check_passing, opts_for, test_case};
+
+fn opts() -> codestyle::rust_checks::RustCheckOptions {
+	opts_for("derive_default")
+}
+
+// === Passing cases ===
+
+#[test]
+fn impls_the_derive_cant_replace_pass() {
+	assert_check_passing(
+		r#"
+		pub struct Config {
+			retries: u32,
+		}
+
+		impl Default for Config {
+			fn default() -> Self {
+				Self { retries: 3 }
+			}
+		}
+
+		pub struct Buffer<T> {
+			items: Vec<T>,
+		}
+
+		impl<T> Default for Buffer<T> {
+			fn default() -> Self {
+				Self { items: Vec::new() }
+			}
+		}
+
+		pub enum Mode {
+			Fast { level: u8 },
+		}
+
+		impl Default for Mode {
+			fn default() -> Self {
+				Mode::Fast { level: 0 }
+			}
+		}
+
+		impl Default for external::Settings {
+			fn default() -> Self {
+				Self { verbose: false }
+			}
+		}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases ===
+
+#[test]
+fn default_is_derived() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		/// Retry settings.
+		#[derive(Clone, Debug)]
+		pub struct Config {
+			pub retries: u32,
+			pub name: String,
+		}
+
+		impl Default for Config {
+			fn default() -> Self {
+				Self { retries: 0, name: String::new() }
+			}
+		}
+
+		#[derive(Debug)]
+		pub enum Mode {
+			Fast,
+			Safe,
+		}
+
+		impl Default for Mode {
+			fn default() -> Self {
+				Mode::Safe
+			}
+		}
+		"#,
+		&opts(),
+	), @"
+	# Assert mode
+	[derive-default] /main.rs:8: `impl Default for Config` does what `#[derive(Default)]` does
+	HINT: derive it
+	[derive-default] /main.rs:20: `impl Default for Mode` does what `#[derive(Default)]` does
+	HINT: derive it, with `#[default]` on `Safe`
+
+	# Format mode
+	/// Retry settings.
+	#[derive(Clone, Debug, Default)]
+	pub struct Config {
+		pub retries: u32,
+		pub name: String,
+	}
+
+	#[derive(Debug, Default)]
+	pub enum Mode {
+		Fast,
+		#[default]
+		Safe,
+	}
+	");
+}
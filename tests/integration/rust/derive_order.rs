@@ -0,0 +1,75 @@
+use crate::utils::{assert_check_passing, opts_for, test_case, test_case_assert_only};
+
+fn opts() -> codestyle::rust_checks::RustCheckOptions {
+	opts_for("derive_order")
+}
+
+// === Passing cases ===
+
+#[test]
+fn canonical_order_passes() {
+	assert_check_passing(
+		r#"
+		#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+		struct Id(u64);
+
+		#[derive(Debug, serde::Deserialize, serde::Serialize, PartialEq)]
+		struct Config {
+			#[serde(default)]
+			name: String,
+		}
+
+		#[derive(Error)]
+		enum E {}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases ===
+
+#[test]
+fn derives_are_reordered() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		#[derive(PartialEq, Serialize, Debug, Clone, Eq)]
+		struct A;
+
+		#[derive(Hash, serde::Deserialize, Default)]
+		enum B {}
+		"#,
+		&opts(),
+	), @"
+	# Assert mode
+	[derive-order] /main.rs:1: derives out of order
+	HINT: expected `Clone, Debug, Serialize, Eq, PartialEq`
+	[derive-order] /main.rs:4: derives out of order
+	HINT: expected `Default, Deserialize, Hash`
+
+	# Format mode
+	#[derive(Clone, Debug, Serialize, Eq, PartialEq)]
+	struct A;
+
+	#[derive(Default, serde::Deserialize, Hash)]
+	enum B {}
+	");
+}
+
+#[test]
+fn comments_in_the_list_block_the_fix() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		#[derive(
+			Debug,
+			// compared by id only
+			PartialEq,
+			Clone,
+		)]
+		struct A;
+		"#,
+		&opts(),
+	), @"
+	[derive-order] /main.rs:1: derives out of order
+	HINT: expected `Clone, Debug, PartialEq`
+	");
+}
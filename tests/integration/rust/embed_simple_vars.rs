@@ -473,3 +473,25 @@ fn debug_format_pretty_print() {
 	}
 	"#);
 }
+
+#[test]
+fn non_ascii_before_placeholder() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		fn test() {
+			let name = "Jürgen";
+			/* ü */ println!("Grüße, {}!", name);
+		}
+		"#,
+		&opts(),
+	), @r#"
+	# Assert mode
+	[embed-simple-vars] /main.rs:3: variable `name` should be embedded in format string: use `{name}` instead of `{}, name`
+
+	# Format mode
+	fn test() {
+		let name = "Jürgen";
+		/* ü */ println!("Grüße, {name}!");
+	}
+	"#);
+}
@@ -0,0 +1,76 @@
+use std::slice;
+
+use codestyle::rust_checks::{self, AssertOptions, exclude::DiscoveryOptions};
+use v_fixtures::Fixture;
+
+use crate::utils::opts_for;
+
+const FIXTURE: &str = r#"
+	//- /src/main.rs
+	fn main() {}
+	//- /src/generated/schema.rs
+	use chrono::Utc;
+	"#;
+
+fn excluding(patterns: &[&str]) -> AssertOptions {
+	AssertOptions {
+		discovery: DiscoveryOptions {
+			exclude: patterns.iter().map(|p| p.to_string()).collect(),
+			..Default::default()
+		},
+		..Default::default()
+	}
+}
+
+#[test]
+fn excluded_dirs_are_not_walked() {
+	let temp = Fixture::parse(FIXTURE).write_to_tempdir();
+	let opts = opts_for("no_chrono");
+	assert_eq!(rust_checks::run_assert(slice::from_ref(&temp.root), &opts, &excluding(&[])), 1);
+	assert_eq!(rust_checks::run_assert(slice::from_ref(&temp.root), &opts, &excluding(&["**/generated"])), 0);
+	assert_eq!(rust_checks::run_assert(slice::from_ref(&temp.root), &opts, &excluding(&["src/generated/*.rs"])), 0);
+}
+
+#[test]
+fn excluded_files_are_skipped_when_passed_directly() {
+	let temp = Fixture::parse(FIXTURE).write_to_tempdir();
+	let opts = opts_for("no_chrono");
+	let paths = [temp.root.join("src/main.rs"), temp.root.join("src/generated/schema.rs")];
+	assert_eq!(rust_checks::run_assert(&paths, &opts, &excluding(&[])), 1);
+	assert_eq!(rust_checks::run_assert(&paths, &opts, &excluding(&["**/schema.rs"])), 0);
+}
+
+#[test]
+fn invalid_pattern_fails() {
+	let temp = Fixture::parse(FIXTURE).write_to_tempdir();
+	assert_eq!(rust_checks::run_assert(slice::from_ref(&temp.root), &opts_for("no_chrono"), &excluding(&["[unclosed"])), 1);
+}
+
+#[test]
+fn nested_packages_are_skipped_unless_included() {
+	let temp = Fixture::parse(
+		r#"
+		//- /Cargo.toml
+		[package]
+		name = "app"
+		//- /src/main.rs
+		fn main() {}
+		//- /src/vendored/Cargo.toml
+		[package]
+		name = "vendored"
+		//- /src/vendored/src/lib.rs
+		use chrono::Utc;
+		"#,
+	)
+	.write_to_tempdir();
+	let opts = opts_for("no_chrono");
+	let including = AssertOptions {
+		discovery: DiscoveryOptions {
+			include_nested: true,
+			..Default::default()
+		},
+		..Default::default()
+	};
+	assert_eq!(rust_checks::run_assert(slice::from_ref(&temp.root), &opts, &excluding(&[])), 0);
+	assert_eq!(rust_checks::run_assert(slice::from_ref(&temp.root), &opts, &including), 1);
+}
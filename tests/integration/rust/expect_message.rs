@@ -0,0 +1,74 @@
+use codestyle::rust_checks::RustCheckOptions;
+
+use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
+
+fn opts() -> RustCheckOptions {
+	opts_for("expect_message")
+}
+
+// === Passing cases ===
+
+#[test]
+fn descriptive_messages_pass() {
+	assert_check_passing(
+		r#"
+		fn main() {
+			let port: u16 = args.port.parse().expect("validated as digits by clap");
+			let file = File::open(path).expect("created by the build script");
+			let guard = state.lock().expect("lock poisoned: a holder panicked");
+			let n = parser.expect(Token::Comma, "");
+			let m = x.expect(&format!("missing {key}"));
+		}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn empty_short_and_restating_messages() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		fn main() {
+			let a: u16 = s.parse().expect("");
+			let b = map.get(k).expect("oops");
+			let c: u16 = s.parse().expect("failed to parse");
+			let d = File::open(path).expect("Couldn't open file");
+			let e = std::fs::read_to_string(path).expect("could not read_to_string");
+			let f = fetch().await.expect("should work");
+		}
+		"#,
+		&opts(),
+	), @r#"
+	[expect-message] /main.rs:2: `.expect()` with an empty message
+	HINT: say which invariant broke, e.g. `.expect("config is validated at startup")`
+	[expect-message] /main.rs:3: `.expect()` message "oops" is shorter than 8 chars
+	HINT: say which invariant broke, e.g. `.expect("config is validated at startup")`
+	[expect-message] /main.rs:4: `.expect()` message "failed to parse" only restates the call
+	HINT: say which invariant broke, e.g. `.expect("config is validated at startup")`
+	[expect-message] /main.rs:6: `.expect()` message "could not read_to_string" only restates the call
+	HINT: say which invariant broke, e.g. `.expect("config is validated at startup")`
+	[expect-message] /main.rs:7: `.expect()` message "should work" only restates the call
+	HINT: say which invariant broke, e.g. `.expect("config is validated at startup")`
+	"#);
+}
+
+#[test]
+fn min_len_is_configurable() {
+	let opts = RustCheckOptions {
+		expect_message_min_len: 20,
+		..opts()
+	};
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		fn main() {
+			let home = std::env::var("HOME").expect("set by login");
+		}
+		"#,
+		&opts,
+	), @r#"
+	[expect-message] /main.rs:2: `.expect()` message "set by login" is shorter than 20 chars
+	HINT: say which invariant broke, e.g. `.expect("config is validated at startup")`
+	"#);
+}
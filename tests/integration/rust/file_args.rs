@@ -0,0 +1,34 @@
+use codestyle::rust_checks::{self, AssertOptions};
+use v_fixtures::Fixture;
+
+use crate::utils::opts_for;
+
+const FIXTURE: &str = r#"
+	//- /src/legacy.rs
+	use chrono::Utc;
+	//- /src/clean.rs
+	fn main() {}
+	//- /README.md
+	# readme
+	"#;
+
+#[test]
+fn files_are_checked_directly() {
+	let temp = Fixture::parse(FIXTURE).write_to_tempdir();
+	let opts = opts_for("no_chrono");
+	let assert = |paths: &[&str]| rust_checks::run_assert(&paths.iter().map(|p| temp.root.join(p)).collect::<Vec<_>>(), &opts, &AssertOptions::default());
+
+	assert_eq!(assert(&["src/clean.rs"]), 0);
+	assert_eq!(assert(&["src/legacy.rs"]), 1);
+	assert_eq!(assert(&["src/clean.rs", "src/legacy.rs"]), 1);
+	// Non-Rust files are skipped, so hooks can pass everything that's staged
+	assert_eq!(assert(&["src/clean.rs", "README.md"]), 0);
+	assert_eq!(assert(&["src/missing.rs"]), 1);
+}
+
+#[test]
+fn files_and_dirs_mix() {
+	let temp = Fixture::parse(FIXTURE).write_to_tempdir();
+	let paths = [temp.root.join("README.md"), temp.root.join("src")];
+	assert_eq!(rust_checks::run_assert(&paths, &opts_for("no_chrono"), &AssertOptions::default()), 1);
+}
@@ -0,0 +1,79 @@
+use codestyle::rust_checks::RustCheckOptions;
+
+use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
+
+fn opts() -> RustCheckOptions {
+	opts_for("fn_params")
+}
+
+// === Passing cases ===
+
+#[test]
+fn up_to_max_params_pass() {
+	assert_check_passing(
+		r#"
+		fn seven(a: u8, b: u8, c: u8, d: u8, e: u8, f: u8, g: u8) {}
+
+		impl Canvas {
+			fn draw(&mut self, a: u8, b: u8, c: u8, d: u8, e: u8, f: u8, g: u8) {}
+		}
+		"#,
+		&opts(),
+	);
+}
+
+#[test]
+fn trait_impls_pass() {
+	assert_check_passing(
+		r#"
+		impl Shader for Flat {
+			fn render(&self, a: u8, b: u8, c: u8, d: u8, e: u8, f: u8, g: u8, h: u8) {}
+		}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn too_many_params() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		fn render(x: u32, y: u32, width: u32, height: u32, color: Color, border: u32, radius: u32, shadow: bool) {}
+
+		trait Shader {
+			fn render(&self, a: u8, b: u8, c: u8, d: u8, e: u8, f: u8, g: u8, h: u8);
+		}
+
+		impl Shader for Flat {
+			fn render(&self, a: u8, b: u8, c: u8, d: u8, e: u8, f: u8, g: u8, h: u8) {
+				fn helper(a: u8, b: u8, c: u8, d: u8, e: u8, f: u8, g: u8, h: u8) {}
+			}
+		}
+		"#,
+		&opts(),
+	), @"
+	[fn-params] /main.rs:1: `render` takes 8 parameters, more than 7
+	HINT: group the ones that belong together into a params struct
+	[fn-params] /main.rs:4: `render` takes 8 parameters, more than 7
+	HINT: group the ones that belong together into a params struct
+	[fn-params] /main.rs:9: `helper` takes 8 parameters, more than 7
+	HINT: group the ones that belong together into a params struct
+	");
+}
+
+#[test]
+fn max_is_configurable() {
+	let opts = RustCheckOptions { fn_params_max: 2, ..opts() };
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		fn pair(a: u8, b: u8) {}
+		fn triple(a: u8, b: u8, c: u8) {}
+		"#,
+		&opts,
+	), @"
+	[fn-params] /main.rs:2: `triple` takes 3 parameters, more than 2
+	HINT: group the ones that belong together into a params struct
+	");
+}
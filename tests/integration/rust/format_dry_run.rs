@@ -0,0 +1,29 @@
+use std::{fs, slice};
+
+use codestyle::rust_checks::{self, FormatOptions};
+use v_fixtures::Fixture;
+
+use crate::utils::opts_for;
+
+const FIXTURE: &str = r#"
+	//- /main.rs
+	#[test]
+	fn test_parses() {}
+	"#;
+
+#[test]
+fn leaves_files_untouched() {
+	let temp = Fixture::parse(FIXTURE).write_to_tempdir();
+	let path = temp.root.join("main.rs");
+	let before = fs::read_to_string(&path).unwrap();
+	let dry_run = FormatOptions {
+		dry_run: true,
+		..Default::default()
+	};
+
+	assert_eq!(rust_checks::run_format(slice::from_ref(&temp.root), &opts_for("test_fn_prefix"), &dry_run), 1);
+	assert_eq!(fs::read_to_string(&path).unwrap(), before);
+
+	assert_eq!(rust_checks::run_format(slice::from_ref(&temp.root), &opts_for("test_fn_prefix"), &FormatOptions::default()), 0);
+	assert_eq!(rust_checks::run_format(slice::from_ref(&temp.root), &opts_for("test_fn_prefix"), &dry_run), 0);
+}
@@ -0,0 +1,33 @@
+use std::{
+	io::Write,
+	process::{Command, Stdio},
+};
+
+fn format_stdin(input: &str) -> (String, bool) {
+	let dir = tempfile::tempdir().unwrap();
+	let mut child = Command::new(env!("CARGO_BIN_EXE_codestyle"))
+		.args(["rust", "--test-fn-prefix=error", "format", "-"])
+		.current_dir(dir.path())
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::null())
+		.spawn()
+		.unwrap();
+	child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+	let output = child.wait_with_output().unwrap();
+	(String::from_utf8(output.stdout).unwrap(), output.status.success())
+}
+
+#[test]
+fn fixes_stdin_to_stdout() {
+	let (output, success) = format_stdin("#[test]\nfn test_parses() {}\n");
+	assert!(success);
+	assert_eq!(output, "#[test]\nfn parses() {}\n");
+}
+
+#[test]
+fn unparseable_input_is_echoed_back() {
+	let (output, success) = format_stdin("fn broken( {\n");
+	assert!(!success);
+	assert_eq!(output, "fn broken( {\n");
+}
@@ -0,0 +1,63 @@
+use std::{fs, slice};
+
+use codestyle::rust_checks::{self, AssertOptions, FormatOptions, exclude::DiscoveryOptions, generated::is_generated};
+use v_fixtures::Fixture;
+
+use crate::utils::opts_for;
+
+#[test]
+fn markers_in_header_comments() {
+	assert!(is_generated("// @generated by prost-build\nfn f() {}\n"));
+	assert!(is_generated("#![allow(clippy::all)]\n/*\n * Code generated by bindgen. DO NOT EDIT.\n */\n"));
+	assert!(!is_generated("const HINT: &str = \"DO NOT EDIT\";\n"));
+	assert!(!is_generated("\n\n\n\n\n// @generated\n"));
+}
+
+#[test]
+fn marked_files_are_neither_flagged_nor_rewritten() {
+	let temp = Fixture::parse(
+		r#"
+		//- /src/main.rs
+		fn main() {}
+		//- /src/schema.rs
+		// @generated
+		#[test]
+		fn test_parses() {}
+		"#,
+	)
+	.write_to_tempdir();
+	let opts = opts_for("test_fn_prefix");
+	let path = temp.root.join("src/schema.rs");
+	let before = fs::read_to_string(&path).unwrap();
+
+	assert_eq!(rust_checks::run_assert(slice::from_ref(&temp.root), &opts, &AssertOptions::default()), 0);
+	assert_eq!(rust_checks::run_format(slice::from_ref(&temp.root), &opts, &FormatOptions::default()), 0);
+	assert_eq!(fs::read_to_string(&path).unwrap(), before);
+}
+
+#[test]
+fn generated_paths_are_skipped() {
+	let temp = Fixture::parse(
+		r#"
+		//- /src/main.rs
+		fn main() {}
+		//- /src/api_generated.rs
+		use chrono::Utc;
+		//- /src/bindings.rs
+		use chrono::Utc;
+		"#,
+	)
+	.write_to_tempdir();
+	let opts = opts_for("no_chrono");
+	let generated = |patterns: &[&str]| AssertOptions {
+		discovery: DiscoveryOptions {
+			generated: patterns.iter().map(|p| p.to_string()).collect(),
+			..Default::default()
+		},
+		..Default::default()
+	};
+
+	// Only `bindings.rs` is left: `*_generated.rs` is skipped by default
+	assert_eq!(rust_checks::run_assert(slice::from_ref(&temp.root), &opts, &generated(&[])), 1);
+	assert_eq!(rust_checks::run_assert(slice::from_ref(&temp.root), &opts, &generated(&["**/bindings.rs"])), 0);
+}
@@ -1,6 +1,8 @@
+use codestyle::{Linter, rust_checks::RustCheckOptions};
+
 use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
 
-fn opts() -> codestyle::rust_checks::RustCheckOptions {
+fn opts() -> RustCheckOptions {
 	opts_for("ignored_error_comment")
 }
 
@@ -284,3 +286,32 @@ fn let_underscore_in_closure() {
 	HINT: could the pattern be allowing to continue with corrupted state? Error out properly or explain why it's part of the intended logic.
 	");
 }
+
+// === test_exempt ===
+
+// The exemption is applied by the runner, so these go through the `Linter`
+fn exempt_lines(path: &str, source: &str) -> Vec<usize> {
+	let opts = RustCheckOptions {
+		test_exempt: vec!["ignored_error_comment".to_string()],
+		..opts()
+	};
+	Linter::new(opts).check_source(path, source).unwrap().violations.iter().map(|v| v.line).collect()
+}
+
+#[test]
+fn test_exempt_skips_cfg_test_modules_and_test_fns() {
+	let source = "fn run() {\n\tlet _ = go();\n}\n\n#[cfg(test)]\nmod tests {\n\tfn helper() {\n\t\tlet _ = go();\n\t}\n}\n\n#[test]\nfn works() {\n\tlet _ = go();\n}\n";
+	assert_eq!(exempt_lines("src/lib.rs", source), [2]);
+}
+
+#[test]
+fn test_exempt_skips_files_under_tests() {
+	assert!(exempt_lines("tests/integration.rs", "fn helper() {\n\tlet _ = go();\n}\n").is_empty());
+}
+
+#[test]
+fn test_code_is_checked_without_exemption() {
+	let source = "#[cfg(test)]\nmod tests {\n\tfn helper() {\n\t\tlet _ = go();\n\t}\n}\n";
+	let report = Linter::new(opts()).check_source("src/lib.rs", source).unwrap();
+	assert_eq!(report.violations.len(), 1);
+}
@@ -245,3 +245,36 @@ fn impl_from_example_in_spec() {
 	//,}}}1
 	"#);
 }
+
+#[test]
+fn marker_moves_to_own_line_past_max_width() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		//- /rustfmt.toml
+		max_width = 20
+
+		//- /main.rs
+		struct LongTypeName;
+		impl LongTypeName {
+			fn one() {}
+		}
+		"#,
+		&opts(),
+	), @"
+	# Assert mode
+	[impl-folds] /main.rs:2: impl block missing vim fold markers
+
+	# Format mode
+	//- /main.rs
+	struct LongTypeName;
+	impl LongTypeName
+	/*{{{1*/ {
+		fn one() {}
+	}
+	//,}}}1
+
+			
+	//- /rustfmt.toml
+	max_width = 20
+	");
+}
@@ -282,3 +282,43 @@ fn join_preserves_nested_indentation() {
 	}
 	");
 }
+
+#[test]
+fn join_follows_rustfmt_toml_indentation() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		//- /rustfmt.toml
+		hard_tabs = false
+		tab_spaces = 2
+
+		//- /main.rs
+		struct Foo;
+		impl Foo {
+			fn one() {}
+		}
+		impl Foo {
+			fn two() {
+				println!("nested");
+			}
+		}
+		"#,
+		&opts(),
+	), @r#"
+	# Assert mode
+	[join-split-impls] /main.rs:5: split `impl Foo` blocks should be joined into one
+
+	# Format mode
+	//- /main.rs
+	struct Foo;
+	impl Foo {
+	  fn one() {}
+	  fn two() {
+	    println!("nested");
+	  }
+	}
+			
+	//- /rustfmt.toml
+	hard_tabs = false
+	tab_spaces = 2
+	"#);
+}
@@ -1,20 +1,21 @@
-//! Tests for impl-related rules: join_split_impls, impl_follows_type, impl_folds.
+//! Tests for impl-related rules: join_split_impls, impl_follows_type, trait_impls_follow, impl_folds.
 //!
-//! Order matters: join_split_impls -> impl_follows_type -> impl_folds
+//! Order matters: join_split_impls -> impl_follows_type -> trait_impls_follow -> impl_folds
 
 mod impl_folds;
 mod impl_follows_type;
 mod join_split_impls;
+mod trait_impls_follow;
 
-use codestyle::rust_checks::RustCheckOptions;
+use codestyle::rust_checks::{RustCheckOptions, Severity};
 
 use crate::utils::test_case;
 
 fn all_impl_opts() -> RustCheckOptions {
 	RustCheckOptions {
-		join_split_impls: true,
-		impl_follows_type: true,
-		impl_folds: true,
+		join_split_impls: Severity::Error,
+		impl_follows_type: Severity::Error,
+		impl_folds: Severity::Error,
 		..Default::default()
 	}
 }
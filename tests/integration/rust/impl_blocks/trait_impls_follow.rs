@@ -0,0 +1,133 @@
+use crate::utils::{assert_check_passing, opts_for, test_case};
+
+fn opts() -> codestyle::rust_checks::RustCheckOptions {
+	opts_for("trait_impls_follow")
+}
+
+// === Passing cases ===
+
+#[test]
+fn grouped_trait_impls_pass() {
+	assert_check_passing(
+		r#"
+		use std::fmt;
+
+		pub struct Client;
+		impl Client {
+			pub fn new() -> Self { Self }
+		}
+
+		impl Clone for Client {
+			fn clone(&self) -> Self { Self }
+		}
+
+		impl fmt::Display for Client {
+			fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { Ok(()) }
+		}
+
+		impl serde::Serialize for Client {}
+
+		fn helper() {}
+
+		impl ToString for String {}
+		"#,
+		&opts(),
+	);
+}
+
+#[test]
+fn skip_on_a_trait_impl_leaves_the_type_alone() {
+	assert_check_passing(
+		r#"
+		struct Config;
+
+		fn helper() {}
+
+		//#[codestyle::skip(trait-impls-follow)]
+		impl Default for Config {
+			fn default() -> Self { Self }
+		}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases ===
+
+#[test]
+fn trait_impls_move_under_the_inherent_impl_in_order() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		use std::fmt;
+
+		pub struct Client;
+		impl Client {
+			pub fn new() -> Self { Self }
+		}
+
+		fn helper() {}
+
+		impl fmt::Display for Client {
+			fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { Ok(()) }
+		}
+
+		/// Cheap, it's a unit struct.
+		impl Clone for Client {
+			fn clone(&self) -> Self { Self }
+		}
+		"#,
+		&opts(),
+	), @"
+	# Assert mode
+	[trait-impls-follow] /main.rs:15: `impl Clone for Client` should follow `Client`'s inherent impl
+	HINT: a type's trait impls go right after its inherent impl, std traits first, then the rest, each in derive order
+
+	# Format mode
+	use std::fmt;
+
+	pub struct Client;
+	impl Client {
+		pub fn new() -> Self { Self }
+	}
+
+	/// Cheap, it's a unit struct.
+	impl Clone for Client {
+		fn clone(&self) -> Self { Self }
+	}
+
+	impl fmt::Display for Client {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { Ok(()) }
+	}
+
+	fn helper() {}
+	");
+}
+
+#[test]
+fn trait_impl_above_its_type_moves_down() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		impl Default for Config {
+			fn default() -> Self { Self { retries: 3 } }
+		}
+
+		struct Config {
+			retries: u32,
+		}
+		"#,
+		&opts(),
+	), @"
+	# Assert mode
+	[trait-impls-follow] /main.rs:1: `impl Default for Config` should follow `Config`
+	HINT: a type's trait impls go right after its inherent impl, std traits first, then the rest, each in derive order
+
+	# Format mode
+	struct Config {
+		retries: u32,
+	}
+
+	impl Default for Config {
+		fn default() -> Self { Self { retries: 3 } }
+	}
+	");
+}
@@ -0,0 +1,98 @@
+use crate::utils::{assert_check_passing, opts_for, test_case};
+
+fn opts() -> codestyle::rust_checks::RustCheckOptions {
+	opts_for("impl_method_order")
+}
+
+// === Passing cases ===
+
+#[test]
+fn ordered_and_trait_impls_pass() {
+	assert_check_passing(
+		r#"
+		pub struct Client;
+
+		impl Client {
+			const RETRIES: u32 = 3;
+
+			pub fn from_env() -> Self {
+				Self
+			}
+
+			pub fn send(&self) {}
+
+			pub fn from_parts(&self) {}
+
+			fn sign(&self) {}
+		}
+
+		impl Default for Client {
+			fn default() -> Self {
+				Self::helper()
+			}
+
+			fn clone_from(&mut self, other: &Self) {}
+		}
+
+		impl Client {
+			fn sign_with(&self) {}
+
+			delegate! { fn send_all(&self); }
+
+			pub fn close(&self) {}
+		}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases ===
+
+#[test]
+fn items_are_moved_into_order() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		pub struct Client;
+
+		impl Client {
+			const RETRIES: u32 = 3;
+
+			fn sign(&self) {}
+
+			/// Sends it.
+			pub fn send(&self) {
+				self.sign();
+			}
+
+			// Reads the env too
+			pub fn new() -> Self {
+				Self
+			}
+		}
+		"#,
+		&opts(),
+	), @"
+	# Assert mode
+	[impl-method-order] /main.rs:9: `pub` method `send` comes after private methods
+	HINT: order an impl's items: associated consts and types, constructors, `pub` methods, then the rest
+
+	# Format mode
+	pub struct Client;
+
+	impl Client {
+		const RETRIES: u32 = 3;
+
+		// Reads the env too
+		pub fn new() -> Self {
+			Self
+		}
+
+		/// Sends it.
+		pub fn send(&self) {
+			self.sign();
+		}
+
+		fn sign(&self) {}
+	}
+	");
+}
@@ -0,0 +1,107 @@
+use std::slice;
+
+use codestyle::rust_checks::{self, AssertOptions};
+use v_fixtures::Fixture;
+
+use crate::utils::opts_for;
+
+fn violates(fixture: &str) -> bool {
+	let temp = Fixture::parse(fixture).write_to_tempdir();
+	rust_checks::run_assert(slice::from_ref(&temp.root), &opts_for("no_chrono"), &AssertOptions::default()) == 1
+}
+
+#[test]
+fn path_attribute_modules_outside_src_are_checked() {
+	assert!(violates(
+		r#"
+		//- /Cargo.toml
+		[package]
+		name = "app"
+		//- /src/main.rs
+		#[path = "../generated/mod.rs"]
+		mod generated;
+		fn main() {}
+		//- /generated/mod.rs
+		mod schema;
+		//- /generated/schema.rs
+		use chrono::Utc;
+		"#,
+	));
+}
+
+#[test]
+fn path_attributes_inside_inline_modules_follow_the_inline_path() {
+	assert!(violates(
+		r#"
+		//- /Cargo.toml
+		[package]
+		name = "app"
+		//- /src/lib.rs
+		mod platform {
+			#[path = "../../shared/time.rs"]
+			mod time;
+		}
+		//- /shared/time.rs
+		use chrono::Utc;
+		"#,
+	));
+}
+
+#[test]
+fn custom_target_paths_are_checked() {
+	assert!(violates(
+		r#"
+		//- /Cargo.toml
+		[package]
+		name = "app"
+
+		[lib]
+		path = "lib.rs"
+
+		[[bin]]
+		name = "tool"
+		path = "src/bin/tool.rs"
+		//- /src/bin/tool.rs
+		fn main() {}
+		//- /lib.rs
+		use chrono::Utc;
+		"#,
+	));
+	assert!(violates(
+		r#"
+		//- /Cargo.toml
+		[package]
+		name = "app"
+
+		[[bin]]
+		name = "tool"
+		path = "tools/tool.rs"
+		//- /src/lib.rs
+		//- /tools/tool.rs
+		#[path = "helpers.rs"]
+		mod helpers;
+		fn main() {}
+		//- /tools/helpers.rs
+		use chrono::Utc;
+		"#,
+	));
+}
+
+#[test]
+fn standard_layout_is_unaffected() {
+	assert!(!violates(
+		r#"
+		//- /Cargo.toml
+		[package]
+		name = "app"
+		//- /src/main.rs
+		#[path = "other.rs"]
+		mod renamed;
+		fn main() {}
+		//- /src/other.rs
+		pub fn f() {}
+		//- /unrelated/stray.rs
+		use chrono::Utc;
+		"#,
+	));
+}
@@ -0,0 +1,125 @@
+use codestyle::rust_checks::RustCheckOptions;
+
+use crate::utils::{assert_check_passing, opts_for, test_case, test_case_assert_only};
+
+fn opts() -> RustCheckOptions {
+	opts_for("let_else")
+}
+
+// === Passing cases ===
+
+#[test]
+fn non_diverging_or_binding_branches_pass() {
+	assert_check_passing(
+		r#"
+		fn main() {
+			let user = match users.get(id) {
+				Some(user) => user,
+				None => User::default(),
+			};
+			let value = match parse(input) {
+				Ok(value) => value,
+				Err(e) => return Err(e.into()),
+			};
+			let first = match items.first() {
+				Some(first) if first.active => first,
+				_ => return,
+			};
+			let len = match name {
+				Some(name) => name.len(),
+				None => return,
+			};
+			let n = match count {
+				0 => return,
+				n => n,
+			};
+			let Some(age) = user.age else { return };
+			if let Some(user) = users.get(id) {
+				greet(user);
+			} else if let Some(guest) = guests.get(id) {
+				greet(guest);
+			} else {
+				return;
+			}
+			if let Some(user) = users.get(id) {
+				greet(user);
+			}
+		}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases ===
+
+#[test]
+fn unwrapping_lets() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		fn find(users: &Users, id: u32) -> Option<String> {
+			let user = match users.get(id) {
+				Some(u) => u,
+				None => return None,
+			};
+			let name = match user.name() {
+				Err(_) => {
+					log::warn!("unnamed");
+					return None;
+				}
+				Ok(name) => name,
+			};
+			let mut age = if let Some(age) = user.age { age } else { return None };
+			Some(name)
+		}
+		"#,
+		&opts(),
+	), @r#"
+	# Assert mode
+	[let-else] /main.rs:2: `match` that only unwraps `Some(user)` or diverges
+	HINT: use `let Some(user) = .. else { .. };`
+	[let-else] /main.rs:6: `match` that only unwraps `Ok(name)` or diverges
+	HINT: use `let Ok(name) = .. else { .. };`
+	[let-else] /main.rs:13: `if let` that only unwraps `Some(mut age)` or diverges
+	HINT: use `let Some(mut age) = .. else { .. };`
+
+	# Format mode
+	fn find(users: &Users, id: u32) -> Option<String> {
+		let Some(user) = users.get(id) else { return None };
+		let Ok(name) = user.name() else {
+			log::warn!("unnamed");
+			return None;
+		};
+		let Some(mut age) = user.age else { return None };
+		Some(name)
+	}
+	"#);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn if_let_statements_and_typed_lets() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		fn main() {
+			for id in ids {
+				if let Some(user) = users.get(id) {
+					greet(user);
+				} else {
+					continue;
+				}
+			}
+			let user: &User = match users.get(id) {
+				Some(user) => user,
+				None => panic!("no user {id}"),
+			};
+		}
+		"#,
+		&opts(),
+	), @"
+	[let-else] /main.rs:3: `if let Some(user)` whose `else` only diverges
+	HINT: use `let Some(user) = .. else { .. };` and dedent the body
+	[let-else] /main.rs:9: `match` that only unwraps `Some(user)` or diverges
+	HINT: use `let Some(user) = .. else { .. };`
+	");
+}
@@ -0,0 +1,47 @@
+use codestyle::{Linter, rust_checks::rustfix::to_diagnostics};
+use v_fixtures::Fixture;
+
+use crate::utils::opts_for;
+
+#[test]
+fn crlf_file_keeps_its_line_endings() {
+	let linter = Linter::new(opts_for("impl_follows_type"));
+	let fixed = linter.apply_fixes("lib.rs", "struct Foo;\r\n\r\n\r\nimpl Foo {\r\n\tfn new() {}\r\n}\r\n").unwrap();
+	assert_eq!(fixed.source, "struct Foo;\r\nimpl Foo {\r\n\tfn new() {}\r\n}\r\n");
+	assert_eq!(fixed.fixed_count, 1);
+}
+
+#[test]
+fn crlf_positions_match_lf_ones() {
+	let linter = Linter::new(opts_for("test_fn_prefix"));
+	let lf = linter.check_source("lib.rs", "fn main() {}\n\n#[test]\nfn test_parses() {}\n").unwrap();
+	let crlf = linter.check_source("lib.rs", "fn main() {}\r\n\r\n#[test]\r\nfn test_parses() {}\r\n").unwrap();
+	let position = |v: &codestyle::rust_checks::Violation| (v.line, v.column, v.end);
+	assert_eq!(crlf.violations.iter().map(position).collect::<Vec<_>>(), lf.violations.iter().map(position).collect::<Vec<_>>());
+
+	let fixed = linter.apply_fixes("lib.rs", "fn main() {}\r\n\r\n#[test]\r\nfn test_parses() {}\r\n").unwrap();
+	assert_eq!(fixed.source, "fn main() {}\r\n\r\n#[test]\r\nfn parses() {}\r\n");
+}
+
+#[test]
+fn unfixed_mixed_file_is_left_alone() {
+	let source = "fn main() {}\r\nfn run() {}\n";
+	let fixed = Linter::new(opts_for("test_fn_prefix")).apply_fixes("lib.rs", source).unwrap();
+	assert_eq!(fixed.source, source);
+}
+
+#[test]
+fn rustfix_suggestions_point_into_the_crlf_file() {
+	let source = "fn main() {}\r\n\r\n#[test]\r\nfn test_parses() {}\r\n";
+	let temp = Fixture::parse("//- /lib.rs\nfn main() {}\n").write_to_tempdir();
+	let path = temp.root.join("lib.rs");
+	std::fs::write(&path, source).unwrap();
+
+	let report = Linter::new(opts_for("test_fn_prefix")).check_path(&path).unwrap();
+	let output = to_diagnostics(&report.violations);
+	let diagnostic: serde_json::Value = serde_json::from_str(output.lines().next().unwrap()).unwrap();
+	let suggestion = &diagnostic["children"][0]["spans"][0];
+	let (start, end) = (suggestion["byte_start"].as_u64().unwrap() as usize, suggestion["byte_end"].as_u64().unwrap() as usize);
+	let fixed = format!("{}{}{}", &source[..start], suggestion["suggested_replacement"].as_str().unwrap(), &source[end..]);
+	assert_eq!(fixed, "fn main() {}\r\n\r\n#[test]\r\nfn parses() {}\r\n");
+}
@@ -0,0 +1,47 @@
+use codestyle::{Linter, rust_checks::exclude::DiscoveryOptions};
+use v_fixtures::Fixture;
+
+use crate::utils::opts_for;
+
+const FIXTURE: &str = r#"
+	//- /src/main.rs
+	fn main() {}
+	//- /src/time.rs
+	use chrono::Utc;
+	"#;
+
+#[test]
+fn check_path_reports_without_printing() {
+	let temp = Fixture::parse(FIXTURE).write_to_tempdir();
+	let report = Linter::new(opts_for("no_chrono")).check_path(&temp.root).unwrap();
+	assert!(report.has_errors());
+	assert_eq!(report.violations.iter().map(|v| v.rule).collect::<Vec<_>>(), ["no-chrono"]);
+
+	let excluding = DiscoveryOptions {
+		exclude: vec!["**/time.rs".into()],
+		..Default::default()
+	};
+	let report = Linter::new(opts_for("no_chrono")).discovery(excluding).check_path(&temp.root).unwrap();
+	assert!(!report.has_errors());
+}
+
+#[test]
+fn missing_paths_are_errors() {
+	assert!(Linter::new(opts_for("no_chrono")).check_path("/no/such/dir").is_err());
+}
+
+#[test]
+fn check_source_and_apply_fixes_work_in_memory() {
+	let linter = Linter::new(opts_for("test_fn_prefix"));
+	let source = "#[test]\nfn test_parses() {}\n";
+
+	let report = linter.check_source("lib.rs", source).unwrap();
+	assert_eq!(report.violations.len(), 1);
+
+	let fixed = linter.apply_fixes("lib.rs", source).unwrap();
+	assert_eq!(fixed.source, "#[test]\nfn parses() {}\n");
+	assert_eq!(fixed.fixed_count, 1);
+	assert!(fixed.remaining.is_empty());
+
+	assert!(linter.apply_fixes("lib.rs", "fn broken( {").is_err());
+}
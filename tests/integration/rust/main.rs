@@ -3,17 +3,82 @@
 //! Each module contains individual #[test] functions that can run in parallel,
 //! enabling proper insta snapshot workflow (all failures at once, accept all at once).
 
+mod allow_reason;
+mod baseline;
+mod bool_params;
+mod cache;
 mod cargo_dep_ordering;
+mod cargo_subcommand;
+mod changed;
+mod commented_code;
+mod custom_rules;
+mod daemon;
+mod dead_files;
+mod derive_debug;
+mod derive_default;
+mod derive_order;
 mod embed_simple_vars;
+mod exclude;
+mod expect_message;
+mod file_args;
+mod fn_params;
+mod format_dry_run;
+mod format_stdin;
+mod generated;
 mod ignored_error_comment;
 mod impl_blocks;
+mod impl_method_order;
 mod insta_snapshots;
 mod instrument;
+mod layout;
+mod let_else;
+mod line_endings;
+mod linter;
 mod loops;
+mod missing_docs;
+mod mod_files;
+mod mod_style;
+mod nesting_depth;
+mod no_as_cast;
+mod no_assert;
+mod no_async_trait;
+mod no_block_on_in_async;
+mod no_blocking_in_async;
 mod no_chrono;
+mod no_dbg;
+mod no_extern_crate;
+mod no_get_prefix;
+mod no_lazy_static;
+mod no_lock_across_await;
+mod no_macro_use;
+mod no_once_cell;
+mod no_other_runtimes;
+mod no_panic;
 mod no_tokio_spawn;
+mod no_unwrap;
+mod organize;
+mod output_formats;
+mod plugins;
+mod pub_fields_first;
 mod pub_first;
+mod pub_use_location;
+mod reqwest_timeout;
+mod rules;
+mod safety_comment;
+mod severity;
+mod should_panic_expected;
 mod skip_attribute;
+mod skip_reason;
+mod stats;
 mod test_fn_prefix;
+mod test_in_cfg_test;
+mod tests_last;
+mod todo_comment;
+mod todo_issue;
+mod unused_pub;
+mod unused_skip;
 mod use_bail;
+mod use_matches;
+mod use_thiserror;
 mod utils;
+mod workspace;
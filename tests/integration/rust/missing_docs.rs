@@ -0,0 +1,112 @@
+use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
+
+fn opts() -> codestyle::rust_checks::RustCheckOptions {
+	opts_for("missing_docs")
+}
+
+// === Passing cases ===
+
+#[test]
+fn documented_and_non_api_items_pass() {
+	assert_check_passing(
+		r#"
+		//- /src/lib.rs
+		/// Parses a config file's contents.
+		pub fn parse(s: &str) -> Option<Config> {
+			None
+		}
+
+		/** Resolved configuration. */
+		pub struct Config;
+		impl Config {
+			/// The defaults.
+			pub fn new() -> Self {
+				Self
+			}
+
+			pub(crate) fn reload(&mut self) {}
+
+			fn validate(&self) {}
+		}
+		impl Default for Config {
+			fn default() -> Self {
+				Self
+			}
+		}
+
+		pub(crate) enum Source {
+			File,
+		}
+
+		#[doc(hidden)]
+		pub trait Sealed {}
+
+		mod private {
+			pub fn helper() {}
+		}
+
+		#[cfg(test)]
+		mod tests {
+			pub fn fixture() {}
+		}
+		"#,
+		&opts(),
+	);
+}
+
+#[test]
+fn binaries_pass() {
+	assert_check_passing(
+		r#"
+		//- /src/lib.rs
+		//! A library.
+		//- /src/main.rs
+		pub fn run() {}
+		//- /src/bin/tool.rs
+		pub struct Args;
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn undocumented_public_items() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		//- /src/lib.rs
+		pub mod config;
+		//- /src/config.rs
+		pub fn parse(s: &str) -> Option<Config> {
+			None
+		}
+
+		// A comment, but not a doc comment
+		pub struct Config;
+		impl Config {
+			pub fn new() -> Self {
+				Self
+			}
+		}
+
+		pub enum Source {
+			File,
+		}
+
+		pub trait Load {}
+		"#,
+		&opts(),
+	), @"
+	[missing-docs] /src/config.rs:1: public fn `parse` has no doc comment
+	HINT: say what it's for, or narrow it to `pub(crate)` if it isn't API
+	[missing-docs] /src/config.rs:6: public struct `Config` has no doc comment
+	HINT: say what it's for, or narrow it to `pub(crate)` if it isn't API
+	[missing-docs] /src/config.rs:8: public fn `new` has no doc comment
+	HINT: say what it's for, or narrow it to `pub(crate)` if it isn't API
+	[missing-docs] /src/config.rs:13: public enum `Source` has no doc comment
+	HINT: say what it's for, or narrow it to `pub(crate)` if it isn't API
+	[missing-docs] /src/config.rs:17: public trait `Load` has no doc comment
+	HINT: say what it's for, or narrow it to `pub(crate)` if it isn't API
+	");
+}
@@ -0,0 +1,97 @@
+use codestyle::rust_checks::RustCheckOptions;
+
+use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
+
+fn opts() -> RustCheckOptions {
+	opts_for("mod_files")
+}
+
+// === Passing cases ===
+
+#[test]
+fn every_file_declared() {
+	assert_check_passing(
+		r#"
+		//- /src/lib.rs
+		mod client;
+		#[path = "vendored/codec.rs"]
+		mod codec;
+
+		//- /src/main.rs
+		mod cli;
+
+		fn main() {}
+
+		//- /src/cli.rs
+		pub fn run() {}
+
+		//- /src/client.rs
+		mod retry;
+
+		//- /src/client/retry.rs
+		fn backoff() {}
+
+		//- /src/vendored/codec.rs
+		fn decode() {}
+
+		//- /src/bin/tool.rs
+		fn main() {}
+
+		//- /tests/common/mod.rs
+		pub fn setup() {}
+
+		//- /tests/api.rs
+		mod common;
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn declarations_without_files_and_files_without_declarations() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		//- /src/lib.rs
+		mod client;
+		mod cache;
+		#[path = "vendored/codec.rs"]
+		mod codec;
+
+		//- /src/client.rs
+		fn connect() {}
+
+		//- /src/legacy.rs
+		fn migrate() {}
+		"#,
+		&opts(),
+	), @r#"
+	[mod-files] /src/lib.rs:2: `mod cache;` has no file: neither `cache.rs` nor `cache/mod.rs` exists
+	HINT: create the file, or drop the declaration
+	[mod-files] /src/lib.rs:4: `#[path = "vendored/codec.rs"]` points to `vendored/codec.rs`, which doesn't exist
+	HINT: fix the path, or drop the declaration
+	[mod-files] /src/lib.rs:3: `legacy.rs` has no `mod legacy;` declaring it, so it isn't compiled
+	HINT: declare it here, or delete the file
+	"#);
+}
+
+#[test]
+fn stray_file_in_a_module_directory() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		//- /src/client.rs
+		mod retry;
+
+		//- /src/client/retry.rs
+		fn backoff() {}
+
+		//- /src/client/stale.rs
+		fn old_backoff() {}
+		"#,
+		&opts(),
+	), @"
+	[mod-files] /src/client.rs:1: `client/stale.rs` has no `mod stale;` declaring it, so it isn't compiled
+	HINT: declare it here, or delete the file
+	");
+}
@@ -0,0 +1,152 @@
+use codestyle::rust_checks::{RustCheckOptions, mod_style::Layout};
+
+use crate::utils::{assert_check_passing, opts_for, test_case, test_case_assert_only};
+
+fn opts() -> RustCheckOptions {
+	opts_for("mod_style")
+}
+
+fn mod_rs_opts() -> RustCheckOptions {
+	RustCheckOptions {
+		mod_style_prefer: Layout::ModRs,
+		..opts()
+	}
+}
+
+// === Passing cases ===
+
+#[test]
+fn file_layout_passes() {
+	assert_check_passing(
+		r#"
+		//- /src/lib.rs
+		mod parser;
+
+		//- /src/parser.rs
+		mod lexer;
+
+		//- /src/parser/lexer.rs
+		fn lex() {}
+
+		//- /tests/common/mod.rs
+		pub fn setup() {}
+
+		//- /tests/api.rs
+		mod common;
+		"#,
+		&opts(),
+	);
+}
+
+#[test]
+fn mod_rs_layout_passes() {
+	assert_check_passing(
+		r#"
+		//- /src/lib.rs
+		mod parser;
+		mod render;
+
+		//- /src/parser/mod.rs
+		mod lexer;
+
+		//- /src/parser/lexer.rs
+		fn lex() {}
+
+		//- /src/render.rs
+		fn render() {}
+
+		//- /tests/integration.rs
+		mod api;
+
+		//- /tests/integration/api.rs
+		fn api() {}
+		"#,
+		&mod_rs_opts(),
+	);
+}
+
+// === Violation cases ===
+
+#[test]
+fn mod_rs_moves_next_to_its_directory() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		//- /src/lib.rs
+		mod render;
+
+		//- /src/render/mod.rs
+		fn render() {}
+		"#,
+		&opts(),
+	), @"
+	# Assert mode
+	[mod-style] /src/render/mod.rs:1: `render/mod.rs` should be `render.rs`
+	HINT: this crate keeps a module's file next to its directory
+
+	# Format mode
+	//- /src/lib.rs
+	mod render;
+
+	//- /src/render.rs
+	fn render() {}
+	");
+}
+
+#[test]
+fn file_moves_into_its_directory() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		//- /src/lib.rs
+		mod render;
+
+		//- /src/render.rs
+		mod html;
+
+		//- /src/render/html.rs
+		fn html() {}
+		"#,
+		&mod_rs_opts(),
+	), @"
+	# Assert mode
+	[mod-style] /src/render.rs:1: `render.rs` should be `render/mod.rs`
+	HINT: this crate keeps a module's file inside its directory, as `mod.rs`
+
+	# Format mode
+	//- /src/lib.rs
+	mod render;
+
+	//- /src/render/html.rs
+	fn html() {}
+			
+	//- /src/render/mod.rs
+	mod html;
+	");
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn taken_targets_and_path_attributes() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		//- /src/lib.rs
+		mod parser;
+
+		//- /src/parser/mod.rs
+		#[path = "generated.rs"]
+		mod generated;
+
+		//- /src/render.rs
+		fn render() {}
+
+		//- /src/render/mod.rs
+		fn render() {}
+		"#,
+		&opts(),
+	), @"
+	[mod-style] /src/parser/mod.rs:1: `parser/mod.rs` should be `parser.rs`
+	HINT: move it by hand, updating its `#[path]` attributes, which are relative to where it is
+	[mod-style] /src/render/mod.rs:1: `render/mod.rs` should be `render.rs`
+	HINT: `render.rs` already exists, so the two have to be merged by hand
+	");
+}
@@ -0,0 +1,117 @@
+use codestyle::rust_checks::RustCheckOptions;
+
+use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
+
+fn opts() -> RustCheckOptions {
+	opts_for("nesting_depth")
+}
+
+// === Passing cases ===
+
+#[test]
+fn shallow_and_else_if_chains_pass() {
+	assert_check_passing(
+		r#"
+		fn classify(items: &[u8]) {
+			for item in items {
+				if *item == 0 {
+					continue;
+				} else if *item == 1 {
+					match item {
+						_ => {
+							let _ = items.iter().map(|i| i + 1);
+						}
+					}
+				} else if *item == 2 {
+				} else {
+				}
+			}
+		}
+		"#,
+		&opts(),
+	);
+}
+
+#[test]
+fn nested_fns_are_measured_on_their_own() {
+	assert_check_passing(
+		r#"
+		fn outer() {
+			loop {
+				if ready() {
+					while busy() {
+						fn inner() {
+							if a() {
+								if b() {}
+							}
+						}
+					}
+				}
+			}
+		}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn deep_nesting_points_at_innermost_block() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		fn sync(items: &[Item]) {
+			for item in items {
+				if item.dirty {
+					match item.kind {
+						Kind::File => {
+							if let Some(path) = &item.path {
+								item.chunks().for_each(|chunk| write(path, chunk));
+							}
+						}
+						_ => {}
+					}
+				}
+			}
+		}
+
+		impl Store {
+			fn flush(&self) {
+				if a {
+					if b {
+						if c {
+							if d {
+								loop {}
+							}
+						}
+					}
+				}
+			}
+		}
+		"#,
+		&opts(),
+	), @"
+	[nesting-depth] /main.rs:7: `sync` nests 5 levels deep, more than 4
+	HINT: return early, use `let .. else` or `continue`, or extract a helper
+	[nesting-depth] /main.rs:22: `flush` nests 5 levels deep, more than 4
+	HINT: return early, use `let .. else` or `continue`, or extract a helper
+	");
+}
+
+#[test]
+fn max_is_configurable() {
+	let opts = RustCheckOptions { nesting_depth_max: 1, ..opts() };
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		fn f() {
+			if a {
+				if b {}
+			}
+		}
+		"#,
+		&opts,
+	), @"
+	[nesting-depth] /main.rs:3: `f` nests 2 levels deep, more than 1
+	HINT: return early, use `let .. else` or `continue`, or extract a helper
+	");
+}
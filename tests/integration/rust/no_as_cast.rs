@@ -0,0 +1,84 @@
+use codestyle::rust_checks::RustCheckOptions;
+
+use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
+
+fn opts() -> RustCheckOptions {
+	opts_for("no_as_cast")
+}
+
+// === Passing cases ===
+
+#[test]
+fn const_contexts_and_lossless_casts_pass() {
+	assert_check_passing(
+		r#"
+		const MASK: u32 = u16::MAX as u32;
+		static TABLE: [u8; 256 as usize] = [0; 256];
+
+		enum Kind {
+			File = 1 << 2 as u8,
+		}
+
+		const fn widen(x: u16) -> u64 {
+			x as u64
+		}
+
+		fn main() {
+			let byte = 255 as u8;
+			let negative = -128 as i8;
+			let tag = Kind::File as u8;
+			let buf = Buf::<{ 4 as usize }>::new();
+			let ptr = &x as *const u8;
+			let table = const { 3 as u8 };
+		}
+		"#,
+		&opts(),
+	);
+}
+
+#[test]
+fn skip_marker_allows_intended_truncation() {
+	assert_check_passing(
+		r#"
+		//#[codestyle::skip(no-as-cast)]
+		fn low_byte(x: u32) -> u8 {
+			x as u8
+		}
+
+		fn checksum(data: &[u32]) -> u8 {
+			//#[codestyle::skip(no-as-cast)]
+			let sum = data.iter().sum::<u32>() as u8;
+			sum
+		}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn numeric_casts() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		fn main() {
+			let len = bytes.len() as u16;
+			let ratio = done as f32 / total as f32;
+			let byte = 256 as u8;
+			let max = u64::MAX as u32;
+		}
+		"#,
+		&opts(),
+	), @"
+	[no-as-cast] /main.rs:2: `as u16` cast may truncate, wrap or round
+	HINT: use `u16::from(..)` if it's lossless, `u16::try_from(..)` if it isn't
+	[no-as-cast] /main.rs:3: `as f32` cast may truncate, wrap or round
+	HINT: use `f32::from(..)` if it's lossless, `f32::try_from(..)` if it isn't
+	[no-as-cast] /main.rs:3: `as f32` cast may truncate, wrap or round
+	HINT: use `f32::from(..)` if it's lossless, `f32::try_from(..)` if it isn't
+	[no-as-cast] /main.rs:4: `as u8` cast may truncate, wrap or round
+	HINT: use `u8::from(..)` if it's lossless, `u8::try_from(..)` if it isn't
+	[no-as-cast] /main.rs:5: `as u32` cast may truncate, wrap or round
+	HINT: use `u32::from(..)` if it's lossless, `u32::try_from(..)` if it isn't
+	");
+}
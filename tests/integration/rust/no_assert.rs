@@ -0,0 +1,80 @@
+use codestyle::rust_checks::RustCheckOptions;
+
+use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
+
+fn opts() -> RustCheckOptions {
+	opts_for("no_assert")
+}
+
+// === Passing cases ===
+
+#[test]
+fn debug_const_and_test_assertions_pass() {
+	assert_check_passing(
+		r#"
+		const _: () = assert!(size_of::<u64>() == 8);
+
+		pub fn split(total: u64, parts: u64) -> u64 {
+			debug_assert!(parts > 0);
+			const { assert!(usize::BITS >= 32) };
+			total / parts
+		}
+
+		#[cfg(test)]
+		mod tests {
+			#[test]
+			fn splits_evenly() {
+				assert_eq!(super::split(10, 2), 5);
+			}
+		}
+		"#,
+		&opts(),
+	);
+}
+
+#[test]
+fn justified_invariants_pass() {
+	let opts = RustCheckOptions {
+		no_assert_marker: "INVARIANT".to_string(),
+		..opts()
+	};
+	assert_check_passing(
+		r#"
+		pub fn index(buf: &[u8], i: usize) -> u8 {
+			//INVARIANT: an out-of-bounds read here would corrupt the cache
+			assert!(i < buf.len());
+			buf[i]
+		}
+		"#,
+		&opts,
+	);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn assertions_outside_tests() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		pub fn split(total: u64, parts: u64) -> u64 {
+			assert!(parts > 0);
+			std::assert_ne!(total, 0);
+			total / parts
+		}
+
+		pub fn index(buf: &[u8], i: usize) -> u8 {
+			//INVARIANT: an out-of-bounds read here would corrupt the cache
+			assert!(i < buf.len());
+			buf[i]
+		}
+		"#,
+		&opts(),
+	), @"
+	[no-assert] /main.rs:2: `assert!` outside tests
+	HINT: return an error if input can break it, or use `debug_assert!` if only a bug can
+	[no-assert] /main.rs:3: `assert_ne!` outside tests
+	HINT: return an error if input can break it, or use `debug_assert_ne!` if only a bug can
+	[no-assert] /main.rs:9: `assert!` outside tests
+	HINT: return an error if input can break it, or use `debug_assert!` if only a bug can
+	");
+}
@@ -0,0 +1,101 @@
+use codestyle::rust_checks::RustCheckOptions;
+
+use crate::utils::{assert_check_passing, opts_for, test_case, test_case_assert_only};
+
+fn opts() -> RustCheckOptions {
+	opts_for("no_async_trait")
+}
+
+// === Passing cases ===
+
+#[test]
+fn native_async_fn_in_trait_passes() {
+	assert_check_passing(
+		r#"
+		trait Store {
+			async fn get(&self, key: &str) -> Option<Bytes>;
+		}
+
+		fn async_trait_count() -> usize {
+			0
+		}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases ===
+
+#[test]
+fn attributes_and_import_removed() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		use std::sync::Arc;
+
+		use async_trait::async_trait;
+		use bytes::Bytes;
+
+		#[async_trait]
+		trait Store {
+			async fn get(&self, key: &str) -> Option<Bytes>;
+		}
+
+		#[async_trait]
+		impl Store for Memory {
+			async fn get(&self, key: &str) -> Option<Bytes> {
+				self.map.get(key).cloned()
+			}
+		}
+		"#,
+		&opts(),
+	), @"
+	# Assert mode
+	[no-async-trait] /main.rs:3: `async_trait` import, for an attribute native `async fn` in traits replaces
+	HINT: remove it along with the `#[async_trait]` attributes
+	[no-async-trait] /main.rs:6: `#[async_trait]` on `Store`, which native `async fn` in traits replaces
+	HINT: remove it; write `-> impl Future<Output = ..> + Send` where callers need `Send` futures
+	[no-async-trait] /main.rs:11: `#[async_trait]` on `Store`, which native `async fn` in traits replaces
+	HINT: remove it; write `-> impl Future<Output = ..> + Send` where callers need `Send` futures
+
+	# Format mode
+	use std::sync::Arc;
+
+	use bytes::Bytes;
+
+	trait Store {
+		async fn get(&self, key: &str) -> Option<Bytes>;
+	}
+
+	impl Store for Memory {
+		async fn get(&self, key: &str) -> Option<Bytes> {
+			self.map.get(key).cloned()
+		}
+	}
+	");
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn dyn_traits_keep_boxing_by_hand() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		use async_trait::async_trait;
+
+		#[async_trait]
+		pub trait Handler: Send + Sync {
+			async fn handle(&self, req: Request) -> Response;
+		}
+
+		pub struct Router {
+			routes: Vec<Box<dyn Handler>>,
+		}
+		"#,
+		&opts(),
+	), @"
+	[no-async-trait] /main.rs:1: `async_trait` import, for an attribute native `async fn` in traits replaces
+	HINT: remove it along with the `#[async_trait]` attributes
+	[no-async-trait] /main.rs:3: `#[async_trait]` on `Handler`, which native `async fn` in traits replaces
+	HINT: `dyn Handler` needs boxed futures, so its methods return `Pin<Box<dyn Future<Output = ..> + Send + '_>>` by hand
+	");
+}
@@ -0,0 +1,68 @@
+use codestyle::rust_checks::RustCheckOptions;
+
+use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
+
+fn opts() -> RustCheckOptions {
+	opts_for("no_block_on_in_async")
+}
+
+// === Passing cases ===
+
+#[test]
+fn block_on_outside_async_code_passes() {
+	assert_check_passing(
+		r#"
+		fn main() -> Result<()> {
+			let rt = Runtime::new()?;
+			rt.block_on(serve())
+		}
+
+		fn sync_bridge(handle: &Handle) -> Token {
+			futures::executor::block_on(handle.spawn(fetch_token())).unwrap()
+		}
+
+		async fn offloaded(handle: Handle) -> Result<Token> {
+			let token = tokio::task::spawn_blocking(move || handle.block_on(fetch_token())).await?;
+			tokio::task::block_in_place(|| Handle::current().block_on(flush()));
+			std::thread::spawn(move || futures::executor::block_on(report(token)));
+			Ok(token)
+		}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn block_on_in_async_code() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		use futures::executor::block_on;
+		use tokio::runtime::Handle;
+
+		async fn refresh(&self) -> Result<()> {
+			let token = Handle::current().block_on(self.auth.token())?;
+			let ids = self.ids.iter().map(|id| block_on(self.lookup(id))).collect::<Vec<_>>();
+			Handle::block_on(&self.handle, self.flush());
+			Ok(())
+		}
+
+		fn main() {
+			let task = async {
+				futures::executor::block_on(tick());
+			};
+		}
+		"#,
+		&opts(),
+	), @"
+	[no-block-on-in-async] /main.rs:5: `.block_on()` inside async code blocks the executor thread it runs on
+	HINT: `.await` the future instead
+	[no-block-on-in-async] /main.rs:6: `futures::executor::block_on` inside async code blocks the executor thread it runs on
+	HINT: `.await` the future instead
+	[no-block-on-in-async] /main.rs:7: `tokio::runtime::Handle::block_on` inside async code blocks the executor thread it runs on
+	HINT: `.await` the future instead
+	[no-block-on-in-async] /main.rs:13: `futures::executor::block_on` inside async code blocks the executor thread it runs on
+	HINT: `.await` the future instead
+	");
+}
@@ -0,0 +1,104 @@
+use codestyle::rust_checks::RustCheckOptions;
+
+use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
+
+fn opts() -> RustCheckOptions {
+	opts_for("no_blocking_in_async")
+}
+
+// === Passing cases ===
+
+#[test]
+fn async_and_offloaded_calls_pass() {
+	assert_check_passing(
+		r#"
+		use std::net::{Ipv4Addr, SocketAddr};
+		use tokio::time::sleep;
+
+		async fn load(path: &Path) -> Result<String> {
+			sleep(BACKOFF).await;
+			let text = tokio::fs::read_to_string(path).await?;
+			let raw = tokio::task::spawn_blocking(move || std::fs::read(path)).await??;
+			let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 8080);
+			Ok(text)
+		}
+
+		async fn outer() {
+			fn helper() {
+				std::thread::sleep(BACKOFF);
+			}
+		}
+
+		fn blocking(path: &Path) -> io::Result<String> {
+			std::fs::read_to_string(path)
+		}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn blocking_calls_in_async_code() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		use std::{fs, thread};
+		use std::fs::File;
+		use std::net::TcpStream;
+
+		async fn load(path: &Path) -> Result<Config> {
+			let text = fs::read_to_string(path)?;
+			thread::sleep(Duration::from_millis(10));
+			let file = File::open(path)?;
+			Ok(parse(&text, file)?)
+		}
+
+		impl Client {
+			async fn connect(&self) -> io::Result<()> {
+				let stream = TcpStream::connect(&self.addr)?;
+				Ok(())
+			}
+		}
+
+		fn main() {
+			let task = async {
+				std::thread::sleep(BACKOFF);
+			};
+		}
+		"#,
+		&opts(),
+	), @"
+	[no-blocking-in-async] /main.rs:6: `std::fs::read_to_string` blocks the executor thread in async code
+	HINT: use `tokio::fs::read_to_string` instead, or run it in `tokio::task::spawn_blocking`
+	[no-blocking-in-async] /main.rs:7: `std::thread::sleep` blocks the executor thread in async code
+	HINT: use `tokio::time::sleep` instead, or run it in `tokio::task::spawn_blocking`
+	[no-blocking-in-async] /main.rs:8: `std::fs::File::open` blocks the executor thread in async code
+	HINT: use `tokio::fs::File::open` instead, or run it in `tokio::task::spawn_blocking`
+	[no-blocking-in-async] /main.rs:14: `std::net::TcpStream::connect` blocks the executor thread in async code
+	HINT: use `tokio::net::TcpStream::connect` instead, or run it in `tokio::task::spawn_blocking`
+	[no-blocking-in-async] /main.rs:21: `std::thread::sleep` blocks the executor thread in async code
+	HINT: use `tokio::time::sleep` instead, or run it in `tokio::task::spawn_blocking`
+	");
+}
+
+#[test]
+fn configured_paths() {
+	let opts = RustCheckOptions {
+		no_blocking_in_async_paths: vec!["reqwest::blocking".to_string()],
+		..opts()
+	};
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		use reqwest::blocking;
+
+		async fn fetch(url: &str) -> Result<String> {
+			Ok(blocking::get(url)?.text()?)
+		}
+		"#,
+		&opts,
+	), @"
+	[no-blocking-in-async] /main.rs:4: `reqwest::blocking::get` blocks the executor thread in async code
+	HINT: use an async equivalent, or run it in `tokio::task::spawn_blocking`
+	");
+}
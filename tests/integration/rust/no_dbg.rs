@@ -0,0 +1,83 @@
+use crate::utils::{assert_check_passing, opts_for, test_case};
+
+fn opts() -> codestyle::rust_checks::RustCheckOptions {
+	opts_for("no_dbg")
+}
+
+// === Passing cases ===
+
+#[test]
+fn no_dbg_passes() {
+	assert_check_passing(
+		r#"
+		fn main() {
+			let dbg = 1;
+			tracing::debug!(dbg);
+			eprintln!("dbg!(x) in a string is fine");
+		}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases ===
+
+#[test]
+fn dbg_is_unwrapped() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		fn main() {
+			let total = dbg!(price * quantity) * 2;
+			let name = dbg!(user.name());
+			let pair = dbg!(a, b);
+			dbg!(total);
+			dbg!();
+		}
+		"#,
+		&opts(),
+	), @"
+	# Assert mode
+	[no-dbg] /main.rs:2: leftover `dbg!`
+	HINT: remove it, or log through `tracing` if the output is worth keeping
+	[no-dbg] /main.rs:3: leftover `dbg!`
+	HINT: remove it, or log through `tracing` if the output is worth keeping
+	[no-dbg] /main.rs:4: leftover `dbg!`
+	HINT: remove it, or log through `tracing` if the output is worth keeping
+	[no-dbg] /main.rs:5: leftover `dbg!`
+	HINT: remove it, or log through `tracing` if the output is worth keeping
+	[no-dbg] /main.rs:6: leftover `dbg!`
+	HINT: remove it, or log through `tracing` if the output is worth keeping
+
+	# Format mode
+	fn main() {
+		let total = (price * quantity) * 2;
+		let name = user.name();
+		let pair = (a, b);
+		total;
+		();
+	}
+	");
+}
+
+#[test]
+fn nested_dbg_takes_two_passes() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		fn main() {
+			let x = std::dbg!(dbg!(compute()));
+		}
+		"#,
+		&opts(),
+	), @"
+	# Assert mode
+	[no-dbg] /main.rs:2: leftover `dbg!`
+	HINT: remove it, or log through `tracing` if the output is worth keeping
+	[no-dbg] /main.rs:2: leftover `dbg!`
+	HINT: remove it, or log through `tracing` if the output is worth keeping
+
+	# Format mode
+	fn main() {
+		let x = compute();
+	}
+	");
+}
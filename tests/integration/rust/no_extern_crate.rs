@@ -0,0 +1,70 @@
+use crate::utils::{assert_check_passing, opts_for, test_case, test_case_assert_only};
+
+fn opts() -> codestyle::rust_checks::RustCheckOptions {
+	opts_for("no_extern_crate")
+}
+
+// === Passing cases ===
+
+#[test]
+fn crates_outside_the_extern_prelude_pass() {
+	assert_check_passing(
+		r#"
+		extern crate alloc;
+		extern crate proc_macro;
+		extern crate self as codestyle;
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases ===
+
+#[test]
+fn extern_crates_are_deleted() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		extern crate alloc;
+		extern crate serde;
+
+		#[cfg(feature = "log")]
+		extern crate log;
+
+		use serde::Deserialize;
+		"#,
+		&opts(),
+	), @r#"
+	# Assert mode
+	[no-extern-crate] /main.rs:2: `extern crate serde` is unneeded since edition 2018
+	HINT: delete it
+	[no-extern-crate] /main.rs:5: `extern crate log` is unneeded since edition 2018
+	HINT: delete it
+
+	# Format mode
+	extern crate alloc;
+
+	use serde::Deserialize;
+	"#);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn scope_changing_extern_crates_are_kept() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		extern crate serde_json as json;
+		pub extern crate tracing;
+		#[macro_use]
+		extern crate lazy_static;
+		"#,
+		&opts(),
+	), @"
+	[no-extern-crate] /main.rs:1: `extern crate serde_json` is unneeded since edition 2018
+	HINT: replace it with `use serde_json as json;`, or rename the dependency in `Cargo.toml`
+	[no-extern-crate] /main.rs:2: `extern crate tracing` is unneeded since edition 2018
+	HINT: replace it with `pub use tracing;`
+	[no-extern-crate] /main.rs:4: `extern crate lazy_static` is unneeded since edition 2018
+	HINT: import the macros with `use lazy_static::..;` where they're used
+	");
+}
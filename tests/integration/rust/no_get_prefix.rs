@@ -0,0 +1,198 @@
+use crate::utils::{assert_check_passing, opts_for, test_case, test_case_assert_only};
+
+fn opts() -> codestyle::rust_checks::RustCheckOptions {
+	opts_for("no_get_prefix")
+}
+
+// === Passing cases ===
+
+#[test]
+fn non_getters_and_trait_methods_pass() {
+	assert_check_passing(
+		r#"
+		pub struct Cache {
+			entries: Vec<String>,
+		}
+
+		impl Cache {
+			pub fn get(&self, i: usize) -> Option<&String> {
+				self.entries.get(i)
+			}
+
+			pub fn get_mut(&mut self) -> &mut Vec<String> {
+				&mut self.entries
+			}
+
+			pub fn get_len(&self) -> usize {
+				self.entries.len()
+			}
+
+			pub fn get_entry(&self, i: usize) -> &str {
+				&self.entries[i]
+			}
+		}
+
+		impl Store for Cache {
+			fn get_entries(&self) -> &[String] {
+				&self.entries
+			}
+		}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases ===
+
+#[test]
+fn getters_and_their_calls_are_renamed() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		pub struct Client {
+			timeout: u64,
+			name: String,
+		}
+
+		impl Client {
+			pub fn get_timeout(&self) -> u64 {
+				self.timeout
+			}
+
+			fn get_name(&self) -> &str {
+				self.name.as_str()
+			}
+
+			fn describe(&self) -> String {
+				let timeout = self.get_timeout();
+				Self::get_name(self).to_string() + &timeout.to_string()
+			}
+		}
+		"#,
+		&opts(),
+	), @"
+	# Assert mode
+	[no-get-prefix] /main.rs:7: getter `get_timeout` has a `get_` prefix
+	HINT: name it `timeout`, and its callers in other files
+	[no-get-prefix] /main.rs:11: getter `get_name` has a `get_` prefix
+	HINT: name it `name`
+
+	# Format mode
+	pub struct Client {
+		timeout: u64,
+		name: String,
+	}
+
+	impl Client {
+		pub fn timeout(&self) -> u64 {
+			self.timeout
+		}
+
+		fn name(&self) -> &str {
+			self.name.as_str()
+		}
+
+		fn describe(&self) -> String {
+			let timeout = self.timeout();
+			Self::name(self).to_string() + &timeout.to_string()
+		}
+	}
+	");
+}
+
+#[test]
+fn only_calls_on_the_type_are_renamed() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		pub struct Client {
+			timeout: u64,
+		}
+
+		impl Client {
+			pub fn get_timeout(&self) -> u64 {
+				self.timeout
+			}
+		}
+
+		pub struct Server {
+			client: Client,
+		}
+
+		impl Server {
+			fn timeouts(&self, other: &Client, conn: &Connection) -> [u64; 4] {
+				let local = Client { timeout: 1 };
+				[self.client.get_timeout(), other.get_timeout(), local.get_timeout(), conn.get_timeout()]
+			}
+		}
+		"#,
+		&opts(),
+	), @"
+	# Assert mode
+	[no-get-prefix] /main.rs:6: getter `get_timeout` has a `get_` prefix
+	HINT: name it `timeout`, renaming the marked uses the fix can't by hand, and its callers in other files
+
+	# Format mode
+	pub struct Client {
+		timeout: u64,
+	}
+
+	impl Client {
+		pub fn timeout(&self) -> u64 {
+			self.timeout
+		}
+	}
+
+	pub struct Server {
+		client: Client,
+	}
+
+	impl Server {
+		fn timeouts(&self, other: &Client, conn: &Connection) -> [u64; 4] {
+			let local = Client { timeout: 1 };
+			[self.client.timeout(), other.timeout(), local.timeout(), conn.get_timeout()]
+		}
+	}
+	");
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn ambiguous_calls_are_left_alone() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		pub struct Client {
+			name: String,
+		}
+
+		impl Client {
+			fn get_name(&self) -> &str {
+				&self.name
+			}
+
+			fn name(&self) -> String {
+				self.name.clone()
+			}
+		}
+
+		pub struct Server {
+			id: u32,
+		}
+
+		impl Server {
+			fn get_id(&self) -> u32 {
+				self.id
+			}
+		}
+
+		fn get_id(server: &Server) -> u32 {
+			server.get_id()
+		}
+		"#,
+		&opts(),
+	), @"
+	[no-get-prefix] /main.rs:6: getter `get_name` has a `get_` prefix
+	HINT: name it `name`, after renaming the `name` method already there
+	[no-get-prefix] /main.rs:20: getter `get_id` has a `get_` prefix
+	HINT: name it `id`, renaming its calls by hand: other fns are named `get_id` too
+	");
+}
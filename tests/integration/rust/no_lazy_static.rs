@@ -0,0 +1,121 @@
+use codestyle::rust_checks::RustCheckOptions;
+
+use crate::utils::{assert_check_passing, opts_for, test_case, test_case_assert_only};
+
+fn opts() -> RustCheckOptions {
+	opts_for("no_lazy_static")
+}
+
+// === Passing cases ===
+
+#[test]
+fn lazy_lock_passes() {
+	assert_check_passing(
+		r#"
+		use std::sync::LazyLock;
+
+		static WORD: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\w+").unwrap());
+
+		fn lazy_static_count() -> usize {
+			0
+		}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases ===
+
+#[test]
+fn single_statics_rewritten() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		use lazy_static::lazy_static;
+		use regex::Regex;
+
+		lazy_static! {
+			static ref WORD: Regex = Regex::new(r"\w+").unwrap();
+		}
+
+		lazy_static! {
+			pub(crate) static ref ALIASES: HashMap<&'static str, &'static str> = [
+				("ls", "list"),
+				("rm", "remove"),
+			]
+			.into_iter()
+			.collect();
+		}
+
+		fn main() {
+			lazy_static! {
+				static ref START: Instant = Instant::now();
+			}
+			println!("{:?}", START.elapsed());
+		}
+		"#,
+		&opts(),
+	), @r#"
+	# Assert mode
+	[no-lazy-static] /main.rs:1: `lazy_static` import
+	HINT: `std::sync::LazyLock` replaces the crate
+	[no-lazy-static] /main.rs:4: `lazy_static!` defining `WORD`
+	HINT: use `static WORD: LazyLock<..> = LazyLock::new(|| ..);`
+	[no-lazy-static] /main.rs:8: `lazy_static!` defining `ALIASES`
+	HINT: use `static ALIASES: LazyLock<..> = LazyLock::new(|| ..);`
+	[no-lazy-static] /main.rs:18: `lazy_static!` defining `START`
+	HINT: use `static START: LazyLock<..> = LazyLock::new(|| ..);`
+
+	# Format mode
+	use std::sync::LazyLock;
+	use regex::Regex;
+
+	static WORD: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\w+").unwrap());
+
+	pub(crate) static ALIASES: LazyLock<HashMap<&'static str, &'static str>> = LazyLock::new(|| [
+		("ls", "list"),
+		("rm", "remove"),
+	]
+	.into_iter()
+	.collect());
+
+	fn main() {
+		static START: LazyLock<Instant> = LazyLock::new(|| Instant::now());
+		println!("{:?}", START.elapsed());
+	}
+	"#);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn attributes_and_several_statics_kept() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		#[macro_use]
+		extern crate lazy_static;
+
+		lazy_static! {
+			/// Compiled once, on first use
+			static ref WORD: Regex = Regex::new(r"\w+").unwrap();
+		}
+
+		lazy_static! {
+			static ref SPACE: Regex = Regex::new(r"\s+").unwrap();
+			static ref DIGIT: Regex = Regex::new(r"\d+").unwrap();
+		}
+
+		//@codestyle::skip(no-lazy-static)
+		lazy_static! {
+			static ref START: Instant = Instant::now();
+		}
+		"#,
+		&opts(),
+	), @"
+	[no-lazy-static] /main.rs:1: `lazy_static` crate
+	HINT: `std::sync::LazyLock` replaces it
+	[no-lazy-static] /main.rs:4: `lazy_static!` defining `WORD`
+	HINT: use `static WORD: LazyLock<..> = LazyLock::new(|| ..);`
+	[no-lazy-static] /main.rs:9: `lazy_static!` defining `SPACE`, `DIGIT`
+	HINT: use a `std::sync::LazyLock` static for each
+	");
+}
@@ -0,0 +1,96 @@
+use codestyle::rust_checks::RustCheckOptions;
+
+use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
+
+fn opts() -> RustCheckOptions {
+	opts_for("no_lock_across_await")
+}
+
+// === Passing cases ===
+
+#[test]
+fn guards_released_before_await_pass() {
+	assert_check_passing(
+		r#"
+		async fn refresh(cache: &Mutex<Cache>, key: Key) {
+			let value = fetch(key).await;
+			let len = cache.lock().unwrap().len();
+			cache.lock().unwrap().insert(key, value);
+		}
+
+		async fn scoped(cache: &Mutex<Cache>) {
+			{
+				let cache = cache.lock().unwrap();
+				cache.trim();
+			}
+			save().await;
+		}
+
+		async fn dropped(cache: &Mutex<Cache>) {
+			let guard = cache.lock().unwrap();
+			let later = async { save().await };
+			drop(guard);
+			later.await;
+		}
+
+		async fn async_lock(cache: &tokio::sync::Mutex<Cache>) {
+			let guard = cache.lock().await;
+			save(&guard).await;
+		}
+
+		fn blocking(cache: &Mutex<Cache>) {
+			let guard = cache.lock().unwrap();
+			block_on(async { save().await });
+		}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn guards_held_across_await() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		async fn refresh(cache: &Mutex<Cache>, key: Key) {
+			let mut cache = cache.lock().unwrap();
+			let value = fetch(key).await;
+			cache.insert(key, value);
+		}
+
+		async fn stats(entries: &RwLock<Vec<Entry>>) -> usize {
+			let entries = entries.read().expect("entries lock poisoned");
+			if entries.is_empty() {
+				sleep(BACKOFF).await;
+			}
+			entries.len()
+		}
+
+		impl Worker {
+			async fn run(&self) -> Result<()> {
+				let state = self.state.write()?;
+				self.flush().await?;
+				Ok(())
+			}
+		}
+
+		fn main() {
+			let task = async {
+				let guard = LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+				tick().await;
+			};
+		}
+		"#,
+		&opts(),
+	), @"
+	[no-lock-across-await] /main.rs:2: `cache` holds a `lock()` guard across an `.await`
+	HINT: drop it before the `.await`, or use `tokio::sync::Mutex`, whose guard can be held across one
+	[no-lock-across-await] /main.rs:8: `entries` holds a `read()` guard across an `.await`
+	HINT: drop it before the `.await`, or use `tokio::sync::RwLock`, whose guard can be held across one
+	[no-lock-across-await] /main.rs:17: `state` holds a `write()` guard across an `.await`
+	HINT: drop it before the `.await`, or use `tokio::sync::RwLock`, whose guard can be held across one
+	[no-lock-across-await] /main.rs:25: `guard` holds a `lock()` guard across an `.await`
+	HINT: drop it before the `.await`, or use `tokio::sync::Mutex`, whose guard can be held across one
+	");
+}
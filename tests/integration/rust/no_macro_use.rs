@@ -0,0 +1,205 @@
+use crate::utils::{assert_check_passing, opts_for, test_case, test_case_assert_only};
+
+fn opts() -> codestyle::rust_checks::RustCheckOptions {
+	opts_for("no_macro_use")
+}
+
+// === Passing cases ===
+
+#[test]
+fn imported_macros_pass() {
+	assert_check_passing(
+		r#"
+		//- /src/lib.rs
+		use crate::log_call;
+
+		mod macros;
+
+		pub fn run() {
+			log_call!();
+		}
+
+		//- /src/macros.rs
+		#[macro_export]
+		macro_rules! log_call {
+			() => {};
+		}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases ===
+
+#[test]
+fn exported_macros_are_imported() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		//- /src/lib.rs
+		#[macro_use]
+		mod macros;
+
+		pub fn run() {
+			log_call!();
+			retry!(3);
+		}
+
+		//- /src/macros.rs
+		#[macro_export]
+		macro_rules! log_call {
+			() => {};
+		}
+
+		#[macro_export]
+		macro_rules! retry {
+			($n:expr) => {};
+		}
+
+		#[macro_export]
+		macro_rules! unused {
+			() => {};
+		}
+		"#,
+		&opts(),
+	), @r#"
+	# Assert mode
+	[no-macro-use] /src/lib.rs:1: `#[macro_use]` on `mod macros`
+	HINT: import its macros with `use crate::..;` where they're used, in other files too
+
+	# Format mode
+	//- /src/lib.rs
+	use crate::{log_call, retry};
+	mod macros;
+
+	pub fn run() {
+		log_call!();
+		retry!(3);
+	}
+
+	//- /src/macros.rs
+	#[macro_export]
+	macro_rules! log_call {
+		() => {};
+	}
+
+	#[macro_export]
+	macro_rules! retry {
+		($n:expr) => {};
+	}
+
+	#[macro_export]
+	macro_rules! unused {
+		() => {};
+	}
+	"#);
+}
+
+#[test]
+fn inline_module_uses_are_not_imported() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		#[macro_use] mod macros {
+			#[macro_export]
+			macro_rules! square {
+				($x:expr) => {
+					$x * $x
+				};
+			}
+
+			pub fn four() -> u32 {
+				square!(2)
+			}
+		}
+		"#,
+		&opts(),
+	), @r#"
+	# Assert mode
+	[no-macro-use] /main.rs:1: `#[macro_use]` on `mod macros`
+	HINT: import its macros with `use crate::..;` where they're used, in other files too
+
+	# Format mode
+	mod macros {
+		#[macro_export]
+		macro_rules! square {
+			($x:expr) => {
+				$x * $x
+			};
+		}
+
+		pub fn four() -> u32 {
+			square!(2)
+		}
+	}
+	"#);
+}
+
+#[test]
+fn imports_go_above_doc_comments_and_under_the_same_cfg() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		/// Logging helpers
+		#[cfg(feature = "log")]
+		#[macro_use]
+		mod macros {
+			#[macro_export]
+			macro_rules! log_call {
+				() => {};
+			}
+		}
+
+		#[cfg(feature = "log")]
+		fn run() {
+			log_call!();
+		}
+		"#,
+		&opts(),
+	), @r#"
+	# Assert mode
+	[no-macro-use] /main.rs:3: `#[macro_use]` on `mod macros`
+	HINT: import its macros with `use crate::..;` where they're used, in other files too
+
+	# Format mode
+	#[cfg(feature = "log")]
+	use crate::log_call;
+	/// Logging helpers
+	#[cfg(feature = "log")]
+	mod macros {
+		#[macro_export]
+		macro_rules! log_call {
+			() => {};
+		}
+	}
+
+	#[cfg(feature = "log")]
+	fn run() {
+		log_call!();
+	}
+	"#);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn extern_crates_and_unexported_macros_are_reported() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		#[macro_use]
+		extern crate lazy_static;
+
+		#[macro_use]
+		mod macros {
+			macro_rules! square {
+				($x:expr) => {
+					$x * $x
+				};
+			}
+		}
+		"#,
+		&opts(),
+	), @"
+	[no-macro-use] /main.rs:1: `#[macro_use]` on `extern crate lazy_static`
+	HINT: import its macros with `use lazy_static::..;` where they're used
+	[no-macro-use] /main.rs:4: `#[macro_use]` on `mod macros`
+	HINT: export `macros`'s macros with `#[macro_export]` and import them with `use crate::..;` where they're used
+	");
+}
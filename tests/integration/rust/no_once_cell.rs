@@ -0,0 +1,94 @@
+use codestyle::rust_checks::RustCheckOptions;
+
+use crate::utils::{assert_check_passing, opts_for, test_case, test_case_assert_only};
+
+fn opts() -> RustCheckOptions {
+	opts_for("no_once_cell")
+}
+
+// === Passing cases ===
+
+#[test]
+fn std_cells_pass() {
+	assert_check_passing(
+		r#"
+		use std::sync::{LazyLock, OnceLock};
+
+		static CONFIG: LazyLock<Config> = LazyLock::new(Config::load);
+		static HANDLE: OnceLock<Handle> = OnceLock::new();
+
+		fn once_cell_count() -> usize {
+			0
+		}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases ===
+
+#[test]
+fn imports_and_paths_renamed() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		use once_cell::sync::{Lazy, OnceCell};
+
+		static CONFIG: Lazy<Config> = Lazy::new(Config::load);
+		static HANDLE: OnceCell<Handle> = OnceCell::new();
+
+		fn first(cell: &once_cell::unsync::OnceCell<u32>) -> Option<&u32> {
+			cell.get()
+		}
+		"#,
+		&opts(),
+	), @"
+	# Assert mode
+	[no-once-cell] /main.rs:1: `once_cell` import of what std has
+	HINT: import `LazyLock`/`OnceLock` from `std::sync`, or `LazyCell`/`OnceCell` from `std::cell`
+	[no-once-cell] /main.rs:3: `once_cell::sync::Lazy` is in std
+	HINT: use `std::sync::LazyLock`
+	[no-once-cell] /main.rs:3: `once_cell::sync::Lazy` is in std
+	HINT: use `std::sync::LazyLock`
+	[no-once-cell] /main.rs:4: `once_cell::sync::OnceCell` is in std
+	HINT: use `std::sync::OnceLock`
+	[no-once-cell] /main.rs:4: `once_cell::sync::OnceCell` is in std
+	HINT: use `std::sync::OnceLock`
+	[no-once-cell] /main.rs:6: `once_cell::unsync::OnceCell` is in std
+	HINT: use `std::cell::OnceCell`
+
+	# Format mode
+	use std::sync::{LazyLock, OnceLock};
+
+	static CONFIG: LazyLock<Config> = LazyLock::new(Config::load);
+	static HANDLE: OnceLock<Handle> = OnceLock::new();
+
+	fn first(cell: &std::cell::OnceCell<u32>) -> Option<&u32> {
+		cell.get()
+	}
+	");
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn get_or_try_init_keeps_once_cell() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		use once_cell::sync::OnceCell;
+
+		static TOKEN: OnceCell<String> = OnceCell::new();
+
+		fn token() -> Result<&'static String> {
+			TOKEN.get_or_try_init(fetch_token)
+		}
+		"#,
+		&opts(),
+	), @"
+	[no-once-cell] /main.rs:1: `once_cell` import of what std has
+	HINT: import `LazyLock`/`OnceLock` from `std::sync`, or `LazyCell`/`OnceCell` from `std::cell`
+	[no-once-cell] /main.rs:3: `once_cell::sync::OnceCell` is in std
+	HINT: use `std::sync::OnceLock`
+	[no-once-cell] /main.rs:3: `once_cell::sync::OnceCell` is in std
+	HINT: use `std::sync::OnceLock`
+	");
+}
@@ -0,0 +1,80 @@
+use codestyle::rust_checks::RustCheckOptions;
+
+use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
+
+fn opts() -> RustCheckOptions {
+	opts_for("no_other_runtimes")
+}
+
+// === Passing cases ===
+
+#[test]
+fn tokio_and_lookalike_names_pass() {
+	assert_check_passing(
+		r#"
+		use tokio::{task, time::sleep};
+
+		#[tokio::main]
+		async fn main() {
+			let smol = Config::smol();
+			task::spawn(serve(smol)).await.unwrap();
+			sleep(smol.backoff).await;
+		}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn other_runtimes() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		use async_std::task;
+		use smol::{Timer, io};
+
+		#[async_std::main]
+		async fn main() {
+			task::sleep(BACKOFF).await;
+			smol::block_on(poll());
+			let executor = async_executor::Executor::new();
+		}
+		"#,
+		&opts(),
+	), @"
+	[no-other-runtimes] /main.rs:1: `async_std` is an async runtime other than tokio
+	HINT: use tokio's counterpart; futures tied to one runtime hang or panic on another
+	[no-other-runtimes] /main.rs:2: `smol` is an async runtime other than tokio
+	HINT: use tokio's counterpart; futures tied to one runtime hang or panic on another
+	[no-other-runtimes] /main.rs:4: `async_std` is an async runtime other than tokio
+	HINT: use tokio's counterpart; futures tied to one runtime hang or panic on another
+	[no-other-runtimes] /main.rs:7: `smol` is an async runtime other than tokio
+	HINT: use tokio's counterpart; futures tied to one runtime hang or panic on another
+	[no-other-runtimes] /main.rs:8: `async_executor`, part of smol, is an async runtime other than tokio
+	HINT: use tokio's counterpart; futures tied to one runtime hang or panic on another
+	");
+}
+
+#[test]
+fn configured_crates() {
+	let opts = RustCheckOptions {
+		no_other_runtimes_crates: vec!["glommio".to_string()],
+		..opts()
+	};
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		extern crate glommio;
+
+		fn main() {
+			glommio::LocalExecutorBuilder::default().spawn(serve).unwrap();
+		}
+		"#,
+		&opts,
+	), @"
+	[no-other-runtimes] /main.rs:1: `glommio` is an async runtime other than tokio
+	HINT: use tokio's counterpart; futures tied to one runtime hang or panic on another
+	[no-other-runtimes] /main.rs:4: `glommio` is an async runtime other than tokio
+	HINT: use tokio's counterpart; futures tied to one runtime hang or panic on another
+	");
+}
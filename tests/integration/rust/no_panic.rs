@@ -0,0 +1,124 @@
+use codestyle::rust_checks::RustCheckOptions;
+
+use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
+
+fn opts() -> RustCheckOptions {
+	opts_for("no_panic")
+}
+
+// === Passing cases ===
+
+#[test]
+fn binaries_and_tests_pass() {
+	assert_check_passing(
+		r#"
+		//- /src/lib.rs
+		pub fn parse(s: &str) -> Option<u8> {
+			s.parse().ok()
+		}
+
+		#[cfg(test)]
+		mod tests {
+			#[test]
+			fn rejects_garbage() {
+				if super::parse("x").is_some() {
+					panic!("parsed garbage");
+				}
+			}
+		}
+		//- /src/main.rs
+		fn main() {
+			todo!()
+		}
+		//- /src/bin/tool.rs
+		fn main() {
+			unimplemented!()
+		}
+		//- /tests/integration.rs
+		fn helper() {
+			panic!("fixture missing");
+		}
+		"#,
+		&opts(),
+	);
+}
+
+#[test]
+fn binary_only_crate_passes() {
+	assert_check_passing(
+		r#"
+		//- /src/main.rs
+		mod cli;
+		fn main() {}
+		//- /src/cli.rs
+		pub fn run() {
+			panic!("bad args");
+		}
+		"#,
+		&opts(),
+	);
+}
+
+#[test]
+fn allowed_macros_pass() {
+	let opts = RustCheckOptions {
+		no_panic_allow: vec!["todo".to_string()],
+		..opts()
+	};
+	assert_check_passing(
+		r#"
+		//- /src/lib.rs
+		pub fn scaffold() {
+			todo!()
+		}
+		"#,
+		&opts,
+	);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn panicking_macros_in_library() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		//- /src/lib.rs
+		pub fn parse(s: &str) -> u8 {
+			if s.is_empty() {
+				std::panic!("empty");
+			}
+			match s {
+				"a" => todo!(),
+				"b" => unimplemented!("b"),
+				_ => unreachable!(),
+			}
+		}
+		"#,
+		&opts(),
+	), @"
+	[no-panic] /src/lib.rs:3: `panic!` in library code
+	HINT: return an error instead, so the caller decides whether it's fatal
+	[no-panic] /src/lib.rs:6: `todo!` in library code
+	HINT: return an error instead, so the caller decides whether it's fatal
+	[no-panic] /src/lib.rs:7: `unimplemented!` in library code
+	HINT: return an error instead, so the caller decides whether it's fatal
+	");
+}
+
+#[test]
+fn library_modules_are_checked() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		//- /src/lib.rs
+		pub mod config;
+		//- /src/config.rs
+		pub fn load() {
+			panic!("no config");
+		}
+		"#,
+		&opts(),
+	), @"
+	[no-panic] /src/config.rs:2: `panic!` in library code
+	HINT: return an error instead, so the caller decides whether it's fatal
+	");
+}
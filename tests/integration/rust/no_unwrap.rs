@@ -0,0 +1,132 @@
+use codestyle::rust_checks::RustCheckOptions;
+
+use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
+
+fn opts() -> RustCheckOptions {
+	opts_for("no_unwrap")
+}
+
+// === Passing cases ===
+
+#[test]
+fn justified_unwraps_pass() {
+	assert_check_passing(
+		r#"
+		fn parse(s: &str) -> u16 {
+			let digits = s.trim();
+			//SAFE: callers only pass strings matched by `^\d{1,4}$`
+			let n: u16 = digits.parse().unwrap();
+			let m: u16 = digits.parse().expect("matched digits"); // SAFE: same as above
+			n + m
+		}
+		"#,
+		&opts(),
+	);
+}
+
+#[test]
+fn test_code_passes() {
+	assert_check_passing(
+		r#"
+		//- /main.rs
+		fn main() {}
+
+		#[cfg(test)]
+		mod tests {
+			#[test]
+			fn parses() {
+				assert_eq!("1".parse::<u8>().unwrap(), 1);
+			}
+		}
+
+		#[tokio::test]
+		async fn fetches() {
+			fetch().await.expect("fetch works");
+		}
+		//- /tests/integration.rs
+		fn helper() -> u8 {
+			"1".parse().unwrap()
+		}
+		"#,
+		&opts(),
+	);
+}
+
+#[test]
+fn similarly_named_methods_pass() {
+	assert_check_passing(
+		r#"
+		fn main() {
+			let a = x.unwrap_or(0); //IGNORED_ERROR: not this rule's business
+			let b = parser.expect(Token::Comma, "a comma");
+			let c = state.unwrap(true);
+		}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn unjustified_unwrap_and_expect() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		fn main() {
+			let port: u16 = std::env::var("PORT").unwrap().parse().expect("a port");
+			// a comment, but not the marker
+			let host = std::env::var("HOST").unwrap();
+		}
+		"#,
+		&opts(),
+	), @"
+	[no-unwrap] /main.rs:2: `.unwrap()` without `//SAFE` comment
+	HINT: propagate the error, or explain why it can't happen or why panicking is the right response
+	[no-unwrap] /main.rs:2: `.expect()` without `//SAFE` comment
+	HINT: propagate the error, or explain why it can't happen or why panicking is the right response
+	[no-unwrap] /main.rs:4: `.unwrap()` without `//SAFE` comment
+	HINT: propagate the error, or explain why it can't happen or why panicking is the right response
+	");
+}
+
+#[test]
+fn marker_is_configurable() {
+	let opts = RustCheckOptions {
+		unwrap_marker: "INVARIANT".to_string(),
+		..opts()
+	};
+	assert_check_passing(
+		r#"
+		fn main() {
+			//INVARIANT: set by the launcher
+			let home = std::env::var("HOME").unwrap();
+		}
+		"#,
+		&opts,
+	);
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		fn main() {
+			//SAFE: set by the launcher
+			let home = std::env::var("HOME").unwrap();
+		}
+		"#,
+		&opts,
+	), @"
+	[no-unwrap] /main.rs:3: `.unwrap()` without `//INVARIANT` comment
+	HINT: propagate the error, or explain why it can't happen or why panicking is the right response
+	");
+}
+
+#[test]
+fn skip_marker_on_fn() {
+	assert_check_passing(
+		r#"
+		//#[codestyle::skip(no-unwrap)]
+		fn main() {
+			let home = std::env::var("HOME").unwrap();
+		}
+		"#,
+		&opts(),
+	);
+}
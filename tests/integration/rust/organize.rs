@@ -0,0 +1,180 @@
+use crate::utils::organize_case;
+
+#[test]
+fn already_organized_is_untouched() {
+	insta::assert_snapshot!(organize_case(
+		r#"
+		//! Crate docs.
+
+		mod utils;
+
+		use std::path::Path;
+
+		const LIMIT: usize = 3;
+
+		pub struct Foo;
+
+		impl Foo {
+			fn new() -> Self { Self }
+		}
+
+		fn helper(_: &Path) {}
+		"#,
+	), @"
+	//! Crate docs.
+
+	mod utils;
+
+	use std::path::Path;
+
+	const LIMIT: usize = 3;
+
+	pub struct Foo;
+
+	impl Foo {
+		fn new() -> Self { Self }
+	}
+
+	fn helper(_: &Path) {}
+	");
+}
+
+#[test]
+fn full_layout() {
+	insta::assert_snapshot!(organize_case(
+		r#"
+		#![allow(dead_code)]
+
+		fn main() {}
+
+		use crate::utils::helper;
+		mod utils;
+		use std::fs;
+
+		impl Display for Foo {
+			fn fmt(&self, f: &mut Formatter) -> fmt::Result { Ok(()) }
+		}
+
+		/// Docs for Foo
+		#[derive(Debug)]
+		pub struct Foo;
+		use clap::Parser;
+
+		impl Foo {
+			fn one() {}
+		}
+
+		const MAX: usize = 10;
+
+		// Explains `Bar`
+		struct Bar;
+
+		impl Foo {
+			fn two() {}
+		}
+
+		pub fn public() {}
+		const MIN: usize = 1;
+
+		macro_rules! noop {
+			() => {};
+		}
+
+		impl External {}
+
+		#[cfg(test)]
+		mod tests {}
+		"#,
+	), @"
+	#![allow(dead_code)]
+
+	mod utils;
+
+	use std::fs;
+
+	use clap::Parser;
+
+	use crate::utils::helper;
+
+	macro_rules! noop {
+		() => {};
+	}
+
+	const MAX: usize = 10;
+	const MIN: usize = 1;
+
+	/// Docs for Foo
+	#[derive(Debug)]
+	pub struct Foo;
+
+	impl Foo {
+		fn one() {}
+		fn two() {}
+	}
+
+	impl Display for Foo {
+		fn fmt(&self, f: &mut Formatter) -> fmt::Result { Ok(()) }
+	}
+
+	pub fn public() {}
+
+	fn main() {}
+
+	// Explains `Bar`
+	struct Bar;
+
+	impl External {}
+
+	#[cfg(test)]
+	mod tests {}
+	");
+}
+
+#[test]
+fn fold_markers_stay_with_their_impl() {
+	insta::assert_snapshot!(organize_case(
+		r#"
+		fn private() {}
+
+		pub struct Foo;
+		impl Foo /*{{{1*/ {
+			fn one() {}
+		}
+		//,}}}1
+
+		pub fn public() {}
+		"#,
+	), @"
+	pub struct Foo;
+
+	impl Foo /*{{{1*/ {
+		fn one() {}
+	}
+	//,}}}1
+
+	pub fn public() {}
+
+	fn private() {}
+	");
+}
+
+#[test]
+fn skip_marker_leaves_file_untouched() {
+	insta::assert_snapshot!(organize_case(
+		r#"
+		pub fn public() {}
+
+		//#[codestyle::skip(pub-first)]
+		fn private() {}
+
+		pub fn another() {}
+		"#,
+	), @"
+	pub fn public() {}
+
+	//#[codestyle::skip(pub-first)]
+	fn private() {}
+
+	pub fn another() {}
+	");
+}
@@ -0,0 +1,145 @@
+use codestyle::rust_checks::{check_file, pretty, rustfix::to_diagnostics, rustfmt::RustfmtConfig, sarif::to_sarif, violations_json};
+use v_fixtures::Fixture;
+
+use crate::utils::{collect_rust_files, opts_for};
+
+#[test]
+fn violations_serialize_with_fixability() {
+	let temp = Fixture::parse(
+		r#"
+		//- /main.rs
+		#[test]
+		fn test_parses() {}
+		"#,
+	)
+	.write_to_tempdir();
+	let files = collect_rust_files(&temp.root);
+	let violations = check_file(&files[0], &opts_for("test_fn_prefix"), &RustfmtConfig::default());
+	let json = violations_json(&violations).replace(&temp.root.display().to_string(), "");
+	insta::assert_snapshot!(json, @r#"
+	[
+	  {
+	    "rule": "test-fn-prefix",
+	    "file": "/main.rs",
+	    "line": 2,
+	    "column": 3,
+	    "end_line": 2,
+	    "end_column": 14,
+	    "message": "test function `test_parses` has redundant `test_` prefix",
+	    "severity": "error",
+	    "fixable": true,
+	    "labels": [],
+	    "notes": [],
+	    "suggestions": []
+	  }
+	]
+	"#);
+}
+
+#[test]
+fn no_violations_is_empty_array() {
+	assert_eq!(violations_json(&[]), "[]");
+}
+
+#[test]
+fn sarif_results_point_at_registered_rules() {
+	let temp = Fixture::parse(
+		r#"
+		//- /main.rs
+		use chrono::Utc;
+
+		#[test]
+		fn test_parses() {}
+		"#,
+	)
+	.write_to_tempdir();
+	let files = collect_rust_files(&temp.root);
+	let violations = check_file(&files[0], &opts_for("no_chrono"), &RustfmtConfig::default());
+	let sarif: serde_json::Value = serde_json::from_str(&to_sarif(&violations)).unwrap();
+
+	let run = &sarif["runs"][0];
+	assert_eq!(sarif["version"], "2.1.0");
+	assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "no-chrono");
+	assert_eq!(run["tool"]["driver"]["rules"][0]["shortDescription"]["text"], "Disallow the chrono crate (use jiff instead)");
+
+	let result = &run["results"][0];
+	assert_eq!(result["ruleIndex"], 0);
+	assert_eq!(result["level"], "error");
+	let region = &result["locations"][0]["physicalLocation"]["region"];
+	assert_eq!((region["startLine"].as_u64(), region["startColumn"].as_u64()), (Some(1), Some(5)));
+}
+
+#[test]
+fn pretty_underlines_source_and_renders_hints() {
+	let source = "fn run() {\n\tloop {\n\t\ttick();\n\t}\n}\n";
+	let temp = Fixture::parse(&format!("//- /main.rs\n{source}")).write_to_tempdir();
+	let files = collect_rust_files(&temp.root);
+	let violations = check_file(&files[0], &opts_for("loops"), &RustfmtConfig::default());
+	let mut rendered = pretty::render(&violations[0], Some(source));
+	rendered = rendered.replace(&temp.root.display().to_string(), "");
+	insta::assert_snapshot!(rendered, @r"
+	error[loop-comment]: Endless loop without `//LOOP` comment
+	 --> /main.rs:2:2
+	  |
+	2 |     loop {
+	  |     ^^^^
+	  |
+	  = help: try to rewrite the loop with `while let` or justify why a bound can't be enforced
+	");
+}
+
+#[test]
+fn pretty_marks_labels_and_primary_extent() {
+	let source = "struct Foo;\n\n\nimpl Foo {}\n";
+	let temp = Fixture::parse(&format!("//- /main.rs\n{source}")).write_to_tempdir();
+	let files = collect_rust_files(&temp.root);
+	let violations = check_file(&files[0], &opts_for("impl_follows_type"), &RustfmtConfig::default());
+	let rendered = pretty::render(&violations[0], Some(source)).replace(&temp.root.display().to_string(), "");
+	insta::assert_snapshot!(rendered, @r"
+	error[impl-follows-type]: `impl Foo` should follow type definition (line 1), but has 2 blank line(s)
+	 --> /main.rs:4:1
+	  |
+	1 | struct Foo;
+	  |        --- `Foo` defined here
+	4 | impl Foo {}
+	  | ^^^^^^^^
+	");
+}
+
+#[test]
+fn pretty_without_source_keeps_location() {
+	let temp = Fixture::parse(
+		r#"
+		//- /main.rs
+		use chrono::Utc;
+		"#,
+	)
+	.write_to_tempdir();
+	let files = collect_rust_files(&temp.root);
+	let violations = check_file(&files[0], &opts_for("no_chrono"), &RustfmtConfig::default());
+	let rendered = pretty::render(&violations[0], None).replace(&temp.root.display().to_string(), "");
+	assert!(rendered.ends_with("--> /main.rs:1:5\n"), "{rendered}");
+}
+
+#[test]
+fn rustfix_diagnostics_carry_fixes_as_suggestions() {
+	let source = "#[test]\nfn test_parses() {}\n";
+	let temp = Fixture::parse(&format!("//- /main.rs\n{source}")).write_to_tempdir();
+	let files = collect_rust_files(&temp.root);
+	let violations = check_file(&files[0], &opts_for("test_fn_prefix"), &RustfmtConfig::default());
+	let output = to_diagnostics(&violations);
+	let diagnostics: Vec<serde_json::Value> = output.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+	assert_eq!(diagnostics.len(), 1);
+
+	let diagnostic = &diagnostics[0];
+	assert_eq!((&diagnostic["code"]["code"], &diagnostic["level"]), (&"test-fn-prefix".into(), &"error".into()));
+	let primary = &diagnostic["spans"][0];
+	assert_eq!((primary["line_start"].as_u64(), primary["column_start"].as_u64()), (Some(2), Some(4)));
+	assert_eq!(primary["byte_start"], source.find("test_parses").unwrap());
+
+	let suggestion = &diagnostic["children"][0]["spans"][0];
+	assert_eq!(suggestion["suggestion_applicability"], "MachineApplicable");
+	let (start, end) = (suggestion["byte_start"].as_u64().unwrap() as usize, suggestion["byte_end"].as_u64().unwrap() as usize);
+	let fixed = format!("{}{}{}", &source[..start], suggestion["suggested_replacement"].as_str().unwrap(), &source[end..]);
+	assert_eq!(fixed, "#[test]\nfn parses() {}\n");
+}
@@ -0,0 +1,100 @@
+#![cfg(unix)]
+
+use std::{fs, os::unix::fs::PermissionsExt, path::Path, slice};
+
+use codestyle::{
+	Linter,
+	rust_checks::{self, AssertOptions, RustCheckOptions, Severity},
+};
+use v_fixtures::Fixture;
+
+/// Flags `println!` as a warning, fixing it to `eprintln!` when it starts the file; its fix for `forever` never settles
+const PLUGIN: &str = r#"#!/bin/sh
+while read -r line; do
+	case "$line" in
+		*'"content":"println'*) echo '{"violations": [{"rule": "acme-no-println", "line": 1, "column": 0, "message": "print to stderr", "severity": "warn", "fix": {"start_byte": 0, "end_byte": 0, "replacement": "e"}}]}' ;;
+		*forever*) echo '{"violations": [{"rule": "acme-never-satisfied", "line": 1, "column": 0, "message": "indent more", "fix": {"start_byte": 0, "end_byte": 0, "replacement": " "}}]}' ;;
+		*garbage*) echo 'not json' ;;
+		*) echo '{"violations": []}' ;;
+	esac
+done
+"#;
+
+fn write_plugin(dir: &Path) -> String {
+	let path = dir.join("acme-rules");
+	fs::write(&path, PLUGIN).unwrap();
+	fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+	path.display().to_string()
+}
+
+fn with_plugins(plugins: Vec<String>) -> RustCheckOptions {
+	RustCheckOptions {
+		plugins,
+		..RustCheckOptions::default()
+	}
+}
+
+#[test]
+fn plugin_violations_are_reported_and_fixed() {
+	let dir = tempfile::tempdir().unwrap();
+	let linter = Linter::new(with_plugins(vec![write_plugin(dir.path())]));
+
+	let report = linter.check_source("main.rs", "println!(\"hi\");\n").unwrap();
+	assert_eq!(report.violations.len(), 1);
+	assert_eq!((report.violations[0].rule, report.violations[0].severity), ("acme-no-println", Severity::Warn));
+	assert!(!report.has_errors());
+	assert!(linter.check_source("main.rs", "fn main() {}\n").unwrap().violations.is_empty());
+
+	let fixed = linter.apply_fixes("main.rs", "println!(\"hi\");\n").unwrap();
+	assert_eq!(fixed.source, "eprintln!(\"hi\");\n");
+}
+
+#[test]
+fn fixes_that_never_settle_stop() {
+	let dir = tempfile::tempdir().unwrap();
+	let linter = Linter::new(with_plugins(vec![write_plugin(dir.path())]));
+	let fixed = linter.apply_fixes("main.rs", "// forever\n").unwrap();
+	// The last of the 100 passes only collects what's left
+	assert_eq!(fixed.fixed_count, 99);
+	assert!(fixed.source.ends_with("// forever\n"));
+}
+
+#[test]
+fn broken_plugins_are_skipped() {
+	let dir = tempfile::tempdir().unwrap();
+	let missing = Linter::new(with_plugins(vec![dir.path().join("no-such-plugin").display().to_string()]));
+	assert!(missing.check_source("main.rs", "println!(\"hi\");\n").unwrap().violations.is_empty());
+
+	// The same command is shared across linters, so this needs a plugin of its own
+	let plugin = dir.path().join("garbage-rules");
+	fs::copy(write_plugin(dir.path()), &plugin).unwrap();
+	let garbage = Linter::new(with_plugins(vec![plugin.display().to_string()]));
+	assert!(garbage.check_source("main.rs", "// garbage\n").unwrap().violations.is_empty());
+	// Skipped from then on, even for input it would have answered
+	assert!(garbage.check_source("main.rs", "println!(\"hi\");\n").unwrap().violations.is_empty());
+}
+
+#[test]
+fn changed_plugins_invalidate_cached_results() {
+	let temp = Fixture::parse(
+		r#"
+		//- /main.rs
+		fn main() {}
+		"#,
+	)
+	.write_to_tempdir();
+	let dir = tempfile::tempdir().unwrap();
+	let plugin = dir.path().join("strict-rules");
+	let write = |answer: &str| {
+		fs::write(&plugin, format!("#!/bin/sh\nwhile read -r line; do echo '{answer}'; done\n")).unwrap();
+		fs::set_permissions(&plugin, fs::Permissions::from_mode(0o755)).unwrap();
+	};
+	let opts = with_plugins(vec![plugin.display().to_string()]);
+	let assert = || rust_checks::run_assert(slice::from_ref(&temp.root), &opts, &AssertOptions::default());
+
+	write(r#"{"violations": [{"rule": "acme-strict", "line": 1, "column": 0, "message": "too lax"}]}"#);
+	assert_eq!(assert(), 1);
+	// Same command and source, but the plugin itself changed, so the cached result can't be reused
+	write(r#"{"violations": []}"#);
+	assert_eq!(assert(), 0);
+}
@@ -0,0 +1,89 @@
+use crate::utils::{assert_check_passing, opts_for, test_case, test_case_assert_only};
+
+fn opts() -> codestyle::rust_checks::RustCheckOptions {
+	opts_for("pub_fields_first")
+}
+
+// === Passing cases ===
+
+#[test]
+fn ordered_and_order_sensitive_structs_pass() {
+	assert_check_passing(
+		r#"
+		pub struct Client {
+			pub timeout: u64,
+			pub(crate) pool: Pool,
+			retries: u32,
+		}
+
+		#[repr(C)]
+		pub struct Header {
+			len: u32,
+			pub kind: u32,
+		}
+
+		#[derive(PartialEq, PartialOrd)]
+		pub struct Version {
+			major: u32,
+			pub minor: u32,
+		}
+
+		pub struct Pair(u8, pub u8);
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases ===
+
+#[test]
+fn pub_fields_are_moved_up() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		pub struct Client {
+			/// Shared connections.
+			pool: Pool,
+			// Seconds, not millis
+			pub timeout: u64,
+			retries: u32,
+			/// Sent with every request.
+			pub user_agent: String
+		}
+		"#,
+		&opts(),
+	), @"
+	# Assert mode
+	[pub-fields-first] /main.rs:5: `pub` field `timeout` of `Client` comes after private ones
+	HINT: move the `pub` fields above the private ones
+
+	# Format mode
+	pub struct Client {
+		// Seconds, not millis
+		pub timeout: u64,
+		/// Sent with every request.
+		pub user_agent: String,
+		/// Shared connections.
+		pool: Pool,
+		retries: u32,
+	}
+	");
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn serialized_structs_are_not_reordered() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		#[derive(serde::Serialize)]
+		pub struct Event {
+			id: u64,
+			pub kind: Kind,
+		}
+		"#,
+		&opts(),
+	), @"
+	[pub-fields-first] /main.rs:4: `pub` field `kind` of `Event` comes after private ones
+	HINT: move the `pub` fields above the private ones
+	");
+}
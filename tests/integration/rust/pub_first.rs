@@ -995,3 +995,21 @@ fn mod_between_clap_items_not_scrambled() {
 	fn main() {}
 	");
 }
+
+#[test]
+fn non_ascii_items_move_whole() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		fn grüße() -> &'static str { "¡hola!" }
+		pub fn öffentlich() -> &'static str { "ü" }
+		"#,
+		&opts(),
+	), @r#"
+	# Assert mode
+	[pub-first] /main.rs:2: public item should come before private items
+
+	# Format mode
+	pub fn öffentlich() -> &'static str { "ü" }
+	fn grüße() -> &'static str { "¡hola!" }
+	"#);
+}
@@ -0,0 +1,103 @@
+use codestyle::rust_checks::RustCheckOptions;
+
+use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
+
+fn opts() -> RustCheckOptions {
+	opts_for("pub_use_location")
+}
+
+// === Passing cases ===
+
+#[test]
+fn roots_and_preludes_pass() {
+	assert_check_passing(
+		r#"
+		//- /src/lib.rs
+		pub use client::Client;
+
+		mod client;
+		pub mod prelude;
+
+		pub mod extra {
+			pub mod prelude {
+				pub use crate::client::Client;
+			}
+		}
+
+		//- /src/client.rs
+		pub(crate) use retry::Backoff;
+
+		pub struct Client;
+
+		//- /src/server/mod.rs
+		pub use crate::client::Client;
+
+		//- /src/prelude.rs
+		pub use crate::client::Client;
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn re_exports_elsewhere_are_flagged() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		//- /src/client.rs
+		pub use retry::Backoff;
+
+		pub struct Client;
+
+		fn connect() {
+			pub use std::net::TcpStream;
+		}
+		"#,
+		&opts(),
+	), @"
+	[pub-use-location] /src/client.rs:1: `pub use` outside `lib.rs`, `mod.rs` or a prelude module
+	HINT: move it to the module's root or a prelude, or narrow it to `pub(crate) use`
+	[pub-use-location] /src/client.rs:6: `pub use` outside `lib.rs`, `mod.rs` or a prelude module
+	HINT: move it to the module's root or a prelude, or narrow it to `pub(crate) use`
+	");
+}
+
+#[test]
+fn inline_modules_of_a_root_are_flagged() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		//- /src/lib.rs
+		mod client;
+
+		mod inline {
+			pub use crate::client::Client;
+		}
+		"#,
+		&opts(),
+	), @"
+	[pub-use-location] /src/lib.rs:4: `pub use` outside `lib.rs`, `mod.rs` or a prelude module
+	HINT: move it to the module's root or a prelude, or narrow it to `pub(crate) use`
+	");
+}
+
+#[test]
+fn configured_modules_replace_prelude() {
+	let opts = RustCheckOptions {
+		pub_use_modules: vec!["exports".to_string()],
+		..opts()
+	};
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		//- /src/exports.rs
+		pub use crate::client::Client;
+
+		//- /src/prelude.rs
+		pub use crate::client::Client;
+		"#,
+		&opts,
+	), @"
+	[pub-use-location] /src/prelude.rs:1: `pub use` outside `lib.rs`, `mod.rs` or a prelude module
+	HINT: move it to the module's root or a prelude, or narrow it to `pub(crate) use`
+	");
+}
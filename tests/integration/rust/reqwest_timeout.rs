@@ -0,0 +1,57 @@
+use codestyle::rust_checks::RustCheckOptions;
+
+use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
+
+fn opts() -> RustCheckOptions {
+	opts_for("reqwest_timeout")
+}
+
+// === Passing cases ===
+
+#[test]
+fn clients_and_requests_with_timeouts_pass() {
+	assert_check_passing(
+		r#"
+		use reqwest::Client;
+
+		fn client() -> reqwest::Result<Client> {
+			Client::builder().connect_timeout(CONNECT).user_agent(AGENT).build()
+		}
+
+		async fn fetch(url: &str) -> Result<String> {
+			let body = Client::new().get(url).timeout(TIMEOUT).send().await?.text().await?;
+			let blocking = reqwest::blocking::Client::new();
+			Ok(body)
+		}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn clients_without_timeouts() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		use reqwest::{Client, ClientBuilder};
+
+		async fn fetch(url: &str) -> Result<String> {
+			let client = Client::new();
+			let pooled = Client::builder().pool_max_idle_per_host(4).build()?;
+			let custom = ClientBuilder::new().user_agent(AGENT).build()?;
+			Ok(reqwest::get(url).await?.text().await?)
+		}
+		"#,
+		&opts(),
+	), @"
+	[reqwest-timeout] /main.rs:4: `reqwest::Client::new` sets no timeout, so a request to a server that stops answering never returns
+	HINT: build it with `Client::builder().timeout(..)`, or call `.timeout(..)` on each request
+	[reqwest-timeout] /main.rs:5: `reqwest::Client::builder` sets no timeout, so a request to a server that stops answering never returns
+	HINT: call `.timeout(..)` or `.connect_timeout(..)` on the builder
+	[reqwest-timeout] /main.rs:6: `reqwest::ClientBuilder::new` sets no timeout, so a request to a server that stops answering never returns
+	HINT: call `.timeout(..)` or `.connect_timeout(..)` on the builder
+	[reqwest-timeout] /main.rs:7: `reqwest::get` sets no timeout, so a request to a server that stops answering never returns
+	HINT: send the request from a `Client` built with `.timeout(..)`
+	");
+}
@@ -0,0 +1,54 @@
+use std::collections::HashSet;
+
+use codestyle::rust_checks::{
+	RustCheckOptions, Severity,
+	rules::{RULES, SOURCE_RULES, find},
+};
+
+#[test]
+fn ids_are_unique() {
+	let mut seen = HashSet::new();
+	for rule in RULES {
+		assert!(seen.insert(rule.id), "duplicate rule id `{}`", rule.id);
+	}
+}
+
+#[test]
+fn options_name_real_fields() {
+	let strict = RustCheckOptions::strict();
+	for rule in RULES {
+		assert_eq!(strict.severity(rule.option), Severity::Error, "`{}` points at unknown option `{}`", rule.id, rule.option);
+	}
+}
+
+#[test]
+fn every_rule_is_explained() {
+	for rule in RULES {
+		let fields = [rule.rationale, rule.bad, rule.good, rule.fix];
+		assert!(fields.iter().all(|f| !f.is_empty()), "`{}` is missing explain metadata", rule.id);
+	}
+	assert_eq!(find("no-tokio-spawn").map(|r| r.option), Some("no_tokio_spawn"));
+	assert!(find("no-such-rule").is_none());
+}
+
+#[test]
+fn violating_examples_contain_triggers() {
+	for rule in RULES {
+		assert!(rule.may_apply(rule.bad), "`{}` would skip its own violating example", rule.id);
+	}
+	assert!(!find("no-chrono").unwrap().may_apply("use jiff::Timestamp;"));
+}
+
+#[test]
+fn every_source_rule_is_registered() {
+	for rule in SOURCE_RULES {
+		let info = rule.info();
+		assert!(find(info.id).is_some_and(|found| found.option == info.option), "`{}` is missing from RULES", info.id);
+	}
+	let options: HashSet<_> = SOURCE_RULES.iter().map(|rule| rule.info().option).collect();
+	// `unused_skip` isn't a source rule: the runner reruns the others to find markers that suppress nothing, and
+	// `unused_pub` and `dead_files` look at whole crates
+	let source_options: HashSet<_> =
+		RULES.iter().map(|rule| rule.option).filter(|&option| !["cargo_dep_ordering", "unused_pub", "dead_files", "unused_skip"].contains(&option)).collect();
+	assert_eq!(options, source_options, "every option but cargo_dep_ordering, unused_pub, dead_files and unused_skip needs a source rule to run");
+}
@@ -0,0 +1,84 @@
+use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
+
+fn opts() -> codestyle::rust_checks::RustCheckOptions {
+	opts_for("safety_comment")
+}
+
+// === Passing cases ===
+
+#[test]
+fn commented_unsafe_passes() {
+	assert_check_passing(
+		r#"
+		fn bytes(v: &Vec<u8>) -> &[u8] {
+			// SAFETY: `ptr` and `len` come from `v`, which outlives the slice
+			let bytes = unsafe { std::slice::from_raw_parts(v.as_ptr(), v.len()) };
+			let first = unsafe { *v.as_ptr() }; //SAFETY: checked non-empty by the caller
+			bytes
+		}
+		"#,
+		&opts(),
+	);
+}
+
+#[test]
+fn comment_block_passes() {
+	assert_check_passing(
+		r#"
+		fn read(p: *const u8) -> u8 {
+			// SAFETY: `p` is non-null and aligned, see `Buffer::new`,
+			// and nothing writes to it while the buffer is borrowed.
+			unsafe { p.read() }
+		}
+		"#,
+		&opts(),
+	);
+}
+
+#[test]
+fn documented_unsafe_fn_passes() {
+	assert_check_passing(
+		r#"
+		/// Reads the first byte.
+		///
+		/// # Safety
+		/// `p` has to point to at least one initialized byte.
+		#[inline]
+		pub unsafe fn first(p: *const u8) -> u8 {
+			// SAFETY: upheld by the caller, see above
+			unsafe { p.read() }
+		}
+
+		// SAFETY: only called with pointers into a live `Vec`
+		unsafe fn second(p: *const u8) -> u8 {
+			// SAFETY: same as `first`
+			unsafe { p.add(1).read() }
+		}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn uncommented_unsafe() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		struct Buffer(*const u8);
+		impl Buffer {
+			/// Reads the first byte.
+			pub unsafe fn first(&self) -> u8 {
+				// reads the pointer
+				unsafe { self.0.read() }
+			}
+		}
+		"#,
+		&opts(),
+	), @"
+	[safety-comment] /main.rs:4: `unsafe fn first` without a `// SAFETY:` comment or `# Safety` doc section
+	HINT: document what callers have to uphold
+	[safety-comment] /main.rs:6: `unsafe` block without a `// SAFETY:` comment
+	HINT: explain why the invariants it relies on hold
+	");
+}
@@ -0,0 +1,108 @@
+use std::{collections::BTreeMap, slice};
+
+use codestyle::rust_checks::{self, AssertOptions, FailOn, FailPolicy, RustCheckOptions, Severity, check_file, overrides::Overrides, rustfmt::RustfmtConfig};
+use v_fixtures::Fixture;
+
+use crate::utils::{collect_rust_files, opts_for};
+
+const FIXTURE: &str = r#"
+	//- /main.rs
+	use chrono::Utc;
+	"#;
+
+fn no_chrono_at(severity: Severity) -> RustCheckOptions {
+	RustCheckOptions {
+		no_chrono: severity,
+		..opts_for("")
+	}
+}
+
+#[test]
+fn violations_carry_configured_severity() {
+	let temp = Fixture::parse(FIXTURE).write_to_tempdir();
+	let files = collect_rust_files(&temp.root);
+	let violations = check_file(&files[0], &no_chrono_at(Severity::Warn), &RustfmtConfig::default());
+	assert_eq!(violations.len(), 1);
+	assert_eq!(violations[0].severity, Severity::Warn);
+}
+
+#[test]
+fn only_errors_fail_assert() {
+	let temp = Fixture::parse(FIXTURE).write_to_tempdir();
+	assert_eq!(rust_checks::run_assert(slice::from_ref(&temp.root), &no_chrono_at(Severity::Error), &AssertOptions::default()), 1);
+	assert_eq!(rust_checks::run_assert(slice::from_ref(&temp.root), &no_chrono_at(Severity::Warn), &AssertOptions::default()), 0);
+	assert_eq!(rust_checks::run_assert(slice::from_ref(&temp.root), &no_chrono_at(Severity::Allow), &AssertOptions::default()), 0);
+}
+
+#[test]
+fn fail_on_sets_which_violations_fail_assert() {
+	let temp = Fixture::parse(FIXTURE).write_to_tempdir();
+	let assert = |severity, fail_on| {
+		let fail = FailPolicy { fail_on, ..Default::default() };
+		rust_checks::run_assert(slice::from_ref(&temp.root), &no_chrono_at(severity), &AssertOptions { fail, ..Default::default() })
+	};
+	assert_eq!(assert(Severity::Warn, FailOn::Warn), 1);
+	assert_eq!(assert(Severity::Allow, FailOn::Warn), 0);
+	assert_eq!(assert(Severity::Error, FailOn::Never), 0);
+}
+
+#[test]
+fn max_violations_tolerates_a_budget() {
+	let temp = Fixture::parse(
+		r#"
+		//- /main.rs
+		use chrono::Utc;
+		use chrono::Local;
+		"#,
+	)
+	.write_to_tempdir();
+	let assert = |max_violations| {
+		let fail = FailPolicy {
+			max_violations,
+			..Default::default()
+		};
+		rust_checks::run_assert(slice::from_ref(&temp.root), &no_chrono_at(Severity::Error), &AssertOptions { fail, ..Default::default() })
+	};
+	assert_eq!(assert(1), 1);
+	assert_eq!(assert(2), 0);
+}
+
+#[test]
+fn parses_levels_and_booleans() {
+	assert_eq!("warn".parse::<Severity>(), Ok(Severity::Warn));
+	assert_eq!("true".parse::<Severity>(), Ok(Severity::Error));
+	assert_eq!("false".parse::<Severity>(), Ok(Severity::Allow));
+	assert!("sometimes".parse::<Severity>().is_err());
+}
+
+#[test]
+fn path_overrides_apply_per_file() {
+	let temp = Fixture::parse(
+		r#"
+		//- /src/time.rs
+		use chrono::Utc;
+		//- /tests/time.rs
+		use chrono::Utc;
+		//- /tests/strict/time.rs
+		use chrono::Utc;
+		"#,
+	)
+	.write_to_tempdir();
+	let opts = RustCheckOptions {
+		overrides: Overrides::new(
+			&temp.root,
+			[
+				("tests/**".to_string(), BTreeMap::from([("no_chrono".to_string(), Severity::Allow)])),
+				("tests/strict/**".to_string(), BTreeMap::from([("no_chrono".to_string(), Severity::Warn)])),
+			],
+		),
+		..no_chrono_at(Severity::Error)
+	};
+	let mut found: Vec<_> = collect_rust_files(&temp.root)
+		.iter()
+		.flat_map(|file| check_file(file, &opts, &RustfmtConfig::default()))
+		.map(|v| (v.file.replace(&temp.root.display().to_string(), ""), v.severity))
+		.collect();
+	found.sort_by(|a, b| a.0.cmp(&b.0));
+	assert_eq!(found, [("/src/time.rs".to_string(), Severity::Error), ("/tests/strict/time.rs".to_string(), Severity::Warn)]);
+}
@@ -0,0 +1,55 @@
+use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
+
+fn opts() -> codestyle::rust_checks::RustCheckOptions {
+	opts_for("should_panic_expected")
+}
+
+// === Passing cases ===
+
+#[test]
+fn expected_messages_pass() {
+	assert_check_passing(
+		r#"
+		#[test]
+		#[should_panic(expected = "denominator is zero")]
+		fn rejects_zero() {
+			Ratio::new(1, 0);
+		}
+
+		#[test]
+		#[should_panic = "index out of bounds"]
+		fn rejects_overflow() {
+			Buffer::default().get(1);
+		}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases ===
+
+// The fix leaves `expected = ""` behind, which is flagged in turn, so only assert mode is snapshotted
+#[test]
+fn missing_and_empty_messages() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		#[test]
+		#[should_panic]
+		fn rejects_zero() {
+			Ratio::new(1, 0);
+		}
+
+		#[test]
+		#[should_panic(expected = "")]
+		fn rejects_overflow() {
+			Buffer::default().get(1);
+		}
+		"#,
+		&opts(),
+	), @r#"
+	[should-panic-expected] /main.rs:2: `#[should_panic]` without an expected message
+	HINT: add `expected = ".."` with the panic message the test is for
+	[should-panic-expected] /main.rs:8: `#[should_panic]` expects an empty message, which every panic has
+	HINT: fill in the panic message the test is for
+	"#);
+}
@@ -1,37 +1,105 @@
-//! Tests for codestyle::skip comment markers - skipping codestyle checks on annotated items.
+//! Tests for codestyle::skip markers - skipping codestyle checks on annotated items.
 //!
 //! Supported formats for skipping all rules:
+//! - `#[codestyle::skip]` (the attribute from `codestyle-attributes`)
 //! - `//#[codestyle::skip]`
 //! - `// #[codestyle::skip]`
 //! - `//@codestyle::skip`
 //! - `// @codestyle::skip`
 //!
 //! Supported formats for skipping specific rules:
+//! - `#[codestyle::skip(rule-name)]`
 //! - `//#[codestyle::skip(rule-name)]`
 //! - `// #[codestyle::skip(rule-name)]`
 //! - `//@codestyle::skip(rule-name)`
 //! - `// @codestyle::skip(rule-name)`
 
-use codestyle::rust_checks::RustCheckOptions;
+use codestyle::{
+	Linter,
+	rust_checks::{RustCheckOptions, Severity, mod_style::Layout, todo_issue},
+};
 
 use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
 
 fn all_opts() -> RustCheckOptions {
 	RustCheckOptions {
-		cargo_dep_ordering: false,
-		instrument: false,
-		loops: true,
-		join_split_impls: true,
-		impl_folds: false,
-		impl_follows_type: true,
-		embed_simple_vars: true,
-		insta_inline_snapshot: false,
-		no_chrono: true,
-		no_tokio_spawn: true,
-		use_bail: true,
-		test_fn_prefix: false,
-		pub_first: true,
-		ignored_error_comment: true,
+		cargo_dep_ordering: Severity::Allow,
+		instrument: Severity::Allow,
+		loops: Severity::Error,
+		safety_comment: Severity::Error,
+		join_split_impls: Severity::Error,
+		impl_folds: Severity::Allow,
+		impl_follows_type: Severity::Error,
+		trait_impls_follow: Severity::Allow,
+		embed_simple_vars: Severity::Error,
+		insta_inline_snapshot: Severity::Allow,
+		no_chrono: Severity::Error,
+		no_tokio_spawn: Severity::Error,
+		no_dbg: Severity::Error,
+		use_bail: Severity::Error,
+		test_fn_prefix: Severity::Allow,
+		test_in_cfg_test: Severity::Allow,
+		pub_first: Severity::Error,
+		tests_last: Severity::Error,
+		ignored_error_comment: Severity::Error,
+		no_unwrap: Severity::Allow,
+		expect_message: Severity::Allow,
+		no_panic: Severity::Allow,
+		allow_reason: Severity::Allow,
+		missing_docs: Severity::Allow,
+		derive_order: Severity::Allow,
+		nesting_depth: Severity::Allow,
+		fn_params: Severity::Allow,
+		bool_params: Severity::Allow,
+		no_as_cast: Severity::Allow,
+		let_else: Severity::Allow,
+		use_matches: Severity::Allow,
+		mod_style: Severity::Allow,
+		no_lock_across_await: Severity::Allow,
+		no_blocking_in_async: Severity::Allow,
+		no_block_on_in_async: Severity::Allow,
+		no_async_trait: Severity::Allow,
+		no_lazy_static: Severity::Allow,
+		no_once_cell: Severity::Allow,
+		no_other_runtimes: Severity::Allow,
+		reqwest_timeout: Severity::Allow,
+		use_thiserror: Severity::Allow,
+		derive_debug: Severity::Allow,
+		derive_default: Severity::Allow,
+		pub_fields_first: Severity::Allow,
+		mod_files: Severity::Allow,
+		impl_method_order: Severity::Allow,
+		no_get_prefix: Severity::Allow,
+		should_panic_expected: Severity::Allow,
+		no_assert: Severity::Allow,
+		todo_issue: Severity::Allow,
+		todo_comment: Severity::Allow,
+		commented_code: Severity::Allow,
+		no_extern_crate: Severity::Allow,
+		no_macro_use: Severity::Allow,
+		pub_use_location: Severity::Allow,
+		unused_pub: Severity::Allow,
+		dead_files: Severity::Allow,
+		skip_reason: Severity::Allow,
+		unused_skip: Severity::Allow,
+		plugins: Vec::new(),
+		custom_rules: Vec::new(),
+		overrides: Default::default(),
+		test_exempt: Vec::new(),
+		unwrap_marker: "SAFE".to_string(),
+		expect_message_min_len: 8,
+		no_panic_allow: Vec::new(),
+		no_assert_marker: String::new(),
+		todo_issue_pattern: todo_issue::default_pattern(),
+		todo_comment_markers: vec!["TODO".to_string(), "FIXME".to_string(), "HACK".to_string()],
+		pub_use_modules: vec!["prelude".to_string()],
+		commented_code_confidence: 0.5,
+		nesting_depth_max: 4,
+		fn_params_max: 7,
+		bool_params_in_pub: true,
+		mod_style_prefer: Layout::File,
+		no_blocking_in_async_paths: Vec::new(),
+		no_other_runtimes_crates: Vec::new(),
 	}
 }
 
@@ -248,6 +316,108 @@ fn all_skip_comment_variants_work() {
 	);
 }
 
+// === The real attribute ===
+
+#[test]
+fn attribute_skips_all_rules() {
+	assert_check_passing(
+		r#"
+		#[codestyle::skip]
+		fn skipped() {
+			let x: Option<i32> = None;
+			let y = x.unwrap_or(0);
+		}
+		"#,
+		&opts_for("ignored_error_comment"),
+	);
+}
+
+#[test]
+fn attribute_among_other_attributes() {
+	assert_check_passing(
+		r#"
+		#[derive(Debug)]
+		#[codestyle::skip(pub-first)]
+		struct Config {
+			private_field: i32,
+			pub public_field: i32,
+		}
+		"#,
+		&opts_for("pub_first"),
+	);
+}
+
+#[test]
+fn attribute_for_other_rule_does_not_skip() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		#[codestyle::skip(loop-comment)]
+		fn not_skipped() {
+			let x: Option<i32> = None;
+			let y = x.unwrap_or(0);
+		}
+		"#,
+		&opts_for("ignored_error_comment"),
+	), @"
+	[ignored-error-comment] /main.rs:4: `unwrap_or` without `//IGNORED_ERROR` comment
+	HINT: Error out properly or explain why it's part of the intended logic and simply erroring out / panicking is not an option.
+	");
+}
+
+// === Skip regions ===
+// Regions are applied by the runner, so these go through the `Linter` rather than the per-rule checks above.
+
+fn region_case(source: &str, opts: RustCheckOptions) -> String {
+	let report = Linter::new(opts).check_source("main.rs", source).unwrap();
+	report.violations.iter().map(|v| format!("[{}] {}: {}", v.rule, v.line, v.message.lines().next().unwrap())).collect::<Vec<_>>().join("\n")
+}
+
+#[test]
+fn region_skips_only_its_lines() {
+	let source = "fn run() {\n\t// codestyle::skip-start(loop-comment)\n\tloop {}\n\tloop {}\n\t// codestyle::skip-end\n\tloop {}\n}\n";
+	insta::assert_snapshot!(region_case(source, opts_for("loops")), @"[loop-comment] 6: Endless loop without `//LOOP` comment");
+}
+
+#[test]
+fn region_for_other_rule_does_not_skip() {
+	let source = "// codestyle::skip-start(pub-first)\nfn run() {\n\tloop {}\n}\n// codestyle::skip-end\n";
+	insta::assert_snapshot!(region_case(source, opts_for("loops")), @"[loop-comment] 3: Endless loop without `//LOOP` comment");
+}
+
+#[test]
+fn region_without_rule_skips_everything() {
+	let source = "// codestyle::skip-start\nfn skipped() {\n\tlet y = None::<i32>.unwrap_or(0);\n}\nstruct Private;\npub struct Public;\n// codestyle::skip-end\n";
+	assert_eq!(region_case(source, all_opts()), "");
+}
+
+#[test]
+fn unmatched_region_markers_are_reported() {
+	let source = "// codestyle::skip-end\nfn run() {\n\t// codestyle::skip-start(loop-comment)\n\tloop {}\n}\n";
+	insta::assert_snapshot!(region_case(source, opts_for("loops")), @"
+	[loop-comment] 4: Endless loop without `//LOOP` comment
+	[skip-region] 1: `codestyle::skip-end` without a matching `codestyle::skip-start`
+	[skip-region] 3: `codestyle::skip-start` without a matching `codestyle::skip-end`
+	");
+}
+
+// === Expiring skips ===
+
+#[test]
+fn skip_applies_until_its_date() {
+	let source = "//@codestyle::skip(loop-comment, until = \"2999-01-01\")\nfn run() {\n\tloop {}\n}\n";
+	assert_eq!(region_case(source, opts_for("loops")), "");
+}
+
+#[test]
+fn expired_skip_stops_applying_and_is_reported() {
+	let source = "//@codestyle::skip(loop-comment, until = \"2025-09-01\")\nfn run() {\n\tloop {}\n}\n#[codestyle::skip(until = \"someday\")]\nfn idle() {}\n";
+	insta::assert_snapshot!(region_case(source, opts_for("loops")), @"
+	[loop-comment] 3: Endless loop without `//LOOP` comment
+	[expired-skip] 1: expired suppression: skip marker was only valid until 2025-09-01
+	[expired-skip] 5: skip marker's `until` isn't a YYYY-MM-DD date: `someday`
+	");
+}
+
 // === Verify violations still occur without skip ===
 
 #[test]
@@ -0,0 +1,59 @@
+use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
+
+fn opts() -> codestyle::rust_checks::RustCheckOptions {
+	opts_for("skip_reason")
+}
+
+#[test]
+fn markers_with_reasons_pass() {
+	assert_check_passing(
+		r#"
+		//@codestyle::skip(pub-first): re-exports read best next to their imports
+		struct Private;
+		// #[codestyle::skip]: vendored, kept byte-for-byte
+		fn vendored() {}
+		// codestyle::skip-start(loop-comment): event loops, each documented on its own
+		fn run() {}
+		// codestyle::skip-end
+		"#,
+		&opts(),
+	);
+}
+
+#[test]
+fn markers_without_reasons() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		//@codestyle::skip(pub-first)
+		struct Private;
+		fn outer() {
+			// #[codestyle::skip]:
+			let _ = 1;
+		}
+		// codestyle::skip-start
+		fn run() {}
+		// codestyle::skip-end
+		"#,
+		&opts(),
+	), @"
+	[skip-reason] /main.rs:1: skip marker without a reason
+	HINT: add `: <reason>` after it, saying why the code can't follow the rule
+	[skip-reason] /main.rs:4: skip marker without a reason
+	HINT: add `: <reason>` after it, saying why the code can't follow the rule
+	[skip-reason] /main.rs:7: skip marker without a reason
+	HINT: add `: <reason>` after it, saying why the code can't follow the rule
+	");
+}
+
+#[test]
+fn reason_does_not_change_what_is_skipped() {
+	assert_check_passing(
+		r#"
+		//@codestyle::skip(loop-comment): drives the whole process
+		fn run() {
+			loop {}
+		}
+		"#,
+		&opts_for("loops"),
+	);
+}
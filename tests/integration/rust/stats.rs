@@ -0,0 +1,123 @@
+use codestyle::rust_checks::stats::Stats;
+use v_fixtures::Fixture;
+
+use crate::utils::collect_rust_files;
+
+fn stats_for(fixture_str: &str) -> String {
+	let temp = Fixture::parse(fixture_str).write_to_tempdir();
+	let mut stats = Stats::new(&temp.root);
+	for info in &collect_rust_files(&temp.root) {
+		stats.add_file(info);
+	}
+	stats.to_string()
+}
+
+#[test]
+fn metrics() {
+	insta::assert_snapshot!(stats_for(
+		r#"
+		//- /Cargo.toml
+		[package]
+		name = "demo"
+
+		//- /src/main.rs
+		fn main() {
+			let x = unsafe { std::mem::zeroed::<u8>() };
+			loop {
+				break;
+			}
+		}
+
+		struct Foo;
+		impl Foo {
+			async fn fetch(&self) {}
+		}
+		impl Default for Foo {
+			fn default() -> Self {
+				Self
+			}
+		}
+
+		//- /src/lib.rs
+		pub trait Greet {
+			fn greet(&self) -> String {
+				unsafe { helper() }
+			}
+		}
+		impl Greet for Bar {}
+		"#,
+	), @"
+	Files: 2, lines: 21
+
+	Functions: 4 (1 async)
+	  length: min 1, median 3, p90 6, max 6
+	  1-10 lines: 4
+	  11-25 lines: 0
+	  26-50 lines: 0
+	  51-100 lines: 0
+	  101+ lines: 0
+
+	Unsafe blocks: 2
+
+	Impl blocks per type (2 types):
+	  Foo: 2
+	  Bar: 1
+
+	Violations per rule (per 1k lines, all rules enabled):
+	  safety-comment: 2 (95.2)
+	  derive-default: 1 (47.6)
+	  impl-folds: 1 (47.6)
+	  loop-comment: 1 (47.6)
+	  missing-docs: 1 (47.6)
+
+	Violations per crate:
+	  .: 6
+
+	Violations per directory (1 directories):
+	  src: 6
+	");
+}
+
+#[test]
+fn violations_per_crate_and_dir_in_json() {
+	let temp = Fixture::parse(
+		r#"
+		//- /Cargo.toml
+		[workspace]
+		members = ["crates/*"]
+
+		//- /crates/core/Cargo.toml
+		[package]
+		name = "core"
+
+		//- /crates/core/src/lib.rs
+		fn run() {
+			loop {}
+		}
+
+		//- /crates/core/src/sched/queue.rs
+		fn spin() {
+			loop {}
+		}
+
+		//- /scripts/tool.rs
+		fn tool() {
+			loop {}
+		}
+		"#,
+	)
+	.write_to_tempdir();
+	let mut stats = Stats::new(&temp.root);
+	for dir in ["crates/core", "scripts"] {
+		for info in &collect_rust_files(&temp.root.join(dir)) {
+			stats.add_file(info);
+		}
+	}
+	let json = stats.to_json();
+	assert_eq!(json["violations"]["per_rule"]["loop-comment"], 3);
+	assert_eq!(json["violations"]["per_crate"], serde_json::json!({ "crates/core": 2, "(no crate)": 1 }));
+	assert_eq!(
+		json["violations"]["per_dir"],
+		serde_json::json!({ "crates/core/src": 1, "crates/core/src/sched": 1, "scripts": 1 })
+	);
+}
@@ -114,3 +114,19 @@ fn tokio_test_with_prefix() {
 	fn async_thing() {}
 	");
 }
+
+#[test]
+fn non_ascii_before_fn_name() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		#[test] /* ü */ fn test_grüße() {}
+		"#,
+		&opts(),
+	), @"
+	# Assert mode
+	[test-fn-prefix] /main.rs:1: test function `test_grüße` has redundant `test_` prefix
+
+	# Format mode
+	#[test] /* ü */ fn grüße() {}
+	");
+}
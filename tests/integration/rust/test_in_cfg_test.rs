@@ -0,0 +1,86 @@
+use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
+
+fn opts() -> codestyle::rust_checks::RustCheckOptions {
+	opts_for("test_in_cfg_test")
+}
+
+// === Passing cases ===
+
+#[test]
+fn tests_in_cfg_test_pass() {
+	assert_check_passing(
+		r#"
+		//- /src/lib.rs
+		fn parse(s: &str) -> u8 {
+			s.parse().unwrap_or(0)
+		}
+
+		#[cfg(test)]
+		mod tests {
+			#[test]
+			fn parses() {}
+
+			mod nested {
+				#[tokio::test]
+				async fn fetches() {}
+			}
+		}
+
+		#[cfg(test)]
+		#[test]
+		fn standalone() {}
+		//- /src/tests.rs
+		#[test]
+		fn declared_behind_cfg_test() {}
+		//- /src/parser/fixtures.rs
+		#![cfg(test)]
+
+		#[test]
+		fn fixture_parses() {}
+		"#,
+		&opts(),
+	);
+}
+
+#[test]
+fn integration_tests_pass() {
+	assert_check_passing(
+		r#"
+		//- /tests/integration.rs
+		#[test]
+		fn end_to_end() {}
+		//- /crates/core/tests/cli.rs
+		#[test]
+		fn runs() {}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn tests_outside_cfg_test() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		//- /src/lib.rs
+		fn parse(s: &str) -> u8 {
+			s.parse().unwrap_or(0)
+		}
+
+		#[test]
+		fn parses() {}
+
+		mod helpers {
+			#[tokio::test]
+			async fn fetches() {}
+		}
+		"#,
+		&opts(),
+	), @"
+	[test-in-cfg-test] /src/lib.rs:6: test `parses` outside a `#[cfg(test)]` module
+	HINT: move it into the file's `mod tests`
+	[test-in-cfg-test] /src/lib.rs:10: test `fetches` outside a `#[cfg(test)]` module
+	HINT: move it into the file's `mod tests`
+	");
+}
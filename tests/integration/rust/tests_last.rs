@@ -0,0 +1,104 @@
+use crate::utils::{assert_check_passing, opts_for, test_case};
+
+fn opts() -> codestyle::rust_checks::RustCheckOptions {
+	opts_for("tests_last")
+}
+
+// === Passing cases ===
+
+#[test]
+fn tests_at_the_bottom_pass() {
+	assert_check_passing(
+		r#"
+		#[cfg(test)]
+		mod integration;
+
+		fn parse() {}
+
+		#[cfg(test)]
+		mod tests {
+			#[test]
+			fn parses() {}
+		}
+
+		#[cfg(all(test, feature = "slow"))]
+		mod slow_tests {}
+		"#,
+		&opts(),
+	);
+}
+
+#[test]
+fn test_declarations_after_the_tests_pass() {
+	assert_check_passing(
+		r#"
+		fn parse() {}
+
+		#[cfg(test)]
+		mod tests {
+			#[test]
+			fn parses() {}
+		}
+
+		#[cfg(test)]
+		mod integration;
+		"#,
+		&opts(),
+	);
+}
+
+#[test]
+fn non_test_modules_pass() {
+	assert_check_passing(
+		r#"
+		#[cfg(not(test))]
+		mod real {}
+
+		mod helpers {}
+
+		fn parse() {}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases ===
+
+#[test]
+fn tests_are_moved_to_the_bottom() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		fn parse() {}
+
+		/// Unit tests
+		#[cfg(test)]
+		mod tests {
+			#[test]
+			fn parses() {}
+		}
+
+		fn helper() {}
+
+		impl Parser {}
+		"#,
+		&opts(),
+	), @"
+	# Assert mode
+	[tests-last] /main.rs:5: `#[cfg(test)] mod tests` isn't the last item in the file
+	HINT: move it to the bottom
+
+	# Format mode
+	fn parse() {}
+
+	fn helper() {}
+
+	impl Parser {}
+
+	/// Unit tests
+	#[cfg(test)]
+	mod tests {
+		#[test]
+		fn parses() {}
+	}
+	");
+}
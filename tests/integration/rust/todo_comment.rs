@@ -0,0 +1,74 @@
+use codestyle::rust_checks::RustCheckOptions;
+
+use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
+
+fn opts() -> RustCheckOptions {
+	opts_for("todo_comment")
+}
+
+// === Passing cases ===
+
+#[test]
+fn referenced_and_non_marker_comments_pass() {
+	assert_check_passing(
+		r#"
+		// TODO(#88): retry on timeouts
+		fn send() {}
+
+		// FIXME: the parser chokes on BOMs,
+		// tracked in https://github.com/acme/parser/issues/3
+		fn parse() {}
+
+		// TODOs live in the tracker, not here
+		const NOTE: &str = "// TODO: in a string, not a comment";
+		"#,
+		&opts(),
+	);
+}
+
+#[test]
+fn custom_markers() {
+	let opts = RustCheckOptions {
+		todo_comment_markers: vec!["XXX".to_string()],
+		..opts()
+	};
+	assert_check_passing(
+		r#"
+		// TODO: retry on timeouts
+		fn send() {}
+		"#,
+		&opts,
+	);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn untracked_markers() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		// TODO: retry on timeouts
+		fn send() {}
+
+		/// Parses the config. HACK: skips the BOM by hand
+		fn parse() {}
+
+		/* FIXME drop once the API is stable */
+		fn legacy() {}
+
+		// TODO: handle redirects
+		// HACK(#12): follows them once
+		fn fetch() {}
+		"#,
+		&opts(),
+	), @r"
+	[todo-comment] /main.rs:1: `TODO` comment without an issue reference
+	HINT: reference the issue tracking it, so it matches `#\d+|https?://\S+`
+	[todo-comment] /main.rs:4: `HACK` comment without an issue reference
+	HINT: reference the issue tracking it, so it matches `#\d+|https?://\S+`
+	[todo-comment] /main.rs:7: `FIXME` comment without an issue reference
+	HINT: reference the issue tracking it, so it matches `#\d+|https?://\S+`
+	[todo-comment] /main.rs:10: `TODO` comment without an issue reference
+	HINT: reference the issue tracking it, so it matches `#\d+|https?://\S+`
+	");
+}
@@ -0,0 +1,65 @@
+use codestyle::rust_checks::RustCheckOptions;
+use regex::Regex;
+
+use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
+
+fn opts() -> RustCheckOptions {
+	opts_for("todo_issue")
+}
+
+// === Passing cases ===
+
+#[test]
+fn referenced_issues_pass() {
+	assert_check_passing(
+		r##"
+		pub fn export(&self) -> Result<()> {
+			todo!("#142: export to parquet")
+		}
+
+		pub fn import(&self) -> Result<()> {
+			unimplemented!("blocked on https://github.com/apache/arrow-rs/issues/1")
+		}
+		"##,
+		&opts(),
+	);
+}
+
+#[test]
+fn custom_pattern() {
+	let opts = RustCheckOptions {
+		todo_issue_pattern: Regex::new(r"PROJ-\d+").unwrap(),
+		..opts()
+	};
+	assert_check_passing(
+		r#"
+		pub fn export(&self) -> Result<()> {
+			todo!("PROJ-17: export to parquet")
+		}
+		"#,
+		&opts,
+	);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn untracked_placeholders() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		pub fn export(&self) -> Result<()> {
+			todo!()
+		}
+
+		pub fn import(&self) -> Result<()> {
+			unimplemented!("needs the arrow reader")
+		}
+		"#,
+		&opts(),
+	), @r"
+	[todo-issue] /main.rs:2: `todo!()` without a message
+	HINT: say what's left, with a reference to the issue tracking it
+	[todo-issue] /main.rs:6: `unimplemented!` message doesn't reference an issue: nothing in it matches `#\d+|https?://\S+`
+	HINT: reference the issue tracking what's left
+	");
+}
@@ -0,0 +1,108 @@
+use std::{path::Path, slice};
+
+use codestyle::rust_checks::{self, exclude::DiscoveryOptions};
+use v_fixtures::Fixture;
+
+use crate::utils::opts_for;
+
+/// `(file, line, message)` of each `unused-pub` violation in the crates of `fixture`.
+fn found(fixture: &str) -> Vec<(String, usize, String)> {
+	let temp = Fixture::parse(fixture).write_to_tempdir();
+	let violations = rust_checks::collect_violations(slice::from_ref(&temp.root), &opts_for("unused_pub"), &DiscoveryOptions::default(), None, false, false).unwrap();
+	violations
+		.into_iter()
+		.map(|v| {
+			let file = Path::new(&v.file).strip_prefix(&temp.root).unwrap().display().to_string();
+			(file, v.line, v.message.lines().next().unwrap().to_string())
+		})
+		.collect()
+}
+
+#[test]
+fn items_only_their_module_uses() {
+	let found = found(
+		r#"
+		//- /Cargo.toml
+		[package]
+		name = "app"
+		//- /src/main.rs
+		mod cache;
+		mod config;
+
+		fn main() {
+			let config: config::Config = config::load();
+			cache::Cache::new(config.size);
+		}
+		//- /src/cache.rs
+		pub struct Cache {
+			entries: Vec<u64>,
+		}
+
+		impl Cache {
+			pub fn new(size: usize) -> Self {
+				Self { entries: Vec::with_capacity(size) }
+			}
+		}
+
+		pub fn evict(cache: &mut Cache) {
+			cache.entries.clear();
+		}
+
+		pub const LIMIT: usize = 64;
+
+		//#[codestyle::skip(unused-pub)]
+		pub fn debug_dump() {}
+
+		#[unsafe(no_mangle)]
+		pub extern "C" fn cache_size() -> usize {
+			LIMIT
+		}
+		//- /src/config.rs
+		pub struct Config {
+			pub size: usize,
+		}
+
+		pub fn load() -> Config {
+			Config { size: inner::DEFAULT }
+		}
+
+		mod inner {
+			pub const DEFAULT: usize = 8;
+		}
+		"#,
+	);
+	assert_eq!(
+		found,
+		vec![
+			("src/cache.rs".to_string(), 11, "`pub` fn `evict` isn't used outside `crate::cache`".to_string()),
+			("src/cache.rs".to_string(), 15, "`pub` const `LIMIT` isn't used outside `crate::cache`".to_string()),
+		]
+	);
+}
+
+#[test]
+fn library_api_is_left_alone() {
+	let found = found(
+		r#"
+		//- /Cargo.toml
+		[package]
+		name = "lib"
+		//- /src/lib.rs
+		pub mod api;
+		mod internal;
+
+		pub use internal::shared::*;
+
+		pub fn root() {}
+		//- /src/api.rs
+		pub fn call() {}
+		//- /src/internal.rs
+		pub mod shared;
+
+		pub fn helper() {}
+		//- /src/internal/shared.rs
+		pub struct Handle;
+		"#,
+	);
+	assert_eq!(found, vec![("src/internal.rs".to_string(), 3, "`pub` fn `helper` isn't used outside `crate::internal`".to_string())]);
+}
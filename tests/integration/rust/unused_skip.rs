@@ -0,0 +1,54 @@
+use codestyle::{
+	Linter,
+	rust_checks::{RustCheckOptions, Severity},
+};
+
+use crate::utils::opts_for;
+
+/// `unused_skip` on top of the rule the markers in these tests are for
+fn opts(check: &str) -> RustCheckOptions {
+	RustCheckOptions {
+		unused_skip: Severity::Error,
+		..opts_for(check)
+	}
+}
+
+fn unused(source: &str, check: &str) -> Vec<(usize, String)> {
+	let report = Linter::new(opts(check)).check_source("main.rs", source).unwrap();
+	report.violations.into_iter().filter(|v| v.rule == "unused-skip").map(|v| (v.line, v.message)).collect()
+}
+
+#[test]
+fn marker_still_suppressing_is_used() {
+	let source = "//@codestyle::skip(loop-comment)\nfn run() {\n\tloop {}\n}\n";
+	assert!(unused(source, "loops").is_empty());
+}
+
+#[test]
+fn marker_on_passing_item_is_unused() {
+	let source = "//@codestyle::skip(loop-comment)\nfn run() {\n\twhile tick() {}\n}\n";
+	assert_eq!(unused(source, "loops"), [(1, "skip marker suppresses no violation of `loop-comment`".to_string())]);
+}
+
+#[test]
+fn skip_all_counts_any_rule() {
+	let used = "// #[codestyle::skip]\nfn run() {\n\tloop {}\n}\n";
+	assert!(unused(used, "loops").is_empty());
+	let stale = "// #[codestyle::skip]: was a loop once\nfn run() {}\n";
+	assert_eq!(unused(stale, "loops"), [(1, "skip marker suppresses no violation of any rule".to_string())]);
+}
+
+#[test]
+fn marker_for_disabled_rule_is_unused() {
+	// Nothing of `pub-first` is checked, so there's nothing for it to suppress
+	let source = "//@codestyle::skip(pub-first)\nfn run() {\n\tloop {}\n}\n";
+	assert_eq!(unused(source, "loops").len(), 1);
+}
+
+#[test]
+fn format_removes_unused_markers() {
+	let source = "fn main() {}\n\n\t//@codestyle::skip(loop-comment)\nfn run() {}\n//@codestyle::skip(loop-comment)\nfn spin() {\n\tloop {}\n}\n";
+	let fixed = Linter::new(opts("loops")).apply_fixes("main.rs", source).unwrap();
+	assert_eq!(fixed.source, "fn main() {}\n\nfn run() {}\n//@codestyle::skip(loop-comment)\nfn spin() {\n\tloop {}\n}\n");
+	assert_eq!(fixed.fixed_count, 1);
+}
@@ -159,7 +159,7 @@ fn return_err_with_format_args() {
 	use eyre::bail;
 
 	fn test(x: i32) -> eyre::Result<()> {
-		bail!("invalid value: {}" , x);
+		bail!("invalid value: {}", x);
 	}
 	"#);
 }
@@ -0,0 +1,92 @@
+use codestyle::rust_checks::RustCheckOptions;
+
+use crate::utils::{assert_check_passing, opts_for, test_case};
+
+fn opts() -> RustCheckOptions {
+	opts_for("use_matches")
+}
+
+// === Passing cases ===
+
+#[test]
+fn matches_that_do_more_than_test_pass() {
+	assert_check_passing(
+		r#"
+		fn main() {
+			let big = if let Some(n) = size { n > 10 } else { false };
+			let class = match c {
+				'0'..='9' => true,
+				'a'..='z' => false,
+				_ => true,
+			};
+			let named = match c {
+				'a' => true,
+				other => false,
+			};
+			let guarded = match c {
+				'a' if strict => true,
+				'b' => true,
+				_ => false,
+			};
+			let plain = if ready { true } else { false };
+			let chained = if ready { false } else if let Some(_) = job { true } else { false };
+			let always = match c {
+				_ => true,
+			};
+		}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases ===
+
+#[test]
+fn bool_matches() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		fn main() {
+			let is_digit = match c {
+				'0'..='9' => true,
+				_ => false,
+			};
+			let is_ws = match c {
+				' ' => true,
+				'\t' | '\n' => true,
+				_ => false,
+			};
+			let is_other = match kind {
+				Kind::File | Kind::Dir => false,
+				_ => true,
+			};
+			let has = if let Some(_) = opt { true } else { false };
+			let positive = match n {
+				x if x > 0 => true,
+				_ => false,
+			};
+		}
+		"#,
+		&opts(),
+	), @r#"
+	# Assert mode
+	[use-matches] /main.rs:2: `match` that only tests for `'0'..='9'`
+	HINT: use `matches!(.., '0'..='9')`
+	[use-matches] /main.rs:6: `match` that only tests for `' ' | '\t' | '\n'`
+	HINT: use `matches!(.., ' ' | '\t' | '\n')`
+	[use-matches] /main.rs:11: `match` that only tests for `Kind::File | Kind::Dir`
+	HINT: use `!matches!(.., Kind::File | Kind::Dir)`
+	[use-matches] /main.rs:15: `if let` that only tests for `Some(_)`
+	HINT: use `matches!(.., Some(_))`
+	[use-matches] /main.rs:16: `match` that only tests for `x if x > 0`
+	HINT: use `matches!(.., x if x > 0)`
+
+	# Format mode
+	fn main() {
+		let is_digit = matches!(c, '0'..='9');
+		let is_ws = matches!(c, ' ' | '\t' | '\n');
+		let is_other = !matches!(kind, Kind::File | Kind::Dir);
+		let has = matches!(opt, Some(_));
+		let positive = matches!(n, x if x > 0);
+	}
+	"#);
+}
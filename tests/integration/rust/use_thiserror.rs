@@ -0,0 +1,78 @@
+use codestyle::rust_checks::RustCheckOptions;
+
+use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
+
+fn opts() -> RustCheckOptions {
+	opts_for("use_thiserror")
+}
+
+// === Passing cases ===
+
+#[test]
+fn derived_private_and_other_traits_pass() {
+	assert_check_passing(
+		r#"
+		use std::fmt;
+
+		#[derive(Debug, thiserror::Error)]
+		pub enum ParseError {
+			#[error("empty input")]
+			Empty,
+		}
+
+		enum CacheError {
+			Stale,
+		}
+
+		impl fmt::Display for CacheError {
+			fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+				f.write_str("stale")
+			}
+		}
+
+		pub enum ConfigError {
+			Custom(String),
+		}
+
+		impl serde::de::Error for ConfigError {
+			fn custom<T: fmt::Display>(msg: T) -> Self {
+				Self::Custom(msg.to_string())
+			}
+		}
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn hand_written_impls_for_public_error_enums() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		use std::{error::Error, fmt};
+
+		pub enum FetchError {
+			Timeout,
+			Status(u16),
+		}
+
+		impl fmt::Display for FetchError {
+			fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+				match self {
+					Self::Timeout => f.write_str("timed out"),
+					Self::Status(code) => write!(f, "status {code}"),
+				}
+			}
+		}
+
+		impl Error for FetchError {}
+		"#,
+		&opts(),
+	), @r#"
+	[use-thiserror] /main.rs:8: hand-written `Display` for the public error enum `FetchError`
+	HINT: `#[derive(thiserror::Error)]` on it, with an `#[error("..")]` on each variant
+	[use-thiserror] /main.rs:17: hand-written `Error` for the public error enum `FetchError`
+	HINT: `#[derive(thiserror::Error)]` on it, with an `#[error("..")]` on each variant
+	"#);
+}
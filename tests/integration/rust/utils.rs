@@ -1,26 +1,95 @@
 //! Test utilities for codestyle integration tests.
 
-use std::path::Path;
+use std::{path::Path, slice};
 
-use codestyle::rust_checks::{self, RustCheckOptions, Violation};
+use codestyle::rust_checks::{
+	self, FileInfo, FormatOptions, RustCheckOptions, Severity, Violation,
+	exclude::{DiscoveryOptions, Exclude},
+	mod_style::Layout,
+	rustfmt::RustfmtConfig,
+	todo_issue,
+};
 use v_fixtures::Fixture;
 
 pub(crate) fn opts_for(check: &str) -> RustCheckOptions {
 	RustCheckOptions {
-		cargo_dep_ordering: check == "cargo_dep_ordering",
-		instrument: check == "instrument",
-		join_split_impls: check == "join_split_impls",
-		impl_folds: check == "impl_folds",
-		impl_follows_type: check == "impl_follows_type",
-		loops: check == "loops",
-		embed_simple_vars: check == "embed_simple_vars",
-		insta_inline_snapshot: check == "insta_inline_snapshot",
-		no_chrono: check == "no_chrono",
-		no_tokio_spawn: check == "no_tokio_spawn",
-		use_bail: check == "use_bail",
-		test_fn_prefix: check == "test_fn_prefix",
-		pub_first: check == "pub_first",
-		ignored_error_comment: check == "ignored_error_comment",
+		cargo_dep_ordering: (check == "cargo_dep_ordering").into(),
+		instrument: (check == "instrument").into(),
+		join_split_impls: (check == "join_split_impls").into(),
+		impl_folds: (check == "impl_folds").into(),
+		impl_follows_type: (check == "impl_follows_type").into(),
+		trait_impls_follow: (check == "trait_impls_follow").into(),
+		loops: (check == "loops").into(),
+		safety_comment: (check == "safety_comment").into(),
+		embed_simple_vars: (check == "embed_simple_vars").into(),
+		insta_inline_snapshot: (check == "insta_inline_snapshot").into(),
+		no_chrono: (check == "no_chrono").into(),
+		no_tokio_spawn: (check == "no_tokio_spawn").into(),
+		no_dbg: (check == "no_dbg").into(),
+		use_bail: (check == "use_bail").into(),
+		test_fn_prefix: (check == "test_fn_prefix").into(),
+		test_in_cfg_test: (check == "test_in_cfg_test").into(),
+		pub_first: (check == "pub_first").into(),
+		tests_last: (check == "tests_last").into(),
+		ignored_error_comment: (check == "ignored_error_comment").into(),
+		no_unwrap: (check == "no_unwrap").into(),
+		expect_message: (check == "expect_message").into(),
+		no_panic: (check == "no_panic").into(),
+		allow_reason: (check == "allow_reason").into(),
+		missing_docs: (check == "missing_docs").into(),
+		derive_order: (check == "derive_order").into(),
+		nesting_depth: (check == "nesting_depth").into(),
+		fn_params: (check == "fn_params").into(),
+		bool_params: (check == "bool_params").into(),
+		no_as_cast: (check == "no_as_cast").into(),
+		let_else: (check == "let_else").into(),
+		use_matches: (check == "use_matches").into(),
+		mod_style: (check == "mod_style").into(),
+		no_lock_across_await: (check == "no_lock_across_await").into(),
+		no_blocking_in_async: (check == "no_blocking_in_async").into(),
+		no_block_on_in_async: (check == "no_block_on_in_async").into(),
+		no_async_trait: (check == "no_async_trait").into(),
+		no_lazy_static: (check == "no_lazy_static").into(),
+		no_once_cell: (check == "no_once_cell").into(),
+		no_other_runtimes: (check == "no_other_runtimes").into(),
+		reqwest_timeout: (check == "reqwest_timeout").into(),
+		use_thiserror: (check == "use_thiserror").into(),
+		derive_debug: (check == "derive_debug").into(),
+		derive_default: (check == "derive_default").into(),
+		pub_fields_first: (check == "pub_fields_first").into(),
+		mod_files: (check == "mod_files").into(),
+		impl_method_order: (check == "impl_method_order").into(),
+		no_get_prefix: (check == "no_get_prefix").into(),
+		should_panic_expected: (check == "should_panic_expected").into(),
+		no_assert: (check == "no_assert").into(),
+		todo_issue: (check == "todo_issue").into(),
+		todo_comment: (check == "todo_comment").into(),
+		commented_code: (check == "commented_code").into(),
+		no_extern_crate: (check == "no_extern_crate").into(),
+		no_macro_use: (check == "no_macro_use").into(),
+		pub_use_location: (check == "pub_use_location").into(),
+		unused_pub: (check == "unused_pub").into(),
+		dead_files: (check == "dead_files").into(),
+		skip_reason: (check == "skip_reason").into(),
+		unused_skip: (check == "unused_skip").into(),
+		plugins: Vec::new(),
+		custom_rules: Vec::new(),
+		overrides: Default::default(),
+		test_exempt: Vec::new(),
+		unwrap_marker: "SAFE".to_string(),
+		expect_message_min_len: 8,
+		no_panic_allow: Vec::new(),
+		no_assert_marker: String::new(),
+		todo_issue_pattern: todo_issue::default_pattern(),
+		todo_comment_markers: vec!["TODO".to_string(), "FIXME".to_string(), "HACK".to_string()],
+		pub_use_modules: vec!["prelude".to_string()],
+		commented_code_confidence: 0.5,
+		nesting_depth_max: 4,
+		fn_params_max: 7,
+		bool_params_in_pub: true,
+		mod_style_prefer: Layout::File,
+		no_blocking_in_async_paths: Vec::new(),
+		no_other_runtimes_crates: Vec::new(),
 	}
 }
 
@@ -78,7 +147,7 @@ pub(crate) fn test_case(fixture_str: &str, opts: &RustCheckOptions) -> String {
 
 	// Format mode: apply fixes
 	let temp_format = fixture.write_to_tempdir();
-	rust_checks::run_format(&temp_format.root, opts);
+	rust_checks::run_format(slice::from_ref(&temp_format.root), opts, &FormatOptions::default());
 	let result = temp_format.read_all_from_disk();
 	let format_out = result.render();
 
@@ -126,56 +195,225 @@ pub(crate) fn test_case_assert_only(fixture_str: &str, opts: &RustCheckOptions)
 		.join("\n")
 }
 
+/// Test primitive for `rust organize`.
+/// Returns the organized fixture, after verifying the result satisfies the structural rules
+/// and that organizing it again changes nothing.
+#[track_caller]
+pub(crate) fn organize_case(fixture_str: &str) -> String {
+	let fixture = Fixture::parse(fixture_str);
+	let temp = fixture.write_to_tempdir();
+	rust_checks::organize::run_organize(&temp.root, &DiscoveryOptions::default());
+	let organized = temp.read_all_from_disk().render();
+
+	let structural_opts = RustCheckOptions {
+		join_split_impls: Severity::Error,
+		impl_follows_type: Severity::Error,
+		pub_first: Severity::Error,
+		..opts_for("")
+	};
+	let violations = collect_violations(&temp.root, &structural_opts, false);
+	assert!(
+		violations.is_empty(),
+		"organized output still has violations:\n{}\n\nOrganized output:\n{organized}",
+		violations.iter().map(|v| format!("[{}] {}: {}", v.rule, v.line, v.message)).collect::<Vec<_>>().join("\n")
+	);
+
+	rust_checks::organize::run_organize(&temp.root, &DiscoveryOptions::default());
+	assert_eq!(temp.read_all_from_disk().render(), organized, "organize is not idempotent");
+
+	organized
+}
+
+/// Every parsed `.rs` file under `root`, with only the default excludes.
+pub(crate) fn collect_rust_files(root: &Path) -> Vec<FileInfo> {
+	let exclude = Exclude::new(root, &DiscoveryOptions::default()).expect("default patterns are valid");
+	rust_checks::collect_rust_files(root, &exclude)
+}
+
 fn collect_violations(root: &Path, opts: &RustCheckOptions, is_format_mode: bool) -> Vec<Violation> {
 	use codestyle::rust_checks::{
-		embed_simple_vars, ignored_error_comment, impl_folds, impl_follows_type, insta_snapshots, instrument, join_split_impls, loops, no_chrono, no_tokio_spawn, pub_first, test_fn_prefix,
-		use_bail,
+		allow_reason, bool_params, commented_code, derive_debug, derive_default, derive_order, embed_simple_vars, expect_message, fn_params, ignored_error_comment, impl_folds,
+		impl_follows_type, impl_method_order, insta_snapshots, instrument, join_split_impls, let_else, loops, missing_docs, mod_files, mod_style, nesting_depth, no_as_cast, no_assert,
+		no_async_trait, no_block_on_in_async, no_blocking_in_async, no_chrono, no_dbg, no_extern_crate, no_get_prefix, no_lazy_static, no_lock_across_await, no_macro_use, no_once_cell,
+		no_other_runtimes, no_panic, no_tokio_spawn, no_unwrap, pub_fields_first, pub_first, pub_use_location, reqwest_timeout, safety_comment, should_panic_expected, skip_reason,
+		test_context::TestContext, test_fn_prefix, test_in_cfg_test, tests_last, todo_comment, trait_impls_follow, use_bail, use_matches, use_thiserror,
 	};
 
-	let file_infos = rust_checks::collect_rust_files(root);
+	let file_infos = collect_rust_files(root);
+	let rustfmt = RustfmtConfig::load(root);
 	let mut violations = Vec::new();
 
 	for info in &file_infos {
-		if opts.instrument {
+		if opts.instrument.is_enabled() {
 			violations.extend(instrument::check_instrument(info));
 		}
-		if opts.loops {
+		if opts.loops.is_enabled() {
 			violations.extend(loops::check_loops(info));
 		}
+		if opts.skip_reason.is_enabled() {
+			violations.extend(skip_reason::check(&info.path, &info.contents));
+		}
 		if let Some(ref tree) = info.syntax_tree {
-			if opts.join_split_impls {
-				violations.extend(join_split_impls::check(&info.path, &info.contents, tree));
+			if opts.safety_comment.is_enabled() {
+				violations.extend(safety_comment::check(&info.path, &info.contents, tree));
+			}
+			if opts.join_split_impls.is_enabled() {
+				violations.extend(join_split_impls::check(&info.path, &info.contents, tree, &rustfmt));
 			}
-			if opts.impl_folds {
-				violations.extend(impl_folds::check(&info.path, &info.contents, tree));
+			if opts.impl_folds.is_enabled() {
+				violations.extend(impl_folds::check(&info.path, &info.contents, tree, &rustfmt));
 			}
-			if opts.impl_follows_type {
+			if opts.impl_follows_type.is_enabled() {
 				violations.extend(impl_follows_type::check(&info.path, &info.contents, tree));
 			}
-			if opts.embed_simple_vars {
+			if opts.trait_impls_follow.is_enabled() {
+				violations.extend(trait_impls_follow::check(&info.path, &info.contents, tree));
+			}
+			if opts.embed_simple_vars.is_enabled() {
 				violations.extend(embed_simple_vars::check(&info.path, &info.contents, tree));
 			}
-			if opts.insta_inline_snapshot {
+			if opts.insta_inline_snapshot.is_enabled() {
 				violations.extend(insta_snapshots::check(&info.path, &info.contents, tree, is_format_mode));
 			}
-			if opts.no_chrono {
+			if opts.no_chrono.is_enabled() {
 				violations.extend(no_chrono::check(&info.path, &info.contents, tree));
 			}
-			if opts.no_tokio_spawn {
+			if opts.no_tokio_spawn.is_enabled() {
 				violations.extend(no_tokio_spawn::check(&info.path, &info.contents, tree));
 			}
-			if opts.use_bail {
+			if opts.no_dbg.is_enabled() {
+				violations.extend(no_dbg::check(&info.path, &info.contents, tree));
+			}
+			if opts.use_bail.is_enabled() {
 				violations.extend(use_bail::check(&info.path, &info.contents, tree));
 			}
-			if opts.test_fn_prefix {
+			if opts.test_fn_prefix.is_enabled() {
 				violations.extend(test_fn_prefix::check(&info.path, &info.contents, tree));
 			}
-			if opts.pub_first {
-				violations.extend(pub_first::check(&info.path, &info.contents, tree));
+			if opts.test_in_cfg_test.is_enabled() {
+				violations.extend(test_in_cfg_test::check(&info.path, &info.contents, tree));
+			}
+			if opts.pub_first.is_enabled() {
+				violations.extend(pub_first::check(&info.path, &info.contents, tree, &rustfmt));
 			}
-			if opts.ignored_error_comment {
+			if opts.tests_last.is_enabled() {
+				violations.extend(tests_last::check(&info.path, &info.contents, tree));
+			}
+			if opts.ignored_error_comment.is_enabled() {
 				violations.extend(ignored_error_comment::check(&info.path, &info.contents, tree));
 			}
+			if opts.no_unwrap.is_enabled() {
+				let tests = TestContext::new(&info.path, tree);
+				violations.extend(no_unwrap::check(&info.path, &info.contents, tree, &tests, &opts.unwrap_marker));
+			}
+			if opts.expect_message.is_enabled() {
+				violations.extend(expect_message::check(&info.path, &info.contents, tree, opts.expect_message_min_len));
+			}
+			if opts.no_panic.is_enabled() && no_panic::is_library_file(&info.path) {
+				let tests = TestContext::new(&info.path, tree);
+				violations.extend(no_panic::check(&info.path, &info.contents, tree, &tests, &opts.no_panic_allow));
+			}
+			if opts.allow_reason.is_enabled() {
+				violations.extend(allow_reason::check(&info.path, &info.contents, tree));
+			}
+			if opts.missing_docs.is_enabled() && no_panic::is_library_file(&info.path) {
+				let tests = TestContext::new(&info.path, tree);
+				violations.extend(missing_docs::check(&info.path, &info.contents, tree, &tests));
+			}
+			if opts.derive_order.is_enabled() {
+				violations.extend(derive_order::check(&info.path, &info.contents, tree));
+			}
+			if opts.nesting_depth.is_enabled() {
+				violations.extend(nesting_depth::check(&info.path, &info.contents, tree, opts.nesting_depth_max));
+			}
+			if opts.fn_params.is_enabled() {
+				violations.extend(fn_params::check(&info.path, &info.contents, tree, opts.fn_params_max));
+			}
+			if opts.bool_params.is_enabled() {
+				violations.extend(bool_params::check(&info.path, &info.contents, tree, opts.bool_params_in_pub));
+			}
+			if opts.no_as_cast.is_enabled() {
+				violations.extend(no_as_cast::check(&info.path, &info.contents, tree));
+			}
+			if opts.let_else.is_enabled() {
+				violations.extend(let_else::check(&info.path, &info.contents, tree));
+			}
+			if opts.use_matches.is_enabled() {
+				violations.extend(use_matches::check(&info.path, &info.contents, tree));
+			}
+			if opts.mod_style.is_enabled() {
+				violations.extend(mod_style::check(&info.path, &info.contents, opts.mod_style_prefer));
+			}
+			if opts.no_lock_across_await.is_enabled() {
+				violations.extend(no_lock_across_await::check(&info.path, &info.contents, tree));
+			}
+			if opts.no_blocking_in_async.is_enabled() {
+				violations.extend(no_blocking_in_async::check(&info.path, &info.contents, tree, &opts.no_blocking_in_async_paths));
+			}
+			if opts.no_block_on_in_async.is_enabled() {
+				violations.extend(no_block_on_in_async::check(&info.path, &info.contents, tree));
+			}
+			if opts.no_async_trait.is_enabled() {
+				violations.extend(no_async_trait::check(&info.path, &info.contents, tree));
+			}
+			if opts.no_lazy_static.is_enabled() {
+				violations.extend(no_lazy_static::check(&info.path, &info.contents, tree));
+			}
+			if opts.no_once_cell.is_enabled() {
+				violations.extend(no_once_cell::check(&info.path, &info.contents, tree));
+			}
+			if opts.no_other_runtimes.is_enabled() {
+				violations.extend(no_other_runtimes::check(&info.path, &info.contents, tree, &opts.no_other_runtimes_crates));
+			}
+			if opts.reqwest_timeout.is_enabled() {
+				violations.extend(reqwest_timeout::check(&info.path, &info.contents, tree));
+			}
+			if opts.use_thiserror.is_enabled() {
+				violations.extend(use_thiserror::check(&info.path, &info.contents, tree));
+			}
+			if opts.derive_debug.is_enabled() {
+				violations.extend(derive_debug::check(&info.path, &info.contents, tree));
+			}
+			if opts.derive_default.is_enabled() {
+				violations.extend(derive_default::check(&info.path, &info.contents, tree));
+			}
+			if opts.pub_fields_first.is_enabled() {
+				violations.extend(pub_fields_first::check(&info.path, &info.contents, tree));
+			}
+			if opts.mod_files.is_enabled() {
+				violations.extend(mod_files::check(&info.path, &info.contents, tree));
+			}
+			if opts.impl_method_order.is_enabled() {
+				violations.extend(impl_method_order::check(&info.path, &info.contents, tree));
+			}
+			if opts.no_get_prefix.is_enabled() {
+				violations.extend(no_get_prefix::check(&info.path, &info.contents, tree));
+			}
+			if opts.should_panic_expected.is_enabled() {
+				violations.extend(should_panic_expected::check(&info.path, &info.contents, tree));
+			}
+			if opts.no_assert.is_enabled() {
+				let tests = TestContext::new(&info.path, tree);
+				violations.extend(no_assert::check(&info.path, &info.contents, tree, &tests, &opts.no_assert_marker));
+			}
+			if opts.todo_issue.is_enabled() {
+				violations.extend(todo_issue::check(&info.path, &info.contents, tree, &opts.todo_issue_pattern));
+			}
+			if opts.todo_comment.is_enabled() {
+				violations.extend(todo_comment::check(&info.path, &info.contents, &opts.todo_comment_markers, &opts.todo_issue_pattern));
+			}
+			if opts.commented_code.is_enabled() {
+				violations.extend(commented_code::check(&info.path, &info.contents, opts.commented_code_confidence));
+			}
+			if opts.no_extern_crate.is_enabled() {
+				violations.extend(no_extern_crate::check(&info.path, &info.contents, tree));
+			}
+			if opts.no_macro_use.is_enabled() {
+				violations.extend(no_macro_use::check(&info.path, &info.contents, tree));
+			}
+			if opts.pub_use_location.is_enabled() {
+				violations.extend(pub_use_location::check(&info.path, &info.contents, tree, &opts.pub_use_modules));
+			}
 		}
 	}
 
@@ -0,0 +1,127 @@
+use std::path::PathBuf;
+
+use codestyle::rust_checks::workspace;
+use v_fixtures::Fixture;
+
+fn packages(fixture: &str) -> Vec<String> {
+	let temp = Fixture::parse(fixture).write_to_tempdir();
+	let relative = |p: PathBuf| p.strip_prefix(&temp.root).unwrap().display().to_string();
+	workspace::packages(&temp.root).into_iter().map(relative).collect()
+}
+
+#[test]
+fn multiline_members_and_root_package() {
+	let found = packages(
+		r#"
+		//- /Cargo.toml
+		[package]
+		name = "app"
+
+		[workspace]
+		members = [
+			"crates/core",
+			"crates/cli",
+		]
+		//- /crates/core/Cargo.toml
+		[package]
+		name = "core"
+		//- /crates/cli/Cargo.toml
+		[package]
+		name = "cli"
+		"#,
+	);
+	assert_eq!(found, ["", "crates/cli", "crates/core"]);
+}
+
+#[test]
+fn path_dependencies_inside_the_workspace_are_members() {
+	let found = packages(
+		r#"
+		//- /Cargo.toml
+		[workspace]
+		members = ["app"]
+		//- /app/Cargo.toml
+		[package]
+		name = "app"
+
+		[dependencies]
+		helper = { path = "../helper" }
+		outside = { path = "../../outside" }
+
+		[target.'cfg(unix)'.dev-dependencies]
+		unix_only = { path = "../unix_only" }
+		//- /helper/Cargo.toml
+		[package]
+		name = "helper"
+		//- /unix_only/Cargo.toml
+		[package]
+		name = "unix_only"
+		"#,
+	);
+	assert_eq!(found, ["app", "helper", "unix_only"]);
+}
+
+#[test]
+fn glob_members_are_expanded() {
+	let found = packages(
+		r#"
+		//- /Cargo.toml
+		[workspace]
+		members = ["crates/*", "tools/gen_?"]
+		//- /crates/core/Cargo.toml
+		[package]
+		name = "core"
+		//- /crates/cli/Cargo.toml
+		[package]
+		name = "cli"
+		//- /crates/README.md
+		not a crate
+		//- /crates/assets/logo.txt
+		not a crate either
+		//- /tools/gen_a/Cargo.toml
+		[package]
+		name = "gen_a"
+		"#,
+	);
+	assert_eq!(found, ["crates/cli", "crates/core", "tools/gen_a"]);
+}
+
+#[test]
+fn standalone_package_has_no_members() {
+	let found = packages(
+		r#"
+		//- /Cargo.toml
+		[package]
+		name = "app"
+
+		[dependencies]
+		helper = { path = "helper" }
+		"#,
+	);
+	assert!(found.is_empty());
+}
+
+#[test]
+fn workspace_exclude_wins_over_members_and_path_dependencies() {
+	let found = packages(
+		r#"
+		//- /Cargo.toml
+		[workspace]
+		members = ["crates/*"]
+		exclude = ["crates/experimental", "./vendor"]
+		//- /crates/core/Cargo.toml
+		[package]
+		name = "core"
+
+		[dependencies]
+		patched = { path = "../../vendor/patched" }
+		//- /crates/experimental/Cargo.toml
+		[package]
+		name = "experimental"
+		//- /vendor/patched/Cargo.toml
+		[package]
+		name = "patched"
+		"#,
+	);
+	assert_eq!(found, ["crates/core"]);
+}
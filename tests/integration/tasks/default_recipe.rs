@@ -0,0 +1,60 @@
+use codestyle::task_checks::{TaskCheckOptions, TaskFileKind};
+
+use crate::utils::{apply_fixes, check, opts_for, render};
+
+fn opts() -> TaskCheckOptions {
+	opts_for("default_recipe")
+}
+
+// === Passing cases ===
+
+#[test]
+fn justfile_with_default_passes() {
+	let content = r#"set shell := ["bash", "-c"]
+
+build:
+	cargo build
+
+default:
+	@just --list
+"#;
+	assert!(check(content, TaskFileKind::Just, &opts()).is_empty());
+}
+
+#[test]
+fn makefile_with_help_passes() {
+	let content = ".PHONY: help build\nhelp:\n\t@grep -E '^[a-z]+:' Makefile\nbuild:\n\tcargo build\n";
+	assert!(check(content, TaskFileKind::Make, &opts()).is_empty());
+}
+
+// === Violation cases ===
+
+#[test]
+fn makefile_without_default() {
+	let content = "CARGO := cargo\n\nbuild:\n\t$(CARGO) build\n";
+	insta::assert_snapshot!(render(&check(content, TaskFileKind::Make, &opts())), @"[default-recipe] Makefile:3: no `default` or `help` recipe; running `make` bare executes `build`");
+}
+
+#[test]
+fn justfile_fix_inserts_listing_recipe() {
+	let content = r#"set shell := ["bash", "-c"]
+alias b := build
+
+# Build everything
+[no-cd]
+build target='debug':
+	cargo build --profile {{target}}
+"#;
+	insta::assert_snapshot!(apply_fixes(content, check(content, TaskFileKind::Just, &opts())), @r#"
+	set shell := ["bash", "-c"]
+	alias b := build
+
+	default:
+		@just --list
+
+	# Build everything
+	[no-cd]
+	build target='debug':
+		cargo build --profile {{target}}
+	"#);
+}
@@ -0,0 +1,49 @@
+use codestyle::task_checks::{TaskCheckOptions, TaskFileKind};
+
+use crate::utils::{check, opts_for, render};
+
+fn opts() -> TaskCheckOptions {
+	opts_for("duplicate_recipe_body")
+}
+
+// === Passing cases ===
+
+#[test]
+fn distinct_bodies_pass() {
+	let content = r#"default:
+	@just --list
+
+build:
+	cargo build
+
+release:
+	cargo build --release
+
+fmt:
+lint:
+"#;
+	assert!(check(content, TaskFileKind::Just, &opts()).is_empty());
+}
+
+#[test]
+fn multi_target_rule_passes() {
+	let content = "build test:\n\tcargo $@\n";
+	assert!(check(content, TaskFileKind::Make, &opts()).is_empty());
+}
+
+// === Violation cases ===
+
+#[test]
+fn duplicated_bodies() {
+	let content = r#"test:
+	cargo nextest run
+	# slow ones too
+	cargo test --doc
+
+ci-test:
+    cargo nextest run
+
+    cargo test --doc
+"#;
+	insta::assert_snapshot!(render(&check(content, TaskFileKind::Just, &opts())), @"[duplicate-recipe-body] justfile:6: recipe `ci-test` has the same body as `test` (line 1)");
+}
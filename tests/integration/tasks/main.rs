@@ -0,0 +1,8 @@
+//! Task-runner file check integration tests.
+//!
+//! Task-runner checks operate on a single file's recipes, so tests call them directly.
+
+mod default_recipe;
+mod duplicate_recipe_body;
+mod phony_targets;
+mod utils;
@@ -0,0 +1,55 @@
+use codestyle::task_checks::{TaskCheckOptions, TaskFileKind};
+
+use crate::utils::{apply_fixes, check, opts_for, render};
+
+fn opts() -> TaskCheckOptions {
+	opts_for("phony_targets")
+}
+
+// === Passing cases ===
+
+#[test]
+fn declared_and_file_targets_pass() {
+	let content = r#".PHONY: build \
+	test
+.PHONY: clean
+build:
+	cargo build
+test: build
+	cargo test
+clean:
+	rm -rf target
+app: main.c
+	cc -o app main.c
+out/report.txt:
+	./report > $@
+%.o: %.c
+	cc -c $<
+"#;
+	assert!(check(content, TaskFileKind::Make, &opts()).is_empty());
+}
+
+// === Violation cases ===
+
+#[test]
+fn undeclared_targets() {
+	let content = "CFLAGS = -O2\nall: build\nbuild:\n\tcargo build\n";
+	insta::assert_snapshot!(render(&check(content, TaskFileKind::Make, &opts())), @"
+	[phony-targets] Makefile:2: target `all` not declared `.PHONY`
+	[phony-targets] Makefile:3: target `build` not declared `.PHONY`
+	");
+}
+
+#[test]
+fn fix_declares_phony() {
+	let content = ".PHONY: build\nbuild:\n\tcargo build\n\ntest lint: build\n\tcargo nextest run\n";
+	insta::assert_snapshot!(apply_fixes(content, check(content, TaskFileKind::Make, &opts())), @"
+	.PHONY: build
+	build:
+		cargo build
+
+	.PHONY: test lint
+	test lint: build
+		cargo nextest run
+	");
+}
@@ -0,0 +1,38 @@
+use std::path::Path;
+
+use codestyle::{
+	rust_checks::Violation,
+	task_checks::{TaskCheckOptions, TaskFileKind, check_file},
+};
+
+/// Options with only the named check enabled.
+pub(crate) fn opts_for(check: &str) -> TaskCheckOptions {
+	TaskCheckOptions {
+		default_recipe: check == "default_recipe",
+		duplicate_recipe_body: check == "duplicate_recipe_body",
+		phony_targets: check == "phony_targets",
+	}
+}
+
+pub(crate) fn check(content: &str, kind: TaskFileKind, opts: &TaskCheckOptions) -> Vec<Violation> {
+	let path = match kind {
+		TaskFileKind::Just => "justfile",
+		TaskFileKind::Make => "Makefile",
+	};
+	check_file(Path::new(path), content, kind, opts)
+}
+
+pub(crate) fn render(violations: &[Violation]) -> String {
+	violations.iter().map(|v| format!("[{}] {}:{}: {}", v.rule, v.file, v.line, v.message)).collect::<Vec<_>>().join("\n")
+}
+
+/// Apply all fixes, highest offset first.
+pub(crate) fn apply_fixes(content: &str, violations: Vec<Violation>) -> String {
+	let mut fixes: Vec<_> = violations.into_iter().filter_map(|v| v.fix).collect();
+	fixes.sort_by_key(|f| std::cmp::Reverse(f.start_byte));
+	let mut result = content.to_string();
+	for fix in fixes {
+		result.replace_range(fix.start_byte..fix.end_byte, &fix.replacement);
+	}
+	result
+}
@@ -0,0 +1,8 @@
+//! TypeScript/JavaScript check integration tests.
+//!
+//! Same layout and primitives as the Rust checks: one module per rule, see `utils`.
+
+mod no_any_cast;
+mod no_console_log;
+mod utils;
+mod void_promise;
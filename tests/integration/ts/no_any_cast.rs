@@ -0,0 +1,55 @@
+use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
+
+fn opts() -> codestyle::ts_checks::TsCheckOptions {
+	opts_for("no_any_cast")
+}
+
+// === Passing cases ===
+
+#[test]
+fn unknown_and_concrete_casts_pass() {
+	assert_check_passing(
+		r#"
+		//- /src/app.ts
+		const a = value as unknown;
+		const b = value as string;
+		const c = <number>other;
+		"#,
+		&opts(),
+	);
+}
+
+#[test]
+fn any_cast_with_comment_passes() {
+	assert_check_passing(
+		r#"
+		//- /src/app.ts
+		//ANY_CAST: the library's typings are wrong, fixed upstream in v3
+		const a = value as any;
+		const b = other as any; // ANY_CAST: same as above
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn any_casts() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		//- /src/app.ts
+		const a = value as any;
+		const b = <any>value;
+		const c = (value as unknown) as any;
+		"#,
+		&opts(),
+	), @"
+	[no-any-cast] /src/app.ts:1: cast to `any` without `//ANY_CAST` comment
+	HINT: use `unknown` and narrow it, or explain why the type system can't express this
+	[no-any-cast] /src/app.ts:2: cast to `any` without `//ANY_CAST` comment
+	HINT: use `unknown` and narrow it, or explain why the type system can't express this
+	[no-any-cast] /src/app.ts:3: cast to `any` without `//ANY_CAST` comment
+	HINT: use `unknown` and narrow it, or explain why the type system can't express this
+	");
+}
@@ -0,0 +1,72 @@
+use crate::utils::{assert_check_passing, opts_for, test_case, test_case_assert_only};
+
+fn opts() -> codestyle::ts_checks::TsCheckOptions {
+	opts_for("no_console_log")
+}
+
+// === Passing cases ===
+
+#[test]
+fn outside_src_passes() {
+	assert_check_passing(
+		r#"
+		//- /scripts/build.ts
+		console.log("building");
+		"#,
+		&opts(),
+	);
+}
+
+#[test]
+fn other_console_methods_pass() {
+	assert_check_passing(
+		r#"
+		//- /src/app.ts
+		console.error("failed");
+		console.warn("deprecated");
+		logger.log("fine");
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation + fix cases ===
+
+#[test]
+fn standalone_statements_removed() {
+	insta::assert_snapshot!(test_case(
+		r#"
+		//- /src/app.tsx
+		function render() {
+			console.log("rendering", props);
+			return <div />;
+		}
+		"#,
+		&opts(),
+	), @r#"
+	# Assert mode
+	[no-console-log] /src/app.tsx:2: `console.log` in src/
+	HINT: use a proper logger, or remove the debug print
+
+	# Format mode
+	function render() {
+		return <div />;
+	}
+	"#);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn nested_in_expression() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		//- /src/app.ts
+		const items = list.map((x) => console.log(x));
+		"#,
+		&opts(),
+	), @"
+	[no-console-log] /src/app.ts:1: `console.log` in src/
+	HINT: use a proper logger, or remove the debug print
+	");
+}
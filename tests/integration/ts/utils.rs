@@ -0,0 +1,82 @@
+//! Test utilities for TypeScript check integration tests.
+
+use std::path::Path;
+
+use codestyle::{
+	rust_checks::Violation,
+	ts_checks::{self, TsCheckOptions},
+};
+use v_fixtures::Fixture;
+
+pub(crate) fn opts_for(check: &str) -> TsCheckOptions {
+	TsCheckOptions {
+		no_any_cast: check == "no_any_cast",
+		no_console_log: check == "no_console_log",
+		void_promise: check == "void_promise",
+	}
+}
+
+/// Assert that a fixture passes all enabled checks (no violations).
+#[track_caller]
+pub(crate) fn assert_check_passing(fixture_str: &str, opts: &TsCheckOptions) {
+	let fixture = Fixture::parse(fixture_str);
+	let temp = fixture.write_to_tempdir();
+	let violations = collect_violations(&temp.root, opts);
+
+	if !violations.is_empty() {
+		panic!("expected no violations, but found {}:\n{}", violations.len(), render_violations(&temp.root, &violations));
+	}
+}
+
+/// Runs both assert mode and format mode, returning a combined snapshot.
+/// Also verifies that the formatted output passes the check.
+#[track_caller]
+pub(crate) fn test_case(fixture_str: &str, opts: &TsCheckOptions) -> String {
+	let fixture = Fixture::parse(fixture_str);
+
+	let temp_assert = fixture.write_to_tempdir();
+	let violations = collect_violations(&temp_assert.root, opts);
+	assert!(!violations.is_empty(), "test_case called but no violations found - use assert_check_passing instead");
+	let assert_out = render_violations(&temp_assert.root, &violations);
+
+	let temp_format = fixture.write_to_tempdir();
+	ts_checks::run_format(&temp_format.root, opts);
+	let format_out = temp_format.read_all_from_disk().render();
+
+	let format_violations = collect_violations(&temp_format.root, opts);
+	if !format_violations.is_empty() {
+		panic!(
+			"formatted output still has {} violation(s):\n{}\n\nFormatted output:\n{format_out}",
+			format_violations.len(),
+			render_violations(&temp_format.root, &format_violations)
+		);
+	}
+
+	format!("# Assert mode\n{assert_out}\n\n# Format mode\n{format_out}")
+}
+
+/// Runs assert mode only, for rules without autofix.
+#[track_caller]
+pub(crate) fn test_case_assert_only(fixture_str: &str, opts: &TsCheckOptions) -> String {
+	let fixture = Fixture::parse(fixture_str);
+	let temp = fixture.write_to_tempdir();
+	let violations = collect_violations(&temp.root, opts);
+	assert!(!violations.is_empty(), "test_case_assert_only called but no violations found - use assert_check_passing instead");
+	render_violations(&temp.root, &violations)
+}
+
+fn collect_violations(root: &Path, opts: &TsCheckOptions) -> Vec<Violation> {
+	ts_checks::collect_ts_files(root).iter().flat_map(|info| ts_checks::check_file(info, opts)).collect()
+}
+
+fn render_violations(root: &Path, violations: &[Violation]) -> String {
+	violations
+		.iter()
+		.map(|v| {
+			let relative_path = v.file.strip_prefix(root.to_str().unwrap_or("")).unwrap_or(&v.file);
+			let relative_path = relative_path.trim_start_matches('/');
+			format!("[{}] /{relative_path}:{}: {}", v.rule, v.line, v.message)
+		})
+		.collect::<Vec<_>>()
+		.join("\n")
+}
@@ -0,0 +1,57 @@
+use crate::utils::{assert_check_passing, opts_for, test_case_assert_only};
+
+fn opts() -> codestyle::ts_checks::TsCheckOptions {
+	opts_for("void_promise")
+}
+
+// === Passing cases ===
+
+#[test]
+fn awaited_and_void_zero_pass() {
+	assert_check_passing(
+		r#"
+		//- /src/app.ts
+		async function main() {
+			await fetchData();
+			const nothing = void 0;
+		}
+		"#,
+		&opts(),
+	);
+}
+
+#[test]
+fn justified_fire_and_forget_passes() {
+	assert_check_passing(
+		r#"
+		//- /src/app.ts
+		//FIRE_AND_FORGET: telemetry is best-effort and must not block the request
+		void sendTelemetry();
+		"#,
+		&opts(),
+	);
+}
+
+// === Violation cases (no autofix) ===
+
+#[test]
+fn void_promises() {
+	insta::assert_snapshot!(test_case_assert_only(
+		r#"
+		//- /src/app.ts
+		function handler() {
+			void fetchData();
+			void this.pending;
+			void promise;
+		}
+		"#,
+		&opts(),
+	), @"
+	[void-promise] /src/app.ts:2: fire-and-forget `void` promise without `//FIRE_AND_FORGET` comment
+	HINT: `await` it, or justify why the work may outlive its caller. See: https://vorpus.org/blog/notes-on-structured-concurrency-or-go-statement-considered-harmful/
+	[void-promise] /src/app.ts:3: fire-and-forget `void` promise without `//FIRE_AND_FORGET` comment
+	HINT: `await` it, or justify why the work may outlive its caller. See: https://vorpus.org/blog/notes-on-structured-concurrency-or-go-statement-considered-harmful/
+	[void-promise] /src/app.ts:4: fire-and-forget `void` promise without `//FIRE_AND_FORGET` comment
+	HINT: `await` it, or justify why the work may outlive its caller. See: https://vorpus.org/blog/notes-on-structured-concurrency-or-go-statement-considered-harmful/
+	");
+}